@@ -6,8 +6,9 @@ use bencher::Bencher;
 use rand::Rng;
 use ta::indicators::{
     BollingerBands, EfficiencyRatio, ExponentialMovingAverage, FastStochastic, Maximum, Minimum,
-    MoneyFlowIndex, MovingAverageConvergenceDivergence, OnBalanceVolume, RateOfChange,
+    Momentum, MoneyFlowIndex, MovingAverageConvergenceDivergence, OnBalanceVolume, RateOfChange,
     RelativeStrengthIndex, SimpleMovingAverage, SlowStochastic, StandardDeviation, TrueRange,
+    VortexIndicator,
 };
 use ta::DataItem;
 use ta::Next;
@@ -36,6 +37,7 @@ fn rand_data_item() -> DataItem {
 macro_rules! bench_indicators {
     ($($indicator:ident), *) => {
         $(
+            #[allow(non_snake_case)]
             fn $indicator(bench: &mut Bencher) {
                 let items: Vec<DataItem> = (0..ITEMS_COUNT).map( |_| rand_data_item() ).collect();
                 let mut indicator = $indicator::default();
@@ -56,17 +58,19 @@ macro_rules! bench_indicators {
 bench_indicators!(
     SimpleMovingAverage,
     ExponentialMovingAverage,
+    MovingAverageConvergenceDivergence,
     StandardDeviation,
     BollingerBands,
     EfficiencyRatio,
     FastStochastic,
     Maximum,
     Minimum,
-    MovingAverageConvergenceDivergence,
+    Momentum,
     RateOfChange,
     RelativeStrengthIndex,
     SlowStochastic,
     TrueRange,
     MoneyFlowIndex,
-    OnBalanceVolume
+    OnBalanceVolume,
+    VortexIndicator
 );
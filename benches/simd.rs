@@ -0,0 +1,56 @@
+#[macro_use]
+extern crate bencher;
+extern crate ta;
+
+use bencher::Bencher;
+use rand::Rng;
+use ta::indicators::{Maximum, RateOfChange, SimpleMovingAverage};
+use ta::{simd, Calculate};
+
+const ITEMS_COUNT: usize = 100_000;
+const LENGTH: u32 = 50;
+
+fn rand_prices() -> Vec<f64> {
+    let mut rng = rand::thread_rng();
+    (0..ITEMS_COUNT)
+        .map(|_| rng.gen_range(100.0, 200.0))
+        .collect()
+}
+
+macro_rules! bench_kernels {
+    ($($name:ident: $indicator:ident vs $kernel:path), *) => {
+        mod streaming {
+            use super::*;
+            $(
+                pub fn $name(bench: &mut Bencher) {
+                    let prices = rand_prices();
+                    let mut output = vec![0.0; prices.len()];
+                    bench.iter(|| {
+                        let mut indicator = $indicator::new(LENGTH).unwrap();
+                        indicator.calc_slice(&prices, &mut output);
+                    })
+                }
+            )*
+        }
+
+        mod vectorized {
+            use super::*;
+            $(
+                pub fn $name(bench: &mut Bencher) {
+                    let prices = rand_prices();
+                    let mut output = vec![0.0; prices.len()];
+                    bench.iter(|| $kernel(&prices, LENGTH, &mut output).unwrap())
+                }
+            )*
+        }
+
+        benchmark_group!(benches, $(streaming::$name, vectorized::$name,)*);
+        benchmark_main!(benches);
+    }
+}
+
+bench_kernels!(
+    sma: SimpleMovingAverage vs simd::sma,
+    maximum: Maximum vs simd::maximum,
+    roc: RateOfChange vs simd::roc
+);
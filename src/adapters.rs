@@ -0,0 +1,322 @@
+//! Bars in the shapes exchange APIs return them.
+//!
+//! Feeding indicators from an exchange usually starts with converting its payloads into a
+//! bar type. This module, enabled by the `adapters` feature, covers the common shapes:
+//!
+//! * `(timestamp, open, high, low, close, volume)` tuples of an `i64` millisecond
+//!   timestamp and `f64` values implement [Open](../trait.Open.html),
+//!   [High](../trait.High.html), [Low](../trait.Low.html), [Close](../trait.Close.html),
+//!   [Volume](../trait.Volume.html) and [Timestamp](../trait.Timestamp.html), so they can be
+//!   passed to indicators as they are.
+//! * [Kline](struct.Kline.html) deserializes the Binance-style kline arrays, which carry
+//!   the prices as strings, e.g. `[1499040000000, "0.016", "0.8", "0.015", "0.016",
+//!   "148976.1", 1499644799999, "2434.19", 308, "1756.87", "28.46", "0"]`.
+//! * `serde_json` values convert into a validated [DataItem](../struct.DataItem.html) with
+//!   `DataItem::try_from(&value)`: objects with `open`, `high`, `low`, `close` and `volume`
+//!   or `o`, `h`, `l`, `c` and `v` keys, and arrays starting with
+//!   `[timestamp, open, high, low, close, volume]`, with numbers or numeric strings.
+//!
+//! # Example
+//!
+//! ```
+//! use ta::adapters::Kline;
+//! use ta::indicators::SimpleMovingAverage;
+//! use ta::Next;
+//!
+//! let payload = r#"[
+//!     [1499040000000, "10.0", "12.0", "9.0", "11.0", "100.0", 1499040059999, "1100.0", 30, "50.0", "550.0", "0"],
+//!     [1499040060000, "11.0", "14.0", "11.0", "13.0", "80.0", 1499040119999, "1040.0", 20, "40.0", "520.0", "0"]
+//! ]"#;
+//! let klines: Vec<Kline> = serde_json::from_str(payload).unwrap();
+//!
+//! let mut sma = SimpleMovingAverage::new(2).unwrap();
+//! let outputs: Vec<f64> = klines.iter().map(|kline| sma.next(kline)).collect();
+//! assert_eq!(outputs, vec![11.0, 12.0]);
+//!
+//! // plain tuples work as well
+//! let bar = (1499040120000_i64, 13.0, 15.0, 12.0, 14.0, 90.0);
+//! assert_eq!(sma.next(&bar), 13.5);
+//! ```
+
+use core::convert::TryFrom;
+
+use serde_json::Value;
+
+use crate::errors::*;
+use crate::{AggressorVolume, Close, DataItem, High, Low, Open, Timestamp, Volume};
+
+impl Timestamp for (i64, f64, f64, f64, f64, f64) {
+    fn timestamp(&self) -> i64 {
+        self.0
+    }
+}
+
+impl Open for (i64, f64, f64, f64, f64, f64) {
+    fn open(&self) -> f64 {
+        self.1
+    }
+}
+
+impl High for (i64, f64, f64, f64, f64, f64) {
+    fn high(&self) -> f64 {
+        self.2
+    }
+}
+
+impl Low for (i64, f64, f64, f64, f64, f64) {
+    fn low(&self) -> f64 {
+        self.3
+    }
+}
+
+impl Close for (i64, f64, f64, f64, f64, f64) {
+    fn close(&self) -> f64 {
+        self.4
+    }
+}
+
+impl Volume for (i64, f64, f64, f64, f64, f64) {
+    fn volume(&self) -> f64 {
+        self.5
+    }
+}
+
+/// Candle of the Binance kline API and of the exchanges mirroring it.
+///
+/// Deserialized from the array of the API, where the prices and volumes are strings. Its
+/// timestamp is the open time, and its taker buy volume splits the volume by the side of the
+/// aggressor for [order flow](../indicators/struct.CumulativeVolumeDelta.html) indicators.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Deserialize)]
+#[serde(try_from = "Value")]
+pub struct Kline {
+    pub open_time: i64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    /// Volume in the base asset.
+    pub volume: f64,
+    pub close_time: i64,
+    /// Volume in the quote asset.
+    pub quote_volume: f64,
+    /// Number of trades.
+    pub trades: u64,
+    /// Volume in the base asset bought by takers, if the payload has it.
+    pub taker_buy_volume: Option<f64>,
+}
+
+impl TryFrom<Value> for Kline {
+    type Error = Error;
+
+    fn try_from(value: Value) -> Result<Self> {
+        let fields = match value.as_array() {
+            Some(fields) if fields.len() >= 9 => fields,
+            _ => return Err(Error::from_kind(ErrorKind::DataItemIncomplete)),
+        };
+        Ok(Self {
+            open_time: integer(&fields[0])?,
+            open: number(&fields[1])?,
+            high: number(&fields[2])?,
+            low: number(&fields[3])?,
+            close: number(&fields[4])?,
+            volume: number(&fields[5])?,
+            close_time: integer(&fields[6])?,
+            quote_volume: number(&fields[7])?,
+            trades: integer(&fields[8])? as u64,
+            taker_buy_volume: fields.get(9).map(number).transpose()?,
+        })
+    }
+}
+
+impl Timestamp for Kline {
+    fn timestamp(&self) -> i64 {
+        self.open_time
+    }
+}
+
+impl Open for Kline {
+    fn open(&self) -> f64 {
+        self.open
+    }
+}
+
+impl High for Kline {
+    fn high(&self) -> f64 {
+        self.high
+    }
+}
+
+impl Low for Kline {
+    fn low(&self) -> f64 {
+        self.low
+    }
+}
+
+impl Close for Kline {
+    fn close(&self) -> f64 {
+        self.close
+    }
+}
+
+impl Volume for Kline {
+    fn volume(&self) -> f64 {
+        self.volume
+    }
+}
+
+/// Klines without the taker buy volume count half of the volume on each side.
+impl AggressorVolume for Kline {
+    fn buy_volume(&self) -> f64 {
+        self.taker_buy_volume.unwrap_or(self.volume / 2.0)
+    }
+
+    fn sell_volume(&self) -> f64 {
+        self.volume - self.buy_volume()
+    }
+}
+
+impl TryFrom<&Value> for DataItem {
+    type Error = Error;
+
+    /// Converts a JSON object or array into a bar, failing with `DataItemIncomplete` if a
+    /// value is missing, `DataItemInvalid` if it isn't a number, and like
+    /// [DataItemBuilder::build](struct.DataItemBuilder.html#method.build) for bars which
+    /// can't occur.
+    fn try_from(value: &Value) -> Result<Self> {
+        let field = |long: &str, short: &str| -> Result<f64> {
+            match value.get(long).or_else(|| value.get(short)) {
+                Some(field) => number(field),
+                None => Err(Error::from_kind(ErrorKind::DataItemIncomplete)),
+            }
+        };
+        let (open, high, low, close, volume) = match value {
+            Value::Array(fields) if fields.len() >= 6 => (
+                number(&fields[1])?,
+                number(&fields[2])?,
+                number(&fields[3])?,
+                number(&fields[4])?,
+                number(&fields[5])?,
+            ),
+            Value::Object(_) => (
+                field("open", "o")?,
+                field("high", "h")?,
+                field("low", "l")?,
+                field("close", "c")?,
+                field("volume", "v")?,
+            ),
+            _ => return Err(Error::from_kind(ErrorKind::DataItemIncomplete)),
+        };
+        DataItem::builder()
+            .open(open)
+            .high(high)
+            .low(low)
+            .close(close)
+            .volume(volume)
+            .build()
+    }
+}
+
+// number or numeric string
+fn number(value: &Value) -> Result<f64> {
+    let number = match value {
+        Value::Number(number) => number.as_f64(),
+        Value::String(string) => string.parse().ok(),
+        _ => None,
+    };
+    number.ok_or_else(|| Error::from_kind(ErrorKind::DataItemInvalid))
+}
+
+fn integer(value: &Value) -> Result<i64> {
+    let integer = match value {
+        Value::Number(number) => number.as_i64(),
+        Value::String(string) => string.parse().ok(),
+        _ => None,
+    };
+    integer.ok_or_else(|| Error::from_kind(ErrorKind::DataItemInvalid))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::round;
+    use serde_json::json;
+
+    #[test]
+    fn test_kline() {
+        let kline: Kline = serde_json::from_value(json!([
+            1499040000000_i64,
+            "0.01634790",
+            "0.80000000",
+            "0.01575800",
+            "0.01577100",
+            "148976.11427815",
+            1499644799999_i64,
+            "2434.19055334",
+            308,
+            "1756.87402397",
+            "28.46694368",
+            "0"
+        ]))
+        .unwrap();
+        assert_eq!(kline.timestamp(), 1499040000000);
+        assert_eq!(kline.open(), 0.0163479);
+        assert_eq!(kline.high(), 0.8);
+        assert_eq!(kline.volume(), 148976.11427815);
+        assert_eq!(kline.trades, 308);
+        assert_eq!(kline.buy_volume(), 1756.87402397);
+        assert_eq!(round(kline.sell_volume()), 147219.24);
+
+        assert!(serde_json::from_value::<Kline>(json!([1, "1.0", "x"])).is_err());
+        assert!(serde_json::from_value::<Kline>(json!({ "open": 1.0 })).is_err());
+    }
+
+    #[test]
+    fn test_value() {
+        let item = DataItem::try_from(&json!({
+            "open": 10.0, "high": 12.0, "low": 9.0, "close": 11.0, "volume": 100
+        }))
+        .unwrap();
+        assert_eq!(item.close(), 11.0);
+        assert_eq!(item.volume(), 100.0);
+
+        let item = DataItem::try_from(&json!({
+            "t": 1, "o": "10", "h": "12", "l": "9", "c": "11", "v": "100"
+        }))
+        .unwrap();
+        assert_eq!(item.high(), 12.0);
+
+        let item = DataItem::try_from(&json!([1, "10", "12", "9", "11", "100", 2])).unwrap();
+        assert_eq!(item.low(), 9.0);
+
+        assert!(matches!(
+            DataItem::try_from(&json!({ "o": 10, "h": 12, "l": 9, "c": 11 }))
+                .unwrap_err()
+                .kind(),
+            ErrorKind::DataItemIncomplete
+        ));
+        assert!(matches!(
+            DataItem::try_from(&json!([1, "10", "12", "nine", "11", "100"]))
+                .unwrap_err()
+                .kind(),
+            ErrorKind::DataItemInvalid
+        ));
+        assert!(matches!(
+            DataItem::try_from(&json!([1, 10, 8, 9, 11, 100]))
+                .unwrap_err()
+                .kind(),
+            ErrorKind::HighBelowLow
+        ));
+        assert!(matches!(
+            DataItem::try_from(&json!(10)).unwrap_err().kind(),
+            ErrorKind::DataItemIncomplete
+        ));
+    }
+
+    #[test]
+    fn test_tuple() {
+        let bar = (5_i64, 1.0, 3.0, 0.5, 2.0, 10.0);
+        assert_eq!(bar.timestamp(), 5);
+        assert_eq!((bar.open(), bar.high(), bar.low()), (1.0, 3.0, 0.5));
+        assert_eq!((bar.close(), bar.volume()), (2.0, 10.0));
+    }
+}
@@ -0,0 +1,636 @@
+//! Alert rules over indicator outputs.
+//!
+//! [Alerts](struct.Alerts.html) evaluates a set of [rules](struct.Rule.html) once per
+//! period. The values of the period, e.g. the outputs of several indicators, are passed as
+//! a slice and every rule watches one of them by its index. Fired rules are reported as
+//! [Alert](struct.Alert.html) events, which can be forwarded to a notification system.
+//!
+//! Every rule has a [Trigger](enum.Trigger.html) and optionally:
+//!
+//! * _confirm_ - number of consecutive periods a crossing has to hold before it fires, to
+//!   debounce values oscillating around a level,
+//! * _cooldown_ - number of periods after firing during which the rule stays silent,
+//! * _once_ - the rule fires only once until the engine is reset.
+//!
+//! # Example
+//!
+//! ```
+//! use ta::alerts::{Alerts, Rule, Trigger};
+//! use ta::flips::Flip;
+//! use ta::indicators::{RelativeStrengthIndex, SimpleMovingAverage};
+//! use ta::Calculate;
+//!
+//! let mut rsi = RelativeStrengthIndex::new(3).unwrap();
+//! let mut sma = SimpleMovingAverage::new(3).unwrap();
+//!
+//! let mut alerts = Alerts::new();
+//! let overbought = alerts
+//!     .try_add(Rule::new("RSI overbought", 0, Trigger::CrossesAbove(70.0)))
+//!     .unwrap();
+//! alerts
+//!     .try_add(Rule::new("price over SMA", 1, Trigger::CrossesOver(2)).cooldown(5))
+//!     .unwrap();
+//!
+//! let mut fired = Vec::new();
+//! for &price in &[10.0, 9.0, 8.0, 9.0, 11.0, 12.0] {
+//!     let values = [rsi.calc(price), price, sma.calc(price)];
+//!     fired.extend(alerts.check(&values));
+//! }
+//! assert_eq!(fired.len(), 2);
+//! assert_eq!((fired[0].rule, fired[0].period), (1, 3));
+//! assert_eq!(fired[1].rule, overbought);
+//! assert_eq!(fired[1].direction, Flip::Bullish);
+//! assert_eq!(alerts.rule(fired[1].rule).name(), "RSI overbought");
+//! ```
+
+use alloc::collections::VecDeque;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::errors::*;
+use crate::flips::Flip;
+use crate::sinks::Sink;
+use crate::{Float, Reset};
+
+/// Condition which fires a [Rule](struct.Rule.html).
+///
+/// Crossings of two values refer to the other value by its index, or by its name in a
+/// [pipeline configuration](../pipeline/struct.AlertConfig.html).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Trigger<F = f64, S = usize> {
+    /// The value crosses above the level.
+    CrossesAbove(F),
+    /// The value crosses below the level.
+    CrossesBelow(F),
+    /// The value crosses above the value with the given index, e.g. a fast average crossing
+    /// over a slow one.
+    CrossesOver(S),
+    /// The value crosses below the value with the given index.
+    CrossesUnder(S),
+    /// The value is higher than all values of the previous _n_ periods.
+    NewHigh(u32),
+    /// The value is lower than all values of the previous _n_ periods.
+    NewLow(u32),
+    /// The value moves into the band between the lower and the upper level, inclusive.
+    EntersBand(F, F),
+    /// The value changes from positive to negative or the other way round, zeros are
+    /// skipped.
+    ChangesSign,
+}
+
+/// Alert rule watching one of the values passed to [Alerts](struct.Alerts.html).
+///
+/// Crossings, including entering a band, are reported when the value moves to the other
+/// side, the side it starts on is not reported. New extremes and sign changes are reported
+/// on every new extreme or change.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rule<F = f64> {
+    name: String,
+    series: usize,
+    trigger: Trigger<F>,
+    confirm: u32,
+    cooldown: u32,
+    once: bool,
+    // a crossing is possible once the condition was seen not to hold
+    armed: bool,
+    // consecutive periods the crossing condition held since the rule was armed
+    streak: u32,
+    history: VecDeque<F>,
+    fired: Option<u64>,
+}
+
+impl Rule {
+    /// Creates a rule watching the value with index `series`.
+    pub fn new<S: Into<String>>(name: S, series: usize, trigger: Trigger<f64>) -> Self {
+        Self::with_type(name, series, trigger)
+    }
+}
+
+impl<F: Float> Rule<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type<S: Into<String>>(name: S, series: usize, trigger: Trigger<F>) -> Self {
+        Self {
+            name: name.into(),
+            series,
+            trigger,
+            confirm: 1,
+            cooldown: 0,
+            once: false,
+            armed: false,
+            streak: 0,
+            history: VecDeque::new(),
+            fired: None,
+        }
+    }
+
+    /// Number of consecutive periods a crossing has to hold before the rule fires. Default
+    /// is 1, which fires on the crossing period. Ignored by new extremes and sign changes.
+    pub fn confirm(mut self, periods: u32) -> Self {
+        self.confirm = periods;
+        self
+    }
+
+    /// Number of periods after firing during which the rule does not fire again. Default
+    /// is 0.
+    pub fn cooldown(mut self, periods: u32) -> Self {
+        self.cooldown = periods;
+        self
+    }
+
+    /// Makes the rule fire only once until the engine is reset.
+    pub fn once(mut self) -> Self {
+        self.once = true;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn series(&self) -> usize {
+        self.series
+    }
+
+    pub fn trigger(&self) -> Trigger<F> {
+        self.trigger
+    }
+
+    fn validate(&self) -> Result<()> {
+        let valid = match self.trigger {
+            Trigger::CrossesAbove(level) | Trigger::CrossesBelow(level) => !level.is_nan(),
+            Trigger::CrossesOver(other) | Trigger::CrossesUnder(other) => other != self.series,
+            Trigger::NewHigh(n) | Trigger::NewLow(n) => n > 0,
+            Trigger::EntersBand(lower, upper) => lower <= upper,
+            Trigger::ChangesSign => true,
+        };
+        if valid && self.confirm > 0 {
+            Ok(())
+        } else {
+            Err(Error::from_kind(ErrorKind::InvalidParameter))
+        }
+    }
+
+    // Direction of the move if the trigger condition is met in this period.
+    fn triggered(&mut self, values: &[F]) -> Option<Flip> {
+        let value = values[self.series];
+        let crossing = |rule: &mut Self, holds: bool| {
+            if !holds {
+                rule.armed = true;
+                rule.streak = 0;
+                return false;
+            }
+            if !rule.armed {
+                return false;
+            }
+            rule.streak += 1;
+            rule.armed = rule.streak < rule.confirm;
+            !rule.armed
+        };
+        let bullish = |fired: bool| if fired { Some(Flip::Bullish) } else { None };
+        let bearish = |fired: bool| if fired { Some(Flip::Bearish) } else { None };
+
+        match self.trigger {
+            Trigger::CrossesAbove(level) => bullish(crossing(self, value > level)),
+            Trigger::CrossesBelow(level) => bearish(crossing(self, value < level)),
+            Trigger::CrossesOver(other) => bullish(crossing(self, value > values[other])),
+            Trigger::CrossesUnder(other) => bearish(crossing(self, value < values[other])),
+            Trigger::NewHigh(n) | Trigger::NewLow(n) => {
+                let high = matches!(self.trigger, Trigger::NewHigh(_));
+                let extreme = self.history.len() == n as usize
+                    && self.history.iter().all(|&previous| {
+                        if high {
+                            value > previous
+                        } else {
+                            value < previous
+                        }
+                    });
+                if !value.is_nan() {
+                    self.history.push_back(value);
+                    if self.history.len() > n as usize {
+                        self.history.pop_front();
+                    }
+                }
+                if high {
+                    bullish(extreme)
+                } else {
+                    bearish(extreme)
+                }
+            }
+            Trigger::EntersBand(lower, upper) => {
+                let inside = value >= lower && value <= upper;
+                if !inside && !value.is_nan() {
+                    // the last value outside tells the side the band is entered from
+                    self.history.clear();
+                    self.history.push_back(value);
+                }
+                if !crossing(self, inside) {
+                    return None;
+                }
+                match self.history.back() {
+                    Some(&outside) if outside > upper => Some(Flip::Bearish),
+                    _ => Some(Flip::Bullish),
+                }
+            }
+            Trigger::ChangesSign => {
+                if value == F::zero() || value.is_nan() {
+                    return None;
+                }
+                let positive = value > F::zero();
+                // the last non-zero value
+                let previous = self.history.back().map(|&previous| previous > F::zero());
+                self.history.clear();
+                self.history.push_back(value);
+                match previous {
+                    Some(previous) if previous != positive => Some(if positive {
+                        Flip::Bullish
+                    } else {
+                        Flip::Bearish
+                    }),
+                    _ => None,
+                }
+            }
+        }
+    }
+
+    // takes over the state of the same rule evaluated before
+    fn inherit(&mut self, previous: &Rule<F>) {
+        self.armed = previous.armed;
+        self.streak = previous.streak;
+        self.history = previous.history.clone();
+        self.fired = previous.fired;
+    }
+
+    fn check(&mut self, period: u64, values: &[F]) -> Option<Alert<F>> {
+        let direction = self.triggered(values)?;
+        let silent = match self.fired {
+            Some(_) if self.once => true,
+            Some(fired) => period - fired <= u64::from(self.cooldown),
+            None => false,
+        };
+        if silent {
+            return None;
+        }
+        self.fired = Some(period);
+        Some(Alert {
+            period,
+            rule: 0,
+            value: values[self.series],
+            direction,
+        })
+    }
+}
+
+impl<F: Float> Reset for Rule<F> {
+    fn reset(&mut self) {
+        self.armed = false;
+        self.streak = 0;
+        self.history.clear();
+        self.fired = None;
+    }
+}
+
+/// Fired [Rule](struct.Rule.html).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Alert<F = f64> {
+    /// Index of the rule, as returned by [Alerts::try_add](struct.Alerts.html#method.try_add).
+    pub rule: usize,
+    /// Number of the period, counted from 0 since the last reset.
+    pub period: u64,
+    /// Watched value in this period.
+    pub value: F,
+    /// Bullish for crossings above or over, new highs, entering a band from below and
+    /// changing to positive, bearish otherwise.
+    pub direction: Flip,
+}
+
+impl<F: Float> fmt::Display for Alert<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let direction = match self.direction {
+            Flip::Bullish => "bullish",
+            Flip::Bearish => "bearish",
+        };
+        write!(
+            f,
+            "rule {} {} at period {}: {}",
+            self.rule, direction, self.period, self.value
+        )
+    }
+}
+
+/// Set of alert rules evaluated together.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Alerts<F = f64> {
+    rules: Vec<Rule<F>>,
+    period: u64,
+}
+
+impl Alerts {
+    pub fn new() -> Self {
+        Self::with_type()
+    }
+}
+
+impl<F: Float> Alerts<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type() -> Self {
+        Self {
+            rules: Vec::new(),
+            period: 0,
+        }
+    }
+
+    /// Adds a rule and returns its index, which identifies its alerts.
+    ///
+    /// Fails with `InvalidParameter` if the rule is invalid: a NaN level, a crossing of a
+    /// value with itself, an extreme over 0 periods, a band with the lower level above the
+    /// upper one or a _confirm_ of 0.
+    pub fn try_add(&mut self, rule: Rule<F>) -> Result<usize> {
+        rule.validate()?;
+        self.rules.push(rule);
+        Ok(self.rules.len() - 1)
+    }
+
+    pub fn rule(&self, index: usize) -> &Rule<F> {
+        &self.rules[index]
+    }
+
+    pub fn rules(&self) -> &[Rule<F>] {
+        &self.rules
+    }
+
+    /// Evaluates all rules with the values of the next period and returns the alerts in the
+    /// order the rules were added.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `values` does not contain a value some rule refers to.
+    pub fn check(&mut self, values: &[F]) -> Vec<Alert<F>> {
+        let period = self.period;
+        self.period += 1;
+        self.rules
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(index, rule)| {
+                let mut alert = rule.check(period, values)?;
+                alert.rule = index;
+                Some(alert)
+            })
+            .collect()
+    }
+
+    /// Continues the periods of `previous` and the state of its rules mapped by `matching`
+    /// from the index of a rule to the index of the same rule in `previous`.
+    pub(crate) fn inherit<M>(&mut self, previous: &Alerts<F>, mut matching: M)
+    where
+        M: FnMut(usize) -> Option<usize>,
+    {
+        self.period = previous.period;
+        for (index, rule) in self.rules.iter_mut().enumerate() {
+            if let Some(old) = matching(index).and_then(|i| previous.rules.get(i)) {
+                rule.inherit(old);
+            }
+        }
+    }
+}
+
+impl<F: Float> Alerts<F> {
+    /// Evaluates all rules like [check](#method.check) and sends the alerts to a
+    /// [Sink](../sinks/trait.Sink.html). Returns the number of alerts sent.
+    pub fn check_into<S: Sink<Alert<F>>>(&mut self, values: &[F], sink: &mut S) -> Result<usize> {
+        let alerts = self.check(values);
+        let count = alerts.len();
+        sink.send_all(alerts)?;
+        Ok(count)
+    }
+}
+
+impl<F: Float> Reset for Alerts<F> {
+    fn reset(&mut self) {
+        self.period = 0;
+        for rule in self.rules.iter_mut() {
+            rule.reset();
+        }
+    }
+}
+
+impl Default for Alerts {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fired(alerts: &mut Alerts, values: &[f64]) -> Vec<u64> {
+        values
+            .iter()
+            .flat_map(|&value| alerts.check(&[value]))
+            .map(|alert| alert.period)
+            .collect()
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut alerts = Alerts::new();
+        assert!(alerts
+            .try_add(Rule::new("a", 0, Trigger::CrossesAbove(f64::NAN)))
+            .is_err());
+        assert!(alerts
+            .try_add(Rule::new("b", 1, Trigger::CrossesOver(1)))
+            .is_err());
+        assert!(alerts
+            .try_add(Rule::new("c", 0, Trigger::NewLow(0)))
+            .is_err());
+        assert!(alerts
+            .try_add(Rule::new("d", 0, Trigger::CrossesBelow(1.0)).confirm(0))
+            .is_err());
+        assert_eq!(
+            alerts
+                .try_add(Rule::new("e", 0, Trigger::NewHigh(3)))
+                .unwrap(),
+            0
+        );
+        assert_eq!(alerts.rules().len(), 1);
+    }
+
+    #[test]
+    fn test_threshold() {
+        let mut alerts = Alerts::new();
+        alerts
+            .try_add(Rule::new("above", 0, Trigger::CrossesAbove(5.0)))
+            .unwrap();
+        // starting above the level is not a crossing
+        assert_eq!(
+            fired(&mut alerts, &[6.0, 4.0, 5.0, 7.0, 8.0, 3.0, 6.0]),
+            vec![3, 6]
+        );
+
+        let mut alerts = Alerts::new();
+        alerts
+            .try_add(Rule::new("below", 0, Trigger::CrossesBelow(5.0)))
+            .unwrap();
+        let alert = alerts
+            .check(&[6.0])
+            .into_iter()
+            .chain(alerts.check(&[4.5]))
+            .next();
+        assert_eq!(
+            alert,
+            Some(Alert {
+                rule: 0,
+                period: 1,
+                value: 4.5,
+                direction: Flip::Bearish
+            })
+        );
+    }
+
+    #[test]
+    fn test_confirm() {
+        let mut alerts = Alerts::new();
+        alerts
+            .try_add(Rule::new("above", 0, Trigger::CrossesAbove(5.0)).confirm(2))
+            .unwrap();
+        // a single period above the level is noise
+        assert_eq!(
+            fired(&mut alerts, &[4.0, 6.0, 4.0, 6.0, 7.0, 8.0, 4.0, 6.0, 6.0]),
+            vec![4, 8]
+        );
+    }
+
+    #[test]
+    fn test_cooldown() {
+        let mut alerts = Alerts::new();
+        alerts
+            .try_add(Rule::new("above", 0, Trigger::CrossesAbove(5.0)).cooldown(3))
+            .unwrap();
+        assert_eq!(
+            fired(&mut alerts, &[4.0, 6.0, 4.0, 6.0, 4.0, 6.0, 4.0, 6.0]),
+            vec![1, 5]
+        );
+    }
+
+    #[test]
+    fn test_once() {
+        let mut alerts = Alerts::new();
+        alerts
+            .try_add(Rule::new("above", 0, Trigger::CrossesAbove(5.0)).once())
+            .unwrap();
+        assert_eq!(fired(&mut alerts, &[4.0, 6.0, 4.0, 6.0]), vec![1]);
+
+        alerts.reset();
+        assert_eq!(fired(&mut alerts, &[4.0, 6.0]), vec![1]);
+    }
+
+    #[test]
+    fn test_crossover() {
+        let mut alerts = Alerts::new();
+        alerts
+            .try_add(Rule::new("golden cross", 0, Trigger::CrossesOver(1)))
+            .unwrap();
+        alerts
+            .try_add(Rule::new("death cross", 0, Trigger::CrossesUnder(1)))
+            .unwrap();
+
+        assert!(alerts.check(&[1.0, 2.0]).is_empty());
+        let alerts_fired = alerts.check(&[3.0, 2.5]);
+        assert_eq!(alerts_fired.len(), 1);
+        assert_eq!(alerts_fired[0].rule, 0);
+        assert_eq!(alerts_fired[0].direction, Flip::Bullish);
+        assert!(alerts.check(&[3.0, 2.5]).is_empty());
+        assert_eq!(alerts.check(&[2.0, 2.5])[0].rule, 1);
+    }
+
+    #[test]
+    fn test_extremes() {
+        let mut alerts = Alerts::new();
+        alerts
+            .try_add(Rule::new("high", 0, Trigger::NewHigh(3)))
+            .unwrap();
+        assert_eq!(
+            fired(
+                &mut alerts,
+                &[1.0, 2.0, 3.0, 4.0, 5.0, 5.0, 2.0, 1.0, 0.0, 3.0]
+            ),
+            vec![3, 4, 9]
+        );
+
+        let mut alerts = Alerts::new();
+        alerts
+            .try_add(Rule::new("low", 0, Trigger::NewLow(2)).cooldown(1))
+            .unwrap();
+        assert_eq!(
+            fired(&mut alerts, &[5.0, 4.0, 3.0, 2.0, 1.0, 0.0]),
+            vec![2, 4]
+        );
+    }
+
+    #[test]
+    fn test_enters_band() {
+        let mut alerts = Alerts::new();
+        alerts
+            .try_add(Rule::new("neutral", 0, Trigger::EntersBand(30.0, 70.0)))
+            .unwrap();
+        let directions: Vec<_> = [50.0, 80.0, 70.0, 60.0, 20.0, 25.0, 40.0]
+            .iter()
+            .flat_map(|&value| alerts.check(&[value]))
+            .map(|alert| (alert.period, alert.direction))
+            .collect();
+        // starting inside the band is not an entry
+        assert_eq!(directions, vec![(2, Flip::Bearish), (6, Flip::Bullish)]);
+        assert!(alerts
+            .try_add(Rule::new("empty", 0, Trigger::EntersBand(2.0, 1.0)))
+            .is_err());
+    }
+
+    #[test]
+    fn test_changes_sign() {
+        let mut alerts = Alerts::new();
+        alerts
+            .try_add(Rule::new("sign", 0, Trigger::ChangesSign))
+            .unwrap();
+        let directions: Vec<_> = [1.0, 2.0, 0.0, -1.0, 0.0, -2.0, 3.0]
+            .iter()
+            .flat_map(|&value| alerts.check(&[value]))
+            .map(|alert| (alert.period, alert.direction))
+            .collect();
+        assert_eq!(directions, vec![(3, Flip::Bearish), (6, Flip::Bullish)]);
+    }
+
+    #[test]
+    fn test_check_into() {
+        let mut alerts = Alerts::new();
+        alerts
+            .try_add(Rule::new("below", 0, Trigger::CrossesBelow(5.0)))
+            .unwrap();
+
+        let mut sent = Vec::new();
+        assert_eq!(alerts.check_into(&[6.0], &mut sent).unwrap(), 0);
+        assert_eq!(alerts.check_into(&[4.5], &mut sent).unwrap(), 1);
+        assert_eq!(sent[0].to_string(), "rule 0 bearish at period 1: 4.5");
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut alerts = Alerts::new();
+        alerts
+            .try_add(Rule::new("above", 0, Trigger::CrossesAbove(5.0)).cooldown(10))
+            .unwrap();
+        assert_eq!(fired(&mut alerts, &[4.0, 6.0]), vec![1]);
+
+        alerts.reset();
+        // the cooldown and the starting side are forgotten
+        assert_eq!(fired(&mut alerts, &[6.0, 4.0, 6.0]), vec![2]);
+    }
+}
@@ -0,0 +1,502 @@
+//! Alignment of daily bars to a trading calendar, and of outputs at different resolutions.
+//!
+//! Daily data from different sources often skips sessions (feed outages, delistings,
+//! differing holiday schedules). Feeding such series straight into indicators silently
+//! shifts their windows, which breaks multi-symbol studies. [DailyAligner](struct.DailyAligner.html)
+//! checks every bar against a [Calendar](../sessions/struct.Calendar.html) and either flags or
+//! forward-fills the sessions that are missing.
+//!
+//! # Example
+//!
+//! ```
+//! use ta::align::{Aligned, DailyAligner, MissingPolicy};
+//! use ta::sessions::{Calendar, DAY, TimeZone};
+//!
+//! let calendar = Calendar::new(TimeZone::UTC);
+//! let mut aligner = DailyAligner::new(calendar, MissingPolicy::ForwardFill);
+//!
+//! // Monday 2020-01-06 and Wednesday 2020-01-08
+//! let monday = 18_267 * DAY;
+//! assert_eq!(
+//!     aligner.next(monday, 10.0).unwrap(),
+//!     vec![Aligned::Bar { day: 18_267, bar: 10.0 }]
+//! );
+//! assert_eq!(
+//!     aligner.next(monday + 2 * DAY, 12.0).unwrap(),
+//!     vec![
+//!         Aligned::Filled { day: 18_268, bar: 10.0 },
+//!         Aligned::Bar { day: 18_269, bar: 12.0 },
+//!     ]
+//! );
+//! ```
+//!
+//! Strategies often combine indicators of several timeframes, e.g. a 1-minute RSI with an
+//! hourly EMA and daily pivots. A [ResolutionAligner](struct.ResolutionAligner.html) keeps the
+//! latest output of every resolution and merges them into one
+//! [record](struct.AlignedRecord.html) per tick, forward-filling the values of the higher
+//! timeframes and flagging those which stopped updating.
+
+use crate::errors::*;
+use crate::sessions::Calendar;
+use crate::Reset;
+use alloc::vec::Vec;
+
+/// What to do with trading days which have no bar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MissingPolicy {
+    /// Report the missing day without a bar.
+    Flag,
+    /// Repeat the previous bar for the missing day.
+    ForwardFill,
+}
+
+/// Bar aligned to a local day number of the calendar.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Aligned<T> {
+    /// Bar present in the input.
+    Bar { day: i64, bar: T },
+    /// Copy of the previous bar standing in for a missing day.
+    Filled { day: i64, bar: T },
+    /// Trading day without a bar.
+    Missing { day: i64 },
+}
+
+impl<T> Aligned<T> {
+    pub fn day(&self) -> i64 {
+        match *self {
+            Aligned::Bar { day, .. } | Aligned::Filled { day, .. } | Aligned::Missing { day } => {
+                day
+            }
+        }
+    }
+
+    /// Returns the bar, if there is one.
+    pub fn bar(&self) -> Option<&T> {
+        match self {
+            Aligned::Bar { bar, .. } | Aligned::Filled { bar, .. } => Some(bar),
+            Aligned::Missing { .. } => None,
+        }
+    }
+}
+
+/// Streaming aligner of daily bars.
+///
+/// Every call to [next](#method.next) returns the trading days missing since the previous
+/// bar, handled according to the [MissingPolicy](enum.MissingPolicy.html), followed by the
+/// bar itself. Bars are expected in chronological order, one per day; a bar for a day that
+/// is not later than the previous one is passed through without gap detection.
+///
+/// At most [max_gap](#method.max_gap) missing days are reported at once, 10 000 by default.
+/// A longer gap fails with `MissingBars`.
+#[derive(Debug, Clone)]
+pub struct DailyAligner<T> {
+    calendar: Calendar,
+    policy: MissingPolicy,
+    max_gap: u32,
+    last: Option<(i64, T)>,
+}
+
+const DEFAULT_MAX_GAP: u32 = 10_000;
+
+impl<T: Clone> DailyAligner<T> {
+    pub fn new(calendar: Calendar, policy: MissingPolicy) -> Self {
+        Self {
+            calendar,
+            policy,
+            max_gap: DEFAULT_MAX_GAP,
+            last: None,
+        }
+    }
+
+    /// Maximum number of trading days missing between two bars.
+    pub fn max_gap(mut self, max_gap: u32) -> Self {
+        self.max_gap = max_gap;
+        self
+    }
+
+    /// Consumes a bar and returns the missing days before it, followed by the bar.
+    ///
+    /// A gap longer than [max_gap](#method.max_gap) fails with `MissingBars`; the bar is
+    /// still taken as the latest one, so the series can go on.
+    pub fn next(&mut self, timestamp: i64, bar: T) -> Result<Vec<Aligned<T>>> {
+        let day = self.calendar.day(timestamp);
+        let mut output = Vec::new();
+
+        if let Some((last_day, ref last_bar)) = self.last {
+            let count = self.calendar.count_trading_days_between(last_day, day);
+            if count > self.max_gap as u64 {
+                self.last = Some((day, bar));
+                let count = count.min(u32::MAX as u64) as u32;
+                return Err(Error::from_kind(ErrorKind::MissingBars(count)));
+            }
+            for missing in self.calendar.trading_days_between(last_day, day) {
+                output.push(match self.policy {
+                    MissingPolicy::Flag => Aligned::Missing { day: missing },
+                    MissingPolicy::ForwardFill => Aligned::Filled {
+                        day: missing,
+                        bar: last_bar.clone(),
+                    },
+                });
+            }
+        }
+
+        self.last = Some((day, bar.clone()));
+        output.push(Aligned::Bar { day, bar });
+        Ok(output)
+    }
+}
+
+impl<T> Reset for DailyAligner<T> {
+    fn reset(&mut self) {
+        self.last = None;
+    }
+}
+
+/// Returns the trading days of the calendar missing from the given chronologically
+/// ordered timestamps.
+///
+/// Fails with `MissingBars` if more than `max_gap` days are missing between two timestamps.
+pub fn missing_days(calendar: &Calendar, timestamps: &[i64], max_gap: u32) -> Result<Vec<i64>> {
+    let mut missing = Vec::new();
+    for pair in timestamps.windows(2) {
+        let (from, to) = (calendar.day(pair[0]), calendar.day(pair[1]));
+        let count = calendar.count_trading_days_between(from, to);
+        if count > max_gap as u64 {
+            let count = count.min(u32::MAX as u64) as u32;
+            return Err(Error::from_kind(ErrorKind::MissingBars(count)));
+        }
+        missing.extend(calendar.trading_days_between(from, to));
+    }
+    Ok(missing)
+}
+
+/// Identifier of a resolution added to a [ResolutionAligner](struct.ResolutionAligner.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ResolutionId(usize);
+
+/// Latest value of a resolution in an [AlignedRecord](struct.AlignedRecord.html).
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlignedValue<T> {
+    pub value: T,
+    /// Timestamp of the bar the value was computed on.
+    pub timestamp: i64,
+    /// Whether at least one bar of the resolution is missing since the value.
+    pub stale: bool,
+}
+
+/// Values of all resolutions at a timestamp, in the order the resolutions were added.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlignedRecord<T> {
+    pub timestamp: i64,
+    /// Latest value of every resolution, `None` before its first value.
+    pub values: Vec<Option<AlignedValue<T>>>,
+}
+
+impl<T> AlignedRecord<T> {
+    /// Latest value of the resolution, stale or not.
+    pub fn get(&self, id: ResolutionId) -> Option<&T> {
+        self.values[id.0].as_ref().map(|aligned| &aligned.value)
+    }
+
+    /// Latest value of the resolution, `None` if it is stale.
+    pub fn fresh(&self, id: ResolutionId) -> Option<&T> {
+        self.values[id.0]
+            .as_ref()
+            .filter(|aligned| !aligned.stale)
+            .map(|aligned| &aligned.value)
+    }
+
+    /// Returns `true` if every resolution has a value which is not stale.
+    pub fn is_complete(&self) -> bool {
+        self.values
+            .iter()
+            .all(|aligned| aligned.as_ref().is_some_and(|aligned| !aligned.stale))
+    }
+}
+
+/// Streaming aligner of outputs computed at different resolutions.
+///
+/// Every resolution is added with its bar interval in milliseconds and updated with the
+/// outputs computed on its bars, timestamped with the opening of the bar. A
+/// [record](#method.record) at any timestamp forward-fills the latest value of every
+/// resolution, ignoring values of bars opening after it, so higher timeframes never leak
+/// into the past.
+///
+/// A value is stale once its bar opened two or more intervals before the record, i.e. when
+/// the bar after it has closed but was not updated, e.g. on a feed outage. Update the
+/// higher resolutions before taking the record of a tick which closes one of their bars.
+///
+/// # Example
+///
+/// ```
+/// use ta::align::ResolutionAligner;
+/// use ta::sessions::{HOUR, MINUTE};
+///
+/// let mut aligner = ResolutionAligner::new();
+/// let rsi = aligner.add(MINUTE).unwrap();
+/// let ema = aligner.add(HOUR).unwrap();
+///
+/// // EMA of the bar from 09:00 to 10:00, RSI of the bar from 10:05 to 10:06
+/// aligner.update(ema, 9 * HOUR, 101.5);
+/// aligner.update(rsi, 10 * HOUR + 5 * MINUTE, 62.0);
+/// let record = aligner.record(10 * HOUR + 6 * MINUTE);
+/// assert_eq!(record.get(ema), Some(&101.5));
+/// assert_eq!(record.fresh(rsi), Some(&62.0));
+/// assert!(record.is_complete());
+///
+/// // the EMA of the bar from 10:00 to 11:00 is missing
+/// let record = aligner.record(11 * HOUR + MINUTE);
+/// assert_eq!(record.get(ema), Some(&101.5));
+/// assert_eq!(record.fresh(ema), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ResolutionAligner<T> {
+    intervals: Vec<i64>,
+    // all values of a resolution which are not superseded, oldest first
+    values: Vec<Vec<(i64, T)>>,
+}
+
+impl<T: Clone> ResolutionAligner<T> {
+    pub fn new() -> Self {
+        Self {
+            intervals: Vec::new(),
+            values: Vec::new(),
+        }
+    }
+
+    /// Adds a resolution with bars of `interval` milliseconds, fails with `InvalidParameter`
+    /// if `interval` is not positive.
+    pub fn add(&mut self, interval: i64) -> Result<ResolutionId> {
+        if interval <= 0 {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        self.intervals.push(interval);
+        self.values.push(Vec::new());
+        Ok(ResolutionId(self.intervals.len() - 1))
+    }
+
+    /// Number of resolutions.
+    pub fn len(&self) -> usize {
+        self.intervals.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Sets the output of the bar opening at `timestamp` of a resolution. A later update of
+    /// the same bar replaces it, updates of older bars are ignored.
+    ///
+    /// # Panics
+    ///
+    /// If `id` was returned by another aligner with fewer resolutions.
+    pub fn update(&mut self, id: ResolutionId, timestamp: i64, value: T) {
+        let values = &mut self.values[id.0];
+        match values.last() {
+            Some(&(last, _)) if timestamp < last => {}
+            Some(&(last, _)) if timestamp == last => {
+                *values.last_mut().unwrap() = (timestamp, value);
+            }
+            _ => values.push((timestamp, value)),
+        }
+    }
+
+    /// Latest values of all resolutions at `timestamp`.
+    ///
+    /// Values of bars opening after `timestamp` are kept for later records, values
+    /// superseded by the latest one up to `timestamp` are dropped, so records are expected
+    /// in chronological order.
+    pub fn record(&mut self, timestamp: i64) -> AlignedRecord<T> {
+        let values = self
+            .values
+            .iter_mut()
+            .zip(self.intervals.iter())
+            .map(|(values, &interval)| {
+                let visible = values.iter().take_while(|&&(t, _)| t <= timestamp).count();
+                if visible > 1 {
+                    values.drain(..visible - 1);
+                }
+                let (opened, value) = values.first().filter(|&&(t, _)| t <= timestamp)?;
+                Some(AlignedValue {
+                    value: value.clone(),
+                    timestamp: *opened,
+                    stale: timestamp - opened >= 2 * interval,
+                })
+            })
+            .collect();
+        AlignedRecord { timestamp, values }
+    }
+}
+
+impl<T: Clone> Default for ResolutionAligner<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Reset for ResolutionAligner<T> {
+    /// Drops the values of all resolutions, keeping the resolutions.
+    fn reset(&mut self) {
+        for values in self.values.iter_mut() {
+            values.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sessions::{TimeZone, DAY, HOUR, MINUTE};
+
+    // Thursday 2020-01-02
+    const THURSDAY: i64 = 18_263;
+
+    fn calendar() -> Calendar {
+        Calendar::new(TimeZone::UTC).holiday(2020, 1, 3)
+    }
+
+    #[test]
+    fn test_flag() {
+        let mut aligner = DailyAligner::new(calendar(), MissingPolicy::Flag);
+
+        let output = aligner.next(THURSDAY * DAY + 20 * HOUR, 1.0).unwrap();
+        assert_eq!(
+            output,
+            vec![Aligned::Bar {
+                day: THURSDAY,
+                bar: 1.0
+            }]
+        );
+
+        // Friday is a holiday and the weekend is skipped
+        let output = aligner.next((THURSDAY + 4) * DAY, 2.0).unwrap();
+        assert_eq!(output.len(), 1);
+
+        let output = aligner.next((THURSDAY + 7) * DAY, 3.0).unwrap();
+        assert_eq!(
+            output,
+            vec![
+                Aligned::Missing { day: THURSDAY + 5 },
+                Aligned::Missing { day: THURSDAY + 6 },
+                Aligned::Bar {
+                    day: THURSDAY + 7,
+                    bar: 3.0
+                },
+            ]
+        );
+        assert_eq!(output[0].bar(), None);
+        assert_eq!(output[2].bar(), Some(&3.0));
+    }
+
+    #[test]
+    fn test_forward_fill() {
+        let mut aligner = DailyAligner::new(calendar(), MissingPolicy::ForwardFill);
+        aligner.next((THURSDAY + 4) * DAY, 2.0).unwrap();
+
+        let output = aligner.next((THURSDAY + 6) * DAY, 3.0).unwrap();
+        assert_eq!(
+            output,
+            vec![
+                Aligned::Filled {
+                    day: THURSDAY + 5,
+                    bar: 2.0
+                },
+                Aligned::Bar {
+                    day: THURSDAY + 6,
+                    bar: 3.0
+                },
+            ]
+        );
+        assert_eq!(output[0].day(), THURSDAY + 5);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut aligner = DailyAligner::new(calendar(), MissingPolicy::Flag);
+        aligner.next(THURSDAY * DAY, 1.0).unwrap();
+        aligner.reset();
+        assert_eq!(aligner.next((THURSDAY + 7) * DAY, 2.0).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_max_gap() {
+        let mut aligner = DailyAligner::new(calendar(), MissingPolicy::Flag).max_gap(2);
+        aligner.next((THURSDAY + 4) * DAY, 1.0).unwrap();
+        assert_eq!(aligner.next((THURSDAY + 7) * DAY, 2.0).unwrap().len(), 3);
+        let err = aligner.next((THURSDAY + 14) * DAY, 3.0).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::MissingBars(4)));
+        // the series goes on after the gap
+        assert_eq!(aligner.next((THURSDAY + 15) * DAY, 4.0).unwrap().len(), 1);
+
+        // the default bounds a bogus timestamp without walking the range
+        let mut aligner = DailyAligner::new(calendar(), MissingPolicy::ForwardFill);
+        aligner.next(i64::MIN, 1.0).unwrap();
+        let err = aligner.next(i64::MAX, 2.0).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::MissingBars(u32::MAX)));
+    }
+
+    #[test]
+    fn test_missing_days() {
+        let timestamps = vec![THURSDAY * DAY, (THURSDAY + 4) * DAY, (THURSDAY + 7) * DAY];
+        assert_eq!(
+            missing_days(&calendar(), &timestamps, 2).unwrap(),
+            vec![THURSDAY + 5, THURSDAY + 6]
+        );
+        assert!(missing_days(&calendar(), &timestamps, 1).is_err());
+    }
+
+    #[test]
+    fn test_add() {
+        let mut aligner = ResolutionAligner::<f64>::new();
+        assert!(aligner.add(0).is_err());
+        assert!(aligner.add(-MINUTE).is_err());
+        assert!(aligner.is_empty());
+        assert!(aligner.add(MINUTE).is_ok());
+        assert_eq!(aligner.len(), 1);
+    }
+
+    #[test]
+    fn test_resolutions() {
+        let mut aligner = ResolutionAligner::new();
+        let minute = aligner.add(MINUTE).unwrap();
+        let daily = aligner.add(DAY).unwrap();
+        assert_eq!(aligner.len(), 2);
+
+        aligner.update(minute, 0, 1.0);
+        let record = aligner.record(MINUTE);
+        assert_eq!(record.get(minute), Some(&1.0));
+        assert_eq!(record.values[daily.0], None);
+        assert!(!record.is_complete());
+
+        // the daily value of the next day is not visible before it opens
+        aligner.update(daily, 0, 10.0);
+        aligner.update(daily, DAY, 20.0);
+        aligner.update(minute, MINUTE, 2.0);
+        let record = aligner.record(2 * MINUTE);
+        assert_eq!(record.get(daily), Some(&10.0));
+        assert_eq!(record.fresh(minute), Some(&2.0));
+        assert!(record.is_complete());
+
+        let record = aligner.record(DAY + MINUTE);
+        assert_eq!(record.get(daily), Some(&20.0));
+        let minute_value = record.values[minute.0].as_ref().unwrap();
+        assert_eq!((minute_value.timestamp, minute_value.stale), (MINUTE, true));
+    }
+
+    #[test]
+    fn test_resolution_updates() {
+        let mut aligner = ResolutionAligner::new();
+        let hourly = aligner.add(HOUR).unwrap();
+        aligner.update(hourly, HOUR, 1.0);
+        // revised value of the same bar
+        aligner.update(hourly, HOUR, 2.0);
+        // late value of an older bar
+        aligner.update(hourly, 0, 3.0);
+        assert_eq!(aligner.record(2 * HOUR).get(hourly), Some(&2.0));
+        assert_eq!(aligner.record(3 * HOUR).fresh(hourly), None);
+
+        aligner.reset();
+        assert_eq!(aligner.len(), 1);
+        assert_eq!(aligner.record(3 * HOUR).get(hourly), None);
+    }
+}
@@ -0,0 +1,419 @@
+//! Audit logs of pipelines, for reproducing their outputs offline.
+//!
+//! [AuditLog](struct.AuditLog.html) runs a [Pipeline](../pipeline/struct.Pipeline.html) and
+//! writes its configuration, every bar and the outputs and alerts computed for it as
+//! newline-delimited JSON:
+//!
+//! ```json
+//! {"config":{"indicators":[{"name":"sma","spec":"SMA(2)","then":[]}],"alerts":[]}}
+//! {"bar":{"period":0,"bar":{"open":10.0,"high":10.0,"low":10.0,"close":10.0,"volume":1.0},"values":[10.0],"alerts":[]}}
+//! ```
+//!
+//! A [Replayer](struct.Replayer.html) rebuilds the pipeline from a log and feeds it the
+//! recorded bars, so that the state behind a signal can be inspected, or
+//! [verifies](struct.Replayer.html#method.verify) that the same outputs are computed again,
+//! e.g. after upgrading. Floats are written so that they are read back exactly, NaN and
+//! infinite values are written as `null`. Requires the `audit` feature.
+//!
+//! # Example
+//!
+//! ```
+//! use ta::audit::{AuditLog, Replayer};
+//! use ta::pipeline::{IndicatorConfig, PipelineConfig};
+//! use ta::registry::Registry;
+//! use ta::DataItem;
+//!
+//! let config = PipelineConfig {
+//!     indicators: vec![IndicatorConfig::new("sma", "SMA(2)")],
+//!     alerts: vec![],
+//!     signals: vec![],
+//! };
+//! let registry = Registry::new();
+//! let mut log = AuditLog::new(&config, &registry, Vec::new()).unwrap();
+//! for &close in &[10.0, 11.0, 10.5] {
+//!     let bar = DataItem::builder()
+//!         .open(close)
+//!         .high(close)
+//!         .low(close)
+//!         .close(close)
+//!         .volume(1.0)
+//!         .build()
+//!         .unwrap();
+//!     log.next(&bar).unwrap();
+//! }
+//! let (_, written) = log.into_inner();
+//!
+//! let replayer = Replayer::new(written.as_slice(), &registry);
+//! assert_eq!(replayer.verify().unwrap(), 3);
+//! ```
+
+use std::io::{self, BufRead, Write};
+
+use serde::{Deserialize, Serialize};
+
+use crate::alerts::Alert;
+use crate::errors::*;
+use crate::pipeline::{Pipeline, PipelineConfig, PipelineOutput};
+use crate::registry::Registry;
+use crate::signals::Signal;
+use crate::{DataItem, Next};
+
+fn failed<E: ToString>(error: E) -> Error {
+    Error::from_kind(ErrorKind::AuditFailed(error.to_string()))
+}
+
+/// Line of an audit log.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Entry {
+    /// Configuration the pipeline was built with, starts a log.
+    Config(PipelineConfig),
+    /// Configuration the pipeline was [reloaded](../pipeline/struct.Pipeline.html#method.reload)
+    /// with.
+    Reload(PipelineConfig),
+    /// Bar and what the pipeline computed for it.
+    Bar(Record),
+}
+
+/// Bar consumed by a pipeline with its outputs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Record {
+    /// Index of the bar since the log started.
+    pub period: u64,
+    pub bar: DataItem,
+    /// Outputs of the indicators, `NaN` for non-finite outputs.
+    #[serde(with = "nullable")]
+    pub values: Vec<f64>,
+    pub alerts: Vec<Alert>,
+    #[serde(default)]
+    pub signals: Vec<Signal>,
+}
+
+impl Record {
+    /// Returns `true` if `output` is what was recorded. Non-finite values are equal.
+    pub fn matches(&self, output: &PipelineOutput) -> bool {
+        self.values.len() == output.values.len()
+            && self
+                .values
+                .iter()
+                .zip(output.values.iter())
+                .all(|(&a, &b)| a == b || !(a.is_finite() || b.is_finite()))
+            && self.alerts == output.alerts
+            && self.signals == output.signals
+    }
+}
+
+// JSON has no NaN and infinity, they are written as null
+mod nullable {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(values: &[f64], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(
+            values
+                .iter()
+                .map(|&value| Some(value).filter(|value| value.is_finite())),
+        )
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<f64>, D::Error> {
+        let values: Vec<Option<f64>> = Deserialize::deserialize(deserializer)?;
+        Ok(values
+            .into_iter()
+            .map(|value| value.unwrap_or(f64::NAN))
+            .collect())
+    }
+}
+
+/// [Pipeline](../pipeline/struct.Pipeline.html) writing an audit log.
+///
+/// Entries are written as the pipeline runs, wrap the writer in a `BufWriter` for files.
+#[derive(Debug)]
+pub struct AuditLog<W> {
+    pipeline: Pipeline,
+    writer: W,
+    period: u64,
+}
+
+impl<W: Write> AuditLog<W> {
+    /// Builds the pipeline like [Pipeline::new](../pipeline/struct.Pipeline.html#method.new)
+    /// and writes its configuration.
+    pub fn new(config: &PipelineConfig, registry: &Registry, writer: W) -> Result<Self> {
+        let mut log = Self {
+            pipeline: Pipeline::new(config, registry)?,
+            writer,
+            period: 0,
+        };
+        log.write(&Entry::Config(config.clone()))?;
+        Ok(log)
+    }
+
+    /// Feeds a bar to the pipeline and writes it with the outputs.
+    pub fn next(&mut self, bar: &DataItem) -> Result<PipelineOutput> {
+        let output = self.pipeline.next(bar);
+        self.write(&Entry::Bar(Record {
+            period: self.period,
+            bar: bar.clone(),
+            values: output.values.clone(),
+            alerts: output.alerts.clone(),
+            signals: output.signals.clone(),
+        }))?;
+        self.period += 1;
+        Ok(output)
+    }
+
+    /// Reloads the pipeline like
+    /// [Pipeline::reload](../pipeline/struct.Pipeline.html#method.reload) and writes the
+    /// configuration.
+    pub fn reload(&mut self, config: &PipelineConfig, registry: &Registry) -> Result<Vec<String>> {
+        let restarted = self.pipeline.reload(config, registry)?;
+        self.write(&Entry::Reload(config.clone()))?;
+        Ok(restarted)
+    }
+
+    pub fn pipeline(&self) -> &Pipeline {
+        &self.pipeline
+    }
+
+    pub fn flush(&mut self) -> Result<()> {
+        self.writer.flush().map_err(failed)
+    }
+
+    pub fn into_inner(self) -> (Pipeline, W) {
+        (self.pipeline, self.writer)
+    }
+
+    fn write(&mut self, entry: &Entry) -> Result<()> {
+        serde_json::to_writer(&mut self.writer, entry).map_err(failed)?;
+        self.writer.write_all(b"\n").map_err(failed)
+    }
+}
+
+/// Bar of an audit log fed to the rebuilt pipeline again.
+#[derive(Debug, Clone)]
+pub struct Replayed {
+    pub record: Record,
+    /// What the rebuilt pipeline computed for the bar.
+    pub output: PipelineOutput,
+}
+
+impl Replayed {
+    pub fn matches(&self) -> bool {
+        self.record.matches(&self.output)
+    }
+}
+
+/// Replays an audit log written by [AuditLog](struct.AuditLog.html).
+///
+/// Iterates over the recorded bars, rebuilding and reloading the pipeline as the log did.
+/// After a bar, [pipeline](#method.pipeline) is in the state it was when the bar was
+/// recorded. Fails on unreadable logs and on bars before the first configuration.
+#[derive(Debug)]
+pub struct Replayer<'a, R> {
+    lines: io::Lines<R>,
+    registry: &'a Registry,
+    pipeline: Option<Pipeline>,
+}
+
+impl<'a, R: BufRead> Replayer<'a, R> {
+    /// Creates a replayer of `reader`, creating the indicators with `registry`, which has to
+    /// know the indicators the log was written with.
+    pub fn new(reader: R, registry: &'a Registry) -> Self {
+        Self {
+            lines: reader.lines(),
+            registry,
+            pipeline: None,
+        }
+    }
+
+    /// Rebuilt pipeline, `None` before the first configuration.
+    pub fn pipeline(&self) -> Option<&Pipeline> {
+        self.pipeline.as_ref()
+    }
+
+    /// Replays the whole log and returns the number of bars, fails with `ReplayMismatch`
+    /// at the first bar with different outputs.
+    pub fn verify(self) -> Result<u64> {
+        let mut count = 0;
+        for replayed in self {
+            let replayed = replayed?;
+            if !replayed.matches() {
+                return Err(Error::from_kind(ErrorKind::ReplayMismatch(
+                    replayed.record.period,
+                )));
+            }
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    fn replay(&mut self, entry: Entry) -> Result<Option<Replayed>> {
+        match entry {
+            Entry::Config(config) => {
+                self.pipeline = Some(Pipeline::new(&config, self.registry)?);
+                Ok(None)
+            }
+            Entry::Reload(config) => {
+                let registry = self.registry;
+                self.pipeline_mut()?.reload(&config, registry)?;
+                Ok(None)
+            }
+            Entry::Bar(record) => {
+                let output = self.pipeline_mut()?.next(&record.bar);
+                Ok(Some(Replayed { record, output }))
+            }
+        }
+    }
+
+    fn pipeline_mut(&mut self) -> Result<&mut Pipeline> {
+        self.pipeline
+            .as_mut()
+            .ok_or_else(|| failed("log does not start with a configuration"))
+    }
+}
+
+impl<'a, R: BufRead> Iterator for Replayer<'a, R> {
+    type Item = Result<Replayed>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(error) => return Some(Err(failed(error))),
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            let replayed = serde_json::from_str(&line)
+                .map_err(failed)
+                .and_then(|entry| self.replay(entry));
+            match replayed {
+                Ok(Some(replayed)) => return Some(Ok(replayed)),
+                Ok(None) => {}
+                Err(error) => return Some(Err(error)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alerts::Trigger;
+    use crate::pipeline::{AlertConfig, IndicatorConfig, SignalConfig, SignalRule};
+
+    fn bar(close: f64) -> DataItem {
+        DataItem::new(close, close + 1.0, close / 2.0, close, 1.0)
+    }
+
+    fn config() -> PipelineConfig {
+        PipelineConfig {
+            indicators: vec![
+                IndicatorConfig::new("fast", "EMA(2)"),
+                IndicatorConfig::new("slow", "SMA(3)"),
+            ],
+            alerts: vec![AlertConfig::new(
+                "golden cross",
+                "fast",
+                Trigger::CrossesOver("slow".into()),
+            )],
+            signals: vec![SignalConfig::new(
+                "trend",
+                SignalRule::Crossover {
+                    fast: "EMA(2)".into(),
+                    slow: "SMA(3)".into(),
+                },
+            )],
+        }
+    }
+
+    fn written() -> Vec<u8> {
+        let registry = Registry::new();
+        let mut log = AuditLog::new(&config(), &registry, Vec::new()).unwrap();
+        for &close in &[10.0, 9.0, 8.1, 9.7] {
+            log.next(&bar(close)).unwrap();
+        }
+        let mut updated = config();
+        updated.indicators[1].spec = "SMA(2)".into();
+        assert_eq!(log.reload(&updated, &registry).unwrap(), vec!["slow"]);
+        for &close in &[10.3, 1.0 / 3.0] {
+            log.next(&bar(close)).unwrap();
+        }
+        log.flush().unwrap();
+        log.into_inner().1
+    }
+
+    #[test]
+    fn test_log() {
+        let written = String::from_utf8(written()).unwrap();
+        let lines: Vec<&str> = written.lines().collect();
+        assert_eq!(lines.len(), 8);
+        assert!(lines[0].starts_with(r#"{"config":{"indicators":[{"name":"fast""#));
+        assert!(lines[1].starts_with(r#"{"bar":{"period":0,"bar":{"open":10.0,"#));
+        assert!(lines[5].starts_with(r#"{"reload":"#));
+
+        let record = Record {
+            period: 1,
+            bar: bar(2.0),
+            values: vec![f64::NAN, 0.1],
+            alerts: vec![],
+            signals: vec![],
+        };
+        let json = serde_json::to_string(&record).unwrap();
+        assert!(json.ends_with(r#""values":[null,0.1],"alerts":[],"signals":[]}"#));
+        let parsed: Record = serde_json::from_str(&json).unwrap();
+        assert!(parsed.values[0].is_nan());
+        assert!(parsed.matches(&PipelineOutput {
+            values: vec![f64::INFINITY, 0.1],
+            alerts: vec![],
+            signals: vec![],
+        }));
+    }
+
+    #[test]
+    fn test_replay() {
+        let registry = Registry::new();
+        let written = written();
+        assert_eq!(
+            Replayer::new(written.as_slice(), &registry)
+                .verify()
+                .unwrap(),
+            6
+        );
+
+        let mut replayer = Replayer::new(written.as_slice(), &registry);
+        let replayed: Vec<Replayed> = replayer.by_ref().take(4).map(Result::unwrap).collect();
+        assert_eq!(replayed[3].record.alerts.len(), 1);
+        assert_eq!(
+            replayed[3].output,
+            PipelineOutput {
+                values: replayed[3].record.values.clone(),
+                alerts: replayed[3].record.alerts.clone(),
+                signals: replayed[3].record.signals.clone(),
+            }
+        );
+        assert_eq!(
+            replayer.pipeline().unwrap().value("slow"),
+            Some(replayed[3].record.values[1])
+        );
+    }
+
+    #[test]
+    fn test_mismatch() {
+        let registry = Registry::new();
+        let written = String::from_utf8(written()).unwrap();
+        let tampered = written.replace(r#""values":[9.967901234567902,"#, r#""values":[9.97,"#);
+        match Replayer::new(tampered.as_bytes(), &registry).verify() {
+            Err(Error(ErrorKind::ReplayMismatch(period), _)) => assert_eq!(period, 4),
+            other => panic!("unexpected {:?}", other),
+        }
+
+        let headless: String = written.lines().skip(1).collect::<Vec<_>>().join("\n");
+        assert!(Replayer::new(headless.as_bytes(), &registry)
+            .verify()
+            .is_err());
+        assert!(Replayer::new(&b"{\"bar\":"[..], &registry)
+            .verify()
+            .is_err());
+        assert_eq!(Replayer::new(&b"\n"[..], &registry).verify().unwrap(), 0);
+    }
+}
@@ -0,0 +1,247 @@
+//! Backtesting of signals on historical bars.
+//!
+//! [Backtest](struct.Backtest.html) feeds bars to a strategy producing
+//! [signals](../signals/enum.Signal.html) and simulates a long-only position: a buy signal
+//! enters with the whole equity when flat and a sell signal exits when long. Orders fill at
+//! the close of the bar of the signal, moved against the trade by the slippage, and pay a
+//! fee proportional to the traded value on every entry and exit.
+//!
+//! The [report](struct.BacktestReport.html) holds the equity curve, relative to an initial
+//! equity of 1, the total return, the maximum drawdown, the win rate and the closed trades.
+//! A position still open after the last bar is marked to market in the equity curve, but
+//! isn't reported as a trade.
+//!
+//! # Example
+//!
+//! ```
+//! use ta::backtest::Backtest;
+//! use ta::indicators::SimpleMovingAverage;
+//! use ta::rules::{above, below, Strategy};
+//! use ta::DataItem;
+//!
+//! let bars: Vec<DataItem> = [10.0, 9.0, 8.0, 9.0, 11.0, 12.0, 11.0, 9.0]
+//!     .iter()
+//!     .map(|&close| {
+//!         DataItem::builder()
+//!             .open(close)
+//!             .high(close)
+//!             .low(close)
+//!             .close(close)
+//!             .volume(1.0)
+//!             .build()
+//!             .unwrap()
+//!     })
+//!     .collect();
+//! let mut strategy = Strategy::new(
+//!     below(SimpleMovingAverage::new(1).unwrap(), 9.0),
+//!     above(SimpleMovingAverage::new(1).unwrap(), 11.5),
+//! );
+//!
+//! // 0.1% fee and 0.05% slippage
+//! let backtest = Backtest::new(0.001, 0.0005).unwrap();
+//! let report = backtest.run(&bars, &mut strategy);
+//! assert_eq!(report.trades.len(), 1);
+//! assert_eq!(report.trades[0].entry_index, 2);
+//! assert_eq!(report.trades[0].exit_index, 5);
+//! assert_eq!(report.win_rate, Some(1.0));
+//! assert_eq!((report.total_return * 100.0).round(), 50.0);
+//! ```
+
+use alloc::vec::Vec;
+
+use crate::errors::*;
+use crate::signals::{NextSignal, Signal};
+use crate::Close;
+
+/// Round trip of a position.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Trade {
+    /// Index of the bar the position was entered on.
+    pub entry_index: usize,
+    /// Index of the bar the position was exited on.
+    pub exit_index: usize,
+    /// Entry price, including slippage.
+    pub entry_price: f64,
+    /// Exit price, including slippage.
+    pub exit_price: f64,
+    /// Return of the trade net of fees, as a fraction of the equity it was entered with.
+    pub profit: f64,
+}
+
+/// Outcome of a [Backtest](struct.Backtest.html).
+#[derive(Debug, Clone, PartialEq)]
+pub struct BacktestReport {
+    /// Equity at the close of every bar, starting from 1.
+    pub equity: Vec<f64>,
+    /// Final equity relative to the initial one, minus 1.
+    pub total_return: f64,
+    /// Largest decline of the equity from a previous peak, as a fraction of the peak.
+    pub max_drawdown: f64,
+    /// Fraction of the trades with a positive profit, `None` without closed trades.
+    pub win_rate: Option<f64>,
+    /// Closed trades.
+    pub trades: Vec<Trade>,
+}
+
+/// Long-only backtest with proportional fees and slippage.
+///
+/// # Parameters
+///
+/// * _fee_ - fee paid on every entry and exit, as a fraction of the traded value (0 to 1
+///   exclusive)
+/// * _slippage_ - adverse move of the fill price, as a fraction of the close (0 to 1
+///   exclusive)
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Backtest {
+    fee: f64,
+    slippage: f64,
+}
+
+impl Backtest {
+    pub fn new(fee: f64, slippage: f64) -> Result<Self> {
+        let valid = |value: f64| (0.0..1.0).contains(&value);
+        if !valid(fee) || !valid(slippage) {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        Ok(Self { fee, slippage })
+    }
+
+    pub fn fee(&self) -> f64 {
+        self.fee
+    }
+
+    pub fn slippage(&self) -> f64 {
+        self.slippage
+    }
+
+    /// Feeds `bars` to `strategy` in order and simulates its signals.
+    pub fn run<T, S>(&self, bars: &[T], strategy: &mut S) -> BacktestReport
+    where
+        T: Close,
+        S: NextSignal<T>,
+    {
+        let mut cash = 1.0;
+        // entry index, entry price, equity before the entry and units held
+        let mut position: Option<(usize, f64, f64, f64)> = None;
+        let mut equity = Vec::with_capacity(bars.len());
+        let mut trades = Vec::new();
+        let mut peak = cash;
+        let mut max_drawdown = 0.0f64;
+
+        for (index, bar) in bars.iter().enumerate() {
+            let close = bar.close();
+            match (strategy.next_signal(bar), position) {
+                (Signal::Buy, None) => {
+                    let price = close * (1.0 + self.slippage);
+                    let units = cash * (1.0 - self.fee) / price;
+                    position = Some((index, price, cash, units));
+                    cash = 0.0;
+                }
+                (Signal::Sell, Some((entry_index, entry_price, entered, units))) => {
+                    let price = close * (1.0 - self.slippage);
+                    cash = units * price * (1.0 - self.fee);
+                    trades.push(Trade {
+                        entry_index,
+                        exit_index: index,
+                        entry_price,
+                        exit_price: price,
+                        profit: cash / entered - 1.0,
+                    });
+                    position = None;
+                }
+                _ => {}
+            }
+
+            let value = match position {
+                Some((_, _, _, units)) => units * close,
+                None => cash,
+            };
+            peak = peak.max(value);
+            max_drawdown = max_drawdown.max(1.0 - value / peak);
+            equity.push(value);
+        }
+
+        let wins = trades.iter().filter(|trade| trade.profit > 0.0).count();
+        let win_rate = if trades.is_empty() {
+            None
+        } else {
+            Some(wins as f64 / trades.len() as f64)
+        };
+
+        BacktestReport {
+            total_return: equity.last().map_or(0.0, |value| value - 1.0),
+            equity,
+            max_drawdown,
+            win_rate,
+            trades,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::{round, Bar};
+    use alloc::vec;
+
+    // buys on odd and sells on even bars, after the first one
+    struct Alternate(usize);
+
+    impl NextSignal<Bar> for Alternate {
+        fn next_signal(&mut self, _: &Bar) -> Signal {
+            self.0 += 1;
+            match self.0 {
+                1 => Signal::Hold,
+                count if count % 2 == 0 => Signal::Buy,
+                _ => Signal::Sell,
+            }
+        }
+    }
+
+    fn bars(closes: &[f64]) -> Vec<Bar> {
+        closes
+            .iter()
+            .map(|&close| Bar::new().close(close))
+            .collect()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(Backtest::new(-0.1, 0.0).is_err());
+        assert!(Backtest::new(0.0, 1.0).is_err());
+        assert!(Backtest::new(f64::NAN, 0.0).is_err());
+        assert!(Backtest::new(0.0, 0.0).is_ok());
+    }
+
+    #[test]
+    fn test_run() {
+        let bars = bars(&[10.0, 10.0, 12.0, 8.0, 6.0, 9.0]);
+        let report = Backtest::default().run(&bars, &mut Alternate(0));
+        let equity: Vec<f64> = report.equity.iter().map(|&value| round(value)).collect();
+        assert_eq!(equity, vec![1.0, 1.0, 1.2, 1.2, 0.9, 0.9]);
+        assert_eq!(report.trades.len(), 2);
+        assert_eq!(report.trades[0].entry_index, 1);
+        assert_eq!(report.trades[0].exit_index, 2);
+        assert_eq!(round(report.trades[0].profit), 0.2);
+        assert_eq!(round(report.trades[1].profit), -0.25);
+        assert_eq!(report.win_rate, Some(0.5));
+        assert_eq!(round(report.max_drawdown), 0.25);
+        // the position entered on the last bar is still open
+        assert_eq!(round(report.total_return), -0.1);
+    }
+
+    #[test]
+    fn test_costs() {
+        let bars = bars(&[100.0, 100.0, 100.0]);
+        let report = Backtest::new(0.01, 0.01)
+            .unwrap()
+            .run(&bars, &mut Alternate(0));
+        let trade = report.trades[0];
+        assert_eq!(trade.entry_price, 101.0);
+        assert_eq!(trade.exit_price, 99.0);
+        let expected = 0.99 / 101.0 * 99.0 * 0.99 - 1.0;
+        assert!((trade.profit - expected).abs() < 1e-12);
+        assert_eq!(report.win_rate, Some(0.0));
+        assert!((report.total_return - expected).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,302 @@
+//! Bracket order simulation at bar resolution.
+//!
+//! When only OHLC bars are available it is unknown whether the stop or the target of
+//! a bracket order was touched first if both lie within the range of the same bar.
+//! [Bracket](struct.Bracket.html) resolves that ambiguity with an explicit
+//! [IntrabarPath](enum.IntrabarPath.html) assumption instead of silently picking
+//! the favourable outcome.
+//!
+//! # Example
+//!
+//! ```
+//! use ta::bracket::{Bracket, ExitKind, IntrabarPath, Side};
+//! use ta::DataItem;
+//!
+//! let bar = |open, high, low, close| {
+//!     DataItem::builder()
+//!         .open(open)
+//!         .high(high)
+//!         .low(low)
+//!         .close(close)
+//!         .volume(0.0)
+//!         .build()
+//!         .unwrap()
+//! };
+//! let bars = vec![bar(100.0, 101.0, 99.0, 100.5), bar(100.5, 106.0, 94.0, 105.0)];
+//!
+//! let bracket = Bracket::new(Side::Long, 100.0, 95.0, 105.0).unwrap();
+//! let exit = bracket.simulate(&bars).unwrap();
+//! assert_eq!(exit.kind, ExitKind::Stop);
+//! assert_eq!(exit.bar_index, 1);
+//!
+//! let exit = bracket.path(IntrabarPath::BestCase).simulate(&bars).unwrap();
+//! assert_eq!(exit.kind, ExitKind::Target);
+//! ```
+
+use crate::errors::*;
+use crate::{High, Low, Open};
+
+/// Direction of the position protected by the bracket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Long,
+    Short,
+}
+
+/// Assumption about the order in which prices were visited inside a bar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntrabarPath {
+    /// If both the stop and the target are inside the bar, the stop is hit first.
+    WorstCase,
+    /// If both the stop and the target are inside the bar, the target is hit first.
+    BestCase,
+    /// The bar moves from the open to the nearer extreme first, then to the other one
+    /// (open → high → low → close or open → low → high → close).
+    Ohlc,
+}
+
+/// Leg of the bracket which closed the position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitKind {
+    Stop,
+    Target,
+}
+
+/// Exit produced by a bracket simulation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BracketExit {
+    pub kind: ExitKind,
+    /// Fill price. When a bar gaps through a level the fill happens at the open.
+    pub price: f64,
+    /// Index of the bar which triggered the exit.
+    pub bar_index: usize,
+}
+
+/// Stop/target bracket around an entry price.
+///
+/// # Parameters
+///
+/// * _side_ - direction of the position
+/// * _entry_ - entry price
+/// * _stop_ - protective stop, below the entry for longs and above it for shorts
+/// * _target_ - profit target, above the entry for longs and below it for shorts
+///
+/// The default intrabar assumption is [IntrabarPath::WorstCase](enum.IntrabarPath.html).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bracket {
+    side: Side,
+    entry: f64,
+    stop: f64,
+    target: f64,
+    path: IntrabarPath,
+}
+
+impl Bracket {
+    pub fn new(side: Side, entry: f64, stop: f64, target: f64) -> Result<Self> {
+        let valid = match side {
+            Side::Long => stop < entry && entry < target,
+            Side::Short => target < entry && entry < stop,
+        };
+        if !valid {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+
+        Ok(Self {
+            side,
+            entry,
+            stop,
+            target,
+            path: IntrabarPath::WorstCase,
+        })
+    }
+
+    /// Sets the intrabar path assumption.
+    pub fn path(mut self, path: IntrabarPath) -> Self {
+        self.path = path;
+        self
+    }
+
+    pub fn side(&self) -> Side {
+        self.side
+    }
+
+    pub fn entry(&self) -> f64 {
+        self.entry
+    }
+
+    pub fn stop(&self) -> f64 {
+        self.stop
+    }
+
+    pub fn target(&self) -> f64 {
+        self.target
+    }
+
+    /// Checks a single bar and returns the exit it triggers, if any.
+    ///
+    /// The returned `bar_index` is always 0; use [simulate](#method.simulate) to run the
+    /// bracket over a sequence of bars.
+    pub fn check<T: Open + High + Low>(&self, bar: &T) -> Option<BracketExit> {
+        let open = bar.open();
+
+        // A gap through a level fills at the open, which is worse (stop)
+        // or better (target) than the level itself.
+        if self.stop_touched(open) {
+            return Some(self.exit(ExitKind::Stop, open));
+        }
+        if self.target_touched(open) {
+            return Some(self.exit(ExitKind::Target, open));
+        }
+
+        let (favorable, adverse) = match self.side {
+            Side::Long => (bar.high(), bar.low()),
+            Side::Short => (bar.low(), bar.high()),
+        };
+        let stop_hit = self.stop_touched(adverse);
+        let target_hit = self.target_touched(favorable);
+
+        match (stop_hit, target_hit) {
+            (false, false) => None,
+            (true, false) => Some(self.exit(ExitKind::Stop, self.stop)),
+            (false, true) => Some(self.exit(ExitKind::Target, self.target)),
+            (true, true) => {
+                let stop_first = match self.path {
+                    IntrabarPath::WorstCase => true,
+                    IntrabarPath::BestCase => false,
+                    IntrabarPath::Ohlc => (open - adverse).abs() <= (favorable - open).abs(),
+                };
+                if stop_first {
+                    Some(self.exit(ExitKind::Stop, self.stop))
+                } else {
+                    Some(self.exit(ExitKind::Target, self.target))
+                }
+            }
+        }
+    }
+
+    /// Runs the bracket over bars following the entry and returns the first exit.
+    ///
+    /// Returns `None` if neither the stop nor the target was reached.
+    pub fn simulate<T: Open + High + Low>(&self, bars: &[T]) -> Option<BracketExit> {
+        bars.iter().enumerate().find_map(|(index, bar)| {
+            self.check(bar).map(|exit| BracketExit {
+                bar_index: index,
+                ..exit
+            })
+        })
+    }
+
+    /// Profit or loss per unit of the position for the given exit.
+    pub fn pnl(&self, exit: &BracketExit) -> f64 {
+        match self.side {
+            Side::Long => exit.price - self.entry,
+            Side::Short => self.entry - exit.price,
+        }
+    }
+
+    fn stop_touched(&self, price: f64) -> bool {
+        match self.side {
+            Side::Long => price <= self.stop,
+            Side::Short => price >= self.stop,
+        }
+    }
+
+    fn target_touched(&self, price: f64) -> bool {
+        match self.side {
+            Side::Long => price >= self.target,
+            Side::Short => price <= self.target,
+        }
+    }
+
+    fn exit(&self, kind: ExitKind, price: f64) -> BracketExit {
+        BracketExit {
+            kind,
+            price,
+            bar_index: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    fn bar(open: f64, high: f64, low: f64) -> Bar {
+        Bar::new().open(open).high(high).low(low)
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(Bracket::new(Side::Long, 100.0, 95.0, 105.0).is_ok());
+        assert!(Bracket::new(Side::Long, 100.0, 105.0, 95.0).is_err());
+        assert!(Bracket::new(Side::Short, 100.0, 105.0, 95.0).is_ok());
+        assert!(Bracket::new(Side::Short, 100.0, 95.0, 105.0).is_err());
+        assert!(Bracket::new(Side::Long, 100.0, 100.0, 105.0).is_err());
+    }
+
+    #[test]
+    fn test_no_exit() {
+        let bracket = Bracket::new(Side::Long, 100.0, 95.0, 105.0).unwrap();
+        let bars = vec![bar(100.0, 104.0, 96.0), bar(101.0, 103.0, 99.0)];
+        assert_eq!(bracket.simulate(&bars), None);
+    }
+
+    #[test]
+    fn test_single_leg() {
+        let bracket = Bracket::new(Side::Long, 100.0, 95.0, 105.0).unwrap();
+        let bars = vec![bar(100.0, 104.0, 96.0), bar(101.0, 106.0, 99.0)];
+        let exit = bracket.simulate(&bars).unwrap();
+        assert_eq!(exit.kind, ExitKind::Target);
+        assert_eq!(exit.price, 105.0);
+        assert_eq!(exit.bar_index, 1);
+        assert_eq!(bracket.pnl(&exit), 5.0);
+
+        let bracket = Bracket::new(Side::Short, 100.0, 105.0, 95.0).unwrap();
+        let exit = bracket.simulate(&bars).unwrap();
+        assert_eq!(exit.kind, ExitKind::Stop);
+        assert_eq!(exit.price, 105.0);
+        assert_eq!(bracket.pnl(&exit), -5.0);
+    }
+
+    #[test]
+    fn test_gap_fills_at_open() {
+        let bracket = Bracket::new(Side::Long, 100.0, 95.0, 105.0).unwrap();
+
+        let exit = bracket.simulate(&[bar(93.0, 97.0, 92.0)]).unwrap();
+        assert_eq!(exit.kind, ExitKind::Stop);
+        assert_eq!(exit.price, 93.0);
+
+        let exit = bracket.simulate(&[bar(107.0, 108.0, 94.0)]).unwrap();
+        assert_eq!(exit.kind, ExitKind::Target);
+        assert_eq!(exit.price, 107.0);
+    }
+
+    #[test]
+    fn test_intrabar_path() {
+        let bracket = Bracket::new(Side::Long, 100.0, 95.0, 105.0).unwrap();
+        // open is closer to the high, so the high is visited first
+        let bars = vec![bar(104.0, 106.0, 94.0)];
+
+        let exit = bracket.simulate(&bars).unwrap();
+        assert_eq!(exit.kind, ExitKind::Stop);
+
+        let exit = bracket
+            .clone()
+            .path(IntrabarPath::BestCase)
+            .simulate(&bars)
+            .unwrap();
+        assert_eq!(exit.kind, ExitKind::Target);
+
+        let exit = bracket
+            .clone()
+            .path(IntrabarPath::Ohlc)
+            .simulate(&bars)
+            .unwrap();
+        assert_eq!(exit.kind, ExitKind::Target);
+
+        let bars = vec![bar(96.0, 106.0, 94.0)];
+        let exit = bracket.path(IntrabarPath::Ohlc).simulate(&bars).unwrap();
+        assert_eq!(exit.kind, ExitKind::Stop);
+    }
+}
@@ -0,0 +1,396 @@
+//! Parquet and Arrow IPC files of pipeline outputs.
+//!
+//! Writers buffer the [outputs](../pipeline/struct.PipelineOutput.html) of a
+//! [Pipeline](../pipeline/struct.Pipeline.html) and write them in batches of columns, for
+//! archiving computed features and loading them into dataframes:
+//!
+//! * [ParquetWriter](struct.ParquetWriter.html) writes a Parquet file, a row group per batch
+//!   (requires the `parquet` feature),
+//! * [IpcWriter](struct.IpcWriter.html) writes an Arrow IPC file (requires the `arrow-ipc`
+//!   feature).
+//!
+//! The [schema](fn.schema.html) has a `period` column counting the outputs, followed by a
+//! `Float64` column per indicator, named like it. The metadata of an indicator column holds
+//! its chain as `indicator`, e.g. `FAST_STOCH(14) -> EMA(3)`, and its `warmup_period`.
+//! Both writers are [sinks](../sinks/trait.Sink.html) of pipeline outputs.
+//!
+//! # Example
+//!
+//! ```
+//! # #[cfg(feature = "parquet")]
+//! # {
+//! use ta::columnar::ParquetWriter;
+//! use ta::pipeline::{IndicatorConfig, Pipeline, PipelineConfig};
+//! use ta::registry::Registry;
+//! use ta::{DataItem, Next};
+//!
+//! let config = PipelineConfig {
+//!     indicators: vec![IndicatorConfig::new("sma", "SMA(2)")],
+//!     alerts: vec![],
+//!     signals: vec![],
+//! };
+//! let mut pipeline = Pipeline::new(&config, &Registry::new()).unwrap();
+//! let mut writer = ParquetWriter::new(&pipeline, Vec::new()).unwrap();
+//! for &close in &[10.0, 11.0, 10.5] {
+//!     let bar = DataItem::builder()
+//!         .open(close)
+//!         .high(close)
+//!         .low(close)
+//!         .close(close)
+//!         .volume(1.0)
+//!         .build()
+//!         .unwrap();
+//!     writer.write(&pipeline.next(&bar)).unwrap();
+//! }
+//! let file = writer.into_inner().unwrap();
+//! assert_eq!(&file[..4], b"PAR1");
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::mem;
+use std::sync::Arc;
+
+use arrow_array::{ArrayRef, Float64Array, RecordBatch, UInt64Array};
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
+
+use crate::errors::*;
+use crate::pipeline::{Pipeline, PipelineOutput};
+use crate::sinks::Sink;
+
+/// Name of the column counting the outputs.
+pub const PERIOD: &str = "period";
+
+/// Default number of rows of a batch.
+pub const BATCH_SIZE: usize = 8192;
+
+fn failed<E: ToString>(error: E) -> Error {
+    Error::from_kind(ErrorKind::SinkFailed(error.to_string()))
+}
+
+/// Arrow schema of the outputs of `pipeline`.
+pub fn schema(pipeline: &Pipeline) -> Schema {
+    let mut fields = vec![Field::new(PERIOD, DataType::UInt64, false)];
+    for (name, chain, warmup_period) in pipeline.chains() {
+        let metadata = HashMap::from([
+            ("indicator".to_string(), chain),
+            ("warmup_period".to_string(), warmup_period.to_string()),
+        ]);
+        fields.push(Field::new(name, DataType::Float64, false).with_metadata(metadata));
+    }
+    Schema::new(fields)
+}
+
+// outputs buffered until a batch is full
+struct Columns {
+    schema: SchemaRef,
+    batch_size: usize,
+    period: u64,
+    periods: Vec<u64>,
+    values: Vec<Vec<f64>>,
+}
+
+impl Columns {
+    fn new(pipeline: &Pipeline) -> Self {
+        let schema = Arc::new(schema(pipeline));
+        let indicators = schema.fields().len() - 1;
+        Self {
+            schema,
+            batch_size: BATCH_SIZE,
+            period: 0,
+            periods: Vec::new(),
+            values: vec![Vec::new(); indicators],
+        }
+    }
+
+    // returns `true` once the batch is full
+    fn push(&mut self, output: &PipelineOutput) -> Result<bool> {
+        if output.values.len() != self.values.len() {
+            return Err(failed("output does not match the schema"));
+        }
+        self.periods.push(self.period);
+        self.period += 1;
+        for (column, &value) in self.values.iter_mut().zip(output.values.iter()) {
+            column.push(value);
+        }
+        Ok(self.periods.len() >= self.batch_size)
+    }
+
+    fn take(&mut self) -> Result<Option<RecordBatch>> {
+        if self.periods.is_empty() {
+            return Ok(None);
+        }
+        let mut columns: Vec<ArrayRef> = Vec::with_capacity(self.values.len() + 1);
+        columns.push(Arc::new(UInt64Array::from(mem::take(&mut self.periods))));
+        for column in self.values.iter_mut() {
+            columns.push(Arc::new(Float64Array::from(mem::take(column))));
+        }
+        RecordBatch::try_new(self.schema.clone(), columns)
+            .map(Some)
+            .map_err(failed)
+    }
+}
+
+/// Writer of pipeline outputs to a Parquet file.
+///
+/// The file is complete once [into_inner](#method.into_inner) wrote its footer.
+#[cfg(feature = "parquet")]
+pub struct ParquetWriter<W: Write + Send> {
+    writer: parquet::arrow::ArrowWriter<W>,
+    columns: Columns,
+}
+
+#[cfg(feature = "parquet")]
+impl<W: Write + Send> ParquetWriter<W> {
+    /// Creates a writer of the outputs of `pipeline`, which writes a row group every
+    /// [BATCH_SIZE](constant.BATCH_SIZE.html) outputs.
+    pub fn new(pipeline: &Pipeline, writer: W) -> Result<Self> {
+        let columns = Columns::new(pipeline);
+        let writer = parquet::arrow::ArrowWriter::try_new(writer, columns.schema.clone(), None)
+            .map_err(failed)?;
+        Ok(Self { writer, columns })
+    }
+
+    /// Number of outputs of a row group (greater than 0).
+    pub fn batch_size(mut self, rows: usize) -> Self {
+        self.columns.batch_size = rows.max(1);
+        self
+    }
+
+    pub fn schema(&self) -> SchemaRef {
+        self.columns.schema.clone()
+    }
+
+    /// Buffers an output, writing a row group once the batch is full.
+    pub fn write(&mut self, output: &PipelineOutput) -> Result<()> {
+        if self.columns.push(output)? {
+            self.write_batch()?;
+        }
+        Ok(())
+    }
+
+    /// Writes the buffered outputs and the footer, and returns the underlying writer.
+    pub fn into_inner(mut self) -> Result<W> {
+        self.write_batch()?;
+        self.writer.into_inner().map_err(failed)
+    }
+
+    fn write_batch(&mut self) -> Result<()> {
+        if let Some(batch) = self.columns.take()? {
+            self.writer.write(&batch).map_err(failed)?;
+            // closes the row group
+            self.writer.flush().map_err(failed)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "parquet")]
+impl<W: Write + Send> Sink<PipelineOutput> for ParquetWriter<W> {
+    fn send(&mut self, record: PipelineOutput) -> Result<()> {
+        self.write(&record)
+    }
+
+    /// Writes the buffered outputs as a row group.
+    fn flush(&mut self) -> Result<()> {
+        self.write_batch()
+    }
+}
+
+/// Writer of pipeline outputs to an Arrow IPC file.
+///
+/// The file is complete once [into_inner](#method.into_inner) wrote its footer.
+#[cfg(feature = "arrow-ipc")]
+pub struct IpcWriter<W: Write> {
+    writer: arrow_ipc::writer::FileWriter<W>,
+    columns: Columns,
+}
+
+#[cfg(feature = "arrow-ipc")]
+impl<W: Write> IpcWriter<W> {
+    /// Creates a writer of the outputs of `pipeline`, which writes a record batch every
+    /// [BATCH_SIZE](constant.BATCH_SIZE.html) outputs.
+    pub fn new(pipeline: &Pipeline, writer: W) -> Result<Self> {
+        let columns = Columns::new(pipeline);
+        let writer =
+            arrow_ipc::writer::FileWriter::try_new(writer, &columns.schema).map_err(failed)?;
+        Ok(Self { writer, columns })
+    }
+
+    /// Number of outputs of a record batch (greater than 0).
+    pub fn batch_size(mut self, rows: usize) -> Self {
+        self.columns.batch_size = rows.max(1);
+        self
+    }
+
+    pub fn schema(&self) -> SchemaRef {
+        self.columns.schema.clone()
+    }
+
+    /// Buffers an output, writing a record batch once the batch is full.
+    pub fn write(&mut self, output: &PipelineOutput) -> Result<()> {
+        if self.columns.push(output)? {
+            self.write_batch()?;
+        }
+        Ok(())
+    }
+
+    /// Writes the buffered outputs and the footer, and returns the underlying writer.
+    pub fn into_inner(mut self) -> Result<W> {
+        self.write_batch()?;
+        self.writer.into_inner().map_err(failed)
+    }
+
+    fn write_batch(&mut self) -> Result<()> {
+        match self.columns.take()? {
+            Some(batch) => self.writer.write(&batch).map_err(failed),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(feature = "arrow-ipc")]
+impl<W: Write> Sink<PipelineOutput> for IpcWriter<W> {
+    fn send(&mut self, record: PipelineOutput) -> Result<()> {
+        self.write(&record)
+    }
+
+    /// Writes the buffered outputs as a record batch.
+    fn flush(&mut self) -> Result<()> {
+        self.write_batch()?;
+        self.writer.flush().map_err(failed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::{IndicatorConfig, PipelineConfig};
+    use crate::registry::Registry;
+    use crate::{DataItem, Next};
+
+    fn pipeline() -> Pipeline {
+        let mut stoch = IndicatorConfig::new("stoch", "FAST_STOCH(3)");
+        stoch.then.push("EMA(2)".into());
+        let config = PipelineConfig {
+            indicators: vec![IndicatorConfig::new("sma", "SMA(2)"), stoch],
+            alerts: vec![],
+            signals: vec![],
+        };
+        Pipeline::new(&config, &Registry::new()).unwrap()
+    }
+
+    fn outputs(pipeline: &mut Pipeline) -> Vec<PipelineOutput> {
+        [10.0, 12.0, 11.0, 13.0, 14.0]
+            .iter()
+            .map(|&close| {
+                pipeline.next(&DataItem::new(close, close + 1.0, close - 1.0, close, 1.0))
+            })
+            .collect()
+    }
+
+    fn column(batch: &RecordBatch, index: usize) -> Vec<f64> {
+        batch
+            .column(index)
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .unwrap()
+            .values()
+            .to_vec()
+    }
+
+    #[test]
+    fn test_schema() {
+        let schema = schema(&pipeline());
+        let names: Vec<&str> = schema
+            .fields()
+            .iter()
+            .map(|field| field.name().as_str())
+            .collect();
+        assert_eq!(names, vec!["period", "sma", "stoch"]);
+        let metadata = schema.field(2).metadata();
+        assert_eq!(metadata["indicator"], "FAST_STOCH(3) -> EMA(2)");
+        assert_eq!(metadata["warmup_period"], "4");
+    }
+
+    #[test]
+    fn test_columns() {
+        let mut pipeline = pipeline();
+        let mut columns = Columns::new(&pipeline);
+        columns.batch_size = 2;
+        let outputs = outputs(&mut pipeline);
+        assert!(!columns.push(&outputs[0]).unwrap());
+        assert!(columns.push(&outputs[1]).unwrap());
+
+        let batch = columns.take().unwrap().unwrap();
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(column(&batch, 1), vec![10.0, 11.0]);
+        assert!(columns.take().unwrap().is_none());
+
+        columns.push(&outputs[2]).unwrap();
+        let batch = columns.take().unwrap().unwrap();
+        let periods = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<UInt64Array>()
+            .unwrap();
+        assert_eq!(periods.values().to_vec(), vec![2]);
+
+        let output = PipelineOutput {
+            values: vec![1.0],
+            alerts: vec![],
+            signals: vec![],
+        };
+        assert!(columns.push(&output).is_err());
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn test_parquet() {
+        use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+        let mut pipeline = pipeline();
+        let outputs = outputs(&mut pipeline);
+        let path = std::env::temp_dir().join(format!("ta-columnar-{}.parquet", std::process::id()));
+        let file = std::fs::File::create(&path).unwrap();
+        let mut writer = ParquetWriter::new(&pipeline, file).unwrap().batch_size(2);
+        writer.send_all(outputs.iter().cloned()).unwrap();
+        writer.into_inner().unwrap();
+
+        let file = std::fs::File::open(&path).unwrap();
+        let builder = ParquetRecordBatchReaderBuilder::try_new(file).unwrap();
+        assert_eq!(builder.metadata().num_row_groups(), 3);
+        let metadata = builder.schema().field(2).metadata().clone();
+        let batches: Vec<RecordBatch> = builder.build().unwrap().map(|b| b.unwrap()).collect();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(metadata["indicator"], "FAST_STOCH(3) -> EMA(2)");
+        let stoch: Vec<f64> = batches.iter().flat_map(|batch| column(batch, 2)).collect();
+        let expected: Vec<f64> = outputs.iter().map(|output| output.values[1]).collect();
+        assert_eq!(stoch, expected);
+    }
+
+    #[cfg(feature = "arrow-ipc")]
+    #[test]
+    fn test_ipc() {
+        use arrow_ipc::reader::FileReader;
+
+        let mut pipeline = pipeline();
+        let outputs = outputs(&mut pipeline);
+        let mut writer = IpcWriter::new(&pipeline, Vec::new()).unwrap();
+        for output in &outputs {
+            writer.write(output).unwrap();
+        }
+        writer.flush().unwrap();
+        writer.send(outputs[0].clone()).unwrap();
+        let file = writer.into_inner().unwrap();
+
+        let reader = FileReader::try_new(std::io::Cursor::new(file), None).unwrap();
+        assert_eq!(reader.schema(), Arc::new(schema(&pipeline)));
+        let batches: Vec<RecordBatch> = reader.map(|batch| batch.unwrap()).collect();
+        assert_eq!(batches.len(), 2);
+        assert_eq!(column(&batches[0], 1), vec![10.0, 11.0, 11.5, 12.0, 13.5]);
+        assert_eq!(column(&batches[1], 1), vec![10.0]);
+    }
+}
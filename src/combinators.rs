@@ -0,0 +1,505 @@
+//! Composition of indicators.
+//!
+//! [IndicatorExt](trait.IndicatorExt.html) adds four combinators to every indicator:
+//!
+//! * [then](trait.IndicatorExt.html#method.then) feeds the output of one indicator to
+//!   another, e.g. a fast stochastic smoothed with an EMA is a slow stochastic.
+//! * [pipe](trait.IndicatorExt.html#method.pipe) feeds the output of a transform producing
+//!   bars, e.g. [HeikinAshi](../indicators/struct.HeikinAshi.html) candles, to an indicator
+//!   of bars.
+//! * [map](trait.IndicatorExt.html#method.map) transforms the output with a closure.
+//! * [zip](trait.IndicatorExt.html#method.zip) feeds the same input to two indicators and
+//!   combines both outputs with a closure.
+//!
+//! The combined indicators implement [Calculate](../trait.Calculate.html),
+//! [Next](../trait.Next.html), [Reset](../trait.Reset.html) and [Warmup](../trait.Warmup.html)
+//! themselves, so they can be combined further.
+//!
+//! # Example
+//!
+//! ```
+//! use ta::indicators::{ExponentialMovingAverage as Ema, FastStochastic, SimpleMovingAverage as Sma};
+//! use ta::{Calculate, IndicatorExt};
+//!
+//! let mut slow_stoch = FastStochastic::new(3).unwrap().then(Ema::new(2).unwrap());
+//! assert_eq!(slow_stoch.calc(10.0), 50.0);
+//! assert_eq!(slow_stoch.calc(50.0).round(), 83.0);
+//! assert_eq!(slow_stoch.to_string(), "FAST_STOCH(3).then(EMA(2))");
+//!
+//! let mut above = Sma::new(1)
+//!     .unwrap()
+//!     .zip(Sma::new(2).unwrap(), |fast, slow| fast - slow)
+//!     .map(|diff: f64| diff > 0.0);
+//! assert!(!above.calc(2.0));
+//! assert!(above.calc(4.0));
+//! assert!(!above.calc(1.0));
+//! ```
+
+use core::fmt;
+
+use crate::errors::Result;
+use crate::sessions::Session;
+use crate::{Calculate, GapAction, Next, OnGap, OnSession, PriceSource, Reset, Warmup, WithSource};
+
+/// Adds composition combinators to every indicator.
+///
+/// Implemented for every type implementing [Reset](../trait.Reset.html), which keeps the
+/// methods away from iterators and options.
+pub trait IndicatorExt: Reset + Sized {
+    /// Feeds every output of `self` to `next`.
+    fn then<B: Reset>(self, next: B) -> Then<Self, B> {
+        Then {
+            first: self,
+            second: next,
+            count: 0,
+        }
+    }
+
+    /// Feeds every output of `self` to `next` as a data item, e.g. the bars of a transform
+    /// to an indicator of bars.
+    fn pipe<B: Reset>(self, next: B) -> Pipe<Self, B> {
+        Pipe {
+            first: self,
+            second: next,
+            count: 0,
+        }
+    }
+
+    /// Transforms every output of `self` with `f`.
+    fn map<Func>(self, f: Func) -> Map<Self, Func> {
+        Map { inner: self, f }
+    }
+
+    /// Feeds the same input to `self` and `other` and combines both outputs with `f`.
+    fn zip<B: Reset, Func>(self, other: B, f: Func) -> Zip<Self, B, Func> {
+        Zip {
+            first: self,
+            second: other,
+            f,
+        }
+    }
+
+    /// Feeds `self` with the given price of every bar instead of the close.
+    fn with_source<F>(self, source: PriceSource) -> WithSource<Self, F> {
+        WithSource::new(self, source)
+    }
+
+    /// Applies `action` to `self` whenever more than `max_gap` milliseconds pass between two
+    /// timestamped inputs.
+    ///
+    /// Fails with `InvalidParameter` unless `max_gap` and the decay interval are positive.
+    fn on_gap(self, max_gap: i64, action: GapAction) -> Result<OnGap<Self>> {
+        OnGap::new(self, max_gap, action)
+    }
+
+    /// Resets `self` before the first timestamped input of every new `session`.
+    fn on_session(self, session: Session) -> OnSession<Self> {
+        OnSession::new(self, session)
+    }
+}
+
+impl<I: Reset> IndicatorExt for I {}
+
+/// Indicator fed with the output of another indicator, created by
+/// [IndicatorExt::then](trait.IndicatorExt.html#method.then).
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Then<A, B> {
+    first: A,
+    second: B,
+    count: u32,
+}
+
+impl<A, B> Then<A, B> {
+    /// Returns both indicators.
+    pub fn into_inner(self) -> (A, B) {
+        (self.first, self.second)
+    }
+}
+
+impl<F, A, B> Calculate<F> for Then<A, B>
+where
+    A: Calculate<F>,
+    B: Calculate<A::Output>,
+{
+    type Output = B::Output;
+
+    fn calc(&mut self, input: F) -> Self::Output {
+        self.count = self.count.saturating_add(1);
+        self.second.calc(self.first.calc(input))
+    }
+}
+
+impl<T, A, B> Next<T> for Then<A, B>
+where
+    A: Next<T>,
+    B: Calculate<A::Output>,
+{
+    type Output = B::Output;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.count = self.count.saturating_add(1);
+        self.second.calc(self.first.next(input))
+    }
+}
+
+impl<A: Reset, B: Reset> Reset for Then<A, B> {
+    fn reset(&mut self) {
+        self.first.reset();
+        self.second.reset();
+        self.count = 0;
+    }
+}
+
+impl<A: Warmup, B: Warmup> Warmup for Then<A, B> {
+    fn warmup_period(&self) -> u32 {
+        // the first meaningful output of `first` starts the warm-up of `second`
+        (self.first.warmup_period() + self.second.warmup_period()).saturating_sub(1)
+    }
+
+    fn is_ready(&self) -> bool {
+        self.count >= self.warmup_period()
+    }
+}
+
+impl<A: fmt::Display, B: fmt::Display> fmt::Display for Then<A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.then({})", self.first, self.second)
+    }
+}
+
+/// Indicator fed with the output of another indicator as a data item, created by
+/// [IndicatorExt::pipe](trait.IndicatorExt.html#method.pipe).
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Pipe<A, B> {
+    first: A,
+    second: B,
+    count: u32,
+}
+
+impl<A, B> Pipe<A, B> {
+    /// Returns both indicators.
+    pub fn into_inner(self) -> (A, B) {
+        (self.first, self.second)
+    }
+}
+
+impl<F, A, B> Calculate<F> for Pipe<A, B>
+where
+    A: Calculate<F>,
+    B: Next<A::Output>,
+{
+    type Output = B::Output;
+
+    fn calc(&mut self, input: F) -> Self::Output {
+        self.count = self.count.saturating_add(1);
+        self.second.next(&self.first.calc(input))
+    }
+}
+
+impl<T, A, B> Next<T> for Pipe<A, B>
+where
+    A: Next<T>,
+    B: Next<A::Output>,
+{
+    type Output = B::Output;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.count = self.count.saturating_add(1);
+        self.second.next(&self.first.next(input))
+    }
+}
+
+impl<A: Reset, B: Reset> Reset for Pipe<A, B> {
+    fn reset(&mut self) {
+        self.first.reset();
+        self.second.reset();
+        self.count = 0;
+    }
+}
+
+impl<A: Warmup, B: Warmup> Warmup for Pipe<A, B> {
+    fn warmup_period(&self) -> u32 {
+        (self.first.warmup_period() + self.second.warmup_period()).saturating_sub(1)
+    }
+
+    fn is_ready(&self) -> bool {
+        self.count >= self.warmup_period()
+    }
+}
+
+impl<A: fmt::Display, B: fmt::Display> fmt::Display for Pipe<A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.pipe({})", self.first, self.second)
+    }
+}
+
+/// Indicator with transformed output, created by
+/// [IndicatorExt::map](trait.IndicatorExt.html#method.map).
+#[derive(Clone)]
+pub struct Map<I, Func> {
+    inner: I,
+    f: Func,
+}
+
+impl<I, Func> Map<I, Func> {
+    /// Returns the inner indicator.
+    pub fn into_inner(self) -> I {
+        self.inner
+    }
+}
+
+impl<F, O, I, Func> Calculate<F> for Map<I, Func>
+where
+    I: Calculate<F>,
+    Func: FnMut(I::Output) -> O,
+{
+    type Output = O;
+
+    fn calc(&mut self, input: F) -> O {
+        (self.f)(self.inner.calc(input))
+    }
+}
+
+impl<T, O, I, Func> Next<T> for Map<I, Func>
+where
+    I: Next<T>,
+    Func: FnMut(I::Output) -> O,
+{
+    type Output = O;
+
+    fn next(&mut self, input: &T) -> O {
+        (self.f)(self.inner.next(input))
+    }
+}
+
+impl<I: Reset, Func> Reset for Map<I, Func> {
+    fn reset(&mut self) {
+        self.inner.reset();
+    }
+}
+
+impl<I: Warmup, Func> Warmup for Map<I, Func> {
+    fn warmup_period(&self) -> u32 {
+        self.inner.warmup_period()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.inner.is_ready()
+    }
+}
+
+impl<I: fmt::Debug, Func> fmt::Debug for Map<I, Func> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Map").field("inner", &self.inner).finish()
+    }
+}
+
+impl<I: fmt::Display, Func> fmt::Display for Map<I, Func> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.map(..)", self.inner)
+    }
+}
+
+/// Two indicators fed with the same input, created by
+/// [IndicatorExt::zip](trait.IndicatorExt.html#method.zip).
+#[derive(Clone)]
+pub struct Zip<A, B, Func> {
+    first: A,
+    second: B,
+    f: Func,
+}
+
+impl<A, B, Func> Zip<A, B, Func> {
+    /// Returns both indicators.
+    pub fn into_inner(self) -> (A, B) {
+        (self.first, self.second)
+    }
+}
+
+impl<F, O, A, B, Func> Calculate<F> for Zip<A, B, Func>
+where
+    F: Copy,
+    A: Calculate<F>,
+    B: Calculate<F>,
+    Func: FnMut(A::Output, B::Output) -> O,
+{
+    type Output = O;
+
+    fn calc(&mut self, input: F) -> O {
+        (self.f)(self.first.calc(input), self.second.calc(input))
+    }
+}
+
+impl<T, O, A, B, Func> Next<T> for Zip<A, B, Func>
+where
+    A: Next<T>,
+    B: Next<T>,
+    Func: FnMut(A::Output, B::Output) -> O,
+{
+    type Output = O;
+
+    fn next(&mut self, input: &T) -> O {
+        (self.f)(self.first.next(input), self.second.next(input))
+    }
+}
+
+impl<A: Reset, B: Reset, Func> Reset for Zip<A, B, Func> {
+    fn reset(&mut self) {
+        self.first.reset();
+        self.second.reset();
+    }
+}
+
+impl<A: Warmup, B: Warmup, Func> Warmup for Zip<A, B, Func> {
+    fn warmup_period(&self) -> u32 {
+        self.first.warmup_period().max(self.second.warmup_period())
+    }
+
+    fn is_ready(&self) -> bool {
+        self.first.is_ready() && self.second.is_ready()
+    }
+}
+
+impl<A: fmt::Debug, B: fmt::Debug, Func> fmt::Debug for Zip<A, B, Func> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Zip")
+            .field("first", &self.first)
+            .field("second", &self.second)
+            .finish()
+    }
+}
+
+impl<A: fmt::Display, B: fmt::Display, Func> fmt::Display for Zip<A, B, Func> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.zip({})", self.first, self.second)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{
+        ExponentialMovingAverage as Ema, FastStochastic, HeikinAshi, Maximum, Minimum,
+        SimpleMovingAverage, SlowStochastic,
+    };
+    use crate::test_helper::*;
+    use crate::Indicator;
+
+    fn bars() -> Vec<Bar> {
+        (0..40)
+            .map(|i| {
+                let close = 10.0 + (i as f64 * 0.7).sin() * 3.0;
+                Bar::new().high(close + 1.0).low(close - 0.5).close(close)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_then() {
+        let mut slow = SlowStochastic::new(14, 3).unwrap();
+        let mut composed = FastStochastic::new(14).unwrap().then(Ema::new(3).unwrap());
+        assert_eq!(composed.warmup_period(), slow.warmup_period());
+
+        for bar in bars() {
+            assert_eq!(composed.next(&bar), slow.next(&bar));
+            assert_eq!(composed.is_ready(), slow.is_ready());
+        }
+
+        let mut slow = SlowStochastic::new(5, 2).unwrap();
+        let mut composed = FastStochastic::new(5).unwrap().then(Ema::new(2).unwrap());
+        for i in 0..40 {
+            let price = (i % 7) as f64;
+            assert_eq!(composed.calc(price), slow.calc(price));
+        }
+    }
+
+    #[test]
+    fn test_pipe() {
+        let mut transform = HeikinAshi::new();
+        let mut stoch = FastStochastic::new(5).unwrap();
+        let mut composed = HeikinAshi::new().pipe(FastStochastic::new(5).unwrap());
+        assert_eq!(composed.warmup_period(), 5);
+        assert_eq!(composed.to_string(), "HA().pipe(FAST_STOCH(5))");
+
+        for bar in bars() {
+            let candle = transform.next(&bar);
+            assert_eq!(composed.next(&bar), stoch.next(&candle));
+        }
+        assert!(composed.is_ready());
+        composed.reset();
+        assert!(!composed.is_ready());
+    }
+
+    #[test]
+    fn test_map() {
+        let mut range = FastStochastic::new(3)
+            .unwrap()
+            .map(|stoch: f64| stoch > 80.0);
+        assert!(!range.calc(1.0));
+        assert!(range.calc(2.0));
+        assert!(!range.next(&Bar::new().high(3).low(1).close(1.5)));
+        assert_eq!(range.warmup_period(), 3);
+    }
+
+    #[test]
+    fn test_zip() {
+        let mut channel = Maximum::new(3)
+            .unwrap()
+            .zip(Minimum::new(3).unwrap(), |max, min| max - min);
+        assert_eq!(channel.next(&Bar::new().high(5).low(4)), 1.0);
+        assert_eq!(channel.next(&Bar::new().high(7).low(6)), 3.0);
+        assert_eq!(channel.next(&Bar::new().high(3).low(1)), 6.0);
+
+        let mut ratio = SimpleMovingAverage::new(1)
+            .unwrap()
+            .zip(SimpleMovingAverage::new(4).unwrap(), |a, b| a / b);
+        assert_eq!(ratio.calc(2.0), 1.0);
+        assert_eq!(ratio.calc(4.0), 4.0 / 3.0);
+        assert_eq!(ratio.warmup_period(), 4);
+        assert!(!ratio.is_ready());
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut composed = SimpleMovingAverage::new(2)
+            .unwrap()
+            .then(Ema::new(2).unwrap())
+            .map(|value: f64| value * 2.0);
+        composed.calc(4.0);
+        composed.calc(8.0);
+        assert!(!composed.is_ready());
+        composed.calc(6.0);
+        assert!(composed.is_ready());
+
+        composed.reset();
+        assert!(!composed.is_ready());
+        assert_eq!(composed.calc(1.0), 2.0);
+    }
+
+    #[test]
+    fn test_boxed() {
+        let composed = FastStochastic::new(14)
+            .unwrap()
+            .then(Ema::new(3).unwrap())
+            .zip(SlowStochastic::new(14, 3).unwrap(), |a, b| a - b);
+        let mut boxed: Box<dyn Indicator<Bar, Output = f64>> = Box::new(composed);
+        for bar in bars() {
+            assert_eq!(boxed.update(&bar), 0.0);
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        let composed = SimpleMovingAverage::new(9)
+            .unwrap()
+            .zip(Ema::new(9).unwrap(), |a: f64, b: f64| a.max(b))
+            .then(Ema::new(3).unwrap())
+            .map(|value: f64| value.round());
+        assert_eq!(
+            composed.to_string(),
+            "SMA(9).zip(EMA(9)).then(EMA(3)).map(..)"
+        );
+    }
+}
@@ -0,0 +1,320 @@
+//! Consensus of many signals.
+//!
+//! [Consensus](struct.Consensus.html) summarizes the [votes](enum.Vote.html) of many signals,
+//! e.g. the trend direction of moving averages and oscillators, into the fractions of
+//! bullish and bearish votes and an overall [Stance](enum.Stance.html) from strong sell to
+//! strong buy.
+//!
+//! # Example
+//!
+//! ```
+//! use ta::consensus::{Consensus, Stance, Vote};
+//! use ta::flips::Flip;
+//! use ta::Next;
+//!
+//! let mut consensus = Consensus::default();
+//! let votes = [
+//!     Vote::from(true),
+//!     Vote::from(Flip::Bullish),
+//!     Vote::from_sign(0.5),
+//!     Vote::Neutral,
+//! ];
+//! let out = consensus.next(&votes);
+//! assert_eq!(out.bullish, 0.75);
+//! assert_eq!(out.bearish, 0.0);
+//! assert_eq!(out.stance, Stance::StrongBuy);
+//! ```
+
+use core::fmt;
+
+use crate::errors::*;
+use crate::flips::Flip;
+use crate::{Finite, Float, Next, Reset, Warmup};
+
+/// Opinion of a single signal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Vote {
+    Bullish,
+    Bearish,
+    Neutral,
+}
+
+impl Vote {
+    /// Bullish for positive values, bearish for negative ones, neutral otherwise.
+    pub fn from_sign<F: Float>(value: F) -> Self {
+        if value > F::zero() {
+            Vote::Bullish
+        } else if value < F::zero() {
+            Vote::Bearish
+        } else {
+            Vote::Neutral
+        }
+    }
+}
+
+impl From<bool> for Vote {
+    fn from(bullish: bool) -> Self {
+        if bullish {
+            Vote::Bullish
+        } else {
+            Vote::Bearish
+        }
+    }
+}
+
+impl From<Flip> for Vote {
+    fn from(flip: Flip) -> Self {
+        match flip {
+            Flip::Bullish => Vote::Bullish,
+            Flip::Bearish => Vote::Bearish,
+        }
+    }
+}
+
+impl From<Option<Flip>> for Vote {
+    fn from(flip: Option<Flip>) -> Self {
+        flip.map_or(Vote::Neutral, Vote::from)
+    }
+}
+
+/// Overall stance of a [Consensus](struct.Consensus.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Stance {
+    StrongSell,
+    Sell,
+    Neutral,
+    Buy,
+    StrongBuy,
+}
+
+impl fmt::Display for Stance {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Stance::StrongSell => "strong sell",
+            Stance::Sell => "sell",
+            Stance::Neutral => "neutral",
+            Stance::Buy => "buy",
+            Stance::StrongBuy => "strong buy",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Breadth of many signals.
+///
+/// Consumes the votes of all signals for a period, as any slice-like collection of
+/// [Vote](enum.Vote.html)s, and returns the fraction of bullish and bearish votes among all
+/// of them. Their difference is the _score_ between -1 and 1, which determines the stance:
+/// buy above _threshold_, strong buy above _strong_threshold_ and the other way round for
+/// sell. Without votes the stance is neutral.
+///
+/// The consensus keeps no state between periods.
+///
+/// # Parameters
+///
+/// * _threshold_ - score above which the stance is buy (between 0 and 1). Default is 0.1.
+/// * _strong_threshold_ - score above which the stance is strong buy (between _threshold_
+///   and 1). Default is 0.5.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Consensus<F = f64> {
+    threshold: F,
+    strong_threshold: F,
+}
+
+/// Output of [Consensus](struct.Consensus.html).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConsensusOutput<F = f64> {
+    /// Fraction of bullish votes.
+    pub bullish: F,
+    /// Fraction of bearish votes.
+    pub bearish: F,
+    /// Bullish minus bearish fraction.
+    pub score: F,
+    pub stance: Stance,
+}
+
+impl<F: Finite> Finite for ConsensusOutput<F> {
+    fn is_finite(&self) -> bool {
+        self.bullish.is_finite() && self.bearish.is_finite() && self.score.is_finite()
+    }
+}
+
+impl Consensus {
+    pub fn new(threshold: f64, strong_threshold: f64) -> Result<Self> {
+        Self::with_type(threshold, strong_threshold)
+    }
+}
+
+impl<F: Float> Consensus<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(threshold: F, strong_threshold: F) -> Result<Self> {
+        if threshold < F::zero() || strong_threshold < threshold || strong_threshold > F::one() {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        Ok(Self {
+            threshold,
+            strong_threshold,
+        })
+    }
+
+    pub fn threshold(&self) -> F {
+        self.threshold
+    }
+
+    pub fn strong_threshold(&self) -> F {
+        self.strong_threshold
+    }
+
+    /// Stance of a score.
+    pub fn stance(&self, score: F) -> Stance {
+        if score > self.strong_threshold {
+            Stance::StrongBuy
+        } else if score > self.threshold {
+            Stance::Buy
+        } else if score < -self.strong_threshold {
+            Stance::StrongSell
+        } else if score < -self.threshold {
+            Stance::Sell
+        } else {
+            Stance::Neutral
+        }
+    }
+}
+
+impl<F: Float, V: AsRef<[Vote]>> Next<V> for Consensus<F> {
+    type Output = ConsensusOutput<F>;
+
+    fn next(&mut self, input: &V) -> Self::Output {
+        let votes = input.as_ref();
+        let count = |vote| votes.iter().filter(|&&v| v == vote).count();
+        let (bullish, bearish) = if votes.is_empty() {
+            (F::zero(), F::zero())
+        } else {
+            let total = F::cast(votes.len());
+            (
+                F::cast(count(Vote::Bullish)) / total,
+                F::cast(count(Vote::Bearish)) / total,
+            )
+        };
+        let score = bullish - bearish;
+        ConsensusOutput {
+            bullish,
+            bearish,
+            score,
+            stance: self.stance(score),
+        }
+    }
+}
+
+impl<F: Float> Reset for Consensus<F> {
+    fn reset(&mut self) {}
+}
+
+impl<F: Float> Warmup for Consensus<F> {
+    fn warmup_period(&self) -> u32 {
+        0
+    }
+
+    fn is_ready(&self) -> bool {
+        true
+    }
+}
+
+impl Default for Consensus {
+    fn default() -> Self {
+        Self::new(0.1, 0.5).unwrap()
+    }
+}
+
+impl<F: Float> fmt::Display for Consensus<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "CONSENSUS({}, {})",
+            self.threshold, self.strong_threshold
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{ExponentialMovingAverage, SimpleMovingAverage};
+    use crate::test_helper::*;
+    use crate::Calculate;
+
+    #[test]
+    fn test_new() {
+        assert!(Consensus::new(-0.1, 0.5).is_err());
+        assert!(Consensus::new(0.6, 0.5).is_err());
+        assert!(Consensus::new(0.1, 1.5).is_err());
+        assert!(Consensus::new(0.0, 0.0).is_ok());
+        assert!(Consensus::new(0.2, 1.0).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        use self::Vote::*;
+        let mut consensus = Consensus::new(0.2, 0.6).unwrap();
+
+        let out = consensus.next(&[Bullish, Bullish, Bearish, Neutral, Bullish]);
+        assert_eq!((out.bullish, out.bearish), (0.6, 0.2));
+        assert_eq!(round(out.score), 0.4);
+        assert_eq!(out.stance, Stance::Buy);
+
+        let out = consensus.next(&vec![Bearish, Bearish, Neutral]);
+        assert_eq!(out.stance, Stance::StrongSell);
+        let out = consensus.next(&[Bearish, Neutral, Neutral, Neutral]);
+        assert_eq!(out.stance, Stance::Sell);
+        let out = consensus.next(&[Bearish, Bullish]);
+        assert_eq!((out.score, out.stance), (0.0, Stance::Neutral));
+
+        let out = consensus.next(&Vec::new());
+        assert_eq!((out.bullish, out.bearish), (0.0, 0.0));
+        assert_eq!(out.stance, Stance::Neutral);
+    }
+
+    #[test]
+    fn test_signals() {
+        let mut consensus = Consensus::default();
+        let mut smas: Vec<_> = [2, 3, 5]
+            .iter()
+            .map(|&n| SimpleMovingAverage::new(n).unwrap())
+            .collect();
+        let mut ema = ExponentialMovingAverage::new(3).unwrap();
+
+        let mut stance = Stance::Neutral;
+        for &price in &[10.0, 11.0, 12.0, 11.5, 10.0, 9.0, 8.0] {
+            let mut votes: Vec<Vote> = smas
+                .iter_mut()
+                .map(|sma| Vote::from(price > sma.calc(price)))
+                .collect();
+            votes.push(Vote::from_sign(price - ema.calc(price)));
+            stance = consensus.next(&votes).stance;
+            if price == 12.0 {
+                assert_eq!(stance, Stance::StrongBuy);
+            }
+        }
+        assert_eq!(stance, Stance::StrongSell);
+    }
+
+    #[test]
+    fn test_votes() {
+        assert_eq!(Vote::from(false), Vote::Bearish);
+        assert_eq!(Vote::from(None), Vote::Neutral);
+        assert_eq!(Vote::from(Some(Flip::Bearish)), Vote::Bearish);
+        assert_eq!(Vote::from_sign(-2.0), Vote::Bearish);
+        assert_eq!(Vote::from_sign(0.0), Vote::Neutral);
+        assert!(Stance::StrongBuy > Stance::Buy);
+        assert_eq!(Stance::StrongSell.to_string(), "strong sell");
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", Consensus::default()), "CONSENSUS(0.1, 0.5)");
+    }
+}
@@ -1,5 +1,11 @@
 use crate::errors::*;
-use crate::traits::{Close, High, Low, Open, Volume};
+use crate::traits::{
+    Aggressor, AggressorSide, AggressorVolume, Close, High, Low, Number, Open, Timestamp, Trade,
+    Volume,
+};
+
+#[cfg(feature = "decimal")]
+use rust_decimal::Decimal;
 
 /// Data item is used as an input for indicators.
 ///
@@ -25,13 +31,55 @@ use crate::traits::{Close, High, Low, Open, Volume};
 /// assert_eq!(item.volume(), 7500.0);
 /// ```
 ///
+/// Items are generic over the [Number](trait.Number.html) type of their prices and default
+/// to `f64`. Items of other types are built with [DataItemBuilder](struct.DataItemBuilder.html),
+/// e.g. with the `decimal` feature `DataItemBuilder::<Decimal>::new()` holds exact
+/// `rust_decimal::Decimal` prices. See also [to_decimal](#method.to_decimal).
+///
+/// With the `serde` feature, deserialized items go through the builder and are rejected with
+/// the same errors as [build](struct.DataItemBuilder.html#method.build).
+///
 #[derive(Debug, Clone)]
-pub struct DataItem {
-    open: f64,
-    high: f64,
-    low: f64,
-    close: f64,
-    volume: f64,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        try_from = "DataItemFields<N>",
+        bound(deserialize = "N: Number + serde::Deserialize<'de>")
+    )
+)]
+pub struct DataItem<N = f64> {
+    open: N,
+    high: N,
+    low: N,
+    close: N,
+    volume: N,
+}
+
+// Fields of a deserialized item, validated by the builder before they become an item.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct DataItemFields<N> {
+    open: N,
+    high: N,
+    low: N,
+    close: N,
+    volume: N,
+}
+
+#[cfg(feature = "serde")]
+impl<N: Number> core::convert::TryFrom<DataItemFields<N>> for DataItem<N> {
+    type Error = Error;
+
+    fn try_from(fields: DataItemFields<N>) -> Result<Self> {
+        DataItemBuilder::new()
+            .open(fields.open)
+            .high(fields.high)
+            .low(fields.low)
+            .close(fields.close)
+            .volume(fields.volume)
+            .build()
+    }
 }
 
 impl DataItem {
@@ -40,45 +88,185 @@ impl DataItem {
     }
 }
 
-impl Open for DataItem {
-    fn open(&self) -> f64 {
+impl<N: Number> DataItem<N> {
+    /// Creates an item without validation, for values derived from valid items.
+    pub(crate) fn new(open: N, high: N, low: N, close: N, volume: N) -> Self {
+        Self {
+            open,
+            high,
+            low,
+            close,
+            volume,
+        }
+    }
+}
+
+#[cfg(feature = "decimal")]
+impl DataItem<f64> {
+    /// Converts the prices and the volume to decimals.
+    ///
+    /// Every value is rounded to the shortest decimal representation of the float, so
+    /// e.g. `0.1` becomes exactly `0.1`. Fails with `DataItemInvalid` for values which can't
+    /// be represented (NaN, infinite or out of the `Decimal` range).
+    ///
+    /// ```
+    /// use rust_decimal::Decimal;
+    /// use ta::{Close, DataItem};
+    ///
+    /// let item = DataItem::builder()
+    ///     .open(0.1)
+    ///     .high(0.3)
+    ///     .low(0.1)
+    ///     .close(0.2)
+    ///     .volume(10.0)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let item = item.to_decimal().unwrap();
+    /// assert_eq!(item.close(), Decimal::new(2, 1));
+    /// ```
+    pub fn to_decimal(&self) -> Result<DataItem<Decimal>> {
+        use num_traits::FromPrimitive;
+
+        let convert = |value: f64| {
+            Decimal::from_f64(value).ok_or_else(|| Error::from_kind(ErrorKind::DataItemInvalid))
+        };
+        Ok(DataItem::new(
+            convert(self.open)?,
+            convert(self.high)?,
+            convert(self.low)?,
+            convert(self.close)?,
+            convert(self.volume)?,
+        ))
+    }
+}
+
+#[cfg(feature = "decimal")]
+impl DataItem<Decimal> {
+    /// Converts the prices and the volume to the nearest floats.
+    pub fn to_f64(&self) -> DataItem<f64> {
+        use num_traits::ToPrimitive;
+
+        let convert = |value: Decimal| value.to_f64().unwrap();
+        DataItem::new(
+            convert(self.open),
+            convert(self.high),
+            convert(self.low),
+            convert(self.close),
+            convert(self.volume),
+        )
+    }
+}
+
+impl<N: Number> Open<N> for DataItem<N> {
+    fn open(&self) -> N {
         self.open
     }
 }
 
-impl High for DataItem {
-    fn high(&self) -> f64 {
+impl<N: Number> High<N> for DataItem<N> {
+    fn high(&self) -> N {
         self.high
     }
 }
 
-impl Low for DataItem {
-    fn low(&self) -> f64 {
+impl<N: Number> Low<N> for DataItem<N> {
+    fn low(&self) -> N {
         self.low
     }
 }
 
-impl Close for DataItem {
-    fn close(&self) -> f64 {
+impl<N: Number> Close<N> for DataItem<N> {
+    fn close(&self) -> N {
         self.close
     }
 }
 
-impl Volume for DataItem {
-    fn volume(&self) -> f64 {
+impl<N: Number> Volume<N> for DataItem<N> {
+    fn volume(&self) -> N {
         self.volume
     }
 }
 
-pub struct DataItemBuilder {
-    open: Option<f64>,
-    high: Option<f64>,
-    low: Option<f64>,
-    close: Option<f64>,
-    volume: Option<f64>,
+// Plain `(open, high, low, close, volume)` tuples and `[open, high, low, close, volume]`
+// arrays are bars as well, for scripts and tests which don't need a dedicated type.
+
+impl<N: Number> Open<N> for (N, N, N, N, N) {
+    fn open(&self) -> N {
+        self.0
+    }
+}
+
+impl<N: Number> High<N> for (N, N, N, N, N) {
+    fn high(&self) -> N {
+        self.1
+    }
+}
+
+impl<N: Number> Low<N> for (N, N, N, N, N) {
+    fn low(&self) -> N {
+        self.2
+    }
+}
+
+impl<N: Number> Close<N> for (N, N, N, N, N) {
+    fn close(&self) -> N {
+        self.3
+    }
+}
+
+impl<N: Number> Volume<N> for (N, N, N, N, N) {
+    fn volume(&self) -> N {
+        self.4
+    }
+}
+
+impl<N: Number> Open<N> for [N; 5] {
+    fn open(&self) -> N {
+        self[0]
+    }
+}
+
+impl<N: Number> High<N> for [N; 5] {
+    fn high(&self) -> N {
+        self[1]
+    }
+}
+
+impl<N: Number> Low<N> for [N; 5] {
+    fn low(&self) -> N {
+        self[2]
+    }
+}
+
+impl<N: Number> Close<N> for [N; 5] {
+    fn close(&self) -> N {
+        self[3]
+    }
 }
 
-impl DataItemBuilder {
+impl<N: Number> Volume<N> for [N; 5] {
+    fn volume(&self) -> N {
+        self[4]
+    }
+}
+
+/// Builder validating a [DataItem](struct.DataItem.html).
+pub struct DataItemBuilder<N = f64> {
+    open: Option<N>,
+    high: Option<N>,
+    low: Option<N>,
+    close: Option<N>,
+    volume: Option<N>,
+}
+
+impl<N: Number> Default for DataItemBuilder<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N: Number> DataItemBuilder<N> {
     pub fn new() -> Self {
         Self {
             open: None,
@@ -89,57 +277,152 @@ impl DataItemBuilder {
         }
     }
 
-    pub fn open(mut self, val: f64) -> Self {
+    pub fn open(mut self, val: N) -> Self {
         self.open = Some(val);
         self
     }
 
-    pub fn high(mut self, val: f64) -> Self {
+    pub fn high(mut self, val: N) -> Self {
         self.high = Some(val);
         self
     }
 
-    pub fn low(mut self, val: f64) -> Self {
+    pub fn low(mut self, val: N) -> Self {
         self.low = Some(val);
         self
     }
 
-    pub fn close(mut self, val: f64) -> Self {
+    pub fn close(mut self, val: N) -> Self {
         self.close = Some(val);
         self
     }
 
-    pub fn volume(mut self, val: f64) -> Self {
+    pub fn volume(mut self, val: N) -> Self {
         self.volume = Some(val);
         self
     }
 
-    pub fn build(self) -> Result<DataItem> {
-        if let (Some(open), Some(high), Some(low), Some(close), Some(volume)) =
-            (self.open, self.high, self.low, self.close, self.volume)
+    /// Builds the item, rejecting bars which can't occur:
+    ///
+    /// * `DataItemIncomplete` - a value is missing
+    /// * `DataItemInvalid` - a value is NaN or infinite
+    /// * `HighBelowLow` - high is below low
+    /// * `OpenOutOfRange`, `CloseOutOfRange` - open or close is outside of [low, high]
+    /// * `NegativePrice` - low is negative
+    /// * `NegativeVolume` - volume is negative
+    ///
+    /// ```
+    /// use ta::errors::ErrorKind;
+    /// use ta::DataItem;
+    ///
+    /// let result = DataItem::builder()
+    ///     .open(20.0)
+    ///     .high(25.0)
+    ///     .low(15.0)
+    ///     .close(26.0)
+    ///     .volume(7500.0)
+    ///     .build();
+    ///
+    /// match result {
+    ///     Err(err) => assert!(matches!(err.kind(), ErrorKind::CloseOutOfRange)),
+    ///     Ok(_) => panic!("close above high must be rejected"),
+    /// }
+    /// ```
+    pub fn build(self) -> Result<DataItem<N>> {
+        let (open, high, low, close, volume) =
+            match (self.open, self.high, self.low, self.close, self.volume) {
+                (Some(open), Some(high), Some(low), Some(close), Some(volume)) => {
+                    (open, high, low, close, volume)
+                }
+                _ => return Err(Error::from_kind(ErrorKind::DataItemIncomplete)),
+            };
+
+        let kind = if [open, high, low, close, volume]
+            .iter()
+            .any(|value| !value.to_f64().is_some_and(f64::is_finite))
         {
-            // validate
-            if low <= open
-                && low <= close
-                && low <= high
-                && high >= open
-                && high >= close
-                && volume >= 0.0
-                && low >= 0.0
-            {
-                let item = DataItem {
-                    open,
-                    high,
-                    low,
-                    close,
-                    volume,
-                };
-                Ok(item)
-            } else {
-                Err(Error::from_kind(ErrorKind::DataItemInvalid))
-            }
+            Some(ErrorKind::DataItemInvalid)
+        } else if high < low {
+            Some(ErrorKind::HighBelowLow)
+        } else if open < low || open > high {
+            Some(ErrorKind::OpenOutOfRange)
+        } else if close < low || close > high {
+            Some(ErrorKind::CloseOutOfRange)
+        } else if low < N::zero() {
+            Some(ErrorKind::NegativePrice)
+        } else if volume < N::zero() {
+            Some(ErrorKind::NegativeVolume)
         } else {
-            Err(Error::from_kind(ErrorKind::DataItemIncomplete))
+            None
+        };
+
+        match kind {
+            Some(kind) => Err(Error::from_kind(kind)),
+            None => Ok(DataItem {
+                open,
+                high,
+                low,
+                close,
+                volume,
+            }),
+        }
+    }
+}
+
+// `(timestamp, price, size)` tuples are trades.
+
+impl<N: Number> Timestamp for (i64, N, N) {
+    fn timestamp(&self) -> i64 {
+        self.0
+    }
+}
+
+impl<N: Number> Trade<N> for (i64, N, N) {
+    fn price(&self) -> N {
+        self.1
+    }
+
+    fn size(&self) -> N {
+        self.2
+    }
+}
+
+// `(timestamp, price, size, side)` tuples are trades with their aggressor.
+
+impl<N: Number> Timestamp for (i64, N, N, AggressorSide) {
+    fn timestamp(&self) -> i64 {
+        self.0
+    }
+}
+
+impl<N: Number> Trade<N> for (i64, N, N, AggressorSide) {
+    fn price(&self) -> N {
+        self.1
+    }
+
+    fn size(&self) -> N {
+        self.2
+    }
+}
+
+impl<N: Number> Aggressor for (i64, N, N, AggressorSide) {
+    fn side(&self) -> AggressorSide {
+        self.3
+    }
+}
+
+impl<N: Number> AggressorVolume<N> for (i64, N, N, AggressorSide) {
+    fn buy_volume(&self) -> N {
+        match self.3 {
+            AggressorSide::Buy => self.2,
+            AggressorSide::Sell => N::zero(),
+        }
+    }
+
+    fn sell_volume(&self) -> N {
+        match self.3 {
+            AggressorSide::Buy => N::zero(),
+            AggressorSide::Sell => self.2,
         }
     }
 }
@@ -199,4 +482,145 @@ mod tests {
             assert_invalid(record)
         }
     }
+
+    #[test]
+    fn test_build_errors() {
+        fn kind((open, high, low, close, volume): (f64, f64, f64, f64, f64)) -> ErrorKind {
+            let result = DataItem::builder()
+                .open(open)
+                .high(high)
+                .low(low)
+                .close(close)
+                .volume(volume)
+                .build();
+            match result {
+                Err(err) => err.0,
+                Ok(_) => panic!("bar must be rejected"),
+            }
+        }
+
+        assert!(matches!(
+            kind((20.0, 15.0, 25.0, 21.0, 7500.0)),
+            ErrorKind::HighBelowLow
+        ));
+        assert!(matches!(
+            kind((25.1, 25.0, 15.0, 21.0, 7500.0)),
+            ErrorKind::OpenOutOfRange
+        ));
+        assert!(matches!(
+            kind((20.0, 25.0, 15.0, 14.9, 7500.0)),
+            ErrorKind::CloseOutOfRange
+        ));
+        assert!(matches!(
+            kind((0.0, 1.0, -1.0, 0.0, 7500.0)),
+            ErrorKind::NegativePrice
+        ));
+        assert!(matches!(
+            kind((20.0, 25.0, 15.0, 21.0, -1.0)),
+            ErrorKind::NegativeVolume
+        ));
+        assert!(matches!(
+            kind((20.0, f64::NAN, 15.0, 21.0, 7500.0)),
+            ErrorKind::DataItemInvalid
+        ));
+        assert!(matches!(
+            kind((20.0, f64::INFINITY, 15.0, 21.0, 7500.0)),
+            ErrorKind::DataItemInvalid
+        ));
+        assert!(matches!(
+            kind((20.0, 25.0, 15.0, 21.0, f64::INFINITY)),
+            ErrorKind::DataItemInvalid
+        ));
+        assert!(matches!(
+            kind((20.0, 25.0, f64::NEG_INFINITY, 21.0, 7500.0)),
+            ErrorKind::DataItemInvalid
+        ));
+
+        let result = DataItem::builder().open(1.0).high(1.0).low(1.0).build();
+        assert!(matches!(
+            result.map_err(|err| err.0),
+            Err(ErrorKind::DataItemIncomplete)
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let item: DataItem =
+            serde_json::from_str(r#"{"open":20,"high":25,"low":15,"close":21,"volume":7500}"#)
+                .unwrap();
+        assert_eq!(item.close(), 21.0);
+        assert_eq!(
+            serde_json::to_string(&item).unwrap(),
+            r#"{"open":20.0,"high":25.0,"low":15.0,"close":21.0,"volume":7500.0}"#
+        );
+
+        let result = serde_json::from_str::<DataItem>(
+            r#"{"open":20,"high":25,"low":15,"close":26,"volume":7500}"#,
+        );
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains(&ErrorKind::CloseOutOfRange.to_string()));
+        assert!(serde_json::from_str::<DataItem>(
+            r#"{"open":20,"high":15,"low":25,"close":21,"volume":7500}"#
+        )
+        .is_err());
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn test_decimal() {
+        let item = DataItem::builder()
+            .open(1.1)
+            .high(1.5)
+            .low(0.9)
+            .close(1.2)
+            .volume(100.0)
+            .build()
+            .unwrap();
+
+        let decimal = item.to_decimal().unwrap();
+        assert_eq!(decimal.open(), Decimal::new(11, 1));
+        assert_eq!(decimal.high(), Decimal::new(15, 1));
+        assert_eq!(decimal.low(), Decimal::new(9, 1));
+        assert_eq!(decimal.close(), Decimal::new(12, 1));
+        assert_eq!(decimal.volume(), Decimal::new(100, 0));
+
+        let item = decimal.to_f64();
+        assert_eq!(item.close(), 1.2);
+        assert!(DataItem::new(f64::NAN, 1.0, 1.0, 1.0, 1.0)
+            .to_decimal()
+            .is_err());
+
+        let built = DataItemBuilder::new()
+            .open(Decimal::new(11, 1))
+            .high(Decimal::new(15, 1))
+            .low(Decimal::new(16, 1))
+            .close(Decimal::new(12, 1))
+            .volume(Decimal::new(100, 0))
+            .build();
+        assert!(built.is_err());
+    }
+
+    #[test]
+    fn test_tuple_and_array() {
+        use crate::indicators::{AverageTrueRange, SimpleMovingAverage};
+        use crate::Next;
+
+        let bar = (10.0, 12.0, 9.0, 11.0, 100.0);
+        assert_eq!((bar.open(), bar.high(), bar.low()), (10.0, 12.0, 9.0));
+        assert_eq!((bar.close(), bar.volume()), (11.0, 100.0));
+
+        let bar = [10.0_f32, 12.0, 9.0, 11.0, 100.0];
+        assert_eq!((bar.open(), bar.high(), bar.low()), (10.0, 12.0, 9.0));
+        assert_eq!((bar.close(), bar.volume()), (11.0, 100.0));
+
+        let mut sma = SimpleMovingAverage::new(2).unwrap();
+        sma.next(&(10.0, 12.0, 9.0, 11.0, 100.0));
+        assert_eq!(sma.next(&[11.0, 14.0, 11.0, 13.0, 80.0]), 12.0);
+
+        let mut atr = AverageTrueRange::new(3).unwrap();
+        assert_eq!(atr.next(&[10.0, 12.0, 9.0, 11.0, 100.0]), 3.0);
+    }
 }
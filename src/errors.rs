@@ -1,7 +1,166 @@
+#[cfg(feature = "std")]
 error_chain! {
     errors {
         InvalidParameter { description("invalid parameter") }
         DataItemIncomplete { description("data item is incomplete") }
         DataItemInvalid { description("data item is invalid") }
+        HighBelowLow { description("high is below low") }
+        OpenOutOfRange { description("open is outside of the low-high range") }
+        CloseOutOfRange { description("close is outside of the low-high range") }
+        NegativePrice { description("price is negative") }
+        NegativeVolume { description("volume is negative") }
+        NotReady { description("indicator is not ready") }
+        NonFiniteInput { description("input is not finite") }
+        NonFiniteOutput { description("output is not finite") }
+        UnknownIndicator(name: String) {
+            description("unknown indicator")
+            display("unknown indicator: '{}'", name)
+        }
+        InvalidSpec(spec: String) {
+            description("invalid indicator spec")
+            display("invalid indicator spec: '{}'", spec)
+        }
+        SinkFailed(reason: String) {
+            description("sink failed")
+            display("sink failed: {}", reason)
+        }
+        MetricsFailed(reason: String) {
+            description("metrics failed")
+            display("metrics failed: {}", reason)
+        }
+        AuditFailed(reason: String) {
+            description("audit log failed")
+            display("audit log failed: {}", reason)
+        }
+        ReplayMismatch(period: u64) {
+            description("replay diverged from the audit log")
+            display("replay diverged from the audit log at period {}", period)
+        }
+        ReadFailed(reason: String) {
+            description("reading history failed")
+            display("reading history failed: {}", reason)
+        }
+        SnapshotFailed(reason: String) {
+            description("snapshot failed")
+            display("snapshot failed: {}", reason)
+        }
+        MissingBars(count: u32) {
+            description("bars are missing")
+            display("{} bars are missing", count)
+        }
     }
 }
+
+#[cfg(not(feature = "std"))]
+pub use self::no_std::*;
+
+// error_chain needs std, these mirror the parts of its API the crate uses
+#[cfg(not(feature = "std"))]
+mod no_std {
+    use alloc::string::String;
+    use core::fmt;
+
+    #[derive(Debug)]
+    pub enum ErrorKind {
+        InvalidParameter,
+        DataItemIncomplete,
+        DataItemInvalid,
+        HighBelowLow,
+        OpenOutOfRange,
+        CloseOutOfRange,
+        NegativePrice,
+        NegativeVolume,
+        NotReady,
+        NonFiniteInput,
+        NonFiniteOutput,
+        UnknownIndicator(String),
+        InvalidSpec(String),
+        SinkFailed(String),
+        MetricsFailed(String),
+        AuditFailed(String),
+        ReplayMismatch(u64),
+        ReadFailed(String),
+        SnapshotFailed(String),
+        MissingBars(u32),
+    }
+
+    impl ErrorKind {
+        pub fn description(&self) -> &str {
+            match self {
+                ErrorKind::InvalidParameter => "invalid parameter",
+                ErrorKind::DataItemIncomplete => "data item is incomplete",
+                ErrorKind::DataItemInvalid => "data item is invalid",
+                ErrorKind::HighBelowLow => "high is below low",
+                ErrorKind::OpenOutOfRange => "open is outside of the low-high range",
+                ErrorKind::CloseOutOfRange => "close is outside of the low-high range",
+                ErrorKind::NegativePrice => "price is negative",
+                ErrorKind::NegativeVolume => "volume is negative",
+                ErrorKind::NotReady => "indicator is not ready",
+                ErrorKind::NonFiniteInput => "input is not finite",
+                ErrorKind::NonFiniteOutput => "output is not finite",
+                ErrorKind::UnknownIndicator(_) => "unknown indicator",
+                ErrorKind::InvalidSpec(_) => "invalid indicator spec",
+                ErrorKind::SinkFailed(_) => "sink failed",
+                ErrorKind::MetricsFailed(_) => "metrics failed",
+                ErrorKind::AuditFailed(_) => "audit log failed",
+                ErrorKind::ReplayMismatch(_) => "replay diverged from the audit log",
+                ErrorKind::ReadFailed(_) => "reading history failed",
+                ErrorKind::SnapshotFailed(_) => "snapshot failed",
+                ErrorKind::MissingBars(_) => "bars are missing",
+            }
+        }
+    }
+
+    impl fmt::Display for ErrorKind {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                ErrorKind::UnknownIndicator(name) => write!(f, "unknown indicator: '{}'", name),
+                ErrorKind::InvalidSpec(spec) => write!(f, "invalid indicator spec: '{}'", spec),
+                ErrorKind::SinkFailed(reason) => write!(f, "sink failed: {}", reason),
+                ErrorKind::MetricsFailed(reason) => write!(f, "metrics failed: {}", reason),
+                ErrorKind::AuditFailed(reason) => write!(f, "audit log failed: {}", reason),
+                ErrorKind::ReplayMismatch(period) => {
+                    write!(f, "replay diverged from the audit log at period {}", period)
+                }
+                ErrorKind::ReadFailed(reason) => write!(f, "reading history failed: {}", reason),
+                ErrorKind::SnapshotFailed(reason) => write!(f, "snapshot failed: {}", reason),
+                ErrorKind::MissingBars(count) => write!(f, "{} bars are missing", count),
+                kind => write!(f, "{}", kind.description()),
+            }
+        }
+    }
+
+    /// Stands in for the backtrace error_chain keeps, so that `Error(kind, _)` patterns
+    /// work with and without std.
+    #[derive(Debug, Default)]
+    pub struct State;
+
+    #[derive(Debug)]
+    pub struct Error(pub ErrorKind, pub State);
+
+    impl Error {
+        pub fn from_kind(kind: ErrorKind) -> Self {
+            Error(kind, State)
+        }
+
+        pub fn kind(&self) -> &ErrorKind {
+            &self.0
+        }
+    }
+
+    impl From<ErrorKind> for Error {
+        fn from(kind: ErrorKind) -> Self {
+            Error::from_kind(kind)
+        }
+    }
+
+    impl fmt::Display for Error {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            self.0.fmt(f)
+        }
+    }
+
+    impl core::error::Error for Error {}
+
+    pub type Result<T> = core::result::Result<T, Error>;
+}
@@ -0,0 +1,269 @@
+//! Execution quality measurement.
+//!
+//! [ExecutionAnalyzer](struct.ExecutionAnalyzer.html) collects the fills of an order
+//! together with the market data observed while the order was working, and compares the
+//! average fill price against the usual benchmarks: arrival price, interval VWAP, interval
+//! TWAP and closing price.
+//!
+//! Slippage is reported in basis points with a positive value meaning a cost, i.e. buying
+//! above or selling below the benchmark.
+//!
+//! # Example
+//!
+//! ```
+//! use ta::execution::{ExecutionAnalyzer, Fill, Side};
+//!
+//! let mut analyzer = ExecutionAnalyzer::new(Side::Buy, 200.0).unwrap();
+//! analyzer.market(0, 100.0, 1_000.0);
+//! analyzer.fill(Fill { timestamp: 0, price: 100.1, quantity: 100.0 });
+//! analyzer.market(60_000, 101.0, 1_000.0);
+//! analyzer.fill(Fill { timestamp: 60_000, price: 101.1, quantity: 100.0 });
+//!
+//! let report = analyzer.report().unwrap();
+//! assert_eq!(report.average_price, 100.6);
+//! assert_eq!(report.arrival_price, 100.0);
+//! assert_eq!(report.arrival_slippage.round(), 60.0);
+//! assert_eq!(report.vwap_slippage.unwrap().round(), 10.0);
+//! ```
+
+use crate::errors::*;
+use crate::indicators::TimeWeightedAveragePrice;
+use crate::{Next, Reset};
+
+/// Direction of the order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Buy,
+    Sell,
+}
+
+impl Side {
+    fn sign(self) -> f64 {
+        match self {
+            Side::Buy => 1.0,
+            Side::Sell => -1.0,
+        }
+    }
+}
+
+/// Partial or full execution of an order.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fill {
+    pub timestamp: i64,
+    pub price: f64,
+    pub quantity: f64,
+}
+
+/// Execution quality summary.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExecutionReport {
+    /// Volume-weighted average fill price.
+    pub average_price: f64,
+    /// Total filled quantity.
+    pub filled_quantity: f64,
+    /// Price at the moment the order was placed.
+    pub arrival_price: f64,
+    /// Slippage against the arrival price, in basis points.
+    pub arrival_slippage: f64,
+    /// Slippage against the interval VWAP, in basis points.
+    pub vwap_slippage: Option<f64>,
+    /// Slippage against the interval TWAP, in basis points.
+    pub twap_slippage: Option<f64>,
+    /// Slippage against the last observed (closing) price, in basis points.
+    pub close_slippage: Option<f64>,
+    /// Implementation shortfall in currency: execution cost of the filled quantity against
+    /// the arrival price plus the opportunity cost of the unfilled quantity.
+    pub implementation_shortfall: f64,
+}
+
+/// Collects fills and market data of a single order.
+///
+/// The arrival price is the first market price passed to [market](#method.market), unless
+/// set explicitly with [arrival_price](#method.arrival_price).
+#[derive(Debug, Clone)]
+pub struct ExecutionAnalyzer {
+    side: Side,
+    target_quantity: f64,
+    arrival: Option<f64>,
+    last_price: Option<f64>,
+    market_value: f64,
+    market_volume: f64,
+    twap: TimeWeightedAveragePrice,
+    twap_value: Option<f64>,
+    fill_value: f64,
+    fill_quantity: f64,
+}
+
+impl ExecutionAnalyzer {
+    /// Creates an analyzer for an order of _target_quantity_ (greater than 0).
+    pub fn new(side: Side, target_quantity: f64) -> Result<Self> {
+        if target_quantity <= 0.0 {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        Ok(Self {
+            side,
+            target_quantity,
+            arrival: None,
+            last_price: None,
+            market_value: 0.0,
+            market_volume: 0.0,
+            twap: TimeWeightedAveragePrice::anchored(),
+            twap_value: None,
+            fill_value: 0.0,
+            fill_quantity: 0.0,
+        })
+    }
+
+    /// Sets the arrival price explicitly.
+    pub fn arrival_price(mut self, price: f64) -> Self {
+        self.arrival = Some(price);
+        self
+    }
+
+    /// Records a market trade or bar observed while the order was working.
+    pub fn market(&mut self, timestamp: i64, price: f64, volume: f64) {
+        if self.arrival.is_none() {
+            self.arrival = Some(price);
+        }
+        self.last_price = Some(price);
+        self.market_value += price * volume;
+        self.market_volume += volume;
+        self.twap_value = Some(self.twap.next(&(timestamp, price)));
+    }
+
+    /// Records a fill of the order.
+    pub fn fill(&mut self, fill: Fill) {
+        self.fill_value += fill.price * fill.quantity;
+        self.fill_quantity += fill.quantity;
+    }
+
+    /// Interval VWAP of the market data recorded so far.
+    pub fn vwap(&self) -> Option<f64> {
+        if self.market_volume > 0.0 {
+            Some(self.market_value / self.market_volume)
+        } else {
+            None
+        }
+    }
+
+    /// Interval TWAP of the market data recorded so far.
+    pub fn twap(&self) -> Option<f64> {
+        self.twap_value
+    }
+
+    /// Builds the report. Returns `None` if there are no fills or no arrival price.
+    pub fn report(&self) -> Option<ExecutionReport> {
+        if self.fill_quantity <= 0.0 {
+            return None;
+        }
+        let arrival = self.arrival?;
+        let average_price = self.fill_value / self.fill_quantity;
+        let slippage =
+            |benchmark: f64| self.side.sign() * (average_price - benchmark) / benchmark * 10_000.0;
+
+        let unfilled = (self.target_quantity - self.fill_quantity).max(0.0);
+        let execution_cost = self.side.sign() * (average_price - arrival) * self.fill_quantity;
+        let opportunity_cost = self
+            .last_price
+            .map(|close| self.side.sign() * (close - arrival) * unfilled)
+            .unwrap_or(0.0);
+
+        Some(ExecutionReport {
+            average_price,
+            filled_quantity: self.fill_quantity,
+            arrival_price: arrival,
+            arrival_slippage: slippage(arrival),
+            vwap_slippage: self.vwap().map(slippage),
+            twap_slippage: self.twap().map(slippage),
+            close_slippage: self.last_price.map(slippage),
+            implementation_shortfall: execution_cost + opportunity_cost,
+        })
+    }
+}
+
+impl Reset for ExecutionAnalyzer {
+    fn reset(&mut self) {
+        self.arrival = None;
+        self.last_price = None;
+        self.market_value = 0.0;
+        self.market_volume = 0.0;
+        self.twap.reset();
+        self.twap_value = None;
+        self.fill_value = 0.0;
+        self.fill_quantity = 0.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    fn fill(price: f64, quantity: f64) -> Fill {
+        Fill {
+            timestamp: 0,
+            price,
+            quantity,
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(ExecutionAnalyzer::new(Side::Buy, 0.0).is_err());
+        assert!(ExecutionAnalyzer::new(Side::Buy, 1.0).is_ok());
+    }
+
+    #[test]
+    fn test_no_fills() {
+        let mut analyzer = ExecutionAnalyzer::new(Side::Buy, 10.0).unwrap();
+        analyzer.market(0, 100.0, 10.0);
+        assert_eq!(analyzer.report(), None);
+    }
+
+    #[test]
+    fn test_buy() {
+        let mut analyzer = ExecutionAnalyzer::new(Side::Buy, 100.0).unwrap();
+        analyzer.market(0, 100.0, 300.0);
+        analyzer.market(1_000, 102.0, 100.0);
+        analyzer.market(3_000, 104.0, 100.0);
+        analyzer.fill(fill(101.0, 50.0));
+
+        assert_eq!(analyzer.vwap(), Some(101.2));
+        assert_eq!(analyzer.twap(), Some((100.0 + 2.0 * 102.0) / 3.0));
+
+        let report = analyzer.report().unwrap();
+        assert_eq!(report.average_price, 101.0);
+        assert_eq!(report.filled_quantity, 50.0);
+        assert_eq!(round(report.arrival_slippage), 100.0);
+        assert_eq!(round(report.vwap_slippage.unwrap()), -19.763);
+        assert_eq!(round(report.close_slippage.unwrap()), -288.462);
+        // 1.0 * 50 paid on fills, 4.0 * 50 missed on the unfilled quantity
+        assert_eq!(report.implementation_shortfall, 250.0);
+    }
+
+    #[test]
+    fn test_sell() {
+        let mut analyzer = ExecutionAnalyzer::new(Side::Sell, 10.0)
+            .unwrap()
+            .arrival_price(50.0);
+        analyzer.market(0, 49.0, 1.0);
+        analyzer.fill(fill(49.0, 10.0));
+
+        let report = analyzer.report().unwrap();
+        assert_eq!(report.arrival_price, 50.0);
+        assert_eq!(report.arrival_slippage, 200.0);
+        assert_eq!(report.vwap_slippage, Some(0.0));
+        assert_eq!(report.implementation_shortfall, 10.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut analyzer = ExecutionAnalyzer::new(Side::Buy, 10.0).unwrap();
+        analyzer.market(0, 100.0, 10.0);
+        analyzer.fill(fill(100.0, 10.0));
+
+        analyzer.reset();
+        assert_eq!(analyzer.report(), None);
+        assert_eq!(analyzer.vwap(), None);
+    }
+}
@@ -0,0 +1,389 @@
+//! Trend flip events of directional indicators.
+//!
+//! [VortexFlips](struct.VortexFlips.html) and [DmiFlips](struct.DmiFlips.html) report a
+//! [Flip](enum.Flip.html) whenever the positive line of the
+//! [Vortex Indicator](../indicators/struct.VortexIndicator.html) (+VI/−VI) or of the
+//! [Directional Movement Index](../indicators/struct.DirectionalMovementIndex.html)
+//! (+DI/−DI) crosses the negative one. The lines of both indicators often tangle in
+//! sideways markets, so the crossovers can be [debounced](struct.Debounce.html).
+//!
+//! The same is available for any two lines, or a line and a constant level, with
+//! [Cross](struct.Cross.html). [crossed_over](fn.crossed_over.html) and
+//! [crossed_under](fn.crossed_under.html) check a single pair of periods.
+//!
+//! # Example
+//!
+//! ```
+//! use ta::flips::{Debounce, Flip, VortexFlips};
+//! use ta::{DataItem, Next};
+//!
+//! let bar = |high: f64, low: f64| {
+//!     DataItem::builder()
+//!         .open(low)
+//!         .high(high)
+//!         .low(low)
+//!         .close(low)
+//!         .volume(0.0)
+//!         .build()
+//!         .unwrap()
+//! };
+//!
+//! let mut flips = VortexFlips::new(3, Debounce::default()).unwrap();
+//! for i in 0..5 {
+//!     // the trend direction is established, but nothing has flipped yet
+//!     assert_eq!(flips.next(&bar(12.0 + i as f64, 10.0 + i as f64)), None);
+//! }
+//! assert_eq!(flips.next(&bar(14.0, 12.0)), None);
+//! assert_eq!(flips.next(&bar(13.0, 11.0)), Some(Flip::Bearish));
+//! ```
+
+use crate::errors::*;
+use crate::indicators::{DirectionalMovementIndex, VortexIndicator};
+use crate::{Close, Float, High, Low, Next, Reset};
+
+/// Direction of a trend flip.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Flip {
+    /// The positive line crossed above the negative line.
+    Bullish,
+    /// The negative line crossed above the positive line.
+    Bearish,
+}
+
+/// Filter of short-lived crossovers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Debounce<F = f64> {
+    /// Number of consecutive bars the lines have to stay crossed before the flip is
+    /// reported (integer greater than 0). 1 reports the flip on the crossing bar.
+    pub confirm: u32,
+    /// Minimal distance between the lines for a bar to count as crossed (not negative).
+    pub margin: F,
+}
+
+impl<F: Float> Default for Debounce<F> {
+    fn default() -> Self {
+        Self {
+            confirm: 1,
+            margin: F::zero(),
+        }
+    }
+}
+
+/// Tracks which of two lines is above the other and reports debounced crosses.
+///
+/// The lines can be two indicators, e.g. a fast and a slow moving average, or an indicator
+/// and a constant level, e.g. 80 for a stochastic. The first line crossing above the second
+/// is [bullish](enum.Flip.html), crossing below it is bearish.
+///
+/// # Example
+///
+/// ```
+/// use ta::flips::{Cross, Flip};
+///
+/// let mut overbought = Cross::default();
+/// assert_eq!(overbought.next(75.0, 80.0), None);
+/// assert_eq!(overbought.next(83.0, 80.0), Some(Flip::Bullish));
+/// assert_eq!(overbought.next(85.0, 80.0), None);
+/// assert_eq!(overbought.side(), Some(Flip::Bullish));
+/// assert_eq!(overbought.next(79.0, 80.0), Some(Flip::Bearish));
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cross<F = f64> {
+    debounce: Debounce<F>,
+    side: Option<Flip>,
+    pending: Option<(Flip, u32)>,
+}
+
+impl Cross {
+    /// Fails with `InvalidParameter` if `debounce` confirms after 0 bars or its margin is
+    /// negative.
+    pub fn new(debounce: Debounce<f64>) -> Result<Self> {
+        Self::with_type(debounce)
+    }
+}
+
+impl<F: Float> Cross<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(debounce: Debounce<F>) -> Result<Self> {
+        if debounce.confirm == 0 || debounce.margin < F::zero() || debounce.margin.is_nan() {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        Ok(Self {
+            debounce,
+            side: None,
+            pending: None,
+        })
+    }
+
+    /// Returns the new side of `first` once the lines have crossed. The side the lines start
+    /// on is not reported.
+    pub fn next(&mut self, first: F, second: F) -> Option<Flip> {
+        let distance = first - second;
+        let side = if distance > self.debounce.margin {
+            Flip::Bullish
+        } else if distance < -self.debounce.margin {
+            Flip::Bearish
+        } else {
+            // too close to tell, the crossover has to start over
+            self.pending = None;
+            return None;
+        };
+
+        if self.side == Some(side) {
+            self.pending = None;
+            return None;
+        }
+
+        let bars = match self.pending {
+            Some((pending, bars)) if pending == side => bars + 1,
+            _ => 1,
+        };
+        if bars < self.debounce.confirm {
+            self.pending = Some((side, bars));
+            return None;
+        }
+
+        self.pending = None;
+        self.side.replace(side).map(|_| side)
+    }
+
+    /// Side of the first line, `Bullish` if it is above the second one, `None` before the
+    /// lines were apart.
+    pub fn side(&self) -> Option<Flip> {
+        self.side
+    }
+}
+
+impl Default for Cross {
+    fn default() -> Self {
+        Self::new(Debounce::default()).unwrap()
+    }
+}
+
+impl<F> Reset for Cross<F> {
+    fn reset(&mut self) {
+        self.side = None;
+        self.pending = None;
+    }
+}
+
+/// Returns `true` if the first line crossed above the second one, given both lines as
+/// `(first, second)` in the previous and the current period.
+///
+/// ```
+/// use ta::flips::{crossed_over, crossed_under};
+///
+/// assert!(crossed_over((19.0, 20.0), (21.0, 20.0)));
+/// assert!(crossed_over((20.0, 20.0), (21.0, 20.0)));
+/// assert!(!crossed_over((21.0, 20.0), (22.0, 20.0)));
+/// assert!(crossed_under((21.0, 20.0), (19.0, 20.0)));
+/// ```
+pub fn crossed_over<F: PartialOrd>(previous: (F, F), current: (F, F)) -> bool {
+    previous.0 <= previous.1 && current.0 > current.1
+}
+
+/// Returns `true` if the first line crossed below the second one, like
+/// [crossed_over](fn.crossed_over.html).
+pub fn crossed_under<F: PartialOrd>(previous: (F, F), current: (F, F)) -> bool {
+    previous.0 >= previous.1 && current.0 < current.1
+}
+
+/// Flips of the [Vortex Indicator](../indicators/struct.VortexIndicator.html): +VI crossing
+/// above −VI is bullish, −VI crossing above +VI is bearish.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VortexFlips<F = f64> {
+    vortex: VortexIndicator<F>,
+    cross: Cross<F>,
+}
+
+impl VortexFlips {
+    pub fn new(length: u32, debounce: Debounce<f64>) -> Result<Self> {
+        Self::with_type(length, debounce)
+    }
+}
+
+impl<F: Float> VortexFlips<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(length: u32, debounce: Debounce<F>) -> Result<Self> {
+        Ok(Self {
+            vortex: VortexIndicator::with_type(length)?,
+            cross: Cross::with_type(debounce)?,
+        })
+    }
+}
+
+impl<F: Float, T: High<F> + Low<F> + Close<F>> Next<T> for VortexFlips<F> {
+    type Output = Option<Flip>;
+
+    fn next(&mut self, input: &T) -> Option<Flip> {
+        let vortex = self.vortex.next(input);
+        self.cross.next(vortex.plus, vortex.minus)
+    }
+}
+
+impl<F: Float> Reset for VortexFlips<F> {
+    fn reset(&mut self) {
+        self.vortex.reset();
+        self.cross.reset();
+    }
+}
+
+/// Flips of the [Directional Movement Index](../indicators/struct.DirectionalMovementIndex.html):
+/// +DI crossing above −DI is bullish, −DI crossing above +DI is bearish.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DmiFlips<F = f64> {
+    dmi: DirectionalMovementIndex<F>,
+    cross: Cross<F>,
+}
+
+impl DmiFlips {
+    pub fn new(length: u32, debounce: Debounce<f64>) -> Result<Self> {
+        Self::with_type(length, debounce)
+    }
+}
+
+impl<F: Float> DmiFlips<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(length: u32, debounce: Debounce<F>) -> Result<Self> {
+        Ok(Self {
+            dmi: DirectionalMovementIndex::with_type(length)?,
+            cross: Cross::with_type(debounce)?,
+        })
+    }
+}
+
+impl<F: Float, T: High<F> + Low<F> + Close<F>> Next<T> for DmiFlips<F> {
+    type Output = Option<Flip>;
+
+    fn next(&mut self, input: &T) -> Option<Flip> {
+        let dmi = self.dmi.next(input);
+        self.cross.next(dmi.plus, dmi.minus)
+    }
+}
+
+impl<F: Float> Reset for DmiFlips<F> {
+    fn reset(&mut self) {
+        self.dmi.reset();
+        self.cross.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    fn debounce(confirm: u32, margin: f64) -> Debounce {
+        Debounce { confirm, margin }
+    }
+
+    fn run(cross: &mut Cross<f64>, lines: &[(f64, f64)]) -> Vec<Option<Flip>> {
+        lines
+            .iter()
+            .map(|&(first, second)| cross.next(first, second))
+            .collect()
+    }
+
+    // rises for 6 bars, then falls for 6 bars
+    fn bars() -> Vec<Bar> {
+        (0..6)
+            .chain((0..6).rev())
+            .map(|i| Bar::new().high(12 + i).low(10 + i).close(11 + i))
+            .collect()
+    }
+
+    #[test]
+    fn test_cross() {
+        let mut cross = Cross::new(Debounce::default()).unwrap();
+        let lines = [(1.0, 2.0), (1.0, 2.0), (3.0, 2.0), (3.0, 3.0), (1.0, 2.0)];
+        assert_eq!(
+            run(&mut cross, &lines),
+            vec![None, None, Some(Flip::Bullish), None, Some(Flip::Bearish)]
+        );
+    }
+
+    #[test]
+    fn test_cross_level() {
+        let mut cross = Cross::default();
+        assert_eq!(cross.side(), None);
+        assert_eq!(
+            run(&mut cross, &[(20.0, 20.0), (25.0, 20.0), (15.0, 20.0)]),
+            vec![None, None, Some(Flip::Bearish)]
+        );
+        assert_eq!(cross.side(), Some(Flip::Bearish));
+
+        cross.reset();
+        assert_eq!(cross.side(), None);
+        assert_eq!(cross.next(25.0, 20.0), None);
+    }
+
+    #[test]
+    fn test_crossed() {
+        assert!(crossed_over((1.0, 2.0), (3.0, 2.0)));
+        assert!(!crossed_over((1.0, 2.0), (2.0, 2.0)));
+        assert!(!crossed_over((3.0, 2.0), (1.0, 2.0)));
+        assert!(crossed_under((2.0, 2.0), (1.0, 2.0)));
+        assert!(!crossed_under((1.0, 2.0), (0.0, 2.0)));
+        assert!(!crossed_over((f64::NAN, 2.0), (3.0, 2.0)));
+    }
+
+    #[test]
+    fn test_debounce_confirm() {
+        let mut cross = Cross::new(debounce(2, 0.0)).unwrap();
+        let lines = [
+            (1.0, 2.0),
+            (1.0, 2.0),
+            (3.0, 2.0),
+            (1.0, 2.0),
+            (3.0, 2.0),
+            (3.0, 2.0),
+        ];
+        assert_eq!(
+            run(&mut cross, &lines),
+            vec![None, None, None, None, None, Some(Flip::Bullish)]
+        );
+    }
+
+    #[test]
+    fn test_debounce_margin() {
+        let mut cross = Cross::new(debounce(1, 0.5)).unwrap();
+        let lines = [(1.0, 2.0), (2.4, 2.0), (2.0, 2.0), (2.6, 2.0)];
+        assert_eq!(
+            run(&mut cross, &lines),
+            vec![None, None, None, Some(Flip::Bullish)]
+        );
+    }
+
+    #[test]
+    fn test_invalid_debounce() {
+        assert!(VortexFlips::new(14, debounce(0, 0.0)).is_err());
+        assert!(DmiFlips::new(14, debounce(1, -1.0)).is_err());
+        assert!(DmiFlips::new(0, debounce(1, 0.0)).is_err());
+    }
+
+    #[test]
+    fn test_vortex_flips() {
+        let mut flips = VortexFlips::new(3, Debounce::default()).unwrap();
+        let events: Vec<_> = bars().iter().map(|bar| flips.next(bar)).collect();
+        assert_eq!(events.iter().flatten().count(), 1);
+        assert_eq!(events[8], Some(Flip::Bearish));
+
+        flips.reset();
+        let events: Vec<_> = bars()[6..].iter().map(|bar| flips.next(bar)).collect();
+        assert!(events.iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn test_dmi_flips() {
+        let mut flips = DmiFlips::new(3, debounce(2, 0.0)).unwrap();
+        let events: Vec<_> = bars().iter().map(|bar| flips.next(bar)).collect();
+        assert_eq!(events.iter().flatten().count(), 1);
+        // one bar later than without the confirmation
+        assert_eq!(events[9], Some(Flip::Bearish));
+    }
+}
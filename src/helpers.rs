@@ -1,6 +1,34 @@
+use crate::Number;
+
 /// Returns the largest of 3 given numbers.
-pub fn max3(a: f64, b: f64, c: f64) -> f64 {
-    a.max(b).max(c)
+pub fn max3<N: Number>(a: N, b: N, c: N) -> N {
+    max(max(a, b), c)
+}
+
+/// Returns the larger of 2 given numbers.
+pub fn max<N: Number>(a: N, b: N) -> N {
+    if b > a {
+        b
+    } else {
+        a
+    }
+}
+
+/// Returns the absolute value of a number.
+pub fn abs<N: Number>(value: N) -> N {
+    if value < N::zero() {
+        -value
+    } else {
+        value
+    }
+}
+
+/// Length of a window fixed at compile time.
+pub struct ConstLength<const N: usize>;
+
+impl<const N: usize> ConstLength<N> {
+    /// Fails to compile wherever it is used with _N_ = 0.
+    pub const NONZERO: () = assert!(N > 0, "window length must be greater than 0");
 }
 
 #[cfg(test)]
@@ -13,4 +41,11 @@ mod tests {
         assert_eq!(max3(2.0, 3.0, 1.0), 3.0);
         assert_eq!(max3(2.0, 1.0, 3.0), 3.0);
     }
+
+    #[test]
+    fn test_abs() {
+        assert_eq!(abs(-2.5), 2.5);
+        assert_eq!(abs(2.5), 2.5);
+        assert_eq!(abs(0.0), 0.0);
+    }
 }
@@ -0,0 +1,492 @@
+//! Indicators over memory-mapped history files.
+//!
+//! Decades of bars derived from ticks don't fit in memory comfortably. The files here are
+//! memory-mapped, so the operating system pages them in and out as needed, and decoded a
+//! chunk of bars at a time while they are fed to an indicator or a
+//! [Pipeline](../pipeline/struct.Pipeline.html):
+//!
+//! * [BarFile](struct.BarFile.html) reads the binary format written by
+//!   [write_bars](fn.write_bars.html), a flat array of records of
+//!   [RECORD_SIZE](constant.RECORD_SIZE.html) bytes, the open, high, low, close and volume
+//!   as little-endian `f64`,
+//! * [ParquetBarFile](struct.ParquetBarFile.html) reads the `open`, `high`, `low`, `close`
+//!   and `volume` columns of a Parquet file (requires the `parquet` feature).
+//!
+//! Every bar is validated like a [DataItem](../struct.DataItem.html) built by its builder.
+//! `feed` reports the [Progress](struct.Progress.html) after every chunk.
+//!
+//! # Example
+//!
+//! ```
+//! use ta::history::{write_bars, BarFile};
+//! use ta::indicators::SimpleMovingAverage;
+//! use ta::DataItem;
+//!
+//! let bars: Vec<DataItem> = (0..10)
+//!     .map(|i| {
+//!         let close = 10.0 + i as f64;
+//!         DataItem::builder()
+//!             .open(close)
+//!             .high(close)
+//!             .low(close)
+//!             .close(close)
+//!             .volume(1.0)
+//!             .build()
+//!             .unwrap()
+//!     })
+//!     .collect();
+//! let path = std::env::temp_dir().join("ta-history-example.bin");
+//! write_bars(std::fs::File::create(&path).unwrap(), &bars).unwrap();
+//!
+//! let file = BarFile::open(&path).unwrap().chunk_size(4);
+//! let mut sma = SimpleMovingAverage::new(3).unwrap();
+//! let mut reported = Vec::new();
+//! let last = file.feed(&mut sma, |progress| reported.push(progress.bars)).unwrap();
+//! # std::fs::remove_file(&path).unwrap();
+//!
+//! assert_eq!(last, Some(18.0));
+//! assert_eq!(reported, vec![4, 8, 10]);
+//! ```
+
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+use memmap2::Mmap;
+
+use crate::errors::*;
+use crate::{Close, DataItem, High, Low, Next, Open, Volume};
+
+/// Size in bytes of a bar of a [BarFile](struct.BarFile.html).
+pub const RECORD_SIZE: usize = 40;
+
+/// Default number of bars of a chunk.
+pub const CHUNK_SIZE: usize = 65_536;
+
+fn failed<E: ToString>(error: E) -> Error {
+    Error::from_kind(ErrorKind::ReadFailed(error.to_string()))
+}
+
+fn map(path: &Path) -> Result<Mmap> {
+    let file = File::open(path).map_err(failed)?;
+    // Safety: the map is read-only, a file truncated or modified by another process while
+    // it is mapped is the caller's responsibility, as with any memory-mapped file.
+    let mmap = unsafe { Mmap::map(&file) }.map_err(failed)?;
+    #[cfg(unix)]
+    {
+        // only a hint for the read-ahead
+        let _ = mmap.advise(memmap2::Advice::Sequential);
+    }
+    Ok(mmap)
+}
+
+fn bar(open: f64, high: f64, low: f64, close: f64, volume: f64) -> Result<DataItem> {
+    DataItem::builder()
+        .open(open)
+        .high(high)
+        .low(low)
+        .close(close)
+        .volume(volume)
+        .build()
+}
+
+/// Number of bars fed so far out of the bars of a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    pub bars: u64,
+    pub total: u64,
+}
+
+impl Progress {
+    /// Fed part of the file, from 0 to 1. An empty file is done.
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 {
+            1.0
+        } else {
+            self.bars as f64 / self.total as f64
+        }
+    }
+}
+
+fn feed<I, N, P>(
+    chunks: I,
+    total: u64,
+    indicator: &mut N,
+    mut progress: P,
+) -> Result<Option<N::Output>>
+where
+    I: Iterator<Item = Result<Vec<DataItem>>>,
+    N: Next<DataItem>,
+    P: FnMut(Progress),
+{
+    let mut last = None;
+    let mut bars = 0;
+    for chunk in chunks {
+        let chunk = chunk?;
+        for bar in &chunk {
+            last = Some(indicator.next(bar));
+        }
+        bars += chunk.len() as u64;
+        progress(Progress { bars, total });
+    }
+    Ok(last)
+}
+
+/// Writes bars in the binary format of a [BarFile](struct.BarFile.html).
+pub fn write_bars<'a, W, T, I>(mut writer: W, bars: I) -> Result<()>
+where
+    W: Write,
+    T: Open + High + Low + Close + Volume + 'a,
+    I: IntoIterator<Item = &'a T>,
+{
+    let mut record = [0u8; RECORD_SIZE];
+    for bar in bars {
+        let values = [bar.open(), bar.high(), bar.low(), bar.close(), bar.volume()];
+        for (bytes, value) in record.chunks_exact_mut(8).zip(values.iter()) {
+            bytes.copy_from_slice(&value.to_le_bytes());
+        }
+        writer.write_all(&record).map_err(failed)?;
+    }
+    writer.flush().map_err(failed)
+}
+
+/// Memory-mapped file of bars in the binary format written by
+/// [write_bars](fn.write_bars.html).
+pub struct BarFile {
+    mmap: Mmap,
+    chunk_size: usize,
+}
+
+impl BarFile {
+    /// Maps the file at `path`, which must hold whole records.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let mmap = map(path.as_ref())?;
+        if mmap.len() % RECORD_SIZE != 0 {
+            return Err(failed("file size is not a multiple of the record size"));
+        }
+        Ok(Self {
+            mmap,
+            chunk_size: CHUNK_SIZE,
+        })
+    }
+
+    /// Number of bars decoded at a time (greater than 0).
+    pub fn chunk_size(mut self, bars: usize) -> Self {
+        self.chunk_size = bars.max(1);
+        self
+    }
+
+    /// Number of bars of the file.
+    pub fn len(&self) -> usize {
+        self.mmap.len() / RECORD_SIZE
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.mmap.is_empty()
+    }
+
+    /// Decodes the bar at `index`.
+    pub fn get(&self, index: usize) -> Option<Result<DataItem>> {
+        let start = index.checked_mul(RECORD_SIZE)?;
+        self.mmap.get(start..start + RECORD_SIZE).map(decode)
+    }
+
+    /// Iterator of the bars decoded a chunk at a time.
+    pub fn chunks(&self) -> Chunks<'_> {
+        Chunks {
+            records: self.mmap.chunks(self.chunk_size * RECORD_SIZE),
+        }
+    }
+
+    /// Feeds every bar to `indicator`, calling `progress` after every chunk, and returns
+    /// the output of the last bar.
+    ///
+    /// Stops at the first invalid bar, the bars before it have been fed.
+    pub fn feed<N, P>(&self, indicator: &mut N, progress: P) -> Result<Option<N::Output>>
+    where
+        N: Next<DataItem>,
+        P: FnMut(Progress),
+    {
+        feed(self.chunks(), self.len() as u64, indicator, progress)
+    }
+}
+
+fn decode(record: &[u8]) -> Result<DataItem> {
+    let mut values = [0.0; 5];
+    for (value, bytes) in values.iter_mut().zip(record.chunks_exact(8)) {
+        let mut le = [0u8; 8];
+        le.copy_from_slice(bytes);
+        *value = f64::from_le_bytes(le);
+    }
+    let [open, high, low, close, volume] = values;
+    bar(open, high, low, close, volume)
+}
+
+/// Chunks of the bars of a [BarFile](struct.BarFile.html).
+pub struct Chunks<'a> {
+    records: core::slice::Chunks<'a, u8>,
+}
+
+impl Iterator for Chunks<'_> {
+    type Item = Result<Vec<DataItem>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.records
+            .next()
+            .map(|chunk| chunk.chunks_exact(RECORD_SIZE).map(decode).collect())
+    }
+}
+
+/// Memory-mapped Parquet file of bars.
+///
+/// The `open`, `high`, `low`, `close` and `volume` columns must be `Float64`, other
+/// columns are not decoded.
+#[cfg(feature = "parquet")]
+pub struct ParquetBarFile {
+    data: bytes::Bytes,
+    rows: usize,
+    chunk_size: usize,
+}
+
+#[cfg(feature = "parquet")]
+const COLUMNS: [&str; 5] = ["open", "high", "low", "close", "volume"];
+
+#[cfg(feature = "parquet")]
+impl ParquetBarFile {
+    /// Maps the Parquet file at `path` and reads its footer.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let data = bytes::Bytes::from_owner(map(path.as_ref())?);
+        let builder =
+            parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder::try_new(data.clone())
+                .map_err(failed)?;
+        for name in COLUMNS.iter() {
+            match builder.schema().field_with_name(name) {
+                Ok(field) if *field.data_type() == arrow_schema::DataType::Float64 => {}
+                Ok(_) => return Err(failed(format!("column '{}' is not Float64", name))),
+                Err(_) => return Err(failed(format!("no column '{}'", name))),
+            }
+        }
+        let rows = builder.metadata().file_metadata().num_rows() as usize;
+        Ok(Self {
+            data,
+            rows,
+            chunk_size: CHUNK_SIZE,
+        })
+    }
+
+    /// Number of bars decoded at a time (greater than 0).
+    pub fn chunk_size(mut self, bars: usize) -> Self {
+        self.chunk_size = bars.max(1);
+        self
+    }
+
+    /// Number of bars of the file.
+    pub fn len(&self) -> usize {
+        self.rows
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows == 0
+    }
+
+    /// Iterator of the bars decoded a chunk at a time.
+    pub fn chunks(&self) -> Result<ParquetChunks> {
+        use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+        use parquet::arrow::ProjectionMask;
+
+        let builder =
+            ParquetRecordBatchReaderBuilder::try_new(self.data.clone()).map_err(failed)?;
+        let projection = ProjectionMask::columns(builder.parquet_schema(), COLUMNS.iter().cloned());
+        let reader = builder
+            .with_projection(projection)
+            .with_batch_size(self.chunk_size)
+            .build()
+            .map_err(failed)?;
+        Ok(ParquetChunks { reader })
+    }
+
+    /// Feeds every bar to `indicator`, calling `progress` after every chunk, and returns
+    /// the output of the last bar.
+    ///
+    /// Stops at the first invalid bar, the bars before it have been fed.
+    pub fn feed<N, P>(&self, indicator: &mut N, progress: P) -> Result<Option<N::Output>>
+    where
+        N: Next<DataItem>,
+        P: FnMut(Progress),
+    {
+        feed(self.chunks()?, self.rows as u64, indicator, progress)
+    }
+}
+
+/// Chunks of the bars of a [ParquetBarFile](struct.ParquetBarFile.html), a record batch
+/// each.
+#[cfg(feature = "parquet")]
+pub struct ParquetChunks {
+    reader: parquet::arrow::arrow_reader::ParquetRecordBatchReader,
+}
+
+#[cfg(feature = "parquet")]
+impl Iterator for ParquetChunks {
+    type Item = Result<Vec<DataItem>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        use arrow_array::{Array, Float64Array};
+
+        let batch = match self.reader.next()? {
+            Ok(batch) => batch,
+            Err(error) => return Some(Err(failed(error))),
+        };
+        let mut columns = Vec::with_capacity(COLUMNS.len());
+        for name in COLUMNS.iter() {
+            let column = batch
+                .column_by_name(name)
+                .and_then(|column| column.as_any().downcast_ref::<Float64Array>());
+            match column {
+                Some(column) if column.null_count() == 0 => columns.push(column.values()),
+                _ => return Some(Err(failed(format!("column '{}' has nulls", name)))),
+            }
+        }
+        let bars = (0..batch.num_rows())
+            .map(|i| {
+                bar(
+                    columns[0][i],
+                    columns[1][i],
+                    columns[2][i],
+                    columns[3][i],
+                    columns[4][i],
+                )
+            })
+            .collect();
+        Some(bars)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::SimpleMovingAverage;
+    use std::path::PathBuf;
+
+    fn bars() -> Vec<DataItem> {
+        (0..25)
+            .map(|i| {
+                let close = 100.0 + (i % 7) as f64;
+                DataItem::new(close - 0.5, close + 1.0, close - 1.0, close, i as f64)
+            })
+            .collect()
+    }
+
+    fn path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("ta-history-{}-{}", std::process::id(), name))
+    }
+
+    fn expected(bars: &[DataItem]) -> f64 {
+        let mut sma = SimpleMovingAverage::new(5).unwrap();
+        bars.iter().map(|bar| sma.next(bar)).last().unwrap()
+    }
+
+    #[test]
+    fn test_bar_file() {
+        let bars = bars();
+        let path = path("bars.bin");
+        write_bars(File::create(&path).unwrap(), &bars).unwrap();
+
+        let file = BarFile::open(&path).unwrap().chunk_size(10);
+        assert_eq!(file.len(), 25);
+        assert_eq!(file.get(3).unwrap().unwrap().volume(), 3.0);
+        assert!(file.get(25).is_none());
+        assert_eq!(file.chunks().count(), 3);
+
+        let mut sma = SimpleMovingAverage::new(5).unwrap();
+        let mut reported = Vec::new();
+        let last = file
+            .feed(&mut sma, |progress| reported.push(progress))
+            .unwrap();
+        assert_eq!(last, Some(expected(&bars)));
+        let fed: Vec<u64> = reported.iter().map(|progress| progress.bars).collect();
+        assert_eq!(fed, vec![10, 20, 25]);
+        assert_eq!(reported[0].fraction(), 0.4);
+        assert_eq!(reported[2].fraction(), 1.0);
+        drop(file);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_invalid() {
+        let path = path("invalid.bin");
+        let mut bytes = Vec::new();
+        write_bars(&mut bytes, &bars()[..2]).unwrap();
+        std::fs::write(&path, &bytes[..RECORD_SIZE + 1]).unwrap();
+        match BarFile::open(&path) {
+            Err(Error(ErrorKind::ReadFailed(_), _)) => {}
+            _ => panic!("expected ReadFailed"),
+        }
+
+        // high below low in the second bar
+        bytes[RECORD_SIZE + 8..RECORD_SIZE + 16].copy_from_slice(&0.0f64.to_le_bytes());
+        std::fs::write(&path, &bytes).unwrap();
+        let file = BarFile::open(&path).unwrap().chunk_size(1);
+        let mut sma = SimpleMovingAverage::new(2).unwrap();
+        let mut fed = 0;
+        match file.feed(&mut sma, |progress| fed = progress.bars) {
+            Err(Error(ErrorKind::HighBelowLow, _)) => {}
+            _ => panic!("expected HighBelowLow"),
+        }
+        assert_eq!(fed, 1);
+        drop(file);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(BarFile::open(path).is_err());
+    }
+
+    #[test]
+    fn test_empty() {
+        let path = path("empty.bin");
+        File::create(&path).unwrap();
+        let file = BarFile::open(&path).unwrap();
+        assert!(file.is_empty());
+        let mut sma = SimpleMovingAverage::new(2).unwrap();
+        assert_eq!(file.feed(&mut sma, |_| {}).unwrap(), None);
+        drop(file);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn test_parquet_bar_file() {
+        use arrow_array::{ArrayRef, Float64Array, RecordBatch};
+        use std::sync::Arc;
+
+        let bars = bars();
+        let column = |value: fn(&DataItem) -> f64| -> ArrayRef {
+            Arc::new(Float64Array::from(
+                bars.iter().map(value).collect::<Vec<f64>>(),
+            ))
+        };
+        let batch = RecordBatch::try_from_iter(vec![
+            ("open", column(|bar| bar.open())),
+            ("high", column(|bar| bar.high())),
+            ("low", column(|bar| bar.low())),
+            ("close", column(|bar| bar.close())),
+            ("volume", column(|bar| bar.volume())),
+        ])
+        .unwrap();
+        let path = path("bars.parquet");
+        let file = File::create(&path).unwrap();
+        let mut writer = parquet::arrow::ArrowWriter::try_new(file, batch.schema(), None).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+
+        let file = ParquetBarFile::open(&path).unwrap().chunk_size(10);
+        assert_eq!(file.len(), 25);
+        let mut sma = SimpleMovingAverage::new(5).unwrap();
+        let mut fed = Vec::new();
+        let last = file
+            .feed(&mut sma, |progress| fed.push(progress.bars))
+            .unwrap();
+        assert_eq!(last, Some(expected(&bars)));
+        assert_eq!(fed, vec![10, 20, 25]);
+        drop(file);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,312 @@
+use core::fmt;
+
+use crate::errors::*;
+use crate::indicators::SmoothedMovingAverage;
+use crate::{Calculate, Finite, Float, High, Low, Next, Reset, RingBuffer, Warmup};
+
+/// State of the [Alligator](struct.Alligator.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AlligatorState {
+    /// The lines are intertwined, the market has no trend.
+    Sleeping,
+    /// Lips above teeth above jaw, the market is trending up.
+    EatingUp,
+    /// Lips below teeth below jaw, the market is trending down.
+    EatingDown,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Line<F> {
+    smma: SmoothedMovingAverage<F>,
+    offset: u32,
+    history: RingBuffer<F>,
+    count: u32,
+}
+
+impl<F: Float> Line<F> {
+    fn new(length: u32, offset: u32) -> Result<Self> {
+        Ok(Self {
+            smma: SmoothedMovingAverage::with_type(length)?,
+            offset,
+            history: RingBuffer::new(offset as usize + 1),
+            count: 0,
+        })
+    }
+
+    // Returns the value computed `offset` bars ago, or the oldest one available.
+    fn calc(&mut self, input: F) -> F {
+        self.count = self.count.saturating_add(1);
+        self.history.push(self.smma.calc(input));
+        self.history[0]
+    }
+
+    fn reset(&mut self) {
+        self.smma.reset();
+        self.history.clear();
+        self.count = 0;
+    }
+
+    // The displaced value is meaningful once the average was ready `offset` bars ago.
+    fn warmup_period(&self) -> u32 {
+        self.smma.length() + self.offset
+    }
+
+    fn is_ready(&self) -> bool {
+        self.count >= self.warmup_period()
+    }
+}
+
+/// Alligator indicator by Bill Williams.
+///
+/// Three smoothed moving averages of the median price, each displaced forward in time:
+///
+/// * jaw - SMMA(13) displaced by 8 bars
+/// * teeth - SMMA(8) displaced by 5 bars
+/// * lips - SMMA(5) displaced by 3 bars
+///
+/// The value of a line displaced by _k_ bars at the current bar is the average computed
+/// _k_ bars ago. While fewer bars are available, the oldest computed value is used.
+///
+/// The [state](enum.AlligatorState.html) tells whether the alligator is "sleeping" (lines
+/// intertwined) or "eating" (lines ordered in the direction of a trend).
+///
+/// # Parameters
+///
+/// * _jaw_length_, _jaw_offset_ - default 13 and 8
+/// * _teeth_length_, _teeth_offset_ - default 8 and 5
+/// * _lips_length_, _lips_offset_ - default 5 and 3
+///
+/// Lengths must be greater than 0.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{Alligator, AlligatorState};
+/// use ta::Calculate;
+///
+/// let mut alligator = Alligator::default();
+/// let mut out = alligator.calc(10.0);
+/// assert_eq!(out.state, AlligatorState::Sleeping);
+/// for i in 1..50 {
+///     out = alligator.calc(10.0 + i as f64);
+/// }
+/// assert_eq!(out.state, AlligatorState::EatingUp);
+/// ```
+///
+/// # Links
+///
+/// * [Alligator, MetaTrader 5 help](https://www.metatrader5.com/en/terminal/help/indicators/bw_indicators/alligator)
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Alligator<F = f64> {
+    jaw: Line<F>,
+    teeth: Line<F>,
+    lips: Line<F>,
+}
+
+/// Output of [Alligator](struct.Alligator.html).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AlligatorOutput<F = f64> {
+    pub jaw: F,
+    pub teeth: F,
+    pub lips: F,
+    pub state: AlligatorState,
+}
+
+impl<F: Finite> Finite for AlligatorOutput<F> {
+    fn is_finite(&self) -> bool {
+        self.jaw.is_finite() && self.teeth.is_finite() && self.lips.is_finite()
+    }
+}
+
+impl Alligator {
+    pub fn new(
+        jaw_length: u32,
+        jaw_offset: u32,
+        teeth_length: u32,
+        teeth_offset: u32,
+        lips_length: u32,
+        lips_offset: u32,
+    ) -> Result<Self> {
+        Self::with_type(
+            jaw_length,
+            jaw_offset,
+            teeth_length,
+            teeth_offset,
+            lips_length,
+            lips_offset,
+        )
+    }
+}
+
+impl<F: Float> Alligator<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(
+        jaw_length: u32,
+        jaw_offset: u32,
+        teeth_length: u32,
+        teeth_offset: u32,
+        lips_length: u32,
+        lips_offset: u32,
+    ) -> Result<Self> {
+        Ok(Self {
+            jaw: Line::new(jaw_length, jaw_offset)?,
+            teeth: Line::new(teeth_length, teeth_offset)?,
+            lips: Line::new(lips_length, lips_offset)?,
+        })
+    }
+}
+
+impl<F: Float> Calculate<F> for Alligator<F> {
+    type Output = AlligatorOutput<F>;
+
+    fn calc(&mut self, input: F) -> Self::Output {
+        let jaw = self.jaw.calc(input);
+        let teeth = self.teeth.calc(input);
+        let lips = self.lips.calc(input);
+
+        let state = if lips > teeth && teeth > jaw {
+            AlligatorState::EatingUp
+        } else if lips < teeth && teeth < jaw {
+            AlligatorState::EatingDown
+        } else {
+            AlligatorState::Sleeping
+        };
+
+        AlligatorOutput {
+            jaw,
+            teeth,
+            lips,
+            state,
+        }
+    }
+}
+
+impl<F: Float, T: High<F> + Low<F>> Next<T> for Alligator<F> {
+    type Output = AlligatorOutput<F>;
+
+    /// Feeds the median price (high + low) / 2 of a bar.
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.calc((input.high() + input.low()) / F::cast(2.0))
+    }
+}
+
+impl<F: Float> Reset for Alligator<F> {
+    fn reset(&mut self) {
+        self.jaw.reset();
+        self.teeth.reset();
+        self.lips.reset();
+    }
+}
+
+impl<F: Float> Warmup for Alligator<F> {
+    fn warmup_period(&self) -> u32 {
+        self.jaw
+            .warmup_period()
+            .max(self.teeth.warmup_period())
+            .max(self.lips.warmup_period())
+    }
+
+    fn is_ready(&self) -> bool {
+        self.jaw.is_ready() && self.teeth.is_ready() && self.lips.is_ready()
+    }
+}
+
+impl Default for Alligator {
+    fn default() -> Self {
+        Self::new(13, 8, 8, 5, 5, 3).unwrap()
+    }
+}
+
+impl<F: Float> fmt::Display for Alligator<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "ALLIGATOR({}, {}, {}, {}, {}, {})",
+            self.jaw.smma.length(),
+            self.jaw.offset,
+            self.teeth.smma.length(),
+            self.teeth.offset,
+            self.lips.smma.length(),
+            self.lips.offset
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(Alligator);
+
+    #[test]
+    fn test_new() {
+        assert!(Alligator::new(0, 8, 8, 5, 5, 3).is_err());
+        assert!(Alligator::new(13, 0, 8, 0, 5, 0).is_ok());
+    }
+
+    #[test]
+    fn test_displacement() {
+        let mut alligator = Alligator::new(1, 2, 1, 1, 1, 0).unwrap();
+        // with length 1 every line equals its input, shifted by the offset
+        let out = alligator.calc(1.0);
+        assert_eq!((out.jaw, out.teeth, out.lips), (1.0, 1.0, 1.0));
+        let out = alligator.calc(2.0);
+        assert_eq!((out.jaw, out.teeth, out.lips), (1.0, 1.0, 2.0));
+        assert_eq!(out.state, AlligatorState::Sleeping);
+        let out = alligator.calc(3.0);
+        assert_eq!((out.jaw, out.teeth, out.lips), (1.0, 2.0, 3.0));
+        assert_eq!(out.state, AlligatorState::EatingUp);
+        let out = alligator.calc(0.0);
+        assert_eq!((out.jaw, out.teeth, out.lips), (2.0, 3.0, 0.0));
+        assert_eq!(out.state, AlligatorState::Sleeping);
+    }
+
+    #[test]
+    fn test_eating_down() {
+        let mut alligator = Alligator::default();
+        let mut out = alligator.next(&Bar::new().high(101.0).low(99.0));
+        for i in 1..50 {
+            let price = 100.0 - i as f64;
+            out = alligator.next(&Bar::new().high(price + 1.0).low(price - 1.0));
+        }
+        assert_eq!(out.state, AlligatorState::EatingDown);
+    }
+
+    #[test]
+    fn test_warmup() {
+        let mut alligator = Alligator::new(2, 1, 1, 0, 1, 0).unwrap();
+        assert_eq!(alligator.warmup_period(), 3);
+        alligator.calc(1.0);
+        alligator.calc(1.0);
+        assert!(!alligator.is_ready());
+        alligator.calc(1.0);
+        assert!(alligator.is_ready());
+
+        assert_eq!(Alligator::default().warmup_period(), 21);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut alligator = Alligator::new(1, 2, 1, 1, 1, 0).unwrap();
+        alligator.calc(1.0);
+        alligator.calc(2.0);
+
+        alligator.reset();
+        let out = alligator.calc(5.0);
+        assert_eq!((out.jaw, out.teeth, out.lips), (5.0, 5.0, 5.0));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            format!("{}", Alligator::default()),
+            "ALLIGATOR(13, 8, 8, 5, 5, 3)"
+        );
+    }
+}
@@ -0,0 +1,106 @@
+use core::fmt;
+
+use crate::errors::*;
+use crate::indicators::SimpleMovingAverage;
+use crate::{Calculate, Float, Next, Quote, Reset, Warmup};
+
+/// Average quoted spread.
+///
+/// Simple moving average of the quoted spread (ask - bid) over the last _n_ quotes.
+///
+/// # Parameters
+///
+/// * _n_ - number of quotes (integer greater than 0). Default is 20.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(deserialize = "F: Float + serde::Deserialize<'de>"))
+)]
+pub struct AverageSpread<F = f64> {
+    sma: SimpleMovingAverage<F>,
+}
+
+impl AverageSpread {
+    pub fn new(n: u32) -> Result<Self> {
+        Self::with_type(n)
+    }
+}
+
+impl<F: Float> AverageSpread<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(n: u32) -> Result<Self> {
+        Ok(Self {
+            sma: SimpleMovingAverage::with_type(n)?,
+        })
+    }
+}
+
+impl<F: Float, T: Quote<F>> Next<T> for AverageSpread<F> {
+    type Output = F;
+
+    fn next(&mut self, input: &T) -> F {
+        self.sma.calc(input.ask() - input.bid())
+    }
+}
+
+impl<F: Float> Reset for AverageSpread<F> {
+    fn reset(&mut self) {
+        self.sma.reset();
+    }
+}
+
+impl<F: Float> Warmup for AverageSpread<F> {
+    fn warmup_period(&self) -> u32 {
+        self.sma.warmup_period()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.sma.is_ready()
+    }
+}
+
+impl Default for AverageSpread {
+    fn default() -> Self {
+        Self::new(20).unwrap()
+    }
+}
+
+impl<F: Float> fmt::Display for AverageSpread<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "AVG_SPREAD({})", self.sma.length())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_new() {
+        assert!(AverageSpread::new(0).is_err());
+        assert!(AverageSpread::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut spread = AverageSpread::new(2).unwrap();
+        assert_eq!(spread.next(&quote(10.0, 10.5, 1.0, 1.0)), 0.5);
+        assert_eq!(spread.next(&quote(10.0, 11.5, 1.0, 1.0)), 1.0);
+        assert_eq!(spread.next(&quote(10.0, 12.5, 1.0, 1.0)), 2.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut spread = AverageSpread::new(2).unwrap();
+        spread.next(&quote(10.0, 10.5, 1.0, 1.0));
+        spread.reset();
+        assert_eq!(spread.next(&quote(10.0, 12.0, 1.0, 1.0)), 2.0);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", AverageSpread::default()), "AVG_SPREAD(20)");
+    }
+}
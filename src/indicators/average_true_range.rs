@@ -1,8 +1,8 @@
-use std::fmt;
+use core::fmt;
 
 use crate::errors::*;
 use crate::indicators::{ExponentialMovingAverage, TrueRange};
-use crate::{Calculate, Close, High, Low, Next, Reset};
+use crate::{Calculate, Close, High, Low, Next, Number, Reset, Warmup};
 
 /// Average true range (ATR).
 ///
@@ -54,47 +54,69 @@ use crate::{Calculate, Close, High, Low, Next, Reset};
 ///     }
 /// }
 #[derive(Debug, Clone)]
-pub struct AverageTrueRange {
-    true_range: TrueRange,
-    ema: ExponentialMovingAverage,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AverageTrueRange<F = f64> {
+    true_range: TrueRange<F>,
+    ema: ExponentialMovingAverage<F>,
 }
 
 impl AverageTrueRange {
     pub fn new(length: u32) -> Result<Self> {
+        Self::with_type(length)
+    }
+}
+
+impl<F: Number> AverageTrueRange<F> {
+    /// Like [new](#method.new), for values of any number type `F`.
+    pub fn with_type(length: u32) -> Result<Self> {
         let indicator = Self {
-            true_range: TrueRange::new(),
-            ema: ExponentialMovingAverage::new(length)?,
+            true_range: TrueRange::with_type(),
+            ema: ExponentialMovingAverage::with_type(length)?,
         };
         Ok(indicator)
     }
 }
 
-impl Calculate for AverageTrueRange {
-    fn calc(&mut self, input: f64) -> f64 {
+impl<F: Number> Calculate<F> for AverageTrueRange<F> {
+    type Output = F;
+
+    fn calc(&mut self, input: F) -> F {
         self.ema.calc(self.true_range.calc(input))
     }
 }
 
-impl<T: High + Low + Close> Next<T> for AverageTrueRange {
-    fn next(&mut self, input: &T) -> f64 {
+impl<F: Number, T: High<F> + Low<F> + Close<F>> Next<T> for AverageTrueRange<F> {
+    type Output = F;
+
+    fn next(&mut self, input: &T) -> F {
         self.ema.calc(self.true_range.next(input))
     }
 }
 
-impl Reset for AverageTrueRange {
+impl<F: Number> Reset for AverageTrueRange<F> {
     fn reset(&mut self) {
         self.true_range.reset();
         self.ema.reset();
     }
 }
 
+impl<F: Number> Warmup for AverageTrueRange<F> {
+    fn warmup_period(&self) -> u32 {
+        self.ema.warmup_period()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.ema.is_ready()
+    }
+}
+
 impl Default for AverageTrueRange {
     fn default() -> Self {
         Self::new(14).unwrap()
     }
 }
 
-impl fmt::Display for AverageTrueRange {
+impl<F: Number> fmt::Display for AverageTrueRange<F> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "ATR({})", self.ema.length())
     }
@@ -125,6 +147,50 @@ mod tests {
         assert_eq!(atr.next(&bar3), 3.375);
     }
 
+    #[test]
+    fn test_next_slice() {
+        let mut atr = AverageTrueRange::new(3).unwrap();
+        let bars = vec![
+            Bar::new().high(10).low(7.5).close(9),
+            Bar::new().high(11).low(9).close(9.5),
+            Bar::new().high(9).low(5).close(8),
+        ];
+
+        let mut output = vec![0.0; bars.len()];
+        atr.next_slice(&bars, &mut output);
+        assert_eq!(output, vec![2.5, 2.25, 3.375]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_next_slice_length_mismatch() {
+        let mut atr = AverageTrueRange::new(3).unwrap();
+        atr.next_slice(&[Bar::new()], &mut [0.0; 2]);
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn test_decimal() {
+        use crate::DataItemBuilder;
+        use rust_decimal::Decimal;
+
+        let bar = |high: i64, low: i64, close: i64| {
+            DataItemBuilder::new()
+                .open(Decimal::new(close, 2))
+                .high(Decimal::new(high, 2))
+                .low(Decimal::new(low, 2))
+                .close(Decimal::new(close, 2))
+                .volume(Decimal::new(0, 0))
+                .build()
+                .unwrap()
+        };
+
+        let mut atr = AverageTrueRange::with_type(3).unwrap();
+        assert_eq!(atr.next(&bar(1000, 750, 900)), Decimal::new(250, 2));
+        assert_eq!(atr.next(&bar(1100, 900, 950)), Decimal::new(225, 2));
+        assert_eq!(atr.next(&bar(900, 500, 800)), Decimal::new(3375, 3));
+    }
+
     #[test]
     fn test_reset() {
         let mut atr = AverageTrueRange::new(9).unwrap();
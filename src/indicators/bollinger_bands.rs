@@ -0,0 +1,230 @@
+use core::fmt;
+
+use crate::errors::*;
+use crate::indicators::{SimpleMovingAverage, StandardDeviation};
+use crate::{Calculate, Close, Finite, Float, Next, Reset, Warmup};
+
+/// Bollinger Bands (BB).
+///
+/// A simple moving average surrounded by two bands placed a multiple of the standard
+/// deviation of the same window above and below it.
+///
+/// # Formula
+///
+/// * _middle_ = SMA(_length_)
+/// * _upper_ = _middle_ + _k_ * SD(_length_)
+/// * _lower_ = _middle_ - _k_ * SD(_length_)
+///
+/// Where SD is the population [standard deviation](struct.StandardDeviation.html).
+///
+/// # Parameters
+///
+/// * _length_ - number of periods (integer greater than 0). Default is 20.
+/// * _multiplier_ - width of the bands in standard deviations (greater than 0). Default is 2.0.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::BollingerBands;
+/// use ta::Calculate;
+///
+/// let mut bb = BollingerBands::new(3, 2.0).unwrap();
+/// bb.calc(2.0);
+/// let out = bb.calc(4.0);
+/// assert_eq!(out.middle, 3.0);
+/// assert_eq!(out.upper, 5.0);
+/// assert_eq!(out.lower, 1.0);
+/// ```
+///
+/// # Links
+///
+/// * [Bollinger Bands, Wikipedia](https://en.wikipedia.org/wiki/Bollinger_Bands)
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(deserialize = "F: Float + serde::Deserialize<'de>"))
+)]
+pub struct BollingerBands<F = f64> {
+    multiplier: F,
+    sma: SimpleMovingAverage<F>,
+    sd: StandardDeviation<F>,
+}
+
+/// Output of [BollingerBands](struct.BollingerBands.html).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BollingerBandsOutput<F = f64> {
+    pub upper: F,
+    pub middle: F,
+    pub lower: F,
+}
+
+impl<F: Finite> Finite for BollingerBandsOutput<F> {
+    fn is_finite(&self) -> bool {
+        self.upper.is_finite() && self.middle.is_finite() && self.lower.is_finite()
+    }
+}
+
+impl BollingerBands {
+    pub fn new(length: u32, multiplier: f64) -> Result<Self> {
+        Self::with_type(length, multiplier)
+    }
+}
+
+impl<F: Float> BollingerBands<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(length: u32, multiplier: F) -> Result<Self> {
+        if multiplier <= F::zero() {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        Ok(Self {
+            multiplier,
+            sma: SimpleMovingAverage::with_type(length)?,
+            sd: StandardDeviation::with_type(length)?,
+        })
+    }
+
+    pub fn length(&self) -> u32 {
+        self.sma.length()
+    }
+
+    pub fn multiplier(&self) -> F {
+        self.multiplier
+    }
+}
+
+impl<F: Float> Calculate<F> for BollingerBands<F> {
+    type Output = BollingerBandsOutput<F>;
+
+    fn calc(&mut self, input: F) -> Self::Output {
+        let middle = self.sma.calc(input);
+        let width = self.multiplier * self.sd.calc(input);
+        BollingerBandsOutput {
+            upper: middle + width,
+            middle,
+            lower: middle - width,
+        }
+    }
+}
+
+impl<F: Float, T: Close<F>> Next<T> for BollingerBands<F> {
+    type Output = BollingerBandsOutput<F>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.calc(input.close())
+    }
+}
+
+impl<F: Float> Reset for BollingerBands<F> {
+    fn reset(&mut self) {
+        self.sma.reset();
+        self.sd.reset();
+    }
+}
+
+impl<F: Float> Warmup for BollingerBands<F> {
+    fn warmup_period(&self) -> u32 {
+        self.sma.warmup_period()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.sma.is_ready()
+    }
+}
+
+impl Default for BollingerBands {
+    fn default() -> Self {
+        Self::new(20, 2.0).unwrap()
+    }
+}
+
+impl<F: Float> fmt::Display for BollingerBands<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "BB({}, {})", self.length(), self.multiplier)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(BollingerBands);
+
+    #[test]
+    fn test_new() {
+        assert!(BollingerBands::new(0, 2.0).is_err());
+        assert!(BollingerBands::new(20, 0.0).is_err());
+        assert!(BollingerBands::new(1, 1.0).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut bb = BollingerBands::new(3, 2.0).unwrap();
+
+        let out = bb.calc(2.0);
+        assert_eq!((out.upper, out.middle, out.lower), (2.0, 2.0, 2.0));
+
+        bb.calc(5.0);
+        let out = bb.next(&Bar::new().close(1.0));
+        assert_eq!(round(out.middle), 2.667);
+        assert_eq!(round(out.upper), 6.066);
+        assert_eq!(round(out.lower), -0.733);
+
+        // 2.0 leaves the window
+        let out = bb.calc(6.0);
+        assert_eq!(out.middle, 4.0);
+        assert_eq!(round(out.upper), 8.320);
+        assert_eq!(round(out.lower), -0.320);
+    }
+
+    #[test]
+    fn test_try_calc() {
+        use crate::TryCalculate;
+
+        let mut bb = BollingerBands::new(2, 2.0).unwrap();
+        match bb.try_calc(1.0) {
+            Err(Error(ErrorKind::NotReady, _)) => {}
+            other => panic!("unexpected {:?}", other),
+        }
+        assert_eq!(bb.try_calc(3.0).unwrap().middle, 2.0);
+
+        // not consumed
+        match bb.try_calc(f64::NAN) {
+            Err(Error(ErrorKind::NonFiniteInput, _)) => {}
+            other => panic!("unexpected {:?}", other),
+        }
+        assert_eq!(bb.try_calc(5.0).unwrap().middle, 4.0);
+
+        match bb.try_calc(f64::MAX) {
+            Err(Error(ErrorKind::NonFiniteOutput, _)) => {}
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut bb = BollingerBands::new(3, 2.0).unwrap();
+        bb.calc(2.0);
+        bb.calc(5.0);
+
+        bb.reset();
+        let out = bb.calc(4.0);
+        assert_eq!((out.upper, out.middle, out.lower), (4.0, 4.0, 4.0));
+    }
+
+    #[test]
+    fn test_default() {
+        let bb = BollingerBands::default();
+        assert_eq!(bb.length(), 20);
+        assert_eq!(bb.multiplier(), 2.0);
+    }
+
+    #[test]
+    fn test_display() {
+        let bb = BollingerBands::new(10, 3.0).unwrap();
+        assert_eq!(format!("{}", bb), "BB(10, 3)");
+    }
+}
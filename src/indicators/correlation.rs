@@ -0,0 +1,335 @@
+use core::fmt;
+
+use crate::errors::*;
+use crate::{Float, Next, Reset, RingBuffer, Warmup};
+
+/// Rolling Pearson correlation of two series.
+///
+/// Correlation of the last n pairs `(a, b)` of simultaneous observations, from -1 when the
+/// series move in opposite directions to 1 when they move together. The means, variances
+/// and the covariance are updated incrementally with Welford's algorithm as pairs enter and
+/// leave the window, so each pair costs O(1), and recomputed exactly from the window every n
+/// pairs to drop their rounding errors. Returns 0 while either series has no variance, i.e.
+/// while all its values in the window are identical.
+///
+/// See [ExponentialCorrelation](struct.ExponentialCorrelation.html) for a correlation
+/// which weights the observations exponentially instead.
+///
+/// # Formula
+///
+/// ρ = cov(a, b) / (σ<sub>a</sub> σ<sub>b</sub>)
+///
+/// # Parameters
+///
+/// * _n_ - number of periods (integer greater than 1). Default is 20.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::Correlation;
+/// use ta::Next;
+///
+/// let mut corr = Correlation::new(3).unwrap();
+/// assert_eq!(corr.next(&(1.0, 2.0)), 0.0);
+/// assert_eq!(corr.next(&(2.0, 4.0)), 1.0);
+/// assert_eq!(corr.next(&(3.0, 6.0)), 1.0);
+/// // the first pair leaves the window
+/// assert_eq!(corr.next(&(4.0, 5.0)), 0.5);
+/// ```
+///
+/// # Links
+///
+/// * [Pearson correlation coefficient, Wikipedia](https://en.wikipedia.org/wiki/Pearson_correlation_coefficient)
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Correlation<F = f64> {
+    n: u32,
+    window: RingBuffer<(F, F)>,
+    mean_a: F,
+    mean_b: F,
+    m2_a: F,
+    m2_b: F,
+    co_moment: F,
+    // number of the latest values of each series equal to their newest one
+    runs: (u32, u32),
+    pushes: u32,
+}
+
+impl Correlation {
+    pub fn new(n: u32) -> Result<Self> {
+        Self::with_type(n)
+    }
+}
+
+impl<F: Float> Correlation<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(n: u32) -> Result<Self> {
+        if n < 2 {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        Ok(Self {
+            n,
+            window: RingBuffer::new(n as usize),
+            mean_a: F::zero(),
+            mean_b: F::zero(),
+            m2_a: F::zero(),
+            m2_b: F::zero(),
+            co_moment: F::zero(),
+            runs: (0, 0),
+            pushes: 0,
+        })
+    }
+
+    pub fn length(&self) -> u32 {
+        self.n
+    }
+
+    /// Population covariance of the pairs in the window.
+    pub fn covariance(&self) -> F {
+        match self.window.len() {
+            0 => F::zero(),
+            len => self.co_moment / F::cast(len),
+        }
+    }
+
+    /// Correlation of the pairs in the window.
+    pub fn correlation(&self) -> F {
+        // rounding errors of the running sums may push them below zero on flat windows
+        let denominator = (self.m2_a.max(F::zero()) * self.m2_b.max(F::zero())).sqrt();
+        if denominator == F::zero() {
+            F::zero()
+        } else {
+            (self.co_moment / denominator).clamp(-F::one(), F::one())
+        }
+    }
+
+    // means, variances and the covariance computed from the pairs of the window
+    fn refresh(&mut self) {
+        let len = F::cast(self.window.len());
+        let (sum_a, sum_b) = self
+            .window
+            .iter()
+            .fold((F::zero(), F::zero()), |sum, &(a, b)| {
+                (sum.0 + a, sum.1 + b)
+            });
+        let (mean_a, mean_b) = (sum_a / len, sum_b / len);
+        self.mean_a = mean_a;
+        self.mean_b = mean_b;
+        self.m2_a = F::zero();
+        self.m2_b = F::zero();
+        self.co_moment = F::zero();
+        for &(a, b) in self.window.iter() {
+            self.m2_a += (a - mean_a) * (a - mean_a);
+            self.m2_b += (b - mean_b) * (b - mean_b);
+            self.co_moment += (a - mean_a) * (b - mean_b);
+        }
+    }
+
+    fn add(&mut self, a: F, b: F, len: usize) {
+        let count = F::cast(len);
+        let delta_a = a - self.mean_a;
+        let delta_b = b - self.mean_b;
+        self.mean_a += delta_a / count;
+        self.mean_b += delta_b / count;
+        self.m2_a += delta_a * (a - self.mean_a);
+        self.m2_b += delta_b * (b - self.mean_b);
+        self.co_moment += delta_a * (b - self.mean_b);
+    }
+
+    fn remove(&mut self, a: F, b: F, len: usize) {
+        let count = F::cast(len);
+        let delta_a = a - self.mean_a;
+        let delta_b = b - self.mean_b;
+        self.mean_a -= delta_a / count;
+        self.mean_b -= delta_b / count;
+        self.m2_a -= delta_a * (a - self.mean_a);
+        self.m2_b -= delta_b * (b - self.mean_b);
+        self.co_moment -= delta_a * (b - self.mean_b);
+    }
+}
+
+impl<F: Float> Next<(F, F)> for Correlation<F> {
+    type Output = F;
+
+    fn next(&mut self, &(a, b): &(F, F)) -> F {
+        let newest = self.window.newest().copied();
+        self.runs = match newest {
+            Some((last_a, last_b)) => (
+                if last_a == a {
+                    self.runs.0.saturating_add(1)
+                } else {
+                    1
+                },
+                if last_b == b {
+                    self.runs.1.saturating_add(1)
+                } else {
+                    1
+                },
+            ),
+            None => (1, 1),
+        };
+        if let Some((old_a, old_b)) = self.window.push((a, b)) {
+            self.remove(old_a, old_b, self.window.len() - 1);
+        }
+        self.add(a, b, self.window.len());
+
+        if self.window.is_full() {
+            self.pushes += 1;
+            if self.pushes >= self.n {
+                self.pushes = 0;
+                self.refresh();
+            }
+        }
+        let len = self.window.len() as u32;
+        if self.runs.0 >= len || self.runs.1 >= len {
+            // a flat series has no variance and no covariance with the other one
+            if self.runs.0 >= len {
+                self.mean_a = a;
+                self.m2_a = F::zero();
+            }
+            if self.runs.1 >= len {
+                self.mean_b = b;
+                self.m2_b = F::zero();
+            }
+            self.co_moment = F::zero();
+        }
+        self.correlation()
+    }
+}
+
+impl<F: Float> Reset for Correlation<F> {
+    fn reset(&mut self) {
+        self.window.clear();
+        self.mean_a = F::zero();
+        self.mean_b = F::zero();
+        self.m2_a = F::zero();
+        self.m2_b = F::zero();
+        self.co_moment = F::zero();
+        self.runs = (0, 0);
+        self.pushes = 0;
+    }
+}
+
+impl<F: Float> Warmup for Correlation<F> {
+    fn warmup_period(&self) -> u32 {
+        self.n
+    }
+
+    fn is_ready(&self) -> bool {
+        self.window.is_full()
+    }
+}
+
+impl Default for Correlation {
+    fn default() -> Self {
+        Self::new(20).unwrap()
+    }
+}
+
+impl<F: Float> fmt::Display for Correlation<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CORR({})", self.n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_new() {
+        assert!(Correlation::new(0).is_err());
+        assert!(Correlation::new(1).is_err());
+        assert!(Correlation::new(2).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut corr = Correlation::new(4).unwrap();
+        assert_eq!(corr.next(&(1.0, 4.0)), 0.0);
+        assert_eq!(round(corr.next(&(2.0, 2.0))), -1.0);
+        assert_eq!(round(corr.next(&(3.0, 3.0))), -0.5);
+        assert_eq!(round(corr.next(&(4.0, 1.0))), -0.8);
+        assert_eq!(round(corr.covariance()), -1.0);
+        // (2, 2), (3, 3), (4, 1), (5, 6)
+        assert_eq!(round(corr.next(&(5.0, 6.0))), 0.598);
+        assert_eq!(corr.covariance(), 1.25);
+    }
+
+    #[test]
+    fn test_matches_batch() {
+        fn batch(pairs: &[(f64, f64)]) -> f64 {
+            let n = pairs.len() as f64;
+            let mean_a = pairs.iter().map(|p| p.0).sum::<f64>() / n;
+            let mean_b = pairs.iter().map(|p| p.1).sum::<f64>() / n;
+            let cov: f64 = pairs.iter().map(|p| (p.0 - mean_a) * (p.1 - mean_b)).sum();
+            let var_a: f64 = pairs.iter().map(|p| (p.0 - mean_a).powi(2)).sum();
+            let var_b: f64 = pairs.iter().map(|p| (p.1 - mean_b).powi(2)).sum();
+            cov / (var_a * var_b).sqrt()
+        }
+
+        let pairs: Vec<(f64, f64)> = (0..50)
+            .map(|i| {
+                let x = i as f64;
+                (x.sin() * 10.0 + 100.0, (x * 0.7).cos() * 5.0 + x)
+            })
+            .collect();
+        let mut corr = Correlation::new(10).unwrap();
+        for (i, pair) in pairs.iter().enumerate() {
+            let value = corr.next(pair);
+            if i >= 9 {
+                assert!((value - batch(&pairs[i - 9..=i])).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_constant_series() {
+        let mut corr = Correlation::new(3).unwrap();
+        for &b in &[1.0, 5.0, 2.0, 7.0] {
+            assert_eq!(corr.next(&(4.2, b)), 0.0);
+        }
+    }
+
+    #[test]
+    fn test_flat_after_varying() {
+        let mut corr = Correlation::new(5).unwrap();
+        for i in 0..50 {
+            let x = i as f64;
+            corr.next(&(1e4 + x.sin() * 123.4, x.cos()));
+        }
+        for i in 0..5 {
+            corr.next(&(1e4 + 0.1, i as f64));
+        }
+        assert_eq!(corr.correlation(), 0.0);
+        assert_eq!(corr.covariance(), 0.0);
+        // varying again
+        assert!(corr.next(&(1e4, 5.0)) < 0.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut corr = Correlation::new(3).unwrap();
+        corr.next(&(1.0, 2.0));
+        corr.next(&(2.0, 4.0));
+        corr.next(&(3.0, 5.0));
+        assert!(corr.is_ready());
+
+        corr.reset();
+        assert!(!corr.is_ready());
+        assert_eq!(corr.next(&(3.0, 6.0)), 0.0);
+        assert_eq!(corr.next(&(1.0, 7.0)), -1.0);
+    }
+
+    #[test]
+    fn test_default() {
+        Correlation::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", Correlation::new(5).unwrap()), "CORR(5)");
+    }
+}
@@ -0,0 +1,223 @@
+use core::fmt;
+
+use crate::{Calculate, Close, Finite, Float, Next, Reset, Warmup};
+
+/// Cumulative statistics of all values since the last reset.
+///
+/// Unlike the windowed indicators it never forgets a value, which makes it suitable for
+/// anchored analyses, e.g. statistics since the session open or since an event. The mean and
+/// variance are updated with Welford's algorithm, which stays accurate over long series.
+///
+/// # Output
+///
+/// * _count_ - number of values consumed
+/// * _mean_ - arithmetic mean
+/// * _variance_ - population variance, like in [StandardDeviation](struct.StandardDeviation.html)
+/// * _min_, _max_ - lowest and highest value
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::CumulativeStats;
+/// use ta::Calculate;
+///
+/// let mut stats = CumulativeStats::new();
+/// stats.calc(2.0);
+/// stats.calc(4.0);
+/// let out = stats.calc(9.0);
+/// assert_eq!(out.count, 3);
+/// assert_eq!(out.mean, 5.0);
+/// assert_eq!(out.min, 2.0);
+/// assert_eq!(out.max, 9.0);
+/// ```
+///
+/// # Links
+///
+/// * [Welford's online algorithm, Wikipedia](https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance#Welford's_online_algorithm)
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CumulativeStats<F = f64> {
+    count: u64,
+    mean: F,
+    m2: F,
+    min: F,
+    max: F,
+}
+
+/// Output of [CumulativeStats](struct.CumulativeStats.html).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CumulativeStatsOutput<F = f64> {
+    pub count: u64,
+    pub mean: F,
+    pub variance: F,
+    pub min: F,
+    pub max: F,
+}
+
+impl<F: Finite> Finite for CumulativeStatsOutput<F> {
+    fn is_finite(&self) -> bool {
+        self.mean.is_finite()
+            && self.variance.is_finite()
+            && self.min.is_finite()
+            && self.max.is_finite()
+    }
+}
+
+impl<F: Float> CumulativeStatsOutput<F> {
+    /// Population standard deviation.
+    pub fn std_dev(&self) -> F {
+        self.variance.sqrt()
+    }
+}
+
+impl CumulativeStats {
+    pub fn new() -> Self {
+        Self::with_type()
+    }
+}
+
+impl<F: Float> CumulativeStats<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type() -> Self {
+        Self {
+            count: 0,
+            mean: F::zero(),
+            m2: F::zero(),
+            min: F::infinity(),
+            max: F::neg_infinity(),
+        }
+    }
+
+    /// Statistics of the values consumed so far.
+    pub fn stats(&self) -> CumulativeStatsOutput<F> {
+        let variance = if self.count == 0 {
+            F::zero()
+        } else {
+            self.m2 / F::cast(self.count)
+        };
+        CumulativeStatsOutput {
+            count: self.count,
+            mean: self.mean,
+            variance,
+            min: self.min,
+            max: self.max,
+        }
+    }
+}
+
+impl<F: Float> Calculate<F> for CumulativeStats<F> {
+    type Output = CumulativeStatsOutput<F>;
+
+    fn calc(&mut self, input: F) -> Self::Output {
+        self.count = self.count.saturating_add(1);
+        let delta = input - self.mean;
+        self.mean += delta / F::cast(self.count);
+        self.m2 += delta * (input - self.mean);
+        self.min = self.min.min(input);
+        self.max = self.max.max(input);
+        self.stats()
+    }
+}
+
+impl<F: Float, T: Close<F>> Next<T> for CumulativeStats<F> {
+    type Output = CumulativeStatsOutput<F>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.calc(input.close())
+    }
+}
+
+impl<F: Float> Reset for CumulativeStats<F> {
+    fn reset(&mut self) {
+        *self = Self::with_type();
+    }
+}
+
+impl<F: Float> Warmup for CumulativeStats<F> {
+    fn warmup_period(&self) -> u32 {
+        1
+    }
+
+    fn is_ready(&self) -> bool {
+        self.count > 0
+    }
+}
+
+impl Default for CumulativeStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: Float> fmt::Display for CumulativeStats<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CUMULATIVE_STATS()")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(CumulativeStats);
+
+    #[test]
+    fn test_next() {
+        let mut stats = CumulativeStats::new();
+
+        let out = stats.calc(4.0);
+        assert_eq!((out.count, out.mean, out.variance), (1, 4.0, 0.0));
+        assert_eq!((out.min, out.max), (4.0, 4.0));
+
+        stats.calc(2.0);
+        stats.calc(6.0);
+        let out = stats.next(&Bar::new().close(8.0));
+        assert_eq!(out.count, 4);
+        assert_eq!(out.mean, 5.0);
+        assert_eq!(out.variance, 5.0);
+        assert_eq!(round(out.std_dev()), 2.236);
+        assert_eq!((out.min, out.max), (2.0, 8.0));
+    }
+
+    #[test]
+    fn test_long_series() {
+        let mut stats = CumulativeStats::new();
+        let mut out = stats.stats();
+        for i in 0..100_000 {
+            out = stats.calc(1e9 + (i % 2) as f64);
+        }
+        assert_eq!(out.mean, 1e9 + 0.5);
+        assert_eq!(round(out.variance), 0.25);
+    }
+
+    #[test]
+    fn test_empty() {
+        let stats = CumulativeStats::new();
+        let out = stats.stats();
+        assert_eq!(out.count, 0);
+        assert_eq!(out.variance, 0.0);
+        assert_eq!(out.min, f64::INFINITY);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut stats = CumulativeStats::new();
+        stats.calc(10.0);
+        stats.calc(-3.0);
+
+        stats.reset();
+        let out = stats.calc(1.0);
+        assert_eq!((out.count, out.mean, out.min, out.max), (1, 1.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            format!("{}", CumulativeStats::default()),
+            "CUMULATIVE_STATS()"
+        );
+    }
+}
@@ -0,0 +1,284 @@
+use core::fmt;
+
+use crate::errors::*;
+use crate::helpers::max3;
+use crate::indicators::SmoothedMovingAverage;
+use crate::{Calculate, Close, Finite, Float, High, Low, Next, Reset, Warmup};
+
+/// Directional Movement Index (DMI) by J. Welles Wilder, with the Average Directional
+/// Index (ADX).
+///
+/// +DI and −DI measure the strength of the upward and the downward movement, their
+/// crossovers signal trend changes. The ADX measures the strength of the trend regardless
+/// of its direction.
+///
+/// # Formula
+///
+/// * _up_ = high<sub>t</sub> - high<sub>t-1</sub>, _down_ = low<sub>t-1</sub> - low<sub>t</sub>
+/// * +DM = _up_ if _up_ > _down_ and _up_ > 0, otherwise 0
+/// * −DM = _down_ if _down_ > _up_ and _down_ > 0, otherwise 0
+/// * +DI = 100 * SMMA(+DM) / SMMA(TR)
+/// * −DI = 100 * SMMA(−DM) / SMMA(TR)
+/// * DX = 100 * |+DI - −DI| / (+DI + −DI)
+/// * ADX = SMMA(DX)
+///
+/// Where _SMMA_ is the [smoothed moving average](struct.SmoothedMovingAverage.html) of
+/// _length_ periods and _TR_ is the [true range](struct.TrueRange.html). All lines are 0
+/// on the first bar.
+///
+/// # Parameters
+///
+/// * _length_ - number of periods (integer greater than 0). Default is 14.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::DirectionalMovementIndex;
+/// use ta::{DataItem, Next};
+///
+/// let mut dmi = DirectionalMovementIndex::new(2).unwrap();
+/// let bar = |high: f64, low: f64, close: f64| {
+///     DataItem::builder()
+///         .open(close)
+///         .high(high)
+///         .low(low)
+///         .close(close)
+///         .volume(0.0)
+///         .build()
+///         .unwrap()
+/// };
+///
+/// dmi.next(&bar(11.0, 9.0, 10.0));
+/// dmi.next(&bar(13.0, 11.0, 12.0));
+/// let out = dmi.next(&bar(12.0, 10.0, 10.5));
+/// assert_eq!(out.plus, 40.0);
+/// assert_eq!(out.minus, 20.0);
+/// ```
+///
+/// # Links
+///
+/// * [Average directional movement index, Wikipedia](https://en.wikipedia.org/wiki/Average_directional_movement_index)
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DirectionalMovementIndex<F = f64> {
+    length: u32,
+    prev: Option<(F, F, F)>,
+    plus_dm: SmoothedMovingAverage<F>,
+    minus_dm: SmoothedMovingAverage<F>,
+    true_range: SmoothedMovingAverage<F>,
+    adx: SmoothedMovingAverage<F>,
+    count: u32,
+}
+
+/// Output of [DirectionalMovementIndex](struct.DirectionalMovementIndex.html).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DirectionalMovementIndexOutput<F = f64> {
+    pub plus: F,
+    pub minus: F,
+    pub adx: F,
+}
+
+impl<F: Finite> Finite for DirectionalMovementIndexOutput<F> {
+    fn is_finite(&self) -> bool {
+        self.plus.is_finite() && self.minus.is_finite() && self.adx.is_finite()
+    }
+}
+
+impl DirectionalMovementIndex {
+    pub fn new(length: u32) -> Result<Self> {
+        Self::with_type(length)
+    }
+}
+
+impl<F: Float> DirectionalMovementIndex<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(length: u32) -> Result<Self> {
+        Ok(Self {
+            length,
+            prev: None,
+            plus_dm: SmoothedMovingAverage::with_type(length)?,
+            minus_dm: SmoothedMovingAverage::with_type(length)?,
+            true_range: SmoothedMovingAverage::with_type(length)?,
+            adx: SmoothedMovingAverage::with_type(length)?,
+            count: 0,
+        })
+    }
+
+    pub fn length(&self) -> u32 {
+        self.length
+    }
+
+    fn update(&mut self, high: F, low: F, close: F) -> DirectionalMovementIndexOutput<F> {
+        self.count = self.count.saturating_add(1);
+        let (prev_high, prev_low, prev_close) = match self.prev.replace((high, low, close)) {
+            Some(prev) => prev,
+            None => {
+                return DirectionalMovementIndexOutput {
+                    plus: F::zero(),
+                    minus: F::zero(),
+                    adx: F::zero(),
+                }
+            }
+        };
+
+        let up = high - prev_high;
+        let down = prev_low - low;
+        let plus_dm = if up > down && up > F::zero() {
+            up
+        } else {
+            F::zero()
+        };
+        let minus_dm = if down > up && down > F::zero() {
+            down
+        } else {
+            F::zero()
+        };
+        let range = max3(
+            high - low,
+            (high - prev_close).abs(),
+            (low - prev_close).abs(),
+        );
+
+        let plus_dm = self.plus_dm.calc(plus_dm);
+        let minus_dm = self.minus_dm.calc(minus_dm);
+        let range = self.true_range.calc(range);
+
+        let hundred = F::cast(100.0);
+        // the averages are rounded separately, a move may come out a hair above the range
+        let (plus, minus) = if range > F::zero() {
+            (
+                (hundred * plus_dm / range).min(hundred),
+                (hundred * minus_dm / range).min(hundred),
+            )
+        } else {
+            (F::zero(), F::zero())
+        };
+        let dx = if plus + minus > F::zero() {
+            hundred * (plus - minus).abs() / (plus + minus)
+        } else {
+            F::zero()
+        };
+
+        DirectionalMovementIndexOutput {
+            plus,
+            minus,
+            adx: self.adx.calc(dx).min(hundred),
+        }
+    }
+}
+
+impl<F: Float> Calculate<F> for DirectionalMovementIndex<F> {
+    type Output = DirectionalMovementIndexOutput<F>;
+
+    fn calc(&mut self, input: F) -> Self::Output {
+        self.update(input, input, input)
+    }
+}
+
+impl<F: Float, T: High<F> + Low<F> + Close<F>> Next<T> for DirectionalMovementIndex<F> {
+    type Output = DirectionalMovementIndexOutput<F>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.update(input.high(), input.low(), input.close())
+    }
+}
+
+impl<F: Float> Reset for DirectionalMovementIndex<F> {
+    fn reset(&mut self) {
+        self.prev = None;
+        self.plus_dm.reset();
+        self.minus_dm.reset();
+        self.true_range.reset();
+        self.adx.reset();
+        self.count = 0;
+    }
+}
+
+impl<F: Float> Warmup for DirectionalMovementIndex<F> {
+    // the DI lines need _length_ movements after the first bar, the ADX _length_ DI values
+    fn warmup_period(&self) -> u32 {
+        2 * self.length
+    }
+
+    fn is_ready(&self) -> bool {
+        self.count >= self.warmup_period()
+    }
+}
+
+impl Default for DirectionalMovementIndex {
+    fn default() -> Self {
+        Self::new(14).unwrap()
+    }
+}
+
+impl<F: Float> fmt::Display for DirectionalMovementIndex<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "DMI({})", self.length)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(DirectionalMovementIndex);
+
+    fn bar(high: f64, low: f64, close: f64) -> Bar {
+        Bar::new().high(high).low(low).close(close)
+    }
+
+    fn round3(out: DirectionalMovementIndexOutput) -> (f64, f64, f64) {
+        (round(out.plus), round(out.minus), round(out.adx))
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(DirectionalMovementIndex::new(0).is_err());
+        assert!(DirectionalMovementIndex::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut dmi = DirectionalMovementIndex::new(2).unwrap();
+
+        assert_eq!(round3(dmi.next(&bar(11.0, 9.0, 10.0))), (0.0, 0.0, 0.0));
+        assert_eq!(
+            round3(dmi.next(&bar(13.0, 11.0, 12.0))),
+            (66.667, 0.0, 100.0)
+        );
+        assert_eq!(
+            round3(dmi.next(&bar(12.0, 10.0, 10.5))),
+            (40.0, 20.0, 66.667)
+        );
+        assert_eq!(round3(dmi.next(&bar(10.0, 8.0, 8.5))), (20.0, 50.0, 54.762));
+        assert_eq!(
+            round3(dmi.next(&bar(11.0, 9.0, 10.5))),
+            (30.0, 25.0, 31.926)
+        );
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut dmi = DirectionalMovementIndex::new(2).unwrap();
+        dmi.next(&bar(11.0, 9.0, 10.0));
+        dmi.next(&bar(13.0, 11.0, 12.0));
+
+        dmi.reset();
+        assert_eq!(round3(dmi.next(&bar(13.0, 11.0, 12.0))), (0.0, 0.0, 0.0));
+        assert_eq!(round3(dmi.next(&bar(12.0, 10.0, 10.5))), (0.0, 50.0, 100.0));
+    }
+
+    #[test]
+    fn test_default() {
+        DirectionalMovementIndex::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let dmi = DirectionalMovementIndex::new(10).unwrap();
+        assert_eq!(format!("{}", dmi), "DMI(10)");
+    }
+}
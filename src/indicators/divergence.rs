@@ -0,0 +1,358 @@
+use core::fmt;
+
+use crate::errors::*;
+use crate::swings::SwingWindow;
+use crate::{Finite, Float, Next, Reset, Warmup};
+
+/// Kind of a [Divergence](struct.Divergence.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DivergenceKind {
+    /// Lower low of the price with a higher low of the oscillator, a possible reversal up.
+    RegularBullish,
+    /// Higher low of the price with a lower low of the oscillator, a possible continuation
+    /// of an uptrend.
+    HiddenBullish,
+    /// Higher high of the price with a lower high of the oscillator, a possible reversal
+    /// down.
+    RegularBearish,
+    /// Lower high of the price with a higher high of the oscillator, a possible continuation
+    /// of a downtrend.
+    HiddenBearish,
+}
+
+impl DivergenceKind {
+    pub fn is_bullish(self) -> bool {
+        matches!(
+            self,
+            DivergenceKind::RegularBullish | DivergenceKind::HiddenBullish
+        )
+    }
+
+    pub fn is_regular(self) -> bool {
+        matches!(
+            self,
+            DivergenceKind::RegularBullish | DivergenceKind::RegularBearish
+        )
+    }
+}
+
+/// Divergence between two swings, returned by [Divergence](struct.Divergence.html).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DivergenceEvent<F = f64> {
+    pub kind: DivergenceKind,
+    /// Price at the previous swing.
+    pub previous_price: F,
+    /// Oscillator at the previous swing.
+    pub previous_oscillator: F,
+    /// Price at the latest swing.
+    pub price: F,
+    /// Oscillator at the latest swing.
+    pub oscillator: F,
+    /// Number of bars between the two swings.
+    pub bars: u32,
+}
+
+impl<F: Float> Finite for DivergenceEvent<F> {
+    fn is_finite(&self) -> bool {
+        self.previous_price.is_finite()
+            && self.previous_oscillator.is_finite()
+            && self.price.is_finite()
+            && self.oscillator.is_finite()
+    }
+}
+
+/// Divergence between the price and an oscillator.
+///
+/// Finds the swing highs and lows of the price, bars higher or lower than the _strength_
+/// bars on both sides, and compares each swing with the previous swing of the same kind,
+/// at most _max_span_ bars before it. A divergence is found when the oscillator, at the
+/// bars of the two swings, disagrees with the price:
+///
+/// * _regular bullish_ - the price makes a lower low, the oscillator a higher low
+/// * _hidden bullish_ - the price makes a higher low, the oscillator a lower low
+/// * _regular bearish_ - the price makes a higher high, the oscillator a lower high
+/// * _hidden bearish_ - the price makes a lower high, the oscillator a higher high
+///
+/// The input is a pair `(price, oscillator)`, e.g. the close and the output of a
+/// [FastStochastic](struct.FastStochastic.html) or a
+/// [RateOfChange](struct.RateOfChange.html) on the same bar. A swing is confirmed only
+/// _strength_ bars after it happened, so the divergence is returned with that delay, and
+/// `None` on all other bars.
+///
+/// # Parameters
+///
+/// * _strength_ - number of bars on each side of a swing (integer greater than 0).
+///   Default is 5.
+/// * _max_span_ - maximum number of bars between two compared swings (integer greater
+///   than 0). Default is 60.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{Divergence, DivergenceKind};
+/// use ta::Next;
+///
+/// let mut divergence = Divergence::new(1, 10).unwrap();
+/// // price and oscillator
+/// for bar in &[(10.0, 50.0), (12.0, 70.0), (11.0, 60.0), (13.0, 65.0)] {
+///     assert_eq!(divergence.next(bar), None);
+/// }
+///
+/// // confirms the swing high at 13.0, above the one at 12.0 with a lower oscillator
+/// let event = divergence.next(&(12.0, 55.0)).unwrap();
+/// assert_eq!(event.kind, DivergenceKind::RegularBearish);
+/// assert_eq!((event.previous_price, event.price), (12.0, 13.0));
+/// assert_eq!(event.bars, 2);
+/// ```
+///
+/// # Links
+///
+/// * [Divergence, Investopedia](https://www.investopedia.com/terms/d/divergence.asp)
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Divergence<F = f64> {
+    strength: u32,
+    max_span: u32,
+    swings: SwingWindow<(F, F)>,
+    // bar number, price and oscillator of the latest swing high and low
+    high: Option<(u64, F, F)>,
+    low: Option<(u64, F, F)>,
+    count: u64,
+}
+
+impl Divergence {
+    pub fn new(strength: u32, max_span: u32) -> Result<Self> {
+        Self::with_type(strength, max_span)
+    }
+}
+
+impl<F: Float> Divergence<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(strength: u32, max_span: u32) -> Result<Self> {
+        if strength == 0 || max_span == 0 {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        Ok(Self {
+            strength,
+            max_span,
+            swings: SwingWindow::new(strength, strength),
+            high: None,
+            low: None,
+            count: 0,
+        })
+    }
+
+    pub fn strength(&self) -> u32 {
+        self.strength
+    }
+
+    pub fn max_span(&self) -> u32 {
+        self.max_span
+    }
+
+    // compares the swing at bar `index` with the previous swing of its kind
+    fn compare(
+        &self,
+        previous: Option<(u64, F, F)>,
+        (index, price, oscillator): (u64, F, F),
+        high: bool,
+    ) -> Option<DivergenceEvent<F>> {
+        let (previous_index, previous_price, previous_oscillator) = previous?;
+        let bars = index - previous_index;
+        if bars > u64::from(self.max_span) {
+            return None;
+        }
+
+        let kind = match (high, price > previous_price, price < previous_price) {
+            (true, true, _) if oscillator < previous_oscillator => DivergenceKind::RegularBearish,
+            (true, _, true) if oscillator > previous_oscillator => DivergenceKind::HiddenBearish,
+            (false, _, true) if oscillator > previous_oscillator => DivergenceKind::RegularBullish,
+            (false, true, _) if oscillator < previous_oscillator => DivergenceKind::HiddenBullish,
+            _ => return None,
+        };
+        Some(DivergenceEvent {
+            kind,
+            previous_price,
+            previous_oscillator,
+            price,
+            oscillator,
+            bars: bars as u32,
+        })
+    }
+}
+
+impl<F: Float> Next<(F, F)> for Divergence<F> {
+    type Output = Option<DivergenceEvent<F>>;
+
+    fn next(&mut self, input: &(F, F)) -> Self::Output {
+        self.count += 1;
+        let (high, low) = self.swings.push(*input, |bar| bar.0, |bar| bar.0);
+        // bar number of the swings, the window is full once there are any
+        let index = self.count.saturating_sub(u64::from(self.strength));
+
+        // with strength > 0 a single price cannot be both a swing high and a swing low
+        if let Some((price, oscillator)) = high {
+            let swing = (index, price, oscillator);
+            let event = self.compare(self.high, swing, true);
+            self.high = Some(swing);
+            event
+        } else if let Some((price, oscillator)) = low {
+            let swing = (index, price, oscillator);
+            let event = self.compare(self.low, swing, false);
+            self.low = Some(swing);
+            event
+        } else {
+            None
+        }
+    }
+}
+
+impl<F: Float> Reset for Divergence<F> {
+    fn reset(&mut self) {
+        self.swings.clear();
+        self.high = None;
+        self.low = None;
+        self.count = 0;
+    }
+}
+
+impl<F: Float> Warmup for Divergence<F> {
+    fn warmup_period(&self) -> u32 {
+        2 * self.strength + 1
+    }
+
+    fn is_ready(&self) -> bool {
+        self.count >= u64::from(self.warmup_period())
+    }
+}
+
+impl Default for Divergence {
+    fn default() -> Self {
+        Self::new(5, 60).unwrap()
+    }
+}
+
+impl<F: Float> fmt::Display for Divergence<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "DIV({}, {})", self.strength, self.max_span)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(divergence: &mut Divergence, bars: &[(f64, f64)]) -> Vec<DivergenceEvent> {
+        bars.iter().filter_map(|bar| divergence.next(bar)).collect()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(Divergence::new(0, 10).is_err());
+        assert!(Divergence::new(2, 0).is_err());
+        assert!(Divergence::new(1, 1).is_ok());
+    }
+
+    #[test]
+    fn test_bullish() {
+        let mut divergence = Divergence::new(1, 10).unwrap();
+        // lower low of the price at 8.0, higher low of the oscillator
+        let bars = [
+            (10.0, 50.0),
+            (9.0, 30.0),
+            (11.0, 60.0),
+            (8.0, 35.0),
+            (12.0, 70.0),
+        ];
+        let events = run(&mut divergence, &bars);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, DivergenceKind::RegularBullish);
+        assert!(events[0].kind.is_bullish() && events[0].kind.is_regular());
+        assert_eq!(
+            (events[0].previous_oscillator, events[0].oscillator),
+            (30.0, 35.0)
+        );
+
+        // higher low of the price at 10.0, lower low of the oscillator
+        let events = run(&mut divergence, &[(10.0, 20.0), (13.0, 80.0)]);
+        assert_eq!(events[0].kind, DivergenceKind::HiddenBullish);
+        assert_eq!(events[0].bars, 2);
+    }
+
+    #[test]
+    fn test_bearish() {
+        let mut divergence = Divergence::new(1, 10).unwrap();
+        // lower high of the price at 11.0, higher high of the oscillator
+        let bars = [
+            (10.0, 50.0),
+            (12.0, 60.0),
+            (9.0, 30.0),
+            (11.0, 65.0),
+            (8.0, 20.0),
+        ];
+        let events = run(&mut divergence, &bars);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, DivergenceKind::HiddenBearish);
+        assert!(!events[0].kind.is_bullish() && !events[0].kind.is_regular());
+    }
+
+    #[test]
+    fn test_no_divergence() {
+        let mut divergence = Divergence::new(1, 10).unwrap();
+        // the oscillator confirms the higher high
+        let bars = [
+            (10.0, 50.0),
+            (12.0, 60.0),
+            (11.0, 55.0),
+            (13.0, 70.0),
+            (12.0, 60.0),
+        ];
+        assert!(run(&mut divergence, &bars).is_empty());
+    }
+
+    #[test]
+    fn test_max_span() {
+        let bars = [
+            (10.0, 50.0),
+            (12.0, 70.0),
+            (11.0, 60.0),
+            (11.5, 60.0),
+            (13.0, 65.0),
+            (12.0, 55.0),
+        ];
+        assert_eq!(run(&mut Divergence::new(1, 3).unwrap(), &bars).len(), 1);
+        assert!(run(&mut Divergence::new(1, 2).unwrap(), &bars).is_empty());
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut divergence = Divergence::new(1, 10).unwrap();
+        let bars = [(10.0, 50.0), (12.0, 70.0), (11.0, 60.0)];
+        run(&mut divergence, &bars);
+        assert!(divergence.is_ready());
+        divergence.reset();
+        assert!(!divergence.is_ready());
+        // the swing high at 12.0 is forgotten
+        assert!(run(&mut divergence, &[(10.0, 80.0), (13.0, 65.0), (12.0, 55.0)]).is_empty());
+    }
+
+    #[test]
+    fn test_warmup() {
+        let mut divergence = Divergence::new(3, 10).unwrap();
+        for i in 0..7 {
+            assert!(!divergence.is_ready());
+            assert_eq!(divergence.next(&(f64::from(i), 50.0)), None);
+        }
+        assert!(divergence.is_ready());
+    }
+
+    #[test]
+    fn test_default_and_display() {
+        let divergence = Divergence::default();
+        assert_eq!(divergence.warmup_period(), 11);
+        assert_eq!(format!("{}", divergence), "DIV(5, 60)");
+    }
+}
@@ -1,13 +1,14 @@
-use std::collections::VecDeque;
-use std::fmt;
+use core::fmt;
 
 use crate::errors::*;
-use crate::traits::{Calculate, Close, Next, Reset};
+use crate::traits::{Calculate, Close, Float, Next, Reset, Warmup};
+use crate::RingBuffer;
 
 /// Kaufman's Efficiency Ratio (ER).
 ///
 /// It is calculated by dividing the price change over a period by the absolute sum of the price movements that occurred to achieve that change.
 /// The resulting ratio ranges between 0.0 and 1.0 with higher values representing a more efficient or trending market.
+/// A flat period, without any price movement, has a ratio of 1.0.
 ///
 /// # Parameters
 ///
@@ -27,32 +28,42 @@ use crate::traits::{Calculate, Close, Next, Reset};
 /// assert_eq!(er.calc(18.0), 0.8);
 /// assert_eq!(er.calc(19.0), 0.75);
 /// ```
-
-pub struct EfficiencyRatio {
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EfficiencyRatio<F = f64> {
     length: u32,
-    prices: VecDeque<f64>,
+    prices: RingBuffer<F>,
 }
 
 impl EfficiencyRatio {
     pub fn new(length: u32) -> Result<Self> {
+        Self::with_type(length)
+    }
+}
+
+impl<F: Float> EfficiencyRatio<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(length: u32) -> Result<Self> {
         if length == 0 {
             Err(Error::from_kind(ErrorKind::InvalidParameter))
         } else {
             let indicator = Self {
-                length: length,
-                prices: VecDeque::with_capacity(length as usize + 1),
+                length,
+                prices: RingBuffer::new(length as usize + 1),
             };
             Ok(indicator)
         }
     }
 }
 
-impl Calculate for EfficiencyRatio {
-    fn calc(&mut self, input: f64) -> f64 {
-        self.prices.push_back(input);
+impl<F: Float> Calculate<F> for EfficiencyRatio<F> {
+    type Output = F;
+
+    fn calc(&mut self, input: F) -> F {
+        self.prices.push(input);
 
         if self.prices.len() <= 2 {
-            return 1.0;
+            return F::one();
         }
 
         let first = self.prices[0];
@@ -62,44 +73,55 @@ impl Calculate for EfficiencyRatio {
             .prices
             .iter()
             .skip(1)
-            .fold((first, 0.0), |(prev, sum), &val| {
+            .fold((first, F::zero()), |(prev, sum), &val| {
                 (val, sum + (prev - val).abs())
             })
             .1;
 
         // Calculate direction
-        let last_index = self.prices.len() - 1;
-        let direction = (first - self.prices[last_index]).abs();
+        let direction = (first - input).abs();
 
-        // Get rid of the first element
-        if self.prices.len() > (self.length as usize) {
-            self.prices.pop_front();
+        // A flat window moved as efficiently as it could
+        if volatility.is_zero() {
+            return F::one();
         }
 
-        // Return actual efficiency ratio
-        direction / volatility
+        // Return actual efficiency ratio, which rounding may push above 1
+        (direction / volatility).min(F::one())
     }
 }
 
-impl<T: Close> Next<T> for EfficiencyRatio {
-    fn next(&mut self, input: &T) -> f64 {
+impl<F: Float, T: Close<F>> Next<T> for EfficiencyRatio<F> {
+    type Output = F;
+
+    fn next(&mut self, input: &T) -> F {
         self.calc(input.close())
     }
 }
 
-impl Reset for EfficiencyRatio {
+impl<F: Float> Reset for EfficiencyRatio<F> {
     fn reset(&mut self) {
         self.prices.clear();
     }
 }
 
+impl<F: Float> Warmup for EfficiencyRatio<F> {
+    fn warmup_period(&self) -> u32 {
+        self.length + 1
+    }
+
+    fn is_ready(&self) -> bool {
+        self.prices.is_full()
+    }
+}
+
 impl Default for EfficiencyRatio {
     fn default() -> Self {
         Self::new(14).unwrap()
     }
 }
 
-impl fmt::Display for EfficiencyRatio {
+impl<F: Float> fmt::Display for EfficiencyRatio<F> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "ER({})", self.length)
     }
@@ -138,6 +160,14 @@ mod tests {
         assert_eq!(round(er.calc(3.0)), 0.0);
     }
 
+    #[test]
+    fn test_flat() {
+        let mut er = EfficiencyRatio::new(3).unwrap();
+        for _ in 0..5 {
+            assert_eq!(er.calc(2.0), 1.0);
+        }
+    }
+
     #[test]
     fn test_display() {
         let er = EfficiencyRatio::new(17).unwrap();
@@ -0,0 +1,217 @@
+use core::fmt;
+
+use crate::errors::*;
+use crate::indicators::{ExponentialMovingAverage, MovingAverageConvergenceDivergence};
+use crate::{Calculate, Close, Finite, Float, Next, Reset, Warmup};
+
+/// Bar color of the [Elder Impulse System](struct.ElderImpulse.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ElderImpulseColor {
+    /// Both the EMA and the MACD histogram are rising: bulls are in control.
+    Green,
+    /// Both the EMA and the MACD histogram are falling: bears are in control.
+    Red,
+    /// The EMA and the MACD histogram disagree, or there is no previous bar yet.
+    Blue,
+}
+
+impl Finite for ElderImpulseColor {
+    fn is_finite(&self) -> bool {
+        true
+    }
+}
+
+/// Elder Impulse System.
+///
+/// Combines the inertia of the trend, measured by the slope of an
+/// [EMA](struct.ExponentialMovingAverage.html), with the momentum, measured by the slope
+/// of the [MACD](struct.MovingAverageConvergenceDivergence.html) histogram. Each bar is
+/// colored:
+///
+/// * _green_ - EMA and histogram both rise
+/// * _red_ - EMA and histogram both fall
+/// * _blue_ - otherwise
+///
+/// Traditionally shorting is avoided on green bars and buying on red bars.
+///
+/// # Parameters
+///
+/// * _ema_length_ - length of the EMA (integer greater than 0). Default is 13.
+/// * _fast_length_, _slow_length_, _signal_length_ - parameters of the MACD.
+///   Default is 12, 26, 9.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{ElderImpulse, ElderImpulseColor};
+/// use ta::Calculate;
+///
+/// let mut impulse = ElderImpulse::new(3, 3, 6, 4).unwrap();
+/// assert_eq!(impulse.calc(2.0), ElderImpulseColor::Blue);
+/// assert_eq!(impulse.calc(3.0), ElderImpulseColor::Green);
+/// ```
+///
+/// # Links
+///
+/// * [Elder Impulse System, StockCharts](https://school.stockcharts.com/doku.php?id=chart_analysis:elder_impulse_system)
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ElderImpulse<F = f64> {
+    ema: ExponentialMovingAverage<F>,
+    macd: MovingAverageConvergenceDivergence<F>,
+    prev: Option<(F, F)>,
+    count: u32,
+}
+
+impl ElderImpulse {
+    pub fn new(
+        ema_length: u32,
+        fast_length: u32,
+        slow_length: u32,
+        signal_length: u32,
+    ) -> Result<Self> {
+        Self::with_type(ema_length, fast_length, slow_length, signal_length)
+    }
+}
+
+impl<F: Float> ElderImpulse<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(
+        ema_length: u32,
+        fast_length: u32,
+        slow_length: u32,
+        signal_length: u32,
+    ) -> Result<Self> {
+        Ok(Self {
+            ema: ExponentialMovingAverage::with_type(ema_length)?,
+            macd: MovingAverageConvergenceDivergence::with_type(
+                fast_length,
+                slow_length,
+                signal_length,
+            )?,
+            prev: None,
+            count: 0,
+        })
+    }
+}
+
+impl<F: Float> Calculate<F> for ElderImpulse<F> {
+    type Output = ElderImpulseColor;
+
+    fn calc(&mut self, input: F) -> ElderImpulseColor {
+        self.count = self.count.saturating_add(1);
+        let ema = self.ema.calc(input);
+        let histogram = self.macd.calc(input).histogram;
+
+        let color = match self.prev {
+            Some((prev_ema, prev_histogram)) if ema > prev_ema && histogram > prev_histogram => {
+                ElderImpulseColor::Green
+            }
+            Some((prev_ema, prev_histogram)) if ema < prev_ema && histogram < prev_histogram => {
+                ElderImpulseColor::Red
+            }
+            _ => ElderImpulseColor::Blue,
+        };
+        self.prev = Some((ema, histogram));
+        color
+    }
+}
+
+impl<F: Float, T: Close<F>> Next<T> for ElderImpulse<F> {
+    type Output = ElderImpulseColor;
+
+    fn next(&mut self, input: &T) -> ElderImpulseColor {
+        self.calc(input.close())
+    }
+}
+
+impl<F: Float> Reset for ElderImpulse<F> {
+    fn reset(&mut self) {
+        self.ema.reset();
+        self.macd.reset();
+        self.prev = None;
+        self.count = 0;
+    }
+}
+
+impl<F: Float> Warmup for ElderImpulse<F> {
+    // the slopes need one more bar after both lines are ready
+    fn warmup_period(&self) -> u32 {
+        self.ema.warmup_period().max(self.macd.warmup_period()) + 1
+    }
+
+    fn is_ready(&self) -> bool {
+        self.count >= self.warmup_period()
+    }
+}
+
+impl Default for ElderImpulse {
+    fn default() -> Self {
+        Self::new(13, 12, 26, 9).unwrap()
+    }
+}
+
+impl<F: Float> fmt::Display for ElderImpulse<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "ELDER_IMPULSE({}, {}, {}, {})",
+            self.ema.length(),
+            self.macd.fast_length(),
+            self.macd.slow_length(),
+            self.macd.signal_length()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+    use ElderImpulseColor::*;
+
+    test_indicator!(ElderImpulse);
+
+    #[test]
+    fn test_new() {
+        assert!(ElderImpulse::new(0, 12, 26, 9).is_err());
+        assert!(ElderImpulse::new(13, 0, 26, 9).is_err());
+        assert!(ElderImpulse::new(13, 12, 26, 9).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut impulse = ElderImpulse::new(3, 3, 6, 4).unwrap();
+        let colors: Vec<_> = [2.0, 3.0, 4.2, 7.0, 6.7, 6.5, 5.0]
+            .iter()
+            .map(|&close| impulse.calc(close))
+            .collect();
+        assert_eq!(colors, vec![Blue, Green, Green, Green, Blue, Blue, Red]);
+        assert_eq!(impulse.next(&Bar::new().close(4.0)), Red);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut impulse = ElderImpulse::new(3, 3, 6, 4).unwrap();
+        impulse.calc(2.0);
+        impulse.calc(3.0);
+
+        impulse.reset();
+        assert_eq!(impulse.calc(3.0), Blue);
+    }
+
+    #[test]
+    fn test_default() {
+        ElderImpulse::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            format!("{}", ElderImpulse::default()),
+            "ELDER_IMPULSE(13, 12, 26, 9)"
+        );
+    }
+}
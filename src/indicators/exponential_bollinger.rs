@@ -0,0 +1,191 @@
+use core::fmt;
+
+use crate::errors::*;
+use crate::indicators::ExponentialMovingVariance;
+use crate::{Calculate, Close, Finite, Float, Next, Reset, Warmup};
+
+/// Exponential Bollinger Bands.
+///
+/// Bollinger Bands built on an exponentially weighted mean and standard deviation
+/// instead of a simple moving average and a windowed standard deviation. The bands are
+/// smoother and react to new data without the jump caused by old values leaving a hard
+/// window.
+///
+/// # Formula
+///
+/// * _middle_ - exponentially weighted mean of the input
+/// * _upper_ = _middle_ + _k_ * σ
+/// * _lower_ = _middle_ - _k_ * σ
+///
+/// Where σ is the [exponentially weighted standard deviation](struct.ExponentialMovingVariance.html).
+///
+/// # Parameters
+///
+/// * _length_ - number of periods (integer greater than 0). Default is 20.
+/// * _multiplier_ - width of the bands in standard deviations (greater than 0). Default is 2.0.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::ExponentialBollinger;
+/// use ta::Calculate;
+///
+/// let mut bb = ExponentialBollinger::new(3, 2.0).unwrap();
+/// bb.calc(2.0);
+/// let out = bb.calc(4.0);
+/// assert_eq!(out.middle, 3.0);
+/// assert_eq!(out.upper, 5.0);
+/// assert_eq!(out.lower, 1.0);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExponentialBollinger<F = f64> {
+    multiplier: F,
+    variance: ExponentialMovingVariance<F>,
+}
+
+/// Output of [ExponentialBollinger](struct.ExponentialBollinger.html).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExponentialBollingerOutput<F = f64> {
+    pub upper: F,
+    pub middle: F,
+    pub lower: F,
+}
+
+impl<F: Finite> Finite for ExponentialBollingerOutput<F> {
+    fn is_finite(&self) -> bool {
+        self.upper.is_finite() && self.middle.is_finite() && self.lower.is_finite()
+    }
+}
+
+impl ExponentialBollinger {
+    pub fn new(length: u32, multiplier: f64) -> Result<Self> {
+        Self::with_type(length, multiplier)
+    }
+}
+
+impl<F: Float> ExponentialBollinger<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(length: u32, multiplier: F) -> Result<Self> {
+        if multiplier <= F::zero() {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        Ok(Self {
+            multiplier,
+            variance: ExponentialMovingVariance::with_type(length)?,
+        })
+    }
+
+    pub fn length(&self) -> u32 {
+        self.variance.length()
+    }
+
+    pub fn multiplier(&self) -> F {
+        self.multiplier
+    }
+}
+
+impl<F: Float> Calculate<F> for ExponentialBollinger<F> {
+    type Output = ExponentialBollingerOutput<F>;
+
+    fn calc(&mut self, input: F) -> Self::Output {
+        self.variance.calc(input);
+        let middle = self.variance.mean();
+        let width = self.multiplier * self.variance.std_dev();
+        ExponentialBollingerOutput {
+            upper: middle + width,
+            middle,
+            lower: middle - width,
+        }
+    }
+}
+
+impl<F: Float, T: Close<F>> Next<T> for ExponentialBollinger<F> {
+    type Output = ExponentialBollingerOutput<F>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.calc(input.close())
+    }
+}
+
+impl<F: Float> Reset for ExponentialBollinger<F> {
+    fn reset(&mut self) {
+        self.variance.reset();
+    }
+}
+
+impl<F: Float> Warmup for ExponentialBollinger<F> {
+    fn warmup_period(&self) -> u32 {
+        self.variance.warmup_period()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.variance.is_ready()
+    }
+}
+
+impl Default for ExponentialBollinger {
+    fn default() -> Self {
+        Self::new(20, 2.0).unwrap()
+    }
+}
+
+impl<F: Float> fmt::Display for ExponentialBollinger<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "EBB({}, {})", self.length(), self.multiplier)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(ExponentialBollinger);
+
+    #[test]
+    fn test_new() {
+        assert!(ExponentialBollinger::new(0, 2.0).is_err());
+        assert!(ExponentialBollinger::new(1, 0.0).is_err());
+        assert!(ExponentialBollinger::new(1, 2.0).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut bb = ExponentialBollinger::new(3, 2.0).unwrap();
+
+        let out = bb.calc(2.0);
+        assert_eq!((out.lower, out.middle, out.upper), (2.0, 2.0, 2.0));
+
+        let out = bb.calc(4.0);
+        assert_eq!((out.lower, out.middle, out.upper), (1.0, 3.0, 5.0));
+
+        let out = bb.next(&Bar::new().close(6.0));
+        assert_eq!(out.middle, 4.5);
+        assert_eq!(round(out.upper), 7.817);
+        assert_eq!(round(out.lower), 1.183);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut bb = ExponentialBollinger::new(3, 2.0).unwrap();
+        bb.calc(2.0);
+        bb.calc(4.0);
+
+        bb.reset();
+        let out = bb.calc(10.0);
+        assert_eq!((out.lower, out.middle, out.upper), (10.0, 10.0, 10.0));
+    }
+
+    #[test]
+    fn test_default() {
+        ExponentialBollinger::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let bb = ExponentialBollinger::new(10, 2.5).unwrap();
+        assert_eq!(format!("{}", bb), "EBB(10, 2.5)");
+    }
+}
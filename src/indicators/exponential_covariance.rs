@@ -0,0 +1,312 @@
+use core::fmt;
+
+use crate::errors::*;
+use crate::{Float, Next, Reset, Warmup};
+
+/// Exponentially weighted covariance of two series.
+///
+/// RiskMetrics-style covariance estimate where the weight of each observation decays by
+/// the factor _λ_ per period. The input is a pair `(a, b)` of simultaneous observations.
+/// The variances of both series are tracked as well, see also
+/// [ExponentialCorrelation](struct.ExponentialCorrelation.html).
+///
+/// # Formula
+///
+/// δa<sub>t</sub> = a<sub>t</sub> - μa<sub>t-1</sub>, δb<sub>t</sub> = b<sub>t</sub> - μb<sub>t-1</sub>
+///
+/// μa<sub>t</sub> = μa<sub>t-1</sub> + (1 - λ) δa<sub>t</sub>
+///
+/// cov<sub>t</sub> = λ (cov<sub>t-1</sub> + (1 - λ) δa<sub>t</sub> δb<sub>t</sub>)
+///
+/// # Parameters
+///
+/// * _lambda_ - decay factor, between 0 and 1 exclusive. Default is 0.94.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::ExponentialCovariance;
+/// use ta::Next;
+///
+/// let mut cov = ExponentialCovariance::new(0.5).unwrap();
+/// assert_eq!(cov.next(&(1.0, 2.0)), 0.0);
+/// assert_eq!(cov.next(&(3.0, 6.0)), 2.0);
+/// assert_eq!(cov.correlation(), 1.0);
+/// ```
+///
+/// # Links
+///
+/// * [RiskMetrics, Wikipedia](https://en.wikipedia.org/wiki/RiskMetrics)
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExponentialCovariance<F = f64> {
+    lambda: F,
+    mean_a: F,
+    mean_b: F,
+    var_a: F,
+    var_b: F,
+    cov: F,
+    count: u32,
+}
+
+impl ExponentialCovariance {
+    pub fn new(lambda: f64) -> Result<Self> {
+        Self::with_type(lambda)
+    }
+}
+
+impl<F: Float> ExponentialCovariance<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(lambda: F) -> Result<Self> {
+        if lambda <= F::zero() || lambda >= F::one() {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        Ok(Self {
+            lambda,
+            mean_a: F::zero(),
+            mean_b: F::zero(),
+            var_a: F::zero(),
+            var_b: F::zero(),
+            cov: F::zero(),
+            count: 0,
+        })
+    }
+
+    pub fn lambda(&self) -> F {
+        self.lambda
+    }
+
+    pub fn covariance(&self) -> F {
+        self.cov
+    }
+
+    /// Variances of the first and the second series.
+    pub fn variances(&self) -> (F, F) {
+        (self.var_a, self.var_b)
+    }
+
+    /// Correlation derived from the current covariance and variances.
+    ///
+    /// Returns 0 while either variance is 0.
+    pub fn correlation(&self) -> F {
+        let denominator = (self.var_a * self.var_b).sqrt();
+        if denominator == F::zero() {
+            F::zero()
+        } else {
+            (self.cov / denominator).clamp(-F::one(), F::one())
+        }
+    }
+}
+
+impl<F: Float> Next<(F, F)> for ExponentialCovariance<F> {
+    type Output = F;
+
+    fn next(&mut self, &(a, b): &(F, F)) -> F {
+        self.count = self.count.saturating_add(1);
+        if self.count == 1 {
+            self.mean_a = a;
+            self.mean_b = b;
+            return self.cov;
+        }
+
+        let alpha = F::one() - self.lambda;
+        let delta_a = a - self.mean_a;
+        let delta_b = b - self.mean_b;
+        self.mean_a += alpha * delta_a;
+        self.mean_b += alpha * delta_b;
+        self.var_a = self.lambda * (self.var_a + alpha * delta_a * delta_a);
+        self.var_b = self.lambda * (self.var_b + alpha * delta_b * delta_b);
+        self.cov = self.lambda * (self.cov + alpha * delta_a * delta_b);
+        self.cov
+    }
+}
+
+impl<F: Float> Reset for ExponentialCovariance<F> {
+    fn reset(&mut self) {
+        self.mean_a = F::zero();
+        self.mean_b = F::zero();
+        self.var_a = F::zero();
+        self.var_b = F::zero();
+        self.cov = F::zero();
+        self.count = 0;
+    }
+}
+
+impl<F: Float> Warmup for ExponentialCovariance<F> {
+    fn warmup_period(&self) -> u32 {
+        2
+    }
+
+    fn is_ready(&self) -> bool {
+        self.count >= 2
+    }
+}
+
+impl Default for ExponentialCovariance {
+    fn default() -> Self {
+        Self::new(0.94).unwrap()
+    }
+}
+
+impl<F: Float> fmt::Display for ExponentialCovariance<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "EWCOV({})", self.lambda)
+    }
+}
+
+/// Exponentially weighted correlation of two series.
+///
+/// Pearson correlation computed from the [exponentially weighted
+/// covariance](struct.ExponentialCovariance.html) and variances of both series. Returns 0
+/// while either series has no variance yet.
+///
+/// # Parameters
+///
+/// * _lambda_ - decay factor, between 0 and 1 exclusive. Default is 0.94.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::ExponentialCorrelation;
+/// use ta::Next;
+///
+/// let mut corr = ExponentialCorrelation::new(0.9).unwrap();
+/// corr.next(&(1.0, 5.0));
+/// assert_eq!(corr.next(&(2.0, 4.0)), -1.0);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExponentialCorrelation<F = f64> {
+    cov: ExponentialCovariance<F>,
+}
+
+impl ExponentialCorrelation {
+    pub fn new(lambda: f64) -> Result<Self> {
+        Self::with_type(lambda)
+    }
+}
+
+impl<F: Float> ExponentialCorrelation<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(lambda: F) -> Result<Self> {
+        Ok(Self {
+            cov: ExponentialCovariance::with_type(lambda)?,
+        })
+    }
+
+    pub fn lambda(&self) -> F {
+        self.cov.lambda()
+    }
+}
+
+impl<F: Float> Next<(F, F)> for ExponentialCorrelation<F> {
+    type Output = F;
+
+    fn next(&mut self, input: &(F, F)) -> F {
+        self.cov.next(input);
+        self.cov.correlation()
+    }
+}
+
+impl<F: Float> Reset for ExponentialCorrelation<F> {
+    fn reset(&mut self) {
+        self.cov.reset();
+    }
+}
+
+impl<F: Float> Warmup for ExponentialCorrelation<F> {
+    fn warmup_period(&self) -> u32 {
+        self.cov.warmup_period()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.cov.is_ready()
+    }
+}
+
+impl Default for ExponentialCorrelation {
+    fn default() -> Self {
+        Self::new(0.94).unwrap()
+    }
+}
+
+impl<F: Float> fmt::Display for ExponentialCorrelation<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "EWCORR({})", self.lambda())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_new() {
+        assert!(ExponentialCovariance::new(0.0).is_err());
+        assert!(ExponentialCovariance::new(1.0).is_err());
+        assert!(ExponentialCovariance::new(0.94).is_ok());
+        assert!(ExponentialCorrelation::new(1.5).is_err());
+        assert!(ExponentialCorrelation::new(0.5).is_ok());
+    }
+
+    #[test]
+    fn test_covariance() {
+        let mut cov = ExponentialCovariance::new(0.5).unwrap();
+        assert_eq!(cov.next(&(1.0, 2.0)), 0.0);
+        // deltas (2, 4), means (2, 4)
+        assert_eq!(cov.next(&(3.0, 6.0)), 2.0);
+        assert_eq!(cov.variances(), (1.0, 4.0));
+        // deltas (-2, 1), means (1, 4.5)
+        assert_eq!(cov.next(&(0.0, 5.0)), 0.5);
+        assert_eq!(cov.variances(), (1.5, 2.25));
+        assert_eq!(round(cov.correlation()), 0.272);
+    }
+
+    #[test]
+    fn test_correlation() {
+        let mut corr = ExponentialCorrelation::new(0.8).unwrap();
+        assert_eq!(corr.next(&(1.0, 1.0)), 0.0);
+        for &(a, b) in &[(2.0, 3.0), (1.5, 2.0), (4.0, 7.0)] {
+            assert_eq!(round(corr.next(&(a, b))), 1.0);
+        }
+        assert_eq!(corr.next(&(4.0, 7.0)), corr.cov.correlation());
+    }
+
+    #[test]
+    fn test_constant_series() {
+        let mut corr = ExponentialCorrelation::new(0.8).unwrap();
+        corr.next(&(1.0, 1.0));
+        assert_eq!(corr.next(&(1.0, 2.0)), 0.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut cov = ExponentialCovariance::new(0.5).unwrap();
+        cov.next(&(1.0, 2.0));
+        cov.next(&(3.0, 6.0));
+
+        cov.reset();
+        assert_eq!(cov.next(&(3.0, 6.0)), 0.0);
+        assert_eq!(cov.variances(), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_default() {
+        ExponentialCovariance::default();
+        ExponentialCorrelation::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            format!("{}", ExponentialCovariance::new(0.97).unwrap()),
+            "EWCOV(0.97)"
+        );
+        assert_eq!(
+            format!("{}", ExponentialCorrelation::default()),
+            "EWCORR(0.94)"
+        );
+    }
+}
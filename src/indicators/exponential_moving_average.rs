@@ -1,7 +1,7 @@
-use std::fmt;
+use core::fmt;
 
 use crate::errors::*;
-use crate::{Calculate, Close, Next, Reset};
+use crate::{Calculate, Close, Decay, Next, Number, Peek, PeekNext, Reset, Warmup};
 
 /// An exponential moving average (EMA), also known as an exponentially weighted moving average
 /// (EWMA).
@@ -32,6 +32,11 @@ use crate::{Calculate, Close, Next, Reset};
 ///
 /// * _length_ - number of periods (integer greater than 0)
 ///
+/// # Gaps
+///
+/// After [decay](../trait.Decay.html) over _n_ missed periods, the next input is weighted as if
+/// it was repeated _n_ + 1 times, with _α_ replaced by 1 - (1 - _α_)<sup>_n_ + 1</sup>.
+///
 /// # Example
 ///
 /// ```
@@ -51,24 +56,36 @@ use crate::{Calculate, Close, Next, Reset};
 ///
 
 #[derive(Debug, Clone)]
-pub struct ExponentialMovingAverage {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExponentialMovingAverage<F = f64> {
     length: u32,
-    k: f64,
-    current: f64,
-    is_new: bool,
+    k: F,
+    current: F,
+    count: u32,
+    // periods missed since the last input
+    #[cfg_attr(feature = "serde", serde(default))]
+    gap: u32,
 }
 
 impl ExponentialMovingAverage {
     pub fn new(length: u32) -> Result<Self> {
+        Self::with_type(length)
+    }
+}
+
+impl<F: Number> ExponentialMovingAverage<F> {
+    /// Like [new](#method.new), for values of any number type `F`.
+    pub fn with_type(length: u32) -> Result<Self> {
         match length {
             0 => Err(Error::from_kind(ErrorKind::InvalidParameter)),
             _ => {
-                let k = 2f64 / (length as f64 + 1f64);
+                let k = F::cast(2.0) / (F::cast(length) + F::one());
                 let indicator = Self {
                     length,
                     k,
-                    current: 0f64,
-                    is_new: true,
+                    current: F::zero(),
+                    count: 0,
+                    gap: 0,
                 };
                 Ok(indicator)
             }
@@ -78,30 +95,77 @@ impl ExponentialMovingAverage {
     pub fn length(&self) -> u32 {
         self.length
     }
+
+    fn smooth(&self, input: F) -> F {
+        if self.gap == 0 {
+            self.k * input + (F::one() - self.k) * self.current
+        } else {
+            let weight = num_traits::pow(F::one() - self.k, self.gap as usize + 1);
+            (F::one() - weight) * input + weight * self.current
+        }
+    }
 }
 
-impl Calculate for ExponentialMovingAverage {
-    fn calc(&mut self, input: f64) -> f64 {
-        if self.is_new {
-            self.is_new = false;
+impl<F: Number> Calculate<F> for ExponentialMovingAverage<F> {
+    type Output = F;
+
+    fn calc(&mut self, input: F) -> F {
+        self.count = self.count.saturating_add(1);
+        if self.count == 1 {
             self.current = input;
         } else {
-            self.current = self.k * input + (1.0 - self.k) * self.current;
+            self.current = self.smooth(input);
         }
+        self.gap = 0;
         self.current
     }
 }
 
-impl<T: Close> Next<T> for ExponentialMovingAverage {
-    fn next(&mut self, input: &T) -> f64 {
+impl<F: Number> Peek<F> for ExponentialMovingAverage<F> {
+    fn peek(&self, input: F) -> F {
+        if self.count == 0 {
+            input
+        } else {
+            self.smooth(input)
+        }
+    }
+}
+
+impl<F: Number, T: Close<F>> Next<T> for ExponentialMovingAverage<F> {
+    type Output = F;
+
+    fn next(&mut self, input: &T) -> F {
         self.calc(input.close())
     }
 }
 
-impl Reset for ExponentialMovingAverage {
+impl<F: Number, T: Close<F>> PeekNext<T> for ExponentialMovingAverage<F> {
+    fn peek_next(&self, input: &T) -> F {
+        self.peek(input.close())
+    }
+}
+
+impl<F: Number> Reset for ExponentialMovingAverage<F> {
     fn reset(&mut self) {
-        self.current = 0.0;
-        self.is_new = true;
+        self.current = F::zero();
+        self.count = 0;
+        self.gap = 0;
+    }
+}
+
+impl<F: Number> Decay for ExponentialMovingAverage<F> {
+    fn decay(&mut self, periods: u32) {
+        self.gap = self.gap.saturating_add(periods);
+    }
+}
+
+impl<F: Number> Warmup for ExponentialMovingAverage<F> {
+    fn warmup_period(&self) -> u32 {
+        self.length
+    }
+
+    fn is_ready(&self) -> bool {
+        self.count >= self.length
     }
 }
 
@@ -111,7 +175,7 @@ impl Default for ExponentialMovingAverage {
     }
 }
 
-impl fmt::Display for ExponentialMovingAverage {
+impl<F: Number> fmt::Display for ExponentialMovingAverage<F> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "EMA({})", self.length)
     }
@@ -146,6 +210,34 @@ mod tests {
         assert_eq!(ema.next(&bar2), 3.5);
     }
 
+    #[test]
+    fn test_peek() {
+        let mut indicator = ExponentialMovingAverage::new(3).unwrap();
+        for &x in &[4.0, 6.0, 8.0, 3.0] {
+            let peeked = indicator.peek(x);
+            assert_eq!(
+                indicator.peek_next(&Bar::new().close(x)),
+                indicator.clone().next(&Bar::new().close(x))
+            );
+            assert_eq!(indicator.calc(x), peeked);
+        }
+    }
+
+    #[test]
+    fn test_decay() {
+        let mut ema = ExponentialMovingAverage::new(3).unwrap();
+        ema.calc(2.0);
+        ema.decay(1);
+        assert_eq!(ema.peek(6.0), 5.0);
+        assert_eq!(ema.calc(6.0), 5.0);
+        assert_eq!(ema.calc(6.0), 5.5);
+
+        ema.decay(10);
+        ema.reset();
+        ema.calc(2.0);
+        assert_eq!(ema.calc(6.0), 4.0);
+    }
+
     #[test]
     fn test_reset() {
         let mut ema = ExponentialMovingAverage::new(5).unwrap();
@@ -0,0 +1,217 @@
+use core::fmt;
+
+use crate::errors::*;
+use crate::{Calculate, Close, Float, Next, Reset, Warmup};
+
+/// Exponentially weighted moving variance.
+///
+/// Tracks the exponentially weighted mean and variance of the input with the same
+/// smoothing factor as [EMA](struct.ExponentialMovingAverage.html), so that recent values
+/// dominate without a hard window cutoff. Returns the variance; the mean and the standard
+/// deviation are available through accessors.
+///
+/// # Formula
+///
+/// δ<sub>t</sub> = p<sub>t</sub> - μ<sub>t-1</sub>
+///
+/// μ<sub>t</sub> = μ<sub>t-1</sub> + α δ<sub>t</sub>
+///
+/// σ²<sub>t</sub> = (1 - α) (σ²<sub>t-1</sub> + α δ<sub>t</sub>²)
+///
+/// Where:
+///
+/// * _μ<sub>t</sub>_ - exponentially weighted mean
+/// * _σ²<sub>t</sub>_ - exponentially weighted variance
+/// * _α_ = 2 / (_length_ + 1)
+///
+/// # Parameters
+///
+/// * _length_ - number of periods (integer greater than 0)
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::ExponentialMovingVariance;
+/// use ta::Calculate;
+///
+/// let mut var = ExponentialMovingVariance::new(3).unwrap();
+/// assert_eq!(var.calc(2.0), 0.0);
+/// assert_eq!(var.calc(4.0), 1.0);
+/// assert_eq!(var.mean(), 3.0);
+/// assert_eq!(var.std_dev(), 1.0);
+/// ```
+///
+/// # Links
+///
+/// * [Exponentially weighted moving variance, Wikipedia](https://en.wikipedia.org/wiki/Moving_average#Exponentially_weighted_moving_variance_and_standard_deviation)
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExponentialMovingVariance<F = f64> {
+    length: u32,
+    k: F,
+    mean: F,
+    variance: F,
+    count: u32,
+}
+
+impl ExponentialMovingVariance {
+    pub fn new(length: u32) -> Result<Self> {
+        Self::with_type(length)
+    }
+}
+
+impl<F: Float> ExponentialMovingVariance<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(length: u32) -> Result<Self> {
+        match length {
+            0 => Err(Error::from_kind(ErrorKind::InvalidParameter)),
+            _ => Ok(Self {
+                length,
+                k: F::cast(2.0) / (F::cast(length) + F::one()),
+                mean: F::zero(),
+                variance: F::zero(),
+                count: 0,
+            }),
+        }
+    }
+
+    pub fn length(&self) -> u32 {
+        self.length
+    }
+
+    /// Current exponentially weighted mean.
+    pub fn mean(&self) -> F {
+        self.mean
+    }
+
+    /// Current exponentially weighted variance.
+    pub fn variance(&self) -> F {
+        self.variance
+    }
+
+    /// Square root of the current variance.
+    pub fn std_dev(&self) -> F {
+        self.variance.sqrt()
+    }
+}
+
+impl<F: Float> Calculate<F> for ExponentialMovingVariance<F> {
+    type Output = F;
+
+    fn calc(&mut self, input: F) -> F {
+        self.count = self.count.saturating_add(1);
+        if self.count == 1 {
+            self.mean = input;
+        } else {
+            let delta = input - self.mean;
+            self.mean += self.k * delta;
+            self.variance = (F::one() - self.k) * (self.variance + self.k * delta * delta);
+        }
+        self.variance
+    }
+}
+
+impl<F: Float, T: Close<F>> Next<T> for ExponentialMovingVariance<F> {
+    type Output = F;
+
+    fn next(&mut self, input: &T) -> F {
+        self.calc(input.close())
+    }
+}
+
+impl<F: Float> Reset for ExponentialMovingVariance<F> {
+    fn reset(&mut self) {
+        self.mean = F::zero();
+        self.variance = F::zero();
+        self.count = 0;
+    }
+}
+
+impl<F: Float> Warmup for ExponentialMovingVariance<F> {
+    fn warmup_period(&self) -> u32 {
+        self.length
+    }
+
+    fn is_ready(&self) -> bool {
+        self.count >= self.length
+    }
+}
+
+impl Default for ExponentialMovingVariance {
+    fn default() -> Self {
+        Self::new(9).unwrap()
+    }
+}
+
+impl<F: Float> fmt::Display for ExponentialMovingVariance<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "EWVAR({})", self.length)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(ExponentialMovingVariance);
+
+    #[test]
+    fn test_new() {
+        assert!(ExponentialMovingVariance::new(0).is_err());
+        assert!(ExponentialMovingVariance::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut var = ExponentialMovingVariance::new(3).unwrap();
+        assert_eq!(var.calc(2.0), 0.0);
+        assert_eq!(var.calc(4.0), 1.0);
+        assert_eq!(var.mean(), 3.0);
+        // delta = 3, mean = 4.5, variance = 0.5 * (1 + 0.5 * 9)
+        assert_eq!(var.calc(6.0), 2.75);
+        assert_eq!(var.mean(), 4.5);
+    }
+
+    #[test]
+    fn test_next_same_values() {
+        let mut var = ExponentialMovingVariance::new(5).unwrap();
+        for _ in 0..10 {
+            assert_eq!(var.calc(4.2), 0.0);
+        }
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        fn bar(close: f64) -> Bar {
+            Bar::new().close(close)
+        }
+
+        let mut var = ExponentialMovingVariance::new(3).unwrap();
+        assert_eq!(var.next(&bar(2.0)), 0.0);
+        assert_eq!(var.next(&bar(4.0)), 1.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut var = ExponentialMovingVariance::new(3).unwrap();
+        var.calc(2.0);
+        var.calc(4.0);
+
+        var.reset();
+        assert_eq!(var.calc(10.0), 0.0);
+        assert_eq!(var.mean(), 10.0);
+    }
+
+    #[test]
+    fn test_default() {
+        ExponentialMovingVariance::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let indicator = ExponentialMovingVariance::new(7).unwrap();
+        assert_eq!(format!("{}", indicator), "EWVAR(7)");
+    }
+}
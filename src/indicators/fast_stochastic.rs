@@ -1,8 +1,10 @@
-use std::fmt;
+use core::fmt;
 
 use crate::errors::*;
-use crate::indicators::{Maximum, Minimum};
-use crate::{Calculate, Close, High, Low, Next, Reset};
+use crate::indicators::{Maximum, Minimum, RangeMode};
+use crate::{
+    Calculate, Close, Float, High, Low, Next, Peek, PeekNext, Replace, Reset, UpdateLast, Warmup,
+};
 
 /// Fast stochastic oscillator.
 ///
@@ -25,6 +27,10 @@ use crate::{Calculate, Close, High, Low, Next, Reset};
 ///
 /// * _length_ - number of periods (integer greater than 0). Default is 14.
 ///
+/// On inconsistent bars, with the close outside of the high-low range of the window, the
+/// output leaves the range of 0 to 100, unless clamped with a
+/// [RangeMode](enum.RangeMode.html).
+///
 /// # Example
 ///
 /// ```
@@ -39,18 +45,41 @@ use crate::{Calculate, Close, High, Low, Next, Reset};
 /// assert_eq!(stoch.calc(15.0), 0.0);
 /// ```
 #[derive(Debug, Clone)]
-pub struct FastStochastic {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(deserialize = "F: Float + serde::Deserialize<'de>"))
+)]
+pub struct FastStochastic<F = f64> {
     length: u32,
-    minimum: Minimum,
-    maximum: Maximum,
+    // The lowest low and the highest high come from a Minimum and a Maximum, which rescan
+    // their window only when the extreme leaves it. Their ring buffers replaced vectors
+    // indexed modulo the length: timed as the best of interleaved runs over 5000 bars, both
+    // stochastic oscillators stay within 3% of the vectors, while single runs of the bundled
+    // benchmark vary by up to 30%.
+    minimum: Minimum<F>,
+    maximum: Maximum<F>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    range_mode: RangeMode,
+    #[cfg_attr(feature = "serde", serde(default))]
+    violations: u64,
 }
 
 impl FastStochastic {
     pub fn new(length: u32) -> Result<Self> {
+        Self::with_type(length)
+    }
+}
+
+impl<F: Float> FastStochastic<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(length: u32) -> Result<Self> {
         let indicator = Self {
-            length: length,
-            minimum: Minimum::new(length)?,
-            maximum: Maximum::new(length)?,
+            length,
+            minimum: Minimum::with_type(length)?,
+            maximum: Maximum::with_type(length)?,
+            range_mode: RangeMode::Unchecked,
+            violations: 0,
         };
         Ok(indicator)
     }
@@ -58,42 +87,127 @@ impl FastStochastic {
     pub fn length(&self) -> u32 {
         self.length
     }
-}
 
-impl Calculate for FastStochastic {
-    fn calc(&mut self, input: f64) -> f64 {
-        let min = self.minimum.calc(input);
-        let max = self.maximum.calc(input);
+    /// Changes the size of the time frame, keeping the latest highs and lows which fit into
+    /// the new one. Fails with `InvalidParameter` if `length` is 0.
+    pub fn set_length(&mut self, length: u32) -> Result<()> {
+        self.minimum.set_length(length)?;
+        self.maximum.set_length(length)?;
+        self.length = length;
+        Ok(())
+    }
+
+    /// Like [set_length](#method.set_length), consuming the indicator.
+    pub fn with_length(mut self, length: u32) -> Result<Self> {
+        self.set_length(length)?;
+        Ok(self)
+    }
+
+    /// Sets how outputs outside of the range of 0 to 100 are treated.
+    pub fn with_range_mode(mut self, range_mode: RangeMode) -> Self {
+        self.range_mode = range_mode;
+        self
+    }
 
-        if min == max {
+    pub fn range_mode(&self) -> RangeMode {
+        self.range_mode
+    }
+
+    /// Number of outputs outside of the range of 0 to 100 since the last reset, counted in
+    /// the `Clamp` and `Strict` range modes.
+    pub fn violations(&self) -> u64 {
+        self.violations
+    }
+
+    fn bounded(&mut self, value: F) -> F {
+        let (value, violated) = self.range_mode.apply(value, F::zero(), F::cast(100.0));
+        if violated {
+            self.violations += 1;
+        }
+        value
+    }
+
+    fn stochastic(close: F, lowest: F, highest: F) -> F {
+        if highest == lowest {
             // When only 1 input was given, than min and max are the same,
-            // therefore it makes sense to return 50
-            50.0
+            // therefore it makes sense to return 50 (and it avoids division by zero)
+            F::cast(50.0)
         } else {
-            (input - min) / (max - min) * 100.0
+            (close - lowest) / (highest - lowest) * F::cast(100.0)
         }
     }
 }
 
-impl<T: High + Low + Close> Next<T> for FastStochastic {
-    fn next(&mut self, input: &T) -> f64 {
+impl<F: Float> Calculate<F> for FastStochastic<F> {
+    type Output = F;
+
+    fn calc(&mut self, input: F) -> F {
+        let min = self.minimum.calc(input);
+        let max = self.maximum.calc(input);
+        Self::stochastic(input, min, max)
+    }
+}
+
+impl<F: Float> UpdateLast<F> for FastStochastic<F> {
+    fn update_last(&mut self, input: F) -> F {
+        let min = self.minimum.update_last(input);
+        let max = self.maximum.update_last(input);
+        Self::stochastic(input, min, max)
+    }
+}
+
+impl<F: Float> Peek<F> for FastStochastic<F> {
+    fn peek(&self, input: F) -> F {
+        let min = self.minimum.peek(input);
+        let max = self.maximum.peek(input);
+        Self::stochastic(input, min, max)
+    }
+}
+
+impl<F: Float, T: High<F> + Low<F> + Close<F>> Next<T> for FastStochastic<F> {
+    type Output = F;
+
+    fn next(&mut self, input: &T) -> F {
         let highest = self.maximum.calc(input.high());
         let lowest = self.minimum.calc(input.low());
-        let close = input.close();
+        let value = Self::stochastic(input.close(), lowest, highest);
+        self.bounded(value)
+    }
+}
 
-        if highest == lowest {
-            // To avoid division by zero, return 50.0
-            50.0
-        } else {
-            (close - lowest) / (highest - lowest) * 100.0
-        }
+impl<F: Float, T: High<F> + Low<F> + Close<F>> Replace<T> for FastStochastic<F> {
+    fn replace(&mut self, input: &T) -> F {
+        let highest = self.maximum.update_last(input.high());
+        let lowest = self.minimum.update_last(input.low());
+        let value = Self::stochastic(input.close(), lowest, highest);
+        self.bounded(value)
     }
 }
 
-impl Reset for FastStochastic {
+impl<F: Float, T: High<F> + Low<F> + Close<F>> PeekNext<T> for FastStochastic<F> {
+    fn peek_next(&self, input: &T) -> F {
+        let highest = self.maximum.peek(input.high());
+        let lowest = self.minimum.peek(input.low());
+        let value = Self::stochastic(input.close(), lowest, highest);
+        self.range_mode.apply(value, F::zero(), F::cast(100.0)).0
+    }
+}
+
+impl<F: Float> Reset for FastStochastic<F> {
     fn reset(&mut self) {
         self.minimum.reset();
         self.maximum.reset();
+        self.violations = 0;
+    }
+}
+
+impl<F: Float> Warmup for FastStochastic<F> {
+    fn warmup_period(&self) -> u32 {
+        self.length
+    }
+
+    fn is_ready(&self) -> bool {
+        self.maximum.is_ready()
     }
 }
 
@@ -103,7 +217,7 @@ impl Default for FastStochastic {
     }
 }
 
-impl fmt::Display for FastStochastic {
+impl<F: Float> fmt::Display for FastStochastic<F> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "FAST_STOCH({})", self.length)
     }
@@ -113,6 +227,7 @@ impl fmt::Display for FastStochastic {
 mod tests {
     use super::*;
     use crate::test_helper::*;
+    use crate::TryNext;
 
     test_indicator!(FastStochastic);
 
@@ -152,6 +267,128 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_try_next() {
+        let mut stoch = FastStochastic::new(3).unwrap();
+        let bar = |high: f64, low: f64, close: f64| Bar::new().high(high).low(low).close(close);
+
+        assert_eq!(stoch.warmup_period(), 3);
+        assert_eq!(stoch.try_next(&bar(20.0, 20.0, 20.0)).ok(), None);
+        assert_eq!(stoch.try_next(&bar(30.0, 10.0, 25.0)).ok(), None);
+        assert_eq!(stoch.try_next(&bar(40.0, 20.0, 25.0)).ok(), Some(50.0));
+        assert!(stoch.is_ready());
+    }
+
+    #[test]
+    fn test_next_f32() {
+        struct Bar32(f32, f32, f32);
+
+        impl High<f32> for Bar32 {
+            fn high(&self) -> f32 {
+                self.0
+            }
+        }
+
+        impl Low<f32> for Bar32 {
+            fn low(&self) -> f32 {
+                self.1
+            }
+        }
+
+        impl Close<f32> for Bar32 {
+            fn close(&self) -> f32 {
+                self.2
+            }
+        }
+
+        let mut stoch = FastStochastic::with_type(3).unwrap();
+        assert_eq!(stoch.next(&Bar32(20.0, 20.0, 20.0)), 50.0f32);
+        assert_eq!(stoch.next(&Bar32(30.0, 10.0, 25.0)), 75.0);
+    }
+
+    #[test]
+    fn test_range_mode() {
+        // the close lies above the high and below the low
+        let above = Bar::new().high(20.0).low(10.0).close(22.0);
+        let below = Bar::new().high(20.0).low(10.0).close(9.0);
+
+        let mut stoch = FastStochastic::new(3).unwrap();
+        assert_eq!(stoch.next(&above), 120.0);
+        assert_eq!(stoch.violations(), 0);
+
+        let mut stoch = FastStochastic::new(3)
+            .unwrap()
+            .with_range_mode(RangeMode::Clamp);
+        assert_eq!(stoch.peek_next(&above), 100.0);
+        assert_eq!(stoch.next(&above), 100.0);
+        assert_eq!(stoch.replace(&below), 0.0);
+        assert_eq!(
+            stoch.next(&Bar::new().high(20.0).low(10.0).close(15.0)),
+            50.0
+        );
+        assert_eq!(stoch.violations(), 2);
+
+        let mut stoch = FastStochastic::new(3)
+            .unwrap()
+            .with_range_mode(RangeMode::Strict);
+        assert_eq!(stoch.next(&below), -10.0);
+        assert_eq!(stoch.violations(), 1);
+        stoch.reset();
+        assert_eq!(stoch.violations(), 0);
+        assert_eq!(stoch.range_mode(), RangeMode::Strict);
+    }
+
+    #[test]
+    fn test_update_last() {
+        let mut stoch = FastStochastic::new(3).unwrap();
+        assert_eq!(stoch.calc(0.0), 50.0);
+        assert_eq!(stoch.calc(200.0), 100.0);
+        assert_eq!(stoch.update_last(100.0), 100.0);
+        assert_eq!(stoch.update_last(50.0), 100.0);
+        assert_eq!(stoch.calc(40.0), 80.0);
+
+        let bar = |high, low, close| Bar::new().high(high).low(low).close(close);
+        let mut stoch = FastStochastic::new(2).unwrap();
+        stoch.next(&bar(20.0, 10.0, 15.0));
+        assert_eq!(stoch.next(&bar(30.0, 20.0, 25.0)), 75.0);
+        assert_eq!(stoch.replace(&bar(25.0, 5.0, 15.0)), 50.0);
+        assert_eq!(stoch.replace(&bar(18.0, 12.0, 14.0)), 40.0);
+    }
+
+    #[test]
+    fn test_peek() {
+        let mut indicator = FastStochastic::new(3).unwrap();
+        for &x in &[0.0, 200.0, 100.0, 120.0, 115.0] {
+            let peeked = indicator.peek(x);
+            assert_eq!(
+                indicator.peek_next(&Bar::new().high(x + 1.0).low(x - 1.0).close(x)),
+                indicator
+                    .clone()
+                    .next(&Bar::new().high(x + 1.0).low(x - 1.0).close(x))
+            );
+            assert_eq!(indicator.calc(x), peeked);
+        }
+    }
+
+    #[test]
+    fn test_set_length() {
+        let mut stoch = FastStochastic::new(3).unwrap();
+        for &value in &[10.0, 20.0, 15.0, 12.0] {
+            stoch.calc(value);
+        }
+        assert!(stoch.set_length(0).is_err());
+        assert_eq!(stoch.length(), 3);
+
+        let mut stoch = stoch.with_length(2).unwrap();
+        let mut expected = FastStochastic::new(2).unwrap();
+        expected.calc(15.0);
+        expected.calc(12.0);
+        assert!(stoch.is_ready());
+        assert_eq!(stoch.calc(14.0), expected.calc(14.0));
+        assert_eq!(stoch.length(), 2);
+        assert_eq!(stoch.to_string(), "FAST_STOCH(2)");
+    }
+
     #[test]
     fn test_reset() {
         let mut indicator = FastStochastic::new(10).unwrap();
@@ -0,0 +1,219 @@
+use core::fmt;
+
+use crate::errors::*;
+use crate::indicators::{SwingKind, SwingPoint};
+use crate::swings::SwingWindow;
+use crate::{Calculate, Finite, Float, High, Low, Next, Reset, Warmup};
+
+/// Output of [Fractals](struct.Fractals.html).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FractalsOutput<F = f64> {
+    /// Up fractal confirmed on the bar, a swing high.
+    pub up: Option<SwingPoint<F>>,
+    /// Down fractal confirmed on the bar, a swing low.
+    pub down: Option<SwingPoint<F>>,
+}
+
+impl<F: Finite> Finite for FractalsOutput<F> {
+    fn is_finite(&self) -> bool {
+        self.up
+            .iter()
+            .chain(self.down.iter())
+            .all(Finite::is_finite)
+    }
+}
+
+/// Bill Williams Fractals.
+///
+/// An up fractal is a bar with a high above the highs of the _width_ bars on each side, a
+/// down fractal a bar with a low below their lows; the classic pattern spans 5 bars. Of
+/// equal highs (lows) after the middle bar only the first one forms a fractal. A fractal is
+/// confirmed once the _width_ bars after it have closed, and is returned on that bar with an
+/// `offset` of _width_. A single bar can form both an up and a down fractal.
+///
+/// Fractals are traditionally traded with the [Alligator](struct.Alligator.html): a breakout
+/// above an up fractal above the teeth, or below a down fractal below them, is a signal.
+///
+/// # Parameters
+///
+/// * _width_ - number of bars on each side of a fractal (integer greater than 0).
+///   Default is 2.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::Fractals;
+/// use ta::Next;
+///
+/// let mut fractals = Fractals::new(2).unwrap();
+/// // open, high, low, close and volume
+/// let bars = [
+///     (10.0, 11.0, 9.0, 10.0, 100.0),
+///     (10.0, 12.0, 9.5, 11.0, 100.0),
+///     (11.0, 14.0, 10.0, 13.0, 100.0),
+///     (13.0, 13.5, 11.0, 12.0, 100.0),
+/// ];
+/// for bar in &bars {
+///     fractals.next(bar);
+/// }
+///
+/// let output = fractals.next(&(12.0, 12.5, 10.5, 11.0, 100.0));
+/// let up = output.up.unwrap();
+/// assert_eq!((up.price, up.offset), (14.0, 2));
+/// assert_eq!(output.down, None);
+/// ```
+///
+/// # Links
+///
+/// * [Fractal Indicator, Investopedia](https://www.investopedia.com/terms/f/fractal.asp)
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Fractals<F = f64> {
+    width: u32,
+    swings: SwingWindow<(F, F)>,
+    count: u64,
+}
+
+impl Fractals {
+    pub fn new(width: u32) -> Result<Self> {
+        Self::with_type(width)
+    }
+}
+
+impl<F: Float> Fractals<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(width: u32) -> Result<Self> {
+        if width == 0 {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        Ok(Self {
+            width,
+            swings: SwingWindow::new(width, width),
+            count: 0,
+        })
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    fn update(&mut self, high: F, low: F) -> FractalsOutput<F> {
+        self.count += 1;
+        let (up, down) = self.swings.push((high, low), |bar| bar.0, |bar| bar.1);
+        let point = |kind, price| SwingPoint {
+            kind,
+            price,
+            offset: self.width,
+        };
+        FractalsOutput {
+            up: up.map(|(high, _)| point(SwingKind::High, high)),
+            down: down.map(|(_, low)| point(SwingKind::Low, low)),
+        }
+    }
+}
+
+impl<F: Float> Calculate<F> for Fractals<F> {
+    type Output = FractalsOutput<F>;
+
+    fn calc(&mut self, input: F) -> Self::Output {
+        self.update(input, input)
+    }
+}
+
+impl<F: Float, T: High<F> + Low<F>> Next<T> for Fractals<F> {
+    type Output = FractalsOutput<F>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.update(input.high(), input.low())
+    }
+}
+
+impl<F: Float> Reset for Fractals<F> {
+    fn reset(&mut self) {
+        self.swings.clear();
+        self.count = 0;
+    }
+}
+
+impl<F: Float> Warmup for Fractals<F> {
+    fn warmup_period(&self) -> u32 {
+        2 * self.width + 1
+    }
+
+    fn is_ready(&self) -> bool {
+        self.count >= u64::from(self.warmup_period())
+    }
+}
+
+impl Default for Fractals {
+    fn default() -> Self {
+        Self::new(2).unwrap()
+    }
+}
+
+impl<F: Float> fmt::Display for Fractals<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "FRACTALS({})", self.width)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(Fractals);
+
+    fn bar(high: f64, low: f64) -> Bar {
+        Bar::new().high(high).low(low)
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(Fractals::new(0).is_err());
+        assert!(Fractals::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_up_and_down() {
+        let mut fractals = Fractals::new(1).unwrap();
+        assert_eq!(fractals.next(&bar(10.0, 8.0)).up, None);
+        // an outside bar
+        fractals.next(&bar(12.0, 7.0));
+        let output = fractals.next(&bar(11.0, 9.0));
+        assert_eq!(output.up.unwrap().price, 12.0);
+        assert_eq!(output.up.unwrap().kind, SwingKind::High);
+        assert_eq!(output.down.unwrap().price, 7.0);
+        assert_eq!(output.down.unwrap().offset, 1);
+    }
+
+    #[test]
+    fn test_equal_highs() {
+        let mut fractals = Fractals::new(2).unwrap();
+        let highs = [1.0, 2.0, 3.0, 3.0, 2.0, 1.0];
+        let ups: Vec<_> = highs
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &high)| fractals.next(&bar(high, 0.5)).up.map(|_| i))
+            .collect();
+        // only the first of the equal highs, confirmed two bars later
+        assert_eq!(ups, vec![4]);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut fractals = Fractals::new(1).unwrap();
+        fractals.calc(1.0);
+        fractals.calc(2.0);
+        fractals.reset();
+        assert_eq!(fractals.calc(1.0).up, None);
+        assert!(!fractals.is_ready());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", Fractals::new(3).unwrap()), "FRACTALS(3)");
+    }
+}
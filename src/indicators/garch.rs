@@ -0,0 +1,273 @@
+use core::fmt;
+
+use crate::errors::*;
+use crate::{Calculate, Close, Float, Next, Reset, Warmup};
+
+/// GARCH(1,1) conditional volatility.
+///
+/// Models the variance of log returns as a mean-reverting process driven by the last
+/// squared return. Each input price produces the conditional volatility forecast for the
+/// next period, which can be used directly for risk-based position sizing.
+///
+/// # Formula
+///
+/// r<sub>t</sub> = ln(p<sub>t</sub> / p<sub>t-1</sub>)
+///
+/// σ²<sub>t+1</sub> = ω + α r²<sub>t</sub> + β σ²<sub>t</sub>
+///
+/// The output is σ<sub>t+1</sub>.
+///
+/// # Calibration
+///
+/// * Fixed parameters ([new](#method.new)) - ω, α and β are given by the user. The
+///   variance starts at its long-run level ω / (1 - α - β).
+/// * Variance targeting ([variance_targeting](#method.variance_targeting)) - α and β are
+///   given, while ω is recalibrated on every bar as (1 - α - β) times the running mean of
+///   squared returns. The variance starts at the first squared return.
+///
+/// # Parameters
+///
+/// * _omega_ - constant term (greater than 0)
+/// * _alpha_ - weight of the last squared return (0 or greater)
+/// * _beta_ - weight of the last variance (0 or greater), _alpha_ + _beta_ must be less than 1
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::Garch11;
+/// use ta::Calculate;
+///
+/// let mut garch = Garch11::new(0.000_002, 0.1, 0.88).unwrap();
+/// // long-run volatility is sqrt(0.000002 / 0.02) = 1%
+/// assert_eq!((garch.calc(100.0) * 1e6).round(), 10_000.0);
+/// let vol = garch.calc(105.0);
+/// assert!(vol > 0.01);
+/// ```
+///
+/// # Links
+///
+/// * [GARCH, Wikipedia](https://en.wikipedia.org/wiki/Autoregressive_conditional_heteroskedasticity#GARCH)
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Garch11<F = f64> {
+    omega: Option<F>,
+    alpha: F,
+    beta: F,
+    variance: F,
+    prev_price: Option<F>,
+    count: u64,
+    sum_squares: F,
+}
+
+impl Garch11 {
+    /// Creates the estimator with fixed parameters.
+    pub fn new(omega: f64, alpha: f64, beta: f64) -> Result<Self> {
+        Self::with_type(omega, alpha, beta)
+    }
+}
+
+impl<F: Float> Garch11<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(omega: F, alpha: F, beta: F) -> Result<Self> {
+        if omega <= F::zero() {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        Self::create(Some(omega), alpha, beta)
+    }
+
+    /// Creates the estimator calibrating ω by variance targeting.
+    pub fn variance_targeting(alpha: F, beta: F) -> Result<Self> {
+        Self::create(None, alpha, beta)
+    }
+
+    fn create(omega: Option<F>, alpha: F, beta: F) -> Result<Self> {
+        if alpha < F::zero() || beta < F::zero() || alpha + beta >= F::one() {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        let mut indicator = Self {
+            omega,
+            alpha,
+            beta,
+            variance: F::zero(),
+            prev_price: None,
+            count: 0,
+            sum_squares: F::zero(),
+        };
+        indicator.reset();
+        Ok(indicator)
+    }
+
+    /// Current value of ω.
+    pub fn omega(&self) -> F {
+        match self.omega {
+            Some(omega) => omega,
+            None if self.count == 0 => F::zero(),
+            None => (F::one() - self.alpha - self.beta) * self.sum_squares / F::cast(self.count),
+        }
+    }
+
+    pub fn alpha(&self) -> F {
+        self.alpha
+    }
+
+    pub fn beta(&self) -> F {
+        self.beta
+    }
+
+    /// Conditional variance forecast for the next period.
+    pub fn variance(&self) -> F {
+        self.variance
+    }
+
+    /// Long-run variance ω / (1 - α - β).
+    pub fn long_run_variance(&self) -> F {
+        self.omega() / (F::one() - self.alpha - self.beta)
+    }
+}
+
+impl<F: Float> Calculate<F> for Garch11<F> {
+    type Output = F;
+
+    fn calc(&mut self, input: F) -> F {
+        if let Some(prev) = self.prev_price {
+            let ret = (input / prev).ln();
+            let squared = ret * ret;
+
+            self.count += 1;
+            self.sum_squares += squared;
+
+            if self.omega.is_none() && self.count == 1 {
+                self.variance = squared;
+            } else {
+                self.variance = self.omega() + self.alpha * squared + self.beta * self.variance;
+            }
+        }
+        self.prev_price = Some(input);
+        self.variance.sqrt()
+    }
+}
+
+impl<F: Float, T: Close<F>> Next<T> for Garch11<F> {
+    type Output = F;
+
+    fn next(&mut self, input: &T) -> F {
+        self.calc(input.close())
+    }
+}
+
+impl<F: Float> Reset for Garch11<F> {
+    fn reset(&mut self) {
+        self.prev_price = None;
+        self.count = 0;
+        self.sum_squares = F::zero();
+        self.variance = match self.omega {
+            Some(_) => self.long_run_variance(),
+            None => F::zero(),
+        };
+    }
+}
+
+impl<F: Float> Warmup for Garch11<F> {
+    fn warmup_period(&self) -> u32 {
+        2
+    }
+
+    fn is_ready(&self) -> bool {
+        self.count >= 1
+    }
+}
+
+impl Default for Garch11 {
+    fn default() -> Self {
+        Self::variance_targeting(0.1, 0.85).unwrap()
+    }
+}
+
+impl<F: Float> fmt::Display for Garch11<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.omega {
+            Some(omega) => write!(f, "GARCH({}, {}, {})", omega, self.alpha, self.beta),
+            None => write!(f, "GARCH(VT, {}, {})", self.alpha, self.beta),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(Garch11);
+
+    #[test]
+    fn test_new() {
+        assert!(Garch11::new(0.0, 0.1, 0.8).is_err());
+        assert!(Garch11::new(0.1, -0.1, 0.8).is_err());
+        assert!(Garch11::new(0.1, 0.2, 0.8).is_err());
+        assert!(Garch11::new(0.1, 0.1, 0.8).is_ok());
+        assert!(Garch11::variance_targeting(0.5, 0.5).is_err());
+        assert!(Garch11::variance_targeting(0.1, 0.8).is_ok());
+    }
+
+    #[test]
+    fn test_fixed() {
+        let mut garch = Garch11::new(0.0001, 0.1, 0.8).unwrap();
+        assert_eq!(round(garch.calc(100.0)), round(0.001f64.sqrt()));
+
+        // r = ln(1.1) = 0.09531, r^2 = 0.009084
+        // variance = 0.0001 + 0.1 * 0.009084 + 0.8 * 0.001 = 0.0018084
+        let vol = garch.calc(110.0);
+        assert_eq!((vol * vol * 1e7).round(), 18_084.0);
+
+        // no change: variance = 0.0001 + 0.8 * 0.0018084
+        let vol = garch.calc(110.0);
+        assert_eq!((vol * vol * 1e7).round(), 15_467.0);
+    }
+
+    #[test]
+    fn test_variance_targeting() {
+        let mut garch = Garch11::variance_targeting(0.1, 0.8).unwrap();
+        assert_eq!(garch.calc(100.0), 0.0);
+
+        let vol = garch.calc(110.0);
+        let r2 = (1.1f64).ln().powi(2);
+        assert_eq!(round(vol), round(r2.sqrt()));
+        assert_eq!(round(garch.long_run_variance()), round(r2));
+
+        // omega = 0.1 * r2 / 2
+        let vol = garch.calc(110.0);
+        assert_eq!(round(vol * vol), round(0.05 * r2 + 0.8 * r2));
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut garch = Garch11::new(0.0001, 0.1, 0.8).unwrap();
+        garch.next(&Bar::new().close(100.0));
+        let vol = garch.next(&Bar::new().close(110.0));
+        assert_eq!((vol * vol * 1e7).round(), 18_084.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut garch = Garch11::variance_targeting(0.1, 0.8).unwrap();
+        garch.calc(100.0);
+        garch.calc(120.0);
+
+        garch.reset();
+        assert_eq!(garch.calc(100.0), 0.0);
+        assert_eq!(garch.omega(), 0.0);
+    }
+
+    #[test]
+    fn test_default() {
+        Garch11::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let garch = Garch11::new(0.0001, 0.1, 0.8).unwrap();
+        assert_eq!(format!("{}", garch), "GARCH(0.0001, 0.1, 0.8)");
+        assert_eq!(format!("{}", Garch11::default()), "GARCH(VT, 0.1, 0.85)");
+    }
+}
@@ -0,0 +1,197 @@
+use core::fmt;
+
+use crate::errors::*;
+use crate::{Calculate, Close, Float, Next, Reset, RingBuffer, Warmup};
+
+/// Rolling geometric mean.
+///
+/// The _n_-th root of the product of the last _n_ values. Unlike the arithmetic mean it
+/// averages growth factors (e.g. `1 + return`) correctly: a gain of 50% followed by a loss
+/// of 50% averages to a factor of about 0.866, not 1.
+///
+/// # Formula
+///
+/// GM<sub>t</sub> = exp((ln(p<sub>t-n+1</sub>) + ... + ln(p<sub>t</sub>)) / n)
+///
+/// The geometric mean is only defined for positive values. While a zero or negative value
+/// is in the window the output is `NaN`.
+///
+/// # Parameters
+///
+/// * _n_ - number of periods (integer greater than 0). Default is 9.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::GeometricMean;
+/// use ta::Calculate;
+///
+/// let mut gm = GeometricMean::new(2).unwrap();
+/// assert_eq!(gm.calc(2.0), 2.0);
+/// assert_eq!(gm.calc(8.0), 4.0);
+/// assert!(gm.calc(0.0).is_nan());
+/// ```
+///
+/// # Links
+///
+/// * [Geometric mean, Wikipedia](https://en.wikipedia.org/wiki/Geometric_mean)
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GeometricMean<F = f64> {
+    n: u32,
+    non_positive: u32,
+    log_sum: F,
+    window: RingBuffer<F>,
+}
+
+impl GeometricMean {
+    pub fn new(n: u32) -> Result<Self> {
+        Self::with_type(n)
+    }
+}
+
+impl<F: Float> GeometricMean<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(n: u32) -> Result<Self> {
+        match n {
+            0 => Err(Error::from_kind(ErrorKind::InvalidParameter)),
+            _ => Ok(Self {
+                n,
+                non_positive: 0,
+                log_sum: F::zero(),
+                window: RingBuffer::new(n as usize),
+            }),
+        }
+    }
+
+    pub fn length(&self) -> u32 {
+        self.n
+    }
+}
+
+impl<F: Float> Calculate<F> for GeometricMean<F> {
+    type Output = F;
+
+    fn calc(&mut self, input: F) -> F {
+        if let Some(old_val) = self.window.push(input) {
+            if old_val > F::zero() {
+                self.log_sum -= old_val.ln();
+            } else {
+                self.non_positive -= 1;
+            }
+        }
+        if input > F::zero() {
+            self.log_sum += input.ln();
+        } else {
+            self.non_positive += 1;
+        }
+
+        if self.non_positive > 0 {
+            F::nan()
+        } else {
+            (self.log_sum / F::cast(self.window.len())).exp()
+        }
+    }
+}
+
+impl<F: Float, T: Close<F>> Next<T> for GeometricMean<F> {
+    type Output = F;
+
+    fn next(&mut self, input: &T) -> F {
+        self.calc(input.close())
+    }
+}
+
+impl<F: Float> Reset for GeometricMean<F> {
+    fn reset(&mut self) {
+        self.non_positive = 0;
+        self.log_sum = F::zero();
+        self.window.clear();
+    }
+}
+
+impl<F: Float> Warmup for GeometricMean<F> {
+    fn warmup_period(&self) -> u32 {
+        self.n
+    }
+
+    fn is_ready(&self) -> bool {
+        self.window.is_full()
+    }
+}
+
+impl Default for GeometricMean {
+    fn default() -> Self {
+        Self::new(9).unwrap()
+    }
+}
+
+impl<F: Float> fmt::Display for GeometricMean<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "GM({})", self.n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(GeometricMean);
+
+    #[test]
+    fn test_new() {
+        assert!(GeometricMean::new(0).is_err());
+        assert!(GeometricMean::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut gm = GeometricMean::new(3).unwrap();
+        assert_eq!(round(gm.calc(1.0)), 1.0);
+        assert_eq!(round(gm.calc(4.0)), 2.0);
+        assert_eq!(round(gm.calc(16.0)), 4.0);
+        assert_eq!(round(gm.calc(2.0)), 5.04);
+        assert_eq!(round(gm.next(&Bar::new().close(8.0))), 6.35);
+    }
+
+    #[test]
+    fn test_growth_factors() {
+        let mut gm = GeometricMean::new(2).unwrap();
+        gm.calc(1.5);
+        assert_eq!(round(gm.calc(0.5)), 0.866);
+    }
+
+    #[test]
+    fn test_non_positive() {
+        let mut gm = GeometricMean::new(2).unwrap();
+        assert_eq!(gm.calc(2.0), 2.0);
+        assert!(gm.calc(-1.0).is_nan());
+        assert!(gm.calc(8.0).is_nan());
+        assert!(gm.calc(0.0).is_nan());
+        assert!(gm.calc(2.0).is_nan());
+        assert_eq!(round(gm.calc(8.0)), 4.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut gm = GeometricMean::new(2).unwrap();
+        gm.calc(-1.0);
+        gm.calc(4.0);
+
+        gm.reset();
+        assert_eq!(round(gm.calc(9.0)), 9.0);
+    }
+
+    #[test]
+    fn test_default() {
+        GeometricMean::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let gm = GeometricMean::new(5).unwrap();
+        assert_eq!(format!("{}", gm), "GM(5)");
+    }
+}
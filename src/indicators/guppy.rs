@@ -0,0 +1,245 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::indicators::ribbon::{width, Ribbon, RibbonAverage};
+use crate::{Calculate, Close, Finite, Float, Next, Reset, Warmup};
+
+const SHORT_LENGTHS: [u32; 6] = [3, 5, 8, 10, 12, 15];
+const LONG_LENGTHS: [u32; 6] = [30, 35, 40, 45, 50, 60];
+
+/// Guppy Multiple Moving Average (GMMA).
+///
+/// Two [ribbons](struct.Ribbon.html) of exponential moving averages: the short group
+/// (3, 5, 8, 10, 12, 15) follows traders, the long group (30, 35, 40, 45, 50, 60)
+/// follows investors. A trend is confirmed when the groups are separated, and its
+/// strength changes as the distance between the groups expands or compresses.
+///
+/// # Output
+///
+/// * _short_, _long_ - values of the short and the long group averages
+/// * _short_width_, _long_width_ - width of each group in percent of its mean
+/// * _separation_ - distance between the means of the groups in percent of the long mean,
+///   positive when the short group is above
+/// * _expansion_ - change of the absolute separation since the previous bar; positive
+///   values mean the groups are expanding, negative values that they are compressing
+/// * _trend_ - 1 if the whole short group is above the long group, -1 if it is below,
+///   0 if the groups overlap
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::Guppy;
+/// use ta::Calculate;
+///
+/// let mut gmma = Guppy::new();
+/// let mut out = gmma.calc(10.0);
+/// for i in 1..100 {
+///     out = gmma.calc(10.0 + i as f64);
+/// }
+/// assert_eq!(out.trend, 1);
+/// assert!(out.separation > 0.0);
+/// ```
+///
+/// # Links
+///
+/// * [Guppy Multiple Moving Average, Investopedia](https://www.investopedia.com/terms/g/guppy-multiple-moving-average.asp)
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(deserialize = "F: Float + serde::Deserialize<'de>"))
+)]
+pub struct Guppy<F = f64> {
+    short: Ribbon<F>,
+    long: Ribbon<F>,
+    prev_separation: Option<F>,
+}
+
+/// Output of [Guppy](struct.Guppy.html).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GuppyOutput<F = f64> {
+    pub short: Vec<F>,
+    pub long: Vec<F>,
+    pub short_width: F,
+    pub long_width: F,
+    pub separation: F,
+    pub expansion: F,
+    pub trend: i8,
+}
+
+impl<F: Finite> Finite for GuppyOutput<F> {
+    fn is_finite(&self) -> bool {
+        self.short.iter().all(Finite::is_finite)
+            && self.long.iter().all(Finite::is_finite)
+            && self.short_width.is_finite()
+            && self.long_width.is_finite()
+            && self.separation.is_finite()
+            && self.expansion.is_finite()
+    }
+}
+
+impl Guppy {
+    pub fn new() -> Self {
+        Self::with_type()
+    }
+}
+
+impl<F: Float> Guppy<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type() -> Self {
+        let ribbon = |lengths: &[u32]| {
+            let (from, to) = (lengths[0], lengths[lengths.len() - 1]);
+            Ribbon::with_lengths(RibbonAverage::Exponential, from, to, 0, lengths).unwrap()
+        };
+        Self {
+            short: ribbon(&SHORT_LENGTHS),
+            long: ribbon(&LONG_LENGTHS),
+            prev_separation: None,
+        }
+    }
+}
+
+impl<F: Float> Calculate<F> for Guppy<F> {
+    type Output = GuppyOutput<F>;
+
+    fn calc(&mut self, input: F) -> Self::Output {
+        let short = self.short.calc(input).values;
+        let long = self.long.calc(input).values;
+
+        let mean = |values: &[F]| values.iter().sum::<F>() / F::cast(values.len());
+        let long_mean = mean(&long);
+        let separation = if long_mean == F::zero() {
+            F::zero()
+        } else {
+            (mean(&short) - long_mean) / long_mean.abs() * F::cast(100.0)
+        };
+        let expansion = match self.prev_separation {
+            Some(prev) => separation.abs() - prev.abs(),
+            None => F::zero(),
+        };
+        self.prev_separation = Some(separation);
+
+        let max = |values: &[F]| values.iter().cloned().fold(F::neg_infinity(), F::max);
+        let min = |values: &[F]| values.iter().cloned().fold(F::infinity(), F::min);
+        let trend = if min(&short) > max(&long) {
+            1
+        } else if max(&short) < min(&long) {
+            -1
+        } else {
+            0
+        };
+
+        GuppyOutput {
+            short_width: width(&short),
+            long_width: width(&long),
+            short,
+            long,
+            separation,
+            expansion,
+            trend,
+        }
+    }
+}
+
+impl<F: Float, T: Close<F>> Next<T> for Guppy<F> {
+    type Output = GuppyOutput<F>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.calc(input.close())
+    }
+}
+
+impl<F: Float> Reset for Guppy<F> {
+    fn reset(&mut self) {
+        self.short.reset();
+        self.long.reset();
+        self.prev_separation = None;
+    }
+}
+
+impl<F: Float> Warmup for Guppy<F> {
+    fn warmup_period(&self) -> u32 {
+        self.long.warmup_period()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.short.is_ready() && self.long.is_ready()
+    }
+}
+
+impl Default for Guppy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: Float> fmt::Display for Guppy<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "GMMA()")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(Guppy);
+
+    #[test]
+    fn test_groups() {
+        let mut gmma = Guppy::new();
+        let out = gmma.calc(5.0);
+        assert_eq!(out.short, vec![5.0; 6]);
+        assert_eq!(out.long, vec![5.0; 6]);
+        assert_eq!(out.separation, 0.0);
+        assert_eq!(out.expansion, 0.0);
+        assert_eq!(out.trend, 0);
+    }
+
+    #[test]
+    fn test_downtrend() {
+        let mut gmma = Guppy::new();
+        let mut prev = gmma.calc(200.0);
+        for i in 1..50 {
+            let out = gmma.next(&Bar::new().close(200.0 - i as f64));
+            assert!(out.separation.abs() >= prev.separation.abs());
+            prev = out;
+        }
+        assert_eq!(prev.trend, -1);
+        assert!(prev.expansion > 0.0);
+        assert!(prev.short_width < prev.long_width);
+    }
+
+    #[test]
+    fn test_compression() {
+        let mut gmma = Guppy::new();
+        for i in 0..50 {
+            gmma.calc(100.0 + i as f64);
+        }
+        let mut out = gmma.calc(149.0);
+        for _ in 0..50 {
+            out = gmma.calc(149.0);
+        }
+        assert!(out.expansion < 0.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut gmma = Guppy::new();
+        gmma.calc(10.0);
+        gmma.calc(20.0);
+
+        gmma.reset();
+        let out = gmma.calc(30.0);
+        assert_eq!(out.short, vec![30.0; 6]);
+        assert_eq!(out.expansion, 0.0);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", Guppy::default()), "GMMA()");
+    }
+}
@@ -0,0 +1,188 @@
+use core::fmt;
+
+use crate::errors::*;
+use crate::{Calculate, Close, Float, Next, Reset, RingBuffer, Warmup};
+
+/// Rolling harmonic mean.
+///
+/// The reciprocal of the arithmetic mean of reciprocals of the last _n_ values. It is the
+/// right average for rates and ratios, e.g. the average price paid when investing the same
+/// amount at each step, or the average of price-to-earnings ratios.
+///
+/// # Formula
+///
+/// HM<sub>t</sub> = n / (1 / p<sub>t-n+1</sub> + ... + 1 / p<sub>t</sub>)
+///
+/// The harmonic mean is only computed for positive values. While a zero or negative value
+/// is in the window the output is `NaN`.
+///
+/// # Parameters
+///
+/// * _n_ - number of periods (integer greater than 0). Default is 9.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::HarmonicMean;
+/// use ta::Calculate;
+///
+/// let mut hm = HarmonicMean::new(2).unwrap();
+/// assert_eq!(hm.calc(2.0), 2.0);
+/// assert_eq!(hm.calc(6.0), 3.0);
+/// assert!(hm.calc(-1.0).is_nan());
+/// ```
+///
+/// # Links
+///
+/// * [Harmonic mean, Wikipedia](https://en.wikipedia.org/wiki/Harmonic_mean)
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HarmonicMean<F = f64> {
+    n: u32,
+    non_positive: u32,
+    reciprocal_sum: F,
+    window: RingBuffer<F>,
+}
+
+impl HarmonicMean {
+    pub fn new(n: u32) -> Result<Self> {
+        Self::with_type(n)
+    }
+}
+
+impl<F: Float> HarmonicMean<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(n: u32) -> Result<Self> {
+        match n {
+            0 => Err(Error::from_kind(ErrorKind::InvalidParameter)),
+            _ => Ok(Self {
+                n,
+                non_positive: 0,
+                reciprocal_sum: F::zero(),
+                window: RingBuffer::new(n as usize),
+            }),
+        }
+    }
+
+    pub fn length(&self) -> u32 {
+        self.n
+    }
+}
+
+impl<F: Float> Calculate<F> for HarmonicMean<F> {
+    type Output = F;
+
+    fn calc(&mut self, input: F) -> F {
+        if let Some(old_val) = self.window.push(input) {
+            if old_val > F::zero() {
+                self.reciprocal_sum -= old_val.recip();
+            } else {
+                self.non_positive -= 1;
+            }
+        }
+        if input > F::zero() {
+            self.reciprocal_sum += input.recip();
+        } else {
+            self.non_positive += 1;
+        }
+
+        if self.non_positive > 0 {
+            F::nan()
+        } else {
+            F::cast(self.window.len()) / self.reciprocal_sum
+        }
+    }
+}
+
+impl<F: Float, T: Close<F>> Next<T> for HarmonicMean<F> {
+    type Output = F;
+
+    fn next(&mut self, input: &T) -> F {
+        self.calc(input.close())
+    }
+}
+
+impl<F: Float> Reset for HarmonicMean<F> {
+    fn reset(&mut self) {
+        self.non_positive = 0;
+        self.reciprocal_sum = F::zero();
+        self.window.clear();
+    }
+}
+
+impl<F: Float> Warmup for HarmonicMean<F> {
+    fn warmup_period(&self) -> u32 {
+        self.n
+    }
+
+    fn is_ready(&self) -> bool {
+        self.window.is_full()
+    }
+}
+
+impl Default for HarmonicMean {
+    fn default() -> Self {
+        Self::new(9).unwrap()
+    }
+}
+
+impl<F: Float> fmt::Display for HarmonicMean<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "HM({})", self.n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(HarmonicMean);
+
+    #[test]
+    fn test_new() {
+        assert!(HarmonicMean::new(0).is_err());
+        assert!(HarmonicMean::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut hm = HarmonicMean::new(3).unwrap();
+        assert_eq!(round(hm.calc(1.0)), 1.0);
+        assert_eq!(round(hm.calc(4.0)), 1.6);
+        assert_eq!(round(hm.calc(4.0)), 2.0);
+        assert_eq!(round(hm.calc(2.0)), 3.0);
+        assert_eq!(round(hm.next(&Bar::new().close(8.0))), 3.429);
+    }
+
+    #[test]
+    fn test_non_positive() {
+        let mut hm = HarmonicMean::new(2).unwrap();
+        assert_eq!(hm.calc(2.0), 2.0);
+        assert!(hm.calc(0.0).is_nan());
+        assert!(hm.calc(3.0).is_nan());
+        assert_eq!(round(hm.calc(6.0)), 4.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut hm = HarmonicMean::new(2).unwrap();
+        hm.calc(0.0);
+        hm.calc(4.0);
+
+        hm.reset();
+        assert_eq!(hm.calc(9.0), 9.0);
+    }
+
+    #[test]
+    fn test_default() {
+        HarmonicMean::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let hm = HarmonicMean::new(5).unwrap();
+        assert_eq!(format!("{}", hm), "HM(5)");
+    }
+}
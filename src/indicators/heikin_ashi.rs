@@ -0,0 +1,214 @@
+use core::fmt;
+
+use crate::{Calculate, Close, Float, High, Low, Next, Open, Reset, Warmup};
+
+/// Heikin-Ashi candles.
+///
+/// Transforms bars into Heikin-Ashi bars, which average out the noise of the raw prices and
+/// make trends easier to see. The output implements [Open](../trait.Open.html),
+/// [High](../trait.High.html), [Low](../trait.Low.html) and [Close](../trait.Close.html), so
+/// any indicator can run on the smoothed candles, e.g. by
+/// [piping](../combinators/trait.IndicatorExt.html#method.pipe) them into it.
+///
+/// # Formula
+///
+/// * HA close = (open + high + low + close) / 4
+/// * HA open = (HA open<sub>prev</sub> + HA close<sub>prev</sub>) / 2, (open + close) / 2 on
+///   the first bar
+/// * HA high = max(high, HA open, HA close)
+/// * HA low = min(low, HA open, HA close)
+///
+/// A single value is transformed as a bar with all its prices equal to it.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{HeikinAshi, SimpleMovingAverage};
+/// use ta::{Close, DataItem, IndicatorExt, Next, Open};
+///
+/// let bar = |open, high, low, close| {
+///     DataItem::builder()
+///         .open(open)
+///         .high(high)
+///         .low(low)
+///         .close(close)
+///         .volume(1.0)
+///         .build()
+///         .unwrap()
+/// };
+///
+/// let mut ha = HeikinAshi::new();
+/// let candle = ha.next(&bar(10.0, 14.0, 9.0, 13.0));
+/// assert_eq!((candle.open(), candle.close()), (11.5, 11.5));
+/// let candle = ha.next(&bar(13.0, 15.0, 12.0, 14.0));
+/// assert_eq!((candle.open(), candle.close()), (11.5, 13.5));
+///
+/// // SMA of the Heikin-Ashi closes
+/// let mut sma = HeikinAshi::new().pipe(SimpleMovingAverage::new(2).unwrap());
+/// sma.next(&bar(10.0, 14.0, 9.0, 13.0));
+/// assert_eq!(sma.next(&bar(13.0, 15.0, 12.0, 14.0)), 12.5);
+/// ```
+///
+/// # Links
+///
+/// * [Heikin-Ashi, Investopedia](https://www.investopedia.com/trading/heikin-ashi-better-candlestick/)
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HeikinAshi<F = f64> {
+    prev: Option<HeikinAshiBar<F>>,
+}
+
+/// Candle of [HeikinAshi](struct.HeikinAshi.html).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HeikinAshiBar<F = f64> {
+    pub open: F,
+    pub high: F,
+    pub low: F,
+    pub close: F,
+}
+
+impl HeikinAshi {
+    pub fn new() -> Self {
+        Self::with_type()
+    }
+}
+
+impl<F: Float> HeikinAshi<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type() -> Self {
+        Self { prev: None }
+    }
+
+    fn transform(&mut self, open: F, high: F, low: F, close: F) -> HeikinAshiBar<F> {
+        let two = F::cast(2);
+        let ha_close = (open + high + low + close) / F::cast(4);
+        let ha_open = match &self.prev {
+            Some(prev) => (prev.open + prev.close) / two,
+            None => (open + close) / two,
+        };
+        let bar = HeikinAshiBar {
+            open: ha_open,
+            high: high.max(ha_open).max(ha_close),
+            low: low.min(ha_open).min(ha_close),
+            close: ha_close,
+        };
+        self.prev = Some(bar);
+        bar
+    }
+}
+
+impl<F: Float> Calculate<F> for HeikinAshi<F> {
+    type Output = HeikinAshiBar<F>;
+
+    fn calc(&mut self, input: F) -> Self::Output {
+        self.transform(input, input, input, input)
+    }
+}
+
+impl<F: Float, T: Open<F> + High<F> + Low<F> + Close<F>> Next<T> for HeikinAshi<F> {
+    type Output = HeikinAshiBar<F>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.transform(input.open(), input.high(), input.low(), input.close())
+    }
+}
+
+impl<F: Float> Reset for HeikinAshi<F> {
+    fn reset(&mut self) {
+        self.prev = None;
+    }
+}
+
+impl<F: Float> Warmup for HeikinAshi<F> {
+    fn warmup_period(&self) -> u32 {
+        1
+    }
+
+    fn is_ready(&self) -> bool {
+        self.prev.is_some()
+    }
+}
+
+impl Default for HeikinAshi {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: Float> fmt::Display for HeikinAshi<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "HA()")
+    }
+}
+
+impl<F: Copy> Open<F> for HeikinAshiBar<F> {
+    fn open(&self) -> F {
+        self.open
+    }
+}
+
+impl<F: Copy> High<F> for HeikinAshiBar<F> {
+    fn high(&self) -> F {
+        self.high
+    }
+}
+
+impl<F: Copy> Low<F> for HeikinAshiBar<F> {
+    fn low(&self) -> F {
+        self.low
+    }
+}
+
+impl<F: Copy> Close<F> for HeikinAshiBar<F> {
+    fn close(&self) -> F {
+        self.close
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(HeikinAshi);
+
+    #[test]
+    fn test_next() {
+        let mut ha = HeikinAshi::new();
+        let first = ha.next(&Bar::new().open(10).high(14).low(9).close(13));
+        assert_eq!(
+            first,
+            HeikinAshiBar {
+                open: 11.5,
+                high: 14.0,
+                low: 9.0,
+                close: 11.5
+            }
+        );
+
+        // a gap down below the previous candle
+        let second = ha.next(&Bar::new().open(8).high(9).low(6).close(7));
+        assert_eq!(second.open, 11.5);
+        assert_eq!(second.close, 7.5);
+        assert_eq!(second.high, 11.5);
+        assert_eq!(second.low, 6.0);
+    }
+
+    #[test]
+    fn test_calc() {
+        let mut ha = HeikinAshi::new();
+        assert_eq!(ha.calc(10.0).close, 10.0);
+        let bar = ha.calc(12.0);
+        assert_eq!(
+            (bar.open, bar.high, bar.low, bar.close),
+            (10.0, 12.0, 10.0, 12.0)
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", HeikinAshi::new()), "HA()");
+    }
+}
@@ -0,0 +1,291 @@
+use core::fmt;
+
+use crate::errors::*;
+use crate::{Calculate, Close, Float, Next, Reset, Warmup};
+
+/// Direction of a [Kagi](struct.Kagi.html) line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum KagiDirection {
+    Up,
+    Down,
+}
+
+/// Thickness of a [Kagi](struct.Kagi.html) line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum KagiThickness {
+    /// Thick line, the price rose above the previous shoulder.
+    Yang,
+    /// Thin line, the price fell below the previous waist.
+    Yin,
+}
+
+/// Line of a [Kagi](struct.Kagi.html) chart.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KagiLine<F = f64> {
+    pub direction: KagiDirection,
+    /// Price the line starts at, the end of the previous line.
+    pub start: F,
+    /// Extreme price the line reached, where the next line turns.
+    pub end: F,
+    /// Thickness at the end of the line.
+    pub thickness: KagiThickness,
+}
+
+/// Kagi chart.
+///
+/// Transforms closing prices into the vertical lines of a Kagi chart, which ignore time and
+/// small moves. The current line is extended while the price moves in its direction and
+/// reverses once the price moves against it by at least the reversal amount. The line turns
+/// thick (yang) when the price rises above the previous shoulder, the top of the last up
+/// line, and thin (yin) when it falls below the previous waist, the bottom of the last down
+/// line.
+///
+/// Returns the completed line on every reversal and `None` otherwise; the forming line is
+/// available with [current](#method.current). The first line starts at the first price and
+/// takes the direction of the first move away from it.
+///
+/// # Parameters
+///
+/// * _reversal_ - price move reversing the line (greater than 0). Default is 1.0.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{Kagi, KagiDirection};
+/// use ta::Calculate;
+///
+/// let mut kagi = Kagi::new(2.0).unwrap();
+/// assert_eq!(kagi.calc(10.0), None);
+/// assert_eq!(kagi.calc(13.0), None);
+/// // less than the reversal amount
+/// assert_eq!(kagi.calc(12.0), None);
+///
+/// let line = kagi.calc(10.5).unwrap();
+/// assert_eq!(line.direction, KagiDirection::Up);
+/// assert_eq!((line.start, line.end), (10.0, 13.0));
+///
+/// let current = kagi.current().unwrap();
+/// assert_eq!(current.direction, KagiDirection::Down);
+/// assert_eq!((current.start, current.end), (13.0, 10.5));
+/// ```
+///
+/// # Links
+///
+/// * [Kagi Chart, Investopedia](https://www.investopedia.com/terms/k/kagichart.asp)
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Kagi<F = f64> {
+    reversal: F,
+    // first price, until the line gets a direction
+    origin: Option<F>,
+    line: Option<KagiLine<F>>,
+    shoulder: Option<F>,
+    waist: Option<F>,
+}
+
+impl Kagi {
+    pub fn new(reversal: f64) -> Result<Self> {
+        Self::with_type(reversal)
+    }
+}
+
+impl<F: Float> Kagi<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(reversal: F) -> Result<Self> {
+        if reversal <= F::zero() || !reversal.is_finite() {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        Ok(Self {
+            reversal,
+            origin: None,
+            line: None,
+            shoulder: None,
+            waist: None,
+        })
+    }
+
+    /// Line still forming, `None` until the price first moves.
+    pub fn current(&self) -> Option<&KagiLine<F>> {
+        self.line.as_ref()
+    }
+
+    fn thickness(&self, price: F, thickness: KagiThickness) -> KagiThickness {
+        match (self.shoulder, self.waist) {
+            (Some(shoulder), _) if price > shoulder => KagiThickness::Yang,
+            (_, Some(waist)) if price < waist => KagiThickness::Yin,
+            _ => thickness,
+        }
+    }
+}
+
+impl<F: Float> Calculate<F> for Kagi<F> {
+    type Output = Option<KagiLine<F>>;
+
+    fn calc(&mut self, input: F) -> Self::Output {
+        let mut line = match self.line {
+            Some(line) => line,
+            None => {
+                let origin = *self.origin.get_or_insert(input);
+                if input != origin {
+                    let (direction, thickness) = if input > origin {
+                        (KagiDirection::Up, KagiThickness::Yang)
+                    } else {
+                        (KagiDirection::Down, KagiThickness::Yin)
+                    };
+                    self.line = Some(KagiLine {
+                        direction,
+                        start: origin,
+                        end: input,
+                        thickness,
+                    });
+                }
+                return None;
+            }
+        };
+
+        let reversed = match line.direction {
+            KagiDirection::Up if input > line.end => {
+                line.end = input;
+                None
+            }
+            KagiDirection::Down if input < line.end => {
+                line.end = input;
+                None
+            }
+            KagiDirection::Up if line.end - input >= self.reversal => {
+                self.shoulder = Some(line.end);
+                Some(KagiDirection::Down)
+            }
+            KagiDirection::Down if input - line.end >= self.reversal => {
+                self.waist = Some(line.end);
+                Some(KagiDirection::Up)
+            }
+            _ => None,
+        };
+
+        match reversed {
+            Some(direction) => {
+                let thickness = self.thickness(input, line.thickness);
+                self.line = Some(KagiLine {
+                    direction,
+                    start: line.end,
+                    end: input,
+                    thickness,
+                });
+                Some(line)
+            }
+            None => {
+                line.thickness = self.thickness(line.end, line.thickness);
+                self.line = Some(line);
+                None
+            }
+        }
+    }
+}
+
+impl<F: Float, T: Close<F>> Next<T> for Kagi<F> {
+    type Output = Option<KagiLine<F>>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.calc(input.close())
+    }
+}
+
+impl<F: Float> Reset for Kagi<F> {
+    fn reset(&mut self) {
+        self.origin = None;
+        self.line = None;
+        self.shoulder = None;
+        self.waist = None;
+    }
+}
+
+impl<F: Float> Warmup for Kagi<F> {
+    fn warmup_period(&self) -> u32 {
+        1
+    }
+
+    fn is_ready(&self) -> bool {
+        self.origin.is_some()
+    }
+}
+
+impl Default for Kagi {
+    fn default() -> Self {
+        Self::new(1.0).unwrap()
+    }
+}
+
+impl<F: Float> fmt::Display for Kagi<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "KAGI({})", self.reversal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(Kagi);
+
+    #[test]
+    fn test_new() {
+        assert!(Kagi::new(0.0).is_err());
+        assert!(Kagi::new(f64::NAN).is_err());
+        assert!(Kagi::new(0.5).is_ok());
+    }
+
+    #[test]
+    fn test_lines() {
+        let mut kagi = Kagi::new(2.0).unwrap();
+        let closes = [10.0, 10.0, 9.0, 7.0, 8.5, 9.5, 12.0, 11.0, 9.0, 8.0, 6.0];
+        let lines: Vec<_> = closes.iter().filter_map(|&c| kagi.calc(c)).collect();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].direction, KagiDirection::Down);
+        assert_eq!((lines[0].start, lines[0].end), (10.0, 7.0));
+        assert_eq!(lines[0].thickness, KagiThickness::Yin);
+        assert_eq!(lines[1].direction, KagiDirection::Up);
+        assert_eq!((lines[1].start, lines[1].end), (7.0, 12.0));
+        assert_eq!(lines[1].thickness, KagiThickness::Yin);
+
+        // below the waist at 7.0
+        let current = kagi.current().unwrap();
+        assert_eq!((current.start, current.end), (12.0, 6.0));
+        assert_eq!(current.thickness, KagiThickness::Yin);
+    }
+
+    #[test]
+    fn test_thickness() {
+        let mut kagi = Kagi::new(1.0).unwrap();
+        for &close in &[5.0, 3.0, 4.0, 2.0, 4.0] {
+            kagi.calc(close);
+        }
+        // the line stays thin up to the shoulder at 4.0 and turns thick above it
+        assert_eq!(kagi.current().unwrap().thickness, KagiThickness::Yin);
+        kagi.calc(4.5);
+        assert_eq!(kagi.current().unwrap().thickness, KagiThickness::Yang);
+        let line = kagi.calc(3.0).unwrap();
+        assert_eq!((line.start, line.end), (2.0, 4.5));
+        assert_eq!(line.thickness, KagiThickness::Yang);
+    }
+
+    #[test]
+    fn test_next() {
+        let mut kagi = Kagi::new(1.0).unwrap();
+        kagi.next(&Bar::new().close(10));
+        kagi.next(&Bar::new().close(12));
+        let line = kagi.next(&Bar::new().close(11)).unwrap();
+        assert_eq!((line.start, line.end), (10.0, 12.0));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", Kagi::new(2.5).unwrap()), "KAGI(2.5)");
+    }
+}
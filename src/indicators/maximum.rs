@@ -1,8 +1,12 @@
-use std::f64::INFINITY;
-use std::fmt;
+use core::fmt;
 
 use crate::errors::*;
-use crate::{Calculate, High, Next, Reset};
+use crate::helpers::ConstLength;
+use crate::{
+    Calculate, Float, High, Next, Peek, PeekNext, Replace, Reset, Storage, UpdateLast, Warmup,
+    Window,
+};
+use alloc::vec::Vec;
 
 /// Returns the highest value in a given time frame.
 ///
@@ -24,15 +28,72 @@ use crate::{Calculate, High, Next, Reset};
 /// assert_eq!(max.calc(8.0), 8.0);
 /// ```
 #[derive(Debug, Clone)]
-pub struct Maximum {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        try_from = "MaximumFields<F, S>",
+        bound(
+            serialize = "F: serde::Serialize, S: Storage<F>",
+            deserialize = "F: Float + serde::Deserialize<'de>, S: Storage<F>"
+        )
+    )
+)]
+pub struct Maximum<F = f64, S = Vec<F>> {
     n: usize,
-    vec: Vec<f64>,
+    window: Window<F, S>,
+    // position of the maximum in the window, counted from the oldest value
     max_index: usize,
-    cur_index: usize,
+}
+
+// Fields of a deserialized maximum, whose index has to point at the maximum of the window.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(deserialize = "F: Float + serde::Deserialize<'de>, S: Storage<F>"))
+)]
+struct MaximumFields<F, S> {
+    n: usize,
+    window: Window<F, S>,
+    max_index: usize,
+}
+
+#[cfg(feature = "serde")]
+impl<F: Float, S: Storage<F>> core::convert::TryFrom<MaximumFields<F, S>> for Maximum<F, S> {
+    type Error = Error;
+
+    fn try_from(fields: MaximumFields<F, S>) -> Result<Self> {
+        let MaximumFields {
+            n,
+            window,
+            max_index,
+        } = fields;
+        let valid = n == window.capacity()
+            && match window.get(max_index) {
+                Some(&max) => !window.iter().any(|&value| value > max),
+                None => window.is_empty() && max_index == 0,
+            };
+        if !valid {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        Ok(Self {
+            n,
+            window,
+            max_index,
+        })
+    }
 }
 
 impl Maximum {
     pub fn new(n: u32) -> Result<Self> {
+        Self::with_type(n)
+    }
+}
+
+impl<F: Float> Maximum<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(n: u32) -> Result<Self> {
         let n = n as usize;
 
         if n == 0 {
@@ -40,55 +101,160 @@ impl Maximum {
         }
 
         let indicator = Self {
-            n: n,
-            vec: vec![-INFINITY; n],
+            n,
+            window: Window::new(n),
             max_index: 0,
-            cur_index: 0,
         };
+
         Ok(indicator)
     }
 
+    /// Changes the size of the time frame, keeping the latest values which fit into the new
+    /// one. Fails with `InvalidParameter` if `n` is 0.
+    pub fn set_length(&mut self, n: u32) -> Result<()> {
+        let n = n as usize;
+        if n == 0 {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+
+        self.n = n;
+        self.window.resize(n);
+        self.max_index = self.find_max_index();
+        Ok(())
+    }
+
+    /// Like [set_length](#method.set_length), consuming the indicator.
+    pub fn with_length(mut self, n: u32) -> Result<Self> {
+        self.set_length(n)?;
+        Ok(self)
+    }
+}
+
+impl<F: Float, S: Storage<F>> Maximum<F, S> {
+    pub fn length(&self) -> u32 {
+        self.n as u32
+    }
+
     fn find_max_index(&self) -> usize {
-        let mut max = -INFINITY;
+        let mut max = F::neg_infinity();
         let mut index: usize = 0;
 
-        for (i, &val) in self.vec.iter().enumerate() {
+        // the two slices are scanned separately, which is faster than a chained iterator
+        let (older, newer) = self.window.as_slices();
+        for (i, &val) in older.iter().enumerate() {
             if val > max {
                 max = val;
                 index = i;
             }
         }
+        for (i, &val) in newer.iter().enumerate() {
+            if val > max {
+                max = val;
+                index = older.len() + i;
+            }
+        }
 
         index
     }
 }
 
-impl Calculate for Maximum {
-    fn calc(&mut self, input: f64) -> f64 {
-        self.cur_index = (self.cur_index + 1) % (self.n as usize);
-        self.vec[self.cur_index] = input;
+impl<F: Float, S: Storage<F>> Calculate<F> for Maximum<F, S> {
+    type Output = F;
+
+    fn calc(&mut self, input: F) -> F {
+        if self.window.push(input).is_some() {
+            if self.max_index == 0 {
+                // the maximum left the window
+                self.max_index = self.find_max_index();
+                return self.window[self.max_index];
+            }
+            self.max_index -= 1;
+        }
+
+        let max = self.window[self.max_index];
+        if input > max {
+            self.max_index = self.window.len() - 1;
+            input
+        } else {
+            max
+        }
+    }
+}
+
+impl<F: Float, S: Storage<F>> UpdateLast<F> for Maximum<F, S> {
+    fn update_last(&mut self, input: F) -> F {
+        if self.window.replace_newest(input).is_none() {
+            return self.calc(input);
+        }
 
-        if input > self.vec[self.max_index] {
-            self.max_index = self.cur_index;
-        } else if self.max_index == self.cur_index {
+        let last = self.window.len() - 1;
+        if input > self.window[self.max_index] {
+            self.max_index = last;
+        } else if self.max_index == last {
             self.max_index = self.find_max_index();
         }
 
-        self.vec[self.max_index]
+        self.window[self.max_index]
+    }
+}
+
+impl<F: Float, S: Storage<F>> Peek<F> for Maximum<F, S> {
+    fn peek(&self, input: F) -> F {
+        let max = if self.window.is_full() && self.max_index == 0 {
+            // the current maximum would leave the window
+            self.window.iter().skip(1).fold(
+                F::neg_infinity(),
+                |max, &val| if val > max { val } else { max },
+            )
+        } else {
+            self.window
+                .get(self.max_index)
+                .copied()
+                .unwrap_or_else(F::neg_infinity)
+        };
+
+        if input > max {
+            input
+        } else {
+            max
+        }
     }
 }
 
-impl<T: High> Next<T> for Maximum {
-    fn next(&mut self, input: &T) -> f64 {
+impl<F: Float, S: Storage<F>, T: High<F>> Next<T> for Maximum<F, S> {
+    type Output = F;
+
+    fn next(&mut self, input: &T) -> F {
         self.calc(input.high())
     }
 }
 
-impl Reset for Maximum {
+impl<F: Float, S: Storage<F>, T: High<F>> Replace<T> for Maximum<F, S> {
+    fn replace(&mut self, input: &T) -> F {
+        self.update_last(input.high())
+    }
+}
+
+impl<F: Float, S: Storage<F>, T: High<F>> PeekNext<T> for Maximum<F, S> {
+    fn peek_next(&self, input: &T) -> F {
+        self.peek(input.high())
+    }
+}
+
+impl<F: Float, S: Storage<F>> Reset for Maximum<F, S> {
     fn reset(&mut self) {
-        for i in 0..self.n {
-            self.vec[i] = -INFINITY;
-        }
+        self.window.clear();
+        self.max_index = 0;
+    }
+}
+
+impl<F: Float, S: Storage<F>> Warmup for Maximum<F, S> {
+    fn warmup_period(&self) -> u32 {
+        self.n as u32
+    }
+
+    fn is_ready(&self) -> bool {
+        self.window.is_full()
     }
 }
 
@@ -98,12 +264,137 @@ impl Default for Maximum {
     }
 }
 
-impl fmt::Display for Maximum {
+impl<F: Float, S: Storage<F>> fmt::Display for Maximum<F, S> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "MAX({})", self.n)
     }
 }
 
+/// [Maximum](struct.Maximum.html) over a window of _N_ values fixed at compile time.
+///
+/// The window is stored inline in an array instead of a heap allocated vector, for
+/// latency-critical paths and targets without an allocator. Outputs are the same as of
+/// `Maximum::new(N)`.
+///
+/// # Parameters
+///
+/// * _N_ - size of the time frame (greater than 0, `default()` fails to compile otherwise).
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::MaximumConst;
+/// use ta::Calculate;
+///
+/// let mut max = MaximumConst::<3>::new().unwrap();
+/// assert_eq!(max.calc(7.0), 7.0);
+/// assert_eq!(max.calc(5.0), 7.0);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        transparent,
+        bound(
+            serialize = "F: Float + serde::Serialize",
+            deserialize = "F: Float + serde::Deserialize<'de>"
+        )
+    )
+)]
+pub struct MaximumConst<const N: usize, F = f64> {
+    inner: Maximum<F, [F; N]>,
+}
+
+impl<const N: usize, F: Float> MaximumConst<N, F> {
+    pub fn new() -> Result<Self> {
+        if N == 0 {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+
+        Ok(Self {
+            inner: Maximum {
+                n: N,
+                window: Window::inline(),
+                max_index: 0,
+            },
+        })
+    }
+
+    pub fn length(&self) -> u32 {
+        N as u32
+    }
+}
+
+impl<const N: usize, F: Float> Calculate<F> for MaximumConst<N, F> {
+    type Output = F;
+
+    fn calc(&mut self, input: F) -> F {
+        self.inner.calc(input)
+    }
+}
+
+impl<const N: usize, F: Float> UpdateLast<F> for MaximumConst<N, F> {
+    fn update_last(&mut self, input: F) -> F {
+        self.inner.update_last(input)
+    }
+}
+
+impl<const N: usize, F: Float> Peek<F> for MaximumConst<N, F> {
+    fn peek(&self, input: F) -> F {
+        self.inner.peek(input)
+    }
+}
+
+impl<const N: usize, F: Float, T: High<F>> Next<T> for MaximumConst<N, F> {
+    type Output = F;
+
+    fn next(&mut self, input: &T) -> F {
+        self.inner.next(input)
+    }
+}
+
+impl<const N: usize, F: Float, T: High<F>> Replace<T> for MaximumConst<N, F> {
+    fn replace(&mut self, input: &T) -> F {
+        self.inner.replace(input)
+    }
+}
+
+impl<const N: usize, F: Float, T: High<F>> PeekNext<T> for MaximumConst<N, F> {
+    fn peek_next(&self, input: &T) -> F {
+        self.inner.peek_next(input)
+    }
+}
+
+impl<const N: usize, F: Float> Reset for MaximumConst<N, F> {
+    fn reset(&mut self) {
+        self.inner.reset();
+    }
+}
+
+impl<const N: usize, F: Float> Warmup for MaximumConst<N, F> {
+    fn warmup_period(&self) -> u32 {
+        self.inner.warmup_period()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.inner.is_ready()
+    }
+}
+
+impl<const N: usize, F: Float> Default for MaximumConst<N, F> {
+    fn default() -> Self {
+        let () = ConstLength::<N>::NONZERO;
+        Self::new().unwrap()
+    }
+}
+
+impl<const N: usize, F: Float> fmt::Display for MaximumConst<N, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -111,6 +402,60 @@ mod tests {
 
     test_indicator!(Maximum);
 
+    mod constant {
+        use super::*;
+
+        type Maximum3 = MaximumConst<3>;
+
+        test_indicator!(Maximum3);
+
+        #[test]
+        fn test_const() {
+            assert!(MaximumConst::<0>::new().is_err());
+
+            let mut heap = Maximum::new(3).unwrap();
+            let mut stack = Maximum3::default();
+            for &x in &[4.0, 1.2, 5.0, 3.0, 4.0, 0.0, -1.0, -2.0, -1.5, 6.0] {
+                assert_eq!(stack.calc(x), heap.calc(x));
+            }
+            assert!(stack.is_ready());
+            assert_eq!(
+                stack.next(&Bar::new().high(9.0)),
+                heap.next(&Bar::new().high(9.0))
+            );
+
+            stack.reset();
+            assert!(!stack.is_ready());
+            assert_eq!(stack.calc(2.0), 2.0);
+            assert_eq!(format!("{}", stack), "MAX(3)");
+        }
+
+        #[cfg(feature = "serde")]
+        #[test]
+        fn test_const_serde() {
+            let mut max = Maximum3::default();
+            for &x in &[1.2, 4.0] {
+                max.calc(x);
+            }
+
+            // values not written yet aren't stored
+            let json = serde_json::to_string(&max).unwrap();
+            let mut restored: Maximum3 = serde_json::from_str(&json).unwrap();
+            assert_eq!(restored.calc(3.0), max.calc(3.0));
+            assert_eq!(restored.calc(0.5), max.calc(0.5));
+
+            let window = r#"{"values":{"capacity":3,"head":0,"values":[1.2,4.0,0.5]},"sum":null}"#;
+            let json = |n: usize, index: usize| {
+                format!(r#"{{"n":{},"window":{},"max_index":{}}}"#, n, window, index)
+            };
+            assert!(serde_json::from_str::<Maximum3>(&json(3, 1)).is_ok());
+            assert!(serde_json::from_str::<Maximum3>(&json(3, 0)).is_err());
+            assert!(serde_json::from_str::<Maximum3>(&json(3, 3)).is_err());
+            assert!(serde_json::from_str::<Maximum3>(&json(4, 1)).is_err());
+            assert!(serde_json::from_str::<MaximumConst<4>>(&json(3, 1)).is_err());
+        }
+    }
+
     #[test]
     fn test_new() {
         assert!(Maximum::new(0).is_err());
@@ -146,6 +491,63 @@ mod tests {
         assert_eq!(max.next(&bar(2.0)), 3.5);
     }
 
+    #[test]
+    fn test_update_last() {
+        let mut max = Maximum::new(2).unwrap();
+        assert_eq!(max.calc(4.0), 4.0);
+        assert_eq!(max.calc(6.0), 6.0);
+        assert_eq!(max.update_last(3.0), 4.0);
+        assert_eq!(max.replace(&Bar::new().high(5.0)), 5.0);
+        assert_eq!(max.calc(1.0), 5.0);
+        assert_eq!(max.calc(2.0), 2.0);
+
+        let mut max = MaximumConst::<2>::new().unwrap();
+        assert_eq!(max.update_last(4.0), 4.0);
+        assert_eq!(max.calc(6.0), 6.0);
+        assert_eq!(max.update_last(3.0), 4.0);
+    }
+
+    #[test]
+    fn test_peek() {
+        let mut indicator = Maximum::new(3).unwrap();
+        for &x in &[5.0, 7.0, 6.0, 3.0, 2.0, 8.0] {
+            let peeked = indicator.peek(x);
+            assert_eq!(
+                indicator.peek_next(&Bar::new().high(x)),
+                indicator.clone().next(&Bar::new().high(x))
+            );
+            assert_eq!(indicator.calc(x), peeked);
+        }
+
+        let mut indicator = MaximumConst::<3>::new().unwrap();
+        for &x in &[5.0, 7.0, 6.0, 3.0, 2.0, 8.0] {
+            let peeked = indicator.peek(x);
+            assert_eq!(indicator.calc(x), peeked);
+        }
+    }
+
+    #[test]
+    fn test_set_length() {
+        let mut max = Maximum::new(3).unwrap();
+        for &value in &[5.0, 7.0, 6.0, 4.0] {
+            max.calc(value);
+        }
+        assert!(max.set_length(0).is_err());
+
+        max.set_length(2).unwrap();
+        assert_eq!(max.length(), 2);
+        assert!(max.is_ready());
+        assert_eq!(max.calc(3.0), 4.0);
+
+        let mut max = max.with_length(4).unwrap();
+        assert!(!max.is_ready());
+        assert_eq!(max.calc(2.0), 4.0);
+        assert_eq!(max.calc(1.0), 4.0);
+        assert!(max.is_ready());
+        assert_eq!(max.calc(0.0), 3.0);
+        assert_eq!(max.to_string(), "MAX(4)");
+    }
+
     #[test]
     fn test_reset() {
         let mut max = Maximum::new(100).unwrap();
@@ -0,0 +1,96 @@
+use core::fmt;
+use core::marker::PhantomData;
+
+use crate::{Float, Next, Quote, Reset, Warmup};
+
+/// Micro price.
+///
+/// Mid price weighted by the sizes on the opposite sides of the book. When the bid is
+/// much larger than the ask, the next trade is more likely to happen at the ask, and the
+/// micro price moves towards it. Falls back to the mid price if both sizes are 0.
+///
+/// # Formula
+///
+/// MICRO = (bid * ask_size + ask * bid_size) / (bid_size + ask_size)
+///
+/// # Links
+///
+/// * [Micro-price, Stoikov](https://ssrn.com/abstract=2970694)
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MicroPrice<F = f64> {
+    float: PhantomData<F>,
+}
+
+impl MicroPrice {
+    pub fn new() -> Self {
+        Self::with_type()
+    }
+}
+
+impl<F: Float> MicroPrice<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type() -> Self {
+        Self { float: PhantomData }
+    }
+}
+
+impl<F: Float, T: Quote<F>> Next<T> for MicroPrice<F> {
+    type Output = F;
+
+    fn next(&mut self, input: &T) -> F {
+        let total_size = input.bid_size() + input.ask_size();
+        if total_size == F::zero() {
+            (input.bid() + input.ask()) / F::cast(2.0)
+        } else {
+            (input.bid() * input.ask_size() + input.ask() * input.bid_size()) / total_size
+        }
+    }
+}
+
+impl<F: Float> Reset for MicroPrice<F> {
+    fn reset(&mut self) {}
+}
+
+impl<F: Float> Warmup for MicroPrice<F> {
+    fn warmup_period(&self) -> u32 {
+        0
+    }
+
+    fn is_ready(&self) -> bool {
+        true
+    }
+}
+
+impl Default for MicroPrice {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: Float> fmt::Display for MicroPrice<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MICRO()")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_next() {
+        let mut micro = MicroPrice::new();
+        assert_eq!(micro.next(&quote(10.0, 11.0, 1.0, 1.0)), 10.5);
+        assert_eq!(micro.next(&quote(10.0, 11.0, 3.0, 1.0)), 10.75);
+        assert_eq!(micro.next(&quote(10.0, 11.0, 0.0, 4.0)), 10.0);
+        assert_eq!(micro.next(&quote(10.0, 11.0, 0.0, 0.0)), 10.5);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", MicroPrice::default()), "MICRO()");
+    }
+}
@@ -0,0 +1,81 @@
+use core::fmt;
+use core::marker::PhantomData;
+
+use crate::{Float, Next, Quote, Reset, Warmup};
+
+/// Mid price.
+///
+/// Average of the best bid and the best ask.
+///
+/// # Formula
+///
+/// MID = (bid + ask) / 2
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MidPrice<F = f64> {
+    float: PhantomData<F>,
+}
+
+impl MidPrice {
+    pub fn new() -> Self {
+        Self::with_type()
+    }
+}
+
+impl<F: Float> MidPrice<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type() -> Self {
+        Self { float: PhantomData }
+    }
+}
+
+impl<F: Float, T: Quote<F>> Next<T> for MidPrice<F> {
+    type Output = F;
+
+    fn next(&mut self, input: &T) -> F {
+        (input.bid() + input.ask()) / F::cast(2.0)
+    }
+}
+
+impl<F: Float> Reset for MidPrice<F> {
+    fn reset(&mut self) {}
+}
+
+impl<F: Float> Warmup for MidPrice<F> {
+    fn warmup_period(&self) -> u32 {
+        0
+    }
+
+    fn is_ready(&self) -> bool {
+        true
+    }
+}
+
+impl Default for MidPrice {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: Float> fmt::Display for MidPrice<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MID()")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_next() {
+        let mut mid = MidPrice::new();
+        assert_eq!(mid.next(&quote(10.0, 10.5, 1.0, 3.0)), 10.25);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", MidPrice::default()), "MID()");
+    }
+}
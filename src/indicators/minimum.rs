@@ -1,8 +1,12 @@
-use std::f64::INFINITY;
-use std::fmt;
+use core::fmt;
 
 use crate::errors::*;
-use crate::{Calculate, Low, Next, Reset};
+use crate::helpers::ConstLength;
+use crate::{
+    Calculate, Float, Low, Next, Peek, PeekNext, Replace, Reset, Storage, UpdateLast, Warmup,
+    Window,
+};
+use alloc::vec::Vec;
 
 /// Returns the lowest value in a given time frame.
 ///
@@ -23,72 +27,233 @@ use crate::{Calculate, Low, Next, Reset};
 /// assert_eq!(min.calc(13.0), 11.0);
 /// ```
 #[derive(Debug, Clone)]
-pub struct Minimum {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        try_from = "MinimumFields<F, S>",
+        bound(
+            serialize = "F: serde::Serialize, S: Storage<F>",
+            deserialize = "F: Float + serde::Deserialize<'de>, S: Storage<F>"
+        )
+    )
+)]
+pub struct Minimum<F = f64, S = Vec<F>> {
     n: usize,
-    vec: Vec<f64>,
+    window: Window<F, S>,
+    // position of the minimum in the window, counted from the oldest value
     min_index: usize,
-    cur_index: usize,
+}
+
+// Fields of a deserialized minimum, whose index has to point at the minimum of the window.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(deserialize = "F: Float + serde::Deserialize<'de>, S: Storage<F>"))
+)]
+struct MinimumFields<F, S> {
+    n: usize,
+    window: Window<F, S>,
+    min_index: usize,
+}
+
+#[cfg(feature = "serde")]
+impl<F: Float, S: Storage<F>> core::convert::TryFrom<MinimumFields<F, S>> for Minimum<F, S> {
+    type Error = Error;
+
+    fn try_from(fields: MinimumFields<F, S>) -> Result<Self> {
+        let MinimumFields {
+            n,
+            window,
+            min_index,
+        } = fields;
+        let valid = n == window.capacity()
+            && match window.get(min_index) {
+                Some(&min) => !window.iter().any(|&value| value < min),
+                None => window.is_empty() && min_index == 0,
+            };
+        if !valid {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        Ok(Self {
+            n,
+            window,
+            min_index,
+        })
+    }
 }
 
 impl Minimum {
     pub fn new(n: u32) -> Result<Self> {
+        Self::with_type(n)
+    }
+}
+
+impl<F: Float> Minimum<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(n: u32) -> Result<Self> {
         let n = n as usize;
 
-        if n <= 0 {
+        if n == 0 {
             return Err(Error::from_kind(ErrorKind::InvalidParameter));
         }
 
         let indicator = Self {
-            n: n,
-            vec: vec![INFINITY; n],
+            n,
+            window: Window::new(n),
             min_index: 0,
-            cur_index: 0,
         };
 
         Ok(indicator)
     }
 
+    /// Changes the size of the time frame, keeping the latest values which fit into the new
+    /// one. Fails with `InvalidParameter` if `n` is 0.
+    pub fn set_length(&mut self, n: u32) -> Result<()> {
+        let n = n as usize;
+        if n == 0 {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+
+        self.n = n;
+        self.window.resize(n);
+        self.min_index = self.find_min_index();
+        Ok(())
+    }
+
+    /// Like [set_length](#method.set_length), consuming the indicator.
+    pub fn with_length(mut self, n: u32) -> Result<Self> {
+        self.set_length(n)?;
+        Ok(self)
+    }
+}
+
+impl<F: Float, S: Storage<F>> Minimum<F, S> {
+    pub fn length(&self) -> u32 {
+        self.n as u32
+    }
+
     fn find_min_index(&self) -> usize {
-        let mut min = ::std::f64::INFINITY;
+        let mut min = F::infinity();
         let mut index: usize = 0;
 
-        for (i, &val) in self.vec.iter().enumerate() {
+        // the two slices are scanned separately, which is faster than a chained iterator
+        let (older, newer) = self.window.as_slices();
+        for (i, &val) in older.iter().enumerate() {
             if val < min {
                 min = val;
                 index = i;
             }
         }
+        for (i, &val) in newer.iter().enumerate() {
+            if val < min {
+                min = val;
+                index = older.len() + i;
+            }
+        }
 
         index
     }
 }
 
-impl Calculate for Minimum {
-    fn calc(&mut self, input: f64) -> f64 {
-        self.cur_index = (self.cur_index + 1) % (self.n as usize);
-        self.vec[self.cur_index] = input;
+impl<F: Float, S: Storage<F>> Calculate<F> for Minimum<F, S> {
+    type Output = F;
+
+    fn calc(&mut self, input: F) -> F {
+        if self.window.push(input).is_some() {
+            if self.min_index == 0 {
+                // the minimum left the window
+                self.min_index = self.find_min_index();
+                return self.window[self.min_index];
+            }
+            self.min_index -= 1;
+        }
+
+        let min = self.window[self.min_index];
+        if input < min {
+            self.min_index = self.window.len() - 1;
+            input
+        } else {
+            min
+        }
+    }
+}
+
+impl<F: Float, S: Storage<F>> UpdateLast<F> for Minimum<F, S> {
+    fn update_last(&mut self, input: F) -> F {
+        if self.window.replace_newest(input).is_none() {
+            return self.calc(input);
+        }
 
-        if input < self.vec[self.min_index] {
-            self.min_index = self.cur_index;
-        } else if self.min_index == self.cur_index {
+        let last = self.window.len() - 1;
+        if input < self.window[self.min_index] {
+            self.min_index = last;
+        } else if self.min_index == last {
             self.min_index = self.find_min_index();
         }
 
-        self.vec[self.min_index]
+        self.window[self.min_index]
     }
 }
 
-impl<T: Low> Next<T> for Minimum {
-    fn next(&mut self, input: &T) -> f64 {
+impl<F: Float, S: Storage<F>> Peek<F> for Minimum<F, S> {
+    fn peek(&self, input: F) -> F {
+        let min = if self.window.is_full() && self.min_index == 0 {
+            // the current minimum would leave the window
+            self.window
+                .iter()
+                .skip(1)
+                .fold(F::infinity(), |min, &val| if val < min { val } else { min })
+        } else {
+            self.window
+                .get(self.min_index)
+                .copied()
+                .unwrap_or_else(F::infinity)
+        };
+
+        if input < min {
+            input
+        } else {
+            min
+        }
+    }
+}
+
+impl<F: Float, S: Storage<F>, T: Low<F>> Next<T> for Minimum<F, S> {
+    type Output = F;
+
+    fn next(&mut self, input: &T) -> F {
         self.calc(input.low())
     }
 }
 
-impl Reset for Minimum {
+impl<F: Float, S: Storage<F>, T: Low<F>> Replace<T> for Minimum<F, S> {
+    fn replace(&mut self, input: &T) -> F {
+        self.update_last(input.low())
+    }
+}
+
+impl<F: Float, S: Storage<F>, T: Low<F>> PeekNext<T> for Minimum<F, S> {
+    fn peek_next(&self, input: &T) -> F {
+        self.peek(input.low())
+    }
+}
+
+impl<F: Float, S: Storage<F>> Reset for Minimum<F, S> {
     fn reset(&mut self) {
-        for i in 0..self.n {
-            self.vec[i] = INFINITY;
-        }
+        self.window.clear();
+        self.min_index = 0;
+    }
+}
+
+impl<F: Float, S: Storage<F>> Warmup for Minimum<F, S> {
+    fn warmup_period(&self) -> u32 {
+        self.n as u32
+    }
+
+    fn is_ready(&self) -> bool {
+        self.window.is_full()
     }
 }
 
@@ -98,12 +263,137 @@ impl Default for Minimum {
     }
 }
 
-impl fmt::Display for Minimum {
+impl<F: Float, S: Storage<F>> fmt::Display for Minimum<F, S> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "MIN({})", self.n)
     }
 }
 
+/// [Minimum](struct.Minimum.html) over a window of _N_ values fixed at compile time.
+///
+/// The window is stored inline in an array instead of a heap allocated vector, for
+/// latency-critical paths and targets without an allocator. Outputs are the same as of
+/// `Minimum::new(N)`.
+///
+/// # Parameters
+///
+/// * _N_ - size of the time frame (greater than 0, `default()` fails to compile otherwise).
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::MinimumConst;
+/// use ta::Calculate;
+///
+/// let mut min = MinimumConst::<3>::new().unwrap();
+/// assert_eq!(min.calc(7.0), 7.0);
+/// assert_eq!(min.calc(5.0), 5.0);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        transparent,
+        bound(
+            serialize = "F: Float + serde::Serialize",
+            deserialize = "F: Float + serde::Deserialize<'de>"
+        )
+    )
+)]
+pub struct MinimumConst<const N: usize, F = f64> {
+    inner: Minimum<F, [F; N]>,
+}
+
+impl<const N: usize, F: Float> MinimumConst<N, F> {
+    pub fn new() -> Result<Self> {
+        if N == 0 {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+
+        Ok(Self {
+            inner: Minimum {
+                n: N,
+                window: Window::inline(),
+                min_index: 0,
+            },
+        })
+    }
+
+    pub fn length(&self) -> u32 {
+        N as u32
+    }
+}
+
+impl<const N: usize, F: Float> Calculate<F> for MinimumConst<N, F> {
+    type Output = F;
+
+    fn calc(&mut self, input: F) -> F {
+        self.inner.calc(input)
+    }
+}
+
+impl<const N: usize, F: Float> UpdateLast<F> for MinimumConst<N, F> {
+    fn update_last(&mut self, input: F) -> F {
+        self.inner.update_last(input)
+    }
+}
+
+impl<const N: usize, F: Float> Peek<F> for MinimumConst<N, F> {
+    fn peek(&self, input: F) -> F {
+        self.inner.peek(input)
+    }
+}
+
+impl<const N: usize, F: Float, T: Low<F>> Next<T> for MinimumConst<N, F> {
+    type Output = F;
+
+    fn next(&mut self, input: &T) -> F {
+        self.inner.next(input)
+    }
+}
+
+impl<const N: usize, F: Float, T: Low<F>> Replace<T> for MinimumConst<N, F> {
+    fn replace(&mut self, input: &T) -> F {
+        self.inner.replace(input)
+    }
+}
+
+impl<const N: usize, F: Float, T: Low<F>> PeekNext<T> for MinimumConst<N, F> {
+    fn peek_next(&self, input: &T) -> F {
+        self.inner.peek_next(input)
+    }
+}
+
+impl<const N: usize, F: Float> Reset for MinimumConst<N, F> {
+    fn reset(&mut self) {
+        self.inner.reset();
+    }
+}
+
+impl<const N: usize, F: Float> Warmup for MinimumConst<N, F> {
+    fn warmup_period(&self) -> u32 {
+        self.inner.warmup_period()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.inner.is_ready()
+    }
+}
+
+impl<const N: usize, F: Float> Default for MinimumConst<N, F> {
+    fn default() -> Self {
+        let () = ConstLength::<N>::NONZERO;
+        Self::new().unwrap()
+    }
+}
+
+impl<const N: usize, F: Float> fmt::Display for MinimumConst<N, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -111,6 +401,60 @@ mod tests {
 
     test_indicator!(Minimum);
 
+    mod constant {
+        use super::*;
+
+        type Minimum3 = MinimumConst<3>;
+
+        test_indicator!(Minimum3);
+
+        #[test]
+        fn test_const() {
+            assert!(MinimumConst::<0>::new().is_err());
+
+            let mut heap = Minimum::new(3).unwrap();
+            let mut stack = Minimum3::default();
+            for &x in &[4.0, 1.2, 5.0, 3.0, 4.0, 0.0, -1.0, -2.0, -1.5, 6.0] {
+                assert_eq!(stack.calc(x), heap.calc(x));
+            }
+            assert!(stack.is_ready());
+            assert_eq!(
+                stack.next(&Bar::new().low(9.0)),
+                heap.next(&Bar::new().low(9.0))
+            );
+
+            stack.reset();
+            assert!(!stack.is_ready());
+            assert_eq!(stack.calc(2.0), 2.0);
+            assert_eq!(format!("{}", stack), "MIN(3)");
+        }
+
+        #[cfg(feature = "serde")]
+        #[test]
+        fn test_const_serde() {
+            let mut min = Minimum3::default();
+            for &x in &[4.0, 1.2] {
+                min.calc(x);
+            }
+
+            // values not written yet aren't stored
+            let json = serde_json::to_string(&min).unwrap();
+            let mut restored: Minimum3 = serde_json::from_str(&json).unwrap();
+            assert_eq!(restored.calc(3.0), min.calc(3.0));
+            assert_eq!(restored.calc(0.5), min.calc(0.5));
+
+            let window = r#"{"values":{"capacity":3,"head":0,"values":[4.0,1.2,5.0]},"sum":null}"#;
+            let json = |n: usize, index: usize| {
+                format!(r#"{{"n":{},"window":{},"min_index":{}}}"#, n, window, index)
+            };
+            assert!(serde_json::from_str::<Minimum3>(&json(3, 1)).is_ok());
+            assert!(serde_json::from_str::<Minimum3>(&json(3, 0)).is_err());
+            assert!(serde_json::from_str::<Minimum3>(&json(3, 3)).is_err());
+            assert!(serde_json::from_str::<Minimum3>(&json(4, 1)).is_err());
+            assert!(serde_json::from_str::<MinimumConst<4>>(&json(3, 1)).is_err());
+        }
+    }
+
     #[test]
     fn test_new() {
         assert!(Minimum::new(0).is_err());
@@ -147,6 +491,96 @@ mod tests {
         assert_eq!(min.next(&bar(5.0)), 1.2);
     }
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let mut min = Minimum::new(3).unwrap();
+        for &low in &[4.0, 1.2, 5.0, 3.0] {
+            min.calc(low);
+        }
+
+        let json = serde_json::to_string(&min).unwrap();
+        let mut restored: Minimum = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.min_index, min.min_index);
+        assert_eq!(restored.window.newest(), min.window.newest());
+        assert_eq!(restored.calc(4.0), 3.0);
+        assert_eq!(restored.calc(6.0), 3.0);
+        assert_eq!(restored.calc(7.0), 4.0);
+
+        let window = r#"{"values":{"capacity":3,"head":1,"values":[3.0,1.2,5.0]},"sum":null}"#;
+        for (n, index) in &[(3, 5), (3, 2), (4, 1)] {
+            let json = format!(r#"{{"n":{},"window":{},"min_index":{}}}"#, n, window, index);
+            assert!(serde_json::from_str::<Minimum>(&json).is_err());
+        }
+        let json = format!(r#"{{"n":3,"window":{},"min_index":0}}"#, window);
+        assert!(serde_json::from_str::<Minimum>(&json).is_ok());
+    }
+
+    #[test]
+    fn test_update_last() {
+        let mut min = Minimum::new(3).unwrap();
+        assert_eq!(min.update_last(5.0), 5.0);
+        assert_eq!(min.calc(3.0), 3.0);
+        assert_eq!(min.update_last(6.0), 5.0);
+        assert_eq!(min.update_last(2.0), 2.0);
+        assert_eq!(min.calc(4.0), 2.0);
+        assert_eq!(min.calc(7.0), 2.0);
+        assert_eq!(min.update_last(1.0), 1.0);
+        assert_eq!(min.replace(&Bar::new().low(8.0)), 2.0);
+
+        let mut fresh = Minimum::new(3).unwrap();
+        for &low in &[5.0, 2.0, 4.0, 8.0] {
+            fresh.calc(low);
+        }
+        assert_eq!(min.calc(9.0), fresh.calc(9.0));
+    }
+
+    #[test]
+    fn test_peek() {
+        let mut indicator = Minimum::new(3).unwrap();
+        for &x in &[5.0, 3.0, 6.0, 7.0, 8.0, 2.0] {
+            let peeked = indicator.peek(x);
+            assert_eq!(
+                indicator.peek_next(&Bar::new().low(x)),
+                indicator.clone().next(&Bar::new().low(x))
+            );
+            assert_eq!(indicator.calc(x), peeked);
+        }
+
+        let mut indicator = MinimumConst::<3>::new().unwrap();
+        for &x in &[5.0, 3.0, 6.0, 7.0, 8.0, 2.0] {
+            let peeked = indicator.peek(x);
+            assert_eq!(indicator.calc(x), peeked);
+        }
+    }
+
+    #[test]
+    fn test_set_length() {
+        let mut min = Minimum::new(3).unwrap();
+        for &value in &[5.0, 3.0, 4.0, 6.0] {
+            min.calc(value);
+        }
+        assert!(min.set_length(0).is_err());
+
+        min.set_length(2).unwrap();
+        assert_eq!(min.length(), 2);
+        assert!(min.is_ready());
+        assert_eq!(min.peek(7.0), 6.0);
+        assert_eq!(min.calc(7.0), 6.0);
+
+        let mut min = min.with_length(4).unwrap();
+        assert!(!min.is_ready());
+        assert_eq!(min.calc(8.0), 6.0);
+        assert_eq!(min.calc(9.0), 6.0);
+        assert!(min.is_ready());
+        assert_eq!(min.calc(10.0), 7.0);
+        assert_eq!(min.to_string(), "MIN(4)");
+
+        let mut empty = Minimum::new(3).unwrap().with_length(1).unwrap();
+        assert_eq!(empty.calc(2.0), 2.0);
+        assert_eq!(empty.calc(3.0), 3.0);
+    }
+
     #[test]
     fn test_reset() {
         let mut min = Minimum::new(10).unwrap();
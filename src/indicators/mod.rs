@@ -2,23 +2,59 @@ mod exponential_moving_average;
 pub use self::exponential_moving_average::ExponentialMovingAverage;
 
 mod simple_moving_average;
-pub use self::simple_moving_average::SimpleMovingAverage;
+pub use self::simple_moving_average::{SimpleMovingAverage, SimpleMovingAverageConst};
 
 mod standard_deviation;
 pub use self::standard_deviation::StandardDeviation;
 
+mod variance;
+pub use self::variance::Variance;
+
 mod relative_strength_index;
 pub use self::relative_strength_index::RelativeStrengthIndex;
 
 mod minimum;
-pub use self::minimum::Minimum;
+pub use self::minimum::{Minimum, MinimumConst};
 
 mod maximum;
-pub use self::maximum::Maximum;
+pub use self::maximum::{Maximum, MaximumConst};
+
+mod run_length;
+pub use self::run_length::{RunLength, RunLengthOutput};
+
+mod divergence;
+pub use self::divergence::{Divergence, DivergenceEvent, DivergenceKind};
+
+mod swing_point;
+pub use self::swing_point::{SwingKind, SwingPoint};
+
+mod pivot_high_low;
+pub use self::pivot_high_low::{PivotHigh, PivotLow};
+
+mod fractals;
+pub use self::fractals::{Fractals, FractalsOutput};
+
+mod zig_zag;
+pub use self::zig_zag::{ZigZag, ZigZagLeg, ZigZagThreshold};
+
+mod pivot_points;
+pub use self::pivot_points::{PivotLevels, PivotMethod, PivotPoints};
+
+mod support_resistance;
+pub use self::support_resistance::{PriceLevel, SupportResistance, SupportResistanceOutput};
+
+mod quantile_channel;
+pub use self::quantile_channel::{QuantileChannel, QuantileChannelOutput};
+
+mod range_mode;
+pub use self::range_mode::RangeMode;
 
 mod fast_stochastic;
 pub use self::fast_stochastic::FastStochastic;
 
+mod williams_r;
+pub use self::williams_r::WilliamsR;
+
 mod slow_stochastic;
 pub use self::slow_stochastic::SlowStochastic;
 
@@ -32,10 +68,127 @@ mod efficiency_ratio;
 pub use self::efficiency_ratio::EfficiencyRatio;
 
 mod rate_of_change;
-pub use self::rate_of_change::RateOfChange;
+pub use self::rate_of_change::{RateOfChange, RateOfChangeKind};
 
 mod money_flow_index;
 pub use self::money_flow_index::MoneyFlowIndex;
 
 mod on_balance_volume;
 pub use self::on_balance_volume::OnBalanceVolume;
+
+mod exponential_moving_variance;
+pub use self::exponential_moving_variance::ExponentialMovingVariance;
+
+mod exponential_bollinger;
+pub use self::exponential_bollinger::{ExponentialBollinger, ExponentialBollingerOutput};
+
+mod correlation;
+pub use self::correlation::Correlation;
+
+mod exponential_covariance;
+pub use self::exponential_covariance::{ExponentialCorrelation, ExponentialCovariance};
+
+mod garch;
+pub use self::garch::Garch11;
+
+mod realized_volatility;
+pub use self::realized_volatility::{RealizedVolatility, RealizedVolatilityOutput};
+
+mod spread;
+pub use self::spread::Spread;
+
+mod mid_price;
+pub use self::mid_price::MidPrice;
+
+mod micro_price;
+pub use self::micro_price::MicroPrice;
+
+mod average_spread;
+pub use self::average_spread::AverageSpread;
+
+mod time_weighted_average_price;
+pub use self::time_weighted_average_price::TimeWeightedAveragePrice;
+
+mod volume_weighted_average_price;
+pub use self::volume_weighted_average_price::{
+    VolumeWeightedAveragePrice, VolumeWeightedAveragePriceOutput, VwapAnchor, VwapBands,
+};
+
+mod volume_delta;
+pub use self::volume_delta::{CumulativeVolumeDelta, DeltaDivergence, VolumeDelta};
+
+mod volume_profile;
+pub use self::volume_profile::{ProfileBins, VolumeBin, VolumeProfile, VolumeProfileOutput};
+
+mod ribbon;
+pub use self::ribbon::{Ribbon, RibbonAverage, RibbonOutput};
+
+mod guppy;
+pub use self::guppy::{Guppy, GuppyOutput};
+
+mod smoothed_moving_average;
+pub use self::smoothed_moving_average::SmoothedMovingAverage;
+
+mod alligator;
+pub use self::alligator::{Alligator, AlligatorOutput, AlligatorState};
+
+mod bollinger_bands;
+pub use self::bollinger_bands::{BollingerBands, BollingerBandsOutput};
+
+mod stochastic_momentum_index;
+pub use self::stochastic_momentum_index::{StochasticMomentumIndex, StochasticMomentumIndexOutput};
+
+mod pretty_good_oscillator;
+pub use self::pretty_good_oscillator::PrettyGoodOscillator;
+
+mod special_k;
+pub use self::special_k::SpecialK;
+
+mod moving_average_convergence_divergence;
+pub use self::moving_average_convergence_divergence::{
+    MovingAverageConvergenceDivergence, MovingAverageConvergenceDivergenceOutput,
+};
+
+mod elder_impulse;
+pub use self::elder_impulse::{ElderImpulse, ElderImpulseColor};
+
+mod traders_dynamic_index;
+pub use self::traders_dynamic_index::{TradersDynamicIndex, TradersDynamicIndexOutput};
+
+mod vortex;
+pub use self::vortex::{VortexIndicator, VortexOutput};
+
+mod directional_movement_index;
+pub use self::directional_movement_index::{
+    DirectionalMovementIndex, DirectionalMovementIndexOutput,
+};
+
+mod momentum;
+pub use self::momentum::Momentum;
+
+mod geometric_mean;
+pub use self::geometric_mean::GeometricMean;
+
+mod harmonic_mean;
+pub use self::harmonic_mean::HarmonicMean;
+
+mod cumulative_stats;
+pub use self::cumulative_stats::{CumulativeStats, CumulativeStatsOutput};
+
+mod mode;
+pub use self::mode::Mode;
+
+mod round_number_proximity;
+pub use self::round_number_proximity::{RoundNumberProximity, RoundNumberProximityOutput};
+
+mod relative_rotation;
+pub use self::relative_rotation::{RelativeRotation, RelativeRotationOutput, RotationQuadrant};
+
+mod heikin_ashi;
+pub use self::heikin_ashi::{HeikinAshi, HeikinAshiBar};
+
+mod kagi;
+pub use self::kagi::{Kagi, KagiDirection, KagiLine, KagiThickness};
+
+mod point_and_figure;
+pub use self::point_and_figure::{ColumnKind, PointAndFigure, PointAndFigureColumn};
@@ -0,0 +1,212 @@
+use alloc::collections::BTreeMap;
+use core::fmt;
+
+use crate::errors::*;
+use crate::{Calculate, Close, Float, Next, Reset, RingBuffer, Warmup};
+
+/// Rolling mode of binned prices.
+///
+/// Prices of the last _n_ periods are grouped into bins of a fixed width and the indicator
+/// returns the middle of the bin with the most prices in it, i.e. the price level where the
+/// market spent most of the time. It is a lightweight cousin of a volume profile for data
+/// without volume.
+///
+/// When several bins hold the same number of prices, the one which was hit most recently
+/// wins.
+///
+/// # Parameters
+///
+/// * _n_ - number of periods (integer greater than 0). Default is 14.
+/// * _bin_width_ - width of a price bin (greater than 0). Bins start at multiples of the
+///   width. Default is 1.0.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::Mode;
+/// use ta::Calculate;
+///
+/// let mut mode = Mode::new(4, 1.0).unwrap();
+/// assert_eq!(mode.calc(10.2), 10.5);
+/// assert_eq!(mode.calc(12.7), 12.5);
+/// assert_eq!(mode.calc(10.9), 10.5);
+/// assert_eq!(mode.calc(12.1), 12.5);
+/// assert_eq!(mode.calc(12.4), 12.5);
+/// ```
+///
+/// # Links
+///
+/// * [Mode, Wikipedia](https://en.wikipedia.org/wiki/Mode_(statistics))
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Mode<F = f64> {
+    n: u32,
+    bin_width: F,
+    count: u64,
+    bins: RingBuffer<i64>,
+    // number of prices in a bin and when it was hit last
+    histogram: BTreeMap<i64, (u32, u64)>,
+}
+
+impl Mode {
+    pub fn new(n: u32, bin_width: f64) -> Result<Self> {
+        Self::with_type(n, bin_width)
+    }
+}
+
+impl<F: Float> Mode<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(n: u32, bin_width: F) -> Result<Self> {
+        if n == 0 || bin_width <= F::zero() || !bin_width.is_finite() {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        Ok(Self {
+            n,
+            bin_width,
+            count: 0,
+            bins: RingBuffer::new(n as usize),
+            histogram: BTreeMap::new(),
+        })
+    }
+
+    pub fn length(&self) -> u32 {
+        self.n
+    }
+
+    pub fn bin_width(&self) -> F {
+        self.bin_width
+    }
+
+    fn bin(&self, price: F) -> i64 {
+        (price / self.bin_width).floor().to_i64().unwrap_or(0)
+    }
+}
+
+impl<F: Float> Calculate<F> for Mode<F> {
+    type Output = F;
+
+    fn calc(&mut self, input: F) -> F {
+        self.count = self.count.saturating_add(1);
+
+        let bin = self.bin(input);
+        let evicted = self.bins.push(bin);
+        let entry = self.histogram.entry(bin).or_insert((0, 0));
+        entry.0 += 1;
+        entry.1 = self.count;
+
+        if let Some(old) = evicted {
+            let prices = &mut self.histogram.get_mut(&old).unwrap().0;
+            *prices -= 1;
+            if *prices == 0 {
+                self.histogram.remove(&old);
+            }
+        }
+
+        let (&mode, _) = self
+            .histogram
+            .iter()
+            .max_by_key(|(_, &(prices, last))| (prices, last))
+            .unwrap();
+        (F::cast(mode) + F::cast(0.5)) * self.bin_width
+    }
+}
+
+impl<F: Float, T: Close<F>> Next<T> for Mode<F> {
+    type Output = F;
+
+    fn next(&mut self, input: &T) -> F {
+        self.calc(input.close())
+    }
+}
+
+impl<F: Float> Reset for Mode<F> {
+    fn reset(&mut self) {
+        self.count = 0;
+        self.bins.clear();
+        self.histogram.clear();
+    }
+}
+
+impl<F: Float> Warmup for Mode<F> {
+    fn warmup_period(&self) -> u32 {
+        self.n
+    }
+
+    fn is_ready(&self) -> bool {
+        self.count >= u64::from(self.n)
+    }
+}
+
+impl Default for Mode {
+    fn default() -> Self {
+        Self::new(14, 1.0).unwrap()
+    }
+}
+
+impl<F: Float> fmt::Display for Mode<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MODE({}, {})", self.n, self.bin_width)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(Mode);
+
+    #[test]
+    fn test_new() {
+        assert!(Mode::new(0, 1.0).is_err());
+        assert!(Mode::new(5, 0.0).is_err());
+        assert!(Mode::new(5, -1.0).is_err());
+        assert!(Mode::new(5, f64::NAN).is_err());
+        assert!(Mode::new(5, 0.25).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut mode = Mode::new(3, 0.5).unwrap();
+        assert_eq!(mode.calc(1.1), 1.25);
+        assert_eq!(mode.calc(1.3), 1.25);
+        assert_eq!(mode.calc(2.0), 1.25);
+        assert_eq!(mode.calc(2.2), 2.25);
+        assert_eq!(mode.next(&Bar::new().close(-0.2)), 2.25);
+        assert_eq!(mode.calc(-0.4), -0.25);
+    }
+
+    #[test]
+    fn test_ties() {
+        let mut mode = Mode::new(4, 1.0).unwrap();
+        mode.calc(5.0);
+        mode.calc(7.0);
+        assert_eq!(mode.calc(5.5), 5.5);
+        assert_eq!(mode.calc(7.5), 7.5);
+        // the first 5.0 leaves the window
+        assert_eq!(mode.calc(9.0), 7.5);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut mode = Mode::new(3, 1.0).unwrap();
+        mode.calc(1.0);
+        mode.calc(1.0);
+
+        mode.reset();
+        assert_eq!(mode.calc(4.2), 4.5);
+        assert_eq!(mode.calc(8.2), 8.5);
+    }
+
+    #[test]
+    fn test_default() {
+        Mode::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let mode = Mode::new(20, 0.5).unwrap();
+        assert_eq!(format!("{}", mode), "MODE(20, 0.5)");
+    }
+}
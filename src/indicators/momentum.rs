@@ -0,0 +1,214 @@
+use core::fmt;
+
+use crate::errors::*;
+use crate::traits::{Calculate, Close, Next, Number, Reset, Warmup};
+use crate::RingBuffer;
+
+/// Momentum (MOM)
+///
+/// The difference between the current price and the price _n_ periods ago. Unlike the
+/// [Rate of Change](struct.RateOfChange.html) it keeps the magnitude in price units.
+///
+/// A displaced momentum reports the momentum as it was _d_ periods ago, i.e. the momentum
+/// of the series shifted back by _d_ periods.
+///
+/// # Formula
+///
+/// MOM = Price<sub>t-d</sub> - Price<sub>t-d-n</sub>
+///
+/// Where:
+///
+/// * P<sub>t-d</sub> - price _d_ periods ago, the current price if not displaced
+/// * P<sub>t-d-n</sub> - price _n_ periods before that
+///
+/// Until enough prices are available the oldest one is used instead.
+///
+/// # Parameters
+///
+/// * _length_ - number of periods (_n_), integer greater than 0. Default is 10.
+/// * _displacement_ - number of periods to shift by (_d_). Default is 0.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::Momentum;
+/// use ta::Calculate;
+///
+/// let mut mom = Momentum::new(2).unwrap();
+/// assert_eq!(mom.calc(10.0), 0.0);
+/// assert_eq!(mom.calc(9.0), -1.0);
+/// assert_eq!(mom.calc(13.0), 3.0);
+/// assert_eq!(mom.calc(14.0), 5.0);
+///
+/// let mut displaced = Momentum::displaced(2, 1).unwrap();
+/// assert_eq!(displaced.calc(10.0), 0.0);
+/// assert_eq!(displaced.calc(9.0), 0.0);
+/// assert_eq!(displaced.calc(13.0), -1.0);
+/// assert_eq!(displaced.calc(14.0), 3.0);
+/// ```
+///
+/// # Links
+///
+/// * [Momentum, Wikipedia](https://en.wikipedia.org/wiki/Momentum_(technical_analysis))
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Momentum<F = f64> {
+    length: u32,
+    displacement: u32,
+    prices: RingBuffer<F>,
+}
+
+impl Momentum {
+    pub fn new(length: u32) -> Result<Self> {
+        Self::with_type(length)
+    }
+
+    pub fn displaced(length: u32, displacement: u32) -> Result<Self> {
+        Self::create(length, displacement)
+    }
+}
+
+impl<F: Number> Momentum<F> {
+    /// Like [new](#method.new), for values of any number type `F`.
+    pub fn with_type(length: u32) -> Result<Self> {
+        Self::create(length, 0)
+    }
+
+    fn create(length: u32, displacement: u32) -> Result<Self> {
+        if length == 0 {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        Ok(Self {
+            length,
+            displacement,
+            prices: RingBuffer::new((length + displacement) as usize + 1),
+        })
+    }
+
+    pub fn length(&self) -> u32 {
+        self.length
+    }
+
+    pub fn displacement(&self) -> u32 {
+        self.displacement
+    }
+}
+
+impl<F: Number> Calculate<F> for Momentum<F> {
+    type Output = F;
+
+    fn calc(&mut self, input: F) -> F {
+        self.prices.push(input);
+
+        let displacement = self.displacement as usize;
+        if self.prices.len() <= displacement {
+            return F::zero();
+        }
+        let current = self.prices.len() - 1 - displacement;
+        let initial = current.saturating_sub(self.length as usize);
+        self.prices[current] - self.prices[initial]
+    }
+}
+
+impl<F: Number, T: Close<F>> Next<T> for Momentum<F> {
+    type Output = F;
+
+    fn next(&mut self, input: &T) -> F {
+        self.calc(input.close())
+    }
+}
+
+impl Default for Momentum {
+    fn default() -> Self {
+        Self::new(10).unwrap()
+    }
+}
+
+impl<F: Number> fmt::Display for Momentum<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.displacement {
+            0 => write!(f, "MOM({})", self.length),
+            displacement => write!(f, "MOM({}, {})", self.length, displacement),
+        }
+    }
+}
+
+impl<F: Number> Reset for Momentum<F> {
+    fn reset(&mut self) {
+        self.prices.clear();
+    }
+}
+
+impl<F: Number> Warmup for Momentum<F> {
+    fn warmup_period(&self) -> u32 {
+        self.length + self.displacement + 1
+    }
+
+    fn is_ready(&self) -> bool {
+        self.prices.is_full()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(Momentum);
+
+    #[test]
+    fn test_new() {
+        assert!(Momentum::new(0).is_err());
+        assert!(Momentum::displaced(0, 1).is_err());
+        assert!(Momentum::new(1).is_ok());
+        assert!(Momentum::displaced(1, 0).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut mom = Momentum::new(3).unwrap();
+
+        assert_eq!(mom.calc(10.0), 0.0);
+        assert_eq!(round(mom.calc(10.4)), 0.4);
+        assert_eq!(round(mom.calc(10.57)), 0.57);
+        assert_eq!(round(mom.calc(10.8)), 0.8);
+        assert_eq!(round(mom.next(&Bar::new().close(10.9))), 0.5);
+        assert_eq!(round(mom.next(&Bar::new().close(10.0))), -0.57);
+    }
+
+    #[test]
+    fn test_displaced() {
+        let mut mom = Momentum::displaced(3, 2).unwrap();
+        let prices = [10.0, 10.4, 10.57, 10.8, 10.9, 10.0, 9.0];
+        let expected = [0.0, 0.0, 0.0, 0.4, 0.57, 0.8, 0.5];
+        for (&price, &expected) in prices.iter().zip(expected.iter()) {
+            assert_eq!(round(mom.calc(price)), expected);
+        }
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut mom = Momentum::new(3).unwrap();
+        mom.calc(12.3);
+        mom.calc(15.0);
+
+        mom.reset();
+        assert_eq!(mom.calc(10.0), 0.0);
+        assert_eq!(round(mom.calc(10.4)), 0.4);
+    }
+
+    #[test]
+    fn test_default() {
+        Momentum::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", Momentum::new(9).unwrap()), "MOM(9)");
+        assert_eq!(
+            format!("{}", Momentum::displaced(9, 3).unwrap()),
+            "MOM(9, 3)"
+        );
+    }
+}
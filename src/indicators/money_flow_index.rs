@@ -1,8 +1,7 @@
-use std::collections::VecDeque;
-use std::fmt;
+use core::fmt;
 
 use crate::errors::*;
-use crate::{Close, High, Low, Next, Reset, Volume};
+use crate::{Close, Float, High, Low, Next, Reset, RingBuffer, Volume, Warmup};
 
 /// Money Flow Index (MFI).
 ///
@@ -25,6 +24,7 @@ use crate::{Close, High, Low, Next, Reset, Volume};
 ///
 /// Money Flow Index(MFI) = PMF / (PMF + NMF) * 100
 ///
+/// Without any money flow over the period, e.g. on bars without volume, MFI is 50.
 ///
 /// # Parameters
 ///
@@ -52,27 +52,35 @@ use crate::{Close, High, Low, Next, Reset, Volume};
 /// * [Money Flow Index, stockcharts](https://stockcharts.com/school/doku.php?id=chart_school:technical_indicators:money_flow_index_mfi)
 
 #[derive(Debug, Clone)]
-pub struct MoneyFlowIndex {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MoneyFlowIndex<F = f64> {
     n: u32,
-    money_flows: VecDeque<f64>,
-    prev_typical_price: f64,
-    total_positive_money_flow: f64,
-    total_absolute_money_flow: f64,
-    is_new: bool,
+    money_flows: RingBuffer<F>,
+    prev_typical_price: F,
+    total_positive_money_flow: F,
+    total_absolute_money_flow: F,
+    count: u32,
 }
 
 impl MoneyFlowIndex {
     pub fn new(n: u32) -> Result<Self> {
+        Self::with_type(n)
+    }
+}
+
+impl<F: Float> MoneyFlowIndex<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(n: u32) -> Result<Self> {
         match n {
             0 => Err(Error::from_kind(ErrorKind::InvalidParameter)),
             _ => {
                 let indicator = Self {
-                    n: n,
-                    money_flows: VecDeque::with_capacity(n as usize + 1),
-                    prev_typical_price: 0.0,
-                    total_positive_money_flow: 0.0,
-                    total_absolute_money_flow: 0.0,
-                    is_new: true,
+                    n,
+                    money_flows: RingBuffer::new(n as usize),
+                    prev_typical_price: F::zero(),
+                    total_positive_money_flow: F::zero(),
+                    total_absolute_money_flow: F::zero(),
+                    count: 0,
                 };
                 Ok(indicator)
             }
@@ -80,17 +88,19 @@ impl MoneyFlowIndex {
     }
 }
 
-impl<T: High + Low + Close + Volume> Next<T> for MoneyFlowIndex {
-    fn next(&mut self, input: &T) -> f64 {
-        let typical_price = (input.high() + input.low() + input.close()) / 3.0;
+impl<F: Float, T: High<F> + Low<F> + Close<F> + Volume<F>> Next<T> for MoneyFlowIndex<F> {
+    type Output = F;
+
+    fn next(&mut self, input: &T) -> F {
+        let typical_price = (input.high() + input.low() + input.close()) / F::cast(3.0);
 
-        if self.is_new {
+        self.count = self.count.saturating_add(1);
+        if self.count == 1 {
             // money flow is 0, because without having previous typical_price
             // it is not possible to determine is it positive or negative.
-            self.money_flows.push_back(0.0);
+            self.money_flows.push(F::zero());
             self.prev_typical_price = typical_price;
-            self.is_new = false;
-            return 50.0;
+            F::cast(50.0)
         } else {
             let money_flow = typical_price * input.volume();
 
@@ -103,9 +113,8 @@ impl<T: High + Low + Close + Volume> Next<T> for MoneyFlowIndex {
 
             self.total_absolute_money_flow += money_flow;
 
-            if self.money_flows.len() == (self.n as usize) {
-                let old_signed_money_flow = self.money_flows.pop_front().unwrap();
-                if old_signed_money_flow > 0.0 {
+            if let Some(old_signed_money_flow) = self.money_flows.push(signed_money_flow) {
+                if old_signed_money_flow > F::zero() {
                     self.total_positive_money_flow -= old_signed_money_flow;
                     self.total_absolute_money_flow -= old_signed_money_flow;
                 } else {
@@ -114,10 +123,16 @@ impl<T: High + Low + Close + Volume> Next<T> for MoneyFlowIndex {
                 }
             }
 
-            self.money_flows.push_back(signed_money_flow);
             self.prev_typical_price = typical_price;
 
-            (self.total_positive_money_flow / self.total_absolute_money_flow) * 100.0
+            // no money flowed, e.g. without volume
+            if self.total_absolute_money_flow <= F::zero() {
+                return F::cast(50.0);
+            }
+
+            // the running totals may drift out of range by rounding errors
+            let ratio = self.total_positive_money_flow / self.total_absolute_money_flow;
+            ratio.max(F::zero()).min(F::one()) * F::cast(100.0)
         }
     }
 }
@@ -128,19 +143,29 @@ impl Default for MoneyFlowIndex {
     }
 }
 
-impl fmt::Display for MoneyFlowIndex {
+impl<F: Float> fmt::Display for MoneyFlowIndex<F> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "MFI({})", self.n)
     }
 }
 
-impl Reset for MoneyFlowIndex {
+impl<F: Float> Reset for MoneyFlowIndex<F> {
     fn reset(&mut self) {
         self.money_flows.clear();
-        self.prev_typical_price = 0.0;
-        self.total_positive_money_flow = 0.0;
-        self.total_absolute_money_flow = 0.0;
-        self.is_new = true;
+        self.prev_typical_price = F::zero();
+        self.total_positive_money_flow = F::zero();
+        self.total_absolute_money_flow = F::zero();
+        self.count = 0;
+    }
+}
+
+impl<F: Float> Warmup for MoneyFlowIndex<F> {
+    fn warmup_period(&self) -> u32 {
+        self.n + 1
+    }
+
+    fn is_ready(&self) -> bool {
+        self.count > self.n
     }
 }
 
@@ -216,6 +241,14 @@ mod tests {
         assert_eq!(round(mfi.next(&bar6)), 44.444);
     }
 
+    #[test]
+    fn test_without_volume() {
+        let mut mfi = MoneyFlowIndex::new(3).unwrap();
+        assert_eq!(mfi.next(&Bar::new().high(2).low(1).close(1.5)), 50.0);
+        assert_eq!(mfi.next(&Bar::new().high(3).low(2).close(2.5)), 50.0);
+        assert_eq!(mfi.next(&Bar::new().high(3).low(1).close(1)), 50.0);
+    }
+
     #[test]
     fn test_default() {
         MoneyFlowIndex::default();
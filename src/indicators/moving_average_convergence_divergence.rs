@@ -0,0 +1,223 @@
+use core::fmt;
+
+use crate::errors::*;
+use crate::indicators::ExponentialMovingAverage;
+use crate::{Calculate, Close, Finite, Float, Next, Reset, Warmup};
+
+/// Moving average converge divergence (MACD).
+///
+/// The MACD indicator (or "oscillator") is a collection of three time series
+/// calculated from historical price data, most often the closing price.
+/// These three series are: the MACD series proper, the "signal" or "average" series,
+/// and the "divergence" series which is the difference between the two.
+///
+/// # Formula
+///
+/// * _MACD_ = EMA(_fast_length_) - EMA(_slow_length_)
+/// * _signal_ = EMA(_MACD_, _signal_length_)
+/// * _histogram_ = _MACD_ - _signal_
+///
+/// # Parameters
+///
+/// * _fast_length_ - length for the fast EMA. Default is 12.
+/// * _slow_length_ - length for the slow EMA. Default is 26.
+/// * _signal_length_ - length for the signal EMA. Default is 9.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::MovingAverageConvergenceDivergence as Macd;
+/// use ta::Calculate;
+///
+/// let mut macd = Macd::new(3, 6, 4).unwrap();
+///
+/// let out = macd.calc(2.0);
+/// assert_eq!((out.macd, out.signal, out.histogram), (0.0, 0.0, 0.0));
+///
+/// let out = macd.calc(3.0);
+/// assert_eq!((out.macd * 100.0).round(), 21.0);
+/// assert_eq!((out.signal * 100.0).round(), 9.0);
+/// assert_eq!((out.histogram * 100.0).round(), 13.0);
+/// ```
+///
+/// # Links
+///
+/// * [MACD, Wikipedia](https://en.wikipedia.org/wiki/MACD)
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MovingAverageConvergenceDivergence<F = f64> {
+    fast_ema: ExponentialMovingAverage<F>,
+    slow_ema: ExponentialMovingAverage<F>,
+    signal_ema: ExponentialMovingAverage<F>,
+    count: u32,
+}
+
+/// Output of [MovingAverageConvergenceDivergence](struct.MovingAverageConvergenceDivergence.html).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MovingAverageConvergenceDivergenceOutput<F = f64> {
+    pub macd: F,
+    pub signal: F,
+    pub histogram: F,
+}
+
+impl<F: Finite> Finite for MovingAverageConvergenceDivergenceOutput<F> {
+    fn is_finite(&self) -> bool {
+        self.macd.is_finite() && self.signal.is_finite() && self.histogram.is_finite()
+    }
+}
+
+impl MovingAverageConvergenceDivergence {
+    pub fn new(fast_length: u32, slow_length: u32, signal_length: u32) -> Result<Self> {
+        Self::with_type(fast_length, slow_length, signal_length)
+    }
+}
+
+impl<F: Float> MovingAverageConvergenceDivergence<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(fast_length: u32, slow_length: u32, signal_length: u32) -> Result<Self> {
+        Ok(Self {
+            fast_ema: ExponentialMovingAverage::with_type(fast_length)?,
+            slow_ema: ExponentialMovingAverage::with_type(slow_length)?,
+            signal_ema: ExponentialMovingAverage::with_type(signal_length)?,
+            count: 0,
+        })
+    }
+
+    pub fn fast_length(&self) -> u32 {
+        self.fast_ema.length()
+    }
+
+    pub fn slow_length(&self) -> u32 {
+        self.slow_ema.length()
+    }
+
+    pub fn signal_length(&self) -> u32 {
+        self.signal_ema.length()
+    }
+}
+
+impl<F: Float> Calculate<F> for MovingAverageConvergenceDivergence<F> {
+    type Output = MovingAverageConvergenceDivergenceOutput<F>;
+
+    fn calc(&mut self, input: F) -> Self::Output {
+        self.count = self.count.saturating_add(1);
+        let macd = self.fast_ema.calc(input) - self.slow_ema.calc(input);
+        let signal = self.signal_ema.calc(macd);
+        MovingAverageConvergenceDivergenceOutput {
+            macd,
+            signal,
+            histogram: macd - signal,
+        }
+    }
+}
+
+impl<F: Float, T: Close<F>> Next<T> for MovingAverageConvergenceDivergence<F> {
+    type Output = MovingAverageConvergenceDivergenceOutput<F>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.calc(input.close())
+    }
+}
+
+impl<F: Float> Reset for MovingAverageConvergenceDivergence<F> {
+    fn reset(&mut self) {
+        self.fast_ema.reset();
+        self.slow_ema.reset();
+        self.signal_ema.reset();
+        self.count = 0;
+    }
+}
+
+impl<F: Float> Warmup for MovingAverageConvergenceDivergence<F> {
+    fn warmup_period(&self) -> u32 {
+        let lines = self
+            .fast_ema
+            .warmup_period()
+            .max(self.slow_ema.warmup_period());
+        lines + self.signal_ema.warmup_period() - 1
+    }
+
+    fn is_ready(&self) -> bool {
+        self.count >= self.warmup_period()
+    }
+}
+
+impl Default for MovingAverageConvergenceDivergence {
+    fn default() -> Self {
+        Self::new(12, 26, 9).unwrap()
+    }
+}
+
+impl<F: Float> fmt::Display for MovingAverageConvergenceDivergence<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "MACD({}, {}, {})",
+            self.fast_length(),
+            self.slow_length(),
+            self.signal_length()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+    type Macd = MovingAverageConvergenceDivergence;
+
+    test_indicator!(Macd);
+
+    fn round3(out: MovingAverageConvergenceDivergenceOutput) -> (f64, f64, f64) {
+        (round(out.macd), round(out.signal), round(out.histogram))
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(Macd::new(0, 1, 1).is_err());
+        assert!(Macd::new(1, 0, 1).is_err());
+        assert!(Macd::new(1, 1, 0).is_err());
+        assert!(Macd::new(1, 1, 1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut macd = Macd::new(3, 6, 4).unwrap();
+
+        assert_eq!(round3(macd.calc(2.0)), (0.0, 0.0, 0.0));
+        assert_eq!(round3(macd.calc(3.0)), (0.214, 0.086, 0.129));
+        assert_eq!(round3(macd.calc(4.2)), (0.517, 0.258, 0.259));
+        assert_eq!(round3(macd.calc(7.0)), (1.152, 0.616, 0.536));
+        assert_eq!(round3(macd.calc(6.7)), (1.149, 0.829, 0.32));
+        assert_eq!(
+            round3(macd.next(&Bar::new().close(6.5))),
+            (0.942, 0.874, 0.067)
+        );
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut macd = Macd::new(3, 6, 4).unwrap();
+
+        assert_eq!(round3(macd.calc(2.0)), (0.0, 0.0, 0.0));
+        assert_eq!(round3(macd.calc(3.0)), (0.214, 0.086, 0.129));
+
+        macd.reset();
+
+        assert_eq!(round3(macd.calc(2.0)), (0.0, 0.0, 0.0));
+        assert_eq!(round3(macd.calc(3.0)), (0.214, 0.086, 0.129));
+    }
+
+    #[test]
+    fn test_default() {
+        Macd::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let indicator = Macd::new(13, 30, 10).unwrap();
+        assert_eq!(format!("{}", indicator), "MACD(13, 30, 10)");
+    }
+}
@@ -1,6 +1,6 @@
-use std::fmt;
+use core::fmt;
 
-use crate::{Close, Next, Reset, Volume};
+use crate::{Close, Next, Number, Reset, Volume, Warmup};
 
 /// On Balance Volume (OBV).
 ///
@@ -57,28 +57,41 @@ use crate::{Close, Next, Reset, Volume};
 /// * [On Balance Volume, stockcharts](https://stockcharts.com/school/doku.php?id=chart_school:technical_indicators:on_balance_volume_obv)
 
 #[derive(Debug, Clone)]
-pub struct OnBalanceVolume {
-    obv: f64,
-    prev_close: f64,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OnBalanceVolume<F = f64> {
+    obv: F,
+    prev_close: F,
+    count: u32,
 }
 
 impl OnBalanceVolume {
     pub fn new() -> Self {
+        Self::with_type()
+    }
+}
+
+impl<F: Number> OnBalanceVolume<F> {
+    /// Like [new](#method.new), for values of any number type `F`.
+    pub fn with_type() -> Self {
         Self {
-            obv: 0.0,
-            prev_close: 0.0,
+            obv: F::zero(),
+            prev_close: F::zero(),
+            count: 0,
         }
     }
 }
 
-impl<T: Close + Volume> Next<T> for OnBalanceVolume {
-    fn next(&mut self, input: &T) -> f64 {
+impl<F: Number, T: Close<F> + Volume<F>> Next<T> for OnBalanceVolume<F> {
+    type Output = F;
+
+    fn next(&mut self, input: &T) -> F {
         if input.close() > self.prev_close {
-            self.obv = self.obv + input.volume();
+            self.obv += input.volume();
         } else if input.close() < self.prev_close {
-            self.obv = self.obv - input.volume();
+            self.obv -= input.volume();
         }
         self.prev_close = input.close();
+        self.count = self.count.saturating_add(1);
         self.obv
     }
 }
@@ -89,16 +102,27 @@ impl Default for OnBalanceVolume {
     }
 }
 
-impl fmt::Display for OnBalanceVolume {
+impl<F: Number> fmt::Display for OnBalanceVolume<F> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "OBV")
     }
 }
 
-impl Reset for OnBalanceVolume {
+impl<F: Number> Reset for OnBalanceVolume<F> {
     fn reset(&mut self) {
-        self.obv = 0.0;
-        self.prev_close = 0.0;
+        self.obv = F::zero();
+        self.prev_close = F::zero();
+        self.count = 0;
+    }
+}
+
+impl<F: Number> Warmup for OnBalanceVolume<F> {
+    fn warmup_period(&self) -> u32 {
+        1
+    }
+
+    fn is_ready(&self) -> bool {
+        self.count >= 1
     }
 }
 
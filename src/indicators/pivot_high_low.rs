@@ -0,0 +1,319 @@
+use core::fmt;
+
+use crate::errors::*;
+use crate::indicators::{SwingKind, SwingPoint};
+use crate::swings::SwingWindow;
+use crate::{Calculate, Float, High, Low, Next, Reset, Warmup};
+
+/// Pivot high.
+///
+/// Finds swing highs, bars with a high above the highs of the _left_ bars before them and
+/// not below the highs of the _right_ bars after them, so of a flat top only the first bar
+/// is a pivot. A pivot is confirmed, and returned with an `offset` of _right_, once the
+/// _right_ bars after it have closed; confirmed pivots never change. Returns `None` on all
+/// other bars.
+///
+/// See [PivotLow](struct.PivotLow.html) for swing lows.
+///
+/// # Parameters
+///
+/// * _left_ - number of bars before a pivot (integer greater than 0). Default is 5.
+/// * _right_ - number of bars after a pivot (integer greater than 0). Default is 5.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::PivotHigh;
+/// use ta::Calculate;
+///
+/// let mut pivot = PivotHigh::new(2, 1).unwrap();
+/// for &high in &[10.0, 11.0, 13.0] {
+///     assert_eq!(pivot.calc(high), None);
+/// }
+/// let swing = pivot.calc(12.0).unwrap();
+/// assert_eq!((swing.price, swing.offset), (13.0, 1));
+/// ```
+///
+/// # Links
+///
+/// * [Pivot Point Highs and Lows, TradingView](https://www.tradingview.com/pine-script-reference/v5/#fun_ta.pivothigh)
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PivotHigh<F = f64> {
+    pivots: Pivots<F>,
+}
+
+/// Pivot low.
+///
+/// Finds swing lows, the counterpart of [PivotHigh](struct.PivotHigh.html): bars with a low
+/// below the lows of the _left_ bars before them and not above the lows of the _right_ bars
+/// after them, confirmed once the _right_ bars have closed.
+///
+/// # Parameters
+///
+/// * _left_ - number of bars before a pivot (integer greater than 0). Default is 5.
+/// * _right_ - number of bars after a pivot (integer greater than 0). Default is 5.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::PivotLow;
+/// use ta::Next;
+///
+/// let mut pivot = PivotLow::new(1, 1).unwrap();
+/// // open, high, low, close and volume
+/// assert_eq!(pivot.next(&(10.0, 11.0, 9.0, 10.0, 100.0)), None);
+/// assert_eq!(pivot.next(&(10.0, 10.5, 8.0, 9.0, 100.0)), None);
+/// let swing = pivot.next(&(9.0, 10.0, 8.5, 9.5, 100.0)).unwrap();
+/// assert_eq!((swing.price, swing.offset), (8.0, 1));
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PivotLow<F = f64> {
+    pivots: Pivots<F>,
+}
+
+// window shared by PivotHigh and PivotLow
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Pivots<F> {
+    left: u32,
+    right: u32,
+    swings: SwingWindow<F>,
+    count: u64,
+}
+
+impl<F: Float> Pivots<F> {
+    fn new(left: u32, right: u32) -> Result<Self> {
+        if left == 0 || right == 0 {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        Ok(Self {
+            left,
+            right,
+            swings: SwingWindow::new(left, right),
+            count: 0,
+        })
+    }
+
+    fn update(&mut self, price: F, kind: SwingKind) -> Option<SwingPoint<F>> {
+        self.count += 1;
+        let (high, low) = self.swings.push(price, |&price| price, |&price| price);
+        let swing = match kind {
+            SwingKind::High => high,
+            SwingKind::Low => low,
+        };
+        swing.map(|price| SwingPoint {
+            kind,
+            price,
+            offset: self.right,
+        })
+    }
+
+    fn reset(&mut self) {
+        self.swings.clear();
+        self.count = 0;
+    }
+
+    fn warmup_period(&self) -> u32 {
+        self.left + self.right + 1
+    }
+}
+
+impl PivotHigh {
+    pub fn new(left: u32, right: u32) -> Result<Self> {
+        Self::with_type(left, right)
+    }
+}
+
+impl<F: Float> PivotHigh<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(left: u32, right: u32) -> Result<Self> {
+        Ok(Self {
+            pivots: Pivots::new(left, right)?,
+        })
+    }
+
+    pub fn left(&self) -> u32 {
+        self.pivots.left
+    }
+
+    pub fn right(&self) -> u32 {
+        self.pivots.right
+    }
+}
+
+impl<F: Float> Calculate<F> for PivotHigh<F> {
+    type Output = Option<SwingPoint<F>>;
+
+    fn calc(&mut self, input: F) -> Self::Output {
+        self.pivots.update(input, SwingKind::High)
+    }
+}
+
+impl<F: Float, T: High<F>> Next<T> for PivotHigh<F> {
+    type Output = Option<SwingPoint<F>>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.pivots.update(input.high(), SwingKind::High)
+    }
+}
+
+impl<F: Float> Reset for PivotHigh<F> {
+    fn reset(&mut self) {
+        self.pivots.reset();
+    }
+}
+
+impl<F: Float> Warmup for PivotHigh<F> {
+    fn warmup_period(&self) -> u32 {
+        self.pivots.warmup_period()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.pivots.count >= u64::from(self.warmup_period())
+    }
+}
+
+impl Default for PivotHigh {
+    fn default() -> Self {
+        Self::new(5, 5).unwrap()
+    }
+}
+
+impl<F: Float> fmt::Display for PivotHigh<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PIVOTHIGH({}, {})", self.left(), self.right())
+    }
+}
+
+impl PivotLow {
+    pub fn new(left: u32, right: u32) -> Result<Self> {
+        Self::with_type(left, right)
+    }
+}
+
+impl<F: Float> PivotLow<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(left: u32, right: u32) -> Result<Self> {
+        Ok(Self {
+            pivots: Pivots::new(left, right)?,
+        })
+    }
+
+    pub fn left(&self) -> u32 {
+        self.pivots.left
+    }
+
+    pub fn right(&self) -> u32 {
+        self.pivots.right
+    }
+}
+
+impl<F: Float> Calculate<F> for PivotLow<F> {
+    type Output = Option<SwingPoint<F>>;
+
+    fn calc(&mut self, input: F) -> Self::Output {
+        self.pivots.update(input, SwingKind::Low)
+    }
+}
+
+impl<F: Float, T: Low<F>> Next<T> for PivotLow<F> {
+    type Output = Option<SwingPoint<F>>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.pivots.update(input.low(), SwingKind::Low)
+    }
+}
+
+impl<F: Float> Reset for PivotLow<F> {
+    fn reset(&mut self) {
+        self.pivots.reset();
+    }
+}
+
+impl<F: Float> Warmup for PivotLow<F> {
+    fn warmup_period(&self) -> u32 {
+        self.pivots.warmup_period()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.pivots.count >= u64::from(self.warmup_period())
+    }
+}
+
+impl Default for PivotLow {
+    fn default() -> Self {
+        Self::new(5, 5).unwrap()
+    }
+}
+
+impl<F: Float> fmt::Display for PivotLow<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PIVOTLOW({}, {})", self.left(), self.right())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    mod pivot_high {
+        use super::*;
+        test_indicator!(PivotHigh);
+    }
+
+    mod pivot_low {
+        use super::*;
+        test_indicator!(PivotLow);
+    }
+
+    fn pivots<I: Calculate<Output = Option<SwingPoint>>>(
+        indicator: &mut I,
+        prices: &[f64],
+    ) -> Vec<(usize, f64)> {
+        prices
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &price)| indicator.calc(price).map(|swing| (i, swing.price)))
+            .collect()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(PivotHigh::new(0, 2).is_err());
+        assert!(PivotLow::new(2, 0).is_err());
+        assert!(PivotHigh::new(1, 3).is_ok());
+    }
+
+    #[test]
+    fn test_pivot_high() {
+        let mut pivot = PivotHigh::new(2, 2).unwrap();
+        let prices = [1.0, 2.0, 5.0, 3.0, 4.0, 2.0, 1.0, 6.0, 6.0, 3.0, 2.0];
+        // 4.0 is below the 5.0 on its left, of the flat top at 6.0 only the first bar counts
+        assert_eq!(pivots(&mut pivot, &prices), vec![(4, 5.0), (9, 6.0)]);
+    }
+
+    #[test]
+    fn test_pivot_low() {
+        let mut pivot = PivotLow::new(1, 2).unwrap();
+        let prices = [5.0, 3.0, 4.0, 4.5, 2.0, 3.0];
+        assert_eq!(pivots(&mut pivot, &prices), vec![(3, 3.0)]);
+        let swing = pivot.next(&Bar::new().low(2.5)).unwrap();
+        assert_eq!(
+            (swing.kind, swing.price, swing.offset),
+            (SwingKind::Low, 2.0, 2)
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            format!("{}", PivotHigh::new(3, 2).unwrap()),
+            "PIVOTHIGH(3, 2)"
+        );
+        assert_eq!(format!("{}", PivotLow::default()), "PIVOTLOW(5, 5)");
+    }
+}
@@ -0,0 +1,284 @@
+use core::fmt;
+
+use crate::{Calculate, Close, Finite, Float, High, Low, Next, Reset, Warmup};
+
+/// Formulas of [PivotPoints](struct.PivotPoints.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PivotMethod {
+    /// Floor trader pivots, levels at multiples of the range around the pivot.
+    #[default]
+    Classic,
+    /// Levels at the 38.2%, 61.8% and 100% Fibonacci ratios of the range around the pivot.
+    Fibonacci,
+    /// Tight levels at 1.1/12, 1.1/6 and 1.1/4 of the range around the close.
+    Camarilla,
+    /// Classic levels around a pivot weighting the close twice.
+    Woodie,
+}
+
+impl fmt::Display for PivotMethod {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PivotMethod::Classic => write!(f, "CLASSIC"),
+            PivotMethod::Fibonacci => write!(f, "FIBONACCI"),
+            PivotMethod::Camarilla => write!(f, "CAMARILLA"),
+            PivotMethod::Woodie => write!(f, "WOODIE"),
+        }
+    }
+}
+
+/// Output of [PivotPoints](struct.PivotPoints.html).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PivotLevels<F = f64> {
+    pub pp: F,
+    pub r1: F,
+    pub r2: F,
+    pub r3: F,
+    pub s1: F,
+    pub s2: F,
+    pub s3: F,
+}
+
+impl<F: Float> Finite for PivotLevels<F> {
+    fn is_finite(&self) -> bool {
+        [
+            self.pp, self.r1, self.r2, self.r3, self.s1, self.s2, self.s3,
+        ]
+        .iter()
+        .all(|level| level.is_finite())
+    }
+}
+
+/// Pivot points.
+///
+/// Support and resistance levels for a period, e.g. a day or a week, computed from the
+/// high, low and close of the previous period. Every input is a completed period, e.g. a
+/// daily bar of a [Resampler](../preprocess/struct.Resampler.html), and the output holds the
+/// levels of the period after it, available with [levels](#method.levels) until the next
+/// period completes.
+///
+/// # Formula
+///
+/// With R = high - low of the previous period:
+///
+/// * _Classic_ - PP = (high + low + close) / 3, R1 = 2 PP - low, S1 = 2 PP - high,
+///   R2 = PP + R, S2 = PP - R, R3 = high + 2 (PP - low), S3 = low - 2 (high - PP)
+/// * _Fibonacci_ - PP as classic, R1, R2, R3 = PP + 0.382 R, 0.618 R, R and
+///   S1, S2, S3 = PP - 0.382 R, 0.618 R, R
+/// * _Camarilla_ - PP as classic, R1, R2, R3 = close + 1.1 R / 12, 1.1 R / 6, 1.1 R / 4 and
+///   S1, S2, S3 = close - 1.1 R / 12, 1.1 R / 6, 1.1 R / 4
+/// * _Woodie_ - PP = (high + low + 2 close) / 4, other levels as classic
+///
+/// # Parameters
+///
+/// * _method_ - formulas of the levels. Default is `Classic`.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{PivotMethod, PivotPoints};
+/// use ta::Next;
+///
+/// let mut pivots = PivotPoints::new(PivotMethod::Classic);
+/// assert!(pivots.levels().is_none());
+///
+/// // open, high, low, close and volume of the previous day
+/// let levels = pivots.next(&(100.0, 110.0, 95.0, 105.0, 1000.0));
+/// assert_eq!(levels.pp, 103.33333333333333);
+/// assert_eq!(levels.r2, 118.33333333333333);
+/// assert_eq!(pivots.levels(), Some(levels));
+/// ```
+///
+/// # Links
+///
+/// * [Pivot Point, Wikipedia](https://en.wikipedia.org/wiki/Pivot_point_(technical_analysis))
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PivotPoints<F = f64> {
+    method: PivotMethod,
+    levels: Option<PivotLevels<F>>,
+}
+
+impl PivotPoints {
+    pub fn new(method: PivotMethod) -> Self {
+        Self::with_type(method)
+    }
+}
+
+impl<F: Float> PivotPoints<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(method: PivotMethod) -> Self {
+        Self {
+            method,
+            levels: None,
+        }
+    }
+
+    pub fn method(&self) -> PivotMethod {
+        self.method
+    }
+
+    /// Levels of the current period, `None` before the first completed period.
+    pub fn levels(&self) -> Option<PivotLevels<F>> {
+        self.levels
+    }
+
+    fn update(&mut self, high: F, low: F, close: F) -> PivotLevels<F> {
+        let range = high - low;
+        let two = F::cast(2.0);
+        let classic = |pp: F| PivotLevels {
+            pp,
+            r1: two * pp - low,
+            r2: pp + range,
+            r3: high + two * (pp - low),
+            s1: two * pp - high,
+            s2: pp - range,
+            s3: low - two * (high - pp),
+        };
+
+        let pp = (high + low + close) / F::cast(3.0);
+        let levels = match self.method {
+            PivotMethod::Classic => classic(pp),
+            PivotMethod::Fibonacci => {
+                let (a, b) = (F::cast(0.382) * range, F::cast(0.618) * range);
+                PivotLevels {
+                    pp,
+                    r1: pp + a,
+                    r2: pp + b,
+                    r3: pp + range,
+                    s1: pp - a,
+                    s2: pp - b,
+                    s3: pp - range,
+                }
+            }
+            PivotMethod::Camarilla => {
+                let step = F::cast(1.1) * range;
+                let (a, b, c) = (
+                    step / F::cast(12.0),
+                    step / F::cast(6.0),
+                    step / F::cast(4.0),
+                );
+                PivotLevels {
+                    pp,
+                    r1: close + a,
+                    r2: close + b,
+                    r3: close + c,
+                    s1: close - a,
+                    s2: close - b,
+                    s3: close - c,
+                }
+            }
+            PivotMethod::Woodie => classic((high + low + two * close) / F::cast(4.0)),
+        };
+        self.levels = Some(levels);
+        levels
+    }
+}
+
+impl<F: Float> Calculate<F> for PivotPoints<F> {
+    type Output = PivotLevels<F>;
+
+    fn calc(&mut self, input: F) -> Self::Output {
+        self.update(input, input, input)
+    }
+}
+
+impl<F: Float, T: High<F> + Low<F> + Close<F>> Next<T> for PivotPoints<F> {
+    type Output = PivotLevels<F>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.update(input.high(), input.low(), input.close())
+    }
+}
+
+impl<F: Float> Reset for PivotPoints<F> {
+    fn reset(&mut self) {
+        self.levels = None;
+    }
+}
+
+impl<F: Float> Warmup for PivotPoints<F> {
+    fn warmup_period(&self) -> u32 {
+        1
+    }
+
+    fn is_ready(&self) -> bool {
+        self.levels.is_some()
+    }
+}
+
+impl Default for PivotPoints {
+    fn default() -> Self {
+        Self::new(PivotMethod::default())
+    }
+}
+
+impl<F: Float> fmt::Display for PivotPoints<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PIVOT({})", self.method)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(PivotPoints);
+
+    fn levels(method: PivotMethod) -> [f64; 7] {
+        let mut pivots = PivotPoints::new(method);
+        let l = pivots.next(&Bar::new().high(110).low(90).close(106));
+        [l.s3, l.s2, l.s1, l.pp, l.r1, l.r2, l.r3].map(round)
+    }
+
+    #[test]
+    fn test_classic() {
+        assert_eq!(
+            levels(PivotMethod::Classic),
+            [74.0, 82.0, 94.0, 102.0, 114.0, 122.0, 134.0]
+        );
+    }
+
+    #[test]
+    fn test_fibonacci() {
+        assert_eq!(
+            levels(PivotMethod::Fibonacci),
+            [82.0, 89.64, 94.36, 102.0, 109.64, 114.36, 122.0]
+        );
+    }
+
+    #[test]
+    fn test_camarilla() {
+        assert_eq!(
+            levels(PivotMethod::Camarilla),
+            [100.5, 102.333, 104.167, 102.0, 107.833, 109.667, 111.5]
+        );
+    }
+
+    #[test]
+    fn test_woodie() {
+        assert_eq!(
+            levels(PivotMethod::Woodie),
+            [76.0, 83.0, 96.0, 103.0, 116.0, 123.0, 136.0]
+        );
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut pivots = PivotPoints::default();
+        pivots.calc(10.0);
+        assert!(pivots.is_ready());
+        pivots.reset();
+        assert_eq!(pivots.levels(), None);
+    }
+
+    #[test]
+    fn test_display() {
+        let pivots = PivotPoints::new(PivotMethod::Camarilla);
+        assert_eq!(format!("{}", pivots), "PIVOT(CAMARILLA)");
+    }
+}
@@ -0,0 +1,253 @@
+use core::fmt;
+
+use crate::errors::*;
+use crate::{Calculate, Close, Float, Next, Reset, Warmup};
+
+/// Kind of a [PointAndFigure](struct.PointAndFigure.html) column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ColumnKind {
+    /// Rising column of Xs.
+    X,
+    /// Falling column of Os.
+    O,
+}
+
+/// Column of a [PointAndFigure](struct.PointAndFigure.html) chart.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PointAndFigureColumn<F = f64> {
+    pub kind: ColumnKind,
+    /// Lowest box boundary of the column.
+    pub low: F,
+    /// Highest box boundary of the column.
+    pub high: F,
+    /// Number of boxes between `low` and `high`.
+    pub boxes: u32,
+}
+
+/// Point and Figure chart.
+///
+/// Transforms closing prices into the columns of a Point and Figure chart on a grid of boxes
+/// of a fixed size, ignoring time and moves smaller than a box. A column of Xs rises while
+/// the price reaches new boxes above it, a column of Os falls while it reaches new boxes
+/// below it, and the column reverses once the price moves against it by the reversal amount
+/// of boxes. The first column starts at the box of the first price, once the price moves a
+/// full box away from it.
+///
+/// Columns span box boundaries, multiples of the box size: a column of Xs from 10 to 13
+/// with a box size of 1 has 3 boxes, and a reversal at 10 starts a column of Os from 13 down
+/// to 10.
+///
+/// Returns the completed column on every reversal and `None` otherwise; the forming column
+/// is available with [current](#method.current).
+///
+/// # Parameters
+///
+/// * _box_size_ - price range of a box (greater than 0). Default is 1.0.
+/// * _reversal_ - number of boxes reversing a column (integer greater than 0). Default is 3.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{ColumnKind, PointAndFigure};
+/// use ta::Calculate;
+///
+/// let mut pnf = PointAndFigure::new(1.0, 3).unwrap();
+/// for &close in &[10.0, 11.5, 13.2, 11.0] {
+///     assert_eq!(pnf.calc(close), None);
+/// }
+///
+/// let column = pnf.calc(9.8).unwrap();
+/// assert_eq!(column.kind, ColumnKind::X);
+/// assert_eq!((column.low, column.high, column.boxes), (10.0, 13.0, 3));
+///
+/// let current = pnf.current().unwrap();
+/// assert_eq!(current.kind, ColumnKind::O);
+/// assert_eq!((current.low, current.high), (10.0, 13.0));
+/// ```
+///
+/// # Links
+///
+/// * [Point and Figure Charting, Investopedia](https://www.investopedia.com/terms/p/pointandfigurechart.asp)
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PointAndFigure<F = f64> {
+    box_size: F,
+    reversal: u32,
+    // first price, until the first column starts
+    origin: Option<F>,
+    // kind and boundaries of the current column, in boxes
+    column: Option<(ColumnKind, F, F)>,
+}
+
+impl PointAndFigure {
+    pub fn new(box_size: f64, reversal: u32) -> Result<Self> {
+        Self::with_type(box_size, reversal)
+    }
+}
+
+impl<F: Float> PointAndFigure<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(box_size: F, reversal: u32) -> Result<Self> {
+        if box_size <= F::zero() || !box_size.is_finite() || reversal == 0 {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        Ok(Self {
+            box_size,
+            reversal,
+            origin: None,
+            column: None,
+        })
+    }
+
+    /// Column still forming, `None` until the price first moves a full box.
+    pub fn current(&self) -> Option<PointAndFigureColumn<F>> {
+        self.column
+            .map(|(kind, low, high)| self.to_column(kind, low, high))
+    }
+
+    fn to_column(&self, kind: ColumnKind, low: F, high: F) -> PointAndFigureColumn<F> {
+        PointAndFigureColumn {
+            kind,
+            low: low * self.box_size,
+            high: high * self.box_size,
+            boxes: (high - low).to_u32().unwrap_or(0),
+        }
+    }
+}
+
+impl<F: Float> Calculate<F> for PointAndFigure<F> {
+    type Output = Option<PointAndFigureColumn<F>>;
+
+    fn calc(&mut self, input: F) -> Self::Output {
+        // highest boundary reached rising and lowest boundary reached falling
+        let rising = (input / self.box_size).floor();
+        let falling = (input / self.box_size).ceil();
+        let reversal = F::cast(self.reversal);
+
+        let (kind, low, high) = match self.column {
+            Some(column) => column,
+            None => {
+                let origin = *self.origin.get_or_insert(input) / self.box_size;
+                if rising > origin.floor() {
+                    self.column = Some((ColumnKind::X, origin.floor(), rising));
+                } else if falling < origin.ceil() {
+                    self.column = Some((ColumnKind::O, falling, origin.ceil()));
+                }
+                return None;
+            }
+        };
+
+        match kind {
+            ColumnKind::X if rising > high => {
+                self.column = Some((kind, low, rising));
+                None
+            }
+            ColumnKind::O if falling < low => {
+                self.column = Some((kind, falling, high));
+                None
+            }
+            ColumnKind::X if high - falling >= reversal => {
+                self.column = Some((ColumnKind::O, falling, high));
+                Some(self.to_column(kind, low, high))
+            }
+            ColumnKind::O if rising - low >= reversal => {
+                self.column = Some((ColumnKind::X, low, rising));
+                Some(self.to_column(kind, low, high))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl<F: Float, T: Close<F>> Next<T> for PointAndFigure<F> {
+    type Output = Option<PointAndFigureColumn<F>>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.calc(input.close())
+    }
+}
+
+impl<F: Float> Reset for PointAndFigure<F> {
+    fn reset(&mut self) {
+        self.origin = None;
+        self.column = None;
+    }
+}
+
+impl<F: Float> Warmup for PointAndFigure<F> {
+    fn warmup_period(&self) -> u32 {
+        1
+    }
+
+    fn is_ready(&self) -> bool {
+        self.origin.is_some()
+    }
+}
+
+impl Default for PointAndFigure {
+    fn default() -> Self {
+        Self::new(1.0, 3).unwrap()
+    }
+}
+
+impl<F: Float> fmt::Display for PointAndFigure<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PNF({}, {})", self.box_size, self.reversal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(PointAndFigure);
+
+    #[test]
+    fn test_new() {
+        assert!(PointAndFigure::new(0.0, 3).is_err());
+        assert!(PointAndFigure::new(1.0, 0).is_err());
+        assert!(PointAndFigure::new(0.5, 1).is_ok());
+    }
+
+    #[test]
+    fn test_columns() {
+        let mut pnf = PointAndFigure::new(2.0, 2).unwrap();
+        let closes = [21.0, 20.5, 19.9, 17.0, 19.0, 20.5, 22.1, 24.0, 21.0, 19.5];
+        let columns: Vec<_> = closes.iter().filter_map(|&c| pnf.calc(c)).collect();
+
+        assert_eq!(columns.len(), 2);
+        assert_eq!(columns[0].kind, ColumnKind::O);
+        assert_eq!(
+            (columns[0].low, columns[0].high, columns[0].boxes),
+            (18.0, 22.0, 2)
+        );
+        assert_eq!(columns[1].kind, ColumnKind::X);
+        assert_eq!(
+            (columns[1].low, columns[1].high, columns[1].boxes),
+            (18.0, 24.0, 3)
+        );
+
+        let current = pnf.current().unwrap();
+        assert_eq!(current.kind, ColumnKind::O);
+        assert_eq!((current.low, current.high, current.boxes), (20.0, 24.0, 2));
+    }
+
+    #[test]
+    fn test_next() {
+        let mut pnf = PointAndFigure::new(1.0, 1).unwrap();
+        assert_eq!(pnf.next(&Bar::new().close(10)), None);
+        assert_eq!(pnf.next(&Bar::new().close(12)), None);
+        let column = pnf.next(&Bar::new().close(11)).unwrap();
+        assert_eq!((column.kind, column.boxes), (ColumnKind::X, 2));
+    }
+
+    #[test]
+    fn test_display() {
+        let pnf = PointAndFigure::new(0.5, 3).unwrap();
+        assert_eq!(format!("{}", pnf), "PNF(0.5, 3)");
+    }
+}
@@ -0,0 +1,183 @@
+use core::fmt;
+
+use crate::errors::*;
+use crate::indicators::{AverageTrueRange, SimpleMovingAverage};
+use crate::{Calculate, Close, Float, High, Low, Next, Reset, Warmup};
+
+/// Pretty Good Oscillator (PGO) by Mark Johnson.
+///
+/// Distance of the close from its simple moving average, expressed in units of the
+/// average true range. Values above 3 (below -3) are traditionally read as the start of
+/// a long-term breakout up (down).
+///
+/// # Formula
+///
+/// PGO<sub>t</sub> = (C<sub>t</sub> - SMA(_length_)<sub>t</sub>) / ATR(_length_)<sub>t</sub>
+///
+/// Where:
+///
+/// * _SMA_ - [simple moving average](struct.SimpleMovingAverage.html) of the close
+/// * _ATR_ - [average true range](struct.AverageTrueRange.html)
+///
+/// When the average true range is zero the PGO is 0.
+///
+/// # Parameters
+///
+/// * _length_ - number of periods (integer greater than 0). Default is 14.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::PrettyGoodOscillator;
+/// use ta::Calculate;
+///
+/// let mut pgo = PrettyGoodOscillator::new(3).unwrap();
+/// assert_eq!(pgo.calc(10.0), 0.0);
+/// assert_eq!(pgo.calc(12.0), 1.0);
+/// assert_eq!(pgo.calc(11.0), 0.0);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(deserialize = "F: Float + serde::Deserialize<'de>"))
+)]
+pub struct PrettyGoodOscillator<F = f64> {
+    length: u32,
+    sma: SimpleMovingAverage<F>,
+    atr: AverageTrueRange<F>,
+}
+
+impl PrettyGoodOscillator {
+    pub fn new(length: u32) -> Result<Self> {
+        Self::with_type(length)
+    }
+}
+
+impl<F: Float> PrettyGoodOscillator<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(length: u32) -> Result<Self> {
+        Ok(Self {
+            length,
+            sma: SimpleMovingAverage::with_type(length)?,
+            atr: AverageTrueRange::with_type(length)?,
+        })
+    }
+
+    pub fn length(&self) -> u32 {
+        self.length
+    }
+
+    fn oscillator(close: F, average: F, range: F) -> F {
+        if range == F::zero() {
+            F::zero()
+        } else {
+            (close - average) / range
+        }
+    }
+}
+
+impl<F: Float> Calculate<F> for PrettyGoodOscillator<F> {
+    type Output = F;
+
+    fn calc(&mut self, input: F) -> F {
+        let average = self.sma.calc(input);
+        let range = self.atr.calc(input);
+        Self::oscillator(input, average, range)
+    }
+}
+
+impl<F: Float, T: High<F> + Low<F> + Close<F>> Next<T> for PrettyGoodOscillator<F> {
+    type Output = F;
+
+    fn next(&mut self, input: &T) -> F {
+        let average = self.sma.calc(input.close());
+        let range = self.atr.next(input);
+        Self::oscillator(input.close(), average, range)
+    }
+}
+
+impl<F: Float> Reset for PrettyGoodOscillator<F> {
+    fn reset(&mut self) {
+        self.sma.reset();
+        self.atr.reset();
+    }
+}
+
+impl<F: Float> Warmup for PrettyGoodOscillator<F> {
+    fn warmup_period(&self) -> u32 {
+        self.length
+    }
+
+    fn is_ready(&self) -> bool {
+        self.sma.is_ready() && self.atr.is_ready()
+    }
+}
+
+impl Default for PrettyGoodOscillator {
+    fn default() -> Self {
+        Self::new(14).unwrap()
+    }
+}
+
+impl<F: Float> fmt::Display for PrettyGoodOscillator<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PGO({})", self.length)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(PrettyGoodOscillator);
+
+    #[test]
+    fn test_new() {
+        assert!(PrettyGoodOscillator::new(0).is_err());
+        assert!(PrettyGoodOscillator::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_calc() {
+        let mut pgo = PrettyGoodOscillator::new(3).unwrap();
+        assert_eq!(pgo.calc(10.0), 0.0);
+        assert_eq!(pgo.calc(12.0), 1.0);
+        assert_eq!(pgo.calc(11.0), 0.0);
+        assert_eq!(round(pgo.calc(14.0)), 0.833);
+    }
+
+    #[test]
+    fn test_next() {
+        let mut pgo = PrettyGoodOscillator::new(3).unwrap();
+        let bar = |high: f64, low: f64, close: f64| Bar::new().high(high).low(low).close(close);
+
+        assert_eq!(pgo.next(&bar(11.0, 9.0, 10.0)), 0.0);
+        assert_eq!(pgo.next(&bar(13.0, 10.0, 12.0)), 0.4);
+        assert_eq!(pgo.next(&bar(12.0, 10.0, 11.0)), 0.0);
+        assert_eq!(round(pgo.next(&bar(15.0, 12.0, 14.0))), 0.533);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut pgo = PrettyGoodOscillator::new(3).unwrap();
+        pgo.calc(10.0);
+        pgo.calc(12.0);
+
+        pgo.reset();
+        assert_eq!(pgo.calc(12.0), 0.0);
+        assert_eq!(pgo.calc(14.0), 1.0);
+    }
+
+    #[test]
+    fn test_default() {
+        PrettyGoodOscillator::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let pgo = PrettyGoodOscillator::new(21).unwrap();
+        assert_eq!(format!("{}", pgo), "PGO(21)");
+    }
+}
@@ -0,0 +1,205 @@
+use core::fmt;
+
+use crate::errors::*;
+use crate::sorted_window::SortedWindow;
+use crate::{Calculate, Close, Finite, Float, Next, Reset, Warmup};
+
+/// Quantile Channel.
+///
+/// Rolling lower and upper quantiles of the last _length_ values, e.g. their 5th and 95th
+/// percentiles, with their median in the middle. It is a robust alternative to a Donchian
+/// channel of the [Minimum](struct.Minimum.html) and [Maximum](struct.Maximum.html): a
+/// single spike moves the extremes of the window, but barely its quantiles.
+///
+/// Quantiles are linearly interpolated between the closest ranks of the sorted window.
+///
+/// # Parameters
+///
+/// * _length_ - number of periods (integer greater than 0). Default is 20.
+/// * _lower_ - quantile of the lower band (0 to 1, less than _upper_). Default is 0.05.
+/// * _upper_ - quantile of the upper band (0 to 1). Default is 0.95.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::QuantileChannel;
+/// use ta::Calculate;
+///
+/// let mut channel = QuantileChannel::new(5, 0.25, 0.75).unwrap();
+/// for &close in &[10.0, 12.0, 11.0, 13.0] {
+///     channel.calc(close);
+/// }
+/// // a spike only moves the upper band from 12.0 to 13.0
+/// let out = channel.calc(100.0);
+/// assert_eq!((out.lower, out.middle, out.upper), (11.0, 12.0, 13.0));
+/// ```
+///
+/// # Links
+///
+/// * [Quantile, Wikipedia](https://en.wikipedia.org/wiki/Quantile)
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QuantileChannel<F = f64> {
+    length: u32,
+    lower: F,
+    upper: F,
+    window: SortedWindow<F>,
+}
+
+/// Output of [QuantileChannel](struct.QuantileChannel.html).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QuantileChannelOutput<F = f64> {
+    pub upper: F,
+    /// Median of the window.
+    pub middle: F,
+    pub lower: F,
+}
+
+impl<F: Finite> Finite for QuantileChannelOutput<F> {
+    fn is_finite(&self) -> bool {
+        self.upper.is_finite() && self.middle.is_finite() && self.lower.is_finite()
+    }
+}
+
+impl QuantileChannel {
+    pub fn new(length: u32, lower: f64, upper: f64) -> Result<Self> {
+        Self::with_type(length, lower, upper)
+    }
+}
+
+impl<F: Float> QuantileChannel<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(length: u32, lower: F, upper: F) -> Result<Self> {
+        if length == 0 || !(F::zero() <= lower && lower < upper && upper <= F::one()) {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        Ok(Self {
+            length,
+            lower,
+            upper,
+            window: SortedWindow::new(length as usize),
+        })
+    }
+
+    pub fn length(&self) -> u32 {
+        self.length
+    }
+
+    pub fn lower(&self) -> F {
+        self.lower
+    }
+
+    pub fn upper(&self) -> F {
+        self.upper
+    }
+}
+
+impl<F: Float> Calculate<F> for QuantileChannel<F> {
+    type Output = QuantileChannelOutput<F>;
+
+    fn calc(&mut self, input: F) -> Self::Output {
+        self.window.push(input);
+        QuantileChannelOutput {
+            upper: self.window.quantile(self.upper),
+            middle: self.window.quantile(F::cast(0.5)),
+            lower: self.window.quantile(self.lower),
+        }
+    }
+}
+
+impl<F: Float, T: Close<F>> Next<T> for QuantileChannel<F> {
+    type Output = QuantileChannelOutput<F>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.calc(input.close())
+    }
+}
+
+impl<F: Float> Reset for QuantileChannel<F> {
+    fn reset(&mut self) {
+        self.window.clear();
+    }
+}
+
+impl<F: Float> Warmup for QuantileChannel<F> {
+    fn warmup_period(&self) -> u32 {
+        self.length
+    }
+
+    fn is_ready(&self) -> bool {
+        self.window.is_full()
+    }
+}
+
+impl Default for QuantileChannel {
+    fn default() -> Self {
+        Self::new(20, 0.05, 0.95).unwrap()
+    }
+}
+
+impl<F: Float> fmt::Display for QuantileChannel<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "QC({}, {}, {})", self.length, self.lower, self.upper)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(QuantileChannel);
+
+    #[test]
+    fn test_new() {
+        assert!(QuantileChannel::new(0, 0.05, 0.95).is_err());
+        assert!(QuantileChannel::new(10, -0.1, 0.95).is_err());
+        assert!(QuantileChannel::new(10, 0.5, 0.5).is_err());
+        assert!(QuantileChannel::new(10, 0.05, 1.1).is_err());
+        assert!(QuantileChannel::new(1, 0.0, 1.0).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut channel = QuantileChannel::new(4, 0.0, 1.0).unwrap();
+        let out = channel.next(&Bar::new().close(4));
+        assert_eq!((out.lower, out.middle, out.upper), (4.0, 4.0, 4.0));
+        channel.calc(2.0);
+        channel.calc(8.0);
+        let out = channel.calc(6.0);
+        assert_eq!((out.lower, out.middle, out.upper), (2.0, 5.0, 8.0));
+        // 4.0 leaves the window
+        let out = channel.calc(7.0);
+        assert_eq!((out.lower, out.middle, out.upper), (2.0, 6.5, 8.0));
+        assert!(channel.is_ready());
+    }
+
+    #[test]
+    fn test_quantiles() {
+        let mut channel = QuantileChannel::new(11, 0.1, 0.9).unwrap();
+        let mut out = channel.calc(0.0);
+        for i in 1..=10 {
+            out = channel.calc(i as f64 * 10.0);
+        }
+        assert_eq!((out.lower, out.middle, out.upper), (10.0, 50.0, 90.0));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut channel = QuantileChannel::new(3, 0.25, 0.75).unwrap();
+        channel.calc(1.0);
+        channel.calc(10.0);
+        channel.reset();
+        assert!(!channel.is_ready());
+        assert_eq!(channel.calc(5.0).upper, 5.0);
+    }
+
+    #[test]
+    fn test_display() {
+        let channel = QuantileChannel::default();
+        assert_eq!(format!("{}", channel), "QC(20, 0.05, 0.95)");
+        assert_eq!((channel.lower(), channel.upper()), (0.05, 0.95));
+    }
+}
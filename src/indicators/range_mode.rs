@@ -0,0 +1,52 @@
+use crate::Float;
+
+/// How a bounded oscillator treats outputs outside of its nominal range.
+///
+/// Oscillators such as [FastStochastic](struct.FastStochastic.html) and
+/// [WilliamsR](struct.WilliamsR.html) stay within their range on consistent bars, but leave it
+/// when the close lies outside of the high-low range of the window, e.g. on bad data.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{FastStochastic, RangeMode};
+/// use ta::Next;
+///
+/// // open, high, low, close and volume, with the close above the high
+/// let bar = (20.0, 20.0, 10.0, 25.0, 1.0);
+///
+/// let mut stoch = FastStochastic::new(3).unwrap();
+/// assert_eq!(stoch.next(&bar), 150.0);
+///
+/// let mut stoch = FastStochastic::new(3).unwrap().with_range_mode(RangeMode::Clamp);
+/// assert_eq!(stoch.next(&bar), 100.0);
+/// assert_eq!(stoch.violations(), 1);
+///
+/// let mut stoch = FastStochastic::new(3).unwrap().with_range_mode(RangeMode::Strict);
+/// assert_eq!(stoch.next(&bar), 150.0);
+/// assert_eq!(stoch.violations(), 1);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RangeMode {
+    /// Outputs are returned as computed, without any checks.
+    #[default]
+    Unchecked,
+    /// Outputs are clamped into the range, and counted as violations.
+    Clamp,
+    /// Outputs are returned as computed, and counted as violations.
+    Strict,
+}
+
+impl RangeMode {
+    /// Applies the mode to `value`, returning the output and whether `value` lies outside of
+    /// `min..=max`.
+    pub(crate) fn apply<F: Float>(self, value: F, min: F, max: F) -> (F, bool) {
+        match self {
+            RangeMode::Unchecked => (value, false),
+            _ if min <= value && value <= max => (value, false),
+            RangeMode::Clamp => (value.max(min).min(max), true),
+            RangeMode::Strict => (value, true),
+        }
+    }
+}
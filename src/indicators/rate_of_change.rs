@@ -1,8 +1,24 @@
-use std::collections::VecDeque;
-use std::fmt;
+use core::fmt;
 
 use crate::errors::*;
-use crate::traits::{Calculate, Close, Next, Reset};
+use crate::traits::{
+    Calculate, Close, Next, Number, Peek, PeekNext, Replace, Reset, UpdateLast, Warmup,
+};
+use crate::Window;
+
+/// Variant of [RateOfChange](struct.RateOfChange.html), named after its TA-Lib function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RateOfChangeKind {
+    /// ROC = (P<sub>t</sub> - P<sub>t-n</sub>) / P<sub>t-n</sub> * 100
+    Percent,
+    /// ROCP = (P<sub>t</sub> - P<sub>t-n</sub>) / P<sub>t-n</sub>
+    Fraction,
+    /// ROCR = P<sub>t</sub> / P<sub>t-n</sub>
+    Ratio,
+    /// ROCR100 = P<sub>t</sub> / P<sub>t-n</sub> * 100
+    Ratio100,
+}
 
 /// Rate of Change (ROC)
 ///
@@ -16,6 +32,9 @@ use crate::traits::{Calculate, Close, Next, Reset};
 /// * P<sub>t</sub> - price at the moment
 /// * P<sub>t-n</sub> - price _n_ periods ago
 ///
+/// The TA-Lib variants ROCP, ROCR and ROCR100 are available through
+/// [with_kind](#method.with_kind) and [RateOfChangeKind](enum.RateOfChangeKind.html).
+///
 /// # Parameters
 ///
 /// * _length_ - number of periods (_n_), integer greater than 0
@@ -23,7 +42,7 @@ use crate::traits::{Calculate, Close, Next, Reset};
 /// # Example
 ///
 /// ```
-/// use ta::indicators::RateOfChange;
+/// use ta::indicators::{RateOfChange, RateOfChangeKind};
 /// use ta::{Calculate, Next};
 ///
 /// let mut roc = RateOfChange::new(2).unwrap();
@@ -31,6 +50,10 @@ use crate::traits::{Calculate, Close, Next, Reset};
 /// assert_eq!(roc.calc(9.7).round(), -3.0);    //  (9.7 - 10) / 10  * 100 = -3
 /// assert_eq!(roc.calc(20.0).round(), 100.0);  //  (20 - 10)  / 10  * 100 = 100
 /// assert_eq!(roc.calc(20.0).round(), 106.0);  //  (20 - 9.7) / 9.7 * 100 = 106
+///
+/// let mut rocr = RateOfChange::with_kind(RateOfChangeKind::Ratio, 1).unwrap();
+/// rocr.calc(10.0);
+/// assert_eq!(rocr.calc(12.0), 1.2);
 /// ```
 ///
 /// # Links
@@ -38,74 +61,194 @@ use crate::traits::{Calculate, Close, Next, Reset};
 /// * [Rate of Change, Wikipedia](https://en.wikipedia.org/wiki/Momentum_(technical_analysis))
 ///
 #[derive(Debug, Clone)]
-pub struct RateOfChange {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(deserialize = "F: Number + serde::Deserialize<'de>"))
+)]
+pub struct RateOfChange<F = f64> {
     length: u32,
-    prices: VecDeque<f64>,
+    kind: RateOfChangeKind,
+    // the last length + 1 prices, the oldest one is the reference price
+    prices: Window<F>,
 }
 
 impl RateOfChange {
     pub fn new(length: u32) -> Result<Self> {
+        Self::with_type(length)
+    }
+
+    /// Creates a rate of change of the given variant.
+    pub fn with_kind(kind: RateOfChangeKind, length: u32) -> Result<Self> {
+        Self::create(kind, length)
+    }
+}
+
+impl<F: Number> RateOfChange<F> {
+    /// Like [new](#method.new), for values of any number type `F`.
+    pub fn with_type(length: u32) -> Result<Self> {
+        Self::create(RateOfChangeKind::Percent, length)
+    }
+
+    fn create(kind: RateOfChangeKind, length: u32) -> Result<Self> {
         match length {
             0 => Err(Error::from_kind(ErrorKind::InvalidParameter)),
             _ => {
                 let indicator = Self {
-                    length: length,
-                    prices: VecDeque::with_capacity(length as usize + 1),
+                    length,
+                    kind,
+                    prices: Window::new(length as usize + 1),
                 };
                 Ok(indicator)
             }
         }
     }
+
+    pub fn length(&self) -> u32 {
+        self.length
+    }
+
+    pub fn kind(&self) -> RateOfChangeKind {
+        self.kind
+    }
+
+    /// Changes the number of periods, keeping the latest prices which are still needed.
+    /// Fails with `InvalidParameter` if `length` is 0.
+    pub fn set_length(&mut self, length: u32) -> Result<()> {
+        if length == 0 {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        self.length = length;
+        self.prices.resize(length as usize + 1);
+        Ok(())
+    }
+
+    /// Like [set_length](#method.set_length), consuming the indicator.
+    pub fn with_length(mut self, length: u32) -> Result<Self> {
+        self.set_length(length)?;
+        Ok(self)
+    }
+
+    // Change of the input since the reference price, none before the first change.
+    fn change(&self, input: F, initial_price: Option<F>) -> F {
+        let initial_price = match initial_price {
+            Some(price) => price,
+            // no change yet
+            None => {
+                return match self.kind {
+                    RateOfChangeKind::Percent | RateOfChangeKind::Fraction => F::zero(),
+                    RateOfChangeKind::Ratio => F::one(),
+                    RateOfChangeKind::Ratio100 => F::cast(100.0),
+                }
+            }
+        };
+
+        match self.kind {
+            RateOfChangeKind::Percent => (input - initial_price) / initial_price * F::cast(100.0),
+            RateOfChangeKind::Fraction => (input - initial_price) / initial_price,
+            RateOfChangeKind::Ratio => input / initial_price,
+            RateOfChangeKind::Ratio100 => input / initial_price * F::cast(100.0),
+        }
+    }
+
+    // Reference price of the latest input.
+    fn initial_price(&self) -> Option<F> {
+        if self.prices.len() > 1 {
+            self.prices.oldest().copied()
+        } else {
+            None
+        }
+    }
 }
 
-impl Calculate for RateOfChange {
-    fn calc(&mut self, input: f64) -> f64 {
-        self.prices.push_back(input);
+impl<F: Number> Calculate<F> for RateOfChange<F> {
+    type Output = F;
 
-        if self.prices.len() == 1 {
-            return 0.0;
+    fn calc(&mut self, input: F) -> F {
+        self.prices.push(input);
+        self.change(input, self.initial_price())
+    }
+}
+
+impl<F: Number> UpdateLast<F> for RateOfChange<F> {
+    fn update_last(&mut self, input: F) -> F {
+        if self.prices.replace_newest(input).is_none() {
+            return self.calc(input);
         }
+        self.change(input, self.initial_price())
+    }
+}
 
-        let initial_price = if self.prices.len() > (self.length as usize) {
-            // unwrap is safe, because the check above.
-            // At this moment there must be at least 2 items in self.prices
-            self.prices.pop_front().unwrap()
+impl<F: Number> Peek<F> for RateOfChange<F> {
+    fn peek(&self, input: F) -> F {
+        let initial_price = if self.prices.is_full() {
+            self.prices.get(1)
         } else {
-            self.prices[0]
+            self.prices.oldest()
         };
-
-        (input - initial_price) / initial_price * 100.0
+        self.change(input, initial_price.copied())
     }
 }
 
-impl<T: Close> Next<T> for RateOfChange {
-    fn next(&mut self, input: &T) -> f64 {
+impl<F: Number, T: Close<F>> Next<T> for RateOfChange<F> {
+    type Output = F;
+
+    fn next(&mut self, input: &T) -> F {
         self.calc(input.close())
     }
 }
 
+impl<F: Number, T: Close<F>> Replace<T> for RateOfChange<F> {
+    fn replace(&mut self, input: &T) -> F {
+        self.update_last(input.close())
+    }
+}
+
+impl<F: Number, T: Close<F>> PeekNext<T> for RateOfChange<F> {
+    fn peek_next(&self, input: &T) -> F {
+        self.peek(input.close())
+    }
+}
+
 impl Default for RateOfChange {
     fn default() -> Self {
         Self::new(9).unwrap()
     }
 }
 
-impl fmt::Display for RateOfChange {
+impl<F: Number> fmt::Display for RateOfChange<F> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "ROC({})", self.length)
+        let name = match self.kind {
+            RateOfChangeKind::Percent => "ROC",
+            RateOfChangeKind::Fraction => "ROCP",
+            RateOfChangeKind::Ratio => "ROCR",
+            RateOfChangeKind::Ratio100 => "ROCR100",
+        };
+        write!(f, "{}({})", name, self.length)
     }
 }
 
-impl Reset for RateOfChange {
+impl<F: Number> Reset for RateOfChange<F> {
     fn reset(&mut self) {
         self.prices.clear();
     }
 }
 
+impl<F: Number> Warmup for RateOfChange<F> {
+    fn warmup_period(&self) -> u32 {
+        self.length + 1
+    }
+
+    fn is_ready(&self) -> bool {
+        self.prices.is_full()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::test_helper::*;
+    use crate::TryCalculate;
 
     test_indicator!(RateOfChange);
 
@@ -141,6 +284,92 @@ mod tests {
         assert_eq!(round(roc.next(&bar(10.57))), 5.7);
     }
 
+    #[test]
+    fn test_kinds() {
+        // Reference values from TA-Lib ROCP, ROCR and ROCR100 with a period of 3.
+        let prices = [10.0, 10.4, 10.57, 10.8, 10.9, 10.0];
+        let expected = [
+            (
+                RateOfChangeKind::Fraction,
+                [0.0, 0.04, 0.057, 0.08, 0.048, -0.054],
+            ),
+            (
+                RateOfChangeKind::Ratio,
+                [1.0, 1.04, 1.057, 1.08, 1.048, 0.946],
+            ),
+            (
+                RateOfChangeKind::Ratio100,
+                [100.0, 104.0, 105.7, 108.0, 104.808, 94.607],
+            ),
+        ];
+
+        for (kind, values) in expected.iter() {
+            let mut roc = RateOfChange::with_kind(*kind, 3).unwrap();
+            assert_eq!(roc.kind(), *kind);
+            for (price, value) in prices.iter().zip(values.iter()) {
+                assert_eq!(round(roc.calc(*price)), *value);
+            }
+        }
+    }
+
+    #[test]
+    fn test_try_calc() {
+        let mut roc = RateOfChange::new(2).unwrap();
+        assert_eq!(roc.try_calc(10.0).ok(), None);
+        assert_eq!(roc.try_calc(11.0).ok(), None);
+        assert_eq!(roc.try_calc(12.0).ok(), Some(20.0));
+    }
+
+    #[test]
+    fn test_update_last() {
+        let mut roc = RateOfChange::new(2).unwrap();
+        assert_eq!(roc.update_last(10.0), 0.0);
+        assert_eq!(roc.calc(20.0), 100.0);
+        assert_eq!(roc.update_last(15.0), 50.0);
+        assert_eq!(roc.calc(12.0), 20.0);
+        assert_eq!(roc.update_last(10.0), 0.0);
+        assert_eq!(roc.replace(&Bar::new().close(30.0)), 200.0);
+        assert_eq!(roc.calc(18.0), 20.0);
+    }
+
+    #[test]
+    fn test_peek() {
+        let mut indicator = RateOfChange::new(2).unwrap();
+        for &x in &[10.0, 20.0, 15.0, 12.0, 18.0] {
+            let peeked = indicator.peek(x);
+            assert_eq!(
+                indicator.peek_next(&Bar::new().close(x)),
+                indicator.clone().next(&Bar::new().close(x))
+            );
+            assert_eq!(indicator.calc(x), peeked);
+        }
+    }
+
+    #[test]
+    fn test_set_length() {
+        let mut roc = RateOfChange::new(3).unwrap();
+        for &price in &[10.0, 11.0, 12.0, 13.0, 14.0] {
+            roc.calc(price);
+        }
+        assert!(roc.set_length(0).is_err());
+
+        roc.set_length(1).unwrap();
+        assert_eq!(roc.length(), 1);
+        assert!(roc.is_ready());
+        assert_eq!(round(roc.calc(15.4)), 10.0);
+
+        let mut roc = roc.with_length(3).unwrap();
+        assert!(!roc.is_ready());
+        let mut expected = RateOfChange::new(3).unwrap();
+        for &price in &[14.0, 15.4, 16.0] {
+            expected.calc(price);
+        }
+        roc.calc(16.0);
+        assert!(!roc.is_ready());
+        assert_eq!(roc.calc(17.0), expected.calc(17.0));
+        assert!(roc.is_ready());
+    }
+
     #[test]
     fn test_reset() {
         let mut roc = RateOfChange::new(3).unwrap();
@@ -154,4 +383,13 @@ mod tests {
         assert_eq!(round(roc.calc(10.4)), 4.0);
         assert_eq!(round(roc.calc(10.57)), 5.7);
     }
+
+    #[test]
+    fn test_display() {
+        let display = |kind| format!("{}", RateOfChange::with_kind(kind, 5).unwrap());
+        assert_eq!(display(RateOfChangeKind::Percent), "ROC(5)");
+        assert_eq!(display(RateOfChangeKind::Fraction), "ROCP(5)");
+        assert_eq!(display(RateOfChangeKind::Ratio), "ROCR(5)");
+        assert_eq!(display(RateOfChangeKind::Ratio100), "ROCR100(5)");
+    }
 }
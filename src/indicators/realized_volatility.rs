@@ -0,0 +1,207 @@
+use core::fmt;
+
+use crate::{Calculate, Close, Float, Next, Reset, Warmup};
+
+/// Realized volatility from intrabar prices.
+///
+/// Accumulates squared log returns of intrabar prices (ticks, trades or minute bar
+/// closes) into realized variance of the bar that is being built. Every input returns the
+/// realized volatility of the current bar so far; [close_bar](#method.close_bar) finalizes
+/// the bar and starts the next one. The return between the last price of a bar and the
+/// first price of the next bar is attributed to the next bar.
+///
+/// # Formula
+///
+/// RV = Σ r<sub>i</sub>², r<sub>i</sub> = ln(p<sub>i</sub> / p<sub>i-1</sub>)
+///
+/// Realized volatility is √RV.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::RealizedVolatility;
+/// use ta::Calculate;
+///
+/// let mut rv = RealizedVolatility::new();
+/// for &price in &[100.0, 101.0, 100.0, 102.0] {
+///     rv.calc(price);
+/// }
+/// let bar = rv.close_bar();
+/// assert_eq!(bar.count, 3);
+/// assert!(bar.volatility > 0.02);
+/// assert_eq!(bar.volatility, bar.variance.sqrt());
+/// ```
+///
+/// # Links
+///
+/// * [Realized variance, Wikipedia](https://en.wikipedia.org/wiki/Realized_variance)
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RealizedVolatility<F = f64> {
+    prev_price: Option<F>,
+    variance: F,
+    count: u32,
+}
+
+/// Realized variance and volatility of one bar.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RealizedVolatilityOutput<F = f64> {
+    pub variance: F,
+    pub volatility: F,
+    /// Number of intrabar returns accumulated.
+    pub count: u32,
+}
+
+impl RealizedVolatility {
+    pub fn new() -> Self {
+        Self::with_type()
+    }
+}
+
+impl<F: Float> RealizedVolatility<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type() -> Self {
+        Self {
+            prev_price: None,
+            variance: F::zero(),
+            count: 0,
+        }
+    }
+
+    /// Realized variance of the current bar so far.
+    pub fn variance(&self) -> F {
+        self.variance
+    }
+
+    /// Finalizes the current bar and returns its realized variance and volatility.
+    pub fn close_bar(&mut self) -> RealizedVolatilityOutput<F> {
+        let output = RealizedVolatilityOutput {
+            variance: self.variance,
+            volatility: self.variance.sqrt(),
+            count: self.count,
+        };
+        self.variance = F::zero();
+        self.count = 0;
+        output
+    }
+}
+
+impl<F: Float> Calculate<F> for RealizedVolatility<F> {
+    type Output = F;
+
+    fn calc(&mut self, input: F) -> F {
+        if let Some(prev) = self.prev_price {
+            let ret = (input / prev).ln();
+            self.variance += ret * ret;
+            self.count += 1;
+        }
+        self.prev_price = Some(input);
+        self.variance.sqrt()
+    }
+}
+
+impl<F: Float, T: Close<F>> Next<T> for RealizedVolatility<F> {
+    type Output = F;
+
+    fn next(&mut self, input: &T) -> F {
+        self.calc(input.close())
+    }
+}
+
+impl<F: Float> Reset for RealizedVolatility<F> {
+    fn reset(&mut self) {
+        self.prev_price = None;
+        self.variance = F::zero();
+        self.count = 0;
+    }
+}
+
+impl<F: Float> Warmup for RealizedVolatility<F> {
+    fn warmup_period(&self) -> u32 {
+        1
+    }
+
+    fn is_ready(&self) -> bool {
+        self.prev_price.is_some()
+    }
+}
+
+impl Default for RealizedVolatility {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: Float> fmt::Display for RealizedVolatility<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "RV()")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(RealizedVolatility);
+
+    #[test]
+    fn test_next() {
+        let mut rv = RealizedVolatility::new();
+        assert_eq!(rv.calc(100.0), 0.0);
+        assert_eq!(round(rv.calc(110.0)), round((1.1f64).ln()));
+
+        let bar = rv.close_bar();
+        assert_eq!(bar.count, 1);
+        assert_eq!(
+            round(bar.variance * 1000.0),
+            round((1.1f64).ln().powi(2) * 1000.0)
+        );
+
+        // the return from 110 to 99 belongs to the next bar
+        assert_eq!(round(rv.calc(99.0)), round((0.9f64).ln().abs()));
+        rv.calc(99.0);
+        let bar = rv.close_bar();
+        assert_eq!(bar.count, 2);
+        assert_eq!(round(bar.volatility), round((0.9f64).ln().abs()));
+    }
+
+    #[test]
+    fn test_empty_bar() {
+        let mut rv = RealizedVolatility::new();
+        let bar = rv.close_bar();
+        assert_eq!(bar.count, 0);
+        assert_eq!(bar.volatility, 0.0);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut rv = RealizedVolatility::new();
+        rv.next(&Bar::new().close(100.0));
+        rv.next(&Bar::new().close(110.0));
+        assert_eq!(round(rv.variance() * 1000.0), 9.084);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut rv = RealizedVolatility::new();
+        rv.calc(100.0);
+        rv.calc(110.0);
+
+        rv.reset();
+        assert_eq!(rv.calc(50.0), 0.0);
+        assert_eq!(rv.close_bar().count, 0);
+    }
+
+    #[test]
+    fn test_default() {
+        RealizedVolatility::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", RealizedVolatility::new()), "RV()");
+    }
+}
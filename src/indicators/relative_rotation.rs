@@ -0,0 +1,389 @@
+use core::fmt;
+
+use crate::errors::*;
+use crate::indicators::{SimpleMovingAverage, StandardDeviation};
+use crate::{Calculate, Finite, Float, Next, Reset, RingBuffer, Warmup};
+
+/// Relative rotation (RS-Ratio and RS-Momentum).
+///
+/// The metrics behind Relative Rotation Graphs, which show sectors or stocks rotating around
+/// a benchmark. The input is a pair `(price, benchmark)` of simultaneous prices.
+///
+/// The relative strength line _RS_ is the price relative to the benchmark. RS-Ratio measures
+/// its trend as the number of standard deviations it is above its _n_-period mean, and
+/// RS-Momentum measures the rate of change of RS-Ratio the same way. Both are centered at
+/// 100, i.e. a value of 101 is one standard deviation above the mean.
+///
+/// Created with [with_beta](#method.with_beta) the relative strength line is beta-adjusted:
+/// instead of the plain price ratio it compounds the excess returns over beta times the
+/// benchmark return, with the beta of the last _beta_n_ returns. A high-beta stock then
+/// does not lead just because the market goes up.
+///
+/// # Formula
+///
+/// RS = 100 * price / benchmark, or with beta adjustment
+/// RS<sub>t</sub> = RS<sub>t-1</sub> * (1 + r<sub>t</sub> - β r<sup>b</sup><sub>t</sub>)
+///
+/// RS-Ratio = 100 + (RS - SMA(RS, n)) / SD(RS, n)
+///
+/// RS-Momentum = 100 + (ROC - SMA(ROC, n)) / SD(ROC, n), where ROC is the one-period rate
+/// of change of RS-Ratio in percent
+///
+/// While a standard deviation is 0 the ratio or momentum is 100.
+///
+/// # Parameters
+///
+/// * _n_ - number of periods for the normalization (integer greater than 0). Default is 14.
+/// * _beta_n_ - number of returns for the beta (integer greater than 1), only with
+///   [with_beta](#method.with_beta).
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{RelativeRotation, RotationQuadrant};
+/// use ta::Next;
+///
+/// let mut rrg = RelativeRotation::new(3).unwrap();
+/// rrg.next(&(10.0, 100.0));
+/// rrg.next(&(10.0, 100.0));
+/// let out = rrg.next(&(11.0, 100.0));
+/// assert!(out.ratio > 100.0);
+/// assert!(out.momentum > 100.0);
+/// assert_eq!(out.quadrant(), RotationQuadrant::Leading);
+/// ```
+///
+/// # Links
+///
+/// * [Relative Rotation Graph, Wikipedia](https://en.wikipedia.org/wiki/Relative_Rotation_Graph)
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(deserialize = "F: Float + serde::Deserialize<'de>"))
+)]
+pub struct RelativeRotation<F = f64> {
+    n: u32,
+    beta_n: Option<u32>,
+    count: u32,
+    rs: F,
+    prev: Option<(F, F)>,
+    prev_ratio: Option<F>,
+    // returns of the price and the benchmark and their sums a, b, a*b, b*b for the beta
+    returns: RingBuffer<(F, F)>,
+    sums: (F, F, F, F),
+    ratio_mean: SimpleMovingAverage<F>,
+    ratio_sd: StandardDeviation<F>,
+    momentum_mean: SimpleMovingAverage<F>,
+    momentum_sd: StandardDeviation<F>,
+}
+
+/// Output of [RelativeRotation](struct.RelativeRotation.html).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RelativeRotationOutput<F = f64> {
+    pub ratio: F,
+    pub momentum: F,
+}
+
+impl<F: Finite> Finite for RelativeRotationOutput<F> {
+    fn is_finite(&self) -> bool {
+        self.ratio.is_finite() && self.momentum.is_finite()
+    }
+}
+
+/// Quadrant of a Relative Rotation Graph.
+///
+/// Rotations usually run clockwise: improving, leading, weakening, lagging.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RotationQuadrant {
+    /// Ratio and momentum at or above 100.
+    Leading,
+    /// Ratio at or above 100, momentum below.
+    Weakening,
+    /// Ratio and momentum below 100.
+    Lagging,
+    /// Ratio below 100, momentum at or above.
+    Improving,
+}
+
+impl<F: Float> RelativeRotationOutput<F> {
+    pub fn quadrant(&self) -> RotationQuadrant {
+        let hundred = F::cast(100);
+        match (self.ratio >= hundred, self.momentum >= hundred) {
+            (true, true) => RotationQuadrant::Leading,
+            (true, false) => RotationQuadrant::Weakening,
+            (false, false) => RotationQuadrant::Lagging,
+            (false, true) => RotationQuadrant::Improving,
+        }
+    }
+}
+
+impl RelativeRotation {
+    pub fn new(n: u32) -> Result<Self> {
+        Self::with_type(n)
+    }
+
+    /// Creates the indicator with a relative strength line adjusted by the rolling beta.
+    pub fn with_beta(n: u32, beta_n: u32) -> Result<Self> {
+        if beta_n < 2 {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        let mut indicator = Self::new(n)?;
+        indicator.beta_n = Some(beta_n);
+        indicator.returns = RingBuffer::new(beta_n as usize);
+        Ok(indicator)
+    }
+}
+
+impl<F: Float> RelativeRotation<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(n: u32) -> Result<Self> {
+        Ok(Self {
+            n,
+            beta_n: None,
+            count: 0,
+            rs: F::cast(100),
+            prev: None,
+            prev_ratio: None,
+            // unused without a beta
+            returns: RingBuffer::new(1),
+            sums: (F::zero(), F::zero(), F::zero(), F::zero()),
+            ratio_mean: SimpleMovingAverage::with_type(n)?,
+            ratio_sd: StandardDeviation::with_type(n)?,
+            momentum_mean: SimpleMovingAverage::with_type(n)?,
+            momentum_sd: StandardDeviation::with_type(n)?,
+        })
+    }
+
+    pub fn length(&self) -> u32 {
+        self.n
+    }
+
+    pub fn beta_length(&self) -> Option<u32> {
+        self.beta_n
+    }
+
+    /// Current value of the relative strength line.
+    pub fn relative_strength(&self) -> F {
+        self.rs
+    }
+
+    /// Beta of the returns in the window, 1 until the benchmark returns vary.
+    pub fn beta(&self) -> F {
+        let (a, b, ab, bb) = self.sums;
+        let len = F::cast(self.returns.len());
+        let denominator = len * bb - b * b;
+        if denominator <= F::zero() {
+            F::one()
+        } else {
+            (len * ab - a * b) / denominator
+        }
+    }
+
+    fn add_returns(&mut self, a: F, b: F) {
+        let evicted = self.returns.push((a, b));
+        self.sums.0 += a;
+        self.sums.1 += b;
+        self.sums.2 += a * b;
+        self.sums.3 += b * b;
+        if let Some((a, b)) = evicted {
+            self.sums.0 -= a;
+            self.sums.1 -= b;
+            self.sums.2 -= a * b;
+            self.sums.3 -= b * b;
+        }
+    }
+}
+
+fn z_score<F: Float>(value: F, mean: F, sd: F) -> F {
+    if sd == F::zero() {
+        F::cast(100)
+    } else {
+        F::cast(100) + (value - mean) / sd
+    }
+}
+
+impl<F: Float> Next<(F, F)> for RelativeRotation<F> {
+    type Output = RelativeRotationOutput<F>;
+
+    fn next(&mut self, &(price, benchmark): &(F, F)) -> Self::Output {
+        self.count = self.count.saturating_add(1);
+
+        if self.beta_n.is_none() {
+            self.rs = F::cast(100) * price / benchmark;
+        } else if let Some((prev_price, prev_benchmark)) = self.prev {
+            let ret = price / prev_price - F::one();
+            let benchmark_ret = benchmark / prev_benchmark - F::one();
+            self.add_returns(ret, benchmark_ret);
+            self.rs *= F::one() + ret - self.beta() * benchmark_ret;
+        }
+        self.prev = Some((price, benchmark));
+
+        let mean = self.ratio_mean.calc(self.rs);
+        let sd = self.ratio_sd.calc(self.rs);
+        let ratio = z_score(self.rs, mean, sd);
+
+        let roc = match self.prev_ratio {
+            Some(prev) => F::cast(100) * (ratio / prev - F::one()),
+            None => F::zero(),
+        };
+        self.prev_ratio = Some(ratio);
+
+        let mean = self.momentum_mean.calc(roc);
+        let sd = self.momentum_sd.calc(roc);
+        RelativeRotationOutput {
+            ratio,
+            momentum: z_score(roc, mean, sd),
+        }
+    }
+}
+
+impl<F: Float> Reset for RelativeRotation<F> {
+    fn reset(&mut self) {
+        self.count = 0;
+        self.rs = F::cast(100);
+        self.prev = None;
+        self.prev_ratio = None;
+        self.returns.clear();
+        self.sums = (F::zero(), F::zero(), F::zero(), F::zero());
+        self.ratio_mean.reset();
+        self.ratio_sd.reset();
+        self.momentum_mean.reset();
+        self.momentum_sd.reset();
+    }
+}
+
+impl<F: Float> Warmup for RelativeRotation<F> {
+    fn warmup_period(&self) -> u32 {
+        // the rate of change starts one period after the ratio
+        self.beta_n.unwrap_or(0) + 2 * self.n
+    }
+
+    fn is_ready(&self) -> bool {
+        self.count >= self.warmup_period()
+    }
+}
+
+impl Default for RelativeRotation {
+    fn default() -> Self {
+        Self::new(14).unwrap()
+    }
+}
+
+impl<F: Float> fmt::Display for RelativeRotation<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.beta_n {
+            Some(beta_n) => write!(f, "RRG({}, {})", self.n, beta_n),
+            None => write!(f, "RRG({})", self.n),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_new() {
+        assert!(RelativeRotation::new(0).is_err());
+        assert!(RelativeRotation::new(1).is_ok());
+        assert!(RelativeRotation::with_beta(10, 1).is_err());
+        assert!(RelativeRotation::with_beta(0, 10).is_err());
+        assert!(RelativeRotation::with_beta(10, 2).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut rrg = RelativeRotation::new(2).unwrap();
+
+        let out = rrg.next(&(10.0, 10.0));
+        assert_eq!((out.ratio, out.momentum), (100.0, 100.0));
+
+        // RS 110, mean 105, SD 5
+        let out = rrg.next(&(11.0, 10.0));
+        assert_eq!(out.ratio, 101.0);
+        assert_eq!(round(out.momentum), 101.0);
+        assert_eq!(out.quadrant(), RotationQuadrant::Leading);
+
+        let out = rrg.next(&(11.0, 11.0));
+        assert_eq!(rrg.relative_strength(), 100.0);
+        assert_eq!(out.ratio, 99.0);
+        assert_eq!(round(out.momentum), 99.0);
+        assert_eq!(out.quadrant(), RotationQuadrant::Lagging);
+        assert!(!rrg.is_ready());
+
+        let out = rrg.next(&(11.0, 11.0));
+        assert_eq!(out.ratio, 100.0);
+        assert_eq!(round(out.momentum), 101.0);
+        assert!(rrg.is_ready());
+    }
+
+    #[test]
+    fn test_quadrant() {
+        let quadrant = |ratio, momentum| RelativeRotationOutput { ratio, momentum }.quadrant();
+        assert_eq!(quadrant(102.0, 99.5), RotationQuadrant::Weakening);
+        assert_eq!(quadrant(99.0, 100.0), RotationQuadrant::Improving);
+        assert_eq!(quadrant(100.0, 100.0), RotationQuadrant::Leading);
+    }
+
+    #[test]
+    fn test_beta() {
+        let mut rrg = RelativeRotation::with_beta(3, 5).unwrap();
+        assert_eq!(rrg.warmup_period(), 11);
+
+        // the price moves half as much as the benchmark
+        let mut price = 100.0;
+        let mut benchmark = 100.0;
+        rrg.next(&(price, benchmark));
+        assert_eq!(rrg.beta(), 1.0);
+        let mut rs = Vec::new();
+        for &ret in &[0.1, -0.1, 0.1, 0.05, -0.2, 0.03] {
+            price *= 1.0 + ret / 2.0;
+            benchmark *= 1.0 + ret;
+            rrg.next(&(price, benchmark));
+            rs.push(round(rrg.relative_strength()));
+        }
+        assert_eq!(round(rrg.beta()), 0.5);
+        // after two returns the beta is known and the relative strength stays flat
+        assert!(rs[1..].iter().all(|&value| value == rs[1]));
+        assert_ne!(rs[1], 100.0);
+
+        // without the adjustment the price lags the rising benchmark
+        let mut plain = RelativeRotation::new(3).unwrap();
+        plain.next(&(100.0, 100.0));
+        plain.next(&(105.0, 110.0));
+        assert_eq!(round(plain.relative_strength()), 95.455);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut rrg = RelativeRotation::with_beta(2, 3).unwrap();
+        rrg.next(&(10.0, 20.0));
+        rrg.next(&(12.0, 21.0));
+        rrg.next(&(11.0, 23.0));
+
+        rrg.reset();
+        assert_eq!(rrg.relative_strength(), 100.0);
+        assert_eq!(rrg.beta(), 1.0);
+        let out = rrg.next(&(5.0, 7.0));
+        assert_eq!((out.ratio, out.momentum), (100.0, 100.0));
+        assert!(!rrg.is_ready());
+    }
+
+    #[test]
+    fn test_default() {
+        RelativeRotation::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", RelativeRotation::new(10).unwrap()), "RRG(10)");
+        assert_eq!(
+            format!("{}", RelativeRotation::with_beta(10, 52).unwrap()),
+            "RRG(10, 52)"
+        );
+    }
+}
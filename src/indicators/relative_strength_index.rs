@@ -1,8 +1,8 @@
-use std::fmt;
+use core::fmt;
 
 use crate::errors::*;
 use crate::indicators::ExponentialMovingAverage as Ema;
-use crate::{Calculate, Close, Next, Reset};
+use crate::{Calculate, Close, Decay, Float, Next, Peek, PeekNext, Reset, Warmup};
 
 /// The relative strength index (RSI).
 ///
@@ -67,74 +67,127 @@ use crate::{Calculate, Close, Next, Reset};
 /// * [RSI (Investopedia)](http://www.investopedia.com/terms/r/rsi.asp)
 ///
 #[derive(Debug, Clone)]
-pub struct RelativeStrengthIndex {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RelativeStrengthIndex<F = f64> {
     n: u32,
-    up_ema_indicator: Ema,
-    down_ema_indicator: Ema,
-    prev_val: f64,
-    is_new: bool,
+    up_ema_indicator: Ema<F>,
+    down_ema_indicator: Ema<F>,
+    prev_val: F,
+    count: u32,
 }
 
 impl RelativeStrengthIndex {
     pub fn new(n: u32) -> Result<Self> {
+        Self::with_type(n)
+    }
+}
+
+impl<F: Float> RelativeStrengthIndex<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(n: u32) -> Result<Self> {
         let rsi = Self {
-            n: n,
-            up_ema_indicator: Ema::new(n)?,
-            down_ema_indicator: Ema::new(n)?,
-            prev_val: 0.0,
-            is_new: true,
+            n,
+            up_ema_indicator: Ema::with_type(n)?,
+            down_ema_indicator: Ema::with_type(n)?,
+            prev_val: F::zero(),
+            count: 0,
         };
         Ok(rsi)
     }
-}
 
-impl Calculate for RelativeStrengthIndex {
-    fn calc(&mut self, input: f64) -> f64 {
-        let mut up = 0.0;
-        let mut down = 0.0;
-
-        if self.is_new {
-            self.is_new = false;
+    // Upward and downward move to the input.
+    fn moves(&self, input: F) -> (F, F) {
+        if self.count == 0 {
             // Initialize with some small seed numbers to avoid division by zero
-            up = 0.1;
-            down = 0.1;
+            (F::cast(0.1), F::cast(0.1))
+        } else if input > self.prev_val {
+            (input - self.prev_val, F::zero())
         } else {
-            if input > self.prev_val {
-                up = input - self.prev_val;
-            } else {
-                down = self.prev_val - input;
-            }
+            (F::zero(), self.prev_val - input)
         }
+    }
+}
 
+// A decay over a long gap can drive both averages to 0, which leaves the market without a
+// direction, like the seed of the first input.
+fn ratio<F: Float>(up_ema: F, down_ema: F) -> F {
+    let total = up_ema + down_ema;
+    if total == F::zero() {
+        F::cast(50.0)
+    } else {
+        up_ema / total * F::cast(100.0)
+    }
+}
+
+impl<F: Float> Calculate<F> for RelativeStrengthIndex<F> {
+    type Output = F;
+
+    fn calc(&mut self, input: F) -> F {
+        let (up, down) = self.moves(input);
+        self.count = self.count.saturating_add(1);
         self.prev_val = input;
         let up_ema = self.up_ema_indicator.calc(up);
         let down_ema = self.down_ema_indicator.calc(down);
-        100.0 * up_ema / (up_ema + down_ema)
+        ratio(up_ema, down_ema)
     }
 }
 
-impl<T: Close> Next<T> for RelativeStrengthIndex {
-    fn next(&mut self, input: &T) -> f64 {
+impl<F: Float> Peek<F> for RelativeStrengthIndex<F> {
+    fn peek(&self, input: F) -> F {
+        let (up, down) = self.moves(input);
+        let up_ema = self.up_ema_indicator.peek(up);
+        let down_ema = self.down_ema_indicator.peek(down);
+        ratio(up_ema, down_ema)
+    }
+}
+
+impl<F: Float, T: Close<F>> Next<T> for RelativeStrengthIndex<F> {
+    type Output = F;
+
+    fn next(&mut self, input: &T) -> F {
         self.calc(input.close())
     }
 }
 
-impl Reset for RelativeStrengthIndex {
+impl<F: Float, T: Close<F>> PeekNext<T> for RelativeStrengthIndex<F> {
+    fn peek_next(&self, input: &T) -> F {
+        self.peek(input.close())
+    }
+}
+
+impl<F: Float> Reset for RelativeStrengthIndex<F> {
     fn reset(&mut self) {
-        self.is_new = true;
-        self.prev_val = 0.0;
+        self.count = 0;
+        self.prev_val = F::zero();
         self.up_ema_indicator.reset();
         self.down_ema_indicator.reset();
     }
 }
 
+impl<F: Float> Decay for RelativeStrengthIndex<F> {
+    fn decay(&mut self, periods: u32) {
+        self.up_ema_indicator.decay(periods);
+        self.down_ema_indicator.decay(periods);
+    }
+}
+
+impl<F: Float> Warmup for RelativeStrengthIndex<F> {
+    fn warmup_period(&self) -> u32 {
+        self.n + 1
+    }
+
+    fn is_ready(&self) -> bool {
+        self.count > self.n
+    }
+}
+
 impl Default for RelativeStrengthIndex {
     fn default() -> Self {
         Self::new(14).unwrap()
     }
 }
 
-impl fmt::Display for RelativeStrengthIndex {
+impl<F: Float> fmt::Display for RelativeStrengthIndex<F> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "RSI({})", self.n)
     }
@@ -162,6 +215,19 @@ mod tests {
         assert_eq!(rsi.calc(9.5).round(), 16.0);
     }
 
+    #[test]
+    fn test_peek() {
+        let mut indicator = RelativeStrengthIndex::new(3).unwrap();
+        for &x in &[10.0, 10.5, 10.0, 9.5, 11.0] {
+            let peeked = indicator.peek(x);
+            assert_eq!(
+                indicator.peek_next(&Bar::new().close(x)),
+                indicator.clone().next(&Bar::new().close(x))
+            );
+            assert_eq!(indicator.calc(x), peeked);
+        }
+    }
+
     #[test]
     fn test_reset() {
         let mut rsi = RelativeStrengthIndex::new(3).unwrap();
@@ -0,0 +1,360 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::errors::*;
+use crate::indicators::{ExponentialMovingAverage, SimpleMovingAverage};
+use crate::{Calculate, Close, Finite, Float, Next, Reset, Warmup};
+
+/// Type of the moving averages in a [Ribbon](struct.Ribbon.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RibbonAverage {
+    Simple,
+    Exponential,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(deserialize = "F: Float + serde::Deserialize<'de>"))
+)]
+enum Average<F> {
+    Simple(SimpleMovingAverage<F>),
+    Exponential(ExponentialMovingAverage<F>),
+}
+
+impl<F: Float> Average<F> {
+    fn calc(&mut self, input: F) -> F {
+        match self {
+            Average::Simple(sma) => sma.calc(input),
+            Average::Exponential(ema) => ema.calc(input),
+        }
+    }
+
+    fn reset(&mut self) {
+        match self {
+            Average::Simple(sma) => sma.reset(),
+            Average::Exponential(ema) => ema.reset(),
+        }
+    }
+
+    fn warmup_period(&self) -> u32 {
+        match self {
+            Average::Simple(sma) => sma.warmup_period(),
+            Average::Exponential(ema) => ema.warmup_period(),
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        match self {
+            Average::Simple(sma) => sma.is_ready(),
+            Average::Exponential(ema) => ema.is_ready(),
+        }
+    }
+}
+
+/// Moving average ribbon.
+///
+/// A set of moving averages with stepped lengths (e.g. 10, 20, ..., 100). Besides the
+/// values of all averages it reports how well the ribbon is ordered and how wide it is:
+/// a fully ordered, expanding ribbon indicates a strong trend, while a tangled, compressed
+/// ribbon indicates consolidation.
+///
+/// # Output
+///
+/// * _values_ - values of the averages, from the shortest to the longest
+/// * _alignment_ - between -1 and 1: the share of adjacent pairs where the shorter average
+///   is above the longer one minus the share where it is below. 1 means a fully bullish
+///   ordering, -1 a fully bearish one.
+/// * _width_ - distance between the highest and the lowest average in percent of their mean
+///
+/// # Parameters
+///
+/// * _from_ - length of the shortest average (integer greater than 0). Default is 10.
+/// * _to_ - upper bound for the length of the longest average (not less than _from_).
+///   Default is 100.
+/// * _step_ - difference between lengths of adjacent averages (integer greater than 0).
+///   Default is 10.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::Ribbon;
+/// use ta::Calculate;
+///
+/// let mut ribbon = Ribbon::new(2, 6, 2).unwrap();
+/// assert_eq!(ribbon.lengths(), vec![2, 4, 6]);
+///
+/// let mut out = ribbon.calc(1.0);
+/// for i in 2..20 {
+///     out = ribbon.calc(i as f64);
+/// }
+/// assert_eq!(out.values.len(), 3);
+/// assert_eq!(out.alignment, 1.0);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(deserialize = "F: Float + serde::Deserialize<'de>"))
+)]
+pub struct Ribbon<F = f64> {
+    from: u32,
+    to: u32,
+    step: u32,
+    kind: RibbonAverage,
+    averages: Vec<Average<F>>,
+}
+
+/// Output of [Ribbon](struct.Ribbon.html).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RibbonOutput<F = f64> {
+    pub values: Vec<F>,
+    pub alignment: F,
+    pub width: F,
+}
+
+impl<F: Finite> Finite for RibbonOutput<F> {
+    fn is_finite(&self) -> bool {
+        self.values.iter().all(Finite::is_finite)
+            && self.alignment.is_finite()
+            && self.width.is_finite()
+    }
+}
+
+impl Ribbon {
+    /// Creates a ribbon of exponential moving averages.
+    pub fn new(from: u32, to: u32, step: u32) -> Result<Self> {
+        Self::with_type(from, to, step)
+    }
+
+    /// Creates a ribbon of moving averages of the given type.
+    pub fn with_average(kind: RibbonAverage, from: u32, to: u32, step: u32) -> Result<Self> {
+        Self::create(kind, from, to, step)
+    }
+}
+
+impl<F: Float> Ribbon<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(from: u32, to: u32, step: u32) -> Result<Self> {
+        Self::create(RibbonAverage::Exponential, from, to, step)
+    }
+
+    fn create(kind: RibbonAverage, from: u32, to: u32, step: u32) -> Result<Self> {
+        if from == 0 || step == 0 || to < from {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        let lengths: Vec<u32> = (from..=to).step_by(step as usize).collect();
+        Self::with_lengths(kind, from, to, step, &lengths)
+    }
+
+    pub(crate) fn with_lengths(
+        kind: RibbonAverage,
+        from: u32,
+        to: u32,
+        step: u32,
+        lengths: &[u32],
+    ) -> Result<Self> {
+        let averages = lengths
+            .iter()
+            .map(|&length| {
+                Ok(match kind {
+                    RibbonAverage::Simple => {
+                        Average::Simple(SimpleMovingAverage::with_type(length)?)
+                    }
+                    RibbonAverage::Exponential => {
+                        Average::Exponential(ExponentialMovingAverage::with_type(length)?)
+                    }
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            from,
+            to,
+            step,
+            kind,
+            averages,
+        })
+    }
+
+    /// Lengths of the averages, from the shortest to the longest.
+    pub fn lengths(&self) -> Vec<u32> {
+        self.averages
+            .iter()
+            .map(|average| match average {
+                Average::Simple(sma) => sma.length(),
+                Average::Exponential(ema) => ema.length(),
+            })
+            .collect()
+    }
+}
+
+impl<F: Float> Calculate<F> for Ribbon<F> {
+    type Output = RibbonOutput<F>;
+
+    fn calc(&mut self, input: F) -> Self::Output {
+        let values: Vec<F> = self
+            .averages
+            .iter_mut()
+            .map(|average| average.calc(input))
+            .collect();
+        RibbonOutput {
+            alignment: alignment(&values),
+            width: width(&values),
+            values,
+        }
+    }
+}
+
+impl<F: Float, T: Close<F>> Next<T> for Ribbon<F> {
+    type Output = RibbonOutput<F>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.calc(input.close())
+    }
+}
+
+pub(crate) fn alignment<F: Float>(values: &[F]) -> F {
+    if values.len() < 2 {
+        return F::zero();
+    }
+    let score: F = values
+        .windows(2)
+        .map(|pair| {
+            if pair[0] > pair[1] {
+                F::one()
+            } else if pair[0] < pair[1] {
+                -F::one()
+            } else {
+                F::zero()
+            }
+        })
+        .sum();
+    score / F::cast(values.len() - 1)
+}
+
+pub(crate) fn width<F: Float>(values: &[F]) -> F {
+    let max = values.iter().cloned().fold(F::neg_infinity(), F::max);
+    let min = values.iter().cloned().fold(F::infinity(), F::min);
+    let mean = values.iter().sum::<F>() / F::cast(values.len());
+    if mean == F::zero() {
+        F::zero()
+    } else {
+        (max - min) / mean.abs() * F::cast(100.0)
+    }
+}
+
+impl<F: Float> Reset for Ribbon<F> {
+    fn reset(&mut self) {
+        for average in self.averages.iter_mut() {
+            average.reset();
+        }
+    }
+}
+
+impl<F: Float> Warmup for Ribbon<F> {
+    fn warmup_period(&self) -> u32 {
+        self.averages
+            .iter()
+            .map(Average::warmup_period)
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn is_ready(&self) -> bool {
+        self.averages.iter().all(Average::is_ready)
+    }
+}
+
+impl Default for Ribbon {
+    fn default() -> Self {
+        Self::new(10, 100, 10).unwrap()
+    }
+}
+
+impl<F: Float> fmt::Display for Ribbon<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self.kind {
+            RibbonAverage::Simple => "SMA_RIBBON",
+            RibbonAverage::Exponential => "EMA_RIBBON",
+        };
+        write!(f, "{}({}, {}, {})", name, self.from, self.to, self.step)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(Ribbon);
+
+    #[test]
+    fn test_new() {
+        assert!(Ribbon::new(0, 10, 1).is_err());
+        assert!(Ribbon::new(10, 5, 1).is_err());
+        assert!(Ribbon::new(1, 10, 0).is_err());
+        assert!(Ribbon::new(5, 5, 1).is_ok());
+    }
+
+    #[test]
+    fn test_lengths() {
+        assert_eq!(Ribbon::new(10, 35, 10).unwrap().lengths(), vec![10, 20, 30]);
+        assert_eq!(Ribbon::default().lengths().len(), 10);
+    }
+
+    #[test]
+    fn test_next() {
+        let mut ribbon = Ribbon::with_average(RibbonAverage::Simple, 1, 3, 1).unwrap();
+
+        let out = ribbon.calc(4.0);
+        assert_eq!(out.values, vec![4.0, 4.0, 4.0]);
+        assert_eq!(out.alignment, 0.0);
+        assert_eq!(out.width, 0.0);
+
+        let out = ribbon.calc(10.0);
+        assert_eq!(out.values, vec![10.0, 7.0, 7.0]);
+        assert_eq!(out.alignment, 0.5);
+        assert_eq!(out.width, 37.5);
+
+        let out = ribbon.next(&Bar::new().close(1.0));
+        assert_eq!(out.values, vec![1.0, 5.5, 5.0]);
+        assert_eq!(out.alignment, 0.0);
+    }
+
+    #[test]
+    fn test_bearish() {
+        let mut ribbon = Ribbon::new(2, 8, 2).unwrap();
+        let mut out = ribbon.calc(100.0);
+        for i in 1..30 {
+            out = ribbon.calc(100.0 - i as f64);
+        }
+        assert_eq!(out.alignment, -1.0);
+        assert!(out.width > 0.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut ribbon = Ribbon::new(1, 3, 1).unwrap();
+        ribbon.calc(4.0);
+        ribbon.calc(10.0);
+
+        ribbon.reset();
+        assert_eq!(ribbon.calc(2.0).values, vec![2.0, 2.0, 2.0]);
+    }
+
+    #[test]
+    fn test_default() {
+        Ribbon::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", Ribbon::default()), "EMA_RIBBON(10, 100, 10)");
+        let ribbon = Ribbon::with_average(RibbonAverage::Simple, 5, 20, 5).unwrap();
+        assert_eq!(format!("{}", ribbon), "SMA_RIBBON(5, 20, 5)");
+    }
+}
@@ -0,0 +1,242 @@
+use core::fmt;
+
+use crate::errors::*;
+use crate::{Calculate, Close, Finite, Float, Next, Reset, RingBuffer, Warmup};
+
+/// Round number proximity.
+///
+/// Prices cluster around round numbers, where many orders and stops are placed. This indicator
+/// measures how far the price is from the nearest round level (a multiple of _step_, e.g.
+/// 0.50 or 100) and how long it has stayed near such levels.
+///
+/// # Output
+///
+/// * _level_ - nearest round level
+/// * _distance_ - price minus the nearest level, between -_step_/2 and _step_/2
+/// * _near_ - whether the distance is within _tolerance_
+/// * _streak_ - number of consecutive periods, including the current one, spent near a level
+/// * _share_ - fraction of the last _length_ periods spent near a level
+///
+/// # Parameters
+///
+/// * _step_ - distance between round levels (greater than 0). Default is 1.0.
+/// * _tolerance_ - distance from a level which is still considered near (not less than 0).
+///   Default is 0.1.
+/// * _length_ - number of periods for _share_ (integer greater than 0). Default is 20.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::RoundNumberProximity;
+/// use ta::Calculate;
+///
+/// let mut rnp = RoundNumberProximity::new(100.0, 5.0, 4).unwrap();
+/// let out = rnp.calc(1_497.0);
+/// assert_eq!(out.level, 1_500.0);
+/// assert_eq!(out.distance, -3.0);
+/// assert!(out.near);
+///
+/// let out = rnp.calc(1_520.0);
+/// assert!(!out.near);
+/// assert_eq!(out.streak, 0);
+/// assert_eq!(out.share, 0.5);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RoundNumberProximity<F = f64> {
+    step: F,
+    tolerance: F,
+    length: u32,
+    streak: u32,
+    near_count: u32,
+    window: RingBuffer<bool>,
+}
+
+/// Output of [RoundNumberProximity](struct.RoundNumberProximity.html).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RoundNumberProximityOutput<F = f64> {
+    pub level: F,
+    pub distance: F,
+    pub near: bool,
+    pub streak: u32,
+    pub share: F,
+}
+
+impl<F: Finite> Finite for RoundNumberProximityOutput<F> {
+    fn is_finite(&self) -> bool {
+        self.level.is_finite() && self.distance.is_finite() && self.share.is_finite()
+    }
+}
+
+impl RoundNumberProximity {
+    pub fn new(step: f64, tolerance: f64, length: u32) -> Result<Self> {
+        Self::with_type(step, tolerance, length)
+    }
+}
+
+impl<F: Float> RoundNumberProximity<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(step: F, tolerance: F, length: u32) -> Result<Self> {
+        if step <= F::zero() || !step.is_finite() || tolerance < F::zero() || length == 0 {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        Ok(Self {
+            step,
+            tolerance,
+            length,
+            streak: 0,
+            near_count: 0,
+            window: RingBuffer::new(length as usize),
+        })
+    }
+
+    pub fn step(&self) -> F {
+        self.step
+    }
+
+    pub fn tolerance(&self) -> F {
+        self.tolerance
+    }
+
+    pub fn length(&self) -> u32 {
+        self.length
+    }
+}
+
+impl<F: Float> Calculate<F> for RoundNumberProximity<F> {
+    type Output = RoundNumberProximityOutput<F>;
+
+    fn calc(&mut self, input: F) -> Self::Output {
+        let level = (input / self.step).round() * self.step;
+        let distance = input - level;
+        let near = distance.abs() <= self.tolerance;
+
+        self.streak = if near {
+            self.streak.saturating_add(1)
+        } else {
+            0
+        };
+        let evicted = self.window.push(near);
+        if near {
+            self.near_count += 1;
+        }
+        if evicted == Some(true) {
+            self.near_count -= 1;
+        }
+
+        RoundNumberProximityOutput {
+            level,
+            distance,
+            near,
+            streak: self.streak,
+            share: F::cast(self.near_count) / F::cast(self.window.len()),
+        }
+    }
+}
+
+impl<F: Float, T: Close<F>> Next<T> for RoundNumberProximity<F> {
+    type Output = RoundNumberProximityOutput<F>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.calc(input.close())
+    }
+}
+
+impl<F: Float> Reset for RoundNumberProximity<F> {
+    fn reset(&mut self) {
+        self.streak = 0;
+        self.near_count = 0;
+        self.window.clear();
+    }
+}
+
+impl<F: Float> Warmup for RoundNumberProximity<F> {
+    fn warmup_period(&self) -> u32 {
+        self.length
+    }
+
+    fn is_ready(&self) -> bool {
+        self.window.is_full()
+    }
+}
+
+impl Default for RoundNumberProximity {
+    fn default() -> Self {
+        Self::new(1.0, 0.1, 20).unwrap()
+    }
+}
+
+impl<F: Float> fmt::Display for RoundNumberProximity<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "RNP({}, {}, {})", self.step, self.tolerance, self.length)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(RoundNumberProximity);
+
+    #[test]
+    fn test_new() {
+        assert!(RoundNumberProximity::new(0.0, 0.1, 10).is_err());
+        assert!(RoundNumberProximity::new(-1.0, 0.1, 10).is_err());
+        assert!(RoundNumberProximity::new(1.0, -0.1, 10).is_err());
+        assert!(RoundNumberProximity::new(1.0, 0.1, 0).is_err());
+        assert!(RoundNumberProximity::new(0.5, 0.0, 1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut rnp = RoundNumberProximity::new(0.5, 0.05, 3).unwrap();
+
+        let out = rnp.calc(10.03);
+        assert_eq!(out.level, 10.0);
+        assert_eq!(round(out.distance), 0.03);
+        assert_eq!((out.near, out.streak, out.share), (true, 1, 1.0));
+
+        let out = rnp.calc(10.48);
+        assert_eq!(out.level, 10.5);
+        assert_eq!(round(out.distance), -0.02);
+        assert_eq!((out.near, out.streak, out.share), (true, 2, 1.0));
+
+        let out = rnp.next(&Bar::new().close(10.3));
+        assert_eq!(out.level, 10.5);
+        assert_eq!((out.near, out.streak), (false, 0));
+        assert_eq!(round(out.share), 0.667);
+
+        let out = rnp.calc(10.2);
+        assert_eq!(out.level, 10.0);
+        assert_eq!((out.near, out.streak), (false, 0));
+        assert_eq!(round(out.share), 0.333);
+
+        let out = rnp.calc(11.0);
+        assert_eq!((out.near, out.streak), (true, 1));
+        assert_eq!(round(out.share), 0.333);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut rnp = RoundNumberProximity::new(1.0, 0.1, 5).unwrap();
+        rnp.calc(1.0);
+        rnp.calc(2.0);
+
+        rnp.reset();
+        let out = rnp.calc(2.5);
+        assert_eq!((out.near, out.streak, out.share), (false, 0, 0.0));
+    }
+
+    #[test]
+    fn test_default() {
+        RoundNumberProximity::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let rnp = RoundNumberProximity::new(100.0, 2.5, 20).unwrap();
+        assert_eq!(format!("{}", rnp), "RNP(100, 2.5, 20)");
+    }
+}
@@ -0,0 +1,302 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::errors::*;
+use crate::{Calculate, Close, Finite, Float, Next, Reset, RingBuffer, Warmup};
+
+/// Run Length statistics.
+///
+/// Distribution of the lengths of the runs of consecutive up and down moves over the last
+/// _length_ price changes, and its entropy. Persistent, trending prices move in long runs of
+/// few different lengths, while noisy prices alternate in short ones; a change in the
+/// statistics hints at a change of regime, e.g. of tick-by-tick direction persistence.
+///
+/// Unchanged prices continue the run of the previous move, like the tick rule; before the
+/// first move they count as up moves. Runs cut by the start of the window count with the
+/// part inside of it.
+///
+/// # Output
+///
+/// * _mean_up_, _mean_down_ - mean length of the up and down runs, 0 without such runs
+/// * _longest_ - length of the longest run
+/// * _run_entropy_ - Shannon entropy of the run lengths in bits, 0 when all runs are
+///   equally long
+/// * _direction_entropy_ - Shannon entropy of the share of up moves in bits, from 0 with
+///   all moves in one direction to 1 with as many up as down moves
+///
+/// # Parameters
+///
+/// * _length_ - number of price changes (integer greater than 0). Default is 20.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::RunLength;
+/// use ta::Calculate;
+///
+/// let mut runs = RunLength::new(6).unwrap();
+/// let mut out = runs.calc(10.0);
+/// // up, up, down, up, up, down
+/// for &close in &[11.0, 12.0, 11.0, 12.0, 13.0, 12.0] {
+///     out = runs.calc(close);
+/// }
+/// assert_eq!((out.mean_up, out.mean_down, out.longest), (2.0, 1.0, 2));
+/// // as many runs of 1 as of 2 moves
+/// assert_eq!(out.run_entropy, 1.0);
+/// // twice as many up as down moves
+/// assert_eq!((out.direction_entropy * 1000.0).round(), 918.0);
+/// ```
+///
+/// # Links
+///
+/// * [Entropy (information theory), Wikipedia](https://en.wikipedia.org/wiki/Entropy_(information_theory))
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RunLength<F = f64> {
+    length: u32,
+    prev: Option<F>,
+    // whether each price change of the window continued or started an up move
+    directions: RingBuffer<bool>,
+    // number of runs by length, reused by every calculation
+    #[cfg_attr(feature = "serde", serde(skip))]
+    counts: Vec<u32>,
+}
+
+/// Output of [RunLength](struct.RunLength.html).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RunLengthOutput<F = f64> {
+    pub mean_up: F,
+    pub mean_down: F,
+    pub longest: u32,
+    pub run_entropy: F,
+    pub direction_entropy: F,
+}
+
+impl<F: Finite> Finite for RunLengthOutput<F> {
+    fn is_finite(&self) -> bool {
+        self.mean_up.is_finite()
+            && self.mean_down.is_finite()
+            && self.run_entropy.is_finite()
+            && self.direction_entropy.is_finite()
+    }
+}
+
+// -p * log2(p)
+fn information<F: Float>(count: u32, total: u32) -> F {
+    if count == 0 {
+        return F::zero();
+    }
+    let p = F::cast(count) / F::cast(total);
+    -p * p.log2()
+}
+
+impl RunLength {
+    pub fn new(length: u32) -> Result<Self> {
+        Self::with_type(length)
+    }
+}
+
+impl<F: Float> RunLength<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(length: u32) -> Result<Self> {
+        if length == 0 {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        Ok(Self {
+            length,
+            prev: None,
+            directions: RingBuffer::new(length as usize),
+            counts: Vec::new(),
+        })
+    }
+
+    pub fn length(&self) -> u32 {
+        self.length
+    }
+
+    fn stats(&mut self) -> RunLengthOutput<F> {
+        self.counts.clear();
+        self.counts.resize(self.length as usize + 1, 0);
+
+        // (runs, moves) of down and up runs
+        let mut totals = [(0, 0), (0, 0)];
+        let mut longest = 0;
+        let mut run: Option<(bool, u32)> = None;
+        let ends = self
+            .directions
+            .iter()
+            .map(Some)
+            .chain(core::iter::once(None));
+        for up in ends {
+            match (run, up) {
+                (Some((direction, len)), Some(&up)) if direction == up => {
+                    run = Some((direction, len + 1));
+                    continue;
+                }
+                (Some((direction, len)), _) => {
+                    self.counts[len as usize] += 1;
+                    longest = longest.max(len);
+                    let total = &mut totals[direction as usize];
+                    *total = (total.0 + 1, total.1 + len);
+                }
+                (None, _) => {}
+            }
+            run = up.map(|&up| (up, 1));
+        }
+        let [(down_runs, down_moves), (up_runs, up_moves)] = totals;
+
+        let mean = |moves: u32, runs: u32| {
+            if runs == 0 {
+                F::zero()
+            } else {
+                F::cast(moves) / F::cast(runs)
+            }
+        };
+        let runs = up_runs + down_runs;
+        let run_entropy = self
+            .counts
+            .iter()
+            .map(|&count| information::<F>(count, runs))
+            .fold(F::zero(), |sum, x| sum + x);
+        let moves = up_moves + down_moves;
+        let direction_entropy =
+            information::<F>(up_moves, moves) + information::<F>(down_moves, moves);
+
+        RunLengthOutput {
+            mean_up: mean(up_moves, up_runs),
+            mean_down: mean(down_moves, down_runs),
+            longest,
+            run_entropy,
+            direction_entropy,
+        }
+    }
+}
+
+impl<F: Float> Calculate<F> for RunLength<F> {
+    type Output = RunLengthOutput<F>;
+
+    fn calc(&mut self, input: F) -> Self::Output {
+        if let Some(prev) = self.prev {
+            let up = if input > prev {
+                true
+            } else if input < prev {
+                false
+            } else {
+                self.directions.newest().copied().unwrap_or(true)
+            };
+            self.directions.push(up);
+        }
+        self.prev = Some(input);
+        self.stats()
+    }
+}
+
+impl<F: Float, T: Close<F>> Next<T> for RunLength<F> {
+    type Output = RunLengthOutput<F>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.calc(input.close())
+    }
+}
+
+impl<F: Float> Reset for RunLength<F> {
+    fn reset(&mut self) {
+        self.prev = None;
+        self.directions.clear();
+    }
+}
+
+impl<F: Float> Warmup for RunLength<F> {
+    fn warmup_period(&self) -> u32 {
+        self.length + 1
+    }
+
+    fn is_ready(&self) -> bool {
+        self.directions.is_full()
+    }
+}
+
+impl Default for RunLength {
+    fn default() -> Self {
+        Self::new(20).unwrap()
+    }
+}
+
+impl<F: Float> fmt::Display for RunLength<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "RUNS({})", self.length)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(RunLength);
+
+    #[test]
+    fn test_new() {
+        assert!(RunLength::new(0).is_err());
+        assert!(RunLength::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_trend() {
+        let mut runs = RunLength::new(4).unwrap();
+        let mut out = runs.calc(1.0);
+        assert_eq!(out.longest, 0);
+        for i in 2..=5 {
+            out = runs.next(&Bar::new().close(i as f64));
+        }
+        assert_eq!((out.mean_up, out.mean_down, out.longest), (4.0, 0.0, 4));
+        assert_eq!((out.run_entropy, out.direction_entropy), (0.0, 0.0));
+        assert!(runs.is_ready());
+    }
+
+    #[test]
+    fn test_runs() {
+        let mut runs = RunLength::new(6).unwrap();
+        let mut out = runs.calc(10.0);
+        // down, unchanged, down, up, down, down
+        for &close in &[9.0, 9.0, 8.0, 9.0, 8.0, 7.0] {
+            out = runs.calc(close);
+        }
+        assert_eq!((out.mean_up, out.mean_down, out.longest), (1.0, 2.5, 3));
+        // runs of 3, 1 and 2 moves
+        assert_eq!(round(out.run_entropy), 1.585);
+        assert_eq!(round(out.direction_entropy), 0.65);
+
+        // the first down run leaves the window
+        out = runs.calc(8.0);
+        assert_eq!((out.mean_up, out.mean_down, out.longest), (1.0, 2.0, 2));
+    }
+
+    #[test]
+    fn test_alternating() {
+        let mut runs = RunLength::new(10).unwrap();
+        let mut out = runs.calc(1.0);
+        for i in 0..20 {
+            out = runs.calc((i % 2) as f64 * 2.0);
+        }
+        assert_eq!((out.mean_up, out.mean_down, out.longest), (1.0, 1.0, 1));
+        assert_eq!((out.run_entropy, out.direction_entropy), (0.0, 1.0));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut runs = RunLength::new(3).unwrap();
+        runs.calc(1.0);
+        runs.calc(0.0);
+        runs.reset();
+        runs.calc(5.0);
+        assert_eq!(runs.calc(5.0).mean_up, 1.0);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", RunLength::new(9).unwrap()), "RUNS(9)");
+    }
+}
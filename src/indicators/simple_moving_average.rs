@@ -1,7 +1,12 @@
-use std::fmt;
+use core::fmt;
 
 use crate::errors::*;
-use crate::{Calculate, Close, Next, Reset};
+use crate::helpers::ConstLength;
+use crate::{
+    Calculate, Close, Next, Number, Peek, PeekNext, Replace, Reset, Storage, UpdateLast, Warmup,
+    Window,
+};
+use alloc::vec::Vec;
 
 /// Simple moving average (SMA).
 ///
@@ -37,25 +42,34 @@ use crate::{Calculate, Close, Next, Reset};
 /// * [Simple Moving Average, Wikipedia](https://en.wikipedia.org/wiki/Moving_average#Simple_moving_average)
 ///
 #[derive(Debug, Clone)]
-pub struct SimpleMovingAverage {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "F: serde::Serialize, S: Storage<F>",
+        deserialize = "F: Number + serde::Deserialize<'de>, S: Storage<F>"
+    ))
+)]
+pub struct SimpleMovingAverage<F = f64, S = Vec<F>> {
     n: u32,
-    index: usize,
-    count: u32,
-    sum: f64,
-    vec: Vec<f64>,
+    window: Window<F, S>,
 }
 
 impl SimpleMovingAverage {
     pub fn new(n: u32) -> Result<Self> {
+        Self::with_type(n)
+    }
+}
+
+impl<F: Number> SimpleMovingAverage<F> {
+    /// Like [new](#method.new), for values of any number type `F`.
+    pub fn with_type(n: u32) -> Result<Self> {
         match n {
             0 => Err(Error::from_kind(ErrorKind::InvalidParameter)),
             _ => {
                 let indicator = Self {
-                    n: n,
-                    index: 0,
-                    count: 0,
-                    sum: 0.0,
-                    vec: vec![0.0; n as usize],
+                    n,
+                    window: Window::with_sum(n as usize),
                 };
                 Ok(indicator)
             }
@@ -63,36 +77,81 @@ impl SimpleMovingAverage {
     }
 }
 
-impl Calculate for SimpleMovingAverage {
-    fn calc(&mut self, input: f64) -> f64 {
-        self.index = (self.index + 1) % (self.n as usize);
+impl<F: Number, S: Storage<F>> SimpleMovingAverage<F, S> {
+    pub fn length(&self) -> u32 {
+        self.n
+    }
+
+    fn sum(&self) -> F {
+        self.window.sum().unwrap_or_else(F::zero)
+    }
+
+    fn mean(&self) -> F {
+        self.window.mean().unwrap_or_else(F::zero)
+    }
+}
+
+impl<F: Number, S: Storage<F>> Calculate<F> for SimpleMovingAverage<F, S> {
+    type Output = F;
 
-        let old_val = self.vec[self.index];
-        self.vec[self.index] = input;
+    fn calc(&mut self, input: F) -> F {
+        self.window.push(input);
+        self.mean()
+    }
+}
 
-        if self.count < self.n {
-            self.count += 1;
+impl<F: Number, S: Storage<F>> UpdateLast<F> for SimpleMovingAverage<F, S> {
+    fn update_last(&mut self, input: F) -> F {
+        if self.window.replace_newest(input).is_none() {
+            return self.calc(input);
         }
+        self.mean()
+    }
+}
 
-        self.sum = self.sum - old_val + input;
-        self.sum / (self.count as f64)
+impl<F: Number, S: Storage<F>> Peek<F> for SimpleMovingAverage<F, S> {
+    fn peek(&self, input: F) -> F {
+        let (old_val, count) = match self.window.next_evicted() {
+            Some(&old_val) => (old_val, self.window.len()),
+            None => (F::zero(), self.window.len() + 1),
+        };
+        (self.sum() - old_val + input) / F::cast(count)
     }
 }
 
-impl<T: Close> Next<T> for SimpleMovingAverage {
-    fn next(&mut self, input: &T) -> f64 {
+impl<F: Number, S: Storage<F>, T: Close<F>> Next<T> for SimpleMovingAverage<F, S> {
+    type Output = F;
+
+    fn next(&mut self, input: &T) -> F {
         self.calc(input.close())
     }
 }
 
-impl Reset for SimpleMovingAverage {
+impl<F: Number, S: Storage<F>, T: Close<F>> Replace<T> for SimpleMovingAverage<F, S> {
+    fn replace(&mut self, input: &T) -> F {
+        self.update_last(input.close())
+    }
+}
+
+impl<F: Number, S: Storage<F>, T: Close<F>> PeekNext<T> for SimpleMovingAverage<F, S> {
+    fn peek_next(&self, input: &T) -> F {
+        self.peek(input.close())
+    }
+}
+
+impl<F: Number, S: Storage<F>> Reset for SimpleMovingAverage<F, S> {
     fn reset(&mut self) {
-        self.index = 0;
-        self.count = 0;
-        self.sum = 0.0;
-        for i in 0..(self.n as usize) {
-            self.vec[i] = 0.0;
-        }
+        self.window.clear();
+    }
+}
+
+impl<F: Number, S: Storage<F>> Warmup for SimpleMovingAverage<F, S> {
+    fn warmup_period(&self) -> u32 {
+        self.n
+    }
+
+    fn is_ready(&self) -> bool {
+        self.window.is_full()
     }
 }
 
@@ -102,12 +161,145 @@ impl Default for SimpleMovingAverage {
     }
 }
 
-impl fmt::Display for SimpleMovingAverage {
+impl<F: Number, S: Storage<F>> fmt::Display for SimpleMovingAverage<F, S> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "SMA({})", self.n)
     }
 }
 
+/// [SimpleMovingAverage](struct.SimpleMovingAverage.html) over a window of _N_ values fixed at
+/// compile time.
+///
+/// The window is stored inline in an array instead of a heap allocated vector, for
+/// latency-critical paths and targets without an allocator. Outputs are the same as of
+/// `SimpleMovingAverage::new(N)`.
+///
+/// # Parameters
+///
+/// * _N_ - number of periods (greater than 0, `default()` fails to compile otherwise)
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::SimpleMovingAverageConst;
+/// use ta::Calculate;
+///
+/// let mut sma = SimpleMovingAverageConst::<3>::new().unwrap();
+/// assert_eq!(sma.calc(10.0), 10.0);
+/// assert_eq!(sma.calc(11.0), 10.5);
+/// assert_eq!(sma.calc(12.0), 11.0);
+/// assert_eq!(sma.calc(13.0), 12.0);
+/// ```
+///
+/// ```compile_fail
+/// use ta::indicators::SimpleMovingAverageConst;
+///
+/// let sma = SimpleMovingAverageConst::<0>::default();
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        transparent,
+        bound(
+            serialize = "F: Number + serde::Serialize",
+            deserialize = "F: Number + serde::Deserialize<'de>"
+        )
+    )
+)]
+pub struct SimpleMovingAverageConst<const N: usize, F = f64> {
+    inner: SimpleMovingAverage<F, [F; N]>,
+}
+
+impl<const N: usize, F: Number> SimpleMovingAverageConst<N, F> {
+    pub fn new() -> Result<Self> {
+        if N == 0 {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+
+        Ok(Self {
+            inner: SimpleMovingAverage {
+                n: N as u32,
+                window: Window::inline_with_sum(),
+            },
+        })
+    }
+
+    pub fn length(&self) -> u32 {
+        N as u32
+    }
+}
+
+impl<const N: usize, F: Number> Calculate<F> for SimpleMovingAverageConst<N, F> {
+    type Output = F;
+
+    fn calc(&mut self, input: F) -> F {
+        self.inner.calc(input)
+    }
+}
+
+impl<const N: usize, F: Number> UpdateLast<F> for SimpleMovingAverageConst<N, F> {
+    fn update_last(&mut self, input: F) -> F {
+        self.inner.update_last(input)
+    }
+}
+
+impl<const N: usize, F: Number> Peek<F> for SimpleMovingAverageConst<N, F> {
+    fn peek(&self, input: F) -> F {
+        self.inner.peek(input)
+    }
+}
+
+impl<const N: usize, F: Number, T: Close<F>> Next<T> for SimpleMovingAverageConst<N, F> {
+    type Output = F;
+
+    fn next(&mut self, input: &T) -> F {
+        self.inner.next(input)
+    }
+}
+
+impl<const N: usize, F: Number, T: Close<F>> Replace<T> for SimpleMovingAverageConst<N, F> {
+    fn replace(&mut self, input: &T) -> F {
+        self.inner.replace(input)
+    }
+}
+
+impl<const N: usize, F: Number, T: Close<F>> PeekNext<T> for SimpleMovingAverageConst<N, F> {
+    fn peek_next(&self, input: &T) -> F {
+        self.inner.peek_next(input)
+    }
+}
+
+impl<const N: usize, F: Number> Reset for SimpleMovingAverageConst<N, F> {
+    fn reset(&mut self) {
+        self.inner.reset();
+    }
+}
+
+impl<const N: usize, F: Number> Warmup for SimpleMovingAverageConst<N, F> {
+    fn warmup_period(&self) -> u32 {
+        self.inner.warmup_period()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.inner.is_ready()
+    }
+}
+
+impl<const N: usize, F: Number> Default for SimpleMovingAverageConst<N, F> {
+    fn default() -> Self {
+        let () = ConstLength::<N>::NONZERO;
+        Self::new().unwrap()
+    }
+}
+
+impl<const N: usize, F: Number> fmt::Display for SimpleMovingAverageConst<N, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -115,6 +307,57 @@ mod tests {
 
     test_indicator!(SimpleMovingAverage);
 
+    mod constant {
+        use super::*;
+
+        type Sma4 = SimpleMovingAverageConst<4>;
+
+        test_indicator!(Sma4);
+
+        #[test]
+        fn test_const() {
+            assert!(SimpleMovingAverageConst::<0>::new().is_err());
+
+            let mut heap = SimpleMovingAverage::new(4).unwrap();
+            let mut stack = Sma4::default();
+            for &x in &[4.0, 5.0, 6.0, 6.0, 6.0, 6.0, 2.0, -3.5, 10.0] {
+                assert_eq!(stack.calc(x), heap.calc(x));
+            }
+            assert!(stack.is_ready());
+            assert_eq!(stack.length(), 4);
+
+            stack.reset();
+            assert_eq!(stack.calc(99.0), 99.0);
+            assert_eq!(format!("{}", stack), "SMA(4)");
+        }
+
+        #[cfg(feature = "serde")]
+        #[test]
+        fn test_const_serde() {
+            let mut sma = Sma4::default();
+            let mut heap = SimpleMovingAverage::new(4).unwrap();
+            for &x in &[4.0, 5.0, 6.0] {
+                sma.calc(x);
+                heap.calc(x);
+            }
+
+            // stored like the average on the heap
+            let json = serde_json::to_string(&sma).unwrap();
+            assert_eq!(json, serde_json::to_string(&heap).unwrap());
+            let mut restored: Sma4 = serde_json::from_str(&json).unwrap();
+            assert_eq!(restored.calc(9.0), sma.calc(9.0));
+            assert!(serde_json::from_str::<SimpleMovingAverageConst<5>>(&json).is_err());
+
+            for json in &[
+                r#"{"n":4,"window":{"values":{"capacity":4,"head":0,"values":[4.0,5.0]},"sum":19.0}}"#,
+                r#"{"n":4,"window":{"values":{"capacity":4,"head":2,"values":[4.0,5.0]},"sum":9.0}}"#,
+                r#"{"n":4,"window":{"values":{"capacity":4,"head":0,"values":[1.0,1.0,1.0,1.0,1.0]},"sum":5.0}}"#,
+            ] {
+                assert!(serde_json::from_str::<Sma4>(json).is_err());
+            }
+        }
+    }
+
     #[test]
     fn test_new() {
         assert!(SimpleMovingAverage::new(0).is_err());
@@ -146,6 +389,61 @@ mod tests {
         assert_eq!(sma.next(&bar(1.0)), 4.0);
     }
 
+    #[test]
+    fn test_f32() {
+        let mut sma = SimpleMovingAverage::<f32>::with_type(2).unwrap();
+        assert_eq!(sma.calc(1.0), 1.0);
+        assert_eq!(sma.calc(2.0), 1.5);
+        assert_eq!(sma.calc(4.0), 3.0);
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn test_decimal() {
+        use rust_decimal::Decimal;
+
+        let mut sma = SimpleMovingAverage::with_type(3).unwrap();
+        assert_eq!(sma.calc(Decimal::new(1, 1)), Decimal::new(1, 1));
+        assert_eq!(sma.calc(Decimal::new(2, 1)), Decimal::new(15, 2));
+        assert_eq!(sma.calc(Decimal::new(3, 1)), Decimal::new(2, 1));
+    }
+
+    #[test]
+    fn test_update_last() {
+        let mut sma = SimpleMovingAverage::new(3).unwrap();
+        assert_eq!(sma.update_last(4.0), 4.0);
+        assert_eq!(sma.calc(6.0), 5.0);
+        assert_eq!(sma.update_last(8.0), 6.0);
+        assert_eq!(sma.calc(3.0), 5.0);
+        assert_eq!(sma.calc(1.0), 4.0);
+        assert_eq!(sma.replace(&Bar::new().close(4.0)), 5.0);
+
+        let mut sma = SimpleMovingAverageConst::<2>::new().unwrap();
+        assert_eq!(sma.calc(4.0), 4.0);
+        assert_eq!(sma.calc(6.0), 5.0);
+        assert_eq!(sma.update_last(2.0), 3.0);
+        assert_eq!(sma.calc(8.0), 5.0);
+    }
+
+    #[test]
+    fn test_peek() {
+        let mut indicator = SimpleMovingAverage::new(3).unwrap();
+        for &x in &[4.0, 6.0, 8.0, 3.0, 1.0] {
+            let peeked = indicator.peek(x);
+            assert_eq!(
+                indicator.peek_next(&Bar::new().close(x)),
+                indicator.clone().next(&Bar::new().close(x))
+            );
+            assert_eq!(indicator.calc(x), peeked);
+        }
+
+        let mut indicator = SimpleMovingAverageConst::<3>::new().unwrap();
+        for &x in &[4.0, 6.0, 8.0, 3.0, 1.0] {
+            let peeked = indicator.peek(x);
+            assert_eq!(indicator.calc(x), peeked);
+        }
+    }
+
     #[test]
     fn test_reset() {
         let mut sma = SimpleMovingAverage::new(4).unwrap();
@@ -1,8 +1,8 @@
-use std::fmt;
+use core::fmt;
 
 use crate::errors::Result;
 use crate::indicators::{ExponentialMovingAverage, FastStochastic};
-use crate::{Calculate, Close, High, Low, Next, Reset};
+use crate::{Calculate, Close, Float, High, Low, Next, Reset, Warmup};
 
 /// Slow stochastic oscillator.
 ///
@@ -11,7 +11,7 @@ use crate::{Calculate, Close, High, Low, Next, Reset};
 /// # Parameters
 ///
 /// * _stochastic_n_ - number of periods for fast stochastic (integer greater than 0). Default is 14.
-/// *_ema_n_ - length for EMA (integer greater than 0). Default is 3.
+/// * _ema_n_ - length for EMA (integer greater than 0). Default is 3.
 ///
 /// # Example
 ///
@@ -27,37 +27,68 @@ use crate::{Calculate, Close, High, Low, Next, Reset};
 /// assert_eq!(stoch.calc(55.0).round(), 77.0);
 /// ```
 #[derive(Clone, Debug)]
-pub struct SlowStochastic {
-    fast_stochastic: FastStochastic,
-    ema: ExponentialMovingAverage,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(deserialize = "F: Float + serde::Deserialize<'de>"))
+)]
+pub struct SlowStochastic<F = f64> {
+    fast_stochastic: FastStochastic<F>,
+    ema: ExponentialMovingAverage<F>,
+    count: u32,
 }
 
 impl SlowStochastic {
     pub fn new(stochastic_n: u32, ema_n: u32) -> Result<Self> {
+        Self::with_type(stochastic_n, ema_n)
+    }
+}
+
+impl<F: Float> SlowStochastic<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(stochastic_n: u32, ema_n: u32) -> Result<Self> {
         let indicator = Self {
-            fast_stochastic: FastStochastic::new(stochastic_n)?,
-            ema: ExponentialMovingAverage::new(ema_n)?,
+            fast_stochastic: FastStochastic::with_type(stochastic_n)?,
+            ema: ExponentialMovingAverage::with_type(ema_n)?,
+            count: 0,
         };
         Ok(indicator)
     }
 }
 
-impl Calculate for SlowStochastic {
-    fn calc(&mut self, input: f64) -> f64 {
+impl<F: Float> Calculate<F> for SlowStochastic<F> {
+    type Output = F;
+
+    fn calc(&mut self, input: F) -> F {
+        self.count = self.count.saturating_add(1);
         self.ema.calc(self.fast_stochastic.calc(input))
     }
 }
 
-impl<T: High + Low + Close> Next<T> for SlowStochastic {
-    fn next(&mut self, input: &T) -> f64 {
+impl<F: Float, T: High<F> + Low<F> + Close<F>> Next<T> for SlowStochastic<F> {
+    type Output = F;
+
+    fn next(&mut self, input: &T) -> F {
+        self.count = self.count.saturating_add(1);
         self.ema.calc(self.fast_stochastic.next(input))
     }
 }
 
-impl Reset for SlowStochastic {
+impl<F: Float> Reset for SlowStochastic<F> {
     fn reset(&mut self) {
         self.fast_stochastic.reset();
         self.ema.reset();
+        self.count = 0;
+    }
+}
+
+impl<F: Float> Warmup for SlowStochastic<F> {
+    fn warmup_period(&self) -> u32 {
+        self.fast_stochastic.warmup_period() + self.ema.warmup_period() - 1
+    }
+
+    fn is_ready(&self) -> bool {
+        self.count >= self.warmup_period()
     }
 }
 
@@ -67,7 +98,7 @@ impl Default for SlowStochastic {
     }
 }
 
-impl fmt::Display for SlowStochastic {
+impl<F: Float> fmt::Display for SlowStochastic<F> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
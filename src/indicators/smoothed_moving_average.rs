@@ -0,0 +1,195 @@
+use core::fmt;
+
+use crate::errors::*;
+use crate::{Calculate, Close, Decay, Next, Number, Reset, Warmup};
+
+/// Smoothed moving average (SMMA), also known as running moving average (RMA) or Wilder's
+/// moving average.
+///
+/// An exponential moving average with the smoothing factor 1 / _length_ instead of
+/// 2 / (_length_ + 1), which makes it react slower. Like the
+/// [EMA](struct.ExponentialMovingAverage.html), it starts with the first input value.
+///
+/// # Formula
+///
+/// SMMA<sub>t</sub> = (SMMA<sub>t-1</sub> * (_length_ - 1) + p<sub>t</sub>) / _length_
+///
+/// # Parameters
+///
+/// * _length_ - number of periods (integer greater than 0)
+///
+/// # Gaps
+///
+/// After [decay](../trait.Decay.html) over _n_ missed periods, the previous value is weighted
+/// with ((_length_ - 1) / _length_)<sup>_n_ + 1</sup>, as if the next input was repeated _n_ + 1
+/// times.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::SmoothedMovingAverage;
+/// use ta::Calculate;
+///
+/// let mut smma = SmoothedMovingAverage::new(4).unwrap();
+/// assert_eq!(smma.calc(4.0), 4.0);
+/// assert_eq!(smma.calc(8.0), 5.0);
+/// assert_eq!(smma.calc(1.0), 4.0);
+/// ```
+///
+/// # Links
+///
+/// * [Modified moving average, Wikipedia](https://en.wikipedia.org/wiki/Moving_average#Modified_moving_average)
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SmoothedMovingAverage<F = f64> {
+    length: u32,
+    current: F,
+    count: u32,
+    // periods missed since the last input
+    #[cfg_attr(feature = "serde", serde(default))]
+    gap: u32,
+}
+
+impl SmoothedMovingAverage {
+    pub fn new(length: u32) -> Result<Self> {
+        Self::with_type(length)
+    }
+}
+
+impl<F: Number> SmoothedMovingAverage<F> {
+    /// Like [new](#method.new), for values of any number type `F`.
+    pub fn with_type(length: u32) -> Result<Self> {
+        match length {
+            0 => Err(Error::from_kind(ErrorKind::InvalidParameter)),
+            _ => Ok(Self {
+                length,
+                current: F::zero(),
+                count: 0,
+                gap: 0,
+            }),
+        }
+    }
+
+    pub fn length(&self) -> u32 {
+        self.length
+    }
+}
+
+impl<F: Number> Calculate<F> for SmoothedMovingAverage<F> {
+    type Output = F;
+
+    fn calc(&mut self, input: F) -> F {
+        self.count = self.count.saturating_add(1);
+        if self.count == 1 {
+            self.current = input;
+        } else {
+            let length = F::cast(self.length);
+            if self.gap == 0 {
+                self.current = (self.current * (length - F::one()) + input) / length;
+            } else {
+                let weight = num_traits::pow((length - F::one()) / length, self.gap as usize + 1);
+                self.current = weight * self.current + (F::one() - weight) * input;
+            }
+        }
+        self.gap = 0;
+        self.current
+    }
+}
+
+impl<F: Number, T: Close<F>> Next<T> for SmoothedMovingAverage<F> {
+    type Output = F;
+
+    fn next(&mut self, input: &T) -> F {
+        self.calc(input.close())
+    }
+}
+
+impl<F: Number> Reset for SmoothedMovingAverage<F> {
+    fn reset(&mut self) {
+        self.current = F::zero();
+        self.count = 0;
+        self.gap = 0;
+    }
+}
+
+impl<F: Number> Decay for SmoothedMovingAverage<F> {
+    fn decay(&mut self, periods: u32) {
+        self.gap = self.gap.saturating_add(periods);
+    }
+}
+
+impl<F: Number> Warmup for SmoothedMovingAverage<F> {
+    fn warmup_period(&self) -> u32 {
+        self.length
+    }
+
+    fn is_ready(&self) -> bool {
+        self.count >= self.length
+    }
+}
+
+impl Default for SmoothedMovingAverage {
+    fn default() -> Self {
+        Self::new(14).unwrap()
+    }
+}
+
+impl<F: Number> fmt::Display for SmoothedMovingAverage<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SMMA({})", self.length)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(SmoothedMovingAverage);
+
+    #[test]
+    fn test_new() {
+        assert!(SmoothedMovingAverage::new(0).is_err());
+        assert!(SmoothedMovingAverage::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut smma = SmoothedMovingAverage::new(3).unwrap();
+        assert_eq!(smma.calc(3.0), 3.0);
+        assert_eq!(smma.calc(6.0), 4.0);
+        assert_eq!(smma.calc(10.0), 6.0);
+        assert_eq!(smma.next(&Bar::new().close(0.0)), 4.0);
+    }
+
+    #[test]
+    fn test_decay() {
+        let mut smma = SmoothedMovingAverage::new(2).unwrap();
+        smma.calc(2.0);
+        smma.decay(1);
+        assert_eq!(smma.calc(6.0), 5.0);
+        assert_eq!(smma.calc(6.0), 5.5);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut smma = SmoothedMovingAverage::new(3).unwrap();
+        smma.calc(3.0);
+        smma.calc(6.0);
+
+        smma.reset();
+        assert_eq!(smma.calc(9.0), 9.0);
+    }
+
+    #[test]
+    fn test_default() {
+        SmoothedMovingAverage::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let smma = SmoothedMovingAverage::new(7).unwrap();
+        assert_eq!(format!("{}", smma), "SMMA(7)");
+    }
+}
@@ -0,0 +1,205 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::indicators::{RateOfChange, SimpleMovingAverage};
+use crate::{Calculate, Close, Float, Next, Reset, Warmup};
+
+// (ROC length, SMA length, weight)
+const COMPONENTS: [(u32, u32, u32); 12] = [
+    (10, 10, 1),
+    (15, 10, 2),
+    (20, 10, 3),
+    (30, 15, 4),
+    (40, 50, 1),
+    (65, 65, 2),
+    (75, 75, 3),
+    (100, 100, 4),
+    (195, 130, 1),
+    (265, 130, 2),
+    (390, 130, 3),
+    (530, 195, 4),
+];
+
+/// Pring's Special K.
+///
+/// A long-term momentum oscillator by Martin Pring combining short-, intermediate- and
+/// long-term cycles: a weighted sum of twelve smoothed [rates of change](struct.RateOfChange.html).
+/// Its turning points tend to coincide with primary trend reversals.
+///
+/// # Formula
+///
+/// Special K = Σ _w<sub>i</sub>_ * SMA(ROC(_r<sub>i</sub>_), _s<sub>i</sub>_)
+///
+/// | ROC _r_ | SMA _s_ | weight _w_ |
+/// |---------|---------|------------|
+/// | 10      | 10      | 1          |
+/// | 15      | 10      | 2          |
+/// | 20      | 10      | 3          |
+/// | 30      | 15      | 4          |
+/// | 40      | 50      | 1          |
+/// | 65      | 65      | 2          |
+/// | 75      | 75      | 3          |
+/// | 100     | 100     | 4          |
+/// | 195     | 130     | 1          |
+/// | 265     | 130     | 2          |
+/// | 390     | 130     | 3          |
+/// | 530     | 195     | 4          |
+///
+/// The lengths are meant for daily bars; all components are filled after 725 inputs.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::SpecialK;
+/// use ta::Calculate;
+///
+/// let mut special_k = SpecialK::new();
+/// let mut value = 0.0;
+/// for i in 0..100 {
+///     value = special_k.calc(100.0 + i as f64);
+/// }
+/// assert!(value > 0.0);
+/// ```
+///
+/// # Links
+///
+/// * [Pring's Special K, StockCharts](https://school.stockcharts.com/doku.php?id=technical_indicators:pring_s_special_k)
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(deserialize = "F: Float + serde::Deserialize<'de>"))
+)]
+pub struct SpecialK<F = f64> {
+    components: Vec<(RateOfChange<F>, SimpleMovingAverage<F>, F)>,
+    count: u32,
+}
+
+impl SpecialK {
+    pub fn new() -> Self {
+        Self::with_type()
+    }
+}
+
+impl<F: Float> SpecialK<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type() -> Self {
+        let components = COMPONENTS
+            .iter()
+            .map(|&(roc, sma, weight)| {
+                (
+                    RateOfChange::with_type(roc).unwrap(),
+                    SimpleMovingAverage::with_type(sma).unwrap(),
+                    F::cast(weight),
+                )
+            })
+            .collect();
+        Self {
+            components,
+            count: 0,
+        }
+    }
+}
+
+impl<F: Float> Calculate<F> for SpecialK<F> {
+    type Output = F;
+
+    fn calc(&mut self, input: F) -> F {
+        self.count = self.count.saturating_add(1);
+        self.components
+            .iter_mut()
+            .map(|(roc, sma, weight)| sma.calc(roc.calc(input)) * *weight)
+            .sum()
+    }
+}
+
+impl<F: Float, T: Close<F>> Next<T> for SpecialK<F> {
+    type Output = F;
+
+    fn next(&mut self, input: &T) -> F {
+        self.calc(input.close())
+    }
+}
+
+impl<F: Float> Reset for SpecialK<F> {
+    fn reset(&mut self) {
+        for (roc, sma, _) in self.components.iter_mut() {
+            roc.reset();
+            sma.reset();
+        }
+        self.count = 0;
+    }
+}
+
+impl<F: Float> Warmup for SpecialK<F> {
+    fn warmup_period(&self) -> u32 {
+        self.components
+            .iter()
+            .map(|(roc, sma, _)| roc.warmup_period() + sma.warmup_period() - 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn is_ready(&self) -> bool {
+        self.count >= self.warmup_period()
+    }
+}
+
+impl Default for SpecialK {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: Float> fmt::Display for SpecialK<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SPECIAL_K()")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(SpecialK);
+
+    #[test]
+    fn test_constant_growth() {
+        // with a constant growth rate every ROC is constant once its window is filled
+        let mut special_k = SpecialK::new();
+        let mut price = 100.0;
+        let mut value = 0.0;
+        for _ in 0..725 {
+            value = special_k.next(&Bar::new().close(price));
+            price *= 1.001;
+        }
+        assert!(special_k.is_ready());
+        assert_eq!(round(value), 609.734);
+    }
+
+    #[test]
+    fn test_flat() {
+        let mut special_k = SpecialK::new();
+        for _ in 0..50 {
+            assert_eq!(special_k.calc(10.0), 0.0);
+        }
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut special_k = SpecialK::new();
+        special_k.calc(10.0);
+        special_k.calc(20.0);
+
+        special_k.reset();
+        assert_eq!(special_k.calc(20.0), 0.0);
+        assert!(!special_k.is_ready());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", SpecialK::new()), "SPECIAL_K()");
+    }
+}
@@ -0,0 +1,97 @@
+use core::fmt;
+use core::marker::PhantomData;
+
+use crate::{Float, Next, Quote, Reset, Warmup};
+
+/// Quoted spread.
+///
+/// Difference between the best ask and the best bid.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::Spread;
+/// use ta::{Next, Quote};
+///
+/// struct Tick(f64, f64);
+///
+/// impl Quote for Tick {
+///     fn bid(&self) -> f64 { self.0 }
+///     fn ask(&self) -> f64 { self.1 }
+///     fn bid_size(&self) -> f64 { 1.0 }
+///     fn ask_size(&self) -> f64 { 1.0 }
+/// }
+///
+/// let mut spread = Spread::new();
+/// assert_eq!(spread.next(&Tick(99.5, 100.0)), 0.5);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Spread<F = f64> {
+    float: PhantomData<F>,
+}
+
+impl Spread {
+    pub fn new() -> Self {
+        Self::with_type()
+    }
+}
+
+impl<F: Float> Spread<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type() -> Self {
+        Self { float: PhantomData }
+    }
+}
+
+impl<F: Float, T: Quote<F>> Next<T> for Spread<F> {
+    type Output = F;
+
+    fn next(&mut self, input: &T) -> F {
+        input.ask() - input.bid()
+    }
+}
+
+impl<F: Float> Reset for Spread<F> {
+    fn reset(&mut self) {}
+}
+
+impl<F: Float> Warmup for Spread<F> {
+    fn warmup_period(&self) -> u32 {
+        0
+    }
+
+    fn is_ready(&self) -> bool {
+        true
+    }
+}
+
+impl Default for Spread {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: Float> fmt::Display for Spread<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SPREAD()")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_next() {
+        let mut spread = Spread::new();
+        assert_eq!(spread.next(&quote(10.0, 10.5, 1.0, 1.0)), 0.5);
+        assert_eq!(spread.next(&quote(10.0, 10.0, 1.0, 1.0)), 0.0);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", Spread::default()), "SPREAD()");
+    }
+}
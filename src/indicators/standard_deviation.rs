@@ -1,11 +1,14 @@
-use std::fmt;
+use core::fmt;
 
 use crate::errors::*;
-use crate::{Calculate, Close, Next, Reset};
+use crate::indicators::Variance;
+use crate::{Calculate, Close, Float, Next, Reset, Warmup};
 
 /// Standard deviation (SD).
 ///
-/// Returns the standard deviation of the last n values.
+/// Returns the standard deviation of the last n values, the square root of their
+/// [variance](struct.Variance.html). It is the population standard deviation by default and
+/// the sample one with [sample](#method.sample).
 ///
 /// # Formula
 ///
@@ -30,6 +33,10 @@ use crate::{Calculate, Close, Next, Reset};
 /// let mut sd = StandardDeviation::new(3).unwrap();
 /// assert_eq!(sd.calc(10.0), 0.0);
 /// assert_eq!(sd.calc(20.0), 5.0);
+///
+/// let mut sd = StandardDeviation::new(3).unwrap().sample();
+/// sd.calc(10.0);
+/// assert_eq!(sd.calc(30.0), 200.0_f64.sqrt());
 /// ```
 ///
 /// # Links
@@ -37,74 +44,69 @@ use crate::{Calculate, Close, Next, Reset};
 /// * [Standard Deviation, Wikipedia](https://en.wikipedia.org/wiki/Standard_deviation)
 ///
 #[derive(Debug, Clone)]
-pub struct StandardDeviation {
-    n: u32,
-    index: usize,
-    count: u32,
-    m: f64,
-    m2: f64,
-    vec: Vec<f64>,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StandardDeviation<F = f64> {
+    variance: Variance<F>,
 }
 
 impl StandardDeviation {
     pub fn new(n: u32) -> Result<Self> {
-        match n {
-            0 => Err(Error::from_kind(ErrorKind::InvalidParameter)),
-            _ => {
-                let std = StandardDeviation {
-                    n,
-                    index: 0,
-                    count: 0,
-                    m: 0.0,
-                    m2: 0.0,
-                    vec: vec![0.0; n as usize],
-                };
-                Ok(std)
-            }
-        }
+        Self::with_type(n)
     }
 }
 
-impl Calculate for StandardDeviation {
-    fn calc(&mut self, input: f64) -> f64 {
-        self.index = (self.index + 1) % (self.n as usize);
-
-        let old_val = self.vec[self.index];
-        self.vec[self.index] = input;
-
-        if self.count < self.n {
-            self.count += 1;
-            let delta = input - self.m;
-            self.m += delta / self.count as f64;
-            let delta2 = input - self.m;
-            self.m2 += delta * delta2;
-        } else {
-            let delta = input - old_val;
-            let old_m = self.m;
-            self.m += delta / self.n as f64;
-            let delta2 = input - self.m + old_val - old_m;
-            self.m2 += delta * delta2;
-        }
+impl<F: Float> StandardDeviation<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(n: u32) -> Result<Self> {
+        Ok(Self {
+            variance: Variance::with_type(n)?,
+        })
+    }
+
+    /// Switches to the sample standard deviation, with Bessel's correction.
+    pub fn sample(mut self) -> Self {
+        self.variance = self.variance.sample();
+        self
+    }
+
+    pub fn is_sample(&self) -> bool {
+        self.variance.is_sample()
+    }
+
+    pub fn length(&self) -> u32 {
+        self.variance.length()
+    }
+}
+
+impl<F: Float> Calculate<F> for StandardDeviation<F> {
+    type Output = F;
 
-        (self.m2 / self.count as f64).sqrt()
+    fn calc(&mut self, input: F) -> F {
+        self.variance.calc(input).sqrt()
     }
 }
 
-impl<T: Close> Next<T> for StandardDeviation {
-    fn next(&mut self, input: &T) -> f64 {
+impl<F: Float, T: Close<F>> Next<T> for StandardDeviation<F> {
+    type Output = F;
+
+    fn next(&mut self, input: &T) -> F {
         self.calc(input.close())
     }
 }
 
-impl Reset for StandardDeviation {
+impl<F: Float> Reset for StandardDeviation<F> {
     fn reset(&mut self) {
-        self.index = 0;
-        self.count = 0;
-        self.m = 0.0;
-        self.m2 = 0.0;
-        for i in 0..(self.n as usize) {
-            self.vec[i] = 0.0;
-        }
+        self.variance.reset();
+    }
+}
+
+impl<F: Float> Warmup for StandardDeviation<F> {
+    fn warmup_period(&self) -> u32 {
+        self.variance.warmup_period()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.variance.is_ready()
     }
 }
 
@@ -114,9 +116,10 @@ impl Default for StandardDeviation {
     }
 }
 
-impl fmt::Display for StandardDeviation {
+impl<F: Float> fmt::Display for StandardDeviation<F> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "SD({})", self.n)
+        let name = if self.is_sample() { "SAMPLE_SD" } else { "SD" };
+        write!(f, "{}({})", name, self.length())
     }
 }
 
@@ -144,6 +147,17 @@ mod tests {
         assert_eq!(round(sd.calc(100.0)), 35.355);
     }
 
+    #[test]
+    fn test_sample() {
+        let mut sd = StandardDeviation::new(4).unwrap().sample();
+        assert!(sd.is_sample());
+        assert_eq!(sd.calc(10.0), 0.0);
+        assert_eq!(round(sd.calc(20.0)), 7.071);
+        assert_eq!(sd.calc(30.0), 10.0);
+        assert_eq!(round(sd.calc(20.0)), 8.165);
+        assert_eq!(round(sd.calc(100.0)), 38.622);
+    }
+
     #[test]
     fn test_next_with_bars() {
         fn bar(close: f64) -> Bar {
@@ -188,5 +202,6 @@ mod tests {
     fn test_display() {
         let sd = StandardDeviation::new(5).unwrap();
         assert_eq!(format!("{}", sd), "SD(5)");
+        assert_eq!(format!("{}", sd.sample()), "SAMPLE_SD(5)");
     }
 }
@@ -0,0 +1,256 @@
+use core::fmt;
+
+use crate::errors::*;
+use crate::indicators::{ExponentialMovingAverage, Maximum, Minimum};
+use crate::{Calculate, Close, Finite, Float, High, Low, Next, Reset, Warmup};
+
+/// Stochastic Momentum Index (SMI).
+///
+/// A refinement of the stochastic oscillator by William Blau. Instead of the position of
+/// the close within the high/low range it measures the distance of the close from the
+/// midpoint of the range, smoothed twice with an exponential moving average. The result
+/// oscillates between -100 and +100.
+///
+/// # Formula
+///
+/// D<sub>t</sub> = C<sub>t</sub> - (H<sub>n</sub> + L<sub>n</sub>) / 2
+///
+/// R<sub>t</sub> = H<sub>n</sub> - L<sub>n</sub>
+///
+/// SMI<sub>t</sub> = 100 * EMA(EMA(D<sub>t</sub>, _s_), _s_) / (EMA(EMA(R<sub>t</sub>, _s_), _s_) / 2)
+///
+/// signal<sub>t</sub> = EMA(SMI<sub>t</sub>, _m_)
+///
+/// Where:
+///
+/// * C<sub>t</sub> - close price of the current period
+/// * L<sub>n</sub> - lowest price for the last _n_ periods
+/// * H<sub>n</sub> - highest price for the last _n_ periods
+///
+/// When the smoothed range is zero the SMI is 0.
+///
+/// # Parameters
+///
+/// * _length_ - number of periods of the high/low range (integer greater than 0). Default is 10.
+/// * _smoothing_ - length of both EMA smoothings (integer greater than 0). Default is 3.
+/// * _signal_length_ - length of the signal line EMA (integer greater than 0). Default is 10.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::StochasticMomentumIndex;
+/// use ta::Calculate;
+///
+/// let mut smi = StochasticMomentumIndex::new(3, 2, 2).unwrap();
+/// assert_eq!(smi.calc(10.0).smi, 0.0);
+/// let out = smi.calc(12.0);
+/// assert_eq!(out.smi, 100.0);
+/// assert!(out.signal < out.smi);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(deserialize = "F: Float + serde::Deserialize<'de>"))
+)]
+pub struct StochasticMomentumIndex<F = f64> {
+    length: u32,
+    maximum: Maximum<F>,
+    minimum: Minimum<F>,
+    distance: [ExponentialMovingAverage<F>; 2],
+    range: [ExponentialMovingAverage<F>; 2],
+    signal: ExponentialMovingAverage<F>,
+    count: u32,
+}
+
+/// Output of [StochasticMomentumIndex](struct.StochasticMomentumIndex.html).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StochasticMomentumIndexOutput<F = f64> {
+    pub smi: F,
+    pub signal: F,
+}
+
+impl<F: Finite> Finite for StochasticMomentumIndexOutput<F> {
+    fn is_finite(&self) -> bool {
+        self.smi.is_finite() && self.signal.is_finite()
+    }
+}
+
+impl StochasticMomentumIndex {
+    pub fn new(length: u32, smoothing: u32, signal_length: u32) -> Result<Self> {
+        Self::with_type(length, smoothing, signal_length)
+    }
+}
+
+impl<F: Float> StochasticMomentumIndex<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(length: u32, smoothing: u32, signal_length: u32) -> Result<Self> {
+        let ema = || ExponentialMovingAverage::with_type(smoothing);
+        Ok(Self {
+            length,
+            maximum: Maximum::with_type(length)?,
+            minimum: Minimum::with_type(length)?,
+            distance: [ema()?, ema()?],
+            range: [ema()?, ema()?],
+            signal: ExponentialMovingAverage::with_type(signal_length)?,
+            count: 0,
+        })
+    }
+
+    pub fn length(&self) -> u32 {
+        self.length
+    }
+
+    fn update(&mut self, high: F, low: F, close: F) -> StochasticMomentumIndexOutput<F> {
+        self.count = self.count.saturating_add(1);
+        let highest = self.maximum.calc(high);
+        let lowest = self.minimum.calc(low);
+
+        let distance = self.distance[0].calc(close - (highest + lowest) / F::cast(2.0));
+        let distance = self.distance[1].calc(distance);
+        let range = self.range[0].calc(highest - lowest);
+        let range = self.range[1].calc(range);
+
+        // a range decayed to the smallest subnormal number halves to 0
+        let half_range = range / F::cast(2.0);
+        let smi = if half_range == F::zero() {
+            F::zero()
+        } else {
+            // the distance and the range are smoothed separately, and the rounding of
+            // prices of very different magnitudes may push it a bit out of the range
+            let hundred = F::cast(100.0);
+            (hundred * distance / half_range).clamp(-hundred, hundred)
+        };
+
+        StochasticMomentumIndexOutput {
+            smi,
+            signal: self.signal.calc(smi),
+        }
+    }
+}
+
+impl<F: Float> Calculate<F> for StochasticMomentumIndex<F> {
+    type Output = StochasticMomentumIndexOutput<F>;
+
+    fn calc(&mut self, input: F) -> Self::Output {
+        self.update(input, input, input)
+    }
+}
+
+impl<F: Float, T: High<F> + Low<F> + Close<F>> Next<T> for StochasticMomentumIndex<F> {
+    type Output = StochasticMomentumIndexOutput<F>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.update(input.high(), input.low(), input.close())
+    }
+}
+
+impl<F: Float> Reset for StochasticMomentumIndex<F> {
+    fn reset(&mut self) {
+        self.maximum.reset();
+        self.minimum.reset();
+        for ema in self.distance.iter_mut().chain(self.range.iter_mut()) {
+            ema.reset();
+        }
+        self.signal.reset();
+        self.count = 0;
+    }
+}
+
+impl<F: Float> Warmup for StochasticMomentumIndex<F> {
+    fn warmup_period(&self) -> u32 {
+        self.length + 2 * (self.distance[0].length() - 1) + self.signal.length() - 1
+    }
+
+    fn is_ready(&self) -> bool {
+        self.count >= self.warmup_period()
+    }
+}
+
+impl Default for StochasticMomentumIndex {
+    fn default() -> Self {
+        Self::new(10, 3, 10).unwrap()
+    }
+}
+
+impl<F: Float> fmt::Display for StochasticMomentumIndex<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "SMI({}, {}, {})",
+            self.length,
+            self.distance[0].length(),
+            self.signal.length()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(StochasticMomentumIndex);
+
+    #[test]
+    fn test_new() {
+        assert!(StochasticMomentumIndex::new(0, 3, 10).is_err());
+        assert!(StochasticMomentumIndex::new(10, 0, 10).is_err());
+        assert!(StochasticMomentumIndex::new(10, 3, 0).is_err());
+        assert!(StochasticMomentumIndex::new(1, 1, 1).is_ok());
+    }
+
+    #[test]
+    fn test_calc() {
+        let mut smi = StochasticMomentumIndex::new(3, 2, 2).unwrap();
+        let expected = [
+            (0.0, 0.0),
+            (100.0, 66.667),
+            (40.0, 48.889),
+            (73.333, 65.185),
+            (55.28, 58.581),
+        ];
+        for (input, &(value, signal)) in [10.0, 12.0, 11.0, 14.0, 13.0].iter().zip(&expected) {
+            let out = smi.calc(*input);
+            assert_eq!(round(out.smi), value);
+            assert_eq!(round(out.signal), signal);
+        }
+    }
+
+    #[test]
+    fn test_next() {
+        let mut smi = StochasticMomentumIndex::new(3, 2, 2).unwrap();
+        let bar = |high: f64, low: f64, close: f64| Bar::new().high(high).low(low).close(close);
+
+        assert_eq!(smi.next(&bar(11.0, 9.0, 10.0)).smi, 0.0);
+        let out = smi.next(&bar(13.0, 10.0, 12.0));
+        assert_eq!(round(out.smi), 30.769);
+        assert_eq!(round(out.signal), 20.513);
+        let out = smi.next(&bar(12.0, 10.0, 11.0));
+        assert_eq!(round(out.smi), 17.021);
+        assert_eq!(round(out.signal), 18.185);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut smi = StochasticMomentumIndex::new(3, 2, 2).unwrap();
+        smi.calc(10.0);
+        smi.calc(12.0);
+
+        smi.reset();
+        assert_eq!(smi.calc(12.0).smi, 0.0);
+        assert_eq!(smi.calc(14.0).smi, 100.0);
+    }
+
+    #[test]
+    fn test_default() {
+        StochasticMomentumIndex::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let smi = StochasticMomentumIndex::new(14, 5, 9).unwrap();
+        assert_eq!(format!("{}", smi), "SMI(14, 5, 9)");
+    }
+}
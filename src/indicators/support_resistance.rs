@@ -0,0 +1,398 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::errors::*;
+use crate::indicators::SimpleMovingAverage;
+use crate::swings::SwingWindow;
+use crate::{Calculate, Close, Finite, Float, High, Low, Next, Reset, Volume, Warmup};
+
+/// Horizontal price level of [SupportResistance](struct.SupportResistance.html).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PriceLevel<F = f64> {
+    /// Mean price of the touches.
+    pub price: F,
+    /// Number of swings and volume nodes at the level.
+    pub touches: u32,
+    /// Number of bars since the first touch.
+    pub age: u32,
+    /// Number of bars since the latest touch.
+    pub since_touch: u32,
+}
+
+/// Output of [SupportResistance](struct.SupportResistance.html).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SupportResistanceOutput<F = f64> {
+    /// Current levels, sorted by price.
+    pub levels: Vec<PriceLevel<F>>,
+    /// Highest level at or below the close.
+    pub support: Option<F>,
+    /// Lowest level above the close.
+    pub resistance: Option<F>,
+}
+
+impl<F: Finite> Finite for SupportResistanceOutput<F> {
+    fn is_finite(&self) -> bool {
+        self.levels.iter().all(|level| level.price.is_finite())
+            && self.support.iter().all(Finite::is_finite)
+            && self.resistance.iter().all(Finite::is_finite)
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(deserialize = "F: Float + serde::Deserialize<'de>"))
+)]
+struct Level<F> {
+    price: F,
+    touches: u32,
+    // bar numbers of the first and the latest touch
+    first: u64,
+    last: u64,
+}
+
+/// Support and resistance levels.
+///
+/// Clusters the swing highs and lows of the price, bars higher or lower than the _strength_
+/// bars on both sides, into horizontal levels: a swing within _tolerance_ of an existing
+/// level touches it and moves the level to the mean price of its touches, any other swing
+/// starts a new level. Levels not touched for _lookback_ bars expire.
+///
+/// Optionally, bars of high volume are touches as well, at their typical price
+/// (high + low + close) / 3, see [volume_nodes](#method.volume_nodes).
+///
+/// Returns all current levels with their number of touches and ages, and the nearest
+/// support and resistance to the close. Swings are confirmed only _strength_ bars after
+/// they happened, so that is the delay of new levels.
+///
+/// # Parameters
+///
+/// * _strength_ - number of bars on each side of a swing (integer greater than 0).
+///   Default is 5.
+/// * _tolerance_ - distance of a touch from a level, relative to its price (between 0 and 1
+///   exclusive). Default is 0.005.
+/// * _lookback_ - number of bars after the latest touch a level expires (integer greater
+///   than 0). Default is 200.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::SupportResistance;
+/// use ta::Calculate;
+///
+/// let mut sr = SupportResistance::new(1, 0.01, 50).unwrap();
+/// // two tops at about 110 and a bottom at 100
+/// for &close in &[105.0, 110.0, 100.0, 109.5, 104.0] {
+///     sr.calc(close);
+/// }
+/// let output = sr.calc(103.0);
+///
+/// assert_eq!(output.levels.len(), 2);
+/// assert_eq!(output.levels[1].price, 109.75);
+/// assert_eq!(output.levels[1].touches, 2);
+/// assert_eq!(output.support, Some(100.0));
+/// assert_eq!(output.resistance, Some(109.75));
+/// ```
+///
+/// # Links
+///
+/// * [Support and Resistance, Investopedia](https://www.investopedia.com/trading/support-and-resistance-basics/)
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(deserialize = "F: Float + serde::Deserialize<'de>"))
+)]
+pub struct SupportResistance<F = f64> {
+    strength: u32,
+    tolerance: F,
+    lookback: u32,
+    volume_factor: Option<F>,
+    swings: SwingWindow<(F, F)>,
+    volume: SimpleMovingAverage<F>,
+    average_volume: Option<F>,
+    levels: Vec<Level<F>>,
+    count: u64,
+}
+
+impl SupportResistance {
+    pub fn new(strength: u32, tolerance: f64, lookback: u32) -> Result<Self> {
+        Self::with_type(strength, tolerance, lookback)
+    }
+}
+
+impl<F: Float> SupportResistance<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(strength: u32, tolerance: F, lookback: u32) -> Result<Self> {
+        if strength == 0 || lookback == 0 || !(tolerance >= F::zero() && tolerance < F::one()) {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        Ok(Self {
+            strength,
+            tolerance,
+            lookback,
+            volume_factor: None,
+            swings: SwingWindow::new(strength, strength),
+            volume: SimpleMovingAverage::with_type(lookback)?,
+            average_volume: None,
+            levels: Vec::new(),
+            count: 0,
+        })
+    }
+
+    /// Adds a touch at the typical price of every bar with a volume of at least `factor`
+    /// times the average volume of the previous _lookback_ bars.
+    pub fn volume_nodes(mut self, factor: F) -> Result<Self> {
+        if factor <= F::zero() || !factor.is_finite() {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        self.volume_factor = Some(factor);
+        Ok(self)
+    }
+
+    pub fn strength(&self) -> u32 {
+        self.strength
+    }
+
+    pub fn tolerance(&self) -> F {
+        self.tolerance
+    }
+
+    pub fn lookback(&self) -> u32 {
+        self.lookback
+    }
+
+    fn touch(&mut self, price: F, index: u64) {
+        let tolerance = self.tolerance;
+        let nearest = self
+            .levels
+            .iter_mut()
+            .map(|level| ((level.price - price).abs(), level))
+            .filter(|(distance, level)| *distance <= tolerance * level.price.abs())
+            .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(core::cmp::Ordering::Equal));
+
+        match nearest {
+            Some((_, level)) => {
+                level.touches += 1;
+                level.price += (price - level.price) / F::cast(level.touches);
+                level.last = level.last.max(index);
+            }
+            None if price.is_finite() => self.levels.push(Level {
+                price,
+                touches: 1,
+                first: index,
+                last: index,
+            }),
+            None => {}
+        }
+    }
+
+    fn update(
+        &mut self,
+        high: F,
+        low: F,
+        close: F,
+        volume: Option<F>,
+    ) -> SupportResistanceOutput<F> {
+        self.count += 1;
+
+        let (swing_high, swing_low) = self.swings.push((high, low), |bar| bar.0, |bar| bar.1);
+        // bar number of the swings, the window is full once there are any
+        let index = self.count.saturating_sub(u64::from(self.strength));
+        if let Some((high, _)) = swing_high {
+            self.touch(high, index);
+        }
+        if let Some((_, low)) = swing_low {
+            self.touch(low, index);
+        }
+
+        if let (Some(factor), Some(volume)) = (self.volume_factor, volume) {
+            // average of the previous bars, without the current one
+            if let Some(average) = self.average_volume {
+                if average > F::zero() && volume >= factor * average {
+                    self.touch((high + low + close) / F::cast(3.0), self.count);
+                }
+            }
+            self.average_volume = Some(self.volume.calc(volume));
+        }
+
+        let (count, lookback) = (self.count, u64::from(self.lookback));
+        self.levels.retain(|level| count - level.last <= lookback);
+
+        let mut levels: Vec<PriceLevel<F>> = self
+            .levels
+            .iter()
+            .map(|level| PriceLevel {
+                price: level.price,
+                touches: level.touches,
+                age: (count - level.first) as u32,
+                since_touch: (count - level.last) as u32,
+            })
+            .collect();
+        levels.sort_by(|a, b| {
+            a.price
+                .partial_cmp(&b.price)
+                .unwrap_or(core::cmp::Ordering::Equal)
+        });
+
+        let support = levels
+            .iter()
+            .rev()
+            .find(|level| level.price <= close)
+            .map(|level| level.price);
+        let resistance = levels
+            .iter()
+            .find(|level| level.price > close)
+            .map(|level| level.price);
+        SupportResistanceOutput {
+            levels,
+            support,
+            resistance,
+        }
+    }
+}
+
+impl<F: Float> Calculate<F> for SupportResistance<F> {
+    type Output = SupportResistanceOutput<F>;
+
+    fn calc(&mut self, input: F) -> Self::Output {
+        self.update(input, input, input, None)
+    }
+}
+
+impl<F: Float, T: High<F> + Low<F> + Close<F> + Volume<F>> Next<T> for SupportResistance<F> {
+    type Output = SupportResistanceOutput<F>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.update(
+            input.high(),
+            input.low(),
+            input.close(),
+            Some(input.volume()),
+        )
+    }
+}
+
+impl<F: Float> Reset for SupportResistance<F> {
+    fn reset(&mut self) {
+        self.swings.clear();
+        self.volume.reset();
+        self.average_volume = None;
+        self.levels.clear();
+        self.count = 0;
+    }
+}
+
+impl<F: Float> Warmup for SupportResistance<F> {
+    fn warmup_period(&self) -> u32 {
+        2 * self.strength + 1
+    }
+
+    fn is_ready(&self) -> bool {
+        self.count >= u64::from(self.warmup_period())
+    }
+}
+
+impl Default for SupportResistance {
+    fn default() -> Self {
+        Self::new(5, 0.005, 200).unwrap()
+    }
+}
+
+impl<F: Float> fmt::Display for SupportResistance<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "SR({}, {}, {})",
+            self.strength, self.tolerance, self.lookback
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(SupportResistance);
+
+    #[test]
+    fn test_new() {
+        assert!(SupportResistance::new(0, 0.01, 10).is_err());
+        assert!(SupportResistance::new(2, 1.0, 10).is_err());
+        assert!(SupportResistance::new(2, f64::NAN, 10).is_err());
+        assert!(SupportResistance::new(2, 0.01, 0).is_err());
+        assert!(SupportResistance::new(2, 0.0, 10).is_ok());
+        let sr = SupportResistance::new(2, 0.01, 10).unwrap();
+        assert!(sr.clone().volume_nodes(0.0).is_err());
+        assert!(sr.volume_nodes(2.0).is_ok());
+    }
+
+    #[test]
+    fn test_levels() {
+        let mut sr = SupportResistance::new(1, 0.01, 50).unwrap();
+        let bar = |high: f64, low: f64| Bar::new().high(high).low(low).close((high + low) / 2.0);
+        let bars = [
+            bar(102.0, 98.0),
+            bar(110.0, 104.0),
+            bar(106.0, 100.0),
+            bar(109.0, 105.0),
+            bar(106.0, 100.5),
+        ];
+        for b in &bars {
+            sr.next(b);
+        }
+        let output = sr.next(&bar(108.0, 103.0));
+
+        // the high of 110 is touched by the swing high of 109.0, the low of 100.0 by 100.5
+        assert_eq!(output.levels.len(), 2);
+        assert_eq!(output.levels[0].price, 100.25);
+        assert_eq!(output.levels[0].touches, 2);
+        assert_eq!((output.levels[0].age, output.levels[0].since_touch), (3, 1));
+        assert_eq!(output.levels[1].price, 109.5);
+        assert_eq!(output.support, Some(100.25));
+        assert_eq!(output.resistance, Some(109.5));
+    }
+
+    #[test]
+    fn test_expiry() {
+        let mut sr = SupportResistance::new(1, 0.01, 3).unwrap();
+        for &close in &[1.0, 2.0, 2.0] {
+            sr.calc(close);
+        }
+        assert_eq!(sr.calc(2.0).levels.len(), 1);
+        assert_eq!(sr.calc(2.0).levels[0].since_touch, 3);
+        assert!(sr.calc(2.0).levels.is_empty());
+    }
+
+    #[test]
+    fn test_volume_nodes() {
+        let mut sr = SupportResistance::new(3, 0.01, 10)
+            .unwrap()
+            .volume_nodes(3.0)
+            .unwrap();
+        let bar = |close: f64, volume: f64| {
+            Bar::new()
+                .high(close + 1.0)
+                .low(close - 1.0)
+                .close(close)
+                .volume(volume)
+        };
+        sr.next(&bar(50.0, 100.0));
+        sr.next(&bar(51.0, 100.0));
+        let output = sr.next(&bar(52.0, 400.0));
+        assert_eq!(output.levels.len(), 1);
+        assert_eq!(output.levels[0].price, 52.0);
+        assert_eq!(output.support, Some(52.0));
+    }
+
+    #[test]
+    fn test_display() {
+        let sr = SupportResistance::new(3, 0.01, 100).unwrap();
+        assert_eq!(format!("{}", sr), "SR(3, 0.01, 100)");
+    }
+}
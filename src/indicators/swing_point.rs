@@ -0,0 +1,27 @@
+use crate::Finite;
+
+/// Kind of a [SwingPoint](struct.SwingPoint.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SwingKind {
+    High,
+    Low,
+}
+
+/// Swing high or low found by [ZigZag](struct.ZigZag.html),
+/// [PivotHigh](struct.PivotHigh.html), [PivotLow](struct.PivotLow.html) or
+/// [Fractals](struct.Fractals.html).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SwingPoint<F = f64> {
+    pub kind: SwingKind,
+    pub price: F,
+    /// Number of bars before the current bar the swing happened at, 0 for the current bar.
+    pub offset: u32,
+}
+
+impl<F: Finite> Finite for SwingPoint<F> {
+    fn is_finite(&self) -> bool {
+        self.price.is_finite()
+    }
+}
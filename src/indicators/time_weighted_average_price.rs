@@ -0,0 +1,266 @@
+use alloc::collections::VecDeque;
+use core::fmt;
+
+use crate::errors::*;
+use crate::sessions::Session;
+use crate::{Float, Next, Reset, Warmup};
+
+/// Time-weighted average price (TWAP).
+///
+/// Average of prices weighted by how long each price was in effect. The input is a pair
+/// `(timestamp, price)` with the timestamp in milliseconds; a price is in effect from its
+/// timestamp until the timestamp of the next input. Until any time has elapsed the TWAP
+/// equals the latest price.
+///
+/// # Modes
+///
+/// * Rolling ([rolling](#method.rolling)) - average over the last _window_ milliseconds.
+/// * Anchored ([anchored](#method.anchored)) - average since the first input after a reset,
+///   or since the start of the current session if a [Session](../sessions/struct.Session.html)
+///   is attached with [session](#method.session).
+///
+/// A rolling TWAP [is ready](../trait.Warmup.html) once the whole window is covered by inputs,
+/// an anchored one once any time has elapsed since the anchor.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::TimeWeightedAveragePrice;
+/// use ta::Next;
+///
+/// let mut twap = TimeWeightedAveragePrice::anchored();
+/// assert_eq!(twap.next(&(0, 10.0)), 10.0);
+/// // 10.0 was in effect for 3 seconds
+/// assert_eq!(twap.next(&(3_000, 20.0)), 10.0);
+/// // 20.0 was in effect for 1 second
+/// assert_eq!(twap.next(&(4_000, 20.0)), 12.5);
+/// ```
+///
+/// # Links
+///
+/// * [Time-weighted average price, Wikipedia](https://en.wikipedia.org/wiki/Time-weighted_average_price)
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TimeWeightedAveragePrice<F = f64> {
+    window: Option<i64>,
+    session: Option<Session>,
+    // (start, end, price)
+    segments: VecDeque<(i64, i64, F)>,
+    sum: F,
+    duration: i64,
+    last: Option<(i64, F)>,
+    // whether the rolling window has been covered
+    full: bool,
+}
+
+impl TimeWeightedAveragePrice {
+    /// TWAP over a rolling window of _window_ milliseconds (greater than 0).
+    pub fn rolling(window: i64) -> Result<Self> {
+        if window <= 0 {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        Ok(Self::create(Some(window)))
+    }
+
+    /// TWAP anchored at the first input after a reset.
+    pub fn anchored() -> Self {
+        Self::create(None)
+    }
+}
+
+impl<F: Float> TimeWeightedAveragePrice<F> {
+    /// Re-anchors the TWAP at the start of every session. Only applies to anchored mode.
+    pub fn session(mut self, session: Session) -> Self {
+        self.session = Some(session);
+        self
+    }
+
+    fn create(window: Option<i64>) -> Self {
+        Self {
+            window,
+            session: None,
+            segments: VecDeque::new(),
+            sum: F::zero(),
+            duration: 0,
+            last: None,
+            full: false,
+        }
+    }
+}
+
+impl<F: Float> Next<(i64, F)> for TimeWeightedAveragePrice<F> {
+    type Output = F;
+
+    fn next(&mut self, &(timestamp, price): &(i64, F)) -> F {
+        if let (None, Some(session), Some((last_timestamp, _))) =
+            (self.window, &self.session, self.last)
+        {
+            if session.is_new_session(last_timestamp, timestamp) {
+                self.reset();
+            }
+        }
+
+        if let Some((last_timestamp, last_price)) = self.last {
+            let elapsed = timestamp - last_timestamp;
+            if elapsed > 0 {
+                self.sum += last_price * F::cast(elapsed);
+                self.duration += elapsed;
+                if self.window.is_some() {
+                    self.segments
+                        .push_back((last_timestamp, timestamp, last_price));
+                }
+            }
+        }
+        self.last = Some((timestamp, price));
+
+        let (mut sum, mut duration) = (self.sum, self.duration);
+        if let Some(window) = self.window {
+            let cutoff = timestamp - window;
+            while let Some(&(start, end, old_price)) = self.segments.front() {
+                if end > cutoff {
+                    break;
+                }
+                self.sum -= old_price * F::cast(end - start);
+                self.duration -= end - start;
+                self.segments.pop_front();
+                self.full = true;
+            }
+            sum = self.sum;
+            duration = self.duration;
+            if let Some(&(start, _, old_price)) = self.segments.front() {
+                if start <= cutoff {
+                    self.full = true;
+                }
+                if start < cutoff {
+                    sum -= old_price * F::cast(cutoff - start);
+                    duration -= cutoff - start;
+                }
+            }
+        }
+
+        if duration == 0 {
+            price
+        } else {
+            sum / F::cast(duration)
+        }
+    }
+}
+
+impl<F: Float> Reset for TimeWeightedAveragePrice<F> {
+    fn reset(&mut self) {
+        self.segments.clear();
+        self.sum = F::zero();
+        self.duration = 0;
+        self.last = None;
+        self.full = false;
+    }
+}
+
+impl<F: Float> Warmup for TimeWeightedAveragePrice<F> {
+    fn warmup_period(&self) -> u32 {
+        2
+    }
+
+    fn is_ready(&self) -> bool {
+        match self.window {
+            Some(_) => self.full,
+            None => self.duration > 0,
+        }
+    }
+}
+
+impl Default for TimeWeightedAveragePrice {
+    fn default() -> Self {
+        Self::anchored()
+    }
+}
+
+impl<F: Float> fmt::Display for TimeWeightedAveragePrice<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.window {
+            Some(window) => write!(f, "TWAP({})", window),
+            None => write!(f, "TWAP(ANCHORED)"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sessions::{TimeZone, DAY, HOUR};
+    use crate::TryNext;
+
+    #[test]
+    fn test_new() {
+        assert!(TimeWeightedAveragePrice::rolling(0).is_err());
+        assert!(TimeWeightedAveragePrice::rolling(1).is_ok());
+    }
+
+    #[test]
+    fn test_anchored() {
+        let mut twap = TimeWeightedAveragePrice::anchored();
+        assert_eq!(twap.next(&(0, 10.0)), 10.0);
+        assert_eq!(twap.next(&(0, 12.0)), 12.0);
+        assert_eq!(twap.next(&(1, 14.0)), 12.0);
+        assert_eq!(twap.next(&(3, 10.0)), (12.0 + 2.0 * 14.0) / 3.0);
+    }
+
+    #[test]
+    fn test_rolling() {
+        let mut twap = TimeWeightedAveragePrice::rolling(10).unwrap();
+        twap.next(&(0, 10.0));
+        assert_eq!(twap.next(&(10, 20.0)), 10.0);
+        // window [5, 15]: 10.0 for 5ms, 20.0 for 5ms
+        assert_eq!(twap.next(&(15, 30.0)), 15.0);
+        // window [20, 30]: 30.0 for 10ms
+        assert_eq!(twap.next(&(30, 40.0)), 30.0);
+        // window [30, 40]: 40.0 for 10ms
+        assert_eq!(twap.next(&(40, 0.0)), 40.0);
+        assert_eq!(twap.segments.len(), 1);
+    }
+
+    #[test]
+    fn test_session_anchor() {
+        let session = Session::crypto(TimeZone::UTC);
+        let mut twap = TimeWeightedAveragePrice::anchored().session(session);
+        twap.next(&(DAY - 2 * HOUR, 10.0));
+        assert_eq!(twap.next(&(DAY - HOUR, 20.0)), 10.0);
+        assert_eq!(twap.next(&(DAY + HOUR, 30.0)), 30.0);
+    }
+
+    #[test]
+    fn test_warmup() {
+        let mut twap = TimeWeightedAveragePrice::rolling(10_000).unwrap();
+        twap.next(&(0, 10.0));
+        twap.next(&(6_000, 20.0));
+        assert!(!twap.is_ready());
+        twap.next(&(10_000, 20.0));
+        assert!(twap.is_ready());
+
+        let mut twap = TimeWeightedAveragePrice::anchored();
+        assert_eq!(twap.try_next(&(0, 10.0)).ok(), None);
+        assert_eq!(twap.try_next(&(1_000, 20.0)).ok(), Some(10.0));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut twap = TimeWeightedAveragePrice::rolling(100).unwrap();
+        twap.next(&(0, 10.0));
+        twap.next(&(10, 20.0));
+
+        twap.reset();
+        assert_eq!(twap.next(&(20, 30.0)), 30.0);
+        assert_eq!(twap.next(&(30, 0.0)), 30.0);
+    }
+
+    #[test]
+    fn test_display() {
+        let twap = TimeWeightedAveragePrice::rolling(60_000).unwrap();
+        assert_eq!(format!("{}", twap), "TWAP(60000)");
+        assert_eq!(
+            format!("{}", TimeWeightedAveragePrice::default()),
+            "TWAP(ANCHORED)"
+        );
+    }
+}
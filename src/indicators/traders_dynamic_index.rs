@@ -0,0 +1,260 @@
+use core::fmt;
+
+use crate::errors::*;
+use crate::indicators::{BollingerBands, RelativeStrengthIndex, SimpleMovingAverage};
+use crate::{Calculate, Close, Finite, Float, Next, Reset, Warmup};
+
+/// Traders Dynamic Index (TDI) by Dean Malone.
+///
+/// A [RSI](struct.RelativeStrengthIndex.html) dashboard: Bollinger Bands around the RSI
+/// show the volatility, their middle line the overall sentiment, and two moving averages
+/// of the RSI give the entry signals when they cross.
+///
+/// # Output
+///
+/// * _upper_, _lower_ - volatility bands, the [Bollinger Bands](struct.BollingerBands.html)
+///   of the RSI
+/// * _middle_ - market base line, the middle of the bands
+/// * _price_ - RSI price line, a short SMA of the RSI
+/// * _signal_ - trade signal line, a longer SMA of the RSI
+///
+/// # Parameters
+///
+/// * _rsi_length_ - length of the RSI (integer greater than 0). Default is 13.
+/// * _band_length_ - length of the bands (integer greater than 0). Default is 34.
+/// * _multiplier_ - width of the bands in standard deviations (greater than 0).
+///   Default is 1.6185.
+/// * _price_length_ - length of the price line (integer greater than 0). Default is 2.
+/// * _signal_length_ - length of the signal line (integer greater than 0). Default is 7.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::TradersDynamicIndex;
+/// use ta::Calculate;
+///
+/// let mut tdi = TradersDynamicIndex::new(3, 4, 2.0, 2, 3).unwrap();
+/// tdi.calc(10.0);
+/// let out = tdi.calc(11.0);
+/// assert!((out.upper - 112.5).abs() < 1e-9);
+/// assert_eq!(out.middle.round(), 71.0);
+/// assert_eq!(out.lower.round(), 29.0);
+/// assert_eq!(out.price.round(), 71.0);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(deserialize = "F: Float + serde::Deserialize<'de>"))
+)]
+pub struct TradersDynamicIndex<F = f64> {
+    rsi: RelativeStrengthIndex<F>,
+    bands: BollingerBands<F>,
+    price: SimpleMovingAverage<F>,
+    signal: SimpleMovingAverage<F>,
+    rsi_length: u32,
+    count: u32,
+}
+
+/// Output of [TradersDynamicIndex](struct.TradersDynamicIndex.html).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TradersDynamicIndexOutput<F = f64> {
+    pub upper: F,
+    pub middle: F,
+    pub lower: F,
+    pub price: F,
+    pub signal: F,
+}
+
+impl<F: Finite> Finite for TradersDynamicIndexOutput<F> {
+    fn is_finite(&self) -> bool {
+        self.upper.is_finite()
+            && self.middle.is_finite()
+            && self.lower.is_finite()
+            && self.price.is_finite()
+            && self.signal.is_finite()
+    }
+}
+
+impl TradersDynamicIndex {
+    pub fn new(
+        rsi_length: u32,
+        band_length: u32,
+        multiplier: f64,
+        price_length: u32,
+        signal_length: u32,
+    ) -> Result<Self> {
+        Self::with_type(
+            rsi_length,
+            band_length,
+            multiplier,
+            price_length,
+            signal_length,
+        )
+    }
+}
+
+impl<F: Float> TradersDynamicIndex<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(
+        rsi_length: u32,
+        band_length: u32,
+        multiplier: F,
+        price_length: u32,
+        signal_length: u32,
+    ) -> Result<Self> {
+        Ok(Self {
+            rsi: RelativeStrengthIndex::with_type(rsi_length)?,
+            bands: BollingerBands::with_type(band_length, multiplier)?,
+            price: SimpleMovingAverage::with_type(price_length)?,
+            signal: SimpleMovingAverage::with_type(signal_length)?,
+            rsi_length,
+            count: 0,
+        })
+    }
+}
+
+impl<F: Float> Calculate<F> for TradersDynamicIndex<F> {
+    type Output = TradersDynamicIndexOutput<F>;
+
+    fn calc(&mut self, input: F) -> Self::Output {
+        self.count = self.count.saturating_add(1);
+        let rsi = self.rsi.calc(input);
+        let bands = self.bands.calc(rsi);
+        TradersDynamicIndexOutput {
+            upper: bands.upper,
+            middle: bands.middle,
+            lower: bands.lower,
+            price: self.price.calc(rsi),
+            signal: self.signal.calc(rsi),
+        }
+    }
+}
+
+impl<F: Float, T: Close<F>> Next<T> for TradersDynamicIndex<F> {
+    type Output = TradersDynamicIndexOutput<F>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.calc(input.close())
+    }
+}
+
+impl<F: Float> Reset for TradersDynamicIndex<F> {
+    fn reset(&mut self) {
+        self.rsi.reset();
+        self.bands.reset();
+        self.price.reset();
+        self.signal.reset();
+        self.count = 0;
+    }
+}
+
+impl<F: Float> Warmup for TradersDynamicIndex<F> {
+    fn warmup_period(&self) -> u32 {
+        let lines = self
+            .bands
+            .warmup_period()
+            .max(self.price.warmup_period())
+            .max(self.signal.warmup_period());
+        self.rsi.warmup_period() + lines - 1
+    }
+
+    fn is_ready(&self) -> bool {
+        self.count >= self.warmup_period()
+    }
+}
+
+impl Default for TradersDynamicIndex {
+    fn default() -> Self {
+        Self::new(13, 34, 1.6185, 2, 7).unwrap()
+    }
+}
+
+impl<F: Float> fmt::Display for TradersDynamicIndex<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "TDI({}, {}, {}, {}, {})",
+            self.rsi_length,
+            self.bands.length(),
+            self.bands.multiplier(),
+            self.price.length(),
+            self.signal.length()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(TradersDynamicIndex);
+
+    fn round5(out: TradersDynamicIndexOutput) -> (f64, f64, f64, f64, f64) {
+        (
+            round(out.upper),
+            round(out.middle),
+            round(out.lower),
+            round(out.price),
+            round(out.signal),
+        )
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(TradersDynamicIndex::new(0, 34, 1.6, 2, 7).is_err());
+        assert!(TradersDynamicIndex::new(13, 0, 1.6, 2, 7).is_err());
+        assert!(TradersDynamicIndex::new(13, 34, 0.0, 2, 7).is_err());
+        assert!(TradersDynamicIndex::new(13, 34, 1.6, 0, 7).is_err());
+        assert!(TradersDynamicIndex::new(13, 34, 1.6, 2, 0).is_err());
+        assert!(TradersDynamicIndex::new(13, 34, 1.6, 2, 7).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut tdi = TradersDynamicIndex::new(3, 4, 2.0, 2, 3).unwrap();
+
+        assert_eq!(round5(tdi.calc(10.0)), (50.0, 50.0, 50.0, 50.0, 50.0));
+        assert_eq!(
+            round5(tdi.calc(11.0)),
+            (112.5, 70.833, 29.167, 70.833, 70.833)
+        );
+        assert_eq!(
+            round5(tdi.calc(10.5)),
+            (103.173, 63.889, 24.605, 70.833, 63.889)
+        );
+        assert_eq!(
+            round5(tdi.calc(12.0)),
+            (108.854, 69.563, 30.272, 68.293, 76.084)
+        );
+        assert_eq!(
+            round5(tdi.next(&Bar::new().close(11.5))),
+            (107.297, 71.612, 35.927, 72.391, 64.927)
+        );
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut tdi = TradersDynamicIndex::new(3, 4, 2.0, 2, 3).unwrap();
+        tdi.calc(10.0);
+        tdi.calc(11.0);
+
+        tdi.reset();
+        assert_eq!(round5(tdi.calc(10.0)), (50.0, 50.0, 50.0, 50.0, 50.0));
+    }
+
+    #[test]
+    fn test_default() {
+        TradersDynamicIndex::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            format!("{}", TradersDynamicIndex::default()),
+            "TDI(13, 34, 1.6185, 2, 7)"
+        );
+    }
+}
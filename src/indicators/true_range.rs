@@ -1,7 +1,7 @@
-use std::fmt;
+use core::fmt;
 
-use crate::helpers::max3;
-use crate::{Calculate, Close, High, Low, Next, Reset};
+use crate::helpers::{abs, max3};
+use crate::{Calculate, Close, High, Low, Next, Number, Reset, Warmup};
 
 /// The range of a day's trading is simply _high_ - _low_.
 /// The true range extends it to yesterday's closing price if it was outside of today's range.
@@ -48,12 +48,20 @@ use crate::{Calculate, Close, High, Low, Next, Reset};
 /// }
 /// ```
 #[derive(Debug, Clone)]
-pub struct TrueRange {
-    prev_close: Option<f64>,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TrueRange<F = f64> {
+    prev_close: Option<F>,
 }
 
 impl TrueRange {
     pub fn new() -> Self {
+        Self::with_type()
+    }
+}
+
+impl<F: Number> TrueRange<F> {
+    /// Like [new](#method.new), for values of any number type `F`.
+    pub fn with_type() -> Self {
         Self { prev_close: None }
     }
 }
@@ -64,30 +72,34 @@ impl Default for TrueRange {
     }
 }
 
-impl fmt::Display for TrueRange {
+impl<F: Number> fmt::Display for TrueRange<F> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "TRUE_RANGE()")
     }
 }
 
-impl Calculate for TrueRange {
-    fn calc(&mut self, input: f64) -> f64 {
+impl<F: Number> Calculate<F> for TrueRange<F> {
+    type Output = F;
+
+    fn calc(&mut self, input: F) -> F {
         let distance = match self.prev_close {
-            Some(prev) => (input - prev).abs(),
-            None => 0.0,
+            Some(prev) => abs(input - prev),
+            None => F::zero(),
         };
         self.prev_close = Some(input);
         distance
     }
 }
 
-impl<T: High + Low + Close> Next<T> for TrueRange {
-    fn next(&mut self, bar: &T) -> f64 {
+impl<F: Number, T: High<F> + Low<F> + Close<F>> Next<T> for TrueRange<F> {
+    type Output = F;
+
+    fn next(&mut self, bar: &T) -> F {
         let max_dist = match self.prev_close {
             Some(prev_close) => {
                 let dist1 = bar.high() - bar.low();
-                let dist2 = (bar.high() - prev_close).abs();
-                let dist3 = (bar.low() - prev_close).abs();
+                let dist2 = abs(bar.high() - prev_close);
+                let dist3 = abs(bar.low() - prev_close);
                 max3(dist1, dist2, dist3)
             }
             None => bar.high() - bar.low(),
@@ -97,12 +109,22 @@ impl<T: High + Low + Close> Next<T> for TrueRange {
     }
 }
 
-impl Reset for TrueRange {
+impl<F: Number> Reset for TrueRange<F> {
     fn reset(&mut self) {
         self.prev_close = None;
     }
 }
 
+impl<F: Number> Warmup for TrueRange<F> {
+    fn warmup_period(&self) -> u32 {
+        1
+    }
+
+    fn is_ready(&self) -> bool {
+        self.prev_close.is_some()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,283 @@
+use core::fmt;
+
+use crate::errors::*;
+use crate::{Calculate, Close, Float, Next, Reset, RingBuffer, Warmup};
+
+/// Variance (VAR).
+///
+/// Returns the variance of the last n values. The mean and the sum of squared deviations are
+/// updated with Welford's algorithm over the sliding window, so each value costs O(1)
+/// regardless of the window size. The rounding errors of the updates, which grow with the
+/// magnitude of the values, are dropped by recomputing both exactly from the window every n
+/// values, and a window of identical values has a variance of exactly 0.
+///
+/// By default it is the population variance, dividing by the number of values. The sample
+/// variance, set with [sample](#method.sample), divides by one less and is 0 until the
+/// window has two values.
+///
+/// # Formula
+///
+/// VAR = Σ (x<sub>i</sub> - μ)<sup>2</sup> / N
+///
+/// Where:
+///
+/// * _μ_ - mean of the values in the window.
+/// * _N_ - number of values in the window, N - 1 for the sample variance.
+/// * _x<sub>i</sub>_ - i-th value of the window.
+///
+/// # Parameters
+///
+/// * _n_ - number of periods (integer greater than 0)
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::Variance;
+/// use ta::Calculate;
+///
+/// let mut var = Variance::new(3).unwrap();
+/// assert_eq!(var.calc(10.0), 0.0);
+/// assert_eq!(var.calc(20.0), 25.0);
+///
+/// let mut var = Variance::new(3).unwrap().sample();
+/// assert_eq!(var.calc(10.0), 0.0);
+/// assert_eq!(var.calc(20.0), 50.0);
+/// ```
+///
+/// # Links
+///
+/// * [Variance, Wikipedia](https://en.wikipedia.org/wiki/Variance)
+/// * [Welford's online algorithm, Wikipedia](https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance#Welford's_online_algorithm)
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Variance<F = f64> {
+    n: u32,
+    sample: bool,
+    mean: F,
+    m2: F,
+    window: RingBuffer<F>,
+    // number of the latest values equal to the newest one
+    run: u32,
+    pushes: u32,
+}
+
+impl Variance {
+    pub fn new(n: u32) -> Result<Self> {
+        Self::with_type(n)
+    }
+}
+
+impl<F: Float> Variance<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(n: u32) -> Result<Self> {
+        match n {
+            0 => Err(Error::from_kind(ErrorKind::InvalidParameter)),
+            _ => Ok(Self {
+                n,
+                sample: false,
+                mean: F::zero(),
+                m2: F::zero(),
+                window: RingBuffer::new(n as usize),
+                run: 0,
+                pushes: 0,
+            }),
+        }
+    }
+
+    /// Switches to the sample variance, with Bessel's correction.
+    pub fn sample(mut self) -> Self {
+        self.sample = true;
+        self
+    }
+
+    pub fn is_sample(&self) -> bool {
+        self.sample
+    }
+
+    pub fn length(&self) -> u32 {
+        self.n
+    }
+
+    // mean and sum of squared deviations computed from the values of the window
+    fn refresh(&mut self) {
+        let len = F::cast(self.window.len());
+        self.mean = self.window.iter().fold(F::zero(), |sum, &x| sum + x) / len;
+        let mean = self.mean;
+        self.m2 = self
+            .window
+            .iter()
+            .fold(F::zero(), |sum, &x| sum + (x - mean) * (x - mean));
+    }
+}
+
+impl<F: Float> Calculate<F> for Variance<F> {
+    type Output = F;
+
+    fn calc(&mut self, input: F) -> F {
+        self.run = match self.window.newest() {
+            Some(&newest) if newest == input => self.run.saturating_add(1),
+            _ => 1,
+        };
+        let old_m = self.mean;
+        match self.window.push(input) {
+            None => {
+                let delta = input - old_m;
+                self.mean = old_m + delta / F::cast(self.window.len());
+                self.m2 += delta * (input - self.mean);
+            }
+            Some(old_val) => {
+                let delta = input - old_val;
+                self.mean = old_m + delta / F::cast(self.window.len());
+                self.m2 += delta * (input - self.mean + old_val - old_m);
+            }
+        }
+
+        let len = self.window.len();
+        if self.run as usize >= len {
+            self.mean = input;
+            self.m2 = F::zero();
+        } else if self.window.is_full() {
+            self.pushes += 1;
+            if self.pushes >= self.n {
+                self.pushes = 0;
+                self.refresh();
+            }
+        }
+
+        let count = if self.sample { len - 1 } else { len };
+        if count == 0 {
+            return F::zero();
+        }
+        // rounding errors of the running sum may push it below zero on flat windows
+        (self.m2 / F::cast(count)).max(F::zero())
+    }
+}
+
+impl<F: Float, T: Close<F>> Next<T> for Variance<F> {
+    type Output = F;
+
+    fn next(&mut self, input: &T) -> F {
+        self.calc(input.close())
+    }
+}
+
+impl<F: Float> Reset for Variance<F> {
+    fn reset(&mut self) {
+        self.mean = F::zero();
+        self.m2 = F::zero();
+        self.window.clear();
+        self.run = 0;
+        self.pushes = 0;
+    }
+}
+
+impl<F: Float> Warmup for Variance<F> {
+    fn warmup_period(&self) -> u32 {
+        self.n
+    }
+
+    fn is_ready(&self) -> bool {
+        self.window.is_full()
+    }
+}
+
+impl Default for Variance {
+    fn default() -> Self {
+        Self::new(9).unwrap()
+    }
+}
+
+impl<F: Float> fmt::Display for Variance<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = if self.sample { "SAMPLE_VAR" } else { "VAR" };
+        write!(f, "{}({})", name, self.n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::StandardDeviation;
+    use crate::test_helper::*;
+
+    test_indicator!(Variance);
+
+    #[test]
+    fn test_new() {
+        assert!(Variance::new(0).is_err());
+        assert!(Variance::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut var = Variance::new(4).unwrap();
+        assert_eq!(var.calc(10.0), 0.0);
+        assert_eq!(var.calc(20.0), 25.0);
+        assert_eq!(round(var.calc(30.0)), 66.667);
+        assert_eq!(var.calc(20.0), 50.0);
+        assert_eq!(var.calc(10.0), 50.0);
+        assert_eq!(var.calc(100.0), 1250.0);
+    }
+
+    #[test]
+    fn test_sample() {
+        let mut var = Variance::new(4).unwrap().sample();
+        assert!(var.is_sample());
+        assert_eq!(var.calc(10.0), 0.0);
+        assert_eq!(var.calc(20.0), 50.0);
+        assert_eq!(var.calc(30.0), 100.0);
+        assert_eq!(round(var.calc(20.0)), 66.667);
+        assert_eq!(round(var.calc(10.0)), 66.667);
+        assert_eq!(round(var.calc(100.0)), 1666.667);
+
+        // a single value has no spread
+        let mut var = Variance::new(1).unwrap().sample();
+        assert_eq!(var.calc(10.0), 0.0);
+        assert_eq!(var.calc(20.0), 0.0);
+    }
+
+    #[test]
+    fn test_large_offset() {
+        let mut var = Variance::new(5).unwrap();
+        let mut sd = StandardDeviation::new(5).unwrap();
+        for i in 0..50 {
+            let x = 5e7 + (i % 7) as f64 * 1234.5678;
+            var.calc(x);
+            sd.calc(x);
+        }
+        // a constant run is flat whatever the offset
+        for _ in 0..4 {
+            var.calc(5e7 + 0.1);
+            sd.calc(5e7 + 0.1);
+        }
+        assert_eq!(var.calc(5e7 + 0.1), 0.0);
+        assert_eq!(sd.calc(5e7 + 0.1), 0.0);
+
+        // the error of the updates doesn't build up over varying values
+        let values: Vec<f64> = (0..1003).map(|i| 1e9 + (i % 13) as f64 * 0.5).collect();
+        let mut var = Variance::new(10).unwrap();
+        let last = values.iter().map(|&x| var.calc(x)).last().unwrap();
+        let window = &values[993..];
+        let mean = window.iter().sum::<f64>() / 10.0;
+        let exact = window.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / 10.0;
+        assert_eq!(round(last), round(exact));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut var = Variance::new(3).unwrap();
+        var.calc(10.0);
+        var.calc(20.0);
+        var.reset();
+        assert!(!var.is_ready());
+        assert_eq!(var.calc(20.0), 0.0);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", Variance::new(5).unwrap()), "VAR(5)");
+        let var = Variance::new(5).unwrap().sample();
+        assert_eq!(format!("{}", var), "SAMPLE_VAR(5)");
+    }
+}
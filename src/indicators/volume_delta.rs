@@ -0,0 +1,392 @@
+use core::fmt;
+use core::marker::PhantomData;
+
+use crate::errors::*;
+use crate::indicators::{Divergence, DivergenceEvent};
+use crate::{AggressorVolume, Close, Float, Next, Number, Reset, Warmup};
+
+/// Volume delta.
+///
+/// Difference between the volume bought by aggressive buyers and the volume sold by
+/// aggressive sellers of a trade or a bar, positive when buyers lift the asks and negative
+/// when sellers hit the bids.
+///
+/// Its input is a trade with a known aggressor, or a bar with its volume split by the side of
+/// the aggressor, like the Binance kline of the adapters. [DataItem](../struct.DataItem.html)
+/// has no such split, so the delta of bars needs a bar type implementing
+/// [AggressorVolume](../trait.AggressorVolume.html).
+///
+/// # Formula
+///
+/// Delta = buy volume - sell volume
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::VolumeDelta;
+/// use ta::{Next, AggressorSide};
+///
+/// let mut delta = VolumeDelta::new();
+/// // timestamp, price, size and side of trades
+/// assert_eq!(delta.next(&(0, 10.0, 2.0, AggressorSide::Buy)), 2.0);
+/// assert_eq!(delta.next(&(1, 9.9, 3.0, AggressorSide::Sell)), -3.0);
+/// ```
+///
+/// # Links
+///
+/// * [Volume Delta, TradingView](https://www.tradingview.com/support/solutions/43000725058-volume-delta/)
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VolumeDelta<F = f64> {
+    count: u32,
+    number: PhantomData<F>,
+}
+
+impl VolumeDelta {
+    pub fn new() -> Self {
+        Self::with_type()
+    }
+}
+
+impl<F: Number> VolumeDelta<F> {
+    /// Like [new](#method.new), for values of any number type `F`.
+    pub fn with_type() -> Self {
+        Self {
+            count: 0,
+            number: PhantomData,
+        }
+    }
+}
+
+impl<F: Number, T: AggressorVolume<F>> Next<T> for VolumeDelta<F> {
+    type Output = F;
+
+    fn next(&mut self, input: &T) -> F {
+        self.count = self.count.saturating_add(1);
+        input.buy_volume() - input.sell_volume()
+    }
+}
+
+impl Default for VolumeDelta {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: Number> fmt::Display for VolumeDelta<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "DELTA")
+    }
+}
+
+impl<F: Number> Reset for VolumeDelta<F> {
+    fn reset(&mut self) {
+        self.count = 0;
+    }
+}
+
+impl<F: Number> Warmup for VolumeDelta<F> {
+    fn warmup_period(&self) -> u32 {
+        1
+    }
+
+    fn is_ready(&self) -> bool {
+        self.count >= 1
+    }
+}
+
+/// Cumulative volume delta (CVD).
+///
+/// Running total of the [volume delta](struct.VolumeDelta.html) of all trades or bars since
+/// the last reset. A rising CVD shows aggressive buying, a falling one aggressive selling.
+/// Session CVDs start over with every session by
+/// [IndicatorExt::on_session](../trait.IndicatorExt.html#method.on_session).
+///
+/// # Formula
+///
+/// CVD<sub>t</sub> = CVD<sub>t-1</sub> + buy volume<sub>t</sub> - sell volume<sub>t</sub>
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::CumulativeVolumeDelta;
+/// use ta::{Next, AggressorSide};
+///
+/// let mut cvd = CumulativeVolumeDelta::new();
+/// // timestamp, price, size and side of trades
+/// cvd.next(&(0, 10.0, 2.0, AggressorSide::Buy));
+/// cvd.next(&(1, 10.1, 1.5, AggressorSide::Buy));
+/// assert_eq!(cvd.next(&(2, 10.0, 1.0, AggressorSide::Sell)), 2.5);
+/// ```
+///
+/// # Links
+///
+/// * [Cumulative Volume Delta, TradingView](https://www.tradingview.com/support/solutions/43000725058-volume-delta/)
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CumulativeVolumeDelta<F = f64> {
+    cvd: F,
+    count: u32,
+}
+
+impl CumulativeVolumeDelta {
+    pub fn new() -> Self {
+        Self::with_type()
+    }
+}
+
+impl<F: Number> CumulativeVolumeDelta<F> {
+    /// Like [new](#method.new), for values of any number type `F`.
+    pub fn with_type() -> Self {
+        Self {
+            cvd: F::zero(),
+            count: 0,
+        }
+    }
+}
+
+impl<F: Number, T: AggressorVolume<F>> Next<T> for CumulativeVolumeDelta<F> {
+    type Output = F;
+
+    fn next(&mut self, input: &T) -> F {
+        self.cvd += input.buy_volume() - input.sell_volume();
+        self.count = self.count.saturating_add(1);
+        self.cvd
+    }
+}
+
+impl Default for CumulativeVolumeDelta {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: Number> fmt::Display for CumulativeVolumeDelta<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CVD")
+    }
+}
+
+impl<F: Number> Reset for CumulativeVolumeDelta<F> {
+    fn reset(&mut self) {
+        self.cvd = F::zero();
+        self.count = 0;
+    }
+}
+
+impl<F: Number> Warmup for CumulativeVolumeDelta<F> {
+    fn warmup_period(&self) -> u32 {
+        1
+    }
+
+    fn is_ready(&self) -> bool {
+        self.count >= 1
+    }
+}
+
+/// Divergence between the price and the cumulative volume delta.
+///
+/// A [Divergence](struct.Divergence.html) of the close and the
+/// [CVD](struct.CumulativeVolumeDelta.html) of the same bars: e.g. a higher high of the
+/// price on a lower high of the CVD is a rally on fading buying pressure, a regular bearish
+/// divergence. Divergences are returned _strength_ bars after the swing, like those of
+/// [Divergence](struct.Divergence.html).
+///
+/// # Parameters
+///
+/// * _strength_ - number of bars on each side of a swing (integer greater than 0).
+///   Default is 5.
+/// * _max_span_ - maximum number of bars between two compared swings (integer greater
+///   than 0). Default is 60.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{DeltaDivergence, DivergenceKind};
+/// use ta::{AggressorVolume, Close, Next};
+///
+/// struct Bar {
+///     close: f64,
+///     buy: f64,
+///     sell: f64,
+/// }
+///
+/// impl Close for Bar {
+///     fn close(&self) -> f64 {
+///         self.close
+///     }
+/// }
+///
+/// impl AggressorVolume for Bar {
+///     fn buy_volume(&self) -> f64 {
+///         self.buy
+///     }
+///
+///     fn sell_volume(&self) -> f64 {
+///         self.sell
+///     }
+/// }
+///
+/// let mut divergence = DeltaDivergence::new(1, 10).unwrap();
+/// // the second high of the price is higher, the CVD is lower after heavy selling
+/// let bars = [(10.0, 10.0, 0.0), (12.0, 50.0, 0.0), (11.0, 0.0, 40.0), (13.0, 10.0, 0.0)];
+/// for &(close, buy, sell) in &bars {
+///     assert_eq!(divergence.next(&Bar { close, buy, sell }), None);
+/// }
+/// let bar = Bar { close: 12.0, buy: 0.0, sell: 10.0 };
+/// let event = divergence.next(&bar).unwrap();
+/// assert_eq!(event.kind, DivergenceKind::RegularBearish);
+/// assert_eq!((event.previous_oscillator, event.oscillator), (60.0, 30.0));
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeltaDivergence<F = f64> {
+    cvd: CumulativeVolumeDelta<F>,
+    divergence: Divergence<F>,
+}
+
+impl DeltaDivergence {
+    pub fn new(strength: u32, max_span: u32) -> Result<Self> {
+        Self::with_type(strength, max_span)
+    }
+}
+
+impl<F: Float> DeltaDivergence<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(strength: u32, max_span: u32) -> Result<Self> {
+        Ok(Self {
+            cvd: CumulativeVolumeDelta::with_type(),
+            divergence: Divergence::with_type(strength, max_span)?,
+        })
+    }
+
+    pub fn strength(&self) -> u32 {
+        self.divergence.strength()
+    }
+
+    pub fn max_span(&self) -> u32 {
+        self.divergence.max_span()
+    }
+}
+
+impl<F: Float, T: Close<F> + AggressorVolume<F>> Next<T> for DeltaDivergence<F> {
+    type Output = Option<DivergenceEvent<F>>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let cvd = self.cvd.next(input);
+        self.divergence.next(&(input.close(), cvd))
+    }
+}
+
+impl<F: Float> Reset for DeltaDivergence<F> {
+    fn reset(&mut self) {
+        self.cvd.reset();
+        self.divergence.reset();
+    }
+}
+
+impl<F: Float> Warmup for DeltaDivergence<F> {
+    fn warmup_period(&self) -> u32 {
+        self.divergence.warmup_period()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.divergence.is_ready()
+    }
+}
+
+impl Default for DeltaDivergence {
+    fn default() -> Self {
+        Self::new(5, 60).unwrap()
+    }
+}
+
+impl<F: Float> fmt::Display for DeltaDivergence<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "DELTA_DIV({}, {})", self.strength(), self.max_span())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::DivergenceKind;
+    use crate::AggressorSide;
+
+    fn trade(size: f64, side: AggressorSide) -> (i64, f64, f64, AggressorSide) {
+        (0, 10.0, size, side)
+    }
+
+    struct Flow(f64, f64);
+
+    impl Close for Flow {
+        fn close(&self) -> f64 {
+            self.0
+        }
+    }
+
+    impl AggressorVolume for Flow {
+        fn buy_volume(&self) -> f64 {
+            self.1.max(0.0)
+        }
+
+        fn sell_volume(&self) -> f64 {
+            (-self.1).max(0.0)
+        }
+    }
+
+    #[test]
+    fn test_volume_delta() {
+        let mut delta = VolumeDelta::new();
+        assert!(!delta.is_ready());
+        assert_eq!(delta.next(&trade(1.5, AggressorSide::Buy)), 1.5);
+        assert_eq!(delta.next(&trade(0.5, AggressorSide::Sell)), -0.5);
+        assert!(delta.is_ready());
+        assert_eq!(format!("{}", delta), "DELTA");
+    }
+
+    #[test]
+    fn test_cumulative_volume_delta() {
+        let mut cvd = CumulativeVolumeDelta::new();
+        assert_eq!(cvd.next(&trade(1.0, AggressorSide::Buy)), 1.0);
+        assert_eq!(cvd.next(&trade(3.0, AggressorSide::Sell)), -2.0);
+        assert_eq!(cvd.next(&trade(0.5, AggressorSide::Buy)), -1.5);
+
+        cvd.reset();
+        assert!(!cvd.is_ready());
+        assert_eq!(cvd.next(&trade(2.0, AggressorSide::Sell)), -2.0);
+        assert_eq!(format!("{}", cvd), "CVD");
+    }
+
+    #[test]
+    fn test_delta_divergence() {
+        assert!(DeltaDivergence::new(0, 10).is_err());
+
+        // close and delta
+        let mut divergence = DeltaDivergence::new(1, 10).unwrap();
+        for &(close, delta) in &[(10.0, -5.0), (8.0, -20.0), (9.0, 10.0), (7.0, -5.0)] {
+            assert_eq!(divergence.next(&Flow(close, delta)), None);
+        }
+        // the lower low of the price at 7.0 has a higher low of the CVD
+        let event = divergence.next(&Flow(9.5, 1.0)).unwrap();
+        assert_eq!(event.kind, DivergenceKind::RegularBullish);
+        assert_eq!(
+            (event.previous_oscillator, event.oscillator),
+            (-25.0, -20.0)
+        );
+        assert!(divergence.is_ready());
+
+        divergence.reset();
+        assert!(!divergence.is_ready());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            format!("{}", DeltaDivergence::default()),
+            "DELTA_DIV(5, 60)"
+        );
+    }
+}
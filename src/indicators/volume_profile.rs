@@ -0,0 +1,716 @@
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::errors::*;
+use crate::{Finite, Float, High, Low, Next, Reset, RingBuffer, Volume, Warmup};
+
+/// Price bins of a [VolumeProfile](struct.VolumeProfile.html).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ProfileBins<F = f64> {
+    /// Bins of a fixed price step, aligned to its multiples, e.g. the tick size.
+    TickSize(F),
+    /// A fixed number of equal bins spanning the range of the profile.
+    Count(u32),
+}
+
+impl<F: fmt::Display> fmt::Display for ProfileBins<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ProfileBins::TickSize(size) => write!(f, "TICK({})", size),
+            ProfileBins::Count(count) => write!(f, "BINS({})", count),
+        }
+    }
+}
+
+/// Price bin of a [VolumeProfile](struct.VolumeProfile.html) histogram.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VolumeBin<F = f64> {
+    /// Lowest price of the bin.
+    pub low: F,
+    /// Highest price of the bin, the lowest price of the bin above.
+    pub high: F,
+    pub volume: F,
+}
+
+/// Output of [VolumeProfile](struct.VolumeProfile.html).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VolumeProfileOutput<F = f64> {
+    /// Point of control, the middle of the bin with the most volume.
+    pub poc: F,
+    /// Top of the value area.
+    pub value_area_high: F,
+    /// Bottom of the value area.
+    pub value_area_low: F,
+}
+
+impl<F: Finite> Finite for VolumeProfileOutput<F> {
+    fn is_finite(&self) -> bool {
+        self.poc.is_finite() && self.value_area_high.is_finite() && self.value_area_low.is_finite()
+    }
+}
+
+/// Volume profile.
+///
+/// Histogram of the volume traded at each price over the last _length_ bars, or over all
+/// bars since the last reset for a [cumulative](#method.cumulative) profile. The volume of a
+/// bar is spread evenly over its high-low range, and accumulated into price
+/// [bins](enum.ProfileBins.html) of a fixed step or a fixed number of bins spanning the
+/// range of the profile.
+///
+/// Every bar returns the point of control (POC), the middle of the bin with the most volume,
+/// and the value area around it: starting from the POC, the neighbouring bin with more
+/// volume is added until the area holds _value_area_ of the total volume. The full
+/// histogram is available with [histogram](#method.histogram). Without any volume, the
+/// POC and the value area are the middle of the latest bar.
+///
+/// A session profile, the market profile of a trading day, is a cumulative profile started
+/// over with every session by
+/// [IndicatorExt::on_session](../trait.IndicatorExt.html#method.on_session).
+///
+/// The bins are updated incrementally. A cumulative profile with a number of bins keeps no
+/// bars: when its range grows, the volume of the old bins is redistributed over the new
+/// ones, as if it was spread evenly over each bin. A profile of the last _length_ bars
+/// rebuilds its bins from the bars only when its range changes. Bars which would make a
+/// tick size profile span more than 100 000 bins, usually bad ticks, are left out of it.
+///
+/// # Parameters
+///
+/// * _bins_ - size or number of the price bins (number or integer greater than 0).
+/// * _length_ - number of bars (integer greater than 0), none for a cumulative profile.
+/// * _value_area_ - fraction of the volume in the value area (greater than 0, at most 1),
+///   set with [value_area](#method.value_area). Default is 0.7.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{ProfileBins, VolumeProfile};
+/// use ta::Next;
+///
+/// let mut profile = VolumeProfile::new(ProfileBins::TickSize(1.0), 20).unwrap();
+/// // open, high, low, close and volume
+/// profile.next(&(10.0, 12.0, 10.0, 11.0, 200.0));
+/// profile.next(&(11.0, 12.0, 11.0, 11.5, 300.0));
+/// let output = profile.next(&(11.5, 14.0, 11.0, 13.0, 60.0));
+///
+/// // 100, 100 + 300 + 20, 20 and 20 traded at 10, 11, 12 and 13
+/// assert_eq!(output.poc, 11.5);
+/// assert_eq!((output.value_area_low, output.value_area_high), (11.0, 12.0));
+/// assert_eq!(profile.histogram().len(), 4);
+/// ```
+///
+/// # Links
+///
+/// * [Volume Profile, TradingView](https://www.tradingview.com/support/solutions/43000502040-volume-profile/)
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VolumeProfile<F = f64> {
+    bins: ProfileBins<F>,
+    length: Option<u32>,
+    value_area: F,
+    // low, high and volume of the last length bars, which the profile is rebuilt from or
+    // evicts, none for the bars left out of it; a cumulative profile keeps no bars
+    bars: Option<RingBuffer<Option<(F, F, F)>>>,
+    // volume of each tick bin from the lowest one, and the number of bars contributing to it
+    ticks: VecDeque<(F, u32)>,
+    first_tick: i64,
+    // range and volume of each bin of a profile with a number of bins
+    range: Option<(F, F)>,
+    volumes: Vec<F>,
+    middle: F,
+    count: u64,
+}
+
+impl VolumeProfile {
+    /// Creates a profile of the last _length_ bars.
+    pub fn new(bins: ProfileBins<f64>, length: u32) -> Result<Self> {
+        Self::with_type(bins, length)
+    }
+
+    /// Creates a profile of all bars since the last reset.
+    pub fn cumulative(bins: ProfileBins<f64>) -> Result<Self> {
+        Self::with_length(bins, None)
+    }
+}
+
+impl<F: Float> VolumeProfile<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(bins: ProfileBins<F>, length: u32) -> Result<Self> {
+        if length == 0 {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        Self::with_length(bins, Some(length))
+    }
+
+    fn with_length(bins: ProfileBins<F>, length: Option<u32>) -> Result<Self> {
+        let valid = match bins {
+            ProfileBins::TickSize(size) => size > F::zero() && size.is_finite(),
+            ProfileBins::Count(count) => count > 0,
+        };
+        if !valid {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        Ok(Self {
+            bins,
+            length,
+            value_area: F::cast(0.7),
+            bars: length.map(|length| RingBuffer::new(length as usize)),
+            ticks: VecDeque::new(),
+            first_tick: 0,
+            range: None,
+            volumes: match bins {
+                ProfileBins::Count(count) => alloc::vec![F::zero(); count as usize],
+                ProfileBins::TickSize(_) => Vec::new(),
+            },
+            middle: F::nan(),
+            count: 0,
+        })
+    }
+
+    /// Sets the fraction of the volume in the value area.
+    pub fn value_area(mut self, value_area: F) -> Result<Self> {
+        if !(value_area > F::zero() && value_area <= F::one()) {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        self.value_area = value_area;
+        Ok(self)
+    }
+
+    pub fn bins(&self) -> ProfileBins<F> {
+        self.bins
+    }
+
+    pub fn length(&self) -> Option<u32> {
+        self.length
+    }
+
+    /// Bins of the profile in ascending order of price, including empty bins between them.
+    pub fn histogram(&self) -> Vec<VolumeBin<F>> {
+        match self.bins {
+            ProfileBins::TickSize(size) => (self.first_tick..)
+                .zip(&self.ticks)
+                .map(|(index, bin)| {
+                    let low = F::cast(index) * size;
+                    VolumeBin {
+                        low,
+                        high: low + size,
+                        volume: bin.0,
+                    }
+                })
+                .collect(),
+            ProfileBins::Count(count) => {
+                let (low, high) = match self.range {
+                    Some(range) => range,
+                    None => return Vec::new(),
+                };
+                let width = (high - low) / F::cast(count);
+                if width == F::zero() {
+                    let volume = self.volumes[0];
+                    return alloc::vec![VolumeBin { low, high, volume }];
+                }
+                self.volumes
+                    .iter()
+                    .enumerate()
+                    .map(|(index, &volume)| VolumeBin {
+                        low: low + F::cast(index) * width,
+                        high: low + F::cast(index + 1) * width,
+                        volume,
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    // indexes of the lowest and the highest tick bin of the bar
+    fn tick_range(bar: (F, F, F), size: F) -> Option<(i64, i64)> {
+        let first = (bar.0 / size).floor().to_i64()?;
+        let end = (bar.1 / size).floor().to_i64()?;
+        Some((first, end))
+    }
+
+    // whether the tick bins can take the bar without spanning too many bins
+    fn fits_ticks(&self, bar: (F, F, F), size: F) -> bool {
+        if !is_valid(bar) {
+            return true;
+        }
+        let (mut first, mut end) = match Self::tick_range(bar, size) {
+            Some(range) => range,
+            None => return false,
+        };
+        if !self.ticks.is_empty() {
+            first = first.min(self.first_tick);
+            end = end.max(self.first_tick + self.ticks.len() as i64 - 1);
+        }
+        end.saturating_sub(first) < MAX_TICK_BINS
+    }
+
+    // adds the bar to the tick bins, or removes it
+    fn update_ticks(&mut self, bar: (F, F, F), size: F, add: bool) {
+        if !is_valid(bar) {
+            return;
+        }
+        if add {
+            // fits_ticks has checked the range
+            let (first, end) = Self::tick_range(bar, size).unwrap_or_default();
+            if self.ticks.is_empty() {
+                self.first_tick = first;
+            }
+            while self.first_tick > first {
+                self.ticks.push_front((F::zero(), 0));
+                self.first_tick -= 1;
+            }
+            while self.first_tick + (self.ticks.len() as i64) <= end {
+                self.ticks.push_back((F::zero(), 0));
+            }
+        }
+
+        let (ticks, first_tick) = (&mut self.ticks, self.first_tick);
+        spread(bar, F::zero(), size, i64::MAX, |index, volume| {
+            let bin = &mut ticks[(index - first_tick) as usize];
+            if add {
+                *bin = (bin.0 + volume, bin.1 + 1);
+            } else if bin.1 > 1 {
+                *bin = (bin.0 - volume, bin.1 - 1);
+            } else {
+                *bin = (F::zero(), 0);
+            }
+        });
+
+        // drops the bins no bar contributes to anymore from both ends
+        while self.ticks.front().is_some_and(|bin| bin.1 == 0) {
+            self.ticks.pop_front();
+            self.first_tick += 1;
+        }
+        while self.ticks.back().is_some_and(|bin| bin.1 == 0) {
+            self.ticks.pop_back();
+        }
+    }
+
+    // adds the bar to the bins of a profile with a number of bins
+    fn add_to_bins(&mut self, bar: (F, F, F)) {
+        if !is_valid(bar) {
+            return;
+        }
+        let range = match self.range {
+            Some((low, high)) => (low.min(bar.0), high.max(bar.1)),
+            None => (bar.0, bar.1),
+        };
+        if self.range != Some(range) {
+            if self.length.is_some() {
+                // the window holds the bars to rebuild from
+                self.range = Some(range);
+                self.rebuild();
+                return;
+            }
+            self.rebin(range);
+        }
+        spread_bins(bar, range, &mut self.volumes, F::one());
+    }
+
+    // replaces an evicted bar with the newest one in the bins of a profile with a number of
+    // bins, the window holds the newest bar already
+    fn replace_in_bins(&mut self, bar: (F, F, F), evicted: (F, F, F)) {
+        let range = self
+            .bars
+            .iter()
+            .flatten()
+            .flatten()
+            .filter(|bar| is_valid(**bar))
+            .fold(None, |range: Option<(F, F)>, bar| match range {
+                Some((low, high)) => Some((low.min(bar.0), high.max(bar.1))),
+                None => Some((bar.0, bar.1)),
+            });
+        if range != self.range {
+            self.range = range;
+            self.rebuild();
+        } else if let Some(range) = range {
+            spread_bins(bar, range, &mut self.volumes, F::one());
+            spread_bins(evicted, range, &mut self.volumes, -F::one());
+        }
+    }
+
+    // spreads the bars of the window over the bins of the current range
+    fn rebuild(&mut self) {
+        self.volumes
+            .iter_mut()
+            .for_each(|volume| *volume = F::zero());
+        if let Some(range) = self.range {
+            for &bar in self.bars.iter().flatten().flatten() {
+                spread_bins(bar, range, &mut self.volumes, F::one());
+            }
+        }
+    }
+
+    // redistributes the volume of the bins over the bins of a wider range
+    fn rebin(&mut self, range: (F, F)) {
+        let mut volumes = alloc::vec![F::zero(); self.volumes.len()];
+        if let Some((low, high)) = self.range {
+            let width = (high - low) / F::cast(self.volumes.len());
+            for (index, &volume) in self.volumes.iter().enumerate() {
+                let bin_low = low + F::cast(index) * width;
+                let bin = (bin_low, (bin_low + width).min(high), volume);
+                spread_bins(bin, range, &mut volumes, F::one());
+            }
+        }
+        self.range = Some(range);
+        self.volumes = volumes;
+    }
+
+    // bins with volume in ascending order of price, with their indexes
+    fn filled_bins(&self) -> Vec<(i64, VolumeBin<F>)> {
+        match self.bins {
+            ProfileBins::TickSize(size) => (self.first_tick..)
+                .zip(&self.ticks)
+                .filter(|(_, bin)| bin.0 > F::zero())
+                .map(|(index, bin)| {
+                    let low = F::cast(index) * size;
+                    let high = low + size;
+                    (
+                        index,
+                        VolumeBin {
+                            low,
+                            high,
+                            volume: bin.0,
+                        },
+                    )
+                })
+                .collect(),
+            ProfileBins::Count(_) => (0..)
+                .zip(self.histogram())
+                .filter(|(_, bin)| bin.volume > F::zero())
+                .collect(),
+        }
+    }
+
+    // The value area grows from the POC towards the neighbouring bin with more volume.
+    // Empty bins have no volume and are not listed: a gap is crossed at once, as stepping
+    // over its empty bins one by one would.
+    fn output(&self) -> VolumeProfileOutput<F> {
+        let filled = self.filled_bins();
+        let bins: Vec<_> = filled.iter().map(|bin| bin.1).collect();
+        let total = bins.iter().fold(F::zero(), |sum, bin| sum + bin.volume);
+        if total <= F::zero() {
+            return VolumeProfileOutput {
+                poc: self.middle,
+                value_area_high: self.middle,
+                value_area_low: self.middle,
+            };
+        }
+
+        let poc = (1..bins.len()).fold(0, |poc, index| {
+            if bins[index].volume > bins[poc].volume {
+                index
+            } else {
+                poc
+            }
+        });
+        let (mut lower, mut upper) = (poc, poc);
+        let mut volume = bins[poc].volume;
+        let adjacent = |a: usize, b: usize| filled[a].0 + 1 == filled[b].0;
+        while volume < self.value_area * total && (lower > 0 || upper + 1 < bins.len()) {
+            let below = match lower {
+                0 => -F::one(),
+                _ if adjacent(lower - 1, lower) => bins[lower - 1].volume,
+                _ => F::zero(),
+            };
+            let above = match bins.get(upper + 1) {
+                None => -F::one(),
+                Some(bin) if adjacent(upper, upper + 1) => bin.volume,
+                Some(_) => F::zero(),
+            };
+            if above >= below {
+                upper += 1;
+                volume += above;
+            } else {
+                lower -= 1;
+                volume += below;
+            }
+        }
+        VolumeProfileOutput {
+            poc: (bins[poc].low + bins[poc].high) / F::cast(2.0),
+            value_area_high: bins[upper].high,
+            value_area_low: bins[lower].low,
+        }
+    }
+}
+
+// widest span of a tick size profile, in bins
+const MAX_TICK_BINS: i64 = 100_000;
+
+fn is_valid<F: Float>((low, high, volume): (F, F, F)) -> bool {
+    low <= high && volume >= F::zero()
+}
+
+// Spreads the volume of a bar over the bins of `width` starting at `origin`, passing the
+// index of every bin the bar overlaps with its share. Indexes above `last` are clamped.
+fn spread<F: Float, A: FnMut(i64, F)>(bar: (F, F, F), origin: F, width: F, last: i64, mut add: A) {
+    let (low, high, volume) = bar;
+    if !is_valid(bar) {
+        return;
+    }
+    let index = |price: F| {
+        ((price - origin) / width)
+            .floor()
+            .to_i64()
+            .unwrap_or(last)
+            .min(last)
+    };
+    let (first, end) = (index(low), index(high));
+    if first == end {
+        add(first, volume);
+        return;
+    }
+    for i in first..=end {
+        let bin_low = origin + F::cast(i) * width;
+        let bin_high = if i == last { high } else { bin_low + width };
+        let overlap = high.min(bin_high) - low.max(bin_low);
+        if overlap > F::zero() {
+            add(i, volume * overlap / (high - low));
+        }
+    }
+}
+
+// Adds the volume of a bar, times `sign`, to the bins spanning `range`.
+fn spread_bins<F: Float>(bar: (F, F, F), range: (F, F), volumes: &mut [F], sign: F) {
+    if !is_valid(bar) {
+        return;
+    }
+    let width = (range.1 - range.0) / F::cast(volumes.len());
+    if width == F::zero() {
+        volumes[0] += sign * bar.2;
+        return;
+    }
+    let last = volumes.len() as i64 - 1;
+    spread(bar, range.0, width, last, |index, volume| {
+        volumes[index.max(0) as usize] += sign * volume
+    });
+}
+
+impl<F: Float, T: High<F> + Low<F> + Volume<F>> Next<T> for VolumeProfile<F> {
+    type Output = VolumeProfileOutput<F>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let bar = (input.low(), input.high(), input.volume());
+        self.count += 1;
+        self.middle = (bar.0 + bar.1) / F::cast(2.0);
+
+        let included = match self.bins {
+            ProfileBins::TickSize(size) if self.fits_ticks(bar, size) => {
+                self.update_ticks(bar, size, true);
+                Some(bar)
+            }
+            ProfileBins::TickSize(_) => None,
+            ProfileBins::Count(_) => Some(bar),
+        };
+        let evicted = self
+            .bars
+            .as_mut()
+            .and_then(|bars| bars.push(included))
+            .flatten();
+        match (self.bins, evicted) {
+            (ProfileBins::TickSize(size), Some(evicted)) => self.update_ticks(evicted, size, false),
+            (ProfileBins::TickSize(_), None) => {}
+            (ProfileBins::Count(_), Some(evicted)) => self.replace_in_bins(bar, evicted),
+            (ProfileBins::Count(_), None) => self.add_to_bins(bar),
+        }
+        self.output()
+    }
+}
+
+impl<F: Float> Reset for VolumeProfile<F> {
+    fn reset(&mut self) {
+        if let Some(bars) = &mut self.bars {
+            bars.clear();
+        }
+        self.ticks.clear();
+        self.range = None;
+        self.volumes
+            .iter_mut()
+            .for_each(|volume| *volume = F::zero());
+        self.middle = F::nan();
+        self.count = 0;
+    }
+}
+
+impl<F: Float> Warmup for VolumeProfile<F> {
+    fn warmup_period(&self) -> u32 {
+        self.length.unwrap_or(1)
+    }
+
+    fn is_ready(&self) -> bool {
+        self.count >= u64::from(self.warmup_period())
+    }
+}
+
+impl<F: Float> fmt::Display for VolumeProfile<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.length {
+            Some(length) => write!(f, "VP({}, {})", self.bins, length),
+            None => write!(f, "VP({})", self.bins),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    fn bar(high: f64, low: f64, volume: f64) -> Bar {
+        Bar::new().high(high).low(low).volume(volume)
+    }
+
+    fn volumes(profile: &VolumeProfile) -> Vec<(f64, f64)> {
+        profile
+            .histogram()
+            .iter()
+            .map(|bin| (bin.low, round(bin.volume)))
+            .collect()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(VolumeProfile::new(ProfileBins::TickSize(1.0), 0).is_err());
+        assert!(VolumeProfile::new(ProfileBins::TickSize(0.0), 5).is_err());
+        assert!(VolumeProfile::new(ProfileBins::TickSize(f64::NAN), 5).is_err());
+        assert!(VolumeProfile::cumulative(ProfileBins::Count(0)).is_err());
+        let profile = VolumeProfile::cumulative(ProfileBins::Count(10)).unwrap();
+        assert!(profile.clone().value_area(0.0).is_err());
+        assert!(profile.clone().value_area(1.1).is_err());
+        assert!(profile.value_area(1.0).is_ok());
+    }
+
+    #[test]
+    fn test_tick_size() {
+        let mut profile = VolumeProfile::new(ProfileBins::TickSize(0.5), 2).unwrap();
+        profile.next(&bar(11.0, 10.0, 100.0));
+        let output = profile.next(&bar(12.0, 11.5, 40.0));
+        // the empty bin at 11.0 is part of the histogram
+        assert_eq!(
+            volumes(&profile),
+            vec![(10.0, 50.0), (10.5, 50.0), (11.0, 0.0), (11.5, 40.0)]
+        );
+        assert_eq!(output.poc, 10.25);
+        assert_eq!(
+            (output.value_area_low, output.value_area_high),
+            (10.0, 11.0)
+        );
+
+        // the first bar leaves the window
+        let output = profile.next(&bar(11.7, 11.6, 10.0));
+        assert_eq!(volumes(&profile), vec![(11.5, 50.0)]);
+        assert_eq!(output.poc, 11.75);
+    }
+
+    #[test]
+    fn test_count() {
+        let mut profile = VolumeProfile::cumulative(ProfileBins::Count(4)).unwrap();
+        let output = profile.next(&bar(10.0, 10.0, 100.0));
+        assert_eq!(volumes(&profile), vec![(10.0, 100.0)]);
+        assert_eq!(output.poc, 10.0);
+
+        // the bins span 10 to 14
+        profile.next(&bar(14.0, 12.0, 100.0));
+        let output = profile.next(&bar(13.0, 12.0, 100.0));
+        assert_eq!(
+            volumes(&profile),
+            vec![(10.0, 100.0), (11.0, 0.0), (12.0, 150.0), (13.0, 50.0)]
+        );
+        assert_eq!(output.poc, 12.5);
+        assert_eq!(
+            (output.value_area_low, output.value_area_high),
+            (10.0, 14.0)
+        );
+    }
+
+    #[test]
+    fn test_count_window() {
+        let mut profile = VolumeProfile::new(ProfileBins::Count(2), 2).unwrap();
+        profile.next(&bar(12.0, 10.0, 100.0));
+        profile.next(&bar(11.0, 10.0, 50.0));
+        assert_eq!(volumes(&profile), vec![(10.0, 100.0), (11.0, 50.0)]);
+
+        // the range shrinks to the remaining bars
+        profile.next(&bar(11.0, 10.5, 10.0));
+        assert_eq!(volumes(&profile), vec![(10.0, 25.0), (10.5, 35.0)]);
+        // the range is the same, the evicted bar is taken out of the bins
+        profile.next(&bar(10.5, 10.0, 20.0));
+        assert_eq!(volumes(&profile), vec![(10.0, 20.0), (10.5, 10.0)]);
+    }
+
+    #[test]
+    fn test_count_cumulative_rebin() {
+        let mut profile = VolumeProfile::cumulative(ProfileBins::Count(2)).unwrap();
+        profile.next(&bar(12.0, 10.0, 100.0));
+        // the bins of 10 to 12 are spread over the bins of 10 to 14
+        profile.next(&bar(14.0, 13.0, 40.0));
+        assert_eq!(volumes(&profile), vec![(10.0, 100.0), (12.0, 40.0)]);
+
+        for i in 0..20_000 {
+            let price = 100.0 + (i % 200) as f64;
+            profile.next(&bar(price + 1.0, price, 1.0));
+        }
+        let total: f64 = profile.histogram().iter().map(|bin| bin.volume).sum();
+        assert_eq!(round(total), 20_140.0);
+    }
+
+    #[test]
+    fn test_bad_tick() {
+        let mut profile = VolumeProfile::new(ProfileBins::TickSize(0.01), 2).unwrap();
+        profile.next(&bar(10.02, 10.0, 10.0));
+        let output = profile.next(&bar(1e9, 0.0, 10.0));
+        assert_eq!(volumes(&profile), vec![(10.0, 5.0), (10.01, 5.0)]);
+        assert_eq!(round(output.poc), 10.005);
+
+        // the bar left out is not evicted
+        profile.next(&bar(10.01, 10.01, 1.0));
+        profile.next(&bar(10.01, 10.01, 1.0));
+        assert_eq!(volumes(&profile), vec![(10.01, 2.0)]);
+    }
+
+    #[test]
+    fn test_value_area() {
+        let mut profile = VolumeProfile::cumulative(ProfileBins::TickSize(1.0))
+            .unwrap()
+            .value_area(0.5)
+            .unwrap();
+        for &(price, volume) in &[(1.0, 10.0), (2.0, 20.0), (3.0, 30.0), (4.0, 25.0)] {
+            profile.next(&bar(price + 0.5, price + 0.5, volume));
+        }
+        let output = profile.next(&bar(5.5, 5.5, 15.0));
+        assert_eq!(output.poc, 3.5);
+        // 30 + 25 of 100
+        assert_eq!((output.value_area_low, output.value_area_high), (3.0, 5.0));
+    }
+
+    #[test]
+    fn test_without_volume() {
+        let mut profile = VolumeProfile::new(ProfileBins::TickSize(1.0), 3).unwrap();
+        let output = profile.next(&bar(12.0, 10.0, 0.0));
+        assert_eq!(output.poc, 11.0);
+        assert_eq!(output.value_area_high, 11.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut profile = VolumeProfile::new(ProfileBins::Count(5), 2).unwrap();
+        profile.next(&bar(12.0, 10.0, 10.0));
+        profile.next(&bar(12.0, 10.0, 10.0));
+        assert!(profile.is_ready());
+        profile.reset();
+        assert!(!profile.is_ready());
+        assert!(profile.histogram().is_empty());
+    }
+
+    #[test]
+    fn test_display() {
+        let profile = VolumeProfile::new(ProfileBins::TickSize(0.25), 50).unwrap();
+        assert_eq!(format!("{}", profile), "VP(TICK(0.25), 50)");
+        let profile = VolumeProfile::cumulative(ProfileBins::Count(24)).unwrap();
+        assert_eq!(format!("{}", profile), "VP(BINS(24))");
+    }
+}
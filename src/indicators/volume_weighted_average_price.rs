@@ -0,0 +1,395 @@
+use core::fmt;
+
+use crate::sessions::{Session, TimeZone};
+use crate::{
+    Close, Finite, Float, High, Low, Next, Open, PriceSource, Reset, Timestamp, Volume, Warmup,
+};
+
+/// When a [VolumeWeightedAveragePrice](struct.VolumeWeightedAveragePrice.html) starts over.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VwapAnchor {
+    /// Never, the VWAP accumulates every bar since the last reset.
+    Cumulative,
+    /// At the start of every session; bars outside of the session continue the previous one.
+    Session(Session),
+    /// At local midnight of every day.
+    Daily(TimeZone),
+    /// At local midnight of every Monday.
+    Weekly(TimeZone),
+    /// Whenever the callback returns `true` for the timestamps of the previous and the current
+    /// bar. Not serializable.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    Custom(fn(i64, i64) -> bool),
+}
+
+impl VwapAnchor {
+    fn is_new_period(&self, prev: i64, timestamp: i64) -> bool {
+        match self {
+            VwapAnchor::Cumulative => false,
+            VwapAnchor::Session(session) => session.is_new_session(prev, timestamp),
+            VwapAnchor::Daily(tz) => tz.day(prev) != tz.day(timestamp),
+            // 1970-01-01 was a Thursday, day 4 the first Monday
+            VwapAnchor::Weekly(tz) => {
+                (tz.day(prev) + 3).div_euclid(7) != (tz.day(timestamp) + 3).div_euclid(7)
+            }
+            VwapAnchor::Custom(f) => f(prev, timestamp),
+        }
+    }
+}
+
+impl fmt::Display for VwapAnchor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            VwapAnchor::Cumulative => "CUMULATIVE",
+            VwapAnchor::Session(_) => "SESSION",
+            VwapAnchor::Daily(_) => "DAILY",
+            VwapAnchor::Weekly(_) => "WEEKLY",
+            VwapAnchor::Custom(_) => "CUSTOM",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Standard deviation bands of a
+/// [VolumeWeightedAveragePrice](struct.VolumeWeightedAveragePrice.html).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VwapBands<F = f64> {
+    /// VWAP + 1σ.
+    pub upper_1: F,
+    /// VWAP - 1σ.
+    pub lower_1: F,
+    /// VWAP + 2σ.
+    pub upper_2: F,
+    /// VWAP - 2σ.
+    pub lower_2: F,
+}
+
+/// Output of [VolumeWeightedAveragePrice](struct.VolumeWeightedAveragePrice.html).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VolumeWeightedAveragePriceOutput<F = f64> {
+    pub vwap: F,
+    /// Bands, if enabled with [bands](struct.VolumeWeightedAveragePrice.html#method.bands).
+    pub bands: Option<VwapBands<F>>,
+}
+
+impl<F: Finite> Finite for VwapBands<F> {
+    fn is_finite(&self) -> bool {
+        self.upper_1.is_finite()
+            && self.lower_1.is_finite()
+            && self.upper_2.is_finite()
+            && self.lower_2.is_finite()
+    }
+}
+
+impl<F: Finite> Finite for VolumeWeightedAveragePriceOutput<F> {
+    fn is_finite(&self) -> bool {
+        self.vwap.is_finite() && self.bands.iter().all(Finite::is_finite)
+    }
+}
+
+/// Volume-weighted average price (VWAP).
+///
+/// Average of the prices of all bars since the anchor, weighted by their volumes. The price
+/// of a bar is its typical price by default, or another [PriceSource](../enum.PriceSource.html)
+/// set with [price](#method.price). The VWAP starts over at the boundaries given by its
+/// [VwapAnchor](enum.VwapAnchor.html): trading sessions, days, weeks or a custom callback.
+///
+/// Bars need a [Timestamp](../trait.Timestamp.html) besides prices and volume. Until any
+/// volume is traded since the anchor, the VWAP equals the latest price.
+///
+/// # Formula
+///
+/// VWAP = Σ(_p_ * _v_) / Σ(_v_)
+///
+/// σ = sqrt(Σ(_p_<sup>2</sup> * _v_) / Σ(_v_) - VWAP<sup>2</sup>)
+///
+/// Where _p_ and _v_ are the prices and volumes of the bars since the anchor, and σ the
+/// volume-weighted standard deviation of the prices around the VWAP, from which the optional
+/// ±1σ and ±2σ bands are placed.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{VolumeWeightedAveragePrice, VwapAnchor};
+/// use ta::sessions::{TimeZone, HOUR};
+/// use ta::{Close, High, Low, Next, Open, PriceSource, Timestamp, Volume};
+///
+/// struct Bar(i64, f64, f64);
+///
+/// impl Timestamp for Bar {
+///     fn timestamp(&self) -> i64 {
+///         self.0
+///     }
+/// }
+/// impl Open for Bar {
+///     fn open(&self) -> f64 {
+///         self.1
+///     }
+/// }
+/// impl High for Bar {
+///     fn high(&self) -> f64 {
+///         self.1
+///     }
+/// }
+/// impl Low for Bar {
+///     fn low(&self) -> f64 {
+///         self.1
+///     }
+/// }
+/// impl Close for Bar {
+///     fn close(&self) -> f64 {
+///         self.1
+///     }
+/// }
+/// impl Volume for Bar {
+///     fn volume(&self) -> f64 {
+///         self.2
+///     }
+/// }
+///
+/// let mut vwap = VolumeWeightedAveragePrice::new(VwapAnchor::Daily(TimeZone::UTC))
+///     .price(PriceSource::Close)
+///     .bands();
+/// assert_eq!(vwap.next(&Bar(0, 10.0, 100.0)).vwap, 10.0);
+///
+/// let out = vwap.next(&Bar(HOUR, 20.0, 300.0));
+/// assert_eq!(out.vwap, 17.5);
+/// let bands = out.bands.unwrap();
+/// assert_eq!((bands.lower_1, bands.upper_1), (13.169872981077806, 21.830127018922195));
+///
+/// // a new day
+/// assert_eq!(vwap.next(&Bar(24 * HOUR, 30.0, 10.0)).vwap, 30.0);
+/// ```
+///
+/// # Links
+///
+/// * [Volume-weighted average price, Wikipedia](https://en.wikipedia.org/wiki/Volume-weighted_average_price)
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VolumeWeightedAveragePrice<F = f64> {
+    anchor: VwapAnchor,
+    source: PriceSource,
+    bands: bool,
+    // sums of volume, price * volume and price^2 * volume since the anchor
+    volume: F,
+    value: F,
+    squares: F,
+    last: Option<i64>,
+}
+
+impl VolumeWeightedAveragePrice {
+    pub fn new(anchor: VwapAnchor) -> Self {
+        Self::with_type(anchor)
+    }
+}
+
+impl<F: Float> VolumeWeightedAveragePrice<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(anchor: VwapAnchor) -> Self {
+        Self {
+            anchor,
+            source: PriceSource::Hlc3,
+            bands: false,
+            volume: F::zero(),
+            value: F::zero(),
+            squares: F::zero(),
+            last: None,
+        }
+    }
+
+    /// Sets the price of every bar, the typical price by default.
+    pub fn price(mut self, source: PriceSource) -> Self {
+        self.source = source;
+        self
+    }
+
+    /// Adds the ±1σ and ±2σ bands to the output.
+    pub fn bands(mut self) -> Self {
+        self.bands = true;
+        self
+    }
+
+    pub fn anchor(&self) -> &VwapAnchor {
+        &self.anchor
+    }
+
+    pub fn source(&self) -> PriceSource {
+        self.source
+    }
+}
+
+impl<F, T> Next<T> for VolumeWeightedAveragePrice<F>
+where
+    F: Float,
+    T: Timestamp + Open<F> + High<F> + Low<F> + Close<F> + Volume<F>,
+{
+    type Output = VolumeWeightedAveragePriceOutput<F>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let timestamp = input.timestamp();
+        if let Some(last) = self.last {
+            if self.anchor.is_new_period(last, timestamp) {
+                self.reset();
+            }
+        }
+        self.last = Some(timestamp);
+
+        let price = self.source.price(input);
+        let volume = input.volume();
+        self.volume += volume;
+        self.value += price * volume;
+        self.squares += price * price * volume;
+
+        let (vwap, sd) = if self.volume > F::zero() {
+            let vwap = self.value / self.volume;
+            let variance = self.squares / self.volume - vwap * vwap;
+            (vwap, variance.max(F::zero()).sqrt())
+        } else {
+            (price, F::zero())
+        };
+
+        let bands = if self.bands {
+            Some(VwapBands {
+                upper_1: vwap + sd,
+                lower_1: vwap - sd,
+                upper_2: vwap + sd * F::cast(2),
+                lower_2: vwap - sd * F::cast(2),
+            })
+        } else {
+            None
+        };
+        VolumeWeightedAveragePriceOutput { vwap, bands }
+    }
+}
+
+impl<F: Float> Reset for VolumeWeightedAveragePrice<F> {
+    fn reset(&mut self) {
+        self.volume = F::zero();
+        self.value = F::zero();
+        self.squares = F::zero();
+        self.last = None;
+    }
+}
+
+impl<F: Float> Warmup for VolumeWeightedAveragePrice<F> {
+    fn warmup_period(&self) -> u32 {
+        1
+    }
+
+    fn is_ready(&self) -> bool {
+        self.volume > F::zero()
+    }
+}
+
+impl Default for VolumeWeightedAveragePrice {
+    fn default() -> Self {
+        Self::new(VwapAnchor::Cumulative)
+    }
+}
+
+impl<F: Float> fmt::Display for VolumeWeightedAveragePrice<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "VWAP({})", self.anchor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sessions::{DAY, HOUR, MINUTE};
+    use crate::test_helper::*;
+
+    fn bar(timestamp: i64, close: f64, volume: f64) -> Bar {
+        Bar::new()
+            .timestamp(timestamp)
+            .high(close)
+            .low(close)
+            .close(close)
+            .volume(volume)
+    }
+
+    #[test]
+    fn test_next() {
+        let mut vwap = VolumeWeightedAveragePrice::default();
+        let typical = Bar::new().high(12).low(6).close(9).volume(2.0);
+        assert_eq!(vwap.next(&typical).vwap, 9.0);
+        assert_eq!(vwap.next(&bar(DAY, 12.0, 1.0)).vwap, 10.0);
+        assert!(vwap.next(&bar(DAY, 12.0, 1.0)).bands.is_none());
+
+        let mut vwap = VolumeWeightedAveragePrice::default()
+            .price(PriceSource::High)
+            .bands();
+        vwap.next(&typical);
+        let out = vwap.next(&bar(0, 15.0, 2.0));
+        assert_eq!(out.vwap, 13.5);
+        let bands = out.bands.unwrap();
+        assert_eq!((bands.lower_1, bands.upper_1), (12.0, 15.0));
+        assert_eq!((bands.lower_2, bands.upper_2), (10.5, 16.5));
+    }
+
+    #[test]
+    fn test_zero_volume() {
+        let mut vwap = VolumeWeightedAveragePrice::default().bands();
+        let out = vwap.next(&bar(0, 10.0, 0.0));
+        assert_eq!(out.vwap, 10.0);
+        assert_eq!(out.bands.unwrap().lower_2, 10.0);
+        assert!(!vwap.is_ready());
+        assert_eq!(vwap.next(&bar(1, 12.0, 0.0)).vwap, 12.0);
+        vwap.next(&bar(2, 14.0, 1.0));
+        assert_eq!(vwap.next(&bar(3, 16.0, 0.0)).vwap, 14.0);
+        assert!(vwap.is_ready());
+    }
+
+    #[test]
+    fn test_anchors() {
+        let run = |anchor, timestamps: &[i64]| {
+            let mut vwap = VolumeWeightedAveragePrice::new(anchor);
+            timestamps
+                .iter()
+                .enumerate()
+                .map(|(i, &t)| vwap.next(&bar(t, i as f64, 1.0)).vwap)
+                .last()
+                .unwrap()
+        };
+
+        // 1970-01-05 was a Monday
+        let monday = 4 * DAY;
+        let week = [monday - HOUR, monday + HOUR, monday + 2 * DAY];
+        assert_eq!(run(VwapAnchor::Cumulative, &week), 1.0);
+        assert_eq!(run(VwapAnchor::Daily(TimeZone::UTC), &week), 2.0);
+        assert_eq!(run(VwapAnchor::Weekly(TimeZone::UTC), &week), 1.5);
+        // midnight in New York is 05:00 UTC
+        let day = [0, 4 * HOUR, 6 * HOUR];
+        assert_eq!(run(VwapAnchor::Daily(TimeZone::new_york()), &day), 2.0);
+
+        let session = Session::crypto(TimeZone::UTC);
+        assert_eq!(run(VwapAnchor::Session(session), &week), 2.0);
+
+        let hourly = |prev: i64, timestamp: i64| prev / HOUR != timestamp / HOUR;
+        let minutes = [0, 30 * MINUTE, 61 * MINUTE, 62 * MINUTE];
+        assert_eq!(run(VwapAnchor::Custom(hourly), &minutes), 2.5);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut vwap = VolumeWeightedAveragePrice::default();
+        vwap.next(&bar(0, 10.0, 1.0));
+        vwap.reset();
+        assert!(!vwap.is_ready());
+        assert_eq!(vwap.next(&bar(0, 20.0, 1.0)).vwap, 20.0);
+    }
+
+    #[test]
+    fn test_display() {
+        let vwap = VolumeWeightedAveragePrice::new(VwapAnchor::Weekly(TimeZone::UTC));
+        assert_eq!(format!("{}", vwap), "VWAP(WEEKLY)");
+        assert_eq!(
+            format!("{}", VolumeWeightedAveragePrice::default()),
+            "VWAP(CUMULATIVE)"
+        );
+    }
+}
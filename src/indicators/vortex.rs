@@ -0,0 +1,253 @@
+use core::fmt;
+
+use crate::errors::*;
+use crate::helpers::max3;
+use crate::{Calculate, Close, Finite, Float, High, Low, Next, Reset, RingBuffer, Warmup};
+
+/// Vortex Indicator (VI).
+///
+/// Two lines measuring the positive and the negative trend movement. A bullish trend is
+/// signaled when +VI crosses above −VI, a bearish one when −VI crosses above +VI.
+///
+/// # Formula
+///
+/// * VM+<sub>t</sub> = |high<sub>t</sub> - low<sub>t-1</sub>|
+/// * VM−<sub>t</sub> = |low<sub>t</sub> - high<sub>t-1</sub>|
+/// * +VI = Σ VM+ / Σ TR
+/// * −VI = Σ VM− / Σ TR
+///
+/// Where the sums run over the last _length_ bars and _TR_ is the
+/// [true range](struct.TrueRange.html). Both lines are 0 on the first bar.
+///
+/// # Parameters
+///
+/// * _length_ - number of periods (integer greater than 0). Default is 14.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::VortexIndicator;
+/// use ta::{DataItem, Next};
+///
+/// let mut vi = VortexIndicator::new(2).unwrap();
+/// let bar = |high: f64, low: f64, close: f64| {
+///     DataItem::builder()
+///         .open(close)
+///         .high(high)
+///         .low(low)
+///         .close(close)
+///         .volume(0.0)
+///         .build()
+///         .unwrap()
+/// };
+///
+/// vi.next(&bar(11.0, 9.0, 10.0));
+/// let out = vi.next(&bar(13.0, 11.0, 12.0));
+/// assert_eq!(out.plus, 4.0 / 3.0);
+/// assert_eq!(out.minus, 0.0);
+/// ```
+///
+/// # Links
+///
+/// * [Vortex indicator, Wikipedia](https://en.wikipedia.org/wiki/Vortex_indicator)
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VortexIndicator<F = f64> {
+    length: u32,
+    prev: Option<(F, F, F)>,
+    window: RingBuffer<(F, F, F)>,
+    plus_sum: F,
+    minus_sum: F,
+    range_sum: F,
+    count: u32,
+}
+
+/// Output of [VortexIndicator](struct.VortexIndicator.html).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VortexOutput<F = f64> {
+    pub plus: F,
+    pub minus: F,
+}
+
+impl<F: Finite> Finite for VortexOutput<F> {
+    fn is_finite(&self) -> bool {
+        self.plus.is_finite() && self.minus.is_finite()
+    }
+}
+
+impl VortexIndicator {
+    pub fn new(length: u32) -> Result<Self> {
+        Self::with_type(length)
+    }
+}
+
+impl<F: Float> VortexIndicator<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(length: u32) -> Result<Self> {
+        if length == 0 {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        Ok(Self {
+            length,
+            prev: None,
+            window: RingBuffer::new(length as usize),
+            plus_sum: F::zero(),
+            minus_sum: F::zero(),
+            range_sum: F::zero(),
+            count: 0,
+        })
+    }
+
+    pub fn length(&self) -> u32 {
+        self.length
+    }
+
+    fn update(&mut self, high: F, low: F, close: F) -> VortexOutput<F> {
+        self.count = self.count.saturating_add(1);
+        if let Some((prev_high, prev_low, prev_close)) = self.prev {
+            let plus = (high - prev_low).abs();
+            let minus = (low - prev_high).abs();
+            let range = max3(
+                high - low,
+                (high - prev_close).abs(),
+                (low - prev_close).abs(),
+            );
+
+            let evicted = self.window.push((plus, minus, range));
+            self.plus_sum += plus;
+            self.minus_sum += minus;
+            self.range_sum += range;
+            if let Some((plus, minus, range)) = evicted {
+                self.plus_sum -= plus;
+                self.minus_sum -= minus;
+                self.range_sum -= range;
+            }
+        }
+        self.prev = Some((high, low, close));
+
+        if self.range_sum > F::zero() {
+            VortexOutput {
+                plus: self.plus_sum / self.range_sum,
+                minus: self.minus_sum / self.range_sum,
+            }
+        } else {
+            VortexOutput {
+                plus: F::zero(),
+                minus: F::zero(),
+            }
+        }
+    }
+}
+
+impl<F: Float> Calculate<F> for VortexIndicator<F> {
+    type Output = VortexOutput<F>;
+
+    fn calc(&mut self, input: F) -> Self::Output {
+        self.update(input, input, input)
+    }
+}
+
+impl<F: Float, T: High<F> + Low<F> + Close<F>> Next<T> for VortexIndicator<F> {
+    type Output = VortexOutput<F>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.update(input.high(), input.low(), input.close())
+    }
+}
+
+impl<F: Float> Reset for VortexIndicator<F> {
+    fn reset(&mut self) {
+        self.prev = None;
+        self.window.clear();
+        self.plus_sum = F::zero();
+        self.minus_sum = F::zero();
+        self.range_sum = F::zero();
+        self.count = 0;
+    }
+}
+
+impl<F: Float> Warmup for VortexIndicator<F> {
+    fn warmup_period(&self) -> u32 {
+        self.length + 1
+    }
+
+    fn is_ready(&self) -> bool {
+        self.count >= self.warmup_period()
+    }
+}
+
+impl Default for VortexIndicator {
+    fn default() -> Self {
+        Self::new(14).unwrap()
+    }
+}
+
+impl<F: Float> fmt::Display for VortexIndicator<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "VI({})", self.length)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(VortexIndicator);
+
+    fn bar(high: f64, low: f64, close: f64) -> Bar {
+        Bar::new().high(high).low(low).close(close)
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(VortexIndicator::new(0).is_err());
+        assert!(VortexIndicator::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut vi = VortexIndicator::new(2).unwrap();
+
+        let out = vi.next(&bar(11.0, 9.0, 10.0));
+        assert_eq!((out.plus, out.minus), (0.0, 0.0));
+
+        // VM+ = 4, VM- = 0, TR = 3
+        let out = vi.next(&bar(13.0, 11.0, 12.0));
+        assert_eq!(round(out.plus), 1.333);
+        assert_eq!(out.minus, 0.0);
+
+        // VM+ = 1, VM- = 3, TR = 2
+        let out = vi.next(&bar(12.0, 10.0, 10.5));
+        assert_eq!(out.plus, 1.0);
+        assert_eq!(out.minus, 0.6);
+
+        // the first movement leaves the window: VM+ = 0, VM- = 4, TR = 2.5
+        let out = vi.next(&bar(10.0, 8.0, 8.5));
+        assert_eq!(round(out.plus), 0.222);
+        assert_eq!(round(out.minus), 1.556);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut vi = VortexIndicator::new(2).unwrap();
+        vi.next(&bar(11.0, 9.0, 10.0));
+        vi.next(&bar(13.0, 11.0, 12.0));
+
+        vi.reset();
+        let out = vi.next(&bar(13.0, 11.0, 12.0));
+        assert_eq!((out.plus, out.minus), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_default() {
+        VortexIndicator::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", VortexIndicator::default()), "VI(14)");
+    }
+}
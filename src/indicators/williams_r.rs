@@ -0,0 +1,192 @@
+use core::fmt;
+
+use crate::errors::*;
+use crate::indicators::{FastStochastic, RangeMode};
+use crate::{Calculate, Close, Float, High, Low, Next, Reset, Warmup};
+
+/// Williams %R.
+///
+/// Momentum oscillator locating the close within the high-low range of the last _length_
+/// periods, from -100 at the lowest low to 0 at the highest high. It mirrors the
+/// [FastStochastic](struct.FastStochastic.html), shifted by 100.
+///
+/// # Formula
+///
+/// %R = (close - highest high) / (highest high - lowest low) * 100
+///
+/// With all prices of the window equal, %R is -50. On inconsistent bars, with the close
+/// outside of the high-low range of the window, the output leaves the range of -100 to 0,
+/// unless clamped with a [RangeMode](enum.RangeMode.html).
+///
+/// # Parameters
+///
+/// * _length_ - number of periods (integer greater than 0). Default is 14.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::WilliamsR;
+/// use ta::Calculate;
+///
+/// let mut wr = WilliamsR::new(3).unwrap();
+/// assert_eq!(wr.calc(10.0), -50.0);
+/// assert_eq!(wr.calc(20.0), 0.0);
+/// assert_eq!(wr.calc(12.0), -80.0);
+/// ```
+///
+/// # Links
+///
+/// * [Williams %R, Wikipedia](https://en.wikipedia.org/wiki/Williams_%25R)
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(deserialize = "F: Float + serde::Deserialize<'de>"))
+)]
+pub struct WilliamsR<F = f64> {
+    stochastic: FastStochastic<F>,
+    range_mode: RangeMode,
+    violations: u64,
+}
+
+impl WilliamsR {
+    pub fn new(length: u32) -> Result<Self> {
+        Self::with_type(length)
+    }
+}
+
+impl<F: Float> WilliamsR<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(length: u32) -> Result<Self> {
+        Ok(Self {
+            stochastic: FastStochastic::with_type(length)?,
+            range_mode: RangeMode::Unchecked,
+            violations: 0,
+        })
+    }
+
+    /// Sets how outputs outside of the range of -100 to 0 are treated.
+    pub fn with_range_mode(mut self, range_mode: RangeMode) -> Self {
+        self.range_mode = range_mode;
+        self
+    }
+
+    pub fn length(&self) -> u32 {
+        self.stochastic.length()
+    }
+
+    pub fn range_mode(&self) -> RangeMode {
+        self.range_mode
+    }
+
+    /// Number of outputs outside of the range of -100 to 0 since the last reset, counted in
+    /// the `Clamp` and `Strict` range modes.
+    pub fn violations(&self) -> u64 {
+        self.violations
+    }
+
+    fn bounded(&mut self, stochastic: F) -> F {
+        let value = stochastic - F::cast(100.0);
+        let (value, violated) = self.range_mode.apply(value, F::cast(-100.0), F::zero());
+        if violated {
+            self.violations += 1;
+        }
+        value
+    }
+}
+
+impl<F: Float> Calculate<F> for WilliamsR<F> {
+    type Output = F;
+
+    fn calc(&mut self, input: F) -> F {
+        let stochastic = self.stochastic.calc(input);
+        self.bounded(stochastic)
+    }
+}
+
+impl<F: Float, T: High<F> + Low<F> + Close<F>> Next<T> for WilliamsR<F> {
+    type Output = F;
+
+    fn next(&mut self, input: &T) -> F {
+        let stochastic = self.stochastic.next(input);
+        self.bounded(stochastic)
+    }
+}
+
+impl<F: Float> Reset for WilliamsR<F> {
+    fn reset(&mut self) {
+        self.stochastic.reset();
+        self.violations = 0;
+    }
+}
+
+impl<F: Float> Warmup for WilliamsR<F> {
+    fn warmup_period(&self) -> u32 {
+        self.stochastic.warmup_period()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.stochastic.is_ready()
+    }
+}
+
+impl Default for WilliamsR {
+    fn default() -> Self {
+        Self::new(14).unwrap()
+    }
+}
+
+impl<F: Float> fmt::Display for WilliamsR<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "WILLR({})", self.length())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(WilliamsR);
+
+    #[test]
+    fn test_new() {
+        assert!(WilliamsR::new(0).is_err());
+        assert!(WilliamsR::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut wr = WilliamsR::new(2).unwrap();
+        let bar = |high, low, close| Bar::new().high(high).low(low).close(close);
+        assert_eq!(wr.next(&bar(20.0, 10.0, 12.0)), -80.0);
+        assert_eq!(wr.next(&bar(25.0, 15.0, 25.0)), 0.0);
+        assert_eq!(round(wr.next(&bar(18.0, 14.0, 16.0))), -81.818);
+    }
+
+    #[test]
+    fn test_range_mode() {
+        let above = Bar::new().high(20.0).low(10.0).close(21.0);
+
+        let mut wr = WilliamsR::new(3).unwrap();
+        assert_eq!(round(wr.next(&above)), 10.0);
+
+        let mut wr = WilliamsR::new(3).unwrap().with_range_mode(RangeMode::Clamp);
+        assert_eq!(wr.next(&above), 0.0);
+        assert_eq!(wr.violations(), 1);
+
+        let mut wr = WilliamsR::new(3)
+            .unwrap()
+            .with_range_mode(RangeMode::Strict);
+        assert_eq!(round(wr.next(&above)), 10.0);
+        assert_eq!(wr.violations(), 1);
+        wr.reset();
+        assert_eq!(wr.violations(), 0);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", WilliamsR::new(9).unwrap()), "WILLR(9)");
+    }
+}
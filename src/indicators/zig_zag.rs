@@ -0,0 +1,377 @@
+use core::fmt;
+
+use crate::errors::*;
+use crate::indicators::{AverageTrueRange, SwingKind, SwingPoint};
+use crate::{Calculate, Close, Float, High, Low, Next, Reset, Warmup};
+
+/// Price move reversing a [ZigZag](struct.ZigZag.html).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ZigZagThreshold<F = f64> {
+    /// Move in percent of the price of the swing, e.g. 5.0 for 5%.
+    Percent(F),
+    /// Move in multiples of the [ATR](struct.AverageTrueRange.html) of _length_ bars.
+    Atr { length: u32, multiplier: F },
+}
+
+impl<F: Float> fmt::Display for ZigZagThreshold<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ZigZagThreshold::Percent(percent) => write!(f, "{}%", percent),
+            ZigZagThreshold::Atr { length, multiplier } => {
+                write!(f, "ATR({}), {}", length, multiplier)
+            }
+        }
+    }
+}
+
+/// Leg of a [ZigZag](struct.ZigZag.html) from the latest confirmed swing to the extreme
+/// price since, which may still move.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ZigZagLeg<F = f64> {
+    /// Latest confirmed swing.
+    pub from: SwingPoint<F>,
+    /// Tentative swing, the highest high of a rising leg or the lowest low of a falling one.
+    pub to: SwingPoint<F>,
+}
+
+/// ZigZag.
+///
+/// Connects the swing highs and lows of the price, ignoring moves smaller than the
+/// threshold, a percentage of the price or a multiple of the ATR. A rising leg is extended
+/// while the highs rise and reverses once a low falls by the threshold below its highest
+/// high, which confirms that high as a swing; a falling leg the other way around. Before
+/// the first reversal both the highest high and the lowest low are tracked, and the first
+/// move by the threshold away from either of them confirms it.
+///
+/// # Repainting
+///
+/// Confirmed swings, returned by `next` on the bar which reverses the leg, never change:
+/// they are safe to feed into strategies and other indicators, e.g. a
+/// [Divergence](struct.Divergence.html). Their `offset` tells how many bars before the
+/// confirming bar the swing happened. The end of the current [leg](#method.leg) is
+/// tentative, it moves with every new extreme until the leg reverses, and is what a
+/// charted zigzag repaints.
+///
+/// # Parameters
+///
+/// * _threshold_ - reversal amount. Default is 5%.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{SwingKind, ZigZag, ZigZagThreshold};
+/// use ta::Calculate;
+///
+/// let mut zigzag = ZigZag::new(ZigZagThreshold::Percent(10.0)).unwrap();
+/// assert_eq!(zigzag.calc(100.0), None);
+/// assert_eq!(zigzag.calc(105.0), None);
+///
+/// // 10% below the high of 105.0
+/// let swing = zigzag.calc(94.0).unwrap();
+/// assert_eq!((swing.kind, swing.price, swing.offset), (SwingKind::High, 105.0, 1));
+///
+/// assert_eq!(zigzag.calc(90.0), None);
+/// let leg = zigzag.leg().unwrap();
+/// assert_eq!((leg.from.price, leg.to.price), (105.0, 90.0));
+/// ```
+///
+/// # Links
+///
+/// * [ZigZag Indicator, Investopedia](https://www.investopedia.com/terms/z/zig_zag_indicator.asp)
+///
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ZigZag<F = f64> {
+    threshold: ZigZagThreshold<F>,
+    atr: Option<AverageTrueRange<F>>,
+    // kind of the tentative swing, `None` before the first reversal
+    direction: Option<SwingKind>,
+    // extreme prices with their bar numbers
+    high: Option<(F, u64)>,
+    low: Option<(F, u64)>,
+    last: Option<(SwingKind, F, u64)>,
+    count: u64,
+}
+
+impl ZigZag {
+    pub fn new(threshold: ZigZagThreshold<f64>) -> Result<Self> {
+        Self::with_type(threshold)
+    }
+}
+
+impl<F: Float> ZigZag<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(threshold: ZigZagThreshold<F>) -> Result<Self> {
+        let (amount, atr) = match threshold {
+            ZigZagThreshold::Percent(percent) => (percent, None),
+            ZigZagThreshold::Atr { length, multiplier } => {
+                (multiplier, Some(AverageTrueRange::with_type(length)?))
+            }
+        };
+        if amount <= F::zero() || !amount.is_finite() {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        Ok(Self {
+            threshold,
+            atr,
+            direction: None,
+            high: None,
+            low: None,
+            last: None,
+            count: 0,
+        })
+    }
+
+    pub fn threshold(&self) -> ZigZagThreshold<F> {
+        self.threshold
+    }
+
+    /// Current leg, `None` before the first confirmed swing.
+    pub fn leg(&self) -> Option<ZigZagLeg<F>> {
+        let (kind, price, index) = self.last?;
+        let (to_kind, (to_price, to_index)) = match self.direction? {
+            SwingKind::High => (SwingKind::High, self.high?),
+            SwingKind::Low => (SwingKind::Low, self.low?),
+        };
+        Some(ZigZagLeg {
+            from: self.point(kind, price, index),
+            to: self.point(to_kind, to_price, to_index),
+        })
+    }
+
+    fn point(&self, kind: SwingKind, price: F, index: u64) -> SwingPoint<F> {
+        SwingPoint {
+            kind,
+            price,
+            offset: (self.count - index) as u32,
+        }
+    }
+
+    fn confirm(&mut self, kind: SwingKind, (price, index): (F, u64)) -> Option<SwingPoint<F>> {
+        self.last = Some((kind, price, index));
+        Some(self.point(kind, price, index))
+    }
+
+    fn update(&mut self, high: F, low: F, atr: Option<F>) -> Option<SwingPoint<F>> {
+        self.count += 1;
+        let count = self.count;
+        let threshold = self.threshold;
+        // reversal amount from a swing at `price`, moves never reverse on a zero amount
+        let distance = |price: F| match threshold {
+            ZigZagThreshold::Percent(percent) => price.abs() * percent / F::cast(100.0),
+            ZigZagThreshold::Atr { multiplier, .. } => multiplier * atr.unwrap_or(F::zero()),
+        };
+        let reverses = |from: F, to: F| {
+            let distance = distance(from);
+            distance > F::zero() && (to - from).abs() >= distance
+        };
+
+        match (self.direction, self.high, self.low) {
+            (Some(SwingKind::High), Some(swing_high), _) => {
+                let extreme = swing_high.0;
+                if high > extreme {
+                    self.high = Some((high, count));
+                } else if low < extreme && reverses(extreme, low) {
+                    let swing = self.confirm(SwingKind::High, swing_high);
+                    self.direction = Some(SwingKind::Low);
+                    self.low = Some((low, count));
+                    return swing;
+                }
+                None
+            }
+            (Some(SwingKind::Low), _, Some(swing_low)) => {
+                let extreme = swing_low.0;
+                if low < extreme {
+                    self.low = Some((low, count));
+                } else if high > extreme && reverses(extreme, high) {
+                    let swing = self.confirm(SwingKind::Low, swing_low);
+                    self.direction = Some(SwingKind::High);
+                    self.high = Some((high, count));
+                    return swing;
+                }
+                None
+            }
+            (_, Some(swing_high), Some(swing_low)) => {
+                let (highest, lowest) = (swing_high.0, swing_low.0);
+                if high > lowest && reverses(lowest, high) {
+                    let swing = self.confirm(SwingKind::Low, swing_low);
+                    self.direction = Some(SwingKind::High);
+                    self.high = Some((high, count));
+                    return swing;
+                }
+                if low < highest && reverses(highest, low) {
+                    let swing = self.confirm(SwingKind::High, swing_high);
+                    self.direction = Some(SwingKind::Low);
+                    self.low = Some((low, count));
+                    return swing;
+                }
+                if high > highest {
+                    self.high = Some((high, count));
+                }
+                if low < lowest {
+                    self.low = Some((low, count));
+                }
+                None
+            }
+            _ => {
+                self.high = Some((high, count));
+                self.low = Some((low, count));
+                None
+            }
+        }
+    }
+}
+
+impl<F: Float> Calculate<F> for ZigZag<F> {
+    type Output = Option<SwingPoint<F>>;
+
+    fn calc(&mut self, input: F) -> Self::Output {
+        let atr = self.atr.as_mut().map(|atr| atr.calc(input));
+        self.update(input, input, atr)
+    }
+}
+
+impl<F: Float, T: High<F> + Low<F> + Close<F>> Next<T> for ZigZag<F> {
+    type Output = Option<SwingPoint<F>>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let atr = self.atr.as_mut().map(|atr| atr.next(input));
+        self.update(input.high(), input.low(), atr)
+    }
+}
+
+impl<F: Float> Reset for ZigZag<F> {
+    fn reset(&mut self) {
+        if let Some(atr) = self.atr.as_mut() {
+            atr.reset();
+        }
+        self.direction = None;
+        self.high = None;
+        self.low = None;
+        self.last = None;
+        self.count = 0;
+    }
+}
+
+impl<F: Float> Warmup for ZigZag<F> {
+    fn warmup_period(&self) -> u32 {
+        self.atr.as_ref().map_or(1, |atr| atr.warmup_period())
+    }
+
+    fn is_ready(&self) -> bool {
+        self.count >= u64::from(self.warmup_period())
+    }
+}
+
+impl Default for ZigZag {
+    fn default() -> Self {
+        Self::new(ZigZagThreshold::Percent(5.0)).unwrap()
+    }
+}
+
+impl<F: Float> fmt::Display for ZigZag<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ZIGZAG({})", self.threshold)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(ZigZag);
+
+    #[test]
+    fn test_new() {
+        assert!(ZigZag::new(ZigZagThreshold::Percent(0.0)).is_err());
+        assert!(ZigZag::new(ZigZagThreshold::Percent(f64::NAN)).is_err());
+        let atr = |length, multiplier| ZigZagThreshold::Atr { length, multiplier };
+        assert!(ZigZag::new(atr(0, 2.0)).is_err());
+        assert!(ZigZag::new(atr(14, -1.0)).is_err());
+        assert!(ZigZag::new(atr(14, 2.0)).is_ok());
+    }
+
+    #[test]
+    fn test_percent() {
+        let mut zigzag = ZigZag::new(ZigZagThreshold::Percent(10.0)).unwrap();
+        let closes = [100.0, 105.0, 94.0, 90.0, 98.0, 99.0, 110.0, 100.0, 98.0];
+        let swings: Vec<_> = closes
+            .iter()
+            .filter_map(|&close| zigzag.calc(close))
+            .map(|swing| (swing.kind, swing.price, swing.offset))
+            .collect();
+        assert_eq!(
+            swings,
+            vec![
+                (SwingKind::High, 105.0, 1),
+                (SwingKind::Low, 90.0, 2),
+                (SwingKind::High, 110.0, 2),
+            ]
+        );
+
+        let leg = zigzag.leg().unwrap();
+        assert_eq!(leg.from.offset, 2);
+        assert_eq!(
+            (leg.to.kind, leg.to.price, leg.to.offset),
+            (SwingKind::Low, 98.0, 0)
+        );
+    }
+
+    #[test]
+    fn test_first_swing_low() {
+        let mut zigzag = ZigZag::new(ZigZagThreshold::Percent(10.0)).unwrap();
+        assert_eq!(zigzag.calc(100.0), None);
+        assert_eq!(zigzag.calc(95.0), None);
+        assert!(zigzag.leg().is_none());
+        let swing = zigzag.calc(105.0).unwrap();
+        assert_eq!((swing.kind, swing.price), (SwingKind::Low, 95.0));
+    }
+
+    #[test]
+    fn test_atr() {
+        let threshold = ZigZagThreshold::Atr {
+            length: 2,
+            multiplier: 2.0,
+        };
+        let mut zigzag = ZigZag::new(threshold).unwrap();
+        let bar = |high: f64, low: f64| Bar::new().high(high).low(low).close((high + low) / 2.0);
+        assert_eq!(zigzag.next(&bar(11.0, 9.0)), None);
+        // a rise of 2.5 from the low of 9.0 is less than two ATRs of about 3.3
+        assert_eq!(zigzag.next(&bar(11.5, 10.5)), None);
+        let swing = zigzag.next(&bar(12.5, 11.5)).unwrap();
+        assert_eq!(
+            (swing.kind, swing.price, swing.offset),
+            (SwingKind::Low, 9.0, 2)
+        );
+        assert!(zigzag.is_ready());
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut zigzag = ZigZag::new(ZigZagThreshold::Percent(10.0)).unwrap();
+        for &close in &[100.0, 120.0, 100.0] {
+            zigzag.calc(close);
+        }
+        assert!(zigzag.leg().is_some());
+        zigzag.reset();
+        assert!(zigzag.leg().is_none());
+        assert_eq!(zigzag.calc(100.0), None);
+    }
+
+    #[test]
+    fn test_display() {
+        let zigzag = ZigZag::new(ZigZagThreshold::Percent(5.0)).unwrap();
+        assert_eq!(format!("{}", zigzag), "ZIGZAG(5%)");
+        let threshold = ZigZagThreshold::Atr {
+            length: 14,
+            multiplier: 3.0,
+        };
+        assert_eq!(
+            format!("{}", ZigZag::new(threshold).unwrap()),
+            "ZIGZAG(ATR(14), 3)"
+        );
+    }
+}
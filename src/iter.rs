@@ -0,0 +1,199 @@
+//! Lazy application of indicators to iterators.
+//!
+//! [IndicatorIteratorExt](trait.IndicatorIteratorExt.html) adds `indicator()` to every
+//! iterator. Streams of numbers are fed to [calc](../trait.Calculate.html), streams of bars
+//! to [next](../trait.Next.html), both by value and by reference.
+//!
+//! # Example
+//!
+//! ```
+//! use ta::indicators::{FastStochastic, SimpleMovingAverage};
+//! use ta::{DataItem, IndicatorIteratorExt};
+//!
+//! let prices = vec![1.0, 2.0, 3.0, 4.0];
+//! let sma: Vec<f64> = prices
+//!     .iter()
+//!     .indicator(SimpleMovingAverage::new(2).unwrap())
+//!     .collect();
+//! assert_eq!(sma, vec![1.0, 1.5, 2.5, 3.5]);
+//!
+//! let bars: Vec<DataItem> = prices
+//!     .iter()
+//!     .map(|&price| {
+//!         DataItem::builder()
+//!             .open(price)
+//!             .high(price + 1.0)
+//!             .low(price - 1.0)
+//!             .close(price)
+//!             .volume(0.0)
+//!             .build()
+//!             .unwrap()
+//!     })
+//!     .collect();
+//! let stoch: Vec<f64> = bars
+//!     .iter()
+//!     .indicator(FastStochastic::new(2).unwrap())
+//!     .map(f64::round)
+//!     .collect();
+//! assert_eq!(stoch, vec![50.0, 67.0, 67.0, 67.0]);
+//! ```
+
+use core::marker::PhantomData;
+
+use crate::{Calculate, Next};
+
+/// Feeds a single item of an iterator to an indicator.
+///
+/// Implemented for every indicator, the `Marker` selects whether the item goes to
+/// [Calculate](../trait.Calculate.html) or [Next](../trait.Next.html) and whether it is
+/// passed by value or by reference. It is inferred and never needs to be written out.
+pub trait Apply<In, Marker> {
+    type Output;
+    fn apply(&mut self, input: In) -> Self::Output;
+}
+
+/// [Apply](trait.Apply.html) marker: numbers by value.
+pub enum CalculateValue {}
+
+/// [Apply](trait.Apply.html) marker: numbers by reference.
+pub enum CalculateRef {}
+
+/// [Apply](trait.Apply.html) marker: bars by value.
+pub enum NextValue {}
+
+/// [Apply](trait.Apply.html) marker: bars by reference.
+pub enum NextRef {}
+
+impl<F, I: Calculate<F>> Apply<F, CalculateValue> for I {
+    type Output = I::Output;
+
+    fn apply(&mut self, input: F) -> Self::Output {
+        self.calc(input)
+    }
+}
+
+impl<'a, F: Copy, I: Calculate<F>> Apply<&'a F, CalculateRef> for I {
+    type Output = I::Output;
+
+    fn apply(&mut self, input: &'a F) -> Self::Output {
+        self.calc(*input)
+    }
+}
+
+impl<T, I: Next<T>> Apply<T, NextValue> for I {
+    type Output = I::Output;
+
+    fn apply(&mut self, input: T) -> Self::Output {
+        self.next(&input)
+    }
+}
+
+impl<'a, T, I: Next<T>> Apply<&'a T, NextRef> for I {
+    type Output = I::Output;
+
+    fn apply(&mut self, input: &'a T) -> Self::Output {
+        self.next(input)
+    }
+}
+
+/// Iterator over the outputs of an indicator, created by
+/// [IndicatorIteratorExt::indicator](trait.IndicatorIteratorExt.html#method.indicator).
+#[derive(Debug, Clone)]
+pub struct IndicatorIter<It, I, Marker> {
+    iter: It,
+    indicator: I,
+    marker: PhantomData<fn() -> Marker>,
+}
+
+impl<It, I, Marker> IndicatorIter<It, I, Marker> {
+    /// Returns the indicator with the state after the items consumed so far.
+    pub fn into_indicator(self) -> I {
+        self.indicator
+    }
+}
+
+impl<It, I, Marker> Iterator for IndicatorIter<It, I, Marker>
+where
+    It: Iterator,
+    I: Apply<It::Item, Marker>,
+{
+    type Item = I::Output;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next()?;
+        Some(self.indicator.apply(item))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+/// Extends iterators with [indicator](#method.indicator).
+pub trait IndicatorIteratorExt: Iterator + Sized {
+    /// Lazily feeds every item to the indicator and yields its outputs.
+    fn indicator<I, Marker>(self, indicator: I) -> IndicatorIter<Self, I, Marker>
+    where
+        I: Apply<Self::Item, Marker>,
+    {
+        IndicatorIter {
+            iter: self,
+            indicator,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<It: Iterator> IndicatorIteratorExt for It {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{
+        ExponentialMovingAverage, FastStochastic, SimpleMovingAverage, TrueRange,
+    };
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_values() {
+        let sma = SimpleMovingAverage::new(2).unwrap();
+        let outputs: Vec<f64> = vec![1.0, 3.0, 5.0].into_iter().indicator(sma).collect();
+        assert_eq!(outputs, vec![1.0, 2.0, 4.0]);
+
+        let prices = [10.0, 20.0, 15.0];
+        let outputs: Vec<f64> = prices
+            .iter()
+            .indicator(FastStochastic::new(14).unwrap())
+            .collect();
+        assert_eq!(outputs, vec![50.0, 100.0, 50.0]);
+    }
+
+    #[test]
+    fn test_bars() {
+        let bars = vec![
+            Bar::new().high(10).low(8).close(9),
+            Bar::new().high(12).low(11).close(11.5),
+        ];
+        let by_ref: Vec<f64> = bars.iter().indicator(TrueRange::new()).collect();
+        assert_eq!(by_ref, vec![2.0, 3.0]);
+
+        let by_value: Vec<f64> = bars.into_iter().indicator(TrueRange::new()).collect();
+        assert_eq!(by_value, by_ref);
+    }
+
+    #[test]
+    fn test_lazy() {
+        let mut fed = 0;
+        let prices = [2.0, 4.0, 6.0, 8.0];
+        let mut iter = prices
+            .iter()
+            .inspect(|_| fed += 1)
+            .indicator(ExponentialMovingAverage::new(3).unwrap());
+
+        assert_eq!(iter.next(), Some(2.0));
+        assert_eq!(iter.next(), Some(3.0));
+        let ema = iter.into_indicator();
+        assert_eq!(fed, 2);
+        assert_eq!(format!("{}", ema), "EMA(3)");
+    }
+}
@@ -5,11 +5,129 @@
 //! state.
 //!
 //! Every indicator implements [Next<T>](trait.Next.html) and [Reset](trait.Reset.html) traits,
-//! which are the core concept of the library.
+//! which are the core concept of the library. The [prelude](prelude/index.html) imports them
+//! together with the other traits of the crate: `use ta::prelude::*;`.
 //!
 //! Since `Next<T>` is a generic trait, most of the indicators can work with both input types: `f64` and more complex
 //! structures like [DataItem](struct.DataItem.html).
 //!
+//! Indicators are generic over the [Float](trait.Float.html) type and default to `f64`: `new()`
+//! and `default()` create `f64` indicators. To compute them in single precision use e.g.
+//! `SimpleMovingAverage::<f32>::with_type(9)` and implement the input traits (`Close<f32>`,
+//! `High<f32>`, ...) for your data type.
+//!
+//! Indicators which only need the basic arithmetic (SMA, EMA, SMMA, ROC, MOM, True Range, ATR
+//! and OBV) accept any [Number](trait.Number.html). With the `decimal` feature this includes
+//! `rust_decimal::Decimal` for exact decimal computations, and
+//! [DataItem](struct.DataItem.html) can hold decimal prices.
+//!
+//! With the `serde` feature every indicator, including its internal windows, implements
+//! `Serialize` and `Deserialize`, so its state can be persisted and restored without replaying
+//! the input. Windows which are not filled yet may hold infinities, which e.g. JSON cannot
+//! represent; prefer a binary format or persist indicators once they [are ready](trait.Warmup.html).
+//!
+//! The crate is `no_std` compatible: without the default `std` feature it only needs `core`
+//! and `alloc`, and all indicators stay available. Errors are then a plain
+//! [Error](errors/struct.Error.html) with the same [ErrorKind](errors/enum.ErrorKind.html)
+//! instead of an `error_chain` one. The `decimal`, `simd` and `parallel` features require `std`.
+//! [SimpleMovingAverageConst](indicators/struct.SimpleMovingAverageConst.html),
+//! [MinimumConst](indicators/struct.MinimumConst.html) and
+//! [MaximumConst](indicators/struct.MaximumConst.html) take their window size as a const
+//! generic and keep the window in an array instead of the heap.
+//!
+//! Indicators also implement [Warmup](trait.Warmup.html), which tells whether enough input
+//! was consumed for the output to be meaningful. [TryNext](trait.TryNext.html) and
+//! [TryCalculate](trait.TryCalculate.html) return a `NotReady` error until then, and errors
+//! for NaN or infinite inputs and outputs, instead of values which are not meaningful.
+//! [ResetWith](trait.ResetWith.html) and [ResetWithItems](trait.ResetWithItems.html) reset
+//! an indicator and warm it up again from the last values or bars, e.g. after restoring a
+//! process, and [WarmUp](trait.WarmUp.html) feeds historical bars on startup, reporting
+//! whether they covered the warm-up period.
+//!
+//! [UpdateLast](trait.UpdateLast.html) and [Replace](trait.Replace.html) revise the latest
+//! input of e.g. [Minimum](indicators/struct.Minimum.html) or
+//! [FastStochastic](indicators/struct.FastStochastic.html), for candles still forming on
+//! live feeds. [Peek](trait.Peek.html) and [PeekNext](trait.PeekNext.html) return the output
+//! for a hypothetical next input without changing the indicator.
+//! A [Scenario](scenario/struct.Scenario.html) evaluates hypothetical next bars on a clone of
+//! any indicator or [Pipeline](pipeline/struct.Pipeline.html).
+//! [Tracked](rollback/struct.Tracked.html) indicators can
+//! [roll back](rollback/trait.Rollback.html) their latest inputs, to replay corrected or late
+//! bars. The windows of [Minimum](indicators/struct.Minimum.html),
+//! [Maximum](indicators/struct.Maximum.html), [FastStochastic](indicators/struct.FastStochastic.html)
+//! and [RateOfChange](indicators/struct.RateOfChange.html) can be resized with `set_length`,
+//! keeping the history which fits into the new window.
+//!
+//! [IndicatorIteratorExt](trait.IndicatorIteratorExt.html) applies an indicator to an iterator,
+//! e.g. `prices.iter().indicator(SimpleMovingAverage::new(9)?)`.
+//! For large series [calc_slice](trait.Calculate.html#method.calc_slice) and
+//! [next_slice](trait.Next.html#method.next_slice) write outputs into a preallocated buffer.
+//! With the `simd` feature the [simd](simd/index.html) module provides vectorized kernels for
+//! the simple moving average, rolling sums, extremes and rate of change.
+//! With the `parallel` feature the [parallel](parallel/index.html) module evaluates indicators
+//! over many series or parameter sets at once.
+//! With the `prometheus` feature the [metrics](metrics/index.html) module exports indicator
+//! outputs and diagnostic counters as Prometheus gauges and counters.
+//! With the `adapters` feature the [adapters](adapters/index.html) module lets timestamped
+//! tuples, Binance-style klines and JSON values feed indicators directly.
+//!
+//! Indicators chosen at runtime can be stored as `Box<dyn Indicator>` ([Indicator](trait.Indicator.html))
+//! and created by name, or parsed from their `Display` form such as `"FAST_STOCH(14)"`, with a
+//! [Registry](registry/struct.Registry.html).
+//!
+//! Indicators are composed with [IndicatorExt](trait.IndicatorExt.html), e.g.
+//! `FastStochastic::new(14)?.then(ExponentialMovingAverage::new(3)?)` is a slow stochastic.
+//! Single-input indicators can be fed the typical, median or another price of every bar
+//! instead of the close with a [PriceSource](enum.PriceSource.html). EMA-based indicators
+//! can be re-seeded or [decayed](trait.Decay.html) after a time gap between timestamped bars,
+//! e.g. an exchange outage, with [OnGap](struct.OnGap.html), and reset at the start of every
+//! trading session with [OnSession](struct.OnSession.html).
+//! Windowed indicators keep their inputs in a [RingBuffer](struct.RingBuffer.html), which is
+//! also available to custom indicators, or in a [Window](struct.Window.html) on top of it,
+//! which also keeps a running sum.
+//!
+//! [Cross](flips/struct.Cross.html) reports when one line crosses another or a constant level,
+//! e.g. a stochastic crossing 80.
+//!
+//! [Signals](signals/index.html) turn oscillator levels, moving average crossovers and band
+//! breakouts into buy, sell or hold.
+//!
+//! [Rules](rules/index.html) combine indicator thresholds with `and`, `or`, `not` and
+//! persistence over bars into strategies evaluated bar by bar, which a
+//! [Backtest](backtest/struct.Backtest.html) runs over historical bars with fees and
+//! slippage.
+//!
+//! [Consensus](consensus/struct.Consensus.html) summarizes many bullish/bearish signals into a
+//! "strong buy" to "strong sell" stance.
+//!
+//! A [RegimeClassifier](regime/struct.RegimeClassifier.html) labels every bar as trending up,
+//! trending down or ranging, and its volatility as low, normal or high.
+//!
+//! [Alerts](alerts/struct.Alerts.html) evaluates rules such as threshold crosses, crossovers and
+//! new extremes, bands entered and sign changes over indicator outputs, with debouncing,
+//! cooldowns and one-shot rules. A [Watcher](watch/struct.Watcher.html) owns indicators
+//! together with such rules and reports the fired rules with the bar index and time.
+//! Alerts and other records can be pushed to a [Sink](sinks/trait.Sink.html): a channel, a
+//! writer or, with the `webhook` feature, an HTTP endpoint. A
+//! [Pipeline](pipeline/struct.Pipeline.html) of indicators and alert rules can be built from
+//! a configuration file, and reloaded at runtime keeping the state of unchanged indicators.
+//! With the `audit` feature its inputs and outputs can be [logged](audit/index.html) and
+//! replayed offline, and with the `parquet` or `arrow-ipc` feature its outputs can be
+//! [archived](columnar/index.html) as columnar files. With the `mmap` feature indicators and
+//! pipelines can be fed from [memory-mapped](history/index.html) history files too large to
+//! load at once. With the `snapshot` feature the state of indicators can be checkpointed as
+//! compact [binary snapshots](snapshot/index.html) instead of JSON.
+//!
+//! A [SymbolManager](symbols/struct.SymbolManager.html) keeps an instance of a template
+//! indicator per symbol, or one created by a function, and routes the bars of many
+//! instruments to them, e.g. for screeners.
+//!
+//! A [ResolutionAligner](align/struct.ResolutionAligner.html) merges the outputs of
+//! indicators at different resolutions into one record per tick, with staleness flags.
+//!
+//! The [seasonality](seasonality/index.html) module fits average returns by hour of day, day of
+//! week or month on bars with a [Timestamp](trait.Timestamp.html) and applies them to new bars.
+//!
 //! # Example
 //! ```
 //! use ta::indicators::ExponentialMovingAverage;
@@ -29,23 +147,78 @@
 //! * Trend
 //!   * [Exponential Moving Average (EMA)](indicators/struct.ExponentialMovingAverage.html)
 //!   * [Simple Moving Average (SMA)](indicators/struct.SimpleMovingAverage.html)
+//!   * [Smoothed Moving Average (SMMA)](indicators/struct.SmoothedMovingAverage.html)
+//!   * [Moving Average Ribbon](indicators/struct.Ribbon.html)
+//!   * [Guppy Multiple Moving Average (GMMA)](indicators/struct.Guppy.html)
+//!   * [Alligator](indicators/struct.Alligator.html)
+//!   * [Vortex Indicator (VI)](indicators/struct.VortexIndicator.html)
+//!   * [Directional Movement Index (DMI)](indicators/struct.DirectionalMovementIndex.html)
 //! * Oscillators
 //!   * [Relative Strength Index (RSI)](indicators/struct.RelativeStrengthIndex.html)
 //!   * [Fast Stochastic](indicators/struct.FastStochastic.html)
 //!   * [Slow Stochastic](indicators/struct.SlowStochastic.html)
+//!   * [Williams %R](indicators/struct.WilliamsR.html)
+//!   * [Stochastic Momentum Index (SMI)](indicators/struct.StochasticMomentumIndex.html)
+//!   * [Pretty Good Oscillator (PGO)](indicators/struct.PrettyGoodOscillator.html)
+//!   * [Special K](indicators/struct.SpecialK.html)
 //!   * [Moving Average Convergence Divergence (MACD)](indicators/struct.MovingAverageConvergenceDivergence.html)
+//!   * [Elder Impulse System](indicators/struct.ElderImpulse.html)
+//!   * [Traders Dynamic Index (TDI)](indicators/struct.TradersDynamicIndex.html)
 //!   * [Money Flow Index (MFI)](indicators/struct.MoneyFlowIndex.html)
+//!   * [Divergence](indicators/struct.Divergence.html)
 //! * Other
 //!   * [Standard Deviation (SD)](indicators/struct.StandardDeviation.html)
+//!   * [Variance (VAR)](indicators/struct.Variance.html)
 //!   * [Bollinger Bands (BB)](indicators/struct.BollingerBands.html)
+//!   * [Exponential Moving Variance](indicators/struct.ExponentialMovingVariance.html)
+//!   * [Exponential Bollinger Bands](indicators/struct.ExponentialBollinger.html)
+//!   * [Quantile Channel](indicators/struct.QuantileChannel.html)
+//!   * [Support and Resistance levels](indicators/struct.SupportResistance.html)
+//!   * [Pivot Points (Classic, Fibonacci, Camarilla, Woodie)](indicators/struct.PivotPoints.html)
+//!   * [Pivot High](indicators/struct.PivotHigh.html) and [Pivot Low](indicators/struct.PivotLow.html)
+//!   * [Bill Williams Fractals](indicators/struct.Fractals.html)
+//!   * [Run Length statistics](indicators/struct.RunLength.html)
+//!   * [Exponential Covariance](indicators/struct.ExponentialCovariance.html)
+//!   * [Correlation](indicators/struct.Correlation.html)
+//!   * [Exponential Correlation](indicators/struct.ExponentialCorrelation.html)
+//!   * [GARCH(1,1) Volatility](indicators/struct.Garch11.html)
+//!   * [Realized Volatility](indicators/struct.RealizedVolatility.html)
+//!   * [Time-Weighted Average Price (TWAP)](indicators/struct.TimeWeightedAveragePrice.html)
+//!   * [Volume-Weighted Average Price (VWAP)](indicators/struct.VolumeWeightedAveragePrice.html)
+//!   * [Volume Profile (POC, value area)](indicators/struct.VolumeProfile.html)
+//!   * [Volume Delta](indicators/struct.VolumeDelta.html)
+//!   * [Cumulative Volume Delta (CVD)](indicators/struct.CumulativeVolumeDelta.html)
+//!   * [Delta Divergence](indicators/struct.DeltaDivergence.html)
+//!   * [Geometric Mean (GM)](indicators/struct.GeometricMean.html)
+//!   * [Harmonic Mean (HM)](indicators/struct.HarmonicMean.html)
+//!   * [Cumulative Statistics](indicators/struct.CumulativeStats.html)
+//!   * [Mode](indicators/struct.Mode.html)
+//!   * [Round Number Proximity (RNP)](indicators/struct.RoundNumberProximity.html)
+//!   * [Relative Rotation (RS-Ratio, RS-Momentum)](indicators/struct.RelativeRotation.html)
 //!   * [Maximum](indicators/struct.Maximum.html)
 //!   * [Minimum](indicators/struct.Minimum.html)
 //!   * [True Range](indicators/struct.TrueRange.html)
 //!   * [Average True Range (ATR)](indicators/struct.AverageTrueRange.html)
 //!   * [Efficiency Ratio (ER)](indicators/struct.EfficiencyRatio.html)
-//!   * [Rate of Change (ROC)](indicators/struct.RateOfChange.html)
+//!   * [Rate of Change (ROC, ROCP, ROCR, ROCR100)](indicators/struct.RateOfChange.html)
+//!   * [Momentum (MOM)](indicators/struct.Momentum.html)
 //!   * [On Balance Volume (OBV)](indicators/struct.OnBalanceVolume.html)
+//! * Quotes
+//!   * [Spread](indicators/struct.Spread.html)
+//!   * [Average Spread](indicators/struct.AverageSpread.html)
+//!   * [Mid Price](indicators/struct.MidPrice.html)
+//!   * [Micro Price](indicators/struct.MicroPrice.html)
+//! * Transforms
+//!   * [Heikin-Ashi](indicators/struct.HeikinAshi.html)
+//!   * [Kagi](indicators/struct.Kagi.html)
+//!   * [Point and Figure](indicators/struct.PointAndFigure.html)
+//!   * [ZigZag](indicators/struct.ZigZag.html)
 //!
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "std")]
 #[macro_use]
 extern crate error_chain;
 
@@ -55,11 +228,71 @@ mod test_helper;
 
 mod helpers;
 
+#[cfg(feature = "adapters")]
+pub mod adapters;
+pub mod alerts;
+pub mod align;
+#[cfg(feature = "audit")]
+pub mod audit;
+pub mod backtest;
+pub mod bracket;
+#[cfg(any(feature = "parquet", feature = "arrow-ipc"))]
+pub mod columnar;
+pub mod combinators;
+pub mod consensus;
 pub mod errors;
+pub mod execution;
+pub mod flips;
+#[cfg(feature = "mmap")]
+pub mod history;
 pub mod indicators;
+pub mod iter;
+#[cfg(feature = "prometheus")]
+pub mod metrics;
+#[cfg(feature = "parallel")]
+pub mod parallel;
+pub mod pipeline;
+pub mod prelude;
+pub mod preprocess;
+pub mod regime;
+pub mod registry;
+pub mod rollback;
+pub mod rules;
+pub mod scenario;
+pub mod seasonality;
+pub mod sessions;
+pub mod signals;
+#[cfg(feature = "simd")]
+pub mod simd;
+pub mod sinks;
+#[cfg(feature = "snapshot")]
+pub mod snapshot;
+pub mod symbols;
+pub mod watch;
 
 mod traits;
 pub use crate::traits::*;
 
 mod data_item;
-pub use crate::data_item::DataItem;
+pub use crate::combinators::IndicatorExt;
+pub use crate::data_item::{DataItem, DataItemBuilder};
+pub use crate::iter::IndicatorIteratorExt;
+
+mod on_gap;
+pub use crate::on_gap::{GapAction, OnGap};
+
+mod on_session;
+pub use crate::on_session::OnSession;
+
+mod price_source;
+pub use crate::price_source::{PriceSource, WithSource};
+
+mod ring_buffer;
+pub use crate::ring_buffer::{RingBuffer, Storage};
+
+mod sorted_window;
+
+mod swings;
+
+mod window;
+pub use crate::window::Window;
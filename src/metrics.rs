@@ -0,0 +1,305 @@
+//! Prometheus metrics of indicator outputs.
+//!
+//! [IndicatorMetrics](struct.IndicatorMetrics.html) holds a family of Prometheus gauges and
+//! counters, labelled by series (e.g. the symbol) and indicator. Indicators wrapped by
+//! [monitor](struct.IndicatorMetrics.html#method.monitor) update them on every input:
+//!
+//! * `<namespace>_indicator_value` - latest output,
+//! * `<namespace>_indicator_ready` - 1 once the indicator [is ready](../trait.Warmup.html),
+//!   0 before,
+//! * `<namespace>_indicator_updates_total` - number of inputs consumed,
+//! * `<namespace>_indicator_non_finite_total` - number of NaN or infinite outputs,
+//! * `<namespace>_indicator_resets_total` - number of resets.
+//!
+//! The metrics are registered with an application's `prometheus::Registry`, which is
+//! exported for scraping the usual way. Requires the `prometheus` feature.
+//!
+//! # Example
+//!
+//! ```
+//! use prometheus::{Encoder, Registry, TextEncoder};
+//! use ta::indicators::SimpleMovingAverage;
+//! use ta::metrics::IndicatorMetrics;
+//! use ta::Calculate;
+//!
+//! let registry = Registry::new();
+//! let metrics = IndicatorMetrics::new("bot").unwrap();
+//! metrics.register(&registry).unwrap();
+//!
+//! let mut sma = metrics.monitor("BTCUSD", SimpleMovingAverage::new(2).unwrap());
+//! sma.calc(10.0);
+//! sma.calc(11.0);
+//!
+//! let mut text = Vec::new();
+//! TextEncoder::new().encode(&registry.gather(), &mut text).unwrap();
+//! let text = String::from_utf8(text).unwrap();
+//! assert!(text.contains(r#"bot_indicator_value{indicator="SMA(2)",series="BTCUSD"} 10.5"#));
+//! ```
+
+use std::fmt;
+
+use prometheus::{GaugeVec, IntCounterVec, Opts, Registry};
+
+use crate::errors::*;
+use crate::{Calculate, Float, Next, Reset, Warmup};
+
+const LABELS: &[&str] = &["series", "indicator"];
+
+fn failed(error: prometheus::Error) -> Error {
+    Error::from_kind(ErrorKind::MetricsFailed(error.to_string()))
+}
+
+/// Prometheus gauges and counters of indicators.
+///
+/// Cloning shares the underlying metrics.
+#[derive(Debug, Clone)]
+pub struct IndicatorMetrics {
+    value: GaugeVec,
+    ready: GaugeVec,
+    updates: IntCounterVec,
+    non_finite: IntCounterVec,
+    resets: IntCounterVec,
+}
+
+impl IndicatorMetrics {
+    /// Creates the metrics with names prefixed by `namespace`, fails if it is not a valid
+    /// metric name.
+    pub fn new(namespace: &str) -> Result<Self> {
+        let opts = |name: &str, help: &str| Opts::new(name, help).namespace(namespace);
+        Ok(Self {
+            value: GaugeVec::new(
+                opts("indicator_value", "Latest output of the indicator."),
+                LABELS,
+            )
+            .map_err(failed)?,
+            ready: GaugeVec::new(
+                opts(
+                    "indicator_ready",
+                    "Whether the indicator finished warming up.",
+                ),
+                LABELS,
+            )
+            .map_err(failed)?,
+            updates: IntCounterVec::new(
+                opts(
+                    "indicator_updates_total",
+                    "Inputs consumed by the indicator.",
+                ),
+                LABELS,
+            )
+            .map_err(failed)?,
+            non_finite: IntCounterVec::new(
+                opts(
+                    "indicator_non_finite_total",
+                    "NaN or infinite outputs of the indicator.",
+                ),
+                LABELS,
+            )
+            .map_err(failed)?,
+            resets: IntCounterVec::new(
+                opts("indicator_resets_total", "Resets of the indicator."),
+                LABELS,
+            )
+            .map_err(failed)?,
+        })
+    }
+
+    /// Registers all metrics, fails if metrics of the same name are registered already.
+    pub fn register(&self, registry: &Registry) -> Result<()> {
+        registry
+            .register(Box::new(self.value.clone()))
+            .map_err(failed)?;
+        registry
+            .register(Box::new(self.ready.clone()))
+            .map_err(failed)?;
+        registry
+            .register(Box::new(self.updates.clone()))
+            .map_err(failed)?;
+        registry
+            .register(Box::new(self.non_finite.clone()))
+            .map_err(failed)?;
+        registry
+            .register(Box::new(self.resets.clone()))
+            .map_err(failed)
+    }
+
+    /// Wraps an indicator, so that its outputs update the metrics labelled with `series`
+    /// and the `Display` form of the indicator, e.g. `RSI(14)`.
+    pub fn monitor<I: fmt::Display>(&self, series: &str, indicator: I) -> Monitored<I> {
+        let name = indicator.to_string();
+        self.monitor_as(series, &name, indicator)
+    }
+
+    /// Like [monitor](#method.monitor), with a custom indicator label.
+    pub fn monitor_as<I>(&self, series: &str, name: &str, indicator: I) -> Monitored<I> {
+        let labels = [series, name];
+        Monitored {
+            indicator,
+            value: self.value.with_label_values(&labels),
+            ready: self.ready.with_label_values(&labels),
+            updates: self.updates.with_label_values(&labels),
+            non_finite: self.non_finite.with_label_values(&labels),
+            resets: self.resets.with_label_values(&labels),
+        }
+    }
+}
+
+/// Indicator updating [IndicatorMetrics](struct.IndicatorMetrics.html) with its outputs.
+///
+/// Implements the same traits as the wrapped indicator with a single float output.
+#[derive(Debug, Clone)]
+pub struct Monitored<I> {
+    indicator: I,
+    value: prometheus::Gauge,
+    ready: prometheus::Gauge,
+    updates: prometheus::IntCounter,
+    non_finite: prometheus::IntCounter,
+    resets: prometheus::IntCounter,
+}
+
+impl<I> Monitored<I> {
+    pub fn inner(&self) -> &I {
+        &self.indicator
+    }
+
+    pub fn into_inner(self) -> I {
+        self.indicator
+    }
+
+    fn observe<F: Float>(&self, output: F, ready: bool) -> F {
+        let value = output.to_f64().unwrap_or(f64::NAN);
+        if value.is_finite() {
+            self.value.set(value);
+        } else {
+            self.non_finite.inc();
+        }
+        self.ready.set(if ready { 1.0 } else { 0.0 });
+        self.updates.inc();
+        output
+    }
+}
+
+impl<F: Float, I: Calculate<F, Output = F> + Warmup> Calculate<F> for Monitored<I> {
+    type Output = F;
+
+    fn calc(&mut self, input: F) -> F {
+        let output = self.indicator.calc(input);
+        self.observe(output, self.indicator.is_ready())
+    }
+}
+
+impl<F: Float, T, I: Next<T, Output = F> + Warmup> Next<T> for Monitored<I> {
+    type Output = F;
+
+    fn next(&mut self, input: &T) -> F {
+        let output = self.indicator.next(input);
+        self.observe(output, self.indicator.is_ready())
+    }
+}
+
+impl<I: Reset + Warmup> Reset for Monitored<I> {
+    fn reset(&mut self) {
+        self.indicator.reset();
+        self.ready
+            .set(if self.indicator.is_ready() { 1.0 } else { 0.0 });
+        self.resets.inc();
+    }
+}
+
+impl<I: Warmup> Warmup for Monitored<I> {
+    fn warmup_period(&self) -> u32 {
+        self.indicator.warmup_period()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.indicator.is_ready()
+    }
+}
+
+impl<I: fmt::Display> fmt::Display for Monitored<I> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.indicator.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{Maximum, RelativeStrengthIndex};
+    use crate::test_helper::*;
+
+    fn sample(registry: &Registry, name: &str, indicator: &str) -> Option<f64> {
+        registry
+            .gather()
+            .iter()
+            .find(|family| family.get_name() == name)?
+            .get_metric()
+            .iter()
+            .find(|metric| {
+                metric
+                    .get_label()
+                    .iter()
+                    .any(|label| label.get_name() == "indicator" && label.get_value() == indicator)
+            })
+            .map(|metric| {
+                if name.ends_with("_total") {
+                    metric.get_counter().get_value()
+                } else {
+                    metric.get_gauge().get_value()
+                }
+            })
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(IndicatorMetrics::new("bad namespace").is_err());
+        assert!(IndicatorMetrics::new("bot").is_ok());
+    }
+
+    #[test]
+    fn test_register() {
+        let registry = Registry::new();
+        let metrics = IndicatorMetrics::new("ta").unwrap();
+        metrics.register(&registry).unwrap();
+        assert!(metrics.register(&registry).is_err());
+    }
+
+    #[test]
+    fn test_monitor() {
+        let registry = Registry::new();
+        let metrics = IndicatorMetrics::new("ta").unwrap();
+        metrics.register(&registry).unwrap();
+
+        let mut max = metrics.monitor("ETHUSD", Maximum::new(2).unwrap());
+        let mut rsi = metrics.monitor_as("ETHUSD", "rsi", RelativeStrengthIndex::new(3).unwrap());
+
+        assert_eq!(max.next(&Bar::new().high(3.0)), 3.0);
+        assert_eq!(sample(&registry, "ta_indicator_value", "MAX(2)"), Some(3.0));
+        assert_eq!(sample(&registry, "ta_indicator_ready", "MAX(2)"), Some(0.0));
+        max.calc(2.0);
+        assert_eq!(sample(&registry, "ta_indicator_ready", "MAX(2)"), Some(1.0));
+        assert_eq!(
+            sample(&registry, "ta_indicator_updates_total", "MAX(2)"),
+            Some(2.0)
+        );
+
+        max.calc(f64::INFINITY);
+        assert_eq!(sample(&registry, "ta_indicator_value", "MAX(2)"), Some(3.0));
+        assert_eq!(
+            sample(&registry, "ta_indicator_non_finite_total", "MAX(2)"),
+            Some(1.0)
+        );
+
+        max.reset();
+        assert_eq!(sample(&registry, "ta_indicator_ready", "MAX(2)"), Some(0.0));
+        assert_eq!(
+            sample(&registry, "ta_indicator_resets_total", "MAX(2)"),
+            Some(1.0)
+        );
+
+        rsi.calc(10.0);
+        assert_eq!(sample(&registry, "ta_indicator_value", "rsi"), Some(50.0));
+        assert_eq!(rsi.to_string(), "RSI(3)");
+        assert_eq!(rsi.into_inner().to_string(), "RSI(3)");
+    }
+}
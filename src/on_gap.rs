@@ -0,0 +1,299 @@
+use core::convert::TryFrom;
+use core::fmt;
+
+use crate::errors::*;
+use crate::{Calculate, Decay, Next, Reset, Timestamp, Warmup};
+
+/// What [OnGap](struct.OnGap.html) does with an indicator after a time gap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GapAction {
+    /// Resets the indicator, which seeds itself again with the first input after the gap.
+    Reseed,
+    /// [Decays](trait.Decay.html) the indicator over the periods of `interval` milliseconds
+    /// missed during the gap.
+    Decay { interval: i64 },
+}
+
+impl fmt::Display for GapAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GapAction::Reseed => write!(f, "RESEED"),
+            GapAction::Decay { interval } => write!(f, "DECAY({})", interval),
+        }
+    }
+}
+
+/// Indicator watching the timestamps of its inputs for gaps, created by
+/// [IndicatorExt::on_gap](trait.IndicatorExt.html#method.on_gap).
+///
+/// When more than _max_gap_ milliseconds pass between two inputs, e.g. after an exchange
+/// outage, the [GapAction](enum.GapAction.html) is applied to the indicator before it is fed
+/// the input after the gap. Inputs fed with [calc](trait.Calculate.html#tymethod.calc) carry
+/// no timestamp and are passed through.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::ExponentialMovingAverage;
+/// use ta::sessions::MINUTE;
+/// use ta::{Close, GapAction, IndicatorExt, Next, Timestamp};
+///
+/// struct Bar(i64, f64);
+///
+/// impl Timestamp for Bar {
+///     fn timestamp(&self) -> i64 {
+///         self.0
+///     }
+/// }
+///
+/// impl Close for Bar {
+///     fn close(&self) -> f64 {
+///         self.1
+///     }
+/// }
+///
+/// let mut ema = ExponentialMovingAverage::new(3)
+///     .unwrap()
+///     .on_gap(5 * MINUTE, GapAction::Reseed)
+///     .unwrap();
+/// assert_eq!(ema.next(&Bar(0, 2.0)), 2.0);
+/// assert_eq!(ema.next(&Bar(MINUTE, 4.0)), 3.0);
+/// // seeded again after an hour without bars
+/// assert_eq!(ema.next(&Bar(61 * MINUTE, 10.0)), 10.0);
+///
+/// // minute bars, decayed over the missed minutes
+/// let mut ema = ExponentialMovingAverage::new(3)
+///     .unwrap()
+///     .on_gap(MINUTE, GapAction::Decay { interval: MINUTE })
+///     .unwrap();
+/// ema.next(&Bar(0, 2.0));
+/// // 6.0 is weighted as if it closed both minutes
+/// assert_eq!(ema.next(&Bar(2 * MINUTE, 6.0)), 5.0);
+/// assert_eq!(ema.next(&Bar(9 * MINUTE, 8.0)).round(), 8.0);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OnGap<I> {
+    inner: I,
+    max_gap: i64,
+    action: GapAction,
+    last: Option<i64>,
+    gaps: u64,
+}
+
+impl<I> OnGap<I> {
+    pub(crate) fn new(inner: I, max_gap: i64, action: GapAction) -> Result<Self> {
+        let valid = match action {
+            GapAction::Reseed => true,
+            GapAction::Decay { interval } => interval > 0,
+        };
+        if max_gap <= 0 || !valid {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        Ok(Self {
+            inner,
+            max_gap,
+            action,
+            last: None,
+            gaps: 0,
+        })
+    }
+
+    pub fn max_gap(&self) -> i64 {
+        self.max_gap
+    }
+
+    pub fn action(&self) -> GapAction {
+        self.action
+    }
+
+    /// Number of gaps detected since the last reset.
+    pub fn gaps(&self) -> u64 {
+        self.gaps
+    }
+
+    /// Returns the inner indicator.
+    pub fn into_inner(self) -> I {
+        self.inner
+    }
+}
+
+impl<F, I: Calculate<F>> Calculate<F> for OnGap<I> {
+    type Output = I::Output;
+
+    fn calc(&mut self, input: F) -> Self::Output {
+        self.inner.calc(input)
+    }
+}
+
+impl<T: Timestamp, I: Next<T> + Reset + Decay> Next<T> for OnGap<I> {
+    type Output = I::Output;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let timestamp = input.timestamp();
+        if let Some(last) = self.last {
+            let gap = timestamp.saturating_sub(last);
+            if gap > self.max_gap {
+                self.gaps += 1;
+                match self.action {
+                    GapAction::Reseed => self.inner.reset(),
+                    GapAction::Decay { interval } => {
+                        // periods between the two inputs, without the one of the input
+                        let missed = (gap / interval).saturating_sub(1);
+                        self.inner.decay(u32::try_from(missed).unwrap_or(u32::MAX));
+                    }
+                }
+            }
+        }
+        self.last = Some(timestamp);
+        self.inner.next(input)
+    }
+}
+
+impl<I: Reset> Reset for OnGap<I> {
+    fn reset(&mut self) {
+        self.inner.reset();
+        self.last = None;
+        self.gaps = 0;
+    }
+}
+
+impl<I: Warmup> Warmup for OnGap<I> {
+    fn warmup_period(&self) -> u32 {
+        self.inner.warmup_period()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.inner.is_ready()
+    }
+}
+
+impl<I: fmt::Display> fmt::Display for OnGap<I> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}.on_gap({}, {})",
+            self.inner, self.max_gap, self.action
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{
+        ExponentialMovingAverage, RelativeStrengthIndex, SmoothedMovingAverage,
+    };
+    use crate::test_helper::*;
+    use crate::IndicatorExt;
+
+    fn bar(timestamp: i64, close: f64) -> Bar {
+        Bar::new().timestamp(timestamp).close(close)
+    }
+
+    #[test]
+    fn test_new() {
+        let smma = || SmoothedMovingAverage::new(3).unwrap();
+        assert!(smma().on_gap(0, GapAction::Reseed).is_err());
+        assert!(smma().on_gap(10, GapAction::Decay { interval: 0 }).is_err());
+        assert!(smma().on_gap(10, GapAction::Decay { interval: 5 }).is_ok());
+    }
+
+    #[test]
+    fn test_reseed() {
+        let mut smma = SmoothedMovingAverage::new(2)
+            .unwrap()
+            .on_gap(10, GapAction::Reseed)
+            .unwrap();
+        assert_eq!(smma.next(&bar(0, 2.0)), 2.0);
+        // exactly the maximum gap is no gap
+        assert_eq!(smma.next(&bar(10, 4.0)), 3.0);
+        assert!(smma.is_ready());
+        assert_eq!(smma.next(&bar(21, 8.0)), 8.0);
+        assert!(!smma.is_ready());
+        assert_eq!(smma.gaps(), 1);
+    }
+
+    #[test]
+    fn test_decay() {
+        let mut smma = SmoothedMovingAverage::new(2)
+            .unwrap()
+            .on_gap(10, GapAction::Decay { interval: 10 })
+            .unwrap();
+        smma.next(&bar(0, 2.0));
+        // two periods, one of them missed
+        assert_eq!(smma.next(&bar(20, 6.0)), 5.0);
+        // out of order inputs are no gaps
+        assert_eq!(smma.next(&bar(5, 6.0)), 5.5);
+        assert_eq!(smma.gaps(), 1);
+
+        let mut rsi = RelativeStrengthIndex::new(3)
+            .unwrap()
+            .on_gap(10, GapAction::Decay { interval: 10 })
+            .unwrap();
+        rsi.next(&bar(0, 10.0));
+        rsi.next(&bar(10, 9.0));
+        let mut reference = rsi.clone().into_inner();
+        assert!(rsi.next(&bar(100, 12.0)) > reference.calc(12.0));
+    }
+
+    #[test]
+    fn test_decay_long_gap() {
+        const MINUTE: i64 = 60_000;
+        let decay = GapAction::Decay { interval: MINUTE };
+        // thousands of missed periods decay the state entirely, to the next input
+        let gap = 30 * MINUTE + 6000 * MINUTE;
+
+        let mut rsi = RelativeStrengthIndex::new(14)
+            .unwrap()
+            .on_gap(MINUTE, decay)
+            .unwrap();
+        for i in 0..30 {
+            rsi.next(&bar(i * MINUTE, 100.0 + (i % 3) as f64));
+        }
+        assert_eq!(rsi.next(&bar(gap, 102.0)), 50.0);
+        assert!(rsi.next(&bar(gap + MINUTE, 103.0)).is_finite());
+
+        let mut ema = ExponentialMovingAverage::new(14)
+            .unwrap()
+            .on_gap(MINUTE, decay)
+            .unwrap();
+        let mut smma = SmoothedMovingAverage::new(14)
+            .unwrap()
+            .on_gap(MINUTE, decay)
+            .unwrap();
+        for i in 0..30 {
+            ema.next(&bar(i * MINUTE, 100.0 + i as f64));
+            smma.next(&bar(i * MINUTE, 100.0 + i as f64));
+        }
+        assert_eq!(ema.next(&bar(gap, 90.0)), 90.0);
+        assert_eq!(smma.next(&bar(gap, 90.0)), 90.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut smma = SmoothedMovingAverage::new(2)
+            .unwrap()
+            .on_gap(10, GapAction::Reseed)
+            .unwrap();
+        smma.next(&bar(0, 2.0));
+        smma.next(&bar(50, 4.0));
+        smma.reset();
+        assert_eq!(smma.gaps(), 0);
+        smma.next(&bar(100, 6.0));
+        assert_eq!(smma.next(&bar(105, 8.0)), 7.0);
+        assert_eq!(smma.calc(9.0), 8.0);
+    }
+
+    #[test]
+    fn test_display() {
+        let smma = SmoothedMovingAverage::new(3)
+            .unwrap()
+            .on_gap(60, GapAction::Decay { interval: 15 })
+            .unwrap();
+        assert_eq!(format!("{}", smma), "SMMA(3).on_gap(60, DECAY(15))");
+        assert_eq!(smma.max_gap(), 60);
+        assert_eq!(smma.action(), GapAction::Decay { interval: 15 });
+    }
+}
@@ -0,0 +1,183 @@
+use core::fmt;
+
+use crate::sessions::Session;
+use crate::{Calculate, Next, Reset, Timestamp, Warmup};
+
+/// Indicator starting over with every trading session, created by
+/// [IndicatorExt::on_session](trait.IndicatorExt.html#method.on_session).
+///
+/// Windowed indicators count bars, not time, so e.g. a moving average over the last bars of
+/// Friday and the first bars of Monday silently mixes both sessions. Wrapped, the indicator
+/// is reset before the first bar of every new
+/// [Session](sessions/struct.Session.html), and warms up again from it. Bars outside of the
+/// session are fed to the indicator of the previous session. Inputs fed with
+/// [calc](trait.Calculate.html#tymethod.calc) carry no timestamp and are passed through.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::SimpleMovingAverage;
+/// use ta::sessions::{Session, DAY, HOUR};
+/// use ta::{Close, IndicatorExt, Next, Timestamp};
+///
+/// struct Bar(i64, f64);
+///
+/// impl Timestamp for Bar {
+///     fn timestamp(&self) -> i64 {
+///         self.0
+///     }
+/// }
+///
+/// impl Close for Bar {
+///     fn close(&self) -> f64 {
+///         self.1
+///     }
+/// }
+///
+/// // 2020-01-10 09:30 New York, a Friday
+/// let friday = 1_578_666_600_000;
+/// let mut sma = SimpleMovingAverage::new(2)
+///     .unwrap()
+///     .on_session(Session::us_rth());
+/// sma.next(&Bar(friday, 10.0));
+/// assert_eq!(sma.next(&Bar(friday + HOUR, 12.0)), 11.0);
+/// // Monday starts over
+/// assert_eq!(sma.next(&Bar(friday + 3 * DAY, 20.0)), 20.0);
+/// assert_eq!(sma.sessions(), 2);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OnSession<I> {
+    inner: I,
+    session: Session,
+    // opening of the session of the last bar within a session
+    start: Option<i64>,
+    sessions: u64,
+}
+
+impl<I> OnSession<I> {
+    pub(crate) fn new(inner: I, session: Session) -> Self {
+        Self {
+            inner,
+            session,
+            start: None,
+            sessions: 0,
+        }
+    }
+
+    pub fn session(&self) -> &Session {
+        &self.session
+    }
+
+    /// Number of sessions started since the last reset.
+    pub fn sessions(&self) -> u64 {
+        self.sessions
+    }
+
+    /// Returns the inner indicator.
+    pub fn into_inner(self) -> I {
+        self.inner
+    }
+}
+
+impl<F, I: Calculate<F>> Calculate<F> for OnSession<I> {
+    type Output = I::Output;
+
+    fn calc(&mut self, input: F) -> Self::Output {
+        self.inner.calc(input)
+    }
+}
+
+impl<T: Timestamp, I: Next<T> + Reset> Next<T> for OnSession<I> {
+    type Output = I::Output;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let start = self.session.session_start(input.timestamp());
+        if start.is_some() && start != self.start {
+            if self.start.is_some() {
+                self.inner.reset();
+            }
+            self.start = start;
+            self.sessions += 1;
+        }
+        self.inner.next(input)
+    }
+}
+
+impl<I: Reset> Reset for OnSession<I> {
+    fn reset(&mut self) {
+        self.inner.reset();
+        self.start = None;
+        self.sessions = 0;
+    }
+}
+
+impl<I: Warmup> Warmup for OnSession<I> {
+    fn warmup_period(&self) -> u32 {
+        self.inner.warmup_period()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.inner.is_ready()
+    }
+}
+
+impl<I: fmt::Display> fmt::Display for OnSession<I> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.on_session()", self.inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::SimpleMovingAverage;
+    use crate::sessions::{TimeZone, DAY, HOUR};
+    use crate::test_helper::*;
+    use crate::IndicatorExt;
+
+    fn bar(timestamp: i64, close: f64) -> Bar {
+        Bar::new().timestamp(timestamp).close(close)
+    }
+
+    #[test]
+    fn test_next() {
+        // 09:00 to 17:00 UTC, Monday to Friday
+        let session = Session::new(TimeZone::UTC, 9 * 60, 17 * 60).unwrap();
+        // 1970-01-05 09:00 UTC, a Monday
+        let monday = 4 * DAY + 9 * HOUR;
+        let mut sma = SimpleMovingAverage::new(3).unwrap().on_session(session);
+
+        sma.next(&bar(monday, 10.0));
+        assert_eq!(sma.next(&bar(monday + HOUR, 20.0)), 15.0);
+        // after hours, still Monday's session
+        assert_eq!(sma.next(&bar(monday + 10 * HOUR, 30.0)), 20.0);
+        assert!(sma.is_ready());
+
+        assert_eq!(sma.next(&bar(monday + DAY, 40.0)), 40.0);
+        assert!(!sma.is_ready());
+        assert_eq!(sma.calc(50.0), 45.0);
+        assert_eq!(sma.sessions(), 2);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut sma = SimpleMovingAverage::new(2)
+            .unwrap()
+            .on_session(Session::crypto(TimeZone::UTC));
+        sma.next(&bar(0, 10.0));
+        sma.reset();
+        assert_eq!(sma.sessions(), 0);
+        assert_eq!(sma.next(&bar(HOUR, 20.0)), 20.0);
+        assert_eq!(sma.sessions(), 1);
+    }
+
+    #[test]
+    fn test_display() {
+        let sma = SimpleMovingAverage::new(5)
+            .unwrap()
+            .on_session(Session::us_rth());
+        assert_eq!(format!("{}", sma), "SMA(5).on_session()");
+        assert_eq!(sma.session(), &Session::us_rth());
+    }
+}
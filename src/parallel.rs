@@ -0,0 +1,158 @@
+//! Parallel evaluation of indicators with [rayon](https://docs.rs/rayon).
+//!
+//! Available with the `parallel` feature.
+//!
+//! * [series](fn.series.html) evaluates one indicator over many series, e.g. thousands of
+//!   symbols. Every series gets its own clone of the template indicator.
+//! * [grid](fn.grid.html) evaluates one series with many parameter sets, e.g. stochastic
+//!   lengths from 5 to 200. Every parameter set gets its own indicator.
+//!
+//! Series can hold numbers, which are fed to [calc](../trait.Calculate.html), or bars, which
+//! are fed to [next](../trait.Next.html).
+//!
+//! # Example
+//!
+//! ```
+//! use std::collections::HashMap;
+//! use ta::indicators::SimpleMovingAverage;
+//! use ta::parallel;
+//!
+//! let mut prices = HashMap::new();
+//! prices.insert("BTC", vec![1.0, 2.0, 3.0]);
+//! prices.insert("ETH", vec![4.0, 8.0, 2.0]);
+//!
+//! let sma = SimpleMovingAverage::new(2).unwrap();
+//! let averages = parallel::series(&sma, &prices);
+//! assert_eq!(averages["BTC"], vec![1.0, 1.5, 2.5]);
+//! assert_eq!(averages["ETH"], vec![4.0, 6.0, 5.0]);
+//!
+//! let averages = parallel::grid(1..=3, |&length| SimpleMovingAverage::new(length), &prices["BTC"])
+//!     .unwrap();
+//! assert_eq!(averages[2], (3, vec![1.0, 1.5, 2.0]));
+//! ```
+
+use core::hash::Hash;
+use std::collections::HashMap;
+
+use rayon::prelude::*;
+
+use crate::errors::*;
+use crate::iter::Apply;
+
+/// Evaluates a clone of `template` over every series, in parallel.
+///
+/// The results are keyed by the keys of `series`.
+pub fn series<I, K, S, T, O, M>(template: &I, series: &HashMap<K, S>) -> HashMap<K, Vec<O>>
+where
+    I: for<'a> Apply<&'a T, M, Output = O> + Clone + Send + Sync,
+    K: Hash + Eq + Clone + Send + Sync,
+    S: AsRef<[T]> + Sync,
+    T: Sync,
+    O: Send,
+{
+    series
+        .par_iter()
+        .map(|(key, values)| {
+            let mut indicator = template.clone();
+            let outputs = values
+                .as_ref()
+                .iter()
+                .map(|value| indicator.apply(value))
+                .collect();
+            (key.clone(), outputs)
+        })
+        .collect()
+}
+
+/// Evaluates an indicator built with `build` for every parameter set over `input`, in
+/// parallel.
+///
+/// The results are keyed by the parameter set and keep the order of `params`, which lets
+/// parameters be floats or other types which cannot be hashed. Fails with the first error
+/// returned by `build`.
+pub fn grid<P, B, I, T, O, M>(
+    params: impl IntoIterator<Item = P>,
+    build: B,
+    input: &[T],
+) -> Result<Vec<(P, Vec<O>)>>
+where
+    P: Send,
+    B: Fn(&P) -> Result<I> + Sync,
+    I: for<'a> Apply<&'a T, M, Output = O>,
+    T: Sync,
+    O: Send,
+{
+    params
+        .into_iter()
+        .collect::<Vec<P>>()
+        .into_par_iter()
+        .map(|param| {
+            let mut indicator = build(&param)?;
+            let outputs = input.iter().map(|value| indicator.apply(value)).collect();
+            Ok((param, outputs))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{FastStochastic, SimpleMovingAverage};
+    use crate::test_helper::*;
+    use crate::{Calculate, Next};
+
+    #[test]
+    fn test_series() {
+        let mut prices = HashMap::new();
+        for symbol in 0..100 {
+            let values: Vec<f64> = (0..50).map(|i| (symbol * i % 17) as f64).collect();
+            prices.insert(symbol, values);
+        }
+
+        let template = SimpleMovingAverage::new(5).unwrap();
+        let averages = series(&template, &prices);
+
+        assert_eq!(averages.len(), 100);
+        for (symbol, values) in prices.iter() {
+            let mut sma = template.clone();
+            let expected: Vec<f64> = values.iter().map(|&value| sma.calc(value)).collect();
+            assert_eq!(averages[symbol], expected);
+        }
+    }
+
+    #[test]
+    fn test_series_bars() {
+        let mut bars = HashMap::new();
+        bars.insert(
+            "a",
+            vec![
+                Bar::new().high(2).low(1).close(2),
+                Bar::new().high(3).low(1).close(1),
+            ],
+        );
+
+        let stoch = series(&FastStochastic::new(2).unwrap(), &bars);
+        assert_eq!(stoch["a"], vec![100.0, 0.0]);
+    }
+
+    #[test]
+    fn test_grid() {
+        let bars: Vec<Bar> = (0..30)
+            .map(|i| Bar::new().high(i + 2).low(i).close(i + 1))
+            .collect();
+
+        let results = grid(5..200, |&length| FastStochastic::new(length), &bars).unwrap();
+        assert_eq!(results.len(), 195);
+        for (length, outputs) in results {
+            let mut stoch = FastStochastic::new(length).unwrap();
+            let expected: Vec<f64> = bars.iter().map(|bar| stoch.next(bar)).collect();
+            assert_eq!(outputs, expected);
+        }
+    }
+
+    #[test]
+    fn test_grid_error() {
+        let prices = [1.0, 2.0];
+        assert!(grid(vec![2, 0, 1], |&n| SimpleMovingAverage::new(n), &prices).is_err());
+    }
+}
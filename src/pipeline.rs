@@ -0,0 +1,859 @@
+//! Pipelines of indicators and alert rules built from configuration.
+//!
+//! A [PipelineConfig](struct.PipelineConfig.html) describes named indicators, as
+//! [registry](../registry/index.html) specs optionally chained with further indicators,
+//! [alert rules](../alerts/index.html) over their outputs and [signals](../signals/index.html)
+//! of indicators given as specs. With the `serde` feature it can be deserialized from any
+//! format serde supports, e.g. JSON:
+//!
+//! ```json
+//! {
+//!   "indicators": [
+//!     { "name": "fast", "spec": "EMA(12)" },
+//!     { "name": "slow", "spec": "EMA(26)" },
+//!     { "name": "stoch", "spec": "FAST_STOCH(14)", "then": ["EMA(3)"] }
+//!   ],
+//!   "alerts": [
+//!     { "name": "golden cross", "series": "fast", "trigger": { "crosses_over": "slow" } },
+//!     { "name": "overbought", "series": "stoch", "trigger": { "crosses_above": 80 },
+//!       "confirm": 2, "cooldown": 10 }
+//!   ],
+//!   "signals": [
+//!     { "name": "rsi", "rule": { "oscillator": { "spec": "RSI(14)", "oversold": 30,
+//!       "overbought": 70 } } },
+//!     { "name": "trend", "rule": { "crossover": { "fast": "EMA(12)", "slow": "EMA(26)" } } }
+//!   ]
+//! }
+//! ```
+//!
+//! or the equivalent YAML or TOML, so strategies can be reconfigured without recompiling.
+//! [Pipeline::new](struct.Pipeline.html#method.new) builds the runtime pipeline with a
+//! [Registry](../registry/struct.Registry.html), which may contain custom indicators.
+//!
+//! Chained indicators are fed the output of the previous one as a bar with all prices equal
+//! to it and no volume. Alert rules refer to indicators by name, or to the bar close as
+//! `close`. Signals are fed the bars with indicators of their own, so that a signal can be
+//! added or tuned without touching the indicators alerts watch.
+//!
+//! A running pipeline applies a changed configuration with
+//! [reload](struct.Pipeline.html#method.reload), which keeps the state of everything the
+//! change does not affect, so that a live strategy can be tuned without warming up from
+//! scratch.
+//!
+//! # Example
+//!
+//! ```
+//! use ta::alerts::Trigger;
+//! use ta::pipeline::{AlertConfig, IndicatorConfig, Pipeline, PipelineConfig};
+//! use ta::registry::Registry;
+//! use ta::{DataItem, Next};
+//!
+//! let config = PipelineConfig {
+//!     indicators: vec![IndicatorConfig::new("sma", "SMA(2)")],
+//!     alerts: vec![AlertConfig::new("above SMA", "close", Trigger::CrossesOver("sma".into()))],
+//!     signals: vec![],
+//! };
+//! let mut pipeline = Pipeline::new(&config, &Registry::new()).unwrap();
+//!
+//! let mut fired = Vec::new();
+//! for &close in &[10.0, 9.0, 8.0, 9.5] {
+//!     let bar = DataItem::builder()
+//!         .open(close)
+//!         .high(close)
+//!         .low(close)
+//!         .close(close)
+//!         .volume(1.0)
+//!         .build()
+//!         .unwrap();
+//!     let output = pipeline.next(&bar);
+//!     fired.extend(output.alerts);
+//! }
+//! assert_eq!(pipeline.value("sma"), Some(8.75));
+//! assert_eq!(fired.len(), 1);
+//! assert_eq!(pipeline.alerts().rule(fired[0].rule).name(), "above SMA");
+//! ```
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::alerts::{Alert, Alerts, Rule, Trigger};
+use crate::errors::*;
+use crate::registry::{BoxedIndicator, Registry};
+use crate::signals::{MaCrossover, NextSignal, Oscillator, Signal};
+use crate::{Close, DataItem, Finite, Next, Reset, Warmup};
+
+/// Name of the bar close series in alert rules.
+pub const CLOSE: &str = "close";
+
+/// Configuration of a [Pipeline](struct.Pipeline.html).
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PipelineConfig {
+    pub indicators: Vec<IndicatorConfig>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub alerts: Vec<AlertConfig>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub signals: Vec<SignalConfig>,
+}
+
+/// Named indicator of a [PipelineConfig](struct.PipelineConfig.html).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IndicatorConfig {
+    /// Name of the output, referred to by alert rules.
+    pub name: String,
+    /// Spec of the indicator fed with bars, e.g. `"RSI(14)"`.
+    pub spec: String,
+    /// Specs of indicators each fed with the output of the previous one.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub then: Vec<String>,
+}
+
+impl IndicatorConfig {
+    pub fn new<N: Into<String>, S: Into<String>>(name: N, spec: S) -> Self {
+        Self {
+            name: name.into(),
+            spec: spec.into(),
+            then: Vec::new(),
+        }
+    }
+}
+
+/// Alert rule of a [PipelineConfig](struct.PipelineConfig.html), see
+/// [Rule](../alerts/struct.Rule.html).
+///
+/// Crossings of two series name the other series in the trigger, e.g.
+/// `Trigger::CrossesOver("slow")`, which is `{ "crosses_over": "slow" }` in JSON.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AlertConfig {
+    pub name: String,
+    /// Name of the watched indicator or `close`.
+    pub series: String,
+    pub trigger: Trigger<f64, String>,
+    #[cfg_attr(feature = "serde", serde(default = "one"))]
+    pub confirm: u32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub cooldown: u32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub once: bool,
+}
+
+#[cfg(feature = "serde")]
+fn one() -> u32 {
+    1
+}
+
+impl AlertConfig {
+    pub fn new<N: Into<String>, S: Into<String>>(
+        name: N,
+        series: S,
+        trigger: Trigger<f64, String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            series: series.into(),
+            trigger,
+            confirm: 1,
+            cooldown: 0,
+            once: false,
+        }
+    }
+}
+
+/// Named trading signal of a [PipelineConfig](struct.PipelineConfig.html), see
+/// [signals](../signals/index.html).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SignalConfig {
+    pub name: String,
+    pub rule: SignalRule,
+}
+
+impl SignalConfig {
+    pub fn new<N: Into<String>>(name: N, rule: SignalRule) -> Self {
+        Self {
+            name: name.into(),
+            rule,
+        }
+    }
+}
+
+/// Rule of a [SignalConfig](struct.SignalConfig.html), with its indicators as registry specs.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum SignalRule {
+    /// [Oscillator](../signals/struct.Oscillator.html) leaving its oversold or overbought
+    /// zone, e.g. `RSI(14)` with 30 and 70.
+    Oscillator {
+        spec: String,
+        oversold: f64,
+        overbought: f64,
+    },
+    /// [Crossover](../signals/struct.MaCrossover.html) of a fast and a slow indicator.
+    Crossover { fast: String, slow: String },
+}
+
+#[derive(Clone)]
+enum SignalNode {
+    Oscillator(Oscillator<BoxedIndicator>),
+    Crossover(MaCrossover<BoxedIndicator, BoxedIndicator>),
+}
+
+impl SignalNode {
+    fn new(rule: &SignalRule, registry: &Registry) -> Result<Self> {
+        Ok(match rule {
+            SignalRule::Oscillator {
+                spec,
+                oversold,
+                overbought,
+            } => SignalNode::Oscillator(Oscillator::new(
+                registry.parse(spec)?,
+                *oversold,
+                *overbought,
+            )?),
+            SignalRule::Crossover { fast, slow } => SignalNode::Crossover(MaCrossover::new(
+                registry.parse(fast)?,
+                registry.parse(slow)?,
+            )),
+        })
+    }
+
+    fn next(&mut self, bar: &DataItem) -> Signal {
+        match self {
+            SignalNode::Oscillator(oscillator) => oscillator.next_signal(bar),
+            SignalNode::Crossover(crossover) => crossover.next_signal(bar),
+        }
+    }
+
+    fn reset(&mut self) {
+        match self {
+            SignalNode::Oscillator(oscillator) => oscillator.reset(),
+            SignalNode::Crossover(crossover) => crossover.reset(),
+        }
+    }
+
+    fn warmup_period(&self) -> u32 {
+        match self {
+            SignalNode::Oscillator(oscillator) => oscillator.indicator().warmup_period(),
+            SignalNode::Crossover(crossover) => crossover
+                .fast()
+                .warmup_period()
+                .max(crossover.slow().warmup_period()),
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        match self {
+            SignalNode::Oscillator(oscillator) => oscillator.indicator().is_ready(),
+            SignalNode::Crossover(crossover) => {
+                crossover.fast().is_ready() && crossover.slow().is_ready()
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+struct Node {
+    spec: String,
+    indicator: BoxedIndicator,
+    // inputs consumed since the previous node of the chain is ready
+    count: u32,
+}
+
+#[derive(Clone)]
+struct Stage {
+    name: String,
+    nodes: Vec<Node>,
+}
+
+impl Stage {
+    fn next(&mut self, bar: &DataItem) -> f64 {
+        let mut value = f64::NAN;
+        let mut ready = true;
+        for (index, node) in self.nodes.iter_mut().enumerate() {
+            value = if index == 0 {
+                node.indicator.update(bar)
+            } else {
+                node.indicator
+                    .update(&DataItem::new(value, value, value, value, 0.0))
+            };
+            if ready {
+                node.count = node.count.saturating_add(1);
+            }
+            ready = node.count >= node.indicator.warmup_period();
+        }
+        value
+    }
+
+    fn warmup_period(&self) -> u32 {
+        self.nodes
+            .iter()
+            .map(|node| node.indicator.warmup_period())
+            .fold(1, |total, period| (total + period).saturating_sub(1))
+    }
+
+    fn is_ready(&self) -> bool {
+        self.nodes
+            .iter()
+            .all(|node| node.count >= node.indicator.warmup_period())
+    }
+}
+
+/// Indicators, alert rules and signals evaluated together on every bar.
+///
+/// Cloning a pipeline copies the state of all its indicators and rules, e.g. to evaluate
+/// hypothetical bars in a [Scenario](../scenario/struct.Scenario.html).
+#[derive(Clone)]
+pub struct Pipeline {
+    stages: Vec<Stage>,
+    alerts: Alerts,
+    // the close followed by the outputs of the stages
+    values: Vec<f64>,
+    alert_configs: Vec<AlertConfig>,
+    signals: Vec<SignalNode>,
+    signal_configs: Vec<SignalConfig>,
+}
+
+/// Output of [Pipeline](struct.Pipeline.html).
+#[derive(Debug, Clone, PartialEq)]
+pub struct PipelineOutput {
+    /// Outputs of the indicators, in the order of the configuration.
+    pub values: Vec<f64>,
+    /// Alerts fired on this bar.
+    pub alerts: Vec<Alert>,
+    /// Signals of this bar, in the order of the configuration.
+    pub signals: Vec<Signal>,
+}
+
+impl Finite for PipelineOutput {
+    fn is_finite(&self) -> bool {
+        self.values.iter().all(Finite::is_finite)
+    }
+}
+
+impl Pipeline {
+    /// Builds a pipeline, creating the indicators with `registry`.
+    ///
+    /// Fails on invalid or unknown indicator specs, on indicator names used twice or
+    /// named `close`, on alert rules referring to unknown names, on invalid rules and on
+    /// signal names used twice.
+    pub fn new(config: &PipelineConfig, registry: &Registry) -> Result<Self> {
+        let mut stages: Vec<Stage> = Vec::with_capacity(config.indicators.len());
+        for indicator in &config.indicators {
+            let name = &indicator.name;
+            if name == CLOSE || stages.iter().any(|stage| &stage.name == name) {
+                return Err(Error::from_kind(ErrorKind::InvalidSpec(name.clone())));
+            }
+            let nodes = core::iter::once(&indicator.spec)
+                .chain(indicator.then.iter())
+                .map(|spec| {
+                    Ok(Node {
+                        spec: spec.clone(),
+                        indicator: registry.parse(spec)?,
+                        count: 0,
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            stages.push(Stage {
+                name: name.clone(),
+                nodes,
+            });
+        }
+
+        let series = |name: &str| {
+            if name == CLOSE {
+                return Ok(0);
+            }
+            match stages.iter().position(|stage| stage.name == name) {
+                Some(index) => Ok(index + 1),
+                None => Err(Error::from_kind(ErrorKind::UnknownIndicator(
+                    name.to_string(),
+                ))),
+            }
+        };
+        let mut alerts = Alerts::new();
+        for alert in &config.alerts {
+            let trigger = match &alert.trigger {
+                Trigger::CrossesAbove(level) => Trigger::CrossesAbove(*level),
+                Trigger::CrossesBelow(level) => Trigger::CrossesBelow(*level),
+                Trigger::CrossesOver(other) => Trigger::CrossesOver(series(other)?),
+                Trigger::CrossesUnder(other) => Trigger::CrossesUnder(series(other)?),
+                Trigger::NewHigh(n) => Trigger::NewHigh(*n),
+                Trigger::NewLow(n) => Trigger::NewLow(*n),
+                Trigger::EntersBand(lower, upper) => Trigger::EntersBand(*lower, *upper),
+                Trigger::ChangesSign => Trigger::ChangesSign,
+            };
+            let mut rule = Rule::new(alert.name.clone(), series(&alert.series)?, trigger)
+                .confirm(alert.confirm)
+                .cooldown(alert.cooldown);
+            if alert.once {
+                rule = rule.once();
+            }
+            alerts.try_add(rule)?;
+        }
+
+        let mut signals = Vec::with_capacity(config.signals.len());
+        for (index, signal) in config.signals.iter().enumerate() {
+            if config.signals[..index]
+                .iter()
+                .any(|other| other.name == signal.name)
+            {
+                return Err(Error::from_kind(ErrorKind::InvalidSpec(
+                    signal.name.clone(),
+                )));
+            }
+            signals.push(SignalNode::new(&signal.rule, registry)?);
+        }
+
+        let values = alloc::vec![f64::NAN; stages.len() + 1];
+        Ok(Self {
+            stages,
+            alerts,
+            values,
+            alert_configs: config.alerts.clone(),
+            signals,
+            signal_configs: config.signals.clone(),
+        })
+    }
+
+    /// Applies an updated configuration without losing the state of the unchanged parts,
+    /// and returns the names of the indicators whose outputs start over.
+    ///
+    /// Indicators are matched by name. Of a chain, the indicators up to the first changed
+    /// spec keep their state and only the rest is created anew, so e.g. changing the
+    /// smoothing of an oscillator does not warm up the oscillator again. Alert rules keep
+    /// their state if neither they nor the series they watch changed, and signals if their
+    /// rule with the same name did not change.
+    ///
+    /// Fails like [new](#method.new), leaving the pipeline unchanged.
+    pub fn reload(&mut self, config: &PipelineConfig, registry: &Registry) -> Result<Vec<String>> {
+        let mut pipeline = Self::new(config, registry)?;
+        let mut previous: Vec<Option<Stage>> = self.stages.drain(..).map(Some).collect();
+        let mut restarted = Vec::new();
+        // names of the series which continue
+        let mut kept = alloc::vec![CLOSE];
+        for (index, stage) in pipeline.stages.iter_mut().enumerate() {
+            let old = previous
+                .iter()
+                .position(|old| matches!(old, Some(old) if old.name == stage.name));
+            if let Some(old_index) = old {
+                // taken, as names are unique
+                let old = previous[old_index].take().unwrap();
+                let old_len = old.nodes.len();
+                let same = old
+                    .nodes
+                    .iter()
+                    .zip(stage.nodes.iter())
+                    .take_while(|(old, new)| old.spec == new.spec)
+                    .count();
+                for (node, old) in stage.nodes.iter_mut().zip(old.nodes).take(same) {
+                    *node = old;
+                }
+                if same == stage.nodes.len() && same == old_len {
+                    pipeline.values[index + 1] = self.values[old_index + 1];
+                    kept.push(config.indicators[index].name.as_str());
+                    continue;
+                }
+            }
+            restarted.push(stage.name.clone());
+        }
+        pipeline.values[0] = self.values[0];
+
+        let continues = |name: &str| kept.contains(&name);
+        let old_configs = &self.alert_configs;
+        pipeline.alerts.inherit(&self.alerts, |index| {
+            let alert = &config.alerts[index];
+            let other = match &alert.trigger {
+                Trigger::CrossesOver(other) | Trigger::CrossesUnder(other) => Some(other),
+                _ => None,
+            };
+            if !core::iter::once(&alert.series)
+                .chain(other)
+                .all(|name| continues(name))
+            {
+                return None;
+            }
+            old_configs.iter().position(|old| old == alert)
+        });
+
+        for (signal, config) in pipeline.signals.iter_mut().zip(&config.signals) {
+            if let Some(old) = self.signal_configs.iter().position(|old| old == config) {
+                *signal = self.signals[old].clone();
+            }
+        }
+
+        *self = pipeline;
+        Ok(restarted)
+    }
+
+    /// Names of the indicators, in the order of their outputs.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.stages.iter().map(|stage| stage.name.as_str())
+    }
+
+    /// Names of the signals, in the order of their outputs.
+    pub fn signal_names(&self) -> impl Iterator<Item = &str> {
+        self.signal_configs
+            .iter()
+            .map(|signal| signal.name.as_str())
+    }
+
+    /// Latest output of the indicator named `name`, `NaN` before the first bar.
+    pub fn value(&self, name: &str) -> Option<f64> {
+        let index = self.stages.iter().position(|stage| stage.name == name)?;
+        Some(self.values[index + 1])
+    }
+
+    // names of the indicators with their chains, e.g. `FAST_STOCH(14) -> EMA(3)`, and their
+    // warm-up periods
+    #[cfg(any(feature = "parquet", feature = "arrow-ipc"))]
+    pub(crate) fn chains(&self) -> impl Iterator<Item = (&str, String, u32)> {
+        self.stages.iter().map(|stage| {
+            let chain: Vec<String> = stage
+                .nodes
+                .iter()
+                .map(|node| node.indicator.to_string())
+                .collect();
+            (
+                stage.name.as_str(),
+                chain.join(" -> "),
+                stage.warmup_period(),
+            )
+        })
+    }
+
+    /// Alert rules, indexed by [Alert::rule](../alerts/struct.Alert.html#structfield.rule).
+    pub fn alerts(&self) -> &Alerts {
+        &self.alerts
+    }
+}
+
+impl Next<DataItem> for Pipeline {
+    type Output = PipelineOutput;
+
+    fn next(&mut self, input: &DataItem) -> PipelineOutput {
+        self.values[0] = input.close();
+        for (stage, value) in self.stages.iter_mut().zip(self.values[1..].iter_mut()) {
+            *value = stage.next(input);
+        }
+        PipelineOutput {
+            values: self.values[1..].to_vec(),
+            alerts: self.alerts.check(&self.values),
+            signals: self
+                .signals
+                .iter_mut()
+                .map(|signal| signal.next(input))
+                .collect(),
+        }
+    }
+}
+
+impl Reset for Pipeline {
+    fn reset(&mut self) {
+        for stage in self.stages.iter_mut() {
+            for node in stage.nodes.iter_mut() {
+                node.indicator.reset();
+                node.count = 0;
+            }
+        }
+        for value in self.values.iter_mut() {
+            *value = f64::NAN;
+        }
+        self.alerts.reset();
+        for signal in self.signals.iter_mut() {
+            signal.reset();
+        }
+    }
+}
+
+impl Warmup for Pipeline {
+    /// Longest warm-up of the indicator chains, where every chained indicator starts warming
+    /// up once the previous one is ready, and of the indicators of the signals.
+    fn warmup_period(&self) -> u32 {
+        self.stages
+            .iter()
+            .map(Stage::warmup_period)
+            .chain(self.signals.iter().map(SignalNode::warmup_period))
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn is_ready(&self) -> bool {
+        self.stages.iter().all(Stage::is_ready) && self.signals.iter().all(SignalNode::is_ready)
+    }
+}
+
+impl fmt::Debug for Pipeline {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut map = f.debug_map();
+        for stage in &self.stages {
+            let specs: Vec<String> = stage
+                .nodes
+                .iter()
+                .map(|node| node.indicator.to_string())
+                .collect();
+            map.entry(&stage.name, &specs);
+        }
+        map.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{ExponentialMovingAverage, FastStochastic, SimpleMovingAverage};
+    use crate::Calculate;
+
+    fn bar(high: f64, low: f64, close: f64) -> DataItem {
+        DataItem::new(close, high, low, close, 1.0)
+    }
+
+    fn config() -> PipelineConfig {
+        let mut stoch = IndicatorConfig::new("stoch", "FAST_STOCH(3)");
+        stoch.then.push("EMA(2)".into());
+        let mut overbought = AlertConfig::new("overbought", "stoch", Trigger::CrossesAbove(80.0));
+        overbought.cooldown = 5;
+        PipelineConfig {
+            indicators: vec![IndicatorConfig::new("fast", "EMA(2)"), stoch],
+            alerts: vec![
+                overbought,
+                AlertConfig::new(
+                    "fast under close",
+                    "fast",
+                    Trigger::CrossesUnder(CLOSE.into()),
+                ),
+            ],
+            signals: vec![
+                SignalConfig::new(
+                    "stoch",
+                    SignalRule::Oscillator {
+                        spec: "FAST_STOCH(3)".into(),
+                        oversold: 20.0,
+                        overbought: 80.0,
+                    },
+                ),
+                SignalConfig::new(
+                    "trend",
+                    SignalRule::Crossover {
+                        fast: "EMA(2)".into(),
+                        slow: "SMA(1)".into(),
+                    },
+                ),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        let registry = Registry::new();
+        assert!(Pipeline::new(&config(), &registry).is_ok());
+        assert!(Pipeline::new(&PipelineConfig::default(), &registry).is_ok());
+
+        let mut invalid = config();
+        invalid.indicators[0].spec = "EMA(0)".into();
+        assert!(Pipeline::new(&invalid, &registry).is_err());
+
+        let mut unknown = config();
+        unknown.indicators[1].then.push("NOPE(3)".into());
+        assert!(Pipeline::new(&unknown, &registry).is_err());
+
+        let mut duplicate = config();
+        duplicate.indicators[1].name = "fast".into();
+        assert!(Pipeline::new(&duplicate, &registry).is_err());
+
+        let mut close = config();
+        close.indicators[1].name = CLOSE.into();
+        assert!(Pipeline::new(&close, &registry).is_err());
+
+        let mut series = config();
+        series.alerts[0].series = "slow".into();
+        match Pipeline::new(&series, &registry) {
+            Err(Error(ErrorKind::UnknownIndicator(name), _)) => assert_eq!(name, "slow"),
+            other => panic!("unexpected {:?}", other),
+        }
+
+        let mut rule = config();
+        rule.alerts[0].confirm = 0;
+        assert!(Pipeline::new(&rule, &registry).is_err());
+
+        let mut signal = config();
+        signal.signals[1].name = "stoch".into();
+        assert!(Pipeline::new(&signal, &registry).is_err());
+
+        let mut levels = config();
+        levels.signals[0].rule = SignalRule::Oscillator {
+            spec: "FAST_STOCH(3)".into(),
+            oversold: 80.0,
+            overbought: 20.0,
+        };
+        assert!(Pipeline::new(&levels, &registry).is_err());
+
+        let mut spec = config();
+        spec.signals[1].rule = SignalRule::Crossover {
+            fast: "EMA(2)".into(),
+            slow: "NOPE(3)".into(),
+        };
+        assert!(Pipeline::new(&spec, &registry).is_err());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut pipeline = Pipeline::new(&config(), &Registry::new()).unwrap();
+        assert_eq!(pipeline.names().collect::<Vec<_>>(), vec!["fast", "stoch"]);
+        assert!(pipeline.value("fast").unwrap().is_nan());
+        assert_eq!(pipeline.value("slow"), None);
+
+        let mut ema = ExponentialMovingAverage::new(2).unwrap();
+        let mut stoch = FastStochastic::new(3).unwrap();
+        let mut smooth = ExponentialMovingAverage::new(2).unwrap();
+        let mut oscillator = Oscillator::new(FastStochastic::new(3).unwrap(), 20.0, 80.0).unwrap();
+        let mut crossover = MaCrossover::new(
+            ExponentialMovingAverage::new(2).unwrap(),
+            SimpleMovingAverage::new(1).unwrap(),
+        );
+        let mut fired = Vec::new();
+        let mut signals = Vec::new();
+        let bars = [
+            bar(11.0, 9.0, 10.0),
+            bar(10.0, 8.0, 8.5),
+            bar(9.0, 7.0, 7.5),
+            bar(12.0, 8.0, 11.5),
+            bar(13.0, 11.0, 12.5),
+        ];
+        for bar in &bars {
+            let output = pipeline.next(bar);
+            let expected = [ema.next(bar), smooth.calc(stoch.next(bar))];
+            assert_eq!(output.values, expected);
+            assert_eq!(pipeline.value("stoch"), Some(expected[1]));
+            fired.extend(output.alerts.iter().map(|alert| (alert.rule, alert.period)));
+            assert_eq!(
+                output.signals,
+                vec![oscillator.next_signal(bar), crossover.next_signal(bar)]
+            );
+            signals.push(output.signals);
+        }
+        assert_eq!(fired, vec![(1, 3), (0, 4)]);
+        assert_eq!(signals[3], vec![Signal::Buy, Signal::Sell]);
+        assert_eq!(
+            pipeline.signal_names().collect::<Vec<_>>(),
+            vec!["stoch", "trend"]
+        );
+
+        pipeline.reset();
+        assert!(pipeline.value("fast").unwrap().is_nan());
+        let output = pipeline.next(&bars[0]);
+        assert_eq!(output.values, vec![10.0, 50.0]);
+    }
+
+    #[test]
+    fn test_warmup() {
+        let mut pipeline = Pipeline::new(&config(), &Registry::new()).unwrap();
+        assert_eq!(pipeline.warmup_period(), 4);
+        for _ in 0..3 {
+            pipeline.next(&bar(2.0, 1.0, 1.5));
+            assert!(!pipeline.is_ready());
+        }
+        pipeline.next(&bar(2.0, 1.0, 1.5));
+        assert!(pipeline.is_ready());
+    }
+
+    #[test]
+    fn test_reload() {
+        let registry = Registry::new();
+        let mut pipeline = Pipeline::new(&config(), &registry).unwrap();
+        let mut untouched = Pipeline::new(&config(), &registry).unwrap();
+        let mut stoch = FastStochastic::new(3).unwrap();
+        let bars = [
+            bar(11.0, 9.0, 10.0),
+            bar(10.0, 8.0, 8.5),
+            bar(9.0, 7.0, 7.5),
+            bar(12.0, 8.0, 11.5),
+            bar(13.0, 11.0, 12.5),
+            bar(12.0, 10.0, 10.5),
+        ];
+        for bar in &bars[..3] {
+            stoch.next(bar);
+            pipeline.next(bar);
+            untouched.next(bar);
+        }
+
+        let mut invalid = config();
+        invalid.indicators[1].then[0] = "EMA(0)".into();
+        assert!(pipeline.reload(&invalid, &registry).is_err());
+        assert!(pipeline.reload(&config(), &registry).unwrap().is_empty());
+        assert_eq!(pipeline.value("stoch"), untouched.value("stoch"));
+        for bar in &bars[3..5] {
+            stoch.next(bar);
+            assert_eq!(pipeline.next(bar), untouched.next(bar));
+        }
+        assert!(pipeline.is_ready());
+
+        let mut updated = config();
+        updated.indicators[1].then[0] = "EMA(3)".into();
+        updated
+            .indicators
+            .push(IndicatorConfig::new("max", "MAX(2)"));
+        updated.alerts.swap(0, 1);
+        updated.signals[1].rule = SignalRule::Crossover {
+            fast: "EMA(2)".into(),
+            slow: "SMA(2)".into(),
+        };
+        let fast = pipeline.value("fast");
+        let restarted = pipeline.reload(&updated, &registry).unwrap();
+        assert_eq!(restarted, vec!["stoch", "max"]);
+        assert_eq!(pipeline.value("fast"), fast);
+        assert!(pipeline.value("stoch").unwrap().is_nan());
+        assert!(!pipeline.is_ready());
+
+        // the oscillator continues, only the smoothing starts over
+        let mut smooth = ExponentialMovingAverage::new(3).unwrap();
+        let output = pipeline.next(&bars[5]);
+        let expected = untouched.next(&bars[5]);
+        assert_eq!(output.values[0], expected.values[0]);
+        assert_eq!(output.values[1], smooth.calc(stoch.next(&bars[5])));
+        assert_eq!(output.values[2], 12.0);
+        // the unchanged signal continues, the changed one warms up again
+        assert_eq!(output.signals[0], expected.signals[0]);
+        assert_eq!(output.signals[1], Signal::Hold);
+        pipeline.next(&bars[5]);
+        assert!(!pipeline.is_ready());
+        pipeline.next(&bars[5]);
+        assert!(pipeline.is_ready());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let json = r#"{
+            "indicators": [
+                { "name": "fast", "spec": "EMA(2)" },
+                { "name": "stoch", "spec": "FAST_STOCH(3)", "then": ["EMA(2)"] }
+            ],
+            "alerts": [
+                { "name": "overbought", "series": "stoch",
+                  "trigger": { "crosses_above": 80 }, "cooldown": 5 },
+                { "name": "fast under close", "series": "fast",
+                  "trigger": { "crosses_under": "close" } }
+            ],
+            "signals": [
+                { "name": "stoch", "rule": { "oscillator": { "spec": "FAST_STOCH(3)",
+                  "oversold": 20, "overbought": 80 } } },
+                { "name": "trend", "rule": { "crossover": { "fast": "EMA(2)", "slow": "SMA(1)" } } }
+            ]
+        }"#;
+        let parsed: PipelineConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(parsed, config());
+
+        let json = serde_json::to_string(&parsed).unwrap();
+        assert_eq!(
+            serde_json::from_str::<PipelineConfig>(&json).unwrap(),
+            parsed
+        );
+    }
+}
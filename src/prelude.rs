@@ -0,0 +1,42 @@
+//! Traits and types needed by most users of the crate.
+//!
+//! `use ta::prelude::*;` brings in the indicator traits ([Next](../trait.Next.html),
+//! [Calculate](../trait.Calculate.html), [Reset](../trait.Reset.html),
+//! [Warmup](../trait.Warmup.html), [Peek](../trait.Peek.html), ...), the bar traits
+//! ([Open](../trait.Open.html), [Close](../trait.Close.html), ...,
+//! [Timestamp](../trait.Timestamp.html)) and the extension traits of the other modules, so
+//! their methods are available without importing each of them: composition with
+//! [IndicatorExt](../trait.IndicatorExt.html), iterators with
+//! [IndicatorIteratorExt](../trait.IndicatorIteratorExt.html), signals with
+//! [NextSignal](../signals/trait.NextSignal.html), rules with
+//! [Rule](../rules/trait.Rule.html) and [RuleExt](../rules/trait.RuleExt.html), and rollback
+//! with [Rollback](../rollback/trait.Rollback.html).
+//!
+//! Indicators themselves stay in the [indicators](../indicators/index.html) module and the
+//! types of the other subsystems (signals, rules, backtests, alerts, pipelines, ...) in their
+//! modules.
+//!
+//! # Example
+//!
+//! ```
+//! use ta::indicators::{RelativeStrengthIndex, SimpleMovingAverage};
+//! use ta::prelude::*;
+//!
+//! let rsi = SimpleMovingAverage::new(2)
+//!     .unwrap()
+//!     .then(RelativeStrengthIndex::new(3).unwrap());
+//! let outputs: Vec<f64> = [1.0, 2.0, 3.0].iter().indicator(rsi).collect();
+//! assert_eq!(outputs[0], 50.0);
+//! ```
+
+pub use crate::combinators::IndicatorExt;
+pub use crate::iter::IndicatorIteratorExt;
+pub use crate::rollback::Rollback;
+pub use crate::rules::{Rule, RuleExt};
+pub use crate::signals::{Bands, NextSignal};
+pub use crate::{
+    Aggressor, AggressorSide, AggressorVolume, Calculate, Close, DataItem, Decay, Finite, Float,
+    High, Indicator, Low, Next, Number, Ohlcv, Open, Peek, PeekNext, PriceSource, Quote, Replace,
+    Reset, ResetWith, ResetWithItems, Timestamp, Trade, TryCalculate, TryNext, UpdateLast, Volume,
+    WarmUp, Warmup,
+};
@@ -0,0 +1,171 @@
+use crate::errors::*;
+use crate::preprocess::{ResampledBar, Resampler};
+use crate::sessions::Session;
+use crate::{DataItem, Reset, Trade};
+
+/// Aggregates trades into time-based bars.
+///
+/// Each bar opens at the price of the first trade of its period, closes at the price of
+/// the last one, spans their prices and sums their sizes as its volume; its `count` is the
+/// number of trades. Periods are aligned like those of a
+/// [Resampler](struct.Resampler.html), to multiples of the interval since the Unix epoch or
+/// since the opening of a [Session](../sessions/struct.Session.html), and periods without
+/// trades produce no bar; a [GapFiller](struct.GapFiller.html) can fill them in.
+///
+/// Trades are expected in order; trades of an earlier period than the current one are
+/// ignored. A bar is emitted once the first trade of the next period arrives, or with
+/// [flush](#method.flush).
+///
+/// # Parameters
+///
+/// * _interval_ - length of the bars in milliseconds (greater than 0)
+///
+/// # Example
+///
+/// ```
+/// use ta::preprocess::BarBuilder;
+/// use ta::sessions::{MINUTE, SECOND};
+/// use ta::{Close, High, Volume};
+///
+/// // timestamp, price and size
+/// let trades = [(0, 10.0, 1.0), (15 * SECOND, 10.5, 2.0), (40 * SECOND, 10.2, 0.5)];
+///
+/// let mut builder = BarBuilder::new(MINUTE).unwrap();
+/// for trade in &trades {
+///     assert!(builder.next(trade).is_none());
+/// }
+///
+/// let bar = builder.next(&(MINUTE + SECOND, 10.3, 1.0)).unwrap();
+/// assert_eq!(bar.timestamp, 0);
+/// assert_eq!((bar.high(), bar.close(), bar.volume()), (10.5, 10.2, 3.5));
+/// assert_eq!(bar.count, 3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct BarBuilder {
+    resampler: Resampler,
+}
+
+impl BarBuilder {
+    pub fn new(interval: i64) -> Result<Self> {
+        Ok(Self {
+            resampler: Resampler::new(interval)?,
+        })
+    }
+
+    /// Aligns the bars to the opening of `session` and ignores trades outside of it.
+    pub fn session(self, session: Session) -> Self {
+        Self {
+            resampler: self.resampler.session(session),
+        }
+    }
+
+    pub fn interval(&self) -> i64 {
+        self.resampler.interval()
+    }
+
+    /// Consumes a trade and returns the previous bar once `trade` starts a new period.
+    pub fn next<T: Trade>(&mut self, trade: &T) -> Option<ResampledBar> {
+        let price = trade.price();
+        let tick = DataItem::new(price, price, price, price, trade.size());
+        self.resampler.next(trade.timestamp(), &tick)
+    }
+
+    /// Bar of the current period, still forming.
+    pub fn current(&self) -> Option<&ResampledBar> {
+        self.resampler.current()
+    }
+
+    /// Returns the bar of the current period, e.g. at the end of the data, and starts over.
+    pub fn flush(&mut self) -> Option<ResampledBar> {
+        self.resampler.flush()
+    }
+}
+
+impl Reset for BarBuilder {
+    fn reset(&mut self) {
+        self.resampler.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sessions::{DAY, HOUR, MINUTE, SECOND};
+    use crate::{Close, High, Low, Open, Timestamp, Volume};
+
+    struct Fill {
+        time: i64,
+        price: f64,
+        quantity: f64,
+    }
+
+    impl Timestamp for Fill {
+        fn timestamp(&self) -> i64 {
+            self.time
+        }
+    }
+
+    impl Trade for Fill {
+        fn price(&self) -> f64 {
+            self.price
+        }
+
+        fn size(&self) -> f64 {
+            self.quantity
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(BarBuilder::new(0).is_err());
+        assert_eq!(BarBuilder::new(MINUTE).unwrap().interval(), MINUTE);
+    }
+
+    #[test]
+    fn test_aggregate() {
+        let mut builder = BarBuilder::new(MINUTE).unwrap();
+        let fill = |time, price, quantity| Fill {
+            time,
+            price,
+            quantity,
+        };
+        assert!(builder.next(&fill(SECOND, 10.0, 1.0)).is_none());
+        assert!(builder.next(&fill(2 * SECOND, 9.5, 1.0)).is_none());
+        assert!(builder.next(&fill(30 * SECOND, 11.0, 2.0)).is_none());
+        assert!(builder.next(&fill(59 * SECOND, 10.5, 0.5)).is_none());
+
+        // the next minute without trades is skipped
+        let bar = builder.next(&fill(2 * MINUTE, 10.0, 1.0)).unwrap();
+        assert_eq!(bar.timestamp, 0);
+        assert_eq!((bar.open(), bar.high()), (10.0, 11.0));
+        assert_eq!((bar.low(), bar.close()), (9.5, 10.5));
+        assert_eq!(bar.volume(), 4.5);
+        assert_eq!(bar.count, 4);
+
+        // a late trade of the first minute
+        assert!(builder.next(&fill(30 * SECOND, 20.0, 1.0)).is_none());
+        let bar = builder.flush().unwrap();
+        assert_eq!(bar.timestamp, 2 * MINUTE);
+        assert_eq!((bar.high(), bar.count), (10.0, 1));
+        assert!(builder.current().is_none());
+    }
+
+    #[test]
+    fn test_session() {
+        // 2020-01-06 09:30 New York (14:30 UTC), a Monday
+        let open = 18_267 * DAY + 14 * HOUR + 30 * MINUTE;
+        let mut builder = BarBuilder::new(HOUR).unwrap().session(Session::us_rth());
+        assert!(builder.next(&(open - SECOND, 50.0, 1.0)).is_none());
+        assert!(builder.current().is_none());
+        builder.next(&(open + MINUTE, 10.0, 1.0));
+        assert_eq!(builder.current().unwrap().timestamp, open);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut builder = BarBuilder::new(MINUTE).unwrap();
+        builder.next(&(0, 10.0, 1.0));
+        builder.reset();
+        assert!(builder.flush().is_none());
+    }
+}
@@ -0,0 +1,238 @@
+use crate::errors::*;
+use crate::preprocess::ResampledBar;
+use crate::{DataItem, High, Low, Open, Reset, Trade, Volume};
+
+/// Threshold closing the bars of a [BarSampler](struct.BarSampler.html).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Sampling {
+    /// Volume bars, closed once the summed size of their trades reaches the threshold.
+    Volume(f64),
+    /// Dollar bars, closed once the summed notional value (price times size) of their trades
+    /// reaches the threshold.
+    Dollar(f64),
+    /// Tick imbalance bars, closed once the absolute sum of the signed sizes of their trades
+    /// reaches the threshold. Trades are signed with the tick rule: buys above the price of
+    /// the previous trade, sells below it, and the side of the previous trade at the same
+    /// price.
+    Imbalance(f64),
+}
+
+impl Sampling {
+    fn threshold(self) -> f64 {
+        match self {
+            Sampling::Volume(threshold)
+            | Sampling::Dollar(threshold)
+            | Sampling::Imbalance(threshold) => threshold,
+        }
+    }
+}
+
+/// Aggregates trades into information-driven bars, sampled by traded volume, notional value
+/// or order flow imbalance instead of time.
+///
+/// Bars sample the market more often when it is active, which gives their returns better
+/// statistical properties than those of time bars. Each bar opens at the price of its first
+/// trade, closes at the price of the trade reaching the threshold, spans their prices and
+/// sums their sizes as its volume; its `timestamp` is the one of its first trade and its
+/// `count` the number of trades. Trades are not split, so the last trade of a bar may carry
+/// it past the threshold.
+///
+/// Bars are [ResampledBar](struct.ResampledBar.html)s, which implement
+/// [Ohlcv](../trait.Ohlcv.html), so they can be fed to any indicator.
+///
+/// # Parameters
+///
+/// * _sampling_ - kind of the bars and their threshold (greater than 0)
+///
+/// # Example
+///
+/// ```
+/// use ta::preprocess::{BarSampler, Sampling};
+/// use ta::sessions::SECOND;
+/// use ta::{Close, High, Volume};
+///
+/// let mut sampler = BarSampler::new(Sampling::Volume(5.0)).unwrap();
+/// assert!(sampler.next(&(0, 10.0, 2.0)).is_none());
+/// assert!(sampler.next(&(SECOND, 10.5, 2.0)).is_none());
+///
+/// let bar = sampler.next(&(2 * SECOND, 10.2, 1.5)).unwrap();
+/// assert_eq!(bar.timestamp, 0);
+/// assert_eq!((bar.high(), bar.close(), bar.volume()), (10.5, 10.2, 5.5));
+/// assert_eq!(bar.count, 3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct BarSampler {
+    sampling: Sampling,
+    current: Option<ResampledBar>,
+    // volume, value or signed flow of the current bar
+    accumulated: f64,
+    // price and tick rule side of the last trade
+    last: Option<(f64, f64)>,
+}
+
+impl BarSampler {
+    pub fn new(sampling: Sampling) -> Result<Self> {
+        let threshold = sampling.threshold();
+        if threshold <= 0.0 || !threshold.is_finite() {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        Ok(Self {
+            sampling,
+            current: None,
+            accumulated: 0.0,
+            last: None,
+        })
+    }
+
+    pub fn sampling(&self) -> Sampling {
+        self.sampling
+    }
+
+    /// Consumes a trade and returns the bar it completes.
+    pub fn next<T: Trade>(&mut self, trade: &T) -> Option<ResampledBar> {
+        let (price, size) = (trade.price(), trade.size());
+        let side = match self.last {
+            Some((last, _)) if price > last => 1.0,
+            Some((last, _)) if price < last => -1.0,
+            Some((_, side)) => side,
+            None => 1.0,
+        };
+        self.last = Some((price, side));
+
+        self.accumulated += match self.sampling {
+            Sampling::Volume(_) => size,
+            Sampling::Dollar(_) => price * size,
+            Sampling::Imbalance(_) => side * size,
+        };
+
+        match &mut self.current {
+            Some(current) => {
+                let bar = &current.bar;
+                current.bar = DataItem::new(
+                    bar.open(),
+                    bar.high().max(price),
+                    bar.low().min(price),
+                    price,
+                    bar.volume() + size,
+                );
+                current.count += 1;
+            }
+            None => {
+                self.current = Some(ResampledBar {
+                    timestamp: trade.timestamp(),
+                    bar: DataItem::new(price, price, price, price, size),
+                    count: 1,
+                });
+            }
+        }
+
+        if self.accumulated.abs() >= self.sampling.threshold() {
+            self.flush()
+        } else {
+            None
+        }
+    }
+
+    /// Bar still forming.
+    pub fn current(&self) -> Option<&ResampledBar> {
+        self.current.as_ref()
+    }
+
+    /// Returns the bar still forming, e.g. at the end of the data, and starts a new one.
+    pub fn flush(&mut self) -> Option<ResampledBar> {
+        self.accumulated = 0.0;
+        self.current.take()
+    }
+}
+
+impl Reset for BarSampler {
+    fn reset(&mut self) {
+        self.current = None;
+        self.accumulated = 0.0;
+        self.last = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::SimpleMovingAverage;
+    use crate::{Close, Next};
+
+    #[test]
+    fn test_new() {
+        assert!(BarSampler::new(Sampling::Volume(0.0)).is_err());
+        assert!(BarSampler::new(Sampling::Dollar(f64::INFINITY)).is_err());
+        assert!(BarSampler::new(Sampling::Imbalance(-1.0)).is_err());
+        let sampler = BarSampler::new(Sampling::Dollar(1e6)).unwrap();
+        assert_eq!(sampler.sampling(), Sampling::Dollar(1e6));
+    }
+
+    #[test]
+    fn test_volume() {
+        let mut sampler = BarSampler::new(Sampling::Volume(10.0)).unwrap();
+        assert!(sampler.next(&(1, 5.0, 4.0)).is_none());
+        assert!(sampler.next(&(2, 4.0, 4.0)).is_none());
+        let bar = sampler.next(&(3, 6.0, 3.0)).unwrap();
+        assert_eq!(bar.timestamp, 1);
+        assert_eq!((bar.open(), bar.high(), bar.low()), (5.0, 6.0, 4.0));
+        assert_eq!((bar.close(), bar.volume(), bar.count), (6.0, 11.0, 3));
+
+        // a large trade makes a bar on its own
+        let bar = sampler.next(&(4, 7.0, 25.0)).unwrap();
+        assert_eq!((bar.timestamp, bar.volume(), bar.count), (4, 25.0, 1));
+        assert!(sampler.current().is_none());
+    }
+
+    #[test]
+    fn test_dollar() {
+        let mut sampler = BarSampler::new(Sampling::Dollar(100.0)).unwrap();
+        assert!(sampler.next(&(1, 10.0, 5.0)).is_none());
+        let bar = sampler.next(&(2, 20.0, 3.0)).unwrap();
+        assert_eq!((bar.close(), bar.volume()), (20.0, 8.0));
+        assert!(sampler.next(&(3, 20.0, 4.0)).is_none());
+        assert_eq!(sampler.current().unwrap().volume(), 4.0);
+    }
+
+    #[test]
+    fn test_imbalance() {
+        let mut sampler = BarSampler::new(Sampling::Imbalance(3.0)).unwrap();
+        // +2, +0.5 at the same price, -2 on a downtick
+        assert!(sampler.next(&(1, 10.0, 2.0)).is_none());
+        assert!(sampler.next(&(2, 10.0, 0.5)).is_none());
+        assert!(sampler.next(&(3, 9.0, 2.0)).is_none());
+        assert!(sampler.next(&(4, 9.0, 2.0)).is_none());
+        // -2 more at the same price, sell flow of 3.5
+        let bar = sampler.next(&(5, 9.0, 2.0)).unwrap();
+        assert_eq!((bar.count, bar.volume()), (5, 8.5));
+
+        // the side carries over to the next bar
+        assert!(sampler.next(&(5, 9.0, 2.0)).is_none());
+        assert!(sampler.next(&(6, 8.0, 1.0)).is_some());
+    }
+
+    #[test]
+    fn test_indicator() {
+        let mut sampler = BarSampler::new(Sampling::Volume(2.0)).unwrap();
+        let mut sma = SimpleMovingAverage::new(2).unwrap();
+        let closes: Vec<f64> = [(1, 10.0, 2.0), (2, 12.0, 1.0), (3, 14.0, 1.0)]
+            .iter()
+            .filter_map(|trade| sampler.next(trade))
+            .map(|bar| sma.next(&bar))
+            .collect();
+        assert_eq!(closes, vec![10.0, 12.0]);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut sampler = BarSampler::new(Sampling::Imbalance(3.0)).unwrap();
+        sampler.next(&(1, 10.0, 2.0));
+        sampler.next(&(2, 9.0, 0.5));
+        sampler.reset();
+        assert!(sampler.flush().is_none());
+        // the first trade counts as a buy again
+        assert!(sampler.next(&(3, 9.0, 2.5)).is_none());
+        assert!(sampler.next(&(4, 9.0, 0.5)).is_some());
+    }
+}
@@ -0,0 +1,315 @@
+use crate::errors::*;
+use crate::sessions::Session;
+use crate::{DataItem, Ohlcv, Reset};
+use alloc::vec::Vec;
+
+/// Strategy for bars missing from a fixed-interval series.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GapPolicy {
+    /// Insert flat bars at the previous close with zero volume.
+    PreviousClose,
+    /// Insert flat bars with closes interpolated linearly between the previous close and
+    /// the close of the bar after the gap, with zero volume.
+    Linear,
+    /// Do not insert anything, only flag the bar after the gap.
+    Skip,
+    /// Fail with `MissingBars` on the bar after the gap.
+    Error,
+}
+
+/// Bar emitted by [GapFiller](struct.GapFiller.html).
+#[derive(Debug, Clone)]
+pub struct GapBar {
+    pub timestamp: i64,
+    pub bar: DataItem,
+    /// `true` if the bar was synthesized to fill a gap.
+    pub filled: bool,
+    /// Number of bars missing right before this bar. Only set on input bars.
+    pub missing_before: u32,
+}
+
+/// Fills gaps in a series of bars sampled at a fixed interval.
+///
+/// Indicators count bars, not time, so a series with silently missing bars makes e.g.
+/// `RateOfChange` compare prices further apart than its length says. The filler detects
+/// gaps from the timestamps and handles them according to a [GapPolicy](enum.GapPolicy.html).
+///
+/// Without a [Session](../sessions/struct.Session.html) every interval between two bars is
+/// expected to have a bar, so e.g. the night and weekend between the Friday close and the
+/// Monday open of a stock count as missing bars. With [session](#method.session) only bars
+/// missing during trading hours count: since the previous bar within the same session, or
+/// since the opening of a new session. Bars outside of the session are passed through.
+///
+/// The filling policies insert at most [max_fill](#method.max_fill) bars, 10 000 by default.
+/// A longer gap, e.g. a bogus timestamp far in the future, fails with `MissingBars` instead
+/// of allocating a bar for every missing interval.
+///
+/// # Parameters
+///
+/// * _interval_ - expected distance between bars in milliseconds (greater than 0)
+/// * _policy_ - gap handling strategy
+///
+/// # Example
+///
+/// ```
+/// use ta::preprocess::{GapFiller, GapPolicy};
+/// use ta::{Close, DataItem};
+///
+/// let bar = |close| {
+///     DataItem::builder()
+///         .open(close)
+///         .high(close)
+///         .low(close)
+///         .close(close)
+///         .volume(100.0)
+///         .build()
+///         .unwrap()
+/// };
+///
+/// let mut filler = GapFiller::new(60_000, GapPolicy::Linear).unwrap();
+/// filler.next(0, &bar(10.0)).unwrap();
+///
+/// let output = filler.next(180_000, &bar(13.0)).unwrap();
+/// let closes: Vec<f64> = output.iter().map(|b| b.bar.close()).collect();
+/// assert_eq!(closes, vec![11.0, 12.0, 13.0]);
+/// assert!(output[0].filled);
+/// assert_eq!(output[2].missing_before, 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct GapFiller {
+    interval: i64,
+    policy: GapPolicy,
+    session: Option<Session>,
+    max_fill: u32,
+    last: Option<(i64, f64)>,
+}
+
+const DEFAULT_MAX_FILL: u32 = 10_000;
+
+impl GapFiller {
+    pub fn new(interval: i64, policy: GapPolicy) -> Result<Self> {
+        if interval <= 0 {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        Ok(Self {
+            interval,
+            policy,
+            session: None,
+            max_fill: DEFAULT_MAX_FILL,
+            last: None,
+        })
+    }
+
+    /// Only counts bars missing during the trading hours of `session`.
+    pub fn session(mut self, session: Session) -> Self {
+        self.session = Some(session);
+        self
+    }
+
+    /// Maximum number of bars inserted into a single gap by the filling policies.
+    pub fn max_fill(mut self, max_fill: u32) -> Self {
+        self.max_fill = max_fill;
+        self
+    }
+
+    /// Consumes a bar and returns the bars filling the gap before it, followed by the bar.
+    ///
+    /// With [GapPolicy::Error](enum.GapPolicy.html#variant.Error) a gap fails with
+    /// `MissingBars`, as does a gap longer than [max_fill](#method.max_fill) with the filling
+    /// policies; the bar is still taken as the latest one, so the series can go on.
+    pub fn next<T: Ohlcv>(&mut self, timestamp: i64, input: &T) -> Result<Vec<GapBar>> {
+        let mut output = Vec::new();
+        let mut missing_before = 0;
+
+        if let Some((from, last_close)) = self.gap_start(timestamp) {
+            let missing = timestamp.saturating_sub(from) / self.interval - 1;
+            if missing > 0 {
+                missing_before = missing.min(u32::MAX as i64) as u32;
+            }
+            let fills = matches!(self.policy, GapPolicy::PreviousClose | GapPolicy::Linear);
+            let too_long = fills && missing_before > self.max_fill;
+            if missing_before > 0 && (self.policy == GapPolicy::Error || too_long) {
+                self.last = Some((timestamp, input.close()));
+                return Err(Error::from_kind(ErrorKind::MissingBars(missing_before)));
+            }
+
+            let step = (input.close() - last_close) / (missing + 1) as f64;
+            for i in 1..=missing {
+                let price = match self.policy {
+                    GapPolicy::Skip | GapPolicy::Error => break,
+                    GapPolicy::PreviousClose => last_close,
+                    GapPolicy::Linear => last_close + step * i as f64,
+                };
+                output.push(GapBar {
+                    timestamp: from + i * self.interval,
+                    bar: DataItem::new(price, price, price, price, 0.0),
+                    filled: true,
+                    missing_before: 0,
+                });
+            }
+        }
+
+        self.last = Some((timestamp, input.close()));
+        output.push(GapBar {
+            timestamp,
+            bar: DataItem::new(
+                input.open(),
+                input.high(),
+                input.low(),
+                input.close(),
+                input.volume(),
+            ),
+            filled: false,
+            missing_before,
+        });
+        Ok(output)
+    }
+
+    // Timestamp after which bars are expected before `timestamp`, with the last close.
+    fn gap_start(&self, timestamp: i64) -> Option<(i64, f64)> {
+        let (last_timestamp, last_close) = self.last?;
+        let session = match &self.session {
+            Some(session) => session,
+            None => return Some((last_timestamp, last_close)),
+        };
+        let start = session.session_start(timestamp)?;
+        if session.session_start(last_timestamp) == Some(start) {
+            Some((last_timestamp, last_close))
+        } else {
+            // the first bar of a session is expected at its opening
+            Some((start - self.interval, last_close))
+        }
+    }
+}
+
+impl Reset for GapFiller {
+    fn reset(&mut self) {
+        self.last = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sessions::{DAY, HOUR, MINUTE};
+    use crate::test_helper::*;
+    use crate::{Close, Volume};
+
+    fn bar(close: f64) -> Bar {
+        Bar::new().open(close).high(close).low(close).close(close)
+    }
+
+    fn closes(output: &[GapBar]) -> Vec<f64> {
+        output.iter().map(|b| b.bar.close()).collect()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(GapFiller::new(0, GapPolicy::Skip).is_err());
+        assert!(GapFiller::new(1, GapPolicy::Skip).is_ok());
+    }
+
+    #[test]
+    fn test_no_gap() {
+        let mut filler = GapFiller::new(10, GapPolicy::Linear).unwrap();
+        assert_eq!(filler.next(0, &bar(1.0)).unwrap().len(), 1);
+        let output = filler.next(10, &bar(2.0)).unwrap();
+        assert_eq!(closes(&output), vec![2.0]);
+        assert_eq!(output[0].missing_before, 0);
+    }
+
+    #[test]
+    fn test_previous_close() {
+        let mut filler = GapFiller::new(10, GapPolicy::PreviousClose).unwrap();
+        filler.next(0, &bar(1.0)).unwrap();
+        let output = filler.next(30, &bar(4.0)).unwrap();
+        assert_eq!(closes(&output), vec![1.0, 1.0, 4.0]);
+        assert_eq!(output[0].timestamp, 10);
+        assert_eq!(output[1].timestamp, 20);
+        assert_eq!(output[1].bar.volume(), 0.0);
+        assert!(!output[2].filled);
+    }
+
+    #[test]
+    fn test_skip() {
+        let mut filler = GapFiller::new(10, GapPolicy::Skip).unwrap();
+        filler.next(0, &bar(1.0)).unwrap();
+        let output = filler.next(40, &bar(4.0)).unwrap();
+        assert_eq!(closes(&output), vec![4.0]);
+        assert_eq!(output[0].missing_before, 3);
+    }
+
+    #[test]
+    fn test_error() {
+        let mut filler = GapFiller::new(10, GapPolicy::Error).unwrap();
+        filler.next(0, &bar(1.0)).unwrap();
+        filler.next(10, &bar(2.0)).unwrap();
+        let err = filler.next(40, &bar(4.0)).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::MissingBars(2)));
+        // the series goes on after the gap
+        assert_eq!(closes(&filler.next(50, &bar(5.0)).unwrap()), vec![5.0]);
+    }
+
+    #[test]
+    fn test_max_fill() {
+        let mut filler = GapFiller::new(10, GapPolicy::PreviousClose)
+            .unwrap()
+            .max_fill(2);
+        filler.next(0, &bar(1.0)).unwrap();
+        assert_eq!(filler.next(30, &bar(2.0)).unwrap().len(), 3);
+        let err = filler.next(70, &bar(3.0)).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::MissingBars(3)));
+        assert_eq!(closes(&filler.next(80, &bar(4.0)).unwrap()), vec![4.0]);
+
+        // the default bounds a bogus timestamp
+        let mut filler = GapFiller::new(1, GapPolicy::Linear).unwrap();
+        filler.next(i64::MIN, &bar(1.0)).unwrap();
+        let err = filler.next(i64::MAX, &bar(2.0)).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::MissingBars(u32::MAX)));
+
+        // skipping allocates nothing
+        let mut filler = GapFiller::new(10, GapPolicy::Skip).unwrap().max_fill(2);
+        filler.next(0, &bar(1.0)).unwrap();
+        assert_eq!(filler.next(70, &bar(2.0)).unwrap()[0].missing_before, 6);
+    }
+
+    #[test]
+    fn test_session() {
+        // 2020-01-10 09:30 New York (14:30 UTC), a Friday
+        let friday = 18_271 * DAY + 14 * HOUR + 30 * MINUTE;
+        let monday = friday + 3 * DAY;
+        let session = Session::us_rth();
+
+        let mut filler = GapFiller::new(HOUR, GapPolicy::Error).unwrap();
+        filler.next(friday + 6 * HOUR, &bar(1.0)).unwrap();
+        assert!(filler.next(monday, &bar(2.0)).is_err());
+
+        let mut filler = GapFiller::new(HOUR, GapPolicy::Error)
+            .unwrap()
+            .session(session.clone());
+        filler.next(friday + 6 * HOUR, &bar(1.0)).unwrap();
+        // the weekend is no gap
+        let output = filler.next(monday, &bar(2.0)).unwrap();
+        assert_eq!(output[0].missing_before, 0);
+        // after-hours bars are passed through
+        assert_eq!(filler.next(monday - HOUR, &bar(3.0)).unwrap().len(), 1);
+
+        // bars missing since the opening
+        let mut filler = GapFiller::new(HOUR, GapPolicy::PreviousClose)
+            .unwrap()
+            .session(session);
+        filler.next(friday + 6 * HOUR, &bar(1.0)).unwrap();
+        let output = filler.next(monday + 2 * HOUR, &bar(2.0)).unwrap();
+        assert_eq!(closes(&output), vec![1.0, 1.0, 2.0]);
+        assert_eq!(output[0].timestamp, monday);
+        assert_eq!(output[2].missing_before, 2);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut filler = GapFiller::new(10, GapPolicy::Linear).unwrap();
+        filler.next(0, &bar(1.0)).unwrap();
+        filler.reset();
+        assert_eq!(filler.next(40, &bar(4.0)).unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,21 @@
+//! Input preprocessing adapters.
+//!
+//! Adapters in this module sit between a data feed and the indicators and repair the
+//! input stream (missing bars, bad ticks), resample it to a longer timeframe or build bars
+//! from trades, sampled by time or by traded volume, value or imbalance, before it reaches
+//! indicator state.
+
+mod bar_builder;
+pub use self::bar_builder::BarBuilder;
+
+mod bar_sampler;
+pub use self::bar_sampler::{BarSampler, Sampling};
+
+mod gaps;
+pub use self::gaps::{GapBar, GapFiller, GapPolicy};
+
+mod outliers;
+pub use self::outliers::{OutlierAction, OutlierDiagnostics, OutlierFilter, OutlierMeasure};
+
+mod resample;
+pub use self::resample::{ResampledBar, Resampler};
@@ -0,0 +1,275 @@
+use crate::errors::*;
+use crate::indicators::{AverageTrueRange, StandardDeviation};
+use crate::{Calculate, Reset};
+
+/// Scale against which price moves are judged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutlierMeasure {
+    /// Standard deviation of price changes over the last _n_ accepted ticks.
+    StandardDeviation,
+    /// Average true range of accepted prices with length _n_.
+    AverageTrueRange,
+}
+
+/// What to do with a tick classified as an outlier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutlierAction {
+    /// Replace the tick with the last value moved by the largest allowed deviation.
+    Clamp,
+    /// Discard the tick.
+    Drop,
+}
+
+/// Counters collected by [OutlierFilter](struct.OutlierFilter.html).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OutlierDiagnostics {
+    /// Number of ticks seen.
+    pub seen: u64,
+    /// Number of ticks replaced by a clamped value.
+    pub clamped: u64,
+    /// Number of ticks discarded.
+    pub dropped: u64,
+    /// Number of outliers accepted as a new level after _n_ consecutive drops.
+    pub reanchored: u64,
+}
+
+#[derive(Debug, Clone)]
+enum Scale {
+    Sd(StandardDeviation),
+    Atr(AverageTrueRange),
+}
+
+/// Bad tick filter.
+///
+/// A tick is an outlier when it deviates from the last accepted value by more than _k_
+/// times the current scale ([OutlierMeasure](enum.OutlierMeasure.html)). Outliers are
+/// clamped or dropped before they reach indicator state, and only accepted values update
+/// the scale. No filtering happens until _n_ price changes have been observed.
+///
+/// Dropped ticks don't move the last accepted value, so after a genuine level shift, e.g. a
+/// gap open, every tick would be dropped. The _n_-th consecutive dropped tick is therefore
+/// accepted as the new level instead, widening the scale with its deviation.
+///
+/// # Parameters
+///
+/// * _n_ - length of the scale estimate (integer greater than 0)
+/// * _k_ - allowed deviation in units of the scale (greater than 0)
+///
+/// # Example
+///
+/// ```
+/// use ta::preprocess::{OutlierAction, OutlierFilter, OutlierMeasure};
+///
+/// let mut filter =
+///     OutlierFilter::new(3, 4.0, OutlierMeasure::AverageTrueRange, OutlierAction::Drop).unwrap();
+/// for &price in &[10.0, 10.1, 10.0, 10.1] {
+///     assert_eq!(filter.filter(price), Some(price));
+/// }
+/// assert_eq!(filter.filter(101.0), None);
+/// assert_eq!(filter.filter(10.2), Some(10.2));
+/// assert_eq!(filter.diagnostics().dropped, 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct OutlierFilter {
+    n: u32,
+    k: f64,
+    action: OutlierAction,
+    scale: Scale,
+    scale_value: f64,
+    changes: u32,
+    // consecutive dropped ticks
+    rejected: u32,
+    last: Option<f64>,
+    diagnostics: OutlierDiagnostics,
+}
+
+impl OutlierFilter {
+    pub fn new(n: u32, k: f64, measure: OutlierMeasure, action: OutlierAction) -> Result<Self> {
+        if !(k > 0.0 && k.is_finite()) {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        let scale = match measure {
+            OutlierMeasure::StandardDeviation => Scale::Sd(StandardDeviation::new(n)?),
+            OutlierMeasure::AverageTrueRange => Scale::Atr(AverageTrueRange::new(n)?),
+        };
+        Ok(Self {
+            n,
+            k,
+            action,
+            scale,
+            scale_value: 0.0,
+            changes: 0,
+            rejected: 0,
+            last: None,
+            diagnostics: OutlierDiagnostics::default(),
+        })
+    }
+
+    /// Filters a tick. Returns the value to pass downstream or `None` if it was dropped.
+    pub fn filter(&mut self, price: f64) -> Option<f64> {
+        self.diagnostics.seen += 1;
+
+        let last = match self.last {
+            Some(last) => last,
+            None => {
+                self.accept(price);
+                return Some(price);
+            }
+        };
+
+        let limit = self.k * self.scale_value;
+        let deviation = price - last;
+        if self.changes >= self.n && limit > 0.0 && deviation.abs() > limit {
+            match self.action {
+                OutlierAction::Drop if self.rejected + 1 < self.n => {
+                    self.rejected += 1;
+                    self.diagnostics.dropped += 1;
+                    return None;
+                }
+                OutlierAction::Drop => {
+                    // the price has stayed away for n ticks, it's a new level
+                    self.diagnostics.reanchored += 1;
+                }
+                OutlierAction::Clamp => {
+                    self.diagnostics.clamped += 1;
+                    let clamped = last + limit.copysign(deviation);
+                    self.accept(clamped);
+                    return Some(clamped);
+                }
+            }
+        }
+
+        self.accept(price);
+        Some(price)
+    }
+
+    pub fn diagnostics(&self) -> OutlierDiagnostics {
+        self.diagnostics
+    }
+
+    fn accept(&mut self, price: f64) {
+        self.scale_value = match (&mut self.scale, self.last) {
+            (Scale::Sd(_), None) => 0.0,
+            (Scale::Sd(sd), Some(last)) => sd.calc(price - last),
+            (Scale::Atr(atr), _) => atr.calc(price),
+        };
+        if self.last.is_some() {
+            self.changes += 1;
+        }
+        self.rejected = 0;
+        self.last = Some(price);
+    }
+}
+
+impl Reset for OutlierFilter {
+    fn reset(&mut self) {
+        match self.scale {
+            Scale::Sd(ref mut sd) => sd.reset(),
+            Scale::Atr(ref mut atr) => atr.reset(),
+        }
+        self.scale_value = 0.0;
+        self.changes = 0;
+        self.rejected = 0;
+        self.last = None;
+        self.diagnostics = OutlierDiagnostics::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_new() {
+        let measure = OutlierMeasure::StandardDeviation;
+        assert!(OutlierFilter::new(0, 3.0, measure, OutlierAction::Drop).is_err());
+        assert!(OutlierFilter::new(3, 0.0, measure, OutlierAction::Drop).is_err());
+        assert!(OutlierFilter::new(3, f64::NAN, measure, OutlierAction::Drop).is_err());
+        assert!(OutlierFilter::new(3, f64::INFINITY, measure, OutlierAction::Drop).is_err());
+        assert!(OutlierFilter::new(3, 3.0, measure, OutlierAction::Drop).is_ok());
+    }
+
+    #[test]
+    fn test_clamp() {
+        let mut filter = OutlierFilter::new(
+            4,
+            2.0,
+            OutlierMeasure::StandardDeviation,
+            OutlierAction::Clamp,
+        )
+        .unwrap();
+        // changes alternate between +1 and -1, standard deviation is 1
+        for &price in &[10.0, 11.0, 10.0, 11.0, 10.0] {
+            assert_eq!(filter.filter(price), Some(price));
+        }
+        assert_eq!(filter.filter(50.0), Some(12.0));
+        // the clamped change widens the scale to 1.299
+        assert_eq!(round(filter.filter(2.0).unwrap()), 9.402);
+
+        let diagnostics = filter.diagnostics();
+        assert_eq!(diagnostics.seen, 7);
+        assert_eq!(diagnostics.clamped, 2);
+        assert_eq!(diagnostics.dropped, 0);
+    }
+
+    #[test]
+    fn test_warm_up() {
+        let mut filter = OutlierFilter::new(
+            3,
+            1.0,
+            OutlierMeasure::AverageTrueRange,
+            OutlierAction::Drop,
+        )
+        .unwrap();
+        assert_eq!(filter.filter(10.0), Some(10.0));
+        assert_eq!(filter.filter(10.5), Some(10.5));
+        assert_eq!(filter.filter(100.0), Some(100.0));
+    }
+
+    #[test]
+    fn test_level_shift() {
+        let mut filter = OutlierFilter::new(
+            3,
+            2.0,
+            OutlierMeasure::AverageTrueRange,
+            OutlierAction::Drop,
+        )
+        .unwrap();
+        for &price in &[10.0, 10.1, 10.0, 10.1] {
+            assert_eq!(filter.filter(price), Some(price));
+        }
+        // a gap open: the first ticks at the new level are dropped, the third one moves it
+        assert_eq!(filter.filter(20.0), None);
+        assert_eq!(filter.filter(20.1), None);
+        assert_eq!(filter.filter(20.0), Some(20.0));
+        assert_eq!(filter.filter(20.1), Some(20.1));
+        // a lone bad tick is still dropped
+        assert_eq!(filter.filter(20.1), Some(20.1));
+        assert_eq!(filter.filter(100.0), None);
+        assert_eq!(filter.filter(20.0), Some(20.0));
+
+        let diagnostics = filter.diagnostics();
+        assert_eq!(diagnostics.dropped, 3);
+        assert_eq!(diagnostics.reanchored, 1);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut filter = OutlierFilter::new(
+            2,
+            2.0,
+            OutlierMeasure::AverageTrueRange,
+            OutlierAction::Drop,
+        )
+        .unwrap();
+        for &price in &[10.0, 10.1, 10.0, 10.1] {
+            filter.filter(price);
+        }
+        assert_eq!(filter.filter(20.0), None);
+
+        filter.reset();
+        assert_eq!(filter.diagnostics(), OutlierDiagnostics::default());
+        assert_eq!(filter.filter(20.0), Some(20.0));
+    }
+}
@@ -0,0 +1,269 @@
+use crate::errors::*;
+use crate::sessions::Session;
+use crate::{Close, DataItem, High, Low, Ohlcv, Open, Reset, Timestamp, Volume};
+
+/// Bar emitted by [Resampler](struct.Resampler.html), [BarBuilder](struct.BarBuilder.html) and
+/// [BarSampler](struct.BarSampler.html).
+///
+/// Implements [Ohlcv](../trait.Ohlcv.html) and [Timestamp](../trait.Timestamp.html), so it
+/// can be fed to indicators as it is.
+#[derive(Debug, Clone)]
+pub struct ResampledBar {
+    /// Start of the period of the bar.
+    pub timestamp: i64,
+    pub bar: DataItem,
+    /// Number of input bars aggregated into the bar.
+    pub count: u32,
+}
+
+/// Aggregates bars into bars of a longer period, e.g. 1-minute bars into 5-minute, hourly
+/// or daily bars.
+///
+/// Each output bar opens at the open of its first input bar, closes at the close of its
+/// last one, spans their highs and lows and sums their volumes. Periods start at multiples
+/// of the interval since the Unix epoch, or, with a [Session](../sessions/struct.Session.html),
+/// at multiples of the interval since the opening of the session, so e.g. hourly bars of
+/// US equities start at 09:30 and an interval of a day yields one bar per session. Input
+/// bars outside of the session are ignored.
+///
+/// Input bars are stamped with the start of their period and expected in order; bars
+/// belonging to an earlier period than the current one are ignored. A bar is emitted once
+/// the first input bar of the next period arrives, or with [flush](#method.flush).
+///
+/// # Parameters
+///
+/// * _interval_ - length of the output periods in milliseconds (greater than 0)
+///
+/// # Example
+///
+/// ```
+/// use ta::preprocess::Resampler;
+/// use ta::sessions::MINUTE;
+/// use ta::{Close, DataItem, High};
+///
+/// let bar = |close| {
+///     DataItem::builder()
+///         .open(close)
+///         .high(close)
+///         .low(close)
+///         .close(close)
+///         .volume(100.0)
+///         .build()
+///         .unwrap()
+/// };
+///
+/// let mut resampler = Resampler::new(5 * MINUTE).unwrap();
+/// for (minute, close) in [10.0, 12.0, 11.0, 9.0, 10.0].iter().enumerate() {
+///     assert!(resampler.next(minute as i64 * MINUTE, &bar(*close)).is_none());
+/// }
+///
+/// let five_minutes = resampler.next(5 * MINUTE, &bar(13.0)).unwrap();
+/// assert_eq!(five_minutes.timestamp, 0);
+/// assert_eq!(five_minutes.bar.high(), 12.0);
+/// assert_eq!(five_minutes.bar.close(), 10.0);
+/// assert_eq!(five_minutes.count, 5);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Resampler {
+    interval: i64,
+    session: Option<Session>,
+    current: Option<ResampledBar>,
+}
+
+impl Resampler {
+    pub fn new(interval: i64) -> Result<Self> {
+        if interval <= 0 {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        Ok(Self {
+            interval,
+            session: None,
+            current: None,
+        })
+    }
+
+    /// Aligns the periods to the opening of `session` and ignores bars outside of it.
+    pub fn session(mut self, session: Session) -> Self {
+        self.session = Some(session);
+        self
+    }
+
+    pub fn interval(&self) -> i64 {
+        self.interval
+    }
+
+    /// Consumes a bar and returns the previous output bar once `input` starts a new period.
+    pub fn next<T: Ohlcv>(&mut self, timestamp: i64, input: &T) -> Option<ResampledBar> {
+        let start = self.period_start(timestamp)?;
+
+        if let Some(current) = &mut self.current {
+            if start == current.timestamp {
+                let bar = &current.bar;
+                current.bar = DataItem::new(
+                    bar.open(),
+                    bar.high().max(input.high()),
+                    bar.low().min(input.low()),
+                    input.close(),
+                    bar.volume() + input.volume(),
+                );
+                current.count += 1;
+                return None;
+            } else if start < current.timestamp {
+                return None;
+            }
+        }
+
+        let bar = DataItem::new(
+            input.open(),
+            input.high(),
+            input.low(),
+            input.close(),
+            input.volume(),
+        );
+        self.current.replace(ResampledBar {
+            timestamp: start,
+            bar,
+            count: 1,
+        })
+    }
+
+    /// Bar of the current period, still forming.
+    pub fn current(&self) -> Option<&ResampledBar> {
+        self.current.as_ref()
+    }
+
+    /// Returns the bar of the current period, e.g. at the end of the data, and starts over.
+    pub fn flush(&mut self) -> Option<ResampledBar> {
+        self.current.take()
+    }
+
+    fn period_start(&self, timestamp: i64) -> Option<i64> {
+        let origin = match &self.session {
+            Some(session) => session.session_start(timestamp)?,
+            None => 0,
+        };
+        Some(timestamp - (timestamp - origin).rem_euclid(self.interval))
+    }
+}
+
+impl Reset for Resampler {
+    fn reset(&mut self) {
+        self.current = None;
+    }
+}
+
+impl Timestamp for ResampledBar {
+    fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+}
+
+impl Open for ResampledBar {
+    fn open(&self) -> f64 {
+        self.bar.open()
+    }
+}
+
+impl High for ResampledBar {
+    fn high(&self) -> f64 {
+        self.bar.high()
+    }
+}
+
+impl Low for ResampledBar {
+    fn low(&self) -> f64 {
+        self.bar.low()
+    }
+}
+
+impl Close for ResampledBar {
+    fn close(&self) -> f64 {
+        self.bar.close()
+    }
+}
+
+impl Volume for ResampledBar {
+    fn volume(&self) -> f64 {
+        self.bar.volume()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sessions::{DAY, HOUR, MINUTE};
+    use crate::test_helper::*;
+
+    fn bar(low: f64, high: f64) -> Bar {
+        Bar::new()
+            .open(low)
+            .high(high)
+            .low(low)
+            .close(high)
+            .volume(10.0)
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(Resampler::new(0).is_err());
+        assert!(Resampler::new(MINUTE).is_ok());
+    }
+
+    #[test]
+    fn test_aggregate() {
+        let mut resampler = Resampler::new(3 * MINUTE).unwrap();
+        assert!(resampler.next(MINUTE, &bar(5.0, 6.0)).is_none());
+        assert!(resampler.next(2 * MINUTE, &bar(4.0, 9.0)).is_none());
+        // a late bar of an earlier period
+        assert!(resampler.next(-MINUTE, &bar(1.0, 2.0)).is_none());
+
+        let output = resampler.next(4 * MINUTE, &bar(7.0, 8.0)).unwrap();
+        assert_eq!(output.timestamp, 0);
+        assert_eq!(output.count, 2);
+        assert_eq!(output.open(), 5.0);
+        assert_eq!(output.high(), 9.0);
+        assert_eq!(output.low(), 4.0);
+        assert_eq!(output.close(), 9.0);
+        assert_eq!(output.volume(), 20.0);
+
+        // a gap skips the empty period
+        let output = resampler.next(10 * MINUTE, &bar(1.0, 2.0)).unwrap();
+        assert_eq!(output.timestamp(), 3 * MINUTE);
+        assert_eq!(resampler.current().unwrap().timestamp, 9 * MINUTE);
+
+        let output = resampler.flush().unwrap();
+        assert_eq!(output.close(), 2.0);
+        assert!(resampler.flush().is_none());
+    }
+
+    #[test]
+    fn test_session() {
+        // 2020-01-06 09:30 New York (14:30 UTC), a Monday
+        let open = 18_267 * DAY + 14 * HOUR + 30 * MINUTE;
+        let mut resampler = Resampler::new(HOUR).unwrap().session(Session::us_rth());
+
+        // pre-market is ignored
+        assert!(resampler.next(open - MINUTE, &bar(1.0, 100.0)).is_none());
+        assert!(resampler.current().is_none());
+        assert!(resampler.next(open, &bar(5.0, 6.0)).is_none());
+        assert!(resampler.next(open + 59 * MINUTE, &bar(6.0, 7.0)).is_none());
+        let output = resampler.next(open + HOUR, &bar(7.0, 8.0)).unwrap();
+        assert_eq!(output.timestamp, open);
+        assert_eq!(output.high(), 7.0);
+
+        let mut daily = Resampler::new(DAY).unwrap().session(Session::us_rth());
+        daily.next(open, &bar(5.0, 6.0));
+        daily.next(open + 6 * HOUR, &bar(4.0, 5.0));
+        let output = daily.next(open + DAY, &bar(7.0, 8.0)).unwrap();
+        assert_eq!(output.timestamp, open);
+        assert_eq!((output.low(), output.high()), (4.0, 6.0));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut resampler = Resampler::new(MINUTE).unwrap();
+        resampler.next(0, &bar(1.0, 2.0));
+        resampler.reset();
+        assert!(resampler.next(MINUTE, &bar(1.0, 2.0)).is_none());
+    }
+}
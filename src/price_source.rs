@@ -0,0 +1,215 @@
+use core::fmt;
+use core::marker::PhantomData;
+
+use crate::{Calculate, Close, Float, High, Low, Next, Open, Reset, Warmup};
+
+/// Price of a bar which is fed to a single-input indicator.
+///
+/// Indicators implement [Next](trait.Next.html) with the close price. Wrapping one with
+/// [IndicatorExt::with_source](trait.IndicatorExt.html#method.with_source) feeds it another
+/// price instead.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::SimpleMovingAverage;
+/// use ta::{DataItem, IndicatorExt, Next, PriceSource};
+///
+/// let bar = DataItem::builder()
+///     .open(1.0)
+///     .high(6.0)
+///     .low(0.0)
+///     .close(3.0)
+///     .volume(100.0)
+///     .build()
+///     .unwrap();
+/// assert_eq!(PriceSource::Hl2.price(&bar), 3.0);
+/// assert_eq!(PriceSource::Ohlc4.price(&bar), 2.5);
+///
+/// let mut sma = SimpleMovingAverage::new(3)
+///     .unwrap()
+///     .with_source(PriceSource::Hlc3);
+/// assert_eq!(sma.next(&bar), 3.0);
+/// assert_eq!(sma.to_string(), "SMA(3).with_source(HLC3)");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PriceSource {
+    Open,
+    High,
+    Low,
+    #[default]
+    Close,
+    /// Median price, (high + low) / 2.
+    Hl2,
+    /// Typical price, (high + low + close) / 3.
+    Hlc3,
+    /// Average price, (open + high + low + close) / 4.
+    Ohlc4,
+    /// Weighted close, (high + low + 2 * close) / 4.
+    Hlcc4,
+}
+
+impl PriceSource {
+    /// Returns the selected price of a bar.
+    pub fn price<F: Float, T: Open<F> + High<F> + Low<F> + Close<F>>(self, bar: &T) -> F {
+        match self {
+            PriceSource::Open => bar.open(),
+            PriceSource::High => bar.high(),
+            PriceSource::Low => bar.low(),
+            PriceSource::Close => bar.close(),
+            PriceSource::Hl2 => (bar.high() + bar.low()) / F::cast(2),
+            PriceSource::Hlc3 => (bar.high() + bar.low() + bar.close()) / F::cast(3),
+            PriceSource::Ohlc4 => (bar.open() + bar.high() + bar.low() + bar.close()) / F::cast(4),
+            PriceSource::Hlcc4 => (bar.high() + bar.low() + bar.close() + bar.close()) / F::cast(4),
+        }
+    }
+}
+
+impl fmt::Display for PriceSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            PriceSource::Open => "OPEN",
+            PriceSource::High => "HIGH",
+            PriceSource::Low => "LOW",
+            PriceSource::Close => "CLOSE",
+            PriceSource::Hl2 => "HL2",
+            PriceSource::Hlc3 => "HLC3",
+            PriceSource::Ohlc4 => "OHLC4",
+            PriceSource::Hlcc4 => "HLCC4",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Indicator fed with a configurable price of every bar, created by
+/// [IndicatorExt::with_source](trait.IndicatorExt.html#method.with_source).
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WithSource<I, F = f64> {
+    inner: I,
+    source: PriceSource,
+    // the price type the bars are read with
+    price: PhantomData<fn() -> F>,
+}
+
+impl<I, F> WithSource<I, F> {
+    pub(crate) fn new(inner: I, source: PriceSource) -> Self {
+        Self {
+            inner,
+            source,
+            price: PhantomData,
+        }
+    }
+
+    pub fn source(&self) -> PriceSource {
+        self.source
+    }
+
+    /// Returns the inner indicator.
+    pub fn into_inner(self) -> I {
+        self.inner
+    }
+}
+
+impl<F, I: Calculate<F>> Calculate<F> for WithSource<I, F> {
+    type Output = I::Output;
+
+    fn calc(&mut self, input: F) -> Self::Output {
+        self.inner.calc(input)
+    }
+}
+
+impl<F, T, I> Next<T> for WithSource<I, F>
+where
+    F: Float,
+    T: Open<F> + High<F> + Low<F> + Close<F>,
+    I: Calculate<F>,
+{
+    type Output = I::Output;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.inner.calc(self.source.price(input))
+    }
+}
+
+impl<I: Reset, F> Reset for WithSource<I, F> {
+    fn reset(&mut self) {
+        self.inner.reset();
+    }
+}
+
+impl<I: Warmup, F> Warmup for WithSource<I, F> {
+    fn warmup_period(&self) -> u32 {
+        self.inner.warmup_period()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.inner.is_ready()
+    }
+}
+
+impl<I: fmt::Display, F> fmt::Display for WithSource<I, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.with_source({})", self.inner, self.source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{RateOfChange, SimpleMovingAverage};
+    use crate::test_helper::*;
+    use crate::IndicatorExt;
+
+    #[test]
+    fn test_price() {
+        let bar = Bar::new().open(2).high(10).low(4).close(9);
+        let prices: Vec<f64> = [
+            PriceSource::Open,
+            PriceSource::High,
+            PriceSource::Low,
+            PriceSource::Close,
+            PriceSource::Hl2,
+            PriceSource::Hlc3,
+            PriceSource::Ohlc4,
+            PriceSource::Hlcc4,
+        ]
+        .iter()
+        .map(|source| round(source.price(&bar)))
+        .collect();
+        assert_eq!(prices, vec![2.0, 10.0, 4.0, 9.0, 7.0, 7.667, 6.25, 8.0]);
+    }
+
+    #[test]
+    fn test_next() {
+        let mut roc = RateOfChange::new(1).unwrap().with_source(PriceSource::Open);
+        assert_eq!(roc.next(&Bar::new().open(10).close(50)), 0.0);
+        assert_eq!(roc.next(&Bar::new().open(12).close(5)), 20.0);
+        assert_eq!(roc.calc(6.0), -50.0);
+        assert!(roc.is_ready());
+
+        roc.reset();
+        assert_eq!(roc.next(&Bar::new().open(12)), 0.0);
+
+        let mut sma = SimpleMovingAverage::new(2)
+            .unwrap()
+            .with_source(PriceSource::Hl2);
+        sma.next(&Bar::new().high(4).low(2));
+        assert_eq!(sma.next(&Bar::new().high(8).low(4)), 4.5);
+    }
+
+    #[test]
+    fn test_default() {
+        assert_eq!(PriceSource::default(), PriceSource::Close);
+    }
+
+    #[test]
+    fn test_display() {
+        let sma = SimpleMovingAverage::new(9)
+            .unwrap()
+            .with_source::<f64>(PriceSource::Hlcc4);
+        assert_eq!(format!("{}", sma), "SMA(9).with_source(HLCC4)");
+        assert_eq!(sma.source(), PriceSource::Hlcc4);
+    }
+}
@@ -0,0 +1,401 @@
+//! Market regime classification.
+//!
+//! [RegimeClassifier](struct.RegimeClassifier.html) labels every bar with a
+//! [Regime](struct.Regime.html): a [Trend](enum.Trend.html), up, down or ranging, from the
+//! slope of a moving average confirmed by the strength of the trend, and a
+//! [Volatility](enum.Volatility.html), low, normal or high, from the percentile of the
+//! current volatility among its recent values.
+//!
+//! # Example
+//!
+//! ```
+//! use ta::regime::{RegimeClassifier, Trend, Volatility};
+//! use ta::Calculate;
+//!
+//! let mut classifier = RegimeClassifier::new(3, 20).unwrap();
+//! let mut regime = None;
+//! for i in 1..=30 {
+//!     regime = Some(classifier.calc(100.0 + i as f64));
+//! }
+//! let regime = regime.unwrap();
+//! assert_eq!(regime.trend, Trend::Up);
+//! // the same moves are ever smaller relative to the price
+//! assert_eq!(regime.volatility, Volatility::Low);
+//! ```
+
+use core::fmt;
+
+use crate::errors::*;
+use crate::indicators::{AverageTrueRange, DirectionalMovementIndex, ExponentialMovingAverage};
+use crate::sorted_window::SortedWindow;
+use crate::{Calculate, Close, Finite, Float, High, Low, Next, Reset, Warmup};
+
+/// Direction of the market.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Trend {
+    Up,
+    Down,
+    /// No direction, or one too weak to be a trend.
+    Ranging,
+}
+
+/// Volatility compared to its recent values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Volatility {
+    Low,
+    Normal,
+    High,
+}
+
+/// Output of [RegimeClassifier](struct.RegimeClassifier.html).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Regime<F = f64> {
+    pub trend: Trend,
+    pub volatility: Volatility,
+    /// Change of the moving average, in percent per bar.
+    pub slope: F,
+    /// Average directional index, from 0 to 100.
+    pub strength: F,
+    /// Percentile of the current volatility among its recent values, from 0 to 1.
+    pub volatility_rank: F,
+}
+
+impl<F: Float> Regime<F> {
+    pub fn is_trending(&self) -> bool {
+        self.trend != Trend::Ranging
+    }
+}
+
+impl<F: Finite> Finite for Regime<F> {
+    fn is_finite(&self) -> bool {
+        self.slope.is_finite() && self.strength.is_finite() && self.volatility_rank.is_finite()
+    }
+}
+
+/// Market regime classifier.
+///
+/// The trend is up when the slope of an [EMA](../indicators/struct.ExponentialMovingAverage.html)
+/// of the close is above _min_slope_ and the [ADX](../indicators/struct.DirectionalMovementIndex.html)
+/// is at least _min_strength_, down when the slope is below -_min_slope_ with the same
+/// strength, and ranging otherwise. A _min_strength_ of 0 classifies by the slope alone.
+///
+/// The volatility is the [ATR](../indicators/struct.AverageTrueRange.html) relative to the
+/// close, ranked among its values of the last _lookback_ bars: low below the _low_
+/// percentile, high above the _high_ one and normal in between. Volatility is normal until
+/// the ATR is ready.
+///
+/// All components use the same _length_.
+///
+/// # Parameters
+///
+/// * _length_ - number of periods of the EMA, ADX and ATR (integer greater than 0).
+///   Default is 14.
+/// * _lookback_ - number of bars the volatility is ranked among (integer greater than 0).
+///   Default is 100.
+/// * _min_slope_ - slope in percent per bar above which the market can trend
+///   (non-negative number), set with [min_slope](#method.min_slope). Default is 0.
+/// * _min_strength_ - ADX from which the market can trend (from 0 to 100), set with
+///   [min_strength](#method.min_strength). Default is 25.
+/// * _low_, _high_ - percentiles of low and high volatility (0 ≤ _low_ ≤ _high_ ≤ 1), set
+///   with [volatility_cutoffs](#method.volatility_cutoffs). Default is 0.2 and 0.8.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RegimeClassifier<F = f64> {
+    length: u32,
+    lookback: u32,
+    min_slope: F,
+    min_strength: F,
+    low: F,
+    high: F,
+    ema: ExponentialMovingAverage<F>,
+    dmi: DirectionalMovementIndex<F>,
+    atr: AverageTrueRange<F>,
+    volatility: SortedWindow<F>,
+    prev_ema: Option<F>,
+    count: u32,
+}
+
+impl RegimeClassifier {
+    pub fn new(length: u32, lookback: u32) -> Result<Self> {
+        Self::with_type(length, lookback)
+    }
+}
+
+impl<F: Float> RegimeClassifier<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(length: u32, lookback: u32) -> Result<Self> {
+        if lookback == 0 {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        Ok(Self {
+            length,
+            lookback,
+            min_slope: F::zero(),
+            min_strength: F::cast(25.0),
+            low: F::cast(0.2),
+            high: F::cast(0.8),
+            ema: ExponentialMovingAverage::with_type(length)?,
+            dmi: DirectionalMovementIndex::with_type(length)?,
+            atr: AverageTrueRange::with_type(length)?,
+            volatility: SortedWindow::new(lookback as usize),
+            prev_ema: None,
+            count: 0,
+        })
+    }
+
+    /// Sets the slope in percent per bar above which the market can trend.
+    pub fn min_slope(mut self, min_slope: F) -> Result<Self> {
+        if min_slope < F::zero() || !min_slope.is_finite() {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        self.min_slope = min_slope;
+        Ok(self)
+    }
+
+    /// Sets the ADX from which the market can trend, 0 ignores the ADX.
+    pub fn min_strength(mut self, min_strength: F) -> Result<Self> {
+        if !(min_strength >= F::zero() && min_strength <= F::cast(100.0)) {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        self.min_strength = min_strength;
+        Ok(self)
+    }
+
+    /// Sets the percentiles below which volatility is low and above which it is high.
+    pub fn volatility_cutoffs(mut self, low: F, high: F) -> Result<Self> {
+        if !(low >= F::zero() && low <= high && high <= F::one()) {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        self.low = low;
+        self.high = high;
+        Ok(self)
+    }
+
+    pub fn length(&self) -> u32 {
+        self.length
+    }
+
+    pub fn lookback(&self) -> u32 {
+        self.lookback
+    }
+
+    fn update(&mut self, close: F, strength: F, atr: F) -> Regime<F> {
+        self.count = self.count.saturating_add(1);
+
+        let ema = self.ema.calc(close);
+        let slope = match self.prev_ema.replace(ema) {
+            Some(prev) if prev != F::zero() => F::cast(100.0) * (ema - prev) / prev.abs(),
+            _ => F::zero(),
+        };
+        let strong = strength >= self.min_strength;
+        let trend = if strong && slope > self.min_slope {
+            Trend::Up
+        } else if strong && slope < -self.min_slope {
+            Trend::Down
+        } else {
+            Trend::Ranging
+        };
+
+        let volatility_rank = if self.atr.is_ready() {
+            let relative = if close != F::zero() {
+                atr / close.abs()
+            } else {
+                atr
+            };
+            self.volatility.push(relative);
+            self.volatility.rank(relative)
+        } else {
+            F::cast(0.5)
+        };
+        let volatility = if volatility_rank < self.low {
+            Volatility::Low
+        } else if volatility_rank > self.high {
+            Volatility::High
+        } else {
+            Volatility::Normal
+        };
+
+        Regime {
+            trend,
+            volatility,
+            slope,
+            strength,
+            volatility_rank,
+        }
+    }
+}
+
+impl<F: Float> Calculate<F> for RegimeClassifier<F> {
+    type Output = Regime<F>;
+
+    fn calc(&mut self, input: F) -> Self::Output {
+        let strength = self.dmi.calc(input).adx;
+        let atr = self.atr.calc(input);
+        self.update(input, strength, atr)
+    }
+}
+
+impl<F: Float, T: High<F> + Low<F> + Close<F>> Next<T> for RegimeClassifier<F> {
+    type Output = Regime<F>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let strength = self.dmi.next(input).adx;
+        let atr = self.atr.next(input);
+        self.update(input.close(), strength, atr)
+    }
+}
+
+impl<F: Float> Reset for RegimeClassifier<F> {
+    fn reset(&mut self) {
+        self.ema.reset();
+        self.dmi.reset();
+        self.atr.reset();
+        self.volatility.clear();
+        self.prev_ema = None;
+        self.count = 0;
+    }
+}
+
+impl<F: Float> Warmup for RegimeClassifier<F> {
+    // the slope needs one more bar after the EMA, the volatility window fills up with the
+    // ATR values after it is ready
+    fn warmup_period(&self) -> u32 {
+        (self.ema.warmup_period() + 1)
+            .max(self.dmi.warmup_period())
+            .max(self.atr.warmup_period() + self.lookback - 1)
+    }
+
+    fn is_ready(&self) -> bool {
+        self.count >= self.warmup_period()
+    }
+}
+
+impl Default for RegimeClassifier {
+    fn default() -> Self {
+        Self::new(14, 100).unwrap()
+    }
+}
+
+impl<F: Float> fmt::Display for RegimeClassifier<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "REGIME({}, {})", self.length, self.lookback)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(RegimeClassifier);
+
+    fn last(classifier: &mut RegimeClassifier, prices: &[f64]) -> Regime {
+        let mut regime = None;
+        for &price in prices {
+            regime = Some(classifier.calc(price));
+        }
+        regime.unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(RegimeClassifier::new(0, 10).is_err());
+        assert!(RegimeClassifier::new(3, 0).is_err());
+        let classifier = RegimeClassifier::new(3, 10).unwrap();
+        assert!(classifier.clone().min_slope(-0.1).is_err());
+        assert!(classifier.clone().min_strength(101.0).is_err());
+        assert!(classifier.clone().min_strength(f64::NAN).is_err());
+        assert!(classifier.clone().volatility_cutoffs(0.6, 0.4).is_err());
+        assert!(classifier.clone().volatility_cutoffs(0.1, 1.1).is_err());
+        assert!(classifier.volatility_cutoffs(0.0, 1.0).is_ok());
+    }
+
+    #[test]
+    fn test_trend() {
+        let mut classifier = RegimeClassifier::new(3, 10).unwrap();
+        let rising: Vec<f64> = (0..10).map(|i| 100.0 + i as f64).collect();
+        let regime = last(&mut classifier, &rising);
+        assert_eq!(regime.trend, Trend::Up);
+        assert!(regime.is_trending());
+        assert_eq!(regime.strength, 100.0);
+
+        let falling: Vec<f64> = (0..10).map(|i| 108.0 - 2.0 * i as f64).collect();
+        assert_eq!(last(&mut classifier, &falling).trend, Trend::Down);
+
+        let choppy: Vec<f64> = (0..20).map(|i| 90.0 + (i % 2) as f64).collect();
+        let regime = last(&mut classifier, &choppy);
+        assert_eq!(regime.trend, Trend::Ranging);
+        assert!(!regime.is_trending());
+    }
+
+    #[test]
+    fn test_min_slope() {
+        let prices: Vec<f64> = (0..20).map(|i| 100.0 + 0.01 * i as f64).collect();
+        let mut classifier = RegimeClassifier::new(3, 10).unwrap();
+        assert_eq!(last(&mut classifier, &prices).trend, Trend::Up);
+
+        let mut classifier = RegimeClassifier::new(3, 10)
+            .unwrap()
+            .min_slope(0.1)
+            .unwrap();
+        let regime = last(&mut classifier, &prices);
+        assert_eq!(regime.trend, Trend::Ranging);
+        assert_eq!(round(regime.slope), 0.01);
+    }
+
+    #[test]
+    fn test_min_strength() {
+        // two steps up, one step down: the EMA rises but the directional moves mostly cancel
+        let prices: Vec<f64> = (0..29)
+            .map(|i| 100.0 + (i / 3) as f64 + [0.0, 2.0, 1.0][i % 3])
+            .collect();
+        let mut classifier = RegimeClassifier::new(3, 10)
+            .unwrap()
+            .min_strength(0.0)
+            .unwrap();
+        assert_eq!(last(&mut classifier, &prices).trend, Trend::Up);
+
+        let mut classifier = RegimeClassifier::new(3, 10)
+            .unwrap()
+            .min_strength(90.0)
+            .unwrap();
+        let regime = last(&mut classifier, &prices);
+        assert!(regime.strength < 90.0);
+        assert_eq!(regime.trend, Trend::Ranging);
+    }
+
+    #[test]
+    fn test_volatility() {
+        let mut classifier = RegimeClassifier::new(2, 10).unwrap();
+        let calm: Vec<f64> = (0..20)
+            .map(|i| if i % 2 == 0 { 100.0 } else { 100.5 })
+            .collect();
+        assert_eq!(last(&mut classifier, &calm).volatility, Volatility::Normal);
+
+        let regime = classifier.next(&Bar::new().high(110.0).low(95.0).close(105.0));
+        assert_eq!(regime.volatility, Volatility::High);
+        assert_eq!(regime.volatility_rank, 0.95);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut classifier = RegimeClassifier::new(3, 10).unwrap();
+        last(&mut classifier, &[1.0, 2.0, 3.0, 4.0, 5.0]);
+        classifier.reset();
+        let regime = classifier.calc(10.0);
+        assert_eq!(regime.slope, 0.0);
+        assert_eq!(regime.volatility_rank, 0.5);
+        assert!(!classifier.is_ready());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            format!("{}", RegimeClassifier::default()),
+            "REGIME(14, 100)"
+        );
+    }
+}
@@ -0,0 +1,447 @@
+//! Creating indicators by name at runtime.
+//!
+//! A [Registry](struct.Registry.html) maps names to constructors of boxed
+//! [indicators](../trait.Indicator.html). [Registry::new](struct.Registry.html#method.new)
+//! knows the built-in indicators with a single `f64` output, under the names they display:
+//!
+//! | Name | Indicator | Parameters |
+//! |------|-----------|------------|
+//! | `SMA` | [SimpleMovingAverage](../indicators/struct.SimpleMovingAverage.html) | length = 9 |
+//! | `EMA` | [ExponentialMovingAverage](../indicators/struct.ExponentialMovingAverage.html) | length = 9 |
+//! | `SMMA` | [SmoothedMovingAverage](../indicators/struct.SmoothedMovingAverage.html) | length = 14 |
+//! | `RSI` | [RelativeStrengthIndex](../indicators/struct.RelativeStrengthIndex.html) | length = 14 |
+//! | `FAST_STOCH` | [FastStochastic](../indicators/struct.FastStochastic.html) | length = 14 |
+//! | `WILLR` | [WilliamsR](../indicators/struct.WilliamsR.html) | length = 14 |
+//! | `SLOW_STOCH` | [SlowStochastic](../indicators/struct.SlowStochastic.html) | stochastic length = 14, EMA length = 3 |
+//! | `PGO` | [PrettyGoodOscillator](../indicators/struct.PrettyGoodOscillator.html) | length = 14 |
+//! | `MFI` | [MoneyFlowIndex](../indicators/struct.MoneyFlowIndex.html) | length = 14 |
+//! | `MAX` | [Maximum](../indicators/struct.Maximum.html) | length = 14 |
+//! | `MIN` | [Minimum](../indicators/struct.Minimum.html) | length = 14 |
+//! | `SD`, `SAMPLE_SD` | [StandardDeviation](../indicators/struct.StandardDeviation.html) | length = 9 |
+//! | `VAR`, `SAMPLE_VAR` | [Variance](../indicators/struct.Variance.html) | length = 9 |
+//! | `EWVAR` | [ExponentialMovingVariance](../indicators/struct.ExponentialMovingVariance.html) | length = 9 |
+//! | `TRUE_RANGE` | [TrueRange](../indicators/struct.TrueRange.html) | |
+//! | `ATR` | [AverageTrueRange](../indicators/struct.AverageTrueRange.html) | length = 14 |
+//! | `ER` | [EfficiencyRatio](../indicators/struct.EfficiencyRatio.html) | length = 14 |
+//! | `ROC` | [RateOfChange](../indicators/struct.RateOfChange.html) | length = 9 |
+//! | `ROCP`, `ROCR`, `ROCR100` | [RateOfChange](../indicators/struct.RateOfChange.html) variants | length = 9 |
+//! | `MOM` | [Momentum](../indicators/struct.Momentum.html) | length = 10, displacement = 0 |
+//! | `OBV` | [OnBalanceVolume](../indicators/struct.OnBalanceVolume.html) | |
+//! | `GM` | [GeometricMean](../indicators/struct.GeometricMean.html) | length = 9 |
+//! | `HM` | [HarmonicMean](../indicators/struct.HarmonicMean.html) | length = 9 |
+//! | `MODE` | [Mode](../indicators/struct.Mode.html) | length = 14, bin width = 1.0 |
+//!
+//! Parameters are passed as a slice of numbers, in the order of the constructor of the
+//! indicator. Missing trailing parameters take the default values.
+//!
+//! Indicators can also be parsed from the textual form produced by their `Display`
+//! implementation, with [Registry::parse](struct.Registry.html#method.parse),
+//! [parse_indicator](fn.parse_indicator.html) or `str::parse`, which makes it possible to
+//! configure them in files:
+//!
+//! ```
+//! use ta::registry::{parse_indicator, BoxedIndicator};
+//!
+//! let roc = parse_indicator("ROC(9)").unwrap();
+//! assert_eq!(roc.to_string(), "ROC(9)");
+//!
+//! let stoch: BoxedIndicator = "SLOW_STOCH(14, 3)".parse().unwrap();
+//! assert_eq!(stoch.to_string(), "SLOW_STOCH(14, 3)");
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! use ta::registry::Registry;
+//! use ta::DataItem;
+//!
+//! let registry = Registry::new();
+//! let mut stoch = registry.create("FAST_STOCH", &[5.0]).unwrap();
+//! assert_eq!(stoch.to_string(), "FAST_STOCH(5)");
+//!
+//! let bar = DataItem::builder()
+//!     .open(2.0)
+//!     .high(3.0)
+//!     .low(1.0)
+//!     .close(2.0)
+//!     .volume(100.0)
+//!     .build()
+//!     .unwrap();
+//! assert_eq!(stoch.update(&bar), 50.0);
+//!
+//! assert!(registry.create("UNKNOWN", &[]).is_err());
+//! assert!(registry.create("SMA", &[0.0]).is_err());
+//! ```
+
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+use crate::errors::*;
+use crate::indicators::*;
+use crate::{DataItem, Indicator, Ohlcv};
+
+/// Boxed indicator created by a [Registry](struct.Registry.html).
+pub type BoxedIndicator<T = DataItem> = Box<dyn Indicator<T, Output = f64>>;
+
+type Constructor<T> = Box<dyn Fn(&[f64]) -> Result<BoxedIndicator<T>> + Send + Sync>;
+
+/// Maps names to constructors of boxed indicators consuming `T`.
+pub struct Registry<T = DataItem> {
+    constructors: BTreeMap<String, Constructor<T>>,
+}
+
+impl<T> Registry<T> {
+    /// Creates a registry without any indicators.
+    pub fn empty() -> Self {
+        Self {
+            constructors: BTreeMap::new(),
+        }
+    }
+
+    /// Registers a constructor under `name`, replacing the previous one with the same name.
+    pub fn register<C>(&mut self, name: impl Into<String>, constructor: C)
+    where
+        C: Fn(&[f64]) -> Result<BoxedIndicator<T>> + Send + Sync + 'static,
+    {
+        self.constructors.insert(name.into(), Box::new(constructor));
+    }
+
+    /// Creates the indicator registered under `name` with the given parameters.
+    pub fn create(&self, name: &str, params: &[f64]) -> Result<BoxedIndicator<T>> {
+        match self.constructors.get(name) {
+            Some(constructor) => constructor(params),
+            None => Err(ErrorKind::UnknownIndicator(name.to_string()).into()),
+        }
+    }
+
+    /// Creates an indicator from its textual form, the same as produced by its `Display`
+    /// implementation, e.g. `"FAST_STOCH(14)"`, `"MOM(10, 2)"` or `"OBV"`.
+    pub fn parse(&self, spec: &str) -> Result<BoxedIndicator<T>> {
+        let (name, params) = split_spec(spec)?;
+        self.create(name, &params)
+    }
+
+    /// Returns `true` if there is an indicator registered under `name`.
+    pub fn contains(&self, name: &str) -> bool {
+        self.constructors.contains_key(name)
+    }
+
+    /// Names of the registered indicators, in alphabetical order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.constructors.keys().map(String::as_str)
+    }
+}
+
+impl<T: Ohlcv + 'static> Registry<T> {
+    /// Creates a registry with all built-in indicators.
+    pub fn new() -> Self {
+        let mut registry = Self::empty();
+        registry.register("SMA", |p| {
+            let [n] = lengths(p, [9])?;
+            boxed(SimpleMovingAverage::new(n))
+        });
+        registry.register("EMA", |p| {
+            let [n] = lengths(p, [9])?;
+            boxed(ExponentialMovingAverage::new(n))
+        });
+        registry.register("SMMA", |p| {
+            let [n] = lengths(p, [14])?;
+            boxed(SmoothedMovingAverage::new(n))
+        });
+        registry.register("RSI", |p| {
+            let [n] = lengths(p, [14])?;
+            boxed(RelativeStrengthIndex::new(n))
+        });
+        registry.register("FAST_STOCH", |p| {
+            let [n] = lengths(p, [14])?;
+            boxed(FastStochastic::new(n))
+        });
+        registry.register("WILLR", |p| {
+            let [n] = lengths(p, [14])?;
+            boxed(WilliamsR::new(n))
+        });
+        registry.register("SLOW_STOCH", |p| {
+            let [a, b] = lengths(p, [14, 3])?;
+            boxed(SlowStochastic::new(a, b))
+        });
+        registry.register("PGO", |p| {
+            let [n] = lengths(p, [14])?;
+            boxed(PrettyGoodOscillator::new(n))
+        });
+        registry.register("MFI", |p| {
+            let [n] = lengths(p, [14])?;
+            boxed(MoneyFlowIndex::new(n))
+        });
+        registry.register("MAX", |p| {
+            let [n] = lengths(p, [14])?;
+            boxed(Maximum::new(n))
+        });
+        registry.register("MIN", |p| {
+            let [n] = lengths(p, [14])?;
+            boxed(Minimum::new(n))
+        });
+        registry.register("SD", |p| {
+            let [n] = lengths(p, [9])?;
+            boxed(StandardDeviation::new(n))
+        });
+        registry.register("SAMPLE_SD", |p| {
+            let [n] = lengths(p, [9])?;
+            boxed(StandardDeviation::new(n).map(StandardDeviation::sample))
+        });
+        registry.register("VAR", |p| {
+            let [n] = lengths(p, [9])?;
+            boxed(Variance::new(n))
+        });
+        registry.register("SAMPLE_VAR", |p| {
+            let [n] = lengths(p, [9])?;
+            boxed(Variance::new(n).map(Variance::sample))
+        });
+        registry.register("EWVAR", |p| {
+            let [n] = lengths(p, [9])?;
+            boxed(ExponentialMovingVariance::new(n))
+        });
+        registry.register("TRUE_RANGE", |p| {
+            lengths(p, [])?;
+            boxed(Ok(TrueRange::new()))
+        });
+        registry.register("ATR", |p| {
+            let [n] = lengths(p, [14])?;
+            boxed(AverageTrueRange::new(n))
+        });
+        registry.register("ER", |p| {
+            let [n] = lengths(p, [14])?;
+            boxed(EfficiencyRatio::new(n))
+        });
+        registry.register("ROC", |p| {
+            let [n] = lengths(p, [9])?;
+            boxed(RateOfChange::new(n))
+        });
+        registry.register("ROCP", |p| {
+            let [n] = lengths(p, [9])?;
+            boxed(RateOfChange::with_kind(RateOfChangeKind::Fraction, n))
+        });
+        registry.register("ROCR", |p| {
+            let [n] = lengths(p, [9])?;
+            boxed(RateOfChange::with_kind(RateOfChangeKind::Ratio, n))
+        });
+        registry.register("ROCR100", |p| {
+            let [n] = lengths(p, [9])?;
+            boxed(RateOfChange::with_kind(RateOfChangeKind::Ratio100, n))
+        });
+        registry.register("MOM", |p| {
+            let [a, b] = lengths(p, [10, 0])?;
+            boxed(Momentum::displaced(a, b))
+        });
+        registry.register("OBV", |p| {
+            lengths(p, [])?;
+            boxed(Ok(OnBalanceVolume::new()))
+        });
+        registry.register("GM", |p| {
+            let [n] = lengths(p, [9])?;
+            boxed(GeometricMean::new(n))
+        });
+        registry.register("HM", |p| {
+            let [n] = lengths(p, [9])?;
+            boxed(HarmonicMean::new(n))
+        });
+        registry.register("MODE", |p| {
+            let [n, bin_width] = numbers(p, [14.0, 1.0])?;
+            boxed(Mode::new(length(n)?, bin_width))
+        });
+        registry
+    }
+}
+
+impl<T: Ohlcv + 'static> Default for Registry<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Parses an indicator spec such as `"ROC(9)"` with the built-in indicators, see
+/// [Registry::parse](struct.Registry.html#method.parse).
+pub fn parse_indicator(spec: &str) -> Result<BoxedIndicator> {
+    Registry::new().parse(spec)
+}
+
+impl<T: Ohlcv + 'static> FromStr for BoxedIndicator<T> {
+    type Err = Error;
+
+    fn from_str(spec: &str) -> Result<Self> {
+        Registry::new().parse(spec)
+    }
+}
+
+impl<T> fmt::Debug for Registry<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.names()).finish()
+    }
+}
+
+/// Splits `NAME(a, b, ...)` into the name and the parameters.
+fn split_spec(spec: &str) -> Result<(&str, Vec<f64>)> {
+    let invalid = || Error::from_kind(ErrorKind::InvalidSpec(spec.to_string()));
+
+    let trimmed = spec.trim();
+    let (name, params) = match trimmed.find('(') {
+        Some(open) => {
+            let params = trimmed[open + 1..].strip_suffix(')').ok_or_else(invalid)?;
+            (trimmed[..open].trim_end(), params)
+        }
+        None => (trimmed, ""),
+    };
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(invalid());
+    }
+
+    if params.trim().is_empty() {
+        return Ok((name, Vec::new()));
+    }
+    let params = params
+        .split(',')
+        .map(|param| param.trim().parse::<f64>().map_err(|_| invalid()))
+        .collect::<Result<Vec<f64>>>()?;
+    Ok((name, params))
+}
+
+fn boxed<T, I>(indicator: Result<I>) -> Result<BoxedIndicator<T>>
+where
+    I: Indicator<T, Output = f64> + 'static,
+{
+    Ok(Box::new(indicator?))
+}
+
+/// Reads integer parameters, taking `defaults` for the missing ones. Fails on fractional or
+/// negative values and on more parameters than expected.
+fn lengths<const N: usize>(params: &[f64], defaults: [u32; N]) -> Result<[u32; N]> {
+    let mut lengths = defaults;
+    let values = numbers(params, defaults.map(f64::from))?;
+    for (length, &value) in lengths.iter_mut().zip(values.iter()) {
+        *length = self::length(value)?;
+    }
+    Ok(lengths)
+}
+
+/// Reads parameters, taking `defaults` for the missing ones. Fails on more parameters than
+/// expected.
+fn numbers<const N: usize>(params: &[f64], defaults: [f64; N]) -> Result<[f64; N]> {
+    if params.len() > N {
+        return Err(Error::from_kind(ErrorKind::InvalidParameter));
+    }
+    let mut numbers = defaults;
+    numbers[..params.len()].copy_from_slice(params);
+    Ok(numbers)
+}
+
+fn length(value: f64) -> Result<u32> {
+    if value < 0.0 || num_traits::Float::fract(value) != 0.0 || value > f64::from(u32::MAX) {
+        return Err(Error::from_kind(ErrorKind::InvalidParameter));
+    }
+    Ok(value as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+    use crate::Next;
+
+    #[test]
+    fn test_builtins() {
+        let registry = Registry::<Bar>::new();
+        let bar = Bar::new().high(3).low(1).close(2).volume(10);
+        for name in registry.names() {
+            let mut indicator = registry.create(name, &[]).unwrap();
+            assert!(indicator.to_string().starts_with(name), "{}", name);
+            indicator.update(&bar);
+        }
+        assert_eq!(registry.names().count(), 28);
+    }
+
+    #[test]
+    fn test_params() {
+        let registry = Registry::<Bar>::new();
+        let create = |name, params: &[f64]| registry.create(name, params).map(|i| i.to_string());
+
+        assert_eq!(create("SMA", &[]).unwrap(), "SMA(9)");
+        assert_eq!(create("SMA", &[20.0]).unwrap(), "SMA(20)");
+        assert_eq!(create("MOM", &[5.0, 2.0]).unwrap(), "MOM(5, 2)");
+        assert!(create("SMA", &[0.0]).is_err());
+        assert!(create("SMA", &[2.5]).is_err());
+        assert!(create("SMA", &[-1.0]).is_err());
+        assert!(create("SMA", &[5.0, 1.0]).is_err());
+        assert!(create("OBV", &[1.0]).is_err());
+    }
+
+    #[test]
+    fn test_parse_round_trip() {
+        let registry = Registry::<Bar>::new();
+        for name in registry.names() {
+            let spec = registry.create(name, &[]).unwrap().to_string();
+            assert_eq!(registry.parse(&spec).unwrap().to_string(), spec);
+        }
+        for spec in [
+            "MOM(5, 2)",
+            "MODE(20, 0.25)",
+            "ROCR100(3)",
+            "SAMPLE_SD(4)",
+            "TRUE_RANGE()",
+        ]
+        .iter()
+        {
+            let parsed: BoxedIndicator<Bar> = spec.parse().unwrap();
+            assert_eq!(&parsed.to_string(), spec);
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(split_spec("OBV").unwrap(), ("OBV", vec![]));
+        assert_eq!(split_spec(" SMA ( 9 ) ").unwrap(), ("SMA", vec![9.0]));
+        assert_eq!(
+            split_spec("MODE(14,0.5)").unwrap(),
+            ("MODE", vec![14.0, 0.5])
+        );
+
+        for spec in ["", "(9)", "SMA(9", "SMA(a)", "SMA(9,)", "S M A", "SMA(9))"].iter() {
+            match split_spec(spec) {
+                Err(Error(ErrorKind::InvalidSpec(s), _)) => assert_eq!(&s, spec),
+                other => panic!("{:?} parsed as {:?}", spec, other.ok()),
+            }
+        }
+
+        assert!(parse_indicator("NOPE(1)").is_err());
+        assert!(parse_indicator("SMA(0)").is_err());
+    }
+
+    #[test]
+    fn test_unknown() {
+        let registry = Registry::<Bar>::new();
+        match registry.create("NOPE", &[]) {
+            Err(Error(ErrorKind::UnknownIndicator(name), _)) => assert_eq!(name, "NOPE"),
+            _ => panic!("expected an unknown indicator error"),
+        }
+    }
+
+    #[test]
+    fn test_register() {
+        let mut registry = Registry::<Bar>::empty();
+        assert!(!registry.contains("FAST"));
+        registry.register("FAST", |_| Ok(Box::new(FastStochastic::new(3)?)));
+        assert!(registry.contains("FAST"));
+        assert_eq!(format!("{:?}", registry), "{\"FAST\"}");
+
+        let mut boxed = registry.create("FAST", &[]).unwrap();
+        let mut stoch = FastStochastic::new(3).unwrap();
+        for close in [2.0, 4.0, 3.0].iter() {
+            let bar = Bar::new().high(5).low(1).close(*close);
+            assert_eq!(boxed.next(&bar), stoch.next(&bar));
+        }
+        boxed.reset();
+        assert!(!boxed.is_ready());
+    }
+}
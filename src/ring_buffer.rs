@@ -0,0 +1,468 @@
+use alloc::vec::Vec;
+use core::iter::Chain;
+use core::marker::PhantomData;
+use core::ops::Index;
+use core::slice;
+
+#[cfg(feature = "serde")]
+use crate::errors::*;
+
+/// Fixed-capacity window of the latest values.
+///
+/// The values are stored in a vector allocated once, which is overwritten in place once
+/// it's full, wrapping around its end, so pushing never allocates nor shifts values.
+/// Windowed indicators keep their inputs in it. With [inline](#method.inline) they are
+/// stored in an array of a capacity fixed at compile time instead, see
+/// [Storage](trait.Storage.html).
+///
+/// Values are indexed from the oldest one (0) to the newest one (`len() - 1`).
+///
+/// # Example
+///
+/// ```
+/// use ta::RingBuffer;
+///
+/// let mut window = RingBuffer::new(3);
+/// assert_eq!(window.push(1), None);
+/// window.push(2);
+/// window.push(3);
+/// assert!(window.is_full());
+///
+/// // evicts the oldest value
+/// assert_eq!(window.push(4), Some(1));
+/// assert_eq!(window.oldest(), Some(&2));
+/// assert_eq!(window.newest(), Some(&4));
+/// assert_eq!(window.iter().copied().collect::<Vec<_>>(), vec![2, 3, 4]);
+/// ```
+///
+/// With the `serde` feature, deserializing a buffer whose position of the oldest value
+/// doesn't fit its values and capacity fails with `InvalidParameter`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        try_from = "RingBufferFields<T>",
+        bound(deserialize = "T: serde::Deserialize<'de>, S: Storage<T>")
+    )
+)]
+pub struct RingBuffer<T, S = Vec<T>> {
+    capacity: usize,
+    // position of the oldest value once the buffer is full
+    head: usize,
+    len: usize,
+    values: S,
+    marker: PhantomData<T>,
+}
+
+/// Storage of the values of a [RingBuffer](struct.RingBuffer.html).
+///
+/// A `Vec` grows up to the capacity of the buffer as values are pushed, an array `[T; N]`
+/// holds a buffer of _N_ values inline, without an allocation.
+pub trait Storage<T>: AsRef<[T]> + AsMut<[T]> + Sized {
+    /// Empty storage for `capacity` values, `None` if it can't hold as many.
+    fn empty(capacity: usize) -> Option<Self>;
+
+    /// Stores `value` after the first `len` values of a storage which is not full.
+    fn append(&mut self, len: usize, value: T);
+
+    /// Drops the values stored after the first `len` ones.
+    fn truncate(&mut self, len: usize);
+}
+
+impl<T> Storage<T> for Vec<T> {
+    fn empty(_capacity: usize) -> Option<Self> {
+        // the values grow up to the capacity as they are pushed, so a deserialized capacity
+        // isn't trusted with an allocation
+        Some(Vec::new())
+    }
+
+    fn append(&mut self, _len: usize, value: T) {
+        self.push(value);
+    }
+
+    fn truncate(&mut self, len: usize) {
+        Vec::truncate(self, len);
+    }
+}
+
+impl<T: Default, const N: usize> Storage<T> for [T; N] {
+    fn empty(capacity: usize) -> Option<Self> {
+        if capacity == N {
+            Some(core::array::from_fn(|_| T::default()))
+        } else {
+            None
+        }
+    }
+
+    fn append(&mut self, len: usize, value: T) {
+        self[len] = value;
+    }
+
+    // values past the length are never read, they are overwritten by the next pushes
+    fn truncate(&mut self, _len: usize) {}
+}
+
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize, S: Storage<T>> serde::Serialize for RingBuffer<T, S> {
+    fn serialize<Z: serde::Serializer>(
+        &self,
+        serializer: Z,
+    ) -> core::result::Result<Z::Ok, Z::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("RingBuffer", 3)?;
+        state.serialize_field("capacity", &self.capacity)?;
+        state.serialize_field("head", &self.head)?;
+        state.serialize_field("values", self.stored())?;
+        state.end()
+    }
+}
+
+// Fields of a deserialized buffer, checked against each other before they become a buffer.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct RingBufferFields<T> {
+    capacity: usize,
+    head: usize,
+    values: Vec<T>,
+}
+
+#[cfg(feature = "serde")]
+impl<T, S: Storage<T>> core::convert::TryFrom<RingBufferFields<T>> for RingBuffer<T, S> {
+    type Error = Error;
+
+    fn try_from(fields: RingBufferFields<T>) -> Result<Self> {
+        let RingBufferFields {
+            capacity,
+            head,
+            values,
+        } = fields;
+        // the oldest value moves from the front only once the buffer is full
+        let valid = if values.len() < capacity {
+            head == 0
+        } else {
+            values.len() == capacity && head < capacity
+        };
+        let mut storage = match S::empty(capacity) {
+            Some(storage) if valid => storage,
+            _ => return Err(Error::from_kind(ErrorKind::InvalidParameter)),
+        };
+        let len = values.len();
+        for (i, value) in values.into_iter().enumerate() {
+            storage.append(i, value);
+        }
+        Ok(Self {
+            capacity,
+            head,
+            len,
+            values: storage,
+            marker: PhantomData,
+        })
+    }
+}
+
+impl<T> RingBuffer<T> {
+    /// Creates an empty buffer of `capacity` values.
+    ///
+    /// # Panics
+    ///
+    /// If `capacity` is 0.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "ring buffer capacity must be greater than 0");
+        Self {
+            capacity,
+            head: 0,
+            len: 0,
+            values: Vec::with_capacity(capacity),
+            marker: PhantomData,
+        }
+    }
+
+    /// Changes the capacity, keeping the newest values which fit.
+    ///
+    /// # Panics
+    ///
+    /// If `capacity` is 0.
+    pub fn resize(&mut self, capacity: usize) {
+        assert!(capacity > 0, "ring buffer capacity must be greater than 0");
+        self.values.rotate_left(self.head);
+        self.head = 0;
+        if self.values.len() > capacity {
+            self.values.drain(..self.values.len() - capacity);
+        }
+        self.values.shrink_to(capacity);
+        self.values.reserve_exact(capacity - self.values.len());
+        self.len = self.values.len();
+        self.capacity = capacity;
+    }
+}
+
+impl<T: Default, const N: usize> RingBuffer<T, [T; N]> {
+    /// Creates an empty buffer of _N_ values stored inline in an array.
+    ///
+    /// # Panics
+    ///
+    /// If _N_ is 0.
+    pub fn inline() -> Self {
+        assert!(N > 0, "ring buffer capacity must be greater than 0");
+        Self {
+            capacity: N,
+            head: 0,
+            len: 0,
+            values: core::array::from_fn(|_| T::default()),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T, S: Storage<T>> RingBuffer<T, S> {
+    // the values pushed so far, starting with the oldest one once the buffer is full
+    fn stored(&self) -> &[T] {
+        &self.values.as_ref()[..self.len]
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len == self.capacity
+    }
+
+    /// Appends a value, returning the oldest one if the buffer was full.
+    pub fn push(&mut self, value: T) -> Option<T> {
+        if self.len < self.capacity {
+            self.values.append(self.len, value);
+            self.len += 1;
+            return None;
+        }
+        let old = core::mem::replace(&mut self.values.as_mut()[self.head], value);
+        self.head += 1;
+        if self.head == self.capacity {
+            self.head = 0;
+        }
+        Some(old)
+    }
+
+    /// Value at `index`, counted from the oldest value.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index < self.len {
+            Some(&self.values.as_ref()[self.position(index)])
+        } else {
+            None
+        }
+    }
+
+    // position in `values` of a valid index, which wraps around without a division
+    fn position(&self, index: usize) -> usize {
+        let position = self.head + index;
+        if position >= self.len {
+            position - self.len
+        } else {
+            position
+        }
+    }
+
+    /// Value at `index`, counted back from the newest value (0).
+    pub fn get_back(&self, index: usize) -> Option<&T> {
+        if index < self.len {
+            self.get(self.len - 1 - index)
+        } else {
+            None
+        }
+    }
+
+    /// Value evicted by the next push, if the buffer is full.
+    pub fn next_evicted(&self) -> Option<&T> {
+        if self.is_full() {
+            self.oldest()
+        } else {
+            None
+        }
+    }
+
+    pub fn oldest(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    pub fn newest(&self) -> Option<&T> {
+        self.get_back(0)
+    }
+
+    pub fn newest_mut(&mut self) -> Option<&mut T> {
+        if self.len == 0 {
+            return None;
+        }
+        let position = self.position(self.len - 1);
+        Some(&mut self.values.as_mut()[position])
+    }
+
+    /// The values from the oldest to the newest one, as two slices.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let (newer, older) = self.stored().split_at(self.head);
+        (older, newer)
+    }
+
+    /// Iterator from the oldest to the newest value.
+    pub fn iter(&self) -> Chain<slice::Iter<'_, T>, slice::Iter<'_, T>> {
+        let (older, newer) = self.as_slices();
+        older.iter().chain(newer.iter())
+    }
+
+    /// Removes all values, keeping the allocation.
+    pub fn clear(&mut self) {
+        self.values.truncate(0);
+        self.len = 0;
+        self.head = 0;
+    }
+}
+
+impl<T, S: Storage<T>> Index<usize> for RingBuffer<T, S> {
+    type Output = T;
+
+    /// Value at `index`, counted from the oldest value.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds.
+    fn index(&self, index: usize) -> &T {
+        self.get(index).expect("ring buffer index out of bounds")
+    }
+}
+
+impl<'a, T, S: Storage<T>> IntoIterator for &'a RingBuffer<T, S> {
+    type Item = &'a T;
+    type IntoIter = Chain<slice::Iter<'a, T>, slice::Iter<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn values(buffer: &RingBuffer<i32>) -> Vec<i32> {
+        buffer.iter().copied().collect()
+    }
+
+    #[test]
+    fn test_push() {
+        let mut buffer = RingBuffer::new(3);
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.oldest(), None);
+        assert_eq!(buffer.newest_mut(), None);
+        assert_eq!(buffer.push(1), None);
+        assert_eq!(buffer.push(2), None);
+        assert_eq!(buffer.next_evicted(), None);
+        assert_eq!(buffer.push(3), None);
+        assert_eq!(buffer.next_evicted(), Some(&1));
+        assert_eq!(buffer.push(4), Some(1));
+        assert_eq!(buffer.push(5), Some(2));
+        assert_eq!(buffer.len(), 3);
+        assert_eq!(values(&buffer), vec![3, 4, 5]);
+        assert_eq!(buffer.as_slices(), (&[3][..], &[4, 5][..]));
+        assert_eq!(buffer.get(0), Some(&3));
+        assert_eq!(buffer.get(3), None);
+        assert_eq!(buffer[1], 4);
+        assert_eq!(buffer.get_back(0), Some(&5));
+        assert_eq!(buffer.get_back(2), Some(&3));
+        assert_eq!(buffer.get_back(3), None);
+
+        *buffer.newest_mut().unwrap() = 6;
+        assert_eq!(values(&buffer), vec![3, 4, 6]);
+
+        buffer.clear();
+        assert!(buffer.is_empty());
+        buffer.push(7);
+        assert_eq!(buffer.newest(), Some(&7));
+    }
+
+    #[test]
+    fn test_inline() {
+        let mut buffer = RingBuffer::<i32, [i32; 3]>::inline();
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.capacity(), 3);
+        for value in 1..=3 {
+            assert_eq!(buffer.push(value), None);
+        }
+        assert!(buffer.is_full());
+        assert_eq!(buffer.push(4), Some(1));
+        assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), vec![2, 3, 4]);
+        assert_eq!(buffer.get_back(0), Some(&4));
+
+        buffer.clear();
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.oldest(), None);
+        buffer.push(5);
+        assert_eq!(buffer.as_slices(), (&[5][..], &[][..]));
+    }
+
+    #[test]
+    fn test_resize() {
+        let mut buffer = RingBuffer::new(4);
+        for i in 1..=6 {
+            buffer.push(i);
+        }
+        buffer.resize(2);
+        assert_eq!(buffer.capacity(), 2);
+        assert_eq!(values(&buffer), vec![5, 6]);
+        assert_eq!(buffer.push(7), Some(5));
+
+        buffer.resize(3);
+        assert!(!buffer.is_full());
+        buffer.push(8);
+        assert_eq!(values(&buffer), vec![6, 7, 8]);
+        assert_eq!(buffer.push(9), Some(6));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let mut buffer = RingBuffer::new(3);
+        for value in 1..=4 {
+            buffer.push(value);
+        }
+        let json = serde_json::to_string(&buffer).unwrap();
+        assert_eq!(json, r#"{"capacity":3,"head":1,"values":[4,2,3]}"#);
+        let mut restored: RingBuffer<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(values(&restored), vec![2, 3, 4]);
+        assert_eq!(restored.push(5), Some(2));
+
+        let partial: RingBuffer<i32> =
+            serde_json::from_str(r#"{"capacity":3,"head":0,"values":[1]}"#).unwrap();
+        assert_eq!(partial.newest(), Some(&1));
+
+        for json in &[
+            r#"{"capacity":3,"head":5,"values":[1,1,1]}"#,
+            r#"{"capacity":3,"head":1,"values":[1,1]}"#,
+            r#"{"capacity":2,"head":0,"values":[1,1,1]}"#,
+            r#"{"capacity":0,"head":0,"values":[]}"#,
+        ] {
+            assert!(serde_json::from_str::<RingBuffer<i32>>(json).is_err());
+        }
+
+        // an array holds exactly its length
+        let inline: RingBuffer<i32, [i32; 3]> = serde_json::from_str(&json).unwrap();
+        assert_eq!(inline.iter().copied().collect::<Vec<_>>(), vec![2, 3, 4]);
+        assert_eq!(serde_json::to_string(&inline).unwrap(), json);
+        assert!(serde_json::from_str::<RingBuffer<i32, [i32; 4]>>(&json).is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_zero_capacity() {
+        RingBuffer::<f64>::new(0);
+    }
+}
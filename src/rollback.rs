@@ -0,0 +1,217 @@
+//! Undoing the latest inputs of an indicator.
+//!
+//! Exchanges occasionally correct bars after publishing them, or deliver late ones.
+//! [Tracked](struct.Tracked.html) keeps the state of an indicator before each of its latest
+//! inputs, so that they can be [rolled back](trait.Rollback.html) and the corrected data
+//! replayed without resetting the indicator and replaying its whole history.
+//!
+//! # Example
+//!
+//! ```
+//! use ta::indicators::SimpleMovingAverage;
+//! use ta::rollback::{Rollback, Tracked};
+//! use ta::Calculate;
+//!
+//! let mut sma = Tracked::new(SimpleMovingAverage::new(2).unwrap(), 3).unwrap();
+//! sma.calc(10.0);
+//! sma.calc(12.0);
+//! sma.calc(99.0); // bad tick
+//!
+//! // the last two inputs are corrected
+//! sma.rollback(2).unwrap();
+//! assert_eq!(sma.calc(11.0), 10.5);
+//! assert_eq!(sma.calc(13.0), 12.0);
+//! ```
+
+use alloc::collections::VecDeque;
+use core::fmt;
+
+use crate::errors::*;
+use crate::{Calculate, Next, Reset, Warmup};
+
+/// Indicator whose latest inputs can be undone.
+pub trait Rollback {
+    /// Restores the state before the latest `n` inputs. Fails with `InvalidParameter`
+    /// without changing anything if fewer than `n` inputs can be undone.
+    fn rollback(&mut self, n: usize) -> Result<()>;
+
+    /// Number of inputs which can be undone.
+    fn undoable(&self) -> usize;
+}
+
+/// Indicator wrapper keeping snapshots of the indicator before each of its last _depth_
+/// inputs.
+///
+/// Every input clones the wrapped indicator, which costs an allocation for indicators with
+/// a window; keep _depth_ to the number of bars which can be corrected.
+///
+/// # Parameters
+///
+/// * _depth_ - maximum number of inputs which can be undone (greater than 0)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Tracked<I> {
+    indicator: I,
+    depth: usize,
+    snapshots: VecDeque<I>,
+}
+
+impl<I: Clone> Tracked<I> {
+    pub fn new(indicator: I, depth: usize) -> Result<Self> {
+        if depth == 0 {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        Ok(Self {
+            indicator,
+            depth,
+            snapshots: VecDeque::with_capacity(depth),
+        })
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    pub fn inner(&self) -> &I {
+        &self.indicator
+    }
+
+    pub fn into_inner(self) -> I {
+        self.indicator
+    }
+
+    fn snapshot(&mut self) {
+        if self.snapshots.len() == self.depth {
+            self.snapshots.pop_front();
+        }
+        self.snapshots.push_back(self.indicator.clone());
+    }
+}
+
+impl<I> Rollback for Tracked<I> {
+    fn rollback(&mut self, n: usize) -> Result<()> {
+        if n > self.snapshots.len() {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        if n > 0 {
+            self.snapshots.truncate(self.snapshots.len() - n + 1);
+            // the snapshot taken before the oldest undone input
+            self.indicator = self.snapshots.pop_back().unwrap();
+        }
+        Ok(())
+    }
+
+    fn undoable(&self) -> usize {
+        self.snapshots.len()
+    }
+}
+
+impl<F, I: Calculate<F> + Clone> Calculate<F> for Tracked<I> {
+    type Output = I::Output;
+
+    fn calc(&mut self, input: F) -> Self::Output {
+        self.snapshot();
+        self.indicator.calc(input)
+    }
+}
+
+impl<T, I: Next<T> + Clone> Next<T> for Tracked<I> {
+    type Output = I::Output;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.snapshot();
+        self.indicator.next(input)
+    }
+}
+
+impl<I: Reset> Reset for Tracked<I> {
+    fn reset(&mut self) {
+        self.indicator.reset();
+        self.snapshots.clear();
+    }
+}
+
+impl<I: Warmup> Warmup for Tracked<I> {
+    fn warmup_period(&self) -> u32 {
+        self.indicator.warmup_period()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.indicator.is_ready()
+    }
+}
+
+impl<I: fmt::Display> fmt::Display for Tracked<I> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.indicator.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{FastStochastic, Maximum};
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_new() {
+        assert!(Tracked::new(Maximum::new(3).unwrap(), 0).is_err());
+        assert!(Tracked::new(Maximum::new(3).unwrap(), 1).is_ok());
+    }
+
+    #[test]
+    fn test_rollback() {
+        let mut max = Tracked::new(Maximum::new(2).unwrap(), 2).unwrap();
+        assert_eq!(max.undoable(), 0);
+        assert!(max.rollback(1).is_err());
+
+        max.calc(5.0);
+        max.calc(3.0);
+        max.calc(9.0);
+        assert_eq!(max.undoable(), 2);
+        assert!(max.rollback(3).is_err());
+        assert_eq!(max.undoable(), 2);
+
+        max.rollback(0).unwrap();
+        max.rollback(1).unwrap();
+        assert_eq!(max.undoable(), 1);
+        assert_eq!(max.calc(4.0), 4.0);
+        max.rollback(2).unwrap();
+        assert_eq!(max.undoable(), 0);
+        assert_eq!(max.calc(1.0), 5.0);
+    }
+
+    #[test]
+    fn test_replay() {
+        let bars: Vec<Bar> = [(11.0, 9.0, 10.0), (12.0, 10.0, 11.0), (10.0, 8.0, 9.0)]
+            .iter()
+            .map(|&(high, low, close)| Bar::new().high(high).low(low).close(close))
+            .collect();
+        let corrected = Bar::new().high(13.0).low(11.0).close(12.5);
+
+        let mut stoch = Tracked::new(FastStochastic::new(3).unwrap(), 2).unwrap();
+        for bar in &bars {
+            stoch.next(bar);
+        }
+        stoch.rollback(2).unwrap();
+        stoch.next(&corrected);
+        let replayed = stoch.next(&bars[2]);
+
+        let mut expected = FastStochastic::new(3).unwrap();
+        expected.next(&bars[0]);
+        expected.next(&corrected);
+        assert_eq!(replayed, expected.next(&bars[2]));
+        assert_eq!(replayed, 20.0);
+        assert_eq!(stoch.inner().to_string(), "FAST_STOCH(3)");
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut max = Tracked::new(Maximum::new(2).unwrap(), 2).unwrap();
+        max.calc(5.0);
+        max.reset();
+        assert_eq!(max.undoable(), 0);
+        assert_eq!(max.calc(1.0), 1.0);
+        assert!(!max.is_ready());
+    }
+}
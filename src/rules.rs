@@ -0,0 +1,441 @@
+//! Trading rules combined from conditions on indicators.
+//!
+//! A [Rule](trait.Rule.html) is a condition evaluated bar by bar. Rules are built from
+//! comparisons of indicators with levels, [above](fn.above.html) and
+//! [below](fn.below.html), or any [condition](fn.condition.html) on the bar, and combined
+//! with [and](trait.RuleExt.html#method.and), [or](trait.RuleExt.html#method.or),
+//! [not](trait.RuleExt.html#method.not) and [for_bars](trait.RuleExt.html#method.for_bars), which
+//! requires a rule to hold for a number of consecutive bars. A
+//! [Strategy](struct.Strategy.html) of a buy and a sell rule produces
+//! [signals](../signals/enum.Signal.html).
+//!
+//! All the rules of a combination are evaluated on every bar, without short-circuiting, so
+//! that their indicators see every bar. Comparisons are false until their indicator is
+//! [ready](../trait.Warmup.html).
+//!
+//! # Example
+//!
+//! `FAST_STOCH(14) < 20 AND ROC(9) > 0`:
+//!
+//! ```
+//! use ta::indicators::{FastStochastic, RateOfChange};
+//! use ta::rules::{above, below, Rule, RuleExt};
+//! use ta::{DataItem, Warmup};
+//!
+//! let mut rule = below(FastStochastic::new(14).unwrap(), 20.0)
+//!     .and(above(RateOfChange::new(9).unwrap(), 0.0));
+//!
+//! let bar = DataItem::builder()
+//!     .open(10.0)
+//!     .high(11.0)
+//!     .low(9.0)
+//!     .close(10.5)
+//!     .volume(100.0)
+//!     .build()
+//!     .unwrap();
+//! // not before the indicators are ready
+//! assert!(!rule.eval(&bar));
+//! assert!(!rule.is_ready());
+//! ```
+
+use crate::signals::{NextSignal, Signal};
+use crate::{Float, Next, Reset, Warmup};
+
+/// Condition evaluated bar by bar.
+pub trait Rule<T> {
+    /// Consumes an input and returns whether the condition holds.
+    fn eval(&mut self, input: &T) -> bool;
+}
+
+/// Combinators of rules.
+///
+/// Kept apart from [Rule](trait.Rule.html), which is generic over the input, so that they
+/// don't need the input type to be known. Implemented for the rules of this module; custom
+/// rules can implement it too or be wrapped in a [condition](fn.condition.html).
+pub trait RuleExt: Sized {
+    /// Holds when both rules hold.
+    fn and<R>(self, other: R) -> And<Self, R> {
+        And(self, other)
+    }
+
+    /// Holds when any of the rules holds.
+    fn or<R>(self, other: R) -> Or<Self, R> {
+        Or(self, other)
+    }
+
+    /// Holds when the rule doesn't.
+    fn not(self) -> Not<Self> {
+        Not(self)
+    }
+
+    /// Holds when the rule held on the last `bars` bars, including the current one.
+    fn for_bars(self, bars: u32) -> Persist<Self> {
+        Persist {
+            rule: self,
+            bars,
+            streak: 0,
+        }
+    }
+}
+
+/// Output of an indicator above a level, see [above](fn.above.html).
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Above<I, F = f64> {
+    indicator: I,
+    level: F,
+}
+
+/// Output of an indicator below a level, see [below](fn.below.html).
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Below<I, F = f64> {
+    indicator: I,
+    level: F,
+}
+
+/// Holds while the output of `indicator` is greater than `level`.
+pub fn above<I, F>(indicator: I, level: F) -> Above<I, F> {
+    Above { indicator, level }
+}
+
+/// Holds while the output of `indicator` is less than `level`.
+pub fn below<I, F>(indicator: I, level: F) -> Below<I, F> {
+    Below { indicator, level }
+}
+
+impl<I, F, T> Rule<T> for Above<I, F>
+where
+    I: Next<T, Output = F> + Warmup,
+    F: Float,
+{
+    fn eval(&mut self, input: &T) -> bool {
+        let value = self.indicator.next(input);
+        self.indicator.is_ready() && value > self.level
+    }
+}
+
+impl<I, F, T> Rule<T> for Below<I, F>
+where
+    I: Next<T, Output = F> + Warmup,
+    F: Float,
+{
+    fn eval(&mut self, input: &T) -> bool {
+        let value = self.indicator.next(input);
+        self.indicator.is_ready() && value < self.level
+    }
+}
+
+impl<I: Warmup, F> Warmup for Above<I, F> {
+    fn warmup_period(&self) -> u32 {
+        self.indicator.warmup_period()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.indicator.is_ready()
+    }
+}
+
+impl<I: Warmup, F> Warmup for Below<I, F> {
+    fn warmup_period(&self) -> u32 {
+        self.indicator.warmup_period()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.indicator.is_ready()
+    }
+}
+
+impl<I: Reset, F> Reset for Above<I, F> {
+    fn reset(&mut self) {
+        self.indicator.reset();
+    }
+}
+
+impl<I: Reset, F> Reset for Below<I, F> {
+    fn reset(&mut self) {
+        self.indicator.reset();
+    }
+}
+
+/// Rule of a closure, see [condition](fn.condition.html).
+#[derive(Debug, Clone)]
+pub struct Condition<C>(C);
+
+/// Holds when `condition` returns `true` for the bar, e.g. `condition(|bar: &DataItem|
+/// bar.close() > bar.open())`.
+pub fn condition<C>(condition: C) -> Condition<C> {
+    Condition(condition)
+}
+
+impl<C: FnMut(&T) -> bool, T> Rule<T> for Condition<C> {
+    fn eval(&mut self, input: &T) -> bool {
+        (self.0)(input)
+    }
+}
+
+/// Both rules, see [RuleExt::and](trait.RuleExt.html#method.and).
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct And<A, B>(A, B);
+
+impl<A: Rule<T>, B: Rule<T>, T> Rule<T> for And<A, B> {
+    fn eval(&mut self, input: &T) -> bool {
+        let first = self.0.eval(input);
+        let second = self.1.eval(input);
+        first && second
+    }
+}
+
+/// Any of the rules, see [RuleExt::or](trait.RuleExt.html#method.or).
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Or<A, B>(A, B);
+
+impl<A: Rule<T>, B: Rule<T>, T> Rule<T> for Or<A, B> {
+    fn eval(&mut self, input: &T) -> bool {
+        let first = self.0.eval(input);
+        let second = self.1.eval(input);
+        first || second
+    }
+}
+
+/// Negated rule, see [RuleExt::not](trait.RuleExt.html#method.not).
+///
+/// It is true while the rule is not ready, check [is_ready](../trait.Warmup.html) or use it
+/// in a [Strategy](struct.Strategy.html), which holds until then.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Not<R>(R);
+
+impl<R: Rule<T>, T> Rule<T> for Not<R> {
+    fn eval(&mut self, input: &T) -> bool {
+        !self.0.eval(input)
+    }
+}
+
+/// Rule held for a number of consecutive bars, see
+/// [RuleExt::for_bars](trait.RuleExt.html#method.for_bars).
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Persist<R> {
+    rule: R,
+    bars: u32,
+    streak: u32,
+}
+
+impl<R: Rule<T>, T> Rule<T> for Persist<R> {
+    fn eval(&mut self, input: &T) -> bool {
+        self.streak = if self.rule.eval(input) {
+            self.streak.saturating_add(1)
+        } else {
+            0
+        };
+        self.streak >= self.bars
+    }
+}
+
+impl<C> Warmup for Condition<C> {
+    fn warmup_period(&self) -> u32 {
+        0
+    }
+
+    fn is_ready(&self) -> bool {
+        true
+    }
+}
+
+impl<A: Warmup, B: Warmup> Warmup for And<A, B> {
+    fn warmup_period(&self) -> u32 {
+        self.0.warmup_period().max(self.1.warmup_period())
+    }
+
+    fn is_ready(&self) -> bool {
+        self.0.is_ready() && self.1.is_ready()
+    }
+}
+
+impl<A: Warmup, B: Warmup> Warmup for Or<A, B> {
+    fn warmup_period(&self) -> u32 {
+        self.0.warmup_period().max(self.1.warmup_period())
+    }
+
+    fn is_ready(&self) -> bool {
+        self.0.is_ready() && self.1.is_ready()
+    }
+}
+
+impl<R: Warmup> Warmup for Not<R> {
+    fn warmup_period(&self) -> u32 {
+        self.0.warmup_period()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.0.is_ready()
+    }
+}
+
+impl<R: Warmup> Warmup for Persist<R> {
+    fn warmup_period(&self) -> u32 {
+        self.rule.warmup_period()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.rule.is_ready()
+    }
+}
+
+impl<I, F> RuleExt for Above<I, F> {}
+impl<I, F> RuleExt for Below<I, F> {}
+impl<C> RuleExt for Condition<C> {}
+impl<A, B> RuleExt for And<A, B> {}
+impl<A, B> RuleExt for Or<A, B> {}
+impl<R> RuleExt for Not<R> {}
+impl<R> RuleExt for Persist<R> {}
+
+impl<A: Reset, B: Reset> Reset for And<A, B> {
+    fn reset(&mut self) {
+        self.0.reset();
+        self.1.reset();
+    }
+}
+
+impl<A: Reset, B: Reset> Reset for Or<A, B> {
+    fn reset(&mut self) {
+        self.0.reset();
+        self.1.reset();
+    }
+}
+
+impl<R: Reset> Reset for Not<R> {
+    fn reset(&mut self) {
+        self.0.reset();
+    }
+}
+
+impl<R: Reset> Reset for Persist<R> {
+    fn reset(&mut self) {
+        self.rule.reset();
+        self.streak = 0;
+    }
+}
+
+/// Signals of a buy and a sell rule.
+///
+/// Buys while only the buy rule holds and sells while only the sell rule holds, otherwise
+/// holds, also until both rules are ready.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Strategy<B, S> {
+    buy: B,
+    sell: S,
+}
+
+impl<B, S> Strategy<B, S> {
+    pub fn new(buy: B, sell: S) -> Self {
+        Self { buy, sell }
+    }
+}
+
+impl<B: Rule<T> + Warmup, S: Rule<T> + Warmup, T> NextSignal<T> for Strategy<B, S> {
+    fn next_signal(&mut self, input: &T) -> Signal {
+        let buy = self.buy.eval(input);
+        let sell = self.sell.eval(input);
+        if !self.buy.is_ready() || !self.sell.is_ready() {
+            return Signal::Hold;
+        }
+        match (buy, sell) {
+            (true, false) => Signal::Buy,
+            (false, true) => Signal::Sell,
+            _ => Signal::Hold,
+        }
+    }
+}
+
+impl<B: Reset, S: Reset> Reset for Strategy<B, S> {
+    fn reset(&mut self) {
+        self.buy.reset();
+        self.sell.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::SimpleMovingAverage;
+    use crate::test_helper::*;
+    use crate::{Close, Warmup};
+
+    fn close() -> SimpleMovingAverage {
+        SimpleMovingAverage::new(1).unwrap()
+    }
+
+    fn eval<R: Rule<Bar>>(rule: &mut R, closes: &[f64]) -> Vec<bool> {
+        closes
+            .iter()
+            .map(|&close| rule.eval(&Bar::new().close(close)))
+            .collect()
+    }
+
+    #[test]
+    fn test_compare() {
+        let mut rule = above(SimpleMovingAverage::new(2).unwrap(), 10.0);
+        assert_eq!(
+            eval(&mut rule, &[12.0, 12.0, 9.0, 7.0]),
+            vec![false, true, true, false]
+        );
+        assert!(rule.is_ready());
+        rule.reset();
+        assert!(!rule.is_ready());
+
+        let mut rule = below(close(), 10.0);
+        assert_eq!(
+            eval(&mut rule, &[12.0, 10.0, 9.0]),
+            vec![false, false, true]
+        );
+    }
+
+    #[test]
+    fn test_combinators() {
+        let mut rule = above(close(), 10.0).and(below(close(), 20.0));
+        assert_eq!(
+            eval(&mut rule, &[5.0, 15.0, 25.0]),
+            vec![false, true, false]
+        );
+
+        let mut rule = below(close(), 10.0).or(above(close(), 20.0));
+        assert_eq!(eval(&mut rule, &[5.0, 15.0, 25.0]), vec![true, false, true]);
+
+        let mut rule = above(close(), 10.0).not();
+        assert_eq!(eval(&mut rule, &[5.0, 15.0]), vec![true, false]);
+
+        // both sides see every bar
+        let mut rule =
+            condition(|_: &Bar| false).and(above(SimpleMovingAverage::new(2).unwrap(), 10.0));
+        eval(&mut rule, &[20.0, 0.0]);
+        assert!(rule.is_ready());
+    }
+
+    #[test]
+    fn test_for_bars() {
+        let mut rule = condition(|bar: &Bar| bar.close() > 10.0).for_bars(3);
+        assert_eq!(
+            eval(&mut rule, &[11.0, 12.0, 13.0, 14.0, 9.0, 11.0]),
+            vec![false, false, true, true, false, false]
+        );
+    }
+
+    #[test]
+    fn test_strategy() {
+        let buy = below(close(), 20.0).for_bars(2);
+        let sell = above(SimpleMovingAverage::new(3).unwrap(), 50.0);
+        let mut strategy = Strategy::new(buy, sell);
+        use Signal::*;
+        let signals: Vec<Signal> = [10.0, 15.0, 30.0, 120.0, 10.0, 10.0]
+            .iter()
+            .map(|&close| strategy.next_signal(&Bar::new().close(close)))
+            .collect();
+        // held while the average warms up
+        assert_eq!(signals, vec![Hold, Hold, Hold, Sell, Sell, Buy]);
+    }
+}
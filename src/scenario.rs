@@ -0,0 +1,148 @@
+//! What-if evaluation of hypothetical bars.
+//!
+//! A [Scenario](struct.Scenario.html) evaluates an indicator, or a whole
+//! [Pipeline](../pipeline/struct.Pipeline.html), on bars which haven't happened, e.g. "if
+//! the price closes at X, Y or Z, what do my indicators read?", for pre-trade checks and
+//! limit placement. Every evaluation runs on a clone of the live state, which is left
+//! untouched.
+//!
+//! Unlike [Peek](../trait.Peek.html), which only some indicators implement, it works for any
+//! indicator implementing `Clone`, at the cost of cloning its window.
+//!
+//! # Example
+//!
+//! ```
+//! use ta::indicators::SimpleMovingAverage;
+//! use ta::scenario::Scenario;
+//! use ta::{DataItem, Next};
+//!
+//! let bar = |close: f64| {
+//!     DataItem::builder()
+//!         .open(close)
+//!         .high(close)
+//!         .low(close)
+//!         .close(close)
+//!         .volume(1.0)
+//!         .build()
+//!         .unwrap()
+//! };
+//! let mut sma = SimpleMovingAverage::new(2).unwrap();
+//! sma.next(&bar(10.0));
+//!
+//! let forming = bar(11.0);
+//! let outputs = Scenario::new(&sma).closes_at(&forming, &[8.0, 12.0, 14.0]);
+//! assert_eq!(outputs, vec![9.0, 11.0, 12.0]);
+//!
+//! // the live state is unchanged
+//! assert_eq!(sma.next(&bar(12.0)), 11.0);
+//! ```
+
+use alloc::vec::Vec;
+
+use crate::{DataItem, Next, Ohlcv};
+
+/// Hypothetical next bars for a live indicator or pipeline.
+#[derive(Debug, Clone, Copy)]
+pub struct Scenario<'a, S> {
+    state: &'a S,
+}
+
+impl<'a, S: Clone> Scenario<'a, S> {
+    pub fn new(state: &'a S) -> Self {
+        Self { state }
+    }
+
+    /// Output if `input` were the next bar.
+    pub fn evaluate<T>(&self, input: &T) -> S::Output
+    where
+        S: Next<T>,
+    {
+        self.state.clone().next(input)
+    }
+
+    /// Outputs if each of `inputs` were the next bar, as alternatives.
+    pub fn evaluate_each<T>(&self, inputs: &[T]) -> Vec<S::Output>
+    where
+        S: Next<T>,
+    {
+        inputs.iter().map(|input| self.evaluate(input)).collect()
+    }
+
+    /// Outputs if `inputs` were the next bars, one after the other.
+    pub fn evaluate_path<T>(&self, inputs: &[T]) -> Vec<S::Output>
+    where
+        S: Next<T>,
+    {
+        let mut branch = self.state.clone();
+        inputs.iter().map(|input| branch.next(input)).collect()
+    }
+
+    /// Outputs if the forming `bar` closed at each of `closes`, as alternatives.
+    ///
+    /// The high and low of the bar are extended to the close where it lies outside of them.
+    pub fn closes_at<T: Ohlcv>(&self, bar: &T, closes: &[f64]) -> Vec<S::Output>
+    where
+        S: Next<DataItem>,
+    {
+        closes
+            .iter()
+            .map(|&close| {
+                self.evaluate(&DataItem::new(
+                    bar.open(),
+                    bar.high().max(close),
+                    bar.low().min(close),
+                    close,
+                    bar.volume(),
+                ))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{Maximum, SimpleMovingAverage};
+    use crate::pipeline::{IndicatorConfig, Pipeline, PipelineConfig};
+    use crate::registry::Registry;
+    use crate::test_helper::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_evaluate() {
+        let mut sma = SimpleMovingAverage::new(2).unwrap();
+        sma.next(&Bar::new().close(10.0));
+        let scenario = Scenario::new(&sma);
+        assert_eq!(scenario.evaluate(&Bar::new().close(20.0)), 15.0);
+
+        let bars = [Bar::new().close(12.0), Bar::new().close(14.0)];
+        assert_eq!(scenario.evaluate_each(&bars), vec![11.0, 12.0]);
+        assert_eq!(scenario.evaluate_path(&bars), vec![11.0, 13.0]);
+        assert_eq!(sma.next(&Bar::new().close(16.0)), 13.0);
+    }
+
+    #[test]
+    fn test_closes_at() {
+        let max = Maximum::new(3).unwrap();
+        let forming = DataItem::new(10.0, 12.0, 9.0, 11.0, 100.0);
+        let outputs = Scenario::new(&max).closes_at(&forming, &[11.0, 13.0]);
+        assert_eq!(outputs, vec![12.0, 13.0]);
+    }
+
+    #[test]
+    fn test_pipeline() {
+        let config = PipelineConfig {
+            indicators: vec![IndicatorConfig::new("sma", "SMA(2)")],
+            alerts: vec![],
+            signals: vec![],
+        };
+        let mut pipeline = Pipeline::new(&config, &Registry::new()).unwrap();
+        pipeline.next(&DataItem::new(10.0, 10.0, 10.0, 10.0, 1.0));
+
+        let forming = DataItem::new(10.0, 10.0, 10.0, 10.0, 1.0);
+        let outputs = Scenario::new(&pipeline).closes_at(&forming, &[12.0, 20.0]);
+        assert_eq!(outputs[0].values, vec![11.0]);
+        assert_eq!(outputs[1].values, vec![15.0]);
+        assert_eq!(pipeline.value("sma"), Some(10.0));
+    }
+}
@@ -0,0 +1,345 @@
+//! Seasonal return profiles.
+//!
+//! Many markets have recurring patterns: the first trading hour is more volatile, some
+//! weekdays drift up, some months are weak. A profile is built in two steps:
+//!
+//! * [SeasonalityBuilder](struct.SeasonalityBuilder.html) is fitted on history and averages
+//!   the bar-to-bar returns by hour of day, day of week or month,
+//! * the resulting [Seasonality](struct.Seasonality.html) is applied to new bars and returns
+//!   the average historical return of the bucket the bar falls into.
+//!
+//! Bars need a [Timestamp](../trait.Timestamp.html) besides the close price. Buckets are
+//! computed in the local time of a [TimeZone](../sessions/struct.TimeZone.html), so the
+//! profile of a US market does not shift with daylight saving time.
+//!
+//! # Example
+//!
+//! ```
+//! use ta::seasonality::{Period, SeasonalityBuilder};
+//! use ta::sessions::{TimeZone, HOUR};
+//! use ta::{Close, Next, Timestamp};
+//!
+//! struct Bar(i64, f64);
+//!
+//! impl Timestamp for Bar {
+//!     fn timestamp(&self) -> i64 {
+//!         self.0
+//!     }
+//! }
+//!
+//! impl Close for Bar {
+//!     fn close(&self) -> f64 {
+//!         self.1
+//!     }
+//! }
+//!
+//! // hourly bars, prices go up 25% at 01:00 and back down at 02:00
+//! let mut builder = SeasonalityBuilder::new(Period::HourOfDay, TimeZone::UTC);
+//! for day in 0..5 {
+//!     let start = day * 24 * HOUR;
+//!     builder.fit(&Bar(start, 100.0));
+//!     builder.fit(&Bar(start + HOUR, 125.0));
+//!     builder.fit(&Bar(start + 2 * HOUR, 100.0));
+//! }
+//!
+//! let mut seasonality = builder.build();
+//! assert_eq!(seasonality.next(&Bar(30 * 24 * HOUR + HOUR, 0.0)), 0.25);
+//! assert_eq!(seasonality.count(1), 5);
+//! ```
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::sessions::{civil_from_days, TimeZone, Weekday, DAY, HOUR};
+use crate::{Close, Float, Next, Reset, Timestamp, Warmup};
+
+/// Seasonal cycle of a [Seasonality](struct.Seasonality.html) profile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Period {
+    /// 24 buckets, 0 is the hour after midnight.
+    HourOfDay,
+    /// 7 buckets, 0 is Monday.
+    DayOfWeek,
+    /// 12 buckets, 0 is January.
+    Month,
+}
+
+impl Period {
+    /// Number of buckets in the cycle.
+    pub fn buckets(self) -> usize {
+        match self {
+            Period::HourOfDay => 24,
+            Period::DayOfWeek => 7,
+            Period::Month => 12,
+        }
+    }
+
+    /// Bucket of a timestamp in the given time zone.
+    pub fn bucket(self, tz: &TimeZone, timestamp: i64) -> usize {
+        let local = tz.to_local(timestamp);
+        match self {
+            Period::HourOfDay => (local.rem_euclid(DAY) / HOUR) as usize,
+            Period::DayOfWeek => Weekday::from_day(local.div_euclid(DAY)).index() as usize,
+            Period::Month => {
+                let (_, month, _) = civil_from_days(local.div_euclid(DAY));
+                month as usize - 1
+            }
+        }
+    }
+}
+
+impl fmt::Display for Period {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Period::HourOfDay => write!(f, "HOUR"),
+            Period::DayOfWeek => write!(f, "WEEKDAY"),
+            Period::Month => write!(f, "MONTH"),
+        }
+    }
+}
+
+/// Fits a [Seasonality](struct.Seasonality.html) profile on historical bars.
+///
+/// The return of every bar is its close relative to the close of the previous bar and it is
+/// attributed to the bucket of the bar's timestamp.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SeasonalityBuilder<F = f64> {
+    period: Period,
+    tz: TimeZone,
+    prev_close: Option<F>,
+    sums: Vec<F>,
+    counts: Vec<u64>,
+}
+
+impl SeasonalityBuilder {
+    pub fn new(period: Period, tz: TimeZone) -> Self {
+        Self::with_type(period, tz)
+    }
+}
+
+impl<F: Float> SeasonalityBuilder<F> {
+    /// Like [new](#method.new), for values of any float type `F`.
+    pub fn with_type(period: Period, tz: TimeZone) -> Self {
+        Self {
+            period,
+            tz,
+            prev_close: None,
+            sums: vec![F::zero(); period.buckets()],
+            counts: vec![0; period.buckets()],
+        }
+    }
+
+    /// Adds a bar of history.
+    ///
+    /// Bars must be fed in chronological order. Returns with a non-positive previous close
+    /// are skipped.
+    pub fn fit<T: Timestamp + Close<F>>(&mut self, bar: &T) {
+        let close = bar.close();
+        if let Some(prev) = self.prev_close {
+            if prev > F::zero() {
+                let bucket = self.period.bucket(&self.tz, bar.timestamp());
+                self.sums[bucket] += close / prev - F::one();
+                self.counts[bucket] += 1;
+            }
+        }
+        self.prev_close = Some(close);
+    }
+
+    /// Starts a new series, e.g. another symbol, without forgetting the returns seen so far.
+    pub fn gap(&mut self) {
+        self.prev_close = None;
+    }
+
+    /// Returns the profile of the history fitted so far.
+    pub fn build(&self) -> Seasonality<F> {
+        let means = self
+            .sums
+            .iter()
+            .zip(self.counts.iter())
+            .map(|(&sum, &count)| {
+                if count == 0 {
+                    F::nan()
+                } else {
+                    sum / F::cast(count)
+                }
+            })
+            .collect();
+        Seasonality {
+            period: self.period,
+            tz: self.tz,
+            means,
+            counts: self.counts.clone(),
+        }
+    }
+}
+
+/// Average historical return by hour of day, day of week or month.
+///
+/// Created by [SeasonalityBuilder](struct.SeasonalityBuilder.html). As an indicator it
+/// returns the seasonal expectation of every bar, i.e. the average return of its bucket,
+/// or NaN when the history has no returns in the bucket. The profile does not change
+/// while it is applied.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Seasonality<F = f64> {
+    period: Period,
+    tz: TimeZone,
+    means: Vec<F>,
+    counts: Vec<u64>,
+}
+
+impl<F: Float> Seasonality<F> {
+    pub fn period(&self) -> Period {
+        self.period
+    }
+
+    /// Average return of a bucket, NaN without history.
+    pub fn mean(&self, bucket: usize) -> F {
+        self.means[bucket]
+    }
+
+    /// Number of returns averaged in a bucket.
+    pub fn count(&self, bucket: usize) -> u64 {
+        self.counts[bucket]
+    }
+
+    /// Average returns of all buckets.
+    pub fn means(&self) -> &[F] {
+        &self.means
+    }
+
+    /// Seasonal expectation at the given time.
+    pub fn expectation(&self, timestamp: i64) -> F {
+        self.means[self.period.bucket(&self.tz, timestamp)]
+    }
+}
+
+impl<F: Float, T: Timestamp> Next<T> for Seasonality<F> {
+    type Output = F;
+
+    fn next(&mut self, input: &T) -> F {
+        self.expectation(input.timestamp())
+    }
+}
+
+impl<F: Float> Reset for Seasonality<F> {
+    fn reset(&mut self) {}
+}
+
+impl<F: Float> Warmup for Seasonality<F> {
+    fn warmup_period(&self) -> u32 {
+        0
+    }
+
+    fn is_ready(&self) -> bool {
+        true
+    }
+}
+
+impl<F: Float> fmt::Display for Seasonality<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SEASONALITY({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sessions::{days_from_civil, Dst};
+    use crate::test_helper::*;
+
+    fn ts(year: i64, month: u32, day: u32, hour: i64) -> i64 {
+        days_from_civil(year, month, day) * DAY + hour * HOUR
+    }
+
+    #[test]
+    fn test_buckets() {
+        let utc = TimeZone::UTC;
+        // 2020-01-06 was a Monday
+        let monday = ts(2020, 1, 6, 15);
+        assert_eq!(Period::HourOfDay.bucket(&utc, monday), 15);
+        assert_eq!(Period::DayOfWeek.bucket(&utc, monday), 0);
+        assert_eq!(Period::Month.bucket(&utc, monday), 0);
+
+        let sunday = ts(2020, 5, 31, 23);
+        assert_eq!(Period::DayOfWeek.bucket(&utc, sunday), 6);
+        assert_eq!(Period::Month.bucket(&utc, sunday), 4);
+
+        // 01:00 UTC is still Sunday in New York
+        let ny = TimeZone::new_york();
+        let early = ts(2020, 6, 1, 1);
+        assert_eq!(Period::DayOfWeek.bucket(&ny, early), 6);
+        assert_eq!(Period::HourOfDay.bucket(&ny, early), 21);
+        assert_eq!(Period::Month.bucket(&ny, early), 4);
+    }
+
+    #[test]
+    fn test_dst() {
+        let ny = TimeZone::new_york();
+        let mut builder = SeasonalityBuilder::new(Period::HourOfDay, ny);
+        // 09:30 opens in winter and summer, 14:30 and 13:30 UTC
+        for &(month, hour) in &[(1, 14), (7, 13)] {
+            builder.fit(
+                &Bar::new()
+                    .close(100)
+                    .timestamp(ts(2020, month, 6, hour - 1)),
+            );
+            builder.fit(&Bar::new().close(102).timestamp(ts(2020, month, 6, hour)));
+            builder.gap();
+        }
+        let seasonality = builder.build();
+        assert_eq!(seasonality.count(9), 2);
+        assert_eq!(round(seasonality.mean(9)), 0.02);
+        assert!(seasonality.mean(10).is_nan());
+
+        let sydney = TimeZone::new(600, Dst::Australia).unwrap();
+        let builder = SeasonalityBuilder::new(Period::Month, sydney);
+        assert_eq!(builder.build().means().len(), 12);
+    }
+
+    #[test]
+    fn test_next() {
+        let mut builder = SeasonalityBuilder::new(Period::DayOfWeek, TimeZone::UTC);
+        let closes = [100.0, 110.0, 99.0, 99.0, 103.95, 103.95, 103.95];
+        for week in 0..2 {
+            for (day, &close) in closes.iter().enumerate() {
+                let timestamp = ts(2020, 1, 6 + week * 7 + day as u32, 0);
+                builder.fit(&Bar::new().close(close).timestamp(timestamp));
+            }
+        }
+        let mut seasonality = builder.build();
+
+        // the first Monday has no previous close
+        assert_eq!(seasonality.count(0), 1);
+        assert_eq!(round(seasonality.mean(0)), -0.038);
+        assert_eq!(seasonality.count(1), 2);
+
+        let tuesday = Bar::new().timestamp(ts(2021, 3, 2, 12));
+        assert_eq!(round(seasonality.next(&tuesday)), 0.1);
+        let wednesday = Bar::new().timestamp(ts(2021, 3, 3, 12));
+        assert_eq!(round(seasonality.next(&wednesday)), -0.1);
+        assert_eq!(round(seasonality.expectation(ts(2021, 3, 5, 0))), 0.05);
+        assert_eq!(seasonality.expectation(ts(2021, 3, 6, 0)), 0.0);
+        assert!(seasonality.is_ready());
+    }
+
+    #[test]
+    fn test_skips_non_positive() {
+        let mut builder = SeasonalityBuilder::new(Period::HourOfDay, TimeZone::UTC);
+        builder.fit(&Bar::new().close(0).timestamp(0));
+        builder.fit(&Bar::new().close(5).timestamp(HOUR));
+        builder.fit(&Bar::new().close(10).timestamp(2 * HOUR));
+        let seasonality = builder.build();
+        assert_eq!(seasonality.count(1), 0);
+        assert_eq!(seasonality.mean(2), 1.0);
+    }
+
+    #[test]
+    fn test_display() {
+        let seasonality = SeasonalityBuilder::new(Period::Month, TimeZone::UTC).build();
+        assert_eq!(format!("{}", seasonality), "SEASONALITY(MONTH)");
+    }
+}
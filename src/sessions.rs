@@ -0,0 +1,693 @@
+//! Trading session definitions.
+//!
+//! Timestamps are milliseconds since the Unix epoch (UTC). A [Session](struct.Session.html)
+//! describes the daily trading hours of a market in its local [TimeZone](struct.TimeZone.html),
+//! so that everything that depends on session boundaries (resampling, session resets,
+//! anchored indicators) agrees on where a trading day starts and ends.
+//!
+//! # Example
+//!
+//! ```
+//! use ta::sessions::Session;
+//!
+//! let rth = Session::us_rth();
+//!
+//! // 2020-01-06 14:30 UTC is 09:30 in New York (EST)
+//! assert!(rth.is_open(1_578_321_000_000));
+//! // 2020-07-06 14:30 UTC is 10:30 in New York (EDT)
+//! assert!(rth.is_open(1_594_045_800_000));
+//! // 2020-07-06 20:30 UTC is 16:30 in New York, after the close
+//! assert!(!rth.is_open(1_594_067_400_000));
+//! ```
+
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+
+use crate::errors::*;
+
+pub const SECOND: i64 = 1_000;
+pub const MINUTE: i64 = 60 * SECOND;
+pub const HOUR: i64 = 60 * MINUTE;
+pub const DAY: i64 = 24 * HOUR;
+
+/// Day of the week.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl Weekday {
+    const ALL: [Weekday; 7] = [
+        Weekday::Monday,
+        Weekday::Tuesday,
+        Weekday::Wednesday,
+        Weekday::Thursday,
+        Weekday::Friday,
+        Weekday::Saturday,
+        Weekday::Sunday,
+    ];
+
+    /// Weekday of a day number (days since 1970-01-01, which was a Thursday).
+    pub fn from_day(day: i64) -> Self {
+        Self::ALL[(day + 3).rem_euclid(7) as usize]
+    }
+
+    /// Number of days since Monday (Monday is 0, Sunday is 6).
+    pub fn index(self) -> u32 {
+        self as u32
+    }
+
+    fn mask(self) -> u8 {
+        1 << self.index()
+    }
+}
+
+/// Daylight saving time rule of a time zone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Dst {
+    /// No daylight saving time.
+    None,
+    /// United States: second Sunday of March to first Sunday of November, 02:00 local.
+    UnitedStates,
+    /// European Union: last Sunday of March to last Sunday of October, 01:00 UTC.
+    Europe,
+    /// South-eastern Australia: first Sunday of October to first Sunday of April, 02:00
+    /// standard time.
+    Australia,
+}
+
+/// Time zone given by its standard UTC offset and daylight saving rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TimeZone {
+    offset: i64,
+    dst: Dst,
+}
+
+impl TimeZone {
+    pub const UTC: TimeZone = TimeZone {
+        offset: 0,
+        dst: Dst::None,
+    };
+
+    /// Creates a time zone with standard offset from UTC in minutes.
+    pub fn new(offset_minutes: i32, dst: Dst) -> Result<Self> {
+        if offset_minutes.abs() >= 24 * 60 {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        Ok(Self {
+            offset: offset_minutes as i64 * MINUTE,
+            dst,
+        })
+    }
+
+    /// America/New_York.
+    pub fn new_york() -> Self {
+        Self {
+            offset: -5 * HOUR,
+            dst: Dst::UnitedStates,
+        }
+    }
+
+    /// America/Chicago.
+    pub fn chicago() -> Self {
+        Self {
+            offset: -6 * HOUR,
+            dst: Dst::UnitedStates,
+        }
+    }
+
+    /// Europe/London.
+    pub fn london() -> Self {
+        Self {
+            offset: 0,
+            dst: Dst::Europe,
+        }
+    }
+
+    /// Asia/Tokyo.
+    pub fn tokyo() -> Self {
+        Self {
+            offset: 9 * HOUR,
+            dst: Dst::None,
+        }
+    }
+
+    /// Australia/Sydney.
+    pub fn sydney() -> Self {
+        Self {
+            offset: 10 * HOUR,
+            dst: Dst::Australia,
+        }
+    }
+
+    /// Offset from UTC in milliseconds at the given instant.
+    pub fn utc_offset(&self, timestamp: i64) -> i64 {
+        if self.is_dst(timestamp) {
+            self.offset + HOUR
+        } else {
+            self.offset
+        }
+    }
+
+    /// Converts a UTC timestamp to local wall-clock milliseconds.
+    pub fn to_local(&self, timestamp: i64) -> i64 {
+        timestamp + self.utc_offset(timestamp)
+    }
+
+    /// Local day number (days since 1970-01-01 in this time zone).
+    pub fn day(&self, timestamp: i64) -> i64 {
+        self.to_local(timestamp).div_euclid(DAY)
+    }
+
+    fn is_dst(&self, timestamp: i64) -> bool {
+        let (year, _, _) = civil_from_days((timestamp + self.offset).div_euclid(DAY));
+        let std_time = |day: i64, hour: i64| day * DAY + hour * HOUR - self.offset;
+
+        match self.dst {
+            Dst::None => false,
+            Dst::UnitedStates => {
+                let start = std_time(nth_sunday(year, 3, 2), 2);
+                // 02:00 daylight time is 01:00 standard time
+                let end = std_time(nth_sunday(year, 11, 1), 1);
+                timestamp >= start && timestamp < end
+            }
+            Dst::Europe => {
+                let start = last_sunday(year, 3) * DAY + HOUR;
+                let end = last_sunday(year, 10) * DAY + HOUR;
+                timestamp >= start && timestamp < end
+            }
+            Dst::Australia => {
+                let end = std_time(nth_sunday(year, 4, 1), 2);
+                let start = std_time(nth_sunday(year, 10, 1), 2);
+                timestamp < end || timestamp >= start
+            }
+        }
+    }
+}
+
+impl Default for TimeZone {
+    fn default() -> Self {
+        Self::UTC
+    }
+}
+
+/// Daily trading hours in a local time zone.
+///
+/// A session opens at `open` and closes at `close`, both given in minutes after local
+/// midnight. When `close` is earlier than `open` the session spans midnight, and when they
+/// are equal the session lasts the whole day. Trading days are filtered by the weekday on
+/// which a session opens.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "SessionFields"))]
+pub struct Session {
+    tz: TimeZone,
+    open: u32,
+    close: u32,
+    days: u8,
+}
+
+// Fields of a deserialized session, validated like the constructor and `days`.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct SessionFields {
+    tz: TimeZone,
+    open: u32,
+    close: u32,
+    days: u8,
+}
+
+#[cfg(feature = "serde")]
+impl core::convert::TryFrom<SessionFields> for Session {
+    type Error = Error;
+
+    fn try_from(fields: SessionFields) -> Result<Self> {
+        let session = Self::new(fields.tz, fields.open, fields.close)?;
+        Ok(Self {
+            days: weekday_mask(fields.days)?,
+            ..session
+        })
+    }
+}
+
+// Rejects an empty set of weekdays, which would never trade.
+fn weekday_mask(mask: u8) -> Result<u8> {
+    if mask & Session::ALL_DAYS == 0 {
+        return Err(Error::from_kind(ErrorKind::InvalidParameter));
+    }
+    Ok(mask & Session::ALL_DAYS)
+}
+
+impl Session {
+    pub fn new(tz: TimeZone, open: u32, close: u32) -> Result<Self> {
+        if open >= 24 * 60 || close >= 24 * 60 {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        Ok(Self {
+            tz,
+            open,
+            close,
+            days: Self::WEEKDAYS,
+        })
+    }
+
+    const WEEKDAYS: u8 = 0b001_1111;
+    const ALL_DAYS: u8 = 0b111_1111;
+
+    /// Sets the weekdays on which the session opens. Default is Monday to Friday.
+    ///
+    /// Fails with `InvalidParameter` if `days` is empty.
+    pub fn days(mut self, days: &[Weekday]) -> Result<Self> {
+        self.days = weekday_mask(days.iter().fold(0, |mask, day| mask | day.mask()))?;
+        Ok(self)
+    }
+
+    /// US equities regular trading hours, 09:30-16:00 New York time.
+    pub fn us_rth() -> Self {
+        Self::new(TimeZone::new_york(), 9 * 60 + 30, 16 * 60).unwrap()
+    }
+
+    /// US equities extended trading hours including pre- and post-market, 04:00-20:00
+    /// New York time.
+    pub fn us_eth() -> Self {
+        Self::new(TimeZone::new_york(), 4 * 60, 20 * 60).unwrap()
+    }
+
+    /// Sydney forex session, 07:00-16:00 Sydney time.
+    pub fn forex_sydney() -> Self {
+        Self::new(TimeZone::sydney(), 7 * 60, 16 * 60).unwrap()
+    }
+
+    /// Tokyo forex session, 09:00-18:00 Tokyo time.
+    pub fn forex_tokyo() -> Self {
+        Self::new(TimeZone::tokyo(), 9 * 60, 18 * 60).unwrap()
+    }
+
+    /// London forex session, 08:00-17:00 London time.
+    pub fn forex_london() -> Self {
+        Self::new(TimeZone::london(), 8 * 60, 17 * 60).unwrap()
+    }
+
+    /// New York forex session, 08:00-17:00 New York time.
+    pub fn forex_new_york() -> Self {
+        Self::new(TimeZone::new_york(), 8 * 60, 17 * 60).unwrap()
+    }
+
+    /// Round-the-clock market with day boundaries at midnight in the given time zone.
+    pub fn crypto(tz: TimeZone) -> Self {
+        Self {
+            tz,
+            open: 0,
+            close: 0,
+            days: Self::ALL_DAYS,
+        }
+    }
+
+    pub fn time_zone(&self) -> TimeZone {
+        self.tz
+    }
+
+    /// Returns `true` if the market is open at the given instant.
+    pub fn is_open(&self, timestamp: i64) -> bool {
+        self.session_start(timestamp).is_some()
+    }
+
+    /// Returns the opening time (UTC) of the session containing the given instant, or
+    /// `None` if the market is closed.
+    pub fn session_start(&self, timestamp: i64) -> Option<i64> {
+        let offset = self.tz.utc_offset(timestamp);
+        let local = timestamp + offset;
+        let day = local.div_euclid(DAY);
+        let minute = (local.rem_euclid(DAY) / MINUTE) as u32;
+
+        let open_day = if self.open == self.close {
+            Some(day)
+        } else if self.open < self.close {
+            if minute >= self.open && minute < self.close {
+                Some(day)
+            } else {
+                None
+            }
+        } else if minute >= self.open {
+            Some(day)
+        } else if minute < self.close {
+            Some(day - 1)
+        } else {
+            None
+        };
+
+        open_day
+            .filter(|&day| self.days & Weekday::from_day(day).mask() != 0)
+            .map(|day| day * DAY + self.open as i64 * MINUTE - offset)
+    }
+
+    /// Returns `true` if the two instants belong to different sessions.
+    ///
+    /// Instants outside of trading hours do not belong to any session, so moving from
+    /// closed to open market always starts a new session.
+    pub fn is_new_session(&self, prev: i64, timestamp: i64) -> bool {
+        match (self.session_start(prev), self.session_start(timestamp)) {
+            (Some(a), Some(b)) => a != b,
+            (_, Some(_)) => true,
+            (_, None) => false,
+        }
+    }
+}
+
+/// Calendar of trading days: weekends plus a set of exchange holidays.
+///
+/// # Example
+///
+/// ```
+/// use ta::sessions::{Calendar, TimeZone};
+///
+/// let calendar = Calendar::new(TimeZone::new_york()).holiday(2020, 7, 3);
+///
+/// // 2020-07-02 21:00 UTC, Thursday
+/// assert!(calendar.is_trading_day(calendar.day(1_593_723_600_000)));
+/// // 2020-07-03 21:00 UTC, Independence Day observed
+/// assert!(!calendar.is_trading_day(calendar.day(1_593_810_000_000)));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "CalendarFields"))]
+pub struct Calendar {
+    tz: TimeZone,
+    days: u8,
+    holidays: BTreeSet<i64>,
+}
+
+// Fields of a deserialized calendar, validated like `days`.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct CalendarFields {
+    tz: TimeZone,
+    days: u8,
+    holidays: BTreeSet<i64>,
+}
+
+#[cfg(feature = "serde")]
+impl core::convert::TryFrom<CalendarFields> for Calendar {
+    type Error = Error;
+
+    fn try_from(fields: CalendarFields) -> Result<Self> {
+        Ok(Self {
+            tz: fields.tz,
+            days: weekday_mask(fields.days)?,
+            holidays: fields.holidays,
+        })
+    }
+}
+
+impl Calendar {
+    /// Creates a calendar trading Monday to Friday without holidays.
+    pub fn new(tz: TimeZone) -> Self {
+        Self {
+            tz,
+            days: Session::WEEKDAYS,
+            holidays: BTreeSet::new(),
+        }
+    }
+
+    /// Sets the weekdays on which the market trades. Default is Monday to Friday.
+    ///
+    /// Fails with `InvalidParameter` if `days` is empty.
+    pub fn days(mut self, days: &[Weekday]) -> Result<Self> {
+        self.days = weekday_mask(days.iter().fold(0, |mask, day| mask | day.mask()))?;
+        Ok(self)
+    }
+
+    /// Adds a holiday given as a local date.
+    pub fn holiday(mut self, year: i64, month: u32, day: u32) -> Self {
+        self.holidays.insert(days_from_civil(year, month, day));
+        self
+    }
+
+    pub fn time_zone(&self) -> TimeZone {
+        self.tz
+    }
+
+    /// Local day number of a timestamp.
+    pub fn day(&self, timestamp: i64) -> i64 {
+        self.tz.day(timestamp)
+    }
+
+    /// Returns `true` if the market trades on the given local day number.
+    pub fn is_trading_day(&self, day: i64) -> bool {
+        self.days & Weekday::from_day(day).mask() != 0 && !self.holidays.contains(&day)
+    }
+
+    /// Returns the first trading day after the given local day number.
+    pub fn next_trading_day(&self, day: i64) -> i64 {
+        let mut next = day + 1;
+        while !self.is_trading_day(next) {
+            next += 1;
+        }
+        next
+    }
+
+    /// Trading days strictly between two local day numbers.
+    ///
+    /// Walks every day of the range, see
+    /// [count_trading_days_between](#method.count_trading_days_between) to check the size of
+    /// a range first.
+    pub fn trading_days_between(&self, from: i64, to: i64) -> Vec<i64> {
+        ((from + 1)..to)
+            .filter(|&day| self.is_trading_day(day))
+            .collect()
+    }
+
+    /// Number of trading days strictly between two local day numbers, without walking the
+    /// range.
+    pub fn count_trading_days_between(&self, from: i64, to: i64) -> u64 {
+        if to - from <= 1 {
+            return 0;
+        }
+        let start = from + 1;
+        let length = to - start;
+        let mut count = (length / 7) as u64 * self.days.count_ones() as u64;
+        for day in (to - length % 7)..to {
+            if self.days & Weekday::from_day(day).mask() != 0 {
+                count += 1;
+            }
+        }
+        let holidays = self
+            .holidays
+            .range(start..to)
+            .filter(|&&day| self.days & Weekday::from_day(day).mask() != 0)
+            .count();
+        count - holidays as u64
+    }
+}
+
+/// Day number of a proleptic Gregorian date (days since 1970-01-01).
+pub(crate) fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let month = month as i64;
+    let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Proleptic Gregorian date `(year, month, day)` of a day number.
+pub(crate) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+fn nth_sunday(year: i64, month: u32, n: i64) -> i64 {
+    let first = days_from_civil(year, month, 1);
+    let first_sunday = first + (6 - Weekday::from_day(first).index() as i64);
+    first_sunday + (n - 1) * 7
+}
+
+fn last_sunday(year: i64, month: u32) -> i64 {
+    let last = if month == 12 {
+        days_from_civil(year + 1, 1, 1) - 1
+    } else {
+        days_from_civil(year, month + 1, 1) - 1
+    };
+    last - (Weekday::from_day(last).index() as i64 + 1) % 7
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ts(year: i64, month: u32, day: u32, hour: i64, minute: i64) -> i64 {
+        days_from_civil(year, month, day) * DAY + hour * HOUR + minute * MINUTE
+    }
+
+    #[test]
+    fn test_civil() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11_017);
+        assert_eq!(civil_from_days(11_017), (2000, 3, 1));
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+        assert_eq!(Weekday::from_day(0), Weekday::Thursday);
+        assert_eq!(Weekday::from_day(-1), Weekday::Wednesday);
+    }
+
+    #[test]
+    fn test_dst_transitions() {
+        let ny = TimeZone::new_york();
+        // DST 2020 started on March 8 and ended on November 1
+        assert_eq!(ny.utc_offset(ts(2020, 3, 8, 6, 59)), -5 * HOUR);
+        assert_eq!(ny.utc_offset(ts(2020, 3, 8, 7, 0)), -4 * HOUR);
+        assert_eq!(ny.utc_offset(ts(2020, 11, 1, 5, 59)), -4 * HOUR);
+        assert_eq!(ny.utc_offset(ts(2020, 11, 1, 6, 0)), -5 * HOUR);
+
+        let london = TimeZone::london();
+        assert_eq!(london.utc_offset(ts(2020, 3, 29, 0, 59)), 0);
+        assert_eq!(london.utc_offset(ts(2020, 3, 29, 1, 0)), HOUR);
+        assert_eq!(london.utc_offset(ts(2020, 10, 25, 1, 0)), 0);
+
+        let sydney = TimeZone::sydney();
+        assert_eq!(sydney.utc_offset(ts(2020, 1, 15, 0, 0)), 11 * HOUR);
+        assert_eq!(sydney.utc_offset(ts(2020, 7, 15, 0, 0)), 10 * HOUR);
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(Session::new(TimeZone::UTC, 0, 24 * 60).is_err());
+        assert!(Session::new(TimeZone::UTC, 9 * 60, 17 * 60).is_ok());
+        assert!(TimeZone::new(24 * 60, Dst::None).is_err());
+        assert!(TimeZone::new(-3 * 60, Dst::None).is_ok());
+    }
+
+    #[test]
+    fn test_us_rth() {
+        let rth = Session::us_rth();
+        // Monday 2020-01-06, EST
+        assert!(!rth.is_open(ts(2020, 1, 6, 14, 29)));
+        assert!(rth.is_open(ts(2020, 1, 6, 14, 30)));
+        assert!(rth.is_open(ts(2020, 1, 6, 20, 59)));
+        assert!(!rth.is_open(ts(2020, 1, 6, 21, 0)));
+        // Saturday
+        assert!(!rth.is_open(ts(2020, 1, 4, 15, 0)));
+        assert_eq!(
+            rth.session_start(ts(2020, 7, 6, 15, 0)),
+            Some(ts(2020, 7, 6, 13, 30))
+        );
+    }
+
+    #[test]
+    fn test_overnight_session() {
+        // CME-style session, Sunday to Thursday 17:00 - 16:00 Chicago time
+        let session = Session::new(TimeZone::chicago(), 17 * 60, 16 * 60)
+            .unwrap()
+            .days(&[
+                Weekday::Sunday,
+                Weekday::Monday,
+                Weekday::Tuesday,
+                Weekday::Wednesday,
+                Weekday::Thursday,
+            ])
+            .unwrap();
+        let sunday_open = ts(2020, 1, 5, 23, 0);
+        assert_eq!(session.session_start(sunday_open), Some(sunday_open));
+        assert_eq!(
+            session.session_start(ts(2020, 1, 6, 15, 0)),
+            Some(sunday_open)
+        );
+        assert!(!session.is_open(ts(2020, 1, 6, 22, 30)));
+        // Friday evening is closed
+        assert!(!session.is_open(ts(2020, 1, 10, 23, 30)));
+    }
+
+    #[test]
+    fn test_crypto() {
+        let utc = Session::crypto(TimeZone::UTC);
+        assert!(utc.is_open(ts(2020, 1, 4, 12, 0)));
+        assert!(!utc.is_new_session(ts(2020, 1, 4, 0, 0), ts(2020, 1, 4, 23, 59)));
+        assert!(utc.is_new_session(ts(2020, 1, 4, 23, 59), ts(2020, 1, 5, 0, 0)));
+
+        let tokyo = Session::crypto(TimeZone::tokyo());
+        assert!(tokyo.is_new_session(ts(2020, 1, 4, 14, 59), ts(2020, 1, 4, 15, 0)));
+    }
+
+    #[test]
+    fn test_days() {
+        assert!(Session::us_rth().days(&[]).is_err());
+        assert!(Session::us_rth().days(&[Weekday::Saturday]).is_ok());
+        assert!(Calendar::new(TimeZone::UTC).days(&[]).is_err());
+
+        let calendar = Calendar::new(TimeZone::UTC)
+            .days(&[Weekday::Sunday])
+            .unwrap();
+        // Thursday 2020-01-02 is followed by Sunday 2020-01-05
+        let thursday = days_from_civil(2020, 1, 2);
+        assert_eq!(calendar.next_trading_day(thursday), thursday + 3);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let session = Session::us_rth();
+        let json = serde_json::to_value(&session).unwrap();
+        assert_eq!(
+            serde_json::from_value::<Session>(json.clone()).unwrap(),
+            session
+        );
+        let mut empty = json.clone();
+        empty["days"] = 0.into();
+        assert!(serde_json::from_value::<Session>(empty).is_err());
+        let mut open = json;
+        open["open"] = (24 * 60).into();
+        assert!(serde_json::from_value::<Session>(open).is_err());
+
+        let calendar = Calendar::new(TimeZone::UTC).holiday(2020, 1, 1);
+        let json = serde_json::to_value(&calendar).unwrap();
+        assert_eq!(
+            serde_json::from_value::<Calendar>(json.clone()).unwrap(),
+            calendar
+        );
+        let mut empty = json;
+        empty["days"] = 0.into();
+        assert!(serde_json::from_value::<Calendar>(empty).is_err());
+    }
+
+    #[test]
+    fn test_calendar() {
+        let calendar = Calendar::new(TimeZone::UTC).holiday(2020, 1, 1);
+        let new_year = days_from_civil(2020, 1, 1);
+
+        assert!(!calendar.is_trading_day(new_year));
+        assert!(calendar.is_trading_day(new_year + 1));
+        // Friday 2020-01-03 is followed by Monday 2020-01-06
+        assert_eq!(calendar.next_trading_day(new_year + 2), new_year + 5);
+        assert_eq!(
+            calendar.trading_days_between(new_year - 1, new_year + 5),
+            vec![new_year + 1, new_year + 2]
+        );
+
+        for from in (new_year - 10)..(new_year + 10) {
+            for to in from..(new_year + 30) {
+                assert_eq!(
+                    calendar.count_trading_days_between(from, to),
+                    calendar.trading_days_between(from, to).len() as u64
+                );
+            }
+        }
+        assert_eq!(calendar.count_trading_days_between(0, 7 * 1000 + 1), 5000);
+    }
+}
@@ -0,0 +1,387 @@
+//! Trading signals of indicators.
+//!
+//! Indicators produce numbers, a bot acts on a [Signal](enum.Signal.html): buy, sell or
+//! hold. The types here wrap indicators and implement
+//! [NextSignal](trait.NextSignal.html), turning their outputs into signals with the usual
+//! rules:
+//!
+//! * [Oscillator](struct.Oscillator.html) - buy when an oscillator rises out of the oversold
+//!   zone, sell when it falls out of the overbought zone, e.g. RSI with 30 and 70,
+//! * [MaCrossover](struct.MaCrossover.html) - buy when a fast moving average crosses above a
+//!   slow one, sell when it crosses below,
+//! * [BandBreakout](struct.BandBreakout.html) - buy when the close breaks out above the upper
+//!   band, e.g. of [Bollinger Bands](../indicators/struct.BollingerBands.html), sell when it
+//!   breaks down below the lower one.
+//!
+//! Signals are held until the wrapped indicators are [ready](../trait.Warmup.html), and
+//! only crosses after that count. Every signal is also a
+//! [Vote](../consensus/enum.Vote.html) of a [Consensus](../consensus/struct.Consensus.html).
+//!
+//! # Example
+//!
+//! ```
+//! use ta::indicators::SimpleMovingAverage;
+//! use ta::signals::{MaCrossover, NextSignal, Signal};
+//! use ta::DataItem;
+//!
+//! let bar = |close: f64| {
+//!     DataItem::builder()
+//!         .open(close)
+//!         .high(close)
+//!         .low(close)
+//!         .close(close)
+//!         .volume(1.0)
+//!         .build()
+//!         .unwrap()
+//! };
+//!
+//! let fast = SimpleMovingAverage::new(2).unwrap();
+//! let slow = SimpleMovingAverage::new(3).unwrap();
+//! let mut crossover = MaCrossover::new(fast, slow);
+//! let signals: Vec<Signal> = [10.0, 9.0, 8.0, 9.0, 11.0]
+//!     .iter()
+//!     .map(|&close| crossover.next_signal(&bar(close)))
+//!     .collect();
+//! assert_eq!(signals.last(), Some(&Signal::Buy));
+//! assert!(signals[..4].iter().all(|&signal| signal == Signal::Hold));
+//! ```
+
+use crate::consensus::Vote;
+use crate::errors::*;
+use crate::flips::{Cross, Debounce, Flip};
+use crate::indicators::{BollingerBandsOutput, ExponentialBollingerOutput};
+use crate::{Close, Float, Next, Reset, Warmup};
+
+/// Action suggested by a signal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Signal {
+    Buy,
+    Sell,
+    Hold,
+}
+
+impl From<Flip> for Signal {
+    fn from(flip: Flip) -> Self {
+        match flip {
+            Flip::Bullish => Signal::Buy,
+            Flip::Bearish => Signal::Sell,
+        }
+    }
+}
+
+impl From<Option<Flip>> for Signal {
+    fn from(flip: Option<Flip>) -> Self {
+        flip.map_or(Signal::Hold, Signal::from)
+    }
+}
+
+impl From<Signal> for Vote {
+    fn from(signal: Signal) -> Self {
+        match signal {
+            Signal::Buy => Vote::Bullish,
+            Signal::Sell => Vote::Bearish,
+            Signal::Hold => Vote::Neutral,
+        }
+    }
+}
+
+/// Consumes an input and returns a [Signal](enum.Signal.html).
+pub trait NextSignal<T> {
+    fn next_signal(&mut self, input: &T) -> Signal;
+}
+
+/// Output with an upper and a lower band, e.g. of
+/// [BollingerBands](../indicators/struct.BollingerBands.html).
+pub trait Bands<F = f64> {
+    fn upper(&self) -> F;
+    fn lower(&self) -> F;
+}
+
+impl<F: Copy> Bands<F> for BollingerBandsOutput<F> {
+    fn upper(&self) -> F {
+        self.upper
+    }
+
+    fn lower(&self) -> F {
+        self.lower
+    }
+}
+
+impl<F: Copy> Bands<F> for ExponentialBollingerOutput<F> {
+    fn upper(&self) -> F {
+        self.upper
+    }
+
+    fn lower(&self) -> F {
+        self.lower
+    }
+}
+
+/// Overbought/oversold signals of an oscillator.
+///
+/// Buys when the oscillator crosses above the _oversold_ level, i.e. leaves the oversold
+/// zone, and sells when it crosses below the _overbought_ level.
+///
+/// # Parameters
+///
+/// * _indicator_ - oscillator, e.g. [RSI](../indicators/struct.RelativeStrengthIndex.html)
+/// * _oversold_ - lower level
+/// * _overbought_ - upper level (greater than _oversold_)
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::RelativeStrengthIndex;
+/// use ta::signals::Oscillator;
+///
+/// let rsi = RelativeStrengthIndex::new(14).unwrap();
+/// let signals = Oscillator::new(rsi, 30.0, 70.0).unwrap();
+/// assert_eq!(signals.levels(), (30.0, 70.0));
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Oscillator<I, F = f64> {
+    indicator: I,
+    oversold: F,
+    overbought: F,
+    oversold_cross: Cross<F>,
+    overbought_cross: Cross<F>,
+}
+
+impl<I, F: Float> Oscillator<I, F> {
+    pub fn new(indicator: I, oversold: F, overbought: F) -> Result<Self> {
+        if oversold.is_nan() || overbought.is_nan() || oversold >= overbought {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        Ok(Self {
+            indicator,
+            oversold,
+            overbought,
+            oversold_cross: Cross::with_type(Debounce::default()).unwrap(),
+            overbought_cross: Cross::with_type(Debounce::default()).unwrap(),
+        })
+    }
+
+    /// The oversold and overbought levels.
+    pub fn levels(&self) -> (F, F) {
+        (self.oversold, self.overbought)
+    }
+
+    pub fn indicator(&self) -> &I {
+        &self.indicator
+    }
+}
+
+impl<I, F, T> NextSignal<T> for Oscillator<I, F>
+where
+    I: Next<T, Output = F> + Warmup,
+    F: Float,
+{
+    fn next_signal(&mut self, input: &T) -> Signal {
+        let value = self.indicator.next(input);
+        if !self.indicator.is_ready() {
+            return Signal::Hold;
+        }
+        let oversold = self.oversold_cross.next(value, self.oversold);
+        let overbought = self.overbought_cross.next(value, self.overbought);
+        match (oversold, overbought) {
+            (Some(Flip::Bullish), _) => Signal::Buy,
+            (_, Some(Flip::Bearish)) => Signal::Sell,
+            _ => Signal::Hold,
+        }
+    }
+}
+
+impl<I: Reset, F> Reset for Oscillator<I, F> {
+    fn reset(&mut self) {
+        self.indicator.reset();
+        self.oversold_cross.reset();
+        self.overbought_cross.reset();
+    }
+}
+
+/// Crossover signals of a fast and a slow moving average.
+///
+/// Buys when the fast average crosses above the slow one and sells when it crosses below.
+/// Any two indicators with the same output work, e.g. an average and the close through
+/// `SimpleMovingAverage::new(1)`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MaCrossover<A, B, F = f64> {
+    fast: A,
+    slow: B,
+    cross: Cross<F>,
+}
+
+impl<A, B, F: Float> MaCrossover<A, B, F> {
+    pub fn new(fast: A, slow: B) -> Self {
+        Self {
+            fast,
+            slow,
+            cross: Cross::with_type(Debounce::default()).unwrap(),
+        }
+    }
+
+    /// Like [new](#method.new), with short-lived crosses filtered out.
+    pub fn debounced(fast: A, slow: B, debounce: Debounce<F>) -> Result<Self> {
+        Ok(Self {
+            fast,
+            slow,
+            cross: Cross::with_type(debounce)?,
+        })
+    }
+
+    pub fn fast(&self) -> &A {
+        &self.fast
+    }
+
+    pub fn slow(&self) -> &B {
+        &self.slow
+    }
+}
+
+impl<A, B, F, T> NextSignal<T> for MaCrossover<A, B, F>
+where
+    A: Next<T, Output = F> + Warmup,
+    B: Next<T, Output = F> + Warmup,
+    F: Float,
+{
+    fn next_signal(&mut self, input: &T) -> Signal {
+        let fast = self.fast.next(input);
+        let slow = self.slow.next(input);
+        if !self.fast.is_ready() || !self.slow.is_ready() {
+            return Signal::Hold;
+        }
+        self.cross.next(fast, slow).into()
+    }
+}
+
+impl<A: Reset, B: Reset, F> Reset for MaCrossover<A, B, F> {
+    fn reset(&mut self) {
+        self.fast.reset();
+        self.slow.reset();
+        self.cross.reset();
+    }
+}
+
+/// Breakout signals of the close and a band indicator.
+///
+/// Buys when the close crosses above the upper band and sells when it crosses below the
+/// lower band.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BandBreakout<I, F = f64> {
+    indicator: I,
+    upper_cross: Cross<F>,
+    lower_cross: Cross<F>,
+}
+
+impl<I, F: Float> BandBreakout<I, F> {
+    pub fn new(indicator: I) -> Self {
+        Self {
+            indicator,
+            upper_cross: Cross::with_type(Debounce::default()).unwrap(),
+            lower_cross: Cross::with_type(Debounce::default()).unwrap(),
+        }
+    }
+
+    pub fn indicator(&self) -> &I {
+        &self.indicator
+    }
+}
+
+impl<I, F, T> NextSignal<T> for BandBreakout<I, F>
+where
+    I: Next<T> + Warmup,
+    I::Output: Bands<F>,
+    F: Float,
+    T: Close<F>,
+{
+    fn next_signal(&mut self, input: &T) -> Signal {
+        let bands = self.indicator.next(input);
+        if !self.indicator.is_ready() {
+            return Signal::Hold;
+        }
+        let close = input.close();
+        let upper = self.upper_cross.next(close, bands.upper());
+        let lower = self.lower_cross.next(close, bands.lower());
+        match (upper, lower) {
+            (Some(Flip::Bullish), _) => Signal::Buy,
+            (_, Some(Flip::Bearish)) => Signal::Sell,
+            _ => Signal::Hold,
+        }
+    }
+}
+
+impl<I: Reset, F> Reset for BandBreakout<I, F> {
+    fn reset(&mut self) {
+        self.indicator.reset();
+        self.upper_cross.reset();
+        self.lower_cross.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{BollingerBands, SimpleMovingAverage};
+    use crate::test_helper::*;
+
+    fn signals<S: NextSignal<Bar>>(signal: &mut S, closes: &[f64]) -> Vec<Signal> {
+        closes
+            .iter()
+            .map(|&close| signal.next_signal(&Bar::new().close(close)))
+            .collect()
+    }
+
+    #[test]
+    fn test_oscillator() {
+        assert!(Oscillator::new(SimpleMovingAverage::new(1).unwrap(), 70.0, 30.0).is_err());
+
+        // the close itself as the oscillator
+        let close = SimpleMovingAverage::new(1).unwrap();
+        let mut oscillator = Oscillator::new(close, 30.0, 70.0).unwrap();
+        use Signal::*;
+        assert_eq!(
+            signals(&mut oscillator, &[50.0, 25.0, 35.0, 40.0, 75.0, 65.0, 60.0]),
+            vec![Hold, Hold, Buy, Hold, Hold, Sell, Hold]
+        );
+
+        oscillator.reset();
+        // starting oversold, the first rise out of it counts
+        assert_eq!(signals(&mut oscillator, &[20.0, 31.0]), vec![Hold, Buy]);
+    }
+
+    #[test]
+    fn test_ma_crossover() {
+        let fast = SimpleMovingAverage::new(1).unwrap();
+        let slow = SimpleMovingAverage::new(3).unwrap();
+        let mut crossover = MaCrossover::new(fast, slow);
+        use Signal::*;
+        // held while the slow average warms up
+        assert_eq!(
+            signals(&mut crossover, &[10.0, 12.0, 11.0, 9.0, 13.0, 14.0, 8.0]),
+            vec![Hold, Hold, Hold, Hold, Buy, Hold, Sell]
+        );
+    }
+
+    #[test]
+    fn test_band_breakout() {
+        let bb = BollingerBands::new(3, 1.0).unwrap();
+        let mut breakout = BandBreakout::new(bb);
+        use Signal::*;
+        assert_eq!(
+            signals(&mut breakout, &[10.0, 11.0, 10.0, 11.0, 14.0, 14.0, 8.0]),
+            vec![Hold, Hold, Hold, Hold, Buy, Hold, Sell]
+        );
+    }
+
+    #[test]
+    fn test_vote() {
+        assert_eq!(Vote::from(Signal::Buy), Vote::Bullish);
+        assert_eq!(Vote::from(Signal::Hold), Vote::Neutral);
+        assert_eq!(Signal::from(Some(Flip::Bearish)), Signal::Sell);
+        assert_eq!(Signal::from(None), Signal::Hold);
+    }
+}
@@ -0,0 +1,379 @@
+//! Vectorized batch kernels for large historical series.
+//!
+//! Available with the `simd` feature. Each kernel writes into `output` the values the
+//! corresponding indicator would return for every item of `input`, as if fed with
+//! [calc_slice](../trait.Calculate.html#method.calc_slice) on a freshly created indicator:
+//!
+//! * [sum](fn.sum.html) - sum of the last _length_ values
+//! * [sma](fn.sma.html) - [SimpleMovingAverage](../indicators/struct.SimpleMovingAverage.html)
+//! * [minimum](fn.minimum.html) - [Minimum](../indicators/struct.Minimum.html)
+//! * [maximum](fn.maximum.html) - [Maximum](../indicators/struct.Maximum.html)
+//! * [roc](fn.roc.html) - [RateOfChange](../indicators/struct.RateOfChange.html)
+//!
+//! Rolling windows are computed with the van Herk/Gil-Werman scheme: the input is split into
+//! blocks of _length_ values, prefix and suffix scans are taken within every block and each
+//! window is then the combination of one suffix and one prefix, which is done four lanes at
+//! a time. The cost per value does not depend on the window length.
+//!
+//! Sums and averages are not accumulated the same way as in the streaming indicators, so
+//! they can differ from them by floating point rounding. They also do not drift on long
+//! series, since no value is ever subtracted from the sum.
+//!
+//! # Example
+//!
+//! ```
+//! use ta::indicators::SimpleMovingAverage;
+//! use ta::{simd, Calculate};
+//!
+//! let input = [1.0, 3.0, 5.0, 9.0, 2.0];
+//!
+//! let mut output = [0.0; 5];
+//! simd::sma(&input, 2, &mut output).unwrap();
+//! assert_eq!(output, [1.0, 2.0, 4.0, 7.0, 5.5]);
+//!
+//! let mut streaming = [0.0; 5];
+//! SimpleMovingAverage::new(2).unwrap().calc_slice(&input, &mut streaming);
+//! assert_eq!(output, streaming);
+//! ```
+use core::convert::TryInto;
+
+use wide::f64x4;
+
+use crate::errors::*;
+
+const LANES: usize = 4;
+
+/// Rolling sum of the last `length` values.
+///
+/// # Panics
+///
+/// Panics if `input` and `output` have different lengths.
+pub fn sum(input: &[f64], length: u32, output: &mut [f64]) -> Result<()> {
+    let n = window(input, length, output)?;
+    rolling(input, n, output, |a, b| a + b, |a, b| a + b);
+    Ok(())
+}
+
+/// Rolling [simple moving average](../indicators/struct.SimpleMovingAverage.html).
+///
+/// # Panics
+///
+/// Panics if `input` and `output` have different lengths.
+pub fn sma(input: &[f64], length: u32, output: &mut [f64]) -> Result<()> {
+    sum(input, length, output)?;
+
+    let n = length as usize;
+    let warmup = (n - 1).min(output.len());
+    for (i, out) in output[..warmup].iter_mut().enumerate() {
+        *out /= (i + 1) as f64;
+    }
+    map(
+        &mut output[warmup..],
+        |sum| sum / n as f64,
+        |sum| sum / f64x4::splat(n as f64),
+    );
+    Ok(())
+}
+
+/// Rolling [minimum](../indicators/struct.Minimum.html).
+///
+/// # Panics
+///
+/// Panics if `input` and `output` have different lengths.
+pub fn minimum(input: &[f64], length: u32, output: &mut [f64]) -> Result<()> {
+    let n = window(input, length, output)?;
+    rolling(input, n, output, f64::min, f64x4::min);
+    Ok(())
+}
+
+/// Rolling [maximum](../indicators/struct.Maximum.html).
+///
+/// # Panics
+///
+/// Panics if `input` and `output` have different lengths.
+pub fn maximum(input: &[f64], length: u32, output: &mut [f64]) -> Result<()> {
+    let n = window(input, length, output)?;
+    rolling(input, n, output, f64::max, f64x4::max);
+    Ok(())
+}
+
+/// [Rate of change](../indicators/struct.RateOfChange.html) in percent over `length`
+/// periods.
+///
+/// # Panics
+///
+/// Panics if `input` and `output` have different lengths.
+pub fn roc(input: &[f64], length: u32, output: &mut [f64]) -> Result<()> {
+    let n = window(input, length, output)?;
+    if input.is_empty() {
+        return Ok(());
+    }
+
+    // until the window is filled the change is measured from the first value
+    let warmup = (n + 1).min(input.len());
+    let first = input[0];
+    for (value, out) in input[..warmup].iter().zip(output.iter_mut()) {
+        *out = (value - first) / first * 100.0;
+    }
+    if warmup == input.len() {
+        return Ok(());
+    }
+
+    let hundred = f64x4::splat(100.0);
+    let current = &input[n..];
+    let previous = &input[..input.len() - n];
+    let output = &mut output[n..];
+    let split = current.len() / LANES * LANES;
+    for ((cur, prev), out) in current[..split]
+        .chunks_exact(LANES)
+        .zip(previous[..split].chunks_exact(LANES))
+        .zip(output[..split].chunks_exact_mut(LANES))
+    {
+        let (cur, prev) = (load(cur), load(prev));
+        out.copy_from_slice(&((cur - prev) / prev * hundred).to_array());
+    }
+    for ((cur, prev), out) in current[split..]
+        .iter()
+        .zip(previous[split..].iter())
+        .zip(output[split..].iter_mut())
+    {
+        *out = (cur - prev) / prev * 100.0;
+    }
+    Ok(())
+}
+
+fn window(input: &[f64], length: u32, output: &[f64]) -> Result<usize> {
+    assert_eq!(
+        input.len(),
+        output.len(),
+        "input and output slices have different lengths"
+    );
+    match length {
+        0 => Err(Error::from_kind(ErrorKind::InvalidParameter)),
+        _ => Ok(length as usize),
+    }
+}
+
+/// Applies an associative operation over every window of `n` values, windows which are not
+/// filled yet cover all values seen so far.
+///
+/// Scans within a block are sequential, so they run on four blocks at a time, one per lane.
+fn rolling<S, V>(input: &[f64], n: usize, output: &mut [f64], scalar: S, vector: V)
+where
+    S: Fn(f64, f64) -> f64,
+    V: Fn(f64x4, f64x4) -> f64x4,
+{
+    let len = input.len();
+    let group = n * LANES;
+
+    // prefix scans are written straight into the output
+    let grouped = len / group * group;
+    for start in (0..grouped).step_by(group) {
+        let mut acc = gather(input, start, n);
+        scatter(output, start, n, acc);
+        for j in 1..n {
+            acc = vector(acc, gather(input, start + j, n));
+            scatter(output, start + j, n, acc);
+        }
+    }
+    for (block, out) in input[grouped..]
+        .chunks(n)
+        .zip(output[grouped..].chunks_mut(n))
+    {
+        let mut acc = block[0];
+        out[0] = acc;
+        for (value, out) in block[1..].iter().zip(out[1..].iter_mut()) {
+            acc = scalar(acc, *value);
+            *out = acc;
+        }
+    }
+
+    // windows ending in the next block start in this one, so they combine a suffix of this
+    // block with a prefix of the next; windows aligned with a block are its prefix already
+    let mut suffix = vec![0.0; group];
+    let mut start = 0;
+    while start + group < len {
+        let mut acc = gather(input, start + n - 1, n);
+        scatter(&mut suffix, n - 1, n, acc);
+        for j in (1..n - 1).rev() {
+            acc = vector(gather(input, start + j, n), acc);
+            scatter(&mut suffix, j, n, acc);
+        }
+        for (k, suffix) in suffix.chunks(n).enumerate() {
+            combine(input, start + k * n, n, suffix, output, &scalar, &vector);
+        }
+        start += group;
+    }
+    while start + n < len {
+        let block = &input[start..start + n];
+        let mut acc = block[n - 1];
+        suffix[n - 1] = acc;
+        for j in (1..n - 1).rev() {
+            acc = scalar(block[j], acc);
+            suffix[j] = acc;
+        }
+        combine(input, start, n, &suffix[..n], output, &scalar, &vector);
+        start += n;
+    }
+}
+
+/// Combines suffixes of the block at `start` with prefixes of the block following it.
+fn combine<S, V>(
+    input: &[f64],
+    start: usize,
+    n: usize,
+    suffix: &[f64],
+    output: &mut [f64],
+    scalar: &S,
+    vector: &V,
+) where
+    S: Fn(f64, f64) -> f64,
+    V: Fn(f64x4, f64x4) -> f64x4,
+{
+    let end = start + n;
+    let count = (n - 1).min(input.len() - end);
+    let suffix = &suffix[1..=count];
+    let output = &mut output[end..end + count];
+    let split = count / LANES * LANES;
+    for (suf, out) in suffix[..split]
+        .chunks_exact(LANES)
+        .zip(output[..split].chunks_exact_mut(LANES))
+    {
+        let combined = vector(load(suf), load(out));
+        out.copy_from_slice(&combined.to_array());
+    }
+    for (suf, out) in suffix[split..].iter().zip(output[split..].iter_mut()) {
+        *out = scalar(*suf, *out);
+    }
+}
+
+/// Loads values at `index` of four consecutive blocks of `n` values.
+fn gather(values: &[f64], index: usize, n: usize) -> f64x4 {
+    f64x4::from([
+        values[index],
+        values[index + n],
+        values[index + 2 * n],
+        values[index + 3 * n],
+    ])
+}
+
+/// Stores lanes at `index` of four consecutive blocks of `n` values.
+fn scatter(values: &mut [f64], index: usize, n: usize, lanes: f64x4) {
+    for (k, lane) in lanes.to_array().iter().enumerate() {
+        values[index + k * n] = *lane;
+    }
+}
+
+fn map<S, V>(values: &mut [f64], scalar: S, vector: V)
+where
+    S: Fn(f64) -> f64,
+    V: Fn(f64x4) -> f64x4,
+{
+    let split = values.len() / LANES * LANES;
+    for chunk in values[..split].chunks_exact_mut(LANES) {
+        let mapped = vector(load(chunk));
+        chunk.copy_from_slice(&mapped.to_array());
+    }
+    for value in values[split..].iter_mut() {
+        *value = scalar(*value);
+    }
+}
+
+fn load(values: &[f64]) -> f64x4 {
+    let values: [f64; LANES] = values.try_into().unwrap();
+    f64x4::from(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{Maximum, Minimum, RateOfChange, SimpleMovingAverage};
+    use crate::Calculate;
+
+    fn series(len: usize) -> Vec<f64> {
+        (0..len)
+            .map(|i| 100.0 + (i as f64 * 0.7).sin() * 10.0 + (i % 7) as f64)
+            .collect()
+    }
+
+    fn check<K, I>(kernel: K, indicator: I, exact: bool)
+    where
+        K: Fn(&[f64], u32, &mut [f64]) -> Result<()>,
+        I: Fn(u32) -> Box<dyn Calculate<f64, Output = f64>>,
+    {
+        for &len in &[0, 1, 3, 4, 9, 50, 103] {
+            for &length in &[1, 2, 3, 4, 5, 8, 13, 120] {
+                let input = series(len);
+                let mut output = vec![0.0; len];
+                kernel(&input, length, &mut output).unwrap();
+
+                let mut expected = vec![0.0; len];
+                let mut streaming = indicator(length);
+                for (value, out) in input.iter().zip(expected.iter_mut()) {
+                    *out = streaming.calc(*value);
+                }
+
+                for (actual, expected) in output.iter().zip(expected.iter()) {
+                    if exact {
+                        assert_eq!(actual, expected, "len {}, length {}", len, length);
+                    } else {
+                        assert!(
+                            (actual - expected).abs() < 1e-9,
+                            "len {}, length {}: {} != {}",
+                            len,
+                            length,
+                            actual,
+                            expected
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_invalid_length() {
+        assert!(sum(&[1.0], 0, &mut [0.0]).is_err());
+        assert!(sma(&[1.0], 0, &mut [0.0]).is_err());
+        assert!(minimum(&[1.0], 0, &mut [0.0]).is_err());
+        assert!(maximum(&[1.0], 0, &mut [0.0]).is_err());
+        assert!(roc(&[1.0], 0, &mut [0.0]).is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_length_mismatch() {
+        sma(&[1.0, 2.0], 2, &mut [0.0]).unwrap();
+    }
+
+    #[test]
+    fn test_sum() {
+        let mut output = [0.0; 6];
+        sum(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], 3, &mut output).unwrap();
+        assert_eq!(output, [1.0, 3.0, 6.0, 9.0, 12.0, 15.0]);
+    }
+
+    #[test]
+    fn test_sma() {
+        check(
+            sma,
+            |n| Box::new(SimpleMovingAverage::new(n).unwrap()),
+            false,
+        );
+    }
+
+    #[test]
+    fn test_minimum() {
+        check(minimum, |n| Box::new(Minimum::new(n).unwrap()), true);
+    }
+
+    #[test]
+    fn test_maximum() {
+        check(maximum, |n| Box::new(Maximum::new(n).unwrap()), true);
+    }
+
+    #[test]
+    fn test_roc() {
+        check(roc, |n| Box::new(RateOfChange::new(n).unwrap()), true);
+    }
+}
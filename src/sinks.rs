@@ -0,0 +1,331 @@
+//! Destinations for computed records.
+//!
+//! A [Sink](trait.Sink.html) receives records such as indicator outputs or
+//! [alerts](../alerts/struct.Alert.html), so that they can be wired to other systems without
+//! glue code:
+//!
+//! * `Vec` collects the records,
+//! * [Callback](struct.Callback.html) calls a closure with every record,
+//! * `std::sync::mpsc` senders forward them to another thread (requires `std`),
+//! * [Lines](struct.Lines.html) writes their `Display` form line by line to any
+//!   `std::io::Write` (requires `std`),
+//! * [Webhook](struct.Webhook.html) posts them as JSON to an HTTP endpoint (requires the
+//!   `webhook` feature).
+//!
+//! # Example
+//!
+//! ```
+//! use std::sync::mpsc;
+//!
+//! use ta::alerts::{Alerts, Rule, Trigger};
+//!
+//! let mut alerts = Alerts::new();
+//! alerts
+//!     .try_add(Rule::new("above 10", 0, Trigger::CrossesAbove(10.0)))
+//!     .unwrap();
+//!
+//! let (mut sender, receiver) = mpsc::channel();
+//! for &price in &[9.0, 10.5, 9.5, 11.0] {
+//!     alerts.check_into(&[price], &mut sender).unwrap();
+//! }
+//! drop(sender);
+//! assert_eq!(receiver.iter().map(|alert| alert.period).collect::<Vec<_>>(), vec![1, 3]);
+//! ```
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use crate::errors::*;
+
+/// Receiver of records.
+pub trait Sink<T> {
+    /// Delivers a record.
+    fn send(&mut self, record: T) -> Result<()>;
+
+    /// Delivers any records the sink buffers. Default does nothing.
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Delivers all records, stops at the first failure.
+    fn send_all<I: IntoIterator<Item = T>>(&mut self, records: I) -> Result<()>
+    where
+        Self: Sized,
+    {
+        for record in records {
+            self.send(record)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T> Sink<T> for Vec<T> {
+    fn send(&mut self, record: T) -> Result<()> {
+        self.push(record);
+        Ok(())
+    }
+}
+
+impl<T, S: Sink<T> + ?Sized> Sink<T> for &mut S {
+    fn send(&mut self, record: T) -> Result<()> {
+        (**self).send(record)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        (**self).flush()
+    }
+}
+
+impl<T, S: Sink<T> + ?Sized> Sink<T> for Box<S> {
+    fn send(&mut self, record: T) -> Result<()> {
+        (**self).send(record)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        (**self).flush()
+    }
+}
+
+/// Sink calling a closure with every record.
+///
+/// ```
+/// use ta::sinks::{Callback, Sink};
+///
+/// let mut total = 0.0;
+/// Callback(|value: f64| total += value).send_all(vec![1.0, 2.5]).unwrap();
+/// assert_eq!(total, 3.5);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Callback<Func>(pub Func);
+
+impl<T, Func: FnMut(T)> Sink<T> for Callback<Func> {
+    fn send(&mut self, record: T) -> Result<()> {
+        (self.0)(record);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> Sink<T> for std::sync::mpsc::Sender<T> {
+    fn send(&mut self, record: T) -> Result<()> {
+        std::sync::mpsc::Sender::send(self, record).map_err(|_| disconnected())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> Sink<T> for std::sync::mpsc::SyncSender<T> {
+    fn send(&mut self, record: T) -> Result<()> {
+        std::sync::mpsc::SyncSender::send(self, record).map_err(|_| disconnected())
+    }
+}
+
+#[cfg(feature = "std")]
+fn disconnected() -> Error {
+    Error::from_kind(ErrorKind::SinkFailed("channel disconnected".into()))
+}
+
+#[cfg(feature = "std")]
+fn failed<E: ToString>(error: E) -> Error {
+    Error::from_kind(ErrorKind::SinkFailed(error.to_string()))
+}
+
+/// Sink writing the `Display` form of every record as a line.
+///
+/// ```
+/// use ta::sinks::{Lines, Sink};
+///
+/// let mut lines = Lines::new(Vec::new());
+/// lines.send_all(vec![1.5, 2.0]).unwrap();
+/// assert_eq!(lines.into_inner(), b"1.5\n2\n");
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct Lines<W> {
+    writer: W,
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> Lines<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: core::fmt::Display, W: std::io::Write> Sink<T> for Lines<W> {
+    fn send(&mut self, record: T) -> Result<()> {
+        writeln!(self.writer, "{}", record).map_err(failed)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.writer.flush().map_err(failed)
+    }
+}
+
+/// Sink posting every record as a JSON body to an HTTP endpoint.
+///
+/// Requests are sent synchronously, a request failing or answered with an error status
+/// fails the `send`. Only plain HTTP is supported unless the `tls` feature of `ureq` is
+/// enabled by the application.
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use ta::sinks::{Sink, Webhook};
+///
+/// let mut webhook = Webhook::new("http://localhost:8080/alerts")
+///     .header("Authorization", "Bearer token")
+///     .timeout(Duration::from_secs(5));
+/// webhook.send(42.0).unwrap();
+/// ```
+#[cfg(feature = "webhook")]
+#[derive(Debug)]
+pub struct Webhook {
+    agent: ureq::Agent,
+    url: alloc::string::String,
+    headers: Vec<(alloc::string::String, alloc::string::String)>,
+}
+
+#[cfg(feature = "webhook")]
+impl Webhook {
+    /// Creates a webhook with a timeout of 10 seconds.
+    pub fn new<S: Into<alloc::string::String>>(url: S) -> Self {
+        Self {
+            agent: ureq::Agent::new(),
+            url: url.into(),
+            headers: Vec::new(),
+        }
+        .timeout(std::time::Duration::from_secs(10))
+    }
+
+    /// Timeout of a whole request.
+    pub fn timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.agent = ureq::AgentBuilder::new().timeout(timeout).build();
+        self
+    }
+
+    /// Adds a header sent with every request.
+    pub fn header<N: Into<alloc::string::String>, V: Into<alloc::string::String>>(
+        mut self,
+        name: N,
+        value: V,
+    ) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+#[cfg(feature = "webhook")]
+impl<T: serde::Serialize> Sink<T> for Webhook {
+    fn send(&mut self, record: T) -> Result<()> {
+        let body = serde_json::to_string(&record).map_err(failed)?;
+        let mut request = self
+            .agent
+            .post(&self.url)
+            .set("Content-Type", "application/json");
+        for (name, value) in &self.headers {
+            request = request.set(name, value);
+        }
+        request.send_string(&body).map_err(failed)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vec() {
+        let mut records = Vec::new();
+        records.send(1).unwrap();
+        records.send_all(vec![2, 3]).unwrap();
+        assert_eq!(records, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_dyn() {
+        let mut count = 0;
+        {
+            let mut sinks: Vec<Box<dyn Sink<u32> + '_>> =
+                vec![Box::new(Vec::new()), Box::new(Callback(|_| count += 1))];
+            for sink in sinks.iter_mut() {
+                sink.send(7).unwrap();
+                sink.flush().unwrap();
+            }
+        }
+        assert_eq!(count, 1);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_channel() {
+        let (mut sender, receiver) = std::sync::mpsc::sync_channel(2);
+        Sink::send(&mut sender, 1.5).unwrap();
+        assert_eq!(receiver.recv().unwrap(), 1.5);
+
+        drop(receiver);
+        let error = Sink::send(&mut sender, 2.0).unwrap_err();
+        assert_eq!(error.to_string(), "sink failed: channel disconnected");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_lines() {
+        let mut lines = Lines::new(Vec::new());
+        lines.send("a").unwrap();
+        lines.send(2.5).unwrap();
+        Sink::<f64>::flush(&mut lines).unwrap();
+        assert_eq!(String::from_utf8(lines.into_inner()).unwrap(), "a\n2.5\n");
+    }
+
+    #[cfg(feature = "webhook")]
+    #[test]
+    fn test_webhook() {
+        use std::io::{BufRead, BufReader, Read, Write};
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut head = Vec::new();
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" {
+                    break;
+                }
+                if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                    length = value.trim().parse().unwrap();
+                }
+                head.push(line);
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            reader
+                .get_mut()
+                .write_all(b"HTTP/1.1 204 No Content\r\nConnection: close\r\n\r\n")
+                .unwrap();
+            (head, String::from_utf8(body).unwrap())
+        });
+
+        let mut webhook = Webhook::new(url).header("X-Token", "secret");
+        webhook.send(vec![1.5, 2.0]).unwrap();
+
+        let (head, body) = server.join().unwrap();
+        assert!(head[0].starts_with("POST /hook "));
+        assert!(head.iter().any(|line| line.starts_with("X-Token: secret")));
+        assert_eq!(body, "[1.5,2.0]");
+    }
+}
@@ -0,0 +1,145 @@
+//! Compact binary snapshots of indicator state.
+//!
+//! Every indicator serializes with serde (with the `serde` feature), which is enough to
+//! checkpoint it as JSON. Bots checkpointing many indicators every few seconds pay for the
+//! text encoding of every float in the windows, though, so this module, enabled by the
+//! `snapshot` feature, encodes the same state with [bincode](https://docs.rs/bincode):
+//! floats take their 8 bytes, lengths and integers are variable-length encoded and there are
+//! no field names. E.g. a `SimpleMovingAverage` of 200 periods takes about 1.6 KB instead
+//! of about 3.6 KB as JSON.
+//!
+//! The encoding is not self-describing: a snapshot can only be read back into the type it
+//! was written from, by the same version of the crate.
+//!
+//! # Size limits
+//!
+//! Snapshots are written and read with a limit on their size, and fail with `SnapshotFailed`
+//! beyond it. Decoding never allocates more than the snapshot can hold, so a corrupted or
+//! hostile snapshot, e.g. one claiming a window of billions of values, fails instead of
+//! exhausting the memory. [to_bytes](fn.to_bytes.html) and [from_bytes](fn.from_bytes.html)
+//! use [DEFAULT_LIMIT](constant.DEFAULT_LIMIT.html) of 1 MiB, which fits windows of about
+//! 130 000 values; larger states need the `_with_limit` variants.
+//!
+//! # Example
+//!
+//! ```
+//! use ta::indicators::SimpleMovingAverage;
+//! use ta::{snapshot, Calculate};
+//!
+//! let mut sma = SimpleMovingAverage::new(3).unwrap();
+//! sma.calc(10.0);
+//! sma.calc(11.0);
+//!
+//! let bytes = snapshot::to_bytes(&sma).unwrap();
+//! let mut restored: SimpleMovingAverage = snapshot::from_bytes(&bytes).unwrap();
+//! assert_eq!(restored.calc(12.0), sma.calc(12.0));
+//! ```
+
+use bincode::Options;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::errors::*;
+
+/// Limit in bytes of [to_bytes](fn.to_bytes.html) and [from_bytes](fn.from_bytes.html),
+/// 1 MiB.
+pub const DEFAULT_LIMIT: u64 = 1 << 20;
+
+fn failed<E: ToString>(error: E) -> Error {
+    Error::from_kind(ErrorKind::SnapshotFailed(error.to_string()))
+}
+
+fn options(limit: u64) -> impl Options {
+    bincode::DefaultOptions::new().with_limit(limit)
+}
+
+/// Encodes `state`, failing if it takes more than [DEFAULT_LIMIT](constant.DEFAULT_LIMIT.html).
+pub fn to_bytes<T: Serialize>(state: &T) -> Result<Vec<u8>> {
+    to_bytes_with_limit(state, DEFAULT_LIMIT)
+}
+
+/// Encodes `state`, failing if it takes more than `limit` bytes.
+pub fn to_bytes_with_limit<T: Serialize>(state: &T, limit: u64) -> Result<Vec<u8>> {
+    options(limit).serialize(state).map_err(failed)
+}
+
+/// Decodes a snapshot written by [to_bytes](fn.to_bytes.html), failing if it is malformed,
+/// has trailing bytes or is longer than [DEFAULT_LIMIT](constant.DEFAULT_LIMIT.html).
+pub fn from_bytes<T: DeserializeOwned>(bytes: &[u8]) -> Result<T> {
+    from_bytes_with_limit(bytes, DEFAULT_LIMIT)
+}
+
+/// Decodes a snapshot, failing if it is longer than `limit` bytes.
+pub fn from_bytes_with_limit<T: DeserializeOwned>(bytes: &[u8], limit: u64) -> Result<T> {
+    if bytes.len() as u64 > limit {
+        return Err(failed("snapshot exceeds the size limit"));
+    }
+    options(limit).deserialize(bytes).map_err(failed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{ExponentialMovingAverage, Maximum, SimpleMovingAverage};
+    use crate::{Calculate, Close, DataItem};
+
+    #[test]
+    fn test_round_trip() {
+        let mut sma = SimpleMovingAverage::new(4).unwrap();
+        let mut max = Maximum::new(3).unwrap();
+        let mut ema = ExponentialMovingAverage::new(5).unwrap();
+        for &value in &[3.0, 7.5, 1.25, 4.0, 9.0] {
+            sma.calc(value);
+            max.calc(value);
+            ema.calc(value);
+        }
+
+        let mut restored: SimpleMovingAverage = from_bytes(&to_bytes(&sma).unwrap()).unwrap();
+        assert_eq!(restored.calc(2.0), sma.calc(2.0));
+        let mut restored: Maximum = from_bytes(&to_bytes(&max).unwrap()).unwrap();
+        assert_eq!(restored.calc(2.0), max.calc(2.0));
+        let mut restored: ExponentialMovingAverage = from_bytes(&to_bytes(&ema).unwrap()).unwrap();
+        assert_eq!(restored.calc(2.0), ema.calc(2.0));
+
+        let item = DataItem::new(10.0, 12.0, 9.0, 11.0, 100.0);
+        let restored: DataItem = from_bytes(&to_bytes(&item).unwrap()).unwrap();
+        assert_eq!(restored.close(), item.close());
+    }
+
+    #[test]
+    fn test_size() {
+        let mut sma = SimpleMovingAverage::new(200).unwrap();
+        for i in 0..200 {
+            sma.calc(100.0 + (i as f64).sqrt());
+        }
+        let bytes = to_bytes(&sma).unwrap();
+        let json = serde_json::to_string(&sma).unwrap();
+        assert!(bytes.len() < 1700);
+        assert!(bytes.len() * 2 < json.len());
+    }
+
+    #[test]
+    fn test_limit() {
+        let mut sma = SimpleMovingAverage::new(100).unwrap();
+        for i in 0..100 {
+            sma.calc(i as f64);
+        }
+        let bytes = to_bytes(&sma).unwrap();
+        assert!(to_bytes_with_limit(&sma, 100).is_err());
+        assert!(from_bytes_with_limit::<SimpleMovingAverage>(&bytes, 100).is_err());
+
+        // a window claiming u64::MAX values
+        let mut corrupted = bytes[..3].to_vec();
+        corrupted.extend_from_slice(&[0xfd; 9]);
+        assert!(from_bytes::<SimpleMovingAverage>(&corrupted).is_err());
+
+        let mut trailing = bytes;
+        trailing.push(0);
+        assert!(matches!(
+            from_bytes::<SimpleMovingAverage>(&trailing)
+                .unwrap_err()
+                .kind(),
+            ErrorKind::SnapshotFailed(_)
+        ));
+    }
+}
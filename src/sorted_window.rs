@@ -0,0 +1,135 @@
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use crate::{Float, RingBuffer};
+
+/// Window of the latest values which also keeps them in ascending order, for order
+/// statistics such as rolling quantiles.
+///
+/// Pushing a value inserts it into the sorted values and removes the evicted one, both
+/// found by binary search, so it costs O(log n) comparisons and O(n) moves, which is
+/// cheap for the window sizes of indicators.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct SortedWindow<F> {
+    window: RingBuffer<F>,
+    sorted: Vec<F>,
+}
+
+// NaN compares equal to everything, which keeps the binary searches from panicking
+fn compare<F: Float>(a: &F, b: &F) -> Ordering {
+    a.partial_cmp(b).unwrap_or(Ordering::Equal)
+}
+
+impl<F: Float> SortedWindow<F> {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            window: RingBuffer::new(capacity),
+            sorted: Vec::with_capacity(capacity),
+        }
+    }
+
+    pub(crate) fn is_full(&self) -> bool {
+        self.window.is_full()
+    }
+
+    pub(crate) fn push(&mut self, value: F) {
+        if let Some(evicted) = self.window.push(value) {
+            let index = match self.sorted.binary_search_by(|x| compare(x, &evicted)) {
+                Ok(index) => index,
+                // only NaN values can be missed
+                Err(_) => self
+                    .sorted
+                    .iter()
+                    .position(|x| x.is_nan())
+                    .unwrap_or(self.sorted.len() - 1),
+            };
+            self.sorted.remove(index);
+        }
+        let index = self
+            .sorted
+            .partition_point(|x| compare(x, &value) != Ordering::Greater);
+        self.sorted.insert(index, value);
+    }
+
+    /// Quantile `q` (0 to 1) of the values, linearly interpolated between the closest ranks.
+    /// Returns 0 if the window is empty.
+    pub(crate) fn quantile(&self, q: F) -> F {
+        let n = self.sorted.len();
+        if n == 0 {
+            return F::zero();
+        }
+        let rank = q * F::cast(n - 1);
+        let lower = rank.floor().to_usize().unwrap_or(0).min(n - 1);
+        let upper = (lower + 1).min(n - 1);
+        let fraction = rank - F::cast(lower);
+        self.sorted[lower] + (self.sorted[upper] - self.sorted[lower]) * fraction
+    }
+
+    /// Percentile rank (0 to 1) of `value` among the values, counting equal values as half
+    /// below and half above it. Returns 0.5 if the window is empty.
+    pub(crate) fn rank(&self, value: F) -> F {
+        let n = self.sorted.len();
+        if n == 0 {
+            return F::cast(0.5);
+        }
+        let below = self
+            .sorted
+            .partition_point(|x| compare(x, &value) == Ordering::Less);
+        let not_above = self
+            .sorted
+            .partition_point(|x| compare(x, &value) != Ordering::Greater);
+        (F::cast(below) + F::cast(not_above)) / F::cast(2 * n)
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.window.clear();
+        self.sorted.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push() {
+        let mut window = SortedWindow::new(3);
+        for &x in &[5.0, 1.0, 3.0, 2.0, 5.0] {
+            window.push(x);
+        }
+        assert_eq!(window.sorted, vec![2.0, 3.0, 5.0]);
+        assert!(window.is_full());
+
+        window.push(1.0);
+        assert_eq!(window.sorted, vec![1.0, 2.0, 5.0]);
+        window.clear();
+        assert!(window.sorted.is_empty());
+    }
+
+    #[test]
+    fn test_quantile() {
+        let mut window = SortedWindow::new(5);
+        assert_eq!(window.quantile(0.5), 0.0);
+        for &x in &[4.0, 1.0, 3.0, 2.0, 5.0] {
+            window.push(x);
+        }
+        assert_eq!(window.quantile(0.0), 1.0);
+        assert_eq!(window.quantile(0.5), 3.0);
+        assert_eq!(window.quantile(0.1), 1.4);
+        assert_eq!(window.quantile(1.0), 5.0);
+    }
+
+    #[test]
+    fn test_rank() {
+        let mut window = SortedWindow::new(4);
+        assert_eq!(window.rank(1.0), 0.5);
+        for &x in &[1.0, 2.0, 2.0, 4.0] {
+            window.push(x);
+        }
+        assert_eq!(window.rank(0.5), 0.0);
+        assert_eq!(window.rank(2.0), 0.5);
+        assert_eq!(window.rank(4.0), 0.875);
+        assert_eq!(window.rank(5.0), 1.0);
+    }
+}
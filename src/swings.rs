@@ -0,0 +1,60 @@
+use crate::{Float, RingBuffer};
+
+/// Window detecting swing highs and lows, bars higher or lower than the _left_ bars before
+/// them and the _right_ bars after them.
+///
+/// A swing is confirmed _right_ bars after it happened, once the window is full. Its bar
+/// has to be strictly higher (lower) than the bars to its left, but only at least as high
+/// (low) as the bars to its right, so of a flat top or bottom only the first bar is a swing.
+/// NaN values are never swings and never prevent one.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct SwingWindow<T> {
+    left: usize,
+    window: RingBuffer<T>,
+}
+
+impl<T: Copy> SwingWindow<T> {
+    pub(crate) fn new(left: u32, right: u32) -> Self {
+        Self {
+            left: left as usize,
+            window: RingBuffer::new(left as usize + right as usize + 1),
+        }
+    }
+
+    /// Pushes a bar, returning the swing high and the swing low confirmed by it, compared
+    /// by the `high` and `low` of the bars.
+    pub(crate) fn push<F: Float>(
+        &mut self,
+        item: T,
+        high: impl Fn(&T) -> F,
+        low: impl Fn(&T) -> F,
+    ) -> (Option<T>, Option<T>) {
+        self.window.push(item);
+        if !self.window.is_full() {
+            return (None, None);
+        }
+
+        let center = *self.window.get(self.left).unwrap();
+        let (center_high, center_low) = (high(&center), low(&center));
+        let (mut is_high, mut is_low) = (true, true);
+        for (i, other) in self.window.iter().enumerate() {
+            let (other_high, other_low) = (high(other), low(other));
+            if i < self.left {
+                is_high &= other_high.is_nan() || other_high < center_high;
+                is_low &= other_low.is_nan() || other_low > center_low;
+            } else if i > self.left {
+                is_high &= other_high.is_nan() || other_high <= center_high;
+                is_low &= other_low.is_nan() || other_low >= center_low;
+            }
+        }
+        is_high &= !center_high.is_nan();
+        is_low &= !center_low.is_nan();
+
+        (is_high.then_some(center), is_low.then_some(center))
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.window.clear();
+    }
+}
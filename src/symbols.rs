@@ -0,0 +1,354 @@
+//! Indicators of many instruments.
+//!
+//! A [SymbolManager](struct.SymbolManager.html) keeps an indicator per symbol, created on the
+//! first bar of the symbol, and routes every `(symbol, bar)` update to the instance of its
+//! symbol. It replaces the map of indicators otherwise needed to run a screener over many
+//! tickers.
+//!
+//! New indicators are clones of a configured template, or with
+//! [with_factory](struct.SymbolManager.html#method.with_factory) created by a function, e.g.
+//! from a [Registry](../registry/struct.Registry.html) or a pipeline configuration.
+//!
+//! # Example
+//!
+//! ```
+//! use ta::indicators::SimpleMovingAverage;
+//! use ta::symbols::SymbolManager;
+//! use ta::Warmup;
+//!
+//! let mut sma = SymbolManager::<String, _>::new(SimpleMovingAverage::new(2).unwrap());
+//!
+//! // symbol and (open, high, low, close, volume)
+//! let updates = [
+//!     ("AAPL", (10.0, 11.0, 9.0, 10.0, 100.0)),
+//!     ("MSFT", (50.0, 51.0, 49.0, 50.0, 300.0)),
+//!     ("AAPL", (10.0, 12.0, 10.0, 12.0, 200.0)),
+//! ];
+//! for (symbol, bar) in &updates {
+//!     sma.update(*symbol, bar);
+//! }
+//! assert_eq!(sma.len(), 2);
+//! assert!(sma.get("AAPL").unwrap().is_ready());
+//! assert!(!sma.get("MSFT").unwrap().is_ready());
+//! // plain values work too
+//! assert_eq!(sma.calc("MSFT", 54.0), 52.0);
+//! ```
+
+use alloc::borrow::ToOwned;
+use alloc::collections::btree_map::{self, BTreeMap};
+use core::borrow::Borrow;
+use core::fmt;
+
+use crate::{Calculate, Next, Reset};
+
+/// Creates the indicator of a new symbol in a [SymbolManager](struct.SymbolManager.html).
+///
+/// Implemented for [Template](struct.Template.html) and for functions returning the indicator.
+pub trait Factory<I> {
+    fn create(&self) -> I;
+}
+
+/// Indicator cloned for every new symbol.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct Template<I>(pub I);
+
+impl<I: Clone> Factory<I> for Template<I> {
+    fn create(&self) -> I {
+        self.0.clone()
+    }
+}
+
+impl<I, F: Fn() -> I> Factory<I> for F {
+    fn create(&self) -> I {
+        self()
+    }
+}
+
+/// Indicators keyed by symbol, see the [module](index.html) documentation.
+///
+/// Symbols are kept in order, so [iter](#method.iter) visits them sorted.
+///
+/// # Example
+///
+/// A factory does not need the indicators to implement `Clone`, and every symbol starts from
+/// a freshly constructed indicator.
+///
+/// ```
+/// use ta::registry::Registry;
+/// use ta::symbols::SymbolManager;
+/// use ta::{DataItem, Indicator, Reset};
+///
+/// let registry = Registry::new();
+/// // the spec is known to be valid
+/// let mut rsi =
+///     SymbolManager::<String, _, _>::with_factory(move || registry.parse("RSI(3)").unwrap());
+///
+/// let bar = DataItem::builder()
+///     .open(10.0)
+///     .high(11.0)
+///     .low(9.0)
+///     .close(10.5)
+///     .volume(100.0)
+///     .build()
+///     .unwrap();
+/// for symbol in &["BTCUSDT", "ETHUSDT", "BTCUSDT"] {
+///     rsi.update(*symbol, &bar);
+/// }
+/// assert_eq!(rsi.symbols().collect::<Vec<_>>(), vec!["BTCUSDT", "ETHUSDT"]);
+///
+/// // e.g. after a reconnect
+/// rsi.reset();
+/// assert!(rsi.iter().all(|(_, indicator)| !indicator.is_ready()));
+/// ```
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SymbolManager<K: Ord, I, F = Template<I>> {
+    factory: F,
+    instances: BTreeMap<K, I>,
+}
+
+impl<K: Ord, I: Clone> SymbolManager<K, I> {
+    /// Creates a manager cloning `template` for every new symbol.
+    pub fn new(template: I) -> Self {
+        Self::with_factory(Template(template))
+    }
+
+    /// Indicator new symbols start from.
+    pub fn template(&self) -> &I {
+        &self.factory.0
+    }
+}
+
+impl<K: Ord, I, F: Factory<I>> SymbolManager<K, I, F> {
+    /// Creates a manager calling `factory` for every new symbol.
+    pub fn with_factory(factory: F) -> Self {
+        Self {
+            factory,
+            instances: BTreeMap::new(),
+        }
+    }
+
+    /// Feeds `input` to the indicator of `symbol`, creating it on the first input.
+    pub fn update<Q, T>(&mut self, symbol: &Q, input: &T) -> I::Output
+    where
+        K: Borrow<Q>,
+        Q: Ord + ToOwned<Owned = K> + ?Sized,
+        I: Next<T>,
+    {
+        self.get_or_create(symbol).next(input)
+    }
+
+    /// Feeds a single value to the indicator of `symbol`, creating it on the first input.
+    pub fn calc<Q, V>(&mut self, symbol: &Q, input: V) -> I::Output
+    where
+        K: Borrow<Q>,
+        Q: Ord + ToOwned<Owned = K> + ?Sized,
+        I: Calculate<V>,
+    {
+        self.get_or_create(symbol).calc(input)
+    }
+
+    /// Indicator of `symbol`, created by the factory if there is none yet.
+    pub fn get_or_create<Q>(&mut self, symbol: &Q) -> &mut I
+    where
+        K: Borrow<Q>,
+        Q: Ord + ToOwned<Owned = K> + ?Sized,
+    {
+        // looked up by reference first, so known symbols are not copied
+        if !self.instances.contains_key(symbol) {
+            self.instances
+                .insert(symbol.to_owned(), self.factory.create());
+        }
+        self.instances.get_mut(symbol).unwrap()
+    }
+}
+
+impl<K: Ord, I, F> SymbolManager<K, I, F> {
+    pub fn get<Q>(&self, symbol: &Q) -> Option<&I>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.instances.get(symbol)
+    }
+
+    pub fn get_mut<Q>(&mut self, symbol: &Q) -> Option<&mut I>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.instances.get_mut(symbol)
+    }
+
+    pub fn contains<Q>(&self, symbol: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.instances.contains_key(symbol)
+    }
+
+    /// Removes the indicator of `symbol`, e.g. of a delisted instrument.
+    pub fn remove<Q>(&mut self, symbol: &Q) -> Option<I>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.instances.remove(symbol)
+    }
+
+    /// Keeps only the symbols for which `f` returns `true`.
+    pub fn retain<R: FnMut(&K, &mut I) -> bool>(&mut self, f: R) {
+        self.instances.retain(f);
+    }
+
+    /// Number of symbols with an indicator.
+    pub fn len(&self) -> usize {
+        self.instances.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.instances.is_empty()
+    }
+
+    /// Symbols with an indicator, sorted.
+    pub fn symbols(&self) -> btree_map::Keys<'_, K, I> {
+        self.instances.keys()
+    }
+
+    /// Symbols and their indicators, sorted by symbol.
+    pub fn iter(&self) -> btree_map::Iter<'_, K, I> {
+        self.instances.iter()
+    }
+
+    /// Symbols and their mutable indicators, sorted by symbol.
+    pub fn iter_mut(&mut self) -> btree_map::IterMut<'_, K, I> {
+        self.instances.iter_mut()
+    }
+
+    /// Removes the indicators of all symbols.
+    pub fn clear(&mut self) {
+        self.instances.clear();
+    }
+}
+
+impl<K: Ord, I: Reset, F> Reset for SymbolManager<K, I, F> {
+    /// Resets the indicators of all symbols, keeping the symbols.
+    fn reset(&mut self) {
+        for indicator in self.instances.values_mut() {
+            indicator.reset();
+        }
+    }
+}
+
+impl<'a, K: Ord, I, F> IntoIterator for &'a SymbolManager<K, I, F> {
+    type Item = (&'a K, &'a I);
+    type IntoIter = btree_map::Iter<'a, K, I>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.instances.iter()
+    }
+}
+
+impl<K: Ord + fmt::Debug, I: fmt::Debug, F> fmt::Debug for SymbolManager<K, I, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SymbolManager")
+            .field("instances", &self.instances)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{ExponentialMovingAverage, SimpleMovingAverage};
+    use crate::test_helper::*;
+    use crate::Warmup;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_update() {
+        let mut ema = SymbolManager::<String, _>::new(ExponentialMovingAverage::new(3).unwrap());
+        assert!(ema.is_empty());
+        assert_eq!(ema.update("A", &Bar::new().close(2)), 2.0);
+        assert_eq!(ema.update("B", &Bar::new().close(8)), 8.0);
+        assert_eq!(ema.update("A", &Bar::new().close(4)), 3.0);
+        assert_eq!(ema.calc("B", 6.0), 7.0);
+
+        let symbols: Vec<&str> = ema.iter().map(|(symbol, _)| symbol.as_str()).collect();
+        assert_eq!(symbols, vec!["A", "B"]);
+        // the template is untouched
+        assert!(!ema.template().is_ready());
+    }
+
+    #[test]
+    fn test_get_and_remove() {
+        let mut sma = SymbolManager::new(SimpleMovingAverage::new(2).unwrap());
+        sma.calc(&7_u32, 1.0);
+        assert!(sma.get(&7).is_some());
+        assert!(sma.get(&8).is_none());
+        sma.get_mut(&7).unwrap().reset();
+        assert_eq!(sma.calc(&7, 3.0), 3.0);
+
+        assert!(sma.remove(&7).is_some());
+        assert_eq!(sma.len(), 0);
+        sma.get_or_create(&9);
+        assert_eq!(sma.len(), 1);
+        sma.clear();
+        assert!(sma.is_empty());
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut sma = SymbolManager::<String, _>::new(SimpleMovingAverage::new(2).unwrap());
+        sma.calc("A", 1.0);
+        sma.calc("B", 1.0);
+        sma.reset();
+        assert_eq!(sma.len(), 2);
+        assert_eq!(sma.calc("A", 5.0), 5.0);
+        assert_eq!(sma.update("B", &Bar::new().close(7)), 7.0);
+    }
+
+    #[test]
+    fn test_factory() {
+        let mut sma =
+            SymbolManager::<String, _, _>::with_factory(|| SimpleMovingAverage::new(2).unwrap());
+        assert_eq!(sma.calc("A", 2.0), 2.0);
+        assert_eq!(sma.update("B", &Bar::new().close(6)), 6.0);
+        assert_eq!(sma.calc("A", 4.0), 3.0);
+        assert!(sma.contains("A"));
+        assert_eq!(sma.get("B").unwrap().length(), 2);
+
+        for (_, indicator) in sma.iter_mut() {
+            indicator.calc(10.0);
+        }
+        let ready: Vec<&str> = (&sma)
+            .into_iter()
+            .filter(|(_, indicator)| indicator.is_ready())
+            .map(|(symbol, _)| symbol.as_str())
+            .collect();
+        assert_eq!(ready, vec!["A", "B"]);
+
+        sma.retain(|symbol, _| symbol != "A");
+        assert_eq!(sma.symbols().collect::<Vec<_>>(), vec!["B"]);
+        assert!(sma.get_mut("A").is_none());
+        assert!(sma.remove("B").is_some());
+        sma.get_or_create("C");
+        sma.clear();
+        assert!(sma.is_empty());
+    }
+
+    #[test]
+    fn test_factory_reset() {
+        let mut ema =
+            SymbolManager::<u32, _, _>::with_factory(|| ExponentialMovingAverage::new(3).unwrap());
+        ema.calc(&1, 2.0);
+        ema.calc(&2, 4.0);
+        ema.reset();
+        assert_eq!(ema.len(), 2);
+        assert_eq!(ema.calc(&1, 8.0), 8.0);
+        assert!(format!("{:?}", ema).starts_with("SymbolManager"));
+    }
+}
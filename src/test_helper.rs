@@ -1,4 +1,4 @@
-use super::{Close, High, Low, Open, Volume};
+use super::{Close, High, Low, Open, Quote, Timestamp, Volume};
 
 #[derive(Debug, PartialEq)]
 pub struct Bar {
@@ -7,6 +7,7 @@ pub struct Bar {
     low: f64,
     close: f64,
     volume: f64,
+    timestamp: i64,
 }
 
 impl Bar {
@@ -17,13 +18,14 @@ impl Bar {
             low: 0.0,
             high: 0.0,
             volume: 0.0,
+            timestamp: 0,
         }
     }
 
-    //pub fn open<T: Into<f64>>(mut self, val :T ) -> Self {
-    //    self.open = val.into();
-    //    self
-    //}
+    pub fn open<T: Into<f64>>(mut self, val: T) -> Self {
+        self.open = val.into();
+        self
+    }
 
     pub fn high<T: Into<f64>>(mut self, val: T) -> Self {
         self.high = val.into();
@@ -40,8 +42,13 @@ impl Bar {
         self
     }
 
-    pub fn volume(mut self, val: f64) -> Self {
-        self.volume = val;
+    pub fn volume<T: Into<f64>>(mut self, val: T) -> Self {
+        self.volume = val.into();
+        self
+    }
+
+    pub fn timestamp(mut self, val: i64) -> Self {
+        self.timestamp = val;
         self
     }
 }
@@ -76,6 +83,47 @@ impl Volume for Bar {
     }
 }
 
+impl Timestamp for Bar {
+    fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct TestQuote {
+    bid: f64,
+    ask: f64,
+    bid_size: f64,
+    ask_size: f64,
+}
+
+pub fn quote(bid: f64, ask: f64, bid_size: f64, ask_size: f64) -> TestQuote {
+    TestQuote {
+        bid,
+        ask,
+        bid_size,
+        ask_size,
+    }
+}
+
+impl Quote for TestQuote {
+    fn bid(&self) -> f64 {
+        self.bid
+    }
+
+    fn ask(&self) -> f64 {
+        self.ask
+    }
+
+    fn bid_size(&self) -> f64 {
+        self.bid_size
+    }
+
+    fn ask_size(&self) -> f64 {
+        self.ask_size
+    }
+}
+
 pub fn round(num: f64) -> f64 {
     (num * 1000.0).round() / 1000.00
 }
@@ -100,7 +148,35 @@ macro_rules! test_indicator {
             assert_eq!(indicator.calc(12.3), first_output);
 
             // ensure Display is implemented
-            format!("{}", indicator);
+            let _ = format!("{}", indicator);
+
+            // ensure the batch path matches the streaming one
+            let input = [12.3, 14.5, 9.1];
+            let mut batch = vec![first_output.clone(); input.len()];
+            indicator.reset();
+            indicator.calc_slice(&input, &mut batch);
+            indicator.reset();
+            for (value, out) in input.iter().zip(batch.iter()) {
+                assert_eq!(&indicator.calc(*value), out);
+            }
+
+            // ensure Warmup is implemented and follows the number of inputs
+            indicator.reset();
+            assert_eq!(indicator.is_ready(), indicator.warmup_period() == 0);
+            for _ in 1..indicator.warmup_period() {
+                indicator.calc(12.3);
+                assert!(!indicator.is_ready());
+            }
+            indicator.calc(12.3);
+            assert!(indicator.is_ready());
+
+            // ensure the state survives serialization
+            #[cfg(feature = "serde")]
+            {
+                let json = serde_json::to_string(&indicator).unwrap();
+                let mut restored: $i = serde_json::from_str(&json).unwrap();
+                assert_eq!(restored.calc(14.5), indicator.calc(14.5));
+            }
         }
     };
 }
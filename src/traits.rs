@@ -1,6 +1,69 @@
 // Indicator traits
 //
 
+use alloc::boxed::Box;
+use core::fmt;
+use core::iter;
+use core::ops;
+
+use crate::errors::*;
+use crate::DataItem;
+
+/// Numeric type indicators are computed with.
+///
+/// Implemented for `f32`, `f64` and, with the `decimal` feature, for `rust_decimal::Decimal`.
+/// Indicators which only need the basic arithmetic (e.g. `SimpleMovingAverage` or
+/// `AverageTrueRange`) accept any `Number`, the rest require [Float](trait.Float.html).
+pub trait Number:
+    num_traits::Num
+    + num_traits::NumAssignOps
+    + num_traits::FromPrimitive
+    + num_traits::ToPrimitive
+    + ops::Neg<Output = Self>
+    + PartialOrd
+    + Copy
+    + iter::Sum
+    + for<'a> iter::Sum<&'a Self>
+    + fmt::Debug
+    + fmt::Display
+    + Default
+    + Send
+    + Sync
+    + 'static
+{
+    /// Converts a primitive number, e.g. an `f64` constant or a `u32` length, into `Self`.
+    fn cast<T: num_traits::ToPrimitive>(value: T) -> Self {
+        value.to_f64().and_then(Self::from_f64).unwrap()
+    }
+}
+
+impl<T> Number for T where
+    T: num_traits::Num
+        + num_traits::NumAssignOps
+        + num_traits::FromPrimitive
+        + num_traits::ToPrimitive
+        + ops::Neg<Output = T>
+        + PartialOrd
+        + Copy
+        + iter::Sum
+        + for<'a> iter::Sum<&'a T>
+        + fmt::Debug
+        + fmt::Display
+        + Default
+        + Send
+        + Sync
+        + 'static
+{
+}
+
+/// Floating point type indicators are computed with, implemented for `f32` and `f64`.
+///
+/// Indicators are generic over it and default to `f64`, e.g. `SimpleMovingAverage` is
+/// `SimpleMovingAverage<f64>` while `SimpleMovingAverage<f32>` works on single precision.
+pub trait Float: Number + num_traits::Float {}
+
+impl<T: Number + num_traits::Float> Float for T {}
+
 /// Resets an indicator to the initial state.
 pub trait Reset {
     fn reset(&mut self);
@@ -12,38 +75,531 @@ pub trait Reset {
 /// traits necessary to calculate value of a particular indicator.
 ///
 /// In most cases `Output` is `f64`, but sometimes it can be different. For example for
-/// [MACD](indicators/struct.MovingAverageConvergenceDivergence.html) it is `(f64, f64, f64)` since
-/// MACD returns 3 values.
+/// [ExponentialBollinger](indicators/struct.ExponentialBollinger.html) it is
+/// [ExponentialBollingerOutput](indicators/struct.ExponentialBollingerOutput.html) since the
+/// bands consist of 3 lines.
 ///
 pub trait Next<T> {
-    fn next(&mut self, input: &T) -> f64;
+    type Output;
+    fn next(&mut self, input: &T) -> Self::Output;
+
+    /// Consumes all items of `input` in order, writing the output for `input[i]` into
+    /// `output[i]`. Gives the same results as calling [next](#tymethod.next) on each item.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `input` and `output` have different lengths.
+    fn next_slice(&mut self, input: &[T], output: &mut [Self::Output]) {
+        assert_eq!(
+            input.len(),
+            output.len(),
+            "input and output slices have different lengths"
+        );
+        for (item, out) in input.iter().zip(output.iter_mut()) {
+            *out = self.next(item);
+        }
+    }
+}
+
+/// Consumes a single value of the float type `F` and returns `Output`.
+pub trait Calculate<F = f64> {
+    type Output;
+    fn calc(&mut self, input: F) -> Self::Output;
+
+    /// Consumes all values of `input` in order, writing the output for `input[i]` into
+    /// `output[i]`. Gives the same results as calling [calc](#tymethod.calc) on each value.
+    ///
+    /// Meant for large historical series, where the output buffer can be allocated once
+    /// and reused.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `input` and `output` have different lengths.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ta::indicators::SimpleMovingAverage;
+    /// use ta::Calculate;
+    ///
+    /// let mut sma = SimpleMovingAverage::new(2).unwrap();
+    /// let mut output = [0.0; 4];
+    /// sma.calc_slice(&[1.0, 3.0, 5.0, 9.0], &mut output);
+    /// assert_eq!(output, [1.0, 2.0, 4.0, 7.0]);
+    /// ```
+    fn calc_slice(&mut self, input: &[F], output: &mut [Self::Output])
+    where
+        F: Copy,
+    {
+        assert_eq!(
+            input.len(),
+            output.len(),
+            "input and output slices have different lengths"
+        );
+        for (&value, out) in input.iter().zip(output.iter_mut()) {
+            *out = self.calc(value);
+        }
+    }
+}
+
+/// Reports whether an indicator has consumed enough input to produce meaningful values.
+///
+/// During the warm-up period indicators still return values (e.g. a
+/// [FastStochastic](indicators/struct.FastStochastic.html) returns 50.0 on the first bar),
+/// but those are computed from incomplete windows.
+pub trait Warmup {
+    /// Number of inputs needed before the output is meaningful.
+    fn warmup_period(&self) -> u32;
+
+    /// Returns `true` once the warm-up period is over.
+    fn is_ready(&self) -> bool;
+}
+
+/// Value which can be checked for NaN and infinity.
+///
+/// Implemented for floats and for the outputs of indicators, which are finite if all their
+/// values are.
+pub trait Finite {
+    fn is_finite(&self) -> bool;
+}
+
+impl Finite for f64 {
+    fn is_finite(&self) -> bool {
+        f64::is_finite(*self)
+    }
+}
+
+impl Finite for f32 {
+    fn is_finite(&self) -> bool {
+        f32::is_finite(*self)
+    }
+}
+
+#[cfg(feature = "decimal")]
+impl Finite for rust_decimal::Decimal {
+    fn is_finite(&self) -> bool {
+        true
+    }
+}
+
+fn checked<O: Finite>(output: O, ready: bool) -> Result<O> {
+    if !ready {
+        Err(Error::from_kind(ErrorKind::NotReady))
+    } else if !output.is_finite() {
+        Err(Error::from_kind(ErrorKind::NonFiniteOutput))
+    } else {
+        Ok(output)
+    }
+}
+
+/// [Next](trait.Next.html) which fails instead of returning values which are not meaningful.
+///
+/// Implemented for every indicator implementing both `Next<T>` and `Warmup`. `try_next`
+/// consumes the input like `next` and fails with `NotReady` until the indicator
+/// [is ready](trait.Warmup.html), and with `NonFiniteOutput` if the output is NaN or
+/// infinite, e.g. after an overflow.
+///
+/// # Example
+///
+/// ```
+/// use ta::errors::ErrorKind;
+/// use ta::indicators::SimpleMovingAverage;
+/// use ta::TryCalculate;
+///
+/// let mut sma = SimpleMovingAverage::new(2).unwrap();
+/// assert!(matches!(sma.try_calc(1.0).unwrap_err().kind(), ErrorKind::NotReady));
+/// assert_eq!(sma.try_calc(3.0).unwrap(), 2.0);
+/// assert!(matches!(sma.try_calc(f64::NAN).unwrap_err().kind(), ErrorKind::NonFiniteInput));
+/// // the sum of the window overflows
+/// sma.try_calc(f64::MAX).unwrap();
+/// assert!(matches!(sma.try_calc(f64::MAX).unwrap_err().kind(), ErrorKind::NonFiniteOutput));
+/// ```
+pub trait TryNext<T>: Next<T> + Warmup {
+    fn try_next(&mut self, input: &T) -> Result<Self::Output>
+    where
+        Self::Output: Finite,
+    {
+        let output = self.next(input);
+        checked(output, self.is_ready())
+    }
+}
+
+impl<T, I: Next<T> + Warmup> TryNext<T> for I {}
+
+/// [Calculate](trait.Calculate.html) which fails instead of returning values which are not
+/// meaningful, like [TryNext](trait.TryNext.html).
+///
+/// A NaN or infinite input fails with `NonFiniteInput` and is not consumed, so it does not
+/// spoil the window of the indicator.
+pub trait TryCalculate<F = f64>: Calculate<F> + Warmup {
+    fn try_calc(&mut self, input: F) -> Result<Self::Output>
+    where
+        F: Finite,
+        Self::Output: Finite,
+    {
+        if !input.is_finite() {
+            return Err(Error::from_kind(ErrorKind::NonFiniteInput));
+        }
+        let output = self.calc(input);
+        checked(output, self.is_ready())
+    }
+}
+
+impl<F, I: Calculate<F> + Warmup> TryCalculate<F> for I {}
+
+/// [Reset](trait.Reset.html) which warms the indicator up again from a short history.
+///
+/// `reset_with` resets the indicator and feeds it the `seed` values, oldest first, so after
+/// restoring a process from only the last N values it produces the same outputs as an
+/// indicator which saw the whole series, as long as N covers the warm-up period.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::SimpleMovingAverage;
+/// use ta::{Calculate, ResetWith, Warmup};
+///
+/// let mut sma = SimpleMovingAverage::new(3).unwrap();
+/// sma.calc(100.0);
+/// sma.reset_with(&[1.0, 2.0, 3.0]);
+/// assert!(sma.is_ready());
+/// assert_eq!(sma.calc(4.0), 3.0);
+/// ```
+pub trait ResetWith<F = f64>: Calculate<F> + Reset {
+    fn reset_with(&mut self, seed: &[F])
+    where
+        F: Copy,
+    {
+        self.reset();
+        for &value in seed {
+            self.calc(value);
+        }
+    }
+}
+
+impl<F, I: Calculate<F> + Reset> ResetWith<F> for I {}
+
+/// [Reset](trait.Reset.html) which warms the indicator up again from the last data items,
+/// like [ResetWith](trait.ResetWith.html) does from values.
+pub trait ResetWithItems<T>: Next<T> + Reset {
+    fn reset_with_items(&mut self, seed: &[T]) {
+        self.reset();
+        for item in seed {
+            self.next(item);
+        }
+    }
+}
+
+impl<T, I: Next<T> + Reset> ResetWithItems<T> for I {}
+
+/// Result of [WarmUp::warm_up](trait.WarmUp.html#method.warm_up).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WarmUpReport {
+    /// Number of bars fed to the indicator.
+    pub bars: usize,
+    /// Number of bars after which the indicator became ready, `None` if it did not or was
+    /// ready before the history.
+    pub ready_after: Option<usize>,
+    /// Whether the indicator is ready after the history.
+    pub ready: bool,
+}
+
+/// [Next](trait.Next.html) which feeds a history of bars at once, e.g. when a live process
+/// starts from the candles of an exchange.
+///
+/// Unlike [ResetWithItems](trait.ResetWithItems.html) it does not reset the indicator, and it
+/// reports whether the history covered the warm-up period.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::ExponentialMovingAverage;
+/// use ta::WarmUp;
+///
+/// // open, high, low, close and volume
+/// let history = [(1.0, 2.0, 1.0, 2.0, 10.0); 5];
+///
+/// let mut ema = ExponentialMovingAverage::new(3).unwrap();
+/// let report = ema.warm_up(&history);
+/// assert_eq!(report.bars, 5);
+/// assert_eq!(report.ready_after, Some(3));
+/// assert!(report.ready);
+///
+/// let mut ema = ExponentialMovingAverage::new(10).unwrap();
+/// assert!(!ema.warm_up(&history).ready);
+/// ```
+pub trait WarmUp<T>: Next<T> + Warmup {
+    fn warm_up(&mut self, history: &[T]) -> WarmUpReport {
+        let mut ready_after = None;
+        let mut ready = self.is_ready();
+        for (i, item) in history.iter().enumerate() {
+            self.next(item);
+            if !ready && self.is_ready() {
+                ready = true;
+                ready_after = Some(i + 1);
+            }
+        }
+        WarmUpReport {
+            bars: history.len(),
+            ready_after,
+            ready: self.is_ready(),
+        }
+    }
+}
+
+impl<T, I: Next<T> + Warmup> WarmUp<T> for I {}
+
+/// [Calculate](trait.Calculate.html) which can revise the latest input.
+///
+/// Live feeds update the current, still forming candle many times before it closes.
+/// `update_last` recomputes the output as if the latest input passed to `calc` had been
+/// `input`, without advancing the window. Before any input it is the same as `calc`.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::Minimum;
+/// use ta::{Calculate, UpdateLast};
+///
+/// let mut min = Minimum::new(2).unwrap();
+/// assert_eq!(min.calc(5.0), 5.0);
+/// assert_eq!(min.calc(4.0), 4.0);
+/// assert_eq!(min.update_last(6.0), 5.0);
+/// assert_eq!(min.calc(7.0), 6.0);
+/// ```
+pub trait UpdateLast<F = f64>: Calculate<F> {
+    fn update_last(&mut self, input: F) -> Self::Output;
+}
+
+/// [Next](trait.Next.html) which can revise the latest data item, like
+/// [UpdateLast](trait.UpdateLast.html) does for values.
+pub trait Replace<T>: Next<T> {
+    fn replace(&mut self, input: &T) -> Self::Output;
 }
 
-pub trait Calculate {
-    fn calc(&mut self, input: f64) -> f64;
+/// [Calculate](trait.Calculate.html) which can evaluate a hypothetical input.
+///
+/// `peek` returns what `calc` would return for `input`, without changing the indicator and
+/// without cloning it. Useful for what-if questions, e.g. whether a stochastic would cross 80
+/// if the price ticked to some value.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::FastStochastic;
+/// use ta::{Calculate, Peek};
+///
+/// let mut stoch = FastStochastic::new(3).unwrap();
+/// stoch.calc(10.0);
+/// stoch.calc(20.0);
+/// assert_eq!(stoch.peek(18.0), 80.0);
+/// assert_eq!(stoch.peek(12.0), 20.0);
+/// assert_eq!(stoch.calc(18.0), 80.0);
+/// ```
+pub trait Peek<F = f64>: Calculate<F> {
+    fn peek(&self, input: F) -> Self::Output;
+}
+
+/// [Next](trait.Next.html) which can evaluate a hypothetical data item, like
+/// [Peek](trait.Peek.html) does for values.
+pub trait PeekNext<T>: Next<T> {
+    fn peek_next(&self, input: &T) -> Self::Output;
+}
+
+/// Object-safe indicator, for choosing indicators at runtime.
+///
+/// Combines [Next](trait.Next.html), [Reset](trait.Reset.html), [Warmup](trait.Warmup.html)
+/// and `Display` for a fixed input type `T`, so that different indicators can be stored as
+/// `Box<dyn Indicator<Output = f64>>`. It is implemented for every indicator implementing
+/// those traits and `Clone`; the [registry](registry/index.html) creates them by name.
+///
+/// A boxed indicator implements `Next<T>`, `Reset`, `Warmup` and `Clone` itself.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{FastStochastic, SimpleMovingAverage};
+/// use ta::{DataItem, Indicator};
+///
+/// let mut indicators: Vec<Box<dyn Indicator<Output = f64>>> = vec![
+///     Box::new(SimpleMovingAverage::new(2).unwrap()),
+///     Box::new(FastStochastic::new(2).unwrap()),
+/// ];
+///
+/// let bar = DataItem::builder()
+///     .open(2.0)
+///     .high(3.0)
+///     .low(1.0)
+///     .close(2.0)
+///     .volume(100.0)
+///     .build()
+///     .unwrap();
+/// let outputs: Vec<f64> = indicators.iter_mut().map(|i| i.update(&bar)).collect();
+/// assert_eq!(outputs, vec![2.0, 50.0]);
+/// assert_eq!(indicators[1].to_string(), "FAST_STOCH(2)");
+/// ```
+pub trait Indicator<T = DataItem>: Reset + Warmup + fmt::Display {
+    type Output;
+
+    /// Consumes a data item, same as [Next::next](trait.Next.html#tymethod.next).
+    fn update(&mut self, input: &T) -> Self::Output;
+
+    /// Clones the indicator with its state into a box, which makes boxed indicators
+    /// [Clone](https://doc.rust-lang.org/std/clone/trait.Clone.html).
+    fn clone_boxed(&self) -> Box<dyn Indicator<T, Output = Self::Output>>;
+}
+
+impl<T, I> Indicator<T> for I
+where
+    T: 'static,
+    I: Next<T> + Reset + Warmup + fmt::Display + Clone + 'static,
+{
+    type Output = I::Output;
+
+    fn update(&mut self, input: &T) -> Self::Output {
+        self.next(input)
+    }
+
+    fn clone_boxed(&self) -> Box<dyn Indicator<T, Output = Self::Output>> {
+        Box::new(self.clone())
+    }
+}
+
+impl<T, O> Clone for Box<dyn Indicator<T, Output = O>> {
+    fn clone(&self) -> Self {
+        (**self).clone_boxed()
+    }
+}
+
+impl<T, O> Next<T> for Box<dyn Indicator<T, Output = O>> {
+    type Output = O;
+
+    fn next(&mut self, input: &T) -> O {
+        (**self).update(input)
+    }
+}
+
+impl<T, O> Reset for Box<dyn Indicator<T, Output = O>> {
+    fn reset(&mut self) {
+        (**self).reset()
+    }
+}
+
+impl<T, O> Warmup for Box<dyn Indicator<T, Output = O>> {
+    fn warmup_period(&self) -> u32 {
+        (**self).warmup_period()
+    }
+
+    fn is_ready(&self) -> bool {
+        (**self).is_ready()
+    }
 }
 
 /// Open price of a particular period.
-pub trait Open {
-    fn open(&self) -> f64;
+pub trait Open<F = f64> {
+    fn open(&self) -> F;
 }
 
 /// Close price of a particular period.
-pub trait Close {
-    fn close(&self) -> f64;
+pub trait Close<F = f64> {
+    fn close(&self) -> F;
 }
 
 /// Lowest price of a particular period.
-pub trait Low {
-    fn low(&self) -> f64;
+pub trait Low<F = f64> {
+    fn low(&self) -> F;
 }
 
 /// Highest price of a particular period.
-pub trait High {
-    fn high(&self) -> f64;
+pub trait High<F = f64> {
+    fn high(&self) -> F;
 }
 
 /// Trading volume of a particular trading period.
-pub trait Volume {
-    fn volume(&self) -> f64;
+pub trait Volume<F = f64> {
+    fn volume(&self) -> F;
+}
+
+/// Indicator with a state fading over time, e.g. an exponential moving average.
+///
+/// Used by [OnGap](struct.OnGap.html) to account for periods without any input, like an
+/// exchange outage, instead of treating the next input as an ordinary continuation.
+pub trait Decay {
+    /// Decays the state over `periods` missed periods, applied with the next input.
+    fn decay(&mut self, periods: u32);
+}
+
+/// Time of a particular trading period, in milliseconds since the Unix epoch (UTC).
+///
+/// Bars are expected to be stamped with the start of their period, like in the
+/// [sessions](sessions/index.html) module.
+pub trait Timestamp {
+    fn timestamp(&self) -> i64;
+}
+
+/// Complete bar with open, high, low and close prices and volume.
+///
+/// Implemented for every type implementing all of [Open](trait.Open.html),
+/// [High](trait.High.html), [Low](trait.Low.html), [Close](trait.Close.html) and
+/// [Volume](trait.Volume.html), including plain `(open, high, low, close, volume)` tuples and
+/// `[open, high, low, close, volume]` arrays:
+///
+/// ```
+/// use ta::indicators::SimpleMovingAverage;
+/// use ta::Next;
+///
+/// let mut sma = SimpleMovingAverage::new(2).unwrap();
+/// sma.next(&(10.0, 12.0, 9.0, 11.0, 100.0));
+/// assert_eq!(sma.next(&[11.0, 14.0, 11.0, 13.0, 80.0]), 12.0);
+/// ```
+pub trait Ohlcv<F = f64>: Open<F> + High<F> + Low<F> + Close<F> + Volume<F> {}
+
+impl<F, T: Open<F> + High<F> + Low<F> + Close<F> + Volume<F>> Ohlcv<F> for T {}
+
+/// Best bid and ask of an order book at a particular moment.
+pub trait Quote<F = f64> {
+    fn bid(&self) -> F;
+    fn ask(&self) -> F;
+    fn bid_size(&self) -> F;
+    fn ask_size(&self) -> F;
+}
+
+/// Single trade of a particular market, e.g. from an exchange trade stream.
+///
+/// Trades can be aggregated into bars with
+/// [BarBuilder](preprocess/struct.BarBuilder.html). Implemented for
+/// `(timestamp, price, size)` tuples.
+pub trait Trade<F = f64>: Timestamp {
+    fn price(&self) -> F;
+    fn size(&self) -> F;
+}
+
+/// Side of the aggressor of a trade, the party taking liquidity from the order book.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AggressorSide {
+    /// A buyer lifted an ask.
+    Buy,
+    /// A seller hit a bid.
+    Sell,
+}
+
+/// Trade whose aggressor is known, e.g. from the taker side flag of an exchange trade
+/// stream. Implemented for `(timestamp, price, size, side)` tuples.
+pub trait Aggressor {
+    fn side(&self) -> AggressorSide;
+}
+
+/// Volume of a trade or a bar split by the side of the aggressor, the input of
+/// [order flow](indicators/struct.CumulativeVolumeDelta.html) indicators.
+///
+/// Implemented for `(timestamp, price, size, side)` tuples, whose size is all on the side of
+/// their aggressor, and for the klines of the [adapters](adapters/index.html). Bars which
+/// only know their total volume, like [DataItem](struct.DataItem.html), can't tell the sides
+/// apart: per-bar order flow needs a bar type implementing this trait.
+pub trait AggressorVolume<F = f64> {
+    /// Volume bought by aggressive buyers.
+    fn buy_volume(&self) -> F;
+    /// Volume sold by aggressive sellers.
+    fn sell_volume(&self) -> F;
 }
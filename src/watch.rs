@@ -0,0 +1,369 @@
+//! Monitoring of indicators.
+//!
+//! A [Watcher](struct.Watcher.html) owns a set of indicators and [alert rules](../alerts/struct.Rule.html)
+//! on their outputs. Every bar fed to it updates all indicators and evaluates all rules,
+//! and the fired rules are returned as [WatchEvent](struct.WatchEvent.html)s with the index
+//! of the bar and, for bars with a [Timestamp](../trait.Timestamp.html), its time.
+//!
+//! # Example
+//!
+//! ```
+//! use ta::alerts::{Rule, Trigger};
+//! use ta::flips::Flip;
+//! use ta::indicators::{
+//!     MovingAverageConvergenceDivergence, MovingAverageConvergenceDivergenceOutput,
+//!     RelativeStrengthIndex,
+//! };
+//! use ta::watch::Watcher;
+//! use ta::sessions::MINUTE;
+//! use ta::{Close, IndicatorExt, Next, Timestamp};
+//!
+//! struct Bar(i64, f64);
+//!
+//! impl Timestamp for Bar {
+//!     fn timestamp(&self) -> i64 {
+//!         self.0
+//!     }
+//! }
+//!
+//! impl Close for Bar {
+//!     fn close(&self) -> f64 {
+//!         self.1
+//!     }
+//! }
+//!
+//! let mut watcher = Watcher::<Bar>::timed();
+//! let rsi = watcher.watch("RSI", RelativeStrengthIndex::new(3).unwrap());
+//! let histogram = watcher.watch(
+//!     "MACD histogram",
+//!     MovingAverageConvergenceDivergence::new(3, 6, 3)
+//!         .unwrap()
+//!         .map(|macd: MovingAverageConvergenceDivergenceOutput| macd.histogram),
+//! );
+//! watcher.try_when(Rule::new("overbought", rsi, Trigger::CrossesAbove(70.0))).unwrap();
+//! watcher.try_when(Rule::new("momentum turns", histogram, Trigger::ChangesSign)).unwrap();
+//!
+//! let mut events = Vec::new();
+//! for (i, &price) in [10.0, 9.0, 8.0, 9.0, 11.0, 12.0].iter().enumerate() {
+//!     events.extend(watcher.next(&Bar(i as i64 * MINUTE, price)));
+//! }
+//! assert_eq!(events.len(), 2);
+//! assert_eq!((events[0].bar, events[0].time), (3, Some(3 * MINUTE)));
+//! assert_eq!(watcher.rule(events[0].rule).name(), "momentum turns");
+//! assert_eq!(events[0].direction, Flip::Bullish);
+//! assert_eq!(watcher.name(events[1].series), "RSI");
+//! ```
+
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::alerts::{Alerts, Rule, Trigger};
+use crate::errors::*;
+use crate::flips::Flip;
+use crate::{Float, Indicator, Next, Reset, Timestamp, Warmup};
+
+/// Fired rule of a [Watcher](struct.Watcher.html).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WatchEvent<F = f64> {
+    /// Index of the rule, as returned by [Watcher::when](struct.Watcher.html#method.when).
+    pub rule: usize,
+    /// Index of the watched indicator, as returned by
+    /// [Watcher::watch](struct.Watcher.html#method.watch).
+    pub series: usize,
+    /// Index of the bar, counted from 0 since the last reset.
+    pub bar: u64,
+    /// Time of the bar, if the watcher is [timed](struct.Watcher.html#method.timed).
+    pub time: Option<i64>,
+    /// Output of the watched indicator on this bar.
+    pub value: F,
+    /// Bullish for crossings above or over, new highs, entering a band from below and
+    /// changing to positive, bearish otherwise.
+    pub direction: Flip,
+}
+
+impl<F: Float> fmt::Display for WatchEvent<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let direction = match self.direction {
+            Flip::Bullish => "bullish",
+            Flip::Bearish => "bearish",
+        };
+        write!(f, "rule {} {} at bar {}", self.rule, direction, self.bar)?;
+        if let Some(time) = self.time {
+            write!(f, " ({})", time)?;
+        }
+        write!(f, ": {}", self.value)
+    }
+}
+
+/// Indicators and alert rules on their outputs, evaluated together on every bar.
+///
+/// Rules refer to indicators by the index returned by [watch](#method.watch), crossings of
+/// two indicators refer to the other one the same way.
+#[derive(Clone)]
+pub struct Watcher<T = crate::DataItem, F = f64> {
+    names: Vec<String>,
+    indicators: Vec<Box<dyn Indicator<T, Output = F>>>,
+    alerts: Alerts<F>,
+    values: Vec<F>,
+    time: Option<fn(&T) -> i64>,
+    bar: u64,
+}
+
+impl<T: 'static, F: Float> Watcher<T, F> {
+    /// Creates a watcher whose events carry no time.
+    pub fn new() -> Self {
+        Self {
+            names: Vec::new(),
+            indicators: Vec::new(),
+            alerts: Alerts::with_type(),
+            values: Vec::new(),
+            time: None,
+            bar: 0,
+        }
+    }
+
+    /// Adds an indicator and returns its index, which rules refer to.
+    pub fn watch<S, I>(&mut self, name: S, indicator: I) -> usize
+    where
+        S: Into<String>,
+        I: Indicator<T, Output = F> + 'static,
+    {
+        self.names.push(name.into());
+        self.indicators.push(Box::new(indicator));
+        self.values.push(F::nan());
+        self.indicators.len() - 1
+    }
+
+    /// Adds a rule and returns its index, which identifies its events. Fails if the rule
+    /// refers to an indicator which is not watched or is invalid, see
+    /// [Alerts::try_add](../alerts/struct.Alerts.html#method.try_add).
+    pub fn try_when(&mut self, rule: Rule<F>) -> Result<usize> {
+        let other = match rule.trigger() {
+            Trigger::CrossesOver(other) | Trigger::CrossesUnder(other) => Some(other),
+            _ => None,
+        };
+        let watched = core::iter::once(rule.series())
+            .chain(other)
+            .all(|series| series < self.indicators.len());
+        if !watched {
+            return Err(Error::from_kind(ErrorKind::InvalidParameter));
+        }
+        self.alerts.try_add(rule)
+    }
+
+    /// Name of the indicator with index `series`.
+    pub fn name(&self, series: usize) -> &str {
+        &self.names[series]
+    }
+
+    /// Latest outputs of the indicators, `NaN` before the first bar.
+    pub fn values(&self) -> &[F] {
+        &self.values
+    }
+
+    pub fn rule(&self, index: usize) -> &Rule<F> {
+        self.alerts.rule(index)
+    }
+
+    pub fn rules(&self) -> &[Rule<F>] {
+        self.alerts.rules()
+    }
+}
+
+impl<T: Timestamp + 'static, F: Float> Watcher<T, F> {
+    /// Creates a watcher whose events carry the timestamps of the bars.
+    pub fn timed() -> Self {
+        Self {
+            time: Some(T::timestamp),
+            ..Self::new()
+        }
+    }
+}
+
+impl<T: 'static, F: Float> Next<T> for Watcher<T, F> {
+    type Output = Vec<WatchEvent<F>>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        for (indicator, value) in self.indicators.iter_mut().zip(self.values.iter_mut()) {
+            *value = indicator.update(input);
+        }
+        let bar = self.bar;
+        self.bar += 1;
+        let time = self.time.map(|time| time(input));
+        let alerts = self.alerts.check(&self.values);
+        alerts
+            .into_iter()
+            .map(|alert| WatchEvent {
+                rule: alert.rule,
+                series: self.alerts.rule(alert.rule).series(),
+                bar,
+                time,
+                value: alert.value,
+                direction: alert.direction,
+            })
+            .collect()
+    }
+}
+
+impl<T: 'static, F: Float> Reset for Watcher<T, F> {
+    fn reset(&mut self) {
+        for indicator in self.indicators.iter_mut() {
+            indicator.reset();
+        }
+        for value in self.values.iter_mut() {
+            *value = F::nan();
+        }
+        self.alerts.reset();
+        self.bar = 0;
+    }
+}
+
+impl<T: 'static, F: Float> Warmup for Watcher<T, F> {
+    fn warmup_period(&self) -> u32 {
+        self.indicators
+            .iter()
+            .map(|indicator| indicator.warmup_period())
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn is_ready(&self) -> bool {
+        self.indicators.iter().all(|indicator| indicator.is_ready())
+    }
+}
+
+impl<T: 'static, F: Float> Default for Watcher<T, F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: 'static, F: Float> fmt::Debug for Watcher<T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Watcher")
+            .field("names", &self.names)
+            .field("values", &self.values)
+            .field("alerts", &self.alerts)
+            .field("bar", &self.bar)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{ExponentialMovingAverage, SimpleMovingAverage};
+    use crate::test_helper::*;
+
+    fn bars(closes: &[f64]) -> Vec<Bar> {
+        closes
+            .iter()
+            .map(|&close| Bar::new().close(close))
+            .collect()
+    }
+
+    #[test]
+    fn test_try_when() {
+        let mut watcher = Watcher::<Bar>::new();
+        let sma = watcher.watch("SMA", SimpleMovingAverage::new(2).unwrap());
+        assert!(watcher
+            .try_when(Rule::new("a", 1, Trigger::CrossesAbove(1.0)))
+            .is_err());
+        assert!(watcher
+            .try_when(Rule::new("b", sma, Trigger::CrossesOver(1)))
+            .is_err());
+        assert!(watcher
+            .try_when(Rule::new("c", sma, Trigger::NewHigh(0)))
+            .is_err());
+        assert_eq!(
+            watcher
+                .try_when(Rule::new("d", sma, Trigger::NewHigh(2)))
+                .unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_next() {
+        let mut watcher = Watcher::new();
+        let fast = watcher.watch("fast", ExponentialMovingAverage::new(2).unwrap());
+        let slow = watcher.watch("slow", SimpleMovingAverage::new(4).unwrap());
+        let close = watcher.watch("close", SimpleMovingAverage::new(1).unwrap());
+        let cross = watcher
+            .try_when(Rule::new("cross", fast, Trigger::CrossesOver(slow)))
+            .unwrap();
+        let band = watcher
+            .try_when(Rule::new("band", close, Trigger::EntersBand(9.0, 11.0)))
+            .unwrap();
+        let high = watcher
+            .try_when(Rule::new("high", close, Trigger::NewHigh(3)))
+            .unwrap();
+
+        let events: Vec<_> = bars(&[12.0, 11.5, 8.0, 7.0, 10.0, 13.0])
+            .iter()
+            .flat_map(|bar| watcher.next(bar))
+            .map(|event| (event.bar, event.rule, event.series, event.direction))
+            .collect();
+        assert_eq!(
+            events,
+            vec![
+                (4, cross, fast, Flip::Bullish),
+                (4, band, close, Flip::Bullish),
+                (5, high, close, Flip::Bullish),
+            ]
+        );
+        assert_eq!(watcher.values()[close], 13.0);
+        assert_eq!(watcher.name(slow), "slow");
+        assert_eq!(watcher.rules().len(), 3);
+    }
+
+    #[test]
+    fn test_time() {
+        let bar = |timestamp, close| Bar::new().timestamp(timestamp).close(close);
+        let mut watcher = Watcher::timed();
+        let price = watcher.watch("price", SimpleMovingAverage::new(1).unwrap());
+        watcher
+            .try_when(Rule::new("above", price, Trigger::CrossesAbove(1.0)))
+            .unwrap();
+        assert!(watcher.next(&bar(1000, 0.5)).is_empty());
+        let event = watcher.next(&bar(2000, 1.5))[0];
+        assert_eq!((event.bar, event.time), (1, Some(2000)));
+        assert_eq!(event.to_string(), "rule 0 bullish at bar 1 (2000): 1.5");
+
+        let mut watcher = Watcher::new();
+        watcher.watch("price", SimpleMovingAverage::new(1).unwrap());
+        watcher
+            .try_when(Rule::new("above", 0, Trigger::CrossesAbove(1.0)))
+            .unwrap();
+        watcher.next(&bar(1000, 0.5));
+        let event = watcher.next(&bar(2000, 1.5))[0];
+        assert_eq!(event.time, None);
+        assert_eq!(event.to_string(), "rule 0 bullish at bar 1: 1.5");
+    }
+
+    #[test]
+    fn test_warmup_and_reset() {
+        let mut watcher = Watcher::new();
+        watcher.watch("sma", SimpleMovingAverage::new(3).unwrap());
+        watcher.watch("ema", ExponentialMovingAverage::new(2).unwrap());
+        watcher
+            .try_when(Rule::new("above", 0, Trigger::CrossesAbove(5.0)))
+            .unwrap();
+        assert_eq!(watcher.warmup_period(), 3);
+
+        for bar in bars(&[4.0, 4.0, 4.0]) {
+            watcher.next(&bar);
+        }
+        assert!(watcher.is_ready());
+        assert_eq!(watcher.next(&Bar::new().close(10.0))[0].bar, 3);
+
+        watcher.reset();
+        assert!(!watcher.is_ready());
+        assert!(watcher.values()[0].is_nan());
+        // the side the value starts on is forgotten
+        assert!(watcher.next(&Bar::new().close(10.0)).is_empty());
+    }
+}
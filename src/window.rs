@@ -0,0 +1,336 @@
+use core::iter::Chain;
+use core::ops::Index;
+use core::slice;
+
+use alloc::vec::Vec;
+
+use crate::{Number, RingBuffer, Storage};
+
+/// Fixed-capacity window of the latest inputs of an indicator, with an optional running
+/// sum.
+///
+/// Pushing is O(1): the values are kept in a [RingBuffer](struct.RingBuffer.html) and,
+/// for windows created with [with_sum](#method.with_sum), the sum is updated with the
+/// pushed and the evicted value instead of being recomputed.
+///
+/// Values are indexed from the oldest one (0) to the newest one (`len() - 1`). Like the
+/// buffer, the window is stored in a `Vec` or, with [inline](#method.inline), in an array.
+///
+/// # Example
+///
+/// ```
+/// use ta::Window;
+///
+/// let mut window = Window::with_sum(3);
+/// window.push(1.0);
+/// window.push(2.0);
+/// window.push(3.0);
+/// assert_eq!(window.push(4.0), Some(1.0));
+/// assert_eq!(window.sum(), Some(9.0));
+/// assert_eq!(window.mean(), Some(3.0));
+/// assert_eq!(window.iter().copied().collect::<Vec<_>>(), vec![2.0, 3.0, 4.0]);
+/// ```
+///
+/// With the `serde` feature, deserializing a window whose sum differs from the sum of its
+/// values by more than rounding errors fails with `InvalidParameter`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        try_from = "WindowFields<T, S>",
+        bound(
+            serialize = "T: serde::Serialize, S: Storage<T>",
+            deserialize = "T: Number + serde::Deserialize<'de>, S: Storage<T>"
+        )
+    )
+)]
+pub struct Window<T, S = Vec<T>> {
+    values: RingBuffer<T, S>,
+    sum: Option<T>,
+}
+
+// Fields of a deserialized window, whose sum is checked against the values.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(bound(deserialize = "T: serde::Deserialize<'de>, S: Storage<T>"))]
+struct WindowFields<T, S> {
+    values: RingBuffer<T, S>,
+    sum: Option<T>,
+}
+
+#[cfg(feature = "serde")]
+impl<T: Number, S: Storage<T>> core::convert::TryFrom<WindowFields<T, S>> for Window<T, S> {
+    type Error = crate::errors::Error;
+
+    fn try_from(fields: WindowFields<T, S>) -> crate::errors::Result<Self> {
+        use crate::errors::{Error, ErrorKind};
+        use core::cmp::Ordering;
+
+        if let Some(sum) = fields.sum {
+            let abs = |value: T| if value < T::zero() { -value } else { value };
+            let total: T = fields.values.iter().copied().sum();
+            let magnitude: T = fields.values.iter().map(|&value| abs(value)).sum();
+            // the running sum drifts from the total by rounding errors, which stay far
+            // below a millionth of the magnitude of the values
+            let tolerance = (magnitude + T::one()) * T::cast(1e-6);
+            let deviation = abs(sum - total).partial_cmp(&tolerance);
+            if !matches!(deviation, Some(Ordering::Less) | Some(Ordering::Equal)) {
+                return Err(Error::from_kind(ErrorKind::InvalidParameter));
+            }
+        }
+        Ok(Self {
+            values: fields.values,
+            sum: fields.sum,
+        })
+    }
+}
+
+impl<T> Window<T> {
+    /// Creates an empty window of `capacity` values, without a running sum.
+    ///
+    /// # Panics
+    ///
+    /// If `capacity` is 0.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            values: RingBuffer::new(capacity),
+            sum: None,
+        }
+    }
+}
+
+impl<T: Default, const N: usize> Window<T, [T; N]> {
+    /// Creates an empty window of _N_ values stored inline in an array, without a running
+    /// sum.
+    ///
+    /// # Panics
+    ///
+    /// If _N_ is 0.
+    pub fn inline() -> Self {
+        Self {
+            values: RingBuffer::inline(),
+            sum: None,
+        }
+    }
+}
+
+impl<T: Number, const N: usize> Window<T, [T; N]> {
+    /// Creates an empty window of _N_ values stored inline in an array, keeping their
+    /// running sum.
+    ///
+    /// # Panics
+    ///
+    /// If _N_ is 0.
+    pub fn inline_with_sum() -> Self {
+        Self {
+            values: RingBuffer::inline(),
+            sum: Some(T::zero()),
+        }
+    }
+}
+
+impl<T, S: Storage<T>> Window<T, S> {
+    pub fn capacity(&self) -> usize {
+        self.values.capacity()
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.values.is_full()
+    }
+
+    /// Value at `index`, counted from the oldest value.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.values.get(index)
+    }
+
+    /// Value at `index`, counted back from the newest value (0).
+    pub fn get_back(&self, index: usize) -> Option<&T> {
+        self.values.get_back(index)
+    }
+
+    /// Value evicted by the next push, if the window is full.
+    pub fn next_evicted(&self) -> Option<&T> {
+        self.values.next_evicted()
+    }
+
+    pub fn oldest(&self) -> Option<&T> {
+        self.values.oldest()
+    }
+
+    pub fn newest(&self) -> Option<&T> {
+        self.values.newest()
+    }
+
+    /// The values from the oldest to the newest one, as two slices.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        self.values.as_slices()
+    }
+
+    /// Iterator from the oldest to the newest value.
+    pub fn iter(&self) -> Chain<slice::Iter<'_, T>, slice::Iter<'_, T>> {
+        self.values.iter()
+    }
+}
+
+impl<T: Number> Window<T> {
+    /// Creates an empty window of `capacity` values, keeping their running sum.
+    ///
+    /// # Panics
+    ///
+    /// If `capacity` is 0.
+    pub fn with_sum(capacity: usize) -> Self {
+        Self {
+            values: RingBuffer::new(capacity),
+            sum: Some(T::zero()),
+        }
+    }
+
+    /// Changes the capacity, keeping the newest values which fit.
+    ///
+    /// # Panics
+    ///
+    /// If `capacity` is 0.
+    pub fn resize(&mut self, capacity: usize) {
+        self.values.resize(capacity);
+        if let Some(sum) = &mut self.sum {
+            *sum = self.values.iter().copied().sum();
+        }
+    }
+}
+
+impl<T: Number, S: Storage<T>> Window<T, S> {
+    /// Sum of the values, if the window keeps it.
+    pub fn sum(&self) -> Option<T> {
+        self.sum
+    }
+
+    /// Mean of the values, if the window keeps their sum and isn't empty.
+    pub fn mean(&self) -> Option<T> {
+        match self.sum {
+            Some(sum) if !self.is_empty() => Some(sum / T::cast(self.len())),
+            _ => None,
+        }
+    }
+
+    /// Appends a value, returning the oldest one if the window was full.
+    pub fn push(&mut self, value: T) -> Option<T> {
+        let old = self.values.push(value);
+        if let Some(sum) = &mut self.sum {
+            *sum += value;
+            if let Some(old) = old {
+                *sum -= old;
+            }
+        }
+        old
+    }
+
+    /// Replaces the newest value, returning it, or returns `None` if the window is empty.
+    pub fn replace_newest(&mut self, value: T) -> Option<T> {
+        let last = self.values.newest_mut()?;
+        let old = core::mem::replace(last, value);
+        if let Some(sum) = &mut self.sum {
+            *sum += value - old;
+        }
+        Some(old)
+    }
+
+    /// Removes all values, keeping the allocation.
+    pub fn clear(&mut self) {
+        self.values.clear();
+        if let Some(sum) = &mut self.sum {
+            *sum = T::zero();
+        }
+    }
+}
+
+impl<T, S: Storage<T>> Index<usize> for Window<T, S> {
+    type Output = T;
+
+    /// Value at `index`, counted from the oldest value.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds.
+    fn index(&self, index: usize) -> &T {
+        &self.values[index]
+    }
+}
+
+impl<'a, T, S: Storage<T>> IntoIterator for &'a Window<T, S> {
+    type Item = &'a T;
+    type IntoIter = Chain<slice::Iter<'a, T>, slice::Iter<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_sum() {
+        let mut window = Window::with_sum(3);
+        assert_eq!(window.sum(), Some(0.0));
+        assert_eq!(window.mean(), None);
+        for value in [1.0, 2.0, 3.0, 4.0, 5.0] {
+            window.push(value);
+        }
+        assert_eq!(window.sum(), Some(12.0));
+        assert_eq!(window.replace_newest(8.0), Some(5.0));
+        assert_eq!(window.sum(), Some(15.0));
+        assert_eq!(window.mean(), Some(5.0));
+
+        window.resize(2);
+        assert_eq!(window.iter().copied().collect::<Vec<_>>(), vec![4.0, 8.0]);
+        assert_eq!(window.sum(), Some(12.0));
+
+        window.clear();
+        assert_eq!(window.sum(), Some(0.0));
+        assert_eq!(window.replace_newest(2.0), None);
+        assert!(window.is_empty());
+    }
+
+    #[test]
+    fn test_without_sum() {
+        let mut window = Window::new(2);
+        window.push(1);
+        window.push(2);
+        assert_eq!(window.push(3), Some(1));
+        assert_eq!(window.sum(), None);
+        assert_eq!(window.mean(), None);
+        assert_eq!(window[0], 2);
+        assert_eq!(window.get_back(0), Some(&3));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let mut window = Window::with_sum(3);
+        for value in &[0.1, 0.2, 0.3, 0.4] {
+            window.push(*value);
+        }
+        let json = serde_json::to_string(&window).unwrap();
+        let restored: Window<f64> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.sum(), window.sum());
+
+        let values = r#"{"capacity":2,"head":0,"values":[1.0,2.0]}"#;
+        for sum in &["3.5", "null", "1e300"] {
+            let json = format!(r#"{{"values":{},"sum":{}}}"#, values, sum);
+            let restored = serde_json::from_str::<Window<f64>>(&json);
+            assert_eq!(restored.is_ok(), *sum == "null");
+        }
+    }
+}
@@ -0,0 +1,443 @@
+// Numerical stability of the indicators on extreme inputs.
+//
+// Every indicator of `Registry::new()`, the indicators with several outputs or other inputs,
+// and indicators decayed or reseeded after time gaps by `OnGap`, are fed generated series of
+// tiny and huge magnitudes, long constant runs, alternating spikes and gaps of thousands of
+// periods, checking that:
+//
+// * finite inputs never produce NaN,
+// * bounded outputs stay within their range,
+// * resetting an indicator makes it reproduce its outputs exactly.
+//
+// Short series run many cases, series of several thousand bars fewer of them.
+
+use proptest::prelude::*;
+use ta::indicators::*;
+use ta::regime::RegimeClassifier;
+use ta::registry::Registry;
+use ta::{
+    AggressorSide, AggressorVolume, Close, DataItem, GapAction, High, Indicator, IndicatorExt, Low,
+    Open, Timestamp, Volume,
+};
+
+const MINUTE: i64 = 60_000;
+
+// oscillators of the registry with a fixed output range
+const BOUNDED: &[(&str, f64, f64)] = &[
+    ("ER", 0.0, 1.0),
+    ("FAST_STOCH", 0.0, 100.0),
+    ("MFI", 0.0, 100.0),
+    ("RSI", 0.0, 100.0),
+    ("SLOW_STOCH", 0.0, 100.0),
+    ("WILLR", -100.0, 0.0),
+];
+
+#[derive(Debug, Clone)]
+struct Bar {
+    timestamp: i64,
+    item: DataItem,
+}
+
+impl Timestamp for Bar {
+    fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+}
+
+impl Open for Bar {
+    fn open(&self) -> f64 {
+        self.item.open()
+    }
+}
+
+impl High for Bar {
+    fn high(&self) -> f64 {
+        self.item.high()
+    }
+}
+
+impl Low for Bar {
+    fn low(&self) -> f64 {
+        self.item.low()
+    }
+}
+
+impl Close for Bar {
+    fn close(&self) -> f64 {
+        self.item.close()
+    }
+}
+
+impl Volume for Bar {
+    fn volume(&self) -> f64 {
+        self.item.volume()
+    }
+}
+
+// the volume of rising bars is bought, of falling bars sold
+impl AggressorVolume for Bar {
+    fn buy_volume(&self) -> f64 {
+        if self.close() >= self.open() {
+            self.volume()
+        } else {
+            0.0
+        }
+    }
+
+    fn sell_volume(&self) -> f64 {
+        self.volume() - self.buy_volume()
+    }
+}
+
+// Indicator under test, fed with bars and flattening its output into numbers.
+trait Probe {
+    fn name(&self) -> String;
+    fn update(&mut self, bar: &Bar) -> Vec<f64>;
+    fn reset(&mut self);
+    fn range(&self) -> Option<(f64, f64)>;
+}
+
+struct Outputs<T, O> {
+    indicator: Box<dyn Indicator<T, Output = O>>,
+    input: fn(&Bar) -> T,
+    values: fn(O) -> Vec<f64>,
+    range: Option<(f64, f64)>,
+}
+
+impl<T: 'static, O: 'static> Outputs<T, O> {
+    fn within(mut self, min: f64, max: f64) -> Self {
+        self.range = Some((min, max));
+        self
+    }
+
+    fn boxed(self) -> Box<dyn Probe> {
+        Box::new(self)
+    }
+}
+
+impl<T: 'static, O: 'static> Probe for Outputs<T, O> {
+    fn name(&self) -> String {
+        self.indicator.to_string()
+    }
+
+    fn update(&mut self, bar: &Bar) -> Vec<f64> {
+        (self.values)(self.indicator.update(&(self.input)(bar)))
+    }
+
+    fn reset(&mut self) {
+        self.indicator.reset();
+    }
+
+    fn range(&self) -> Option<(f64, f64)> {
+        self.range
+    }
+}
+
+fn bar(bar: &Bar) -> Bar {
+    bar.clone()
+}
+
+fn close_volume(bar: &Bar) -> (f64, f64) {
+    (bar.close(), bar.volume())
+}
+
+fn trade(bar: &Bar) -> (i64, f64, f64, AggressorSide) {
+    let side = if bar.close() >= bar.open() {
+        AggressorSide::Buy
+    } else {
+        AggressorSide::Sell
+    };
+    (bar.timestamp, bar.close(), bar.volume(), side)
+}
+
+fn probe<I, O>(indicator: I, values: fn(O) -> Vec<f64>) -> Outputs<Bar, O>
+where
+    I: Indicator<Bar, Output = O> + 'static,
+    O: 'static,
+{
+    Outputs {
+        indicator: Box::new(indicator),
+        input: bar,
+        values,
+        range: None,
+    }
+}
+
+fn single(value: f64) -> Vec<f64> {
+    vec![value]
+}
+
+fn swing(point: Option<SwingPoint>) -> Vec<f64> {
+    point.map(|point| point.price).into_iter().collect()
+}
+
+fn divergence(event: Option<DivergenceEvent>) -> Vec<f64> {
+    event
+        .map(|e| {
+            vec![
+                e.previous_price,
+                e.previous_oscillator,
+                e.price,
+                e.oscillator,
+            ]
+        })
+        .unwrap_or_default()
+}
+
+fn probes() -> Vec<Box<dyn Probe>> {
+    let registry = Registry::<Bar>::new();
+    let mut probes: Vec<Box<dyn Probe>> = registry
+        .names()
+        .map(|name| {
+            let probe = Outputs {
+                indicator: registry.create(name, &[]).unwrap(),
+                input: bar,
+                values: single,
+                range: None,
+            };
+            match BOUNDED.iter().find(|bounded| bounded.0 == name) {
+                Some(&(_, min, max)) => probe.within(min, max).boxed(),
+                None => probe.boxed(),
+            }
+        })
+        .collect();
+
+    // several outputs
+    probes.extend(vec![
+        probe(MovingAverageConvergenceDivergence::default(), |o| {
+            vec![o.macd, o.signal, o.histogram]
+        })
+        .boxed(),
+        probe(BollingerBands::default(), |o| {
+            vec![o.upper, o.middle, o.lower]
+        })
+        .boxed(),
+        probe(ExponentialBollinger::default(), |o| {
+            vec![o.upper, o.middle, o.lower]
+        })
+        .boxed(),
+        probe(QuantileChannel::default(), |o| {
+            vec![o.upper, o.middle, o.lower]
+        })
+        .boxed(),
+        probe(DirectionalMovementIndex::default(), |o| {
+            vec![o.plus, o.minus, o.adx]
+        })
+        .within(0.0, 100.0)
+        .boxed(),
+        probe(VortexIndicator::default(), |o| vec![o.plus, o.minus]).boxed(),
+        probe(StochasticMomentumIndex::default(), |o| {
+            vec![o.smi, o.signal]
+        })
+        .within(-100.0, 100.0)
+        .boxed(),
+        probe(TradersDynamicIndex::default(), |o| {
+            vec![o.upper, o.middle, o.lower, o.price, o.signal]
+        })
+        .boxed(),
+        probe(Alligator::default(), |o| vec![o.jaw, o.teeth, o.lips]).boxed(),
+        probe(CumulativeStats::new(), |o| {
+            vec![o.mean, o.variance, o.min, o.max]
+        })
+        .boxed(),
+        probe(RunLength::default(), |o| {
+            vec![o.mean_up, o.mean_down, o.run_entropy, o.direction_entropy]
+        })
+        .boxed(),
+        probe(RealizedVolatility::new(), single).boxed(),
+        probe(VolumeWeightedAveragePrice::default(), |o| vec![o.vwap]).boxed(),
+        probe(RegimeClassifier::default(), |o| vec![o.slope]).boxed(),
+        probe(RegimeClassifier::default(), |o| {
+            vec![o.strength, 100.0 * o.volatility_rank]
+        })
+        .within(0.0, 100.0)
+        .boxed(),
+        probe(
+            VolumeProfile::new(ProfileBins::TickSize(0.5), 50).unwrap(),
+            |o| vec![o.poc, o.value_area_high, o.value_area_low],
+        )
+        .boxed(),
+        probe(
+            VolumeProfile::cumulative(ProfileBins::Count(24)).unwrap(),
+            |o| vec![o.poc, o.value_area_high, o.value_area_low],
+        )
+        .boxed(),
+        probe(SupportResistance::default(), |o| {
+            o.levels.iter().map(|level| level.price).collect()
+        })
+        .boxed(),
+        probe(Fractals::default(), |o| {
+            let mut prices = swing(o.up);
+            prices.extend(swing(o.down));
+            prices
+        })
+        .boxed(),
+        probe(DeltaDivergence::default(), divergence).boxed(),
+    ]);
+
+    // other inputs
+    probes.extend(vec![
+        Outputs {
+            indicator: Box::new(Correlation::default()),
+            input: close_volume,
+            values: single,
+            range: None,
+        }
+        .within(-1.0, 1.0)
+        .boxed(),
+        Outputs {
+            indicator: Box::new(VolumeDelta::new()),
+            input: trade,
+            values: single,
+            range: None,
+        }
+        .boxed(),
+        Outputs {
+            indicator: Box::new(CumulativeVolumeDelta::new()),
+            input: trade,
+            values: single,
+            range: None,
+        }
+        .boxed(),
+    ]);
+
+    // decayed and reseeded after gaps of more than a minute
+    for &action in &[GapAction::Decay { interval: MINUTE }, GapAction::Reseed] {
+        probes.extend(vec![
+            probe(
+                ExponentialMovingAverage::default()
+                    .on_gap(MINUTE, action)
+                    .unwrap(),
+                single,
+            )
+            .boxed(),
+            probe(
+                SmoothedMovingAverage::default()
+                    .on_gap(MINUTE, action)
+                    .unwrap(),
+                single,
+            )
+            .boxed(),
+            probe(
+                RelativeStrengthIndex::default()
+                    .on_gap(MINUTE, action)
+                    .unwrap(),
+                single,
+            )
+            .within(0.0, 100.0)
+            .boxed(),
+        ]);
+    }
+    probes
+}
+
+// value of the given magnitude, e.g. 3.7e-9
+fn magnitude() -> impl Strategy<Value = f64> {
+    (1.0..10.0_f64, -12..12_i32).prop_map(|(mantissa, exponent)| mantissa * 10f64.powi(exponent))
+}
+
+fn closes(max_len: usize, max_run: usize) -> impl Strategy<Value = Vec<f64>> {
+    prop_oneof![
+        // each close of a random magnitude
+        prop::collection::vec(magnitude(), 1..max_len),
+        // long runs of constant closes
+        prop::collection::vec((magnitude(), 1..max_run), 1..6).prop_map(|runs| {
+            runs.into_iter()
+                .flat_map(|(close, count)| std::iter::repeat_n(close, count))
+                .collect()
+        }),
+        // a base level with alternating spikes
+        (magnitude(), magnitude(), 2..max_len).prop_map(|(base, spike, count)| {
+            (0..count)
+                .map(|i| if i % 2 == 0 { base } else { spike })
+                .collect()
+        }),
+    ]
+}
+
+// bars a minute apart, with occasional gaps of up to thousands of minutes
+fn bars(max_len: usize, max_run: usize) -> impl Strategy<Value = Vec<Bar>> {
+    (
+        closes(max_len, max_run),
+        prop::collection::vec(prop_oneof![Just(0.0), magnitude()], 80),
+        prop::collection::vec(prop_oneof![8 => Just(1_i64), 1 => 2..10_000_i64], 80),
+    )
+        .prop_map(|(closes, volumes, gaps)| {
+            let mut open = closes[0];
+            let mut timestamp = 0;
+            closes
+                .iter()
+                .zip(volumes.iter().cycle())
+                .zip(gaps.iter().cycle())
+                .map(|((&close, &volume), &gap)| {
+                    let item = DataItem::builder()
+                        .open(open)
+                        .high(open.max(close))
+                        .low(open.min(close))
+                        .close(close)
+                        .volume(volume)
+                        .build()
+                        .unwrap();
+                    open = close;
+                    timestamp += gap * MINUTE;
+                    Bar { timestamp, item }
+                })
+                .collect()
+        })
+}
+
+fn run(probe: &mut Box<dyn Probe>, bars: &[Bar]) -> Vec<Vec<f64>> {
+    bars.iter().map(|bar| probe.update(bar)).collect()
+}
+
+fn check(bars: &[Bar]) -> Result<(), TestCaseError> {
+    for mut probe in probes() {
+        let outputs = run(&mut probe, bars);
+        for (i, values) in outputs.iter().enumerate() {
+            for &value in values {
+                prop_assert!(!value.is_nan(), "{} is NaN at bar {}", probe.name(), i);
+                if let Some((min, max)) = probe.range() {
+                    prop_assert!(
+                        min <= value && value <= max,
+                        "{} is {} at bar {}",
+                        probe.name(),
+                        value,
+                        i
+                    );
+                }
+            }
+        }
+
+        probe.reset();
+        let replayed = run(&mut probe, bars);
+        let bits = |outputs: &[Vec<f64>]| {
+            outputs
+                .iter()
+                .map(|values| values.iter().map(|v| v.to_bits()).collect())
+                .collect::<Vec<Vec<_>>>()
+        };
+        prop_assert_eq!(
+            bits(&outputs),
+            bits(&replayed),
+            "{} after reset",
+            probe.name()
+        );
+    }
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn test_short_series(bars in bars(80, 40)) {
+        check(&bars)?;
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(8))]
+
+    #[test]
+    fn test_long_series(bars in bars(5000, 3000)) {
+        check(&bars)?;
+    }
+}